@@ -0,0 +1,45 @@
+//! AI Toolbox core
+//!
+//! The skill sync engine, the tool catalog and the MCP config file formats
+//! of AI Toolbox, without Tauri, a database or a UI, so other hosts (an
+//! editor extension, a server) can sync skills and MCP servers the same way
+//! the desktop app does.
+//!
+//! The stable API is what this root re-exports: the skill and sync types,
+//! the [`ToolAdapter`] trait and the built-in tool catalog, and the MCP
+//! server record with the functions that read and write it in a tool's
+//! config. The modules under [`skills`], [`tools`] and [`mcp`] are public
+//! for the app itself and may change between minor versions.
+//!
+//! The library keeps no process-wide state: every sync takes a
+//! [`SyncContext`] with the symlink style, the sync trash directory and the
+//! global copy ignore patterns the host loaded from its settings.
+//!
+//! Storage, MCP config path resolution and routing, WSL / SSH targets and
+//! the Tauri commands stay in the app, which re-exports these modules under
+//! their old paths.
+
+pub mod mcp;
+pub mod skills;
+pub mod tools;
+
+pub use mcp::config_file::{
+    import_servers_from_json, import_servers_from_toml, remove_server_from_json,
+    remove_server_from_toml, sync_server_to_json, sync_server_to_toml, ImportedServers,
+};
+pub use mcp::types::{McpFormatConfig, McpServer};
+
+pub use skills::sync_engine::{
+    sync_dir_dry_run, sync_dir_for_tool_with_link_style, sync_dir_for_tool_with_overwrite,
+    verify_copy, SyncContext,
+};
+pub use skills::types::{
+    Skill, SkillPathRules, SkillTarget, SyncDryRun, SyncFileChanges, SyncMode, SyncOutcome,
+    SyncTargetSpec,
+};
+#[cfg(feature = "wasm-plugins")]
+pub use tools::wasm_plugin::WasmToolPlugin;
+pub use tools::{
+    builtin_adapter, builtin_adapters, BoxedToolAdapter, BuiltinTool, ToolAdapter, ToolLoadRules,
+    BUILTIN_TOOLS,
+};
@@ -3,7 +3,7 @@
 //! Defines format conversion rules for different tools.
 //! Each tool may have its own configuration format for MCP servers.
 
-use crate::coding::tools::McpFormatConfig;
+use super::types::McpFormatConfig;
 
 /// OpenCode format configuration
 ///
@@ -12,6 +12,7 @@ use crate::coding::tools::McpFormatConfig;
 /// - `command` + `args` merged into `command: [...]`
 /// - `env` -> `environment`
 /// - Requires `enabled: true` field
+///
 /// Note: `http` must come before `sse` so that `map_type_from_tool("remote")`
 /// returns "http" (the preferred unified type) instead of "sse".
 pub const OPENCODE_FORMAT: McpFormatConfig = McpFormatConfig {
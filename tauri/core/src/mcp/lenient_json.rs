@@ -0,0 +1,252 @@
+//! Lenient reader for tool-owned JSON configs
+//!
+//! Tools accept more than strict JSON in their config files: a UTF-8 BOM,
+//! comments and trailing commas in plain `.json`, duplicate keys (the last
+//! one wins). `parse_lenient` reads all of that instead of failing the
+//! import, and reports each recovery as a warning so the user knows the file
+//! was not what its extension claims. Nesting deeper than `MAX_DEPTH` is
+//! rejected before it can exhaust the stack.
+
+use std::cell::RefCell;
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value};
+
+const BOM: char = '\u{feff}';
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug)]
+pub struct LenientJson {
+    pub value: Value,
+    /// Strict JSON once the BOM is removed (no comments, trailing commas or
+    /// other JSON5 syntax)
+    pub strict: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Parse `content` as JSON5, recording everything that strict JSON would
+/// reject or silently drop
+pub fn parse_lenient(content: &str) -> Result<LenientJson, String> {
+    let mut warnings = Vec::new();
+    let content = match content.strip_prefix(BOM) {
+        Some(rest) => {
+            warnings.push("Ignored the UTF-8 byte order mark".to_string());
+            rest
+        }
+        None => content,
+    };
+
+    let tracked = RefCell::new(Vec::new());
+    let mut deserializer = json5::Deserializer::from_str(content);
+    let value = Tracked {
+        path: String::new(),
+        depth: 0,
+        warnings: &tracked,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|e| e.to_string())?;
+    // Same trailing-input check as `json5::from_str`
+    json5::from_str::<IgnoredAny>(content).map_err(|e| e.to_string())?;
+
+    warnings.extend(tracked.into_inner());
+    Ok(LenientJson {
+        value,
+        strict: serde_json::from_str::<IgnoredAny>(content).is_ok(),
+        warnings,
+    })
+}
+
+fn child_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+/// Builds a `Value` like `serde_json` does, but notes duplicate keys and
+/// unrepresentable numbers and stops at `MAX_DEPTH`
+struct Tracked<'a> {
+    path: String,
+    depth: usize,
+    warnings: &'a RefCell<Vec<String>>,
+}
+
+impl Tracked<'_> {
+    fn child(&self, path: String) -> Self {
+        Tracked {
+            path,
+            depth: self.depth + 1,
+            warnings: self.warnings,
+        }
+    }
+
+    fn rounded(&self, value: f64) -> Value {
+        self.warnings.borrow_mut().push(format!(
+            "Rounded the number at {} to fit 64 bits",
+            self.location()
+        ));
+        Value::from(value)
+    }
+
+    fn location(&self) -> &str {
+        if self.path.is_empty() {
+            "the root"
+        } else {
+            &self.path
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Tracked<'_> {
+    type Value = Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        if self.depth > MAX_DEPTH {
+            return Err(de::Error::custom(format!(
+                "Nesting deeper than {MAX_DEPTH} levels at {}",
+                self.location()
+            )));
+        }
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Tracked<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_i128<E>(self, value: i128) -> Result<Value, E> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::from(value)),
+            Err(_) => Ok(self.rounded(value as f64)),
+        }
+    }
+
+    fn visit_u128<E>(self, value: u128) -> Result<Value, E> {
+        match u64::try_from(value) {
+            Ok(value) => Ok(Value::from(value)),
+            Err(_) => Ok(self.rounded(value as f64)),
+        }
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(match Number::from_f64(value) {
+            Some(number) => Value::Number(number),
+            None => {
+                self.warnings.borrow_mut().push(format!(
+                    "Replaced the non-finite number at {} with null",
+                    self.location()
+                ));
+                Value::Null
+            }
+        })
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        DeserializeSeed::deserialize(self, deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) =
+            seq.next_element_seed(self.child(format!("{}[{}]", self.path, items.len())))?
+        {
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Value, A::Error> {
+        let mut map = Map::new();
+        while let Some(key) = access.next_key::<String>()? {
+            let path = child_path(&self.path, &key);
+            let value = access.next_value_seed(self.child(path.clone()))?;
+            if map.insert(key, value).is_some() {
+                self.warnings
+                    .borrow_mut()
+                    .push(format!("Duplicate key {path}; the last value is used"));
+            }
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovers_what_tools_accept_and_reports_it() {
+        let parsed = parse_lenient(
+            "\u{feff}{\n  // local servers\n  \"mcpServers\": {\n    \"fs\": { \"command\": \"npx\" },\n    \"fs\": { \"command\": \"uvx\", },\n  },\n}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.value,
+            serde_json::json!({ "mcpServers": { "fs": { "command": "uvx" } } })
+        );
+        assert!(!parsed.strict);
+        assert_eq!(
+            parsed.warnings,
+            vec![
+                "Ignored the UTF-8 byte order mark".to_string(),
+                "Duplicate key mcpServers.fs; the last value is used".to_string(),
+            ]
+        );
+
+        let strict = parse_lenient("{\"a\": [1, 2.5, null]}").unwrap();
+        assert!(strict.strict);
+        assert!(strict.warnings.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_input_without_panicking() {
+        let deep = "[".repeat(MAX_DEPTH * 8) + &"]".repeat(MAX_DEPTH * 8);
+        for input in [
+            "",
+            "\u{feff}",
+            "{",
+            "{\"a\": }",
+            "{\"a\": 1} trailing",
+            "/* unterminated",
+            "[1, 2",
+            deep.as_str(),
+        ] {
+            assert!(parse_lenient(input).is_err(), "accepted {input:?}");
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! MCP server configs in the tools' own files
+//!
+//! The unified server record, the per-tool format rules and reading and
+//! writing the servers field of a tool's JSON/JSONC or TOML config. Where
+//! the config lives, routing through the gateway and the apply history stay
+//! in the app.
+
+pub mod command_normalize;
+pub mod config_file;
+pub mod format_configs;
+pub mod lenient_json;
+pub mod types;
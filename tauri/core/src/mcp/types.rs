@@ -0,0 +1,97 @@
+//! MCP server record and the per-tool config format rules
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// MCP server in AI Toolbox's unified format (the app stores it in SQLite)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct McpServer {
+    pub id: String,
+    pub name: String,
+    pub server_type: String,
+    pub server_config: Value,
+    pub enabled_tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_details: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_note: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<i64>,
+    #[serde(default)]
+    pub sort_index: i32,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// MCP format configuration for different tools
+/// Defines how to convert between ai-toolbox's unified format and tool-specific formats
+#[derive(Clone, Debug)]
+pub struct McpFormatConfig {
+    /// Type mappings (e.g., "stdio" -> "local", "sse" -> "remote")
+    pub type_mappings: &'static [(&'static str, &'static str)],
+    /// Whether to merge command and args into a single array
+    pub merge_command_args: bool,
+    /// Field name for environment variables ("env" or "environment")
+    pub env_field: &'static str,
+    /// Whether the format requires an "enabled" field
+    pub requires_enabled: bool,
+    /// Default tool type when type field is missing (e.g., "local" for OpenCode)
+    pub default_tool_type: &'static str,
+    /// Whether the format supports a "timeout" field
+    pub supports_timeout: bool,
+    /// Field mappings for remote server URLs (e.g. "http" -> "httpUrl")
+    pub remote_url_field_mappings: &'static [(&'static str, &'static str)],
+    /// Whether missing type should be inferred from tool-specific remote URL fields
+    pub infer_remote_type_from_url_fields_when_type_missing: bool,
+}
+
+impl McpFormatConfig {
+    /// Map a server type from unified format to tool format
+    pub fn map_type_to_tool(&self, server_type: &str) -> String {
+        for (from, to) in self.type_mappings {
+            if *from == server_type {
+                return to.to_string();
+            }
+        }
+        server_type.to_string()
+    }
+
+    /// Map a server type from tool format to unified format
+    pub fn map_type_from_tool(&self, tool_type: &str) -> String {
+        for (from, to) in self.type_mappings {
+            if *to == tool_type {
+                return from.to_string();
+            }
+        }
+        tool_type.to_string()
+    }
+
+    /// Resolve the tool-side URL field for a unified remote server type.
+    pub fn remote_url_field_for_type(&self, server_type: &str) -> &'static str {
+        for (from, field) in self.remote_url_field_mappings {
+            if *from == server_type {
+                return field;
+            }
+        }
+        "url"
+    }
+
+    /// Infer unified remote server type from tool-specific URL field names.
+    pub fn infer_remote_type_from_url_fields(
+        &self,
+        server_config: &serde_json::Value,
+    ) -> Option<String> {
+        for (server_type, field) in self.remote_url_field_mappings {
+            if server_config.get(*field).is_some() {
+                return Some((*server_type).to_string());
+            }
+        }
+        None
+    }
+}
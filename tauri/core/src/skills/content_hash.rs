@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use walkdir::{DirEntry, WalkDir};
+
+use super::path_rules;
+use super::skill_ignore::SkillIgnore;
+use super::types::SkillPathRules;
+
+const IGNORE_NAMES: [&str; 5] = [
+    ".git",
+    ".DS_Store",
+    "Thumbs.db",
+    ".gitignore",
+    super::target_marker::MARKER_FILE,
+];
+
+fn is_ignored(entry: &DirEntry) -> bool {
+    let file_name = entry.file_name().to_string_lossy();
+    IGNORE_NAMES.iter().any(|name| name == &file_name.as_ref())
+}
+
+/// Hash directory contents for change detection
+pub fn hash_dir(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        if is_ignored(&entry) {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(path)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?;
+        hasher.update(relative.to_string_lossy().as_bytes());
+
+        if entry.file_type().is_file() {
+            let bytes = std::fs::read(entry.path())
+                .with_context(|| format!("read file {:?}", entry.path()))?;
+            hasher.update(bytes);
+        }
+    }
+
+    let digest = hasher.finalize();
+    Ok(hex::encode(digest))
+}
+
+/// Hash of the files a copy target receives: those kept by `rules` (see
+/// `path_rules`) and not left out by `ignore` (see `skill_ignore`). Only
+/// those files count, so a filtered copy hashes like its filtered source.
+pub fn hash_dir_with_rules(
+    path: &Path,
+    rules: &SkillPathRules,
+    ignore: &SkillIgnore,
+) -> Result<String> {
+    let mut hasher = Sha256::new();
+    for relative in list_files(path)? {
+        if !path_rules::keeps(rules, &relative) || ignore.is_ignored(&relative, false) {
+            continue;
+        }
+        let bytes = std::fs::read(path.join(&relative))
+            .with_context(|| format!("read file {:?}", path.join(&relative)))?;
+        hasher.update(relative.as_bytes());
+        hasher.update(bytes);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Cheap change signature of the entries `hash_dir` covers: latest mtime,
+/// entry count and total file size, read from metadata only
+pub fn dir_signature(path: &Path) -> Result<String> {
+    let mut latest_ms = 0u128;
+    let mut entries = 0u64;
+    let mut bytes = 0u64;
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        let metadata = entry
+            .metadata()
+            .with_context(|| format!("stat {:?}", entry.path()))?;
+        if let Ok(modified) = metadata.modified() {
+            let ms = modified
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_millis())
+                .unwrap_or_default();
+            latest_ms = latest_ms.max(ms);
+        }
+        entries += 1;
+        if metadata.is_file() {
+            bytes += metadata.len();
+        }
+    }
+    Ok(format!("{latest_ms}:{entries}:{bytes}"))
+}
+
+/// Relative paths (with `/` separators) of the files that `hash_dir` covers
+pub fn list_files(path: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(path)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?;
+        files.push(relative.to_string_lossy().replace('\\', "/"));
+    }
+    Ok(files)
+}
+
+/// SHA-256 of every file `list_files` returns, keyed by relative path
+pub fn file_hashes(path: &Path) -> Result<BTreeMap<String, String>> {
+    let mut hashes = BTreeMap::new();
+    for relative in list_files(path)? {
+        let bytes = std::fs::read(path.join(&relative))
+            .with_context(|| format!("read file {:?}", path.join(&relative)))?;
+        hashes.insert(relative, hex::encode(Sha256::digest(&bytes)));
+    }
+    Ok(hashes)
+}
+
+/// Whether any component of a relative path is skipped by `hash_dir`
+pub fn is_ignored_path(relative: &str) -> bool {
+    relative.split('/').any(|part| IGNORE_NAMES.contains(&part))
+}
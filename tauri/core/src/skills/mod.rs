@@ -0,0 +1,19 @@
+//! Skill sync engine
+//!
+//! Syncs a skill directory into a tool's skills dir as a link or a copy,
+//! with the ignore patterns, path rules, content hashes and the journal of
+//! replaced targets it relies on.
+
+pub mod content_hash;
+pub mod name_collision;
+pub mod path_rules;
+pub mod skill_ignore;
+pub mod sync_engine;
+pub mod sync_journal;
+pub mod target_conflict;
+pub mod target_marker;
+pub mod types;
+pub mod unicode_names;
+pub mod write_protect;
+
+pub use types::*;
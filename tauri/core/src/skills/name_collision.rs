@@ -0,0 +1,84 @@
+//! Case-insensitive skill name collisions
+//!
+//! macOS and Windows file systems are case-insensitive by default, so
+//! `MySkill` and `myskill` resolve to the same central or tool directory and a
+//! copy sync silently overwrites the other skill. Names that differ only by
+//! case are refused on every platform so a library stays portable between
+//! machines; the error carries a free name to rename to.
+
+use std::path::Path;
+
+use super::unicode_names::nfc_name;
+
+/// Error prefix: `NAME_CASE_COLLISION|name|existing|suggestion`
+pub const NAME_CASE_COLLISION: &str = "NAME_CASE_COLLISION";
+
+/// First of `existing` that equals `name` ignoring case but is spelled
+/// differently; exact matches (after NFC) are the same skill, not a collision
+pub fn find_case_collision<'a, I>(name: &str, existing: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let name = nfc_name(name);
+    let folded = name.to_lowercase();
+    existing.into_iter().find(|other| {
+        let other = nfc_name(other);
+        other != name && other.to_lowercase() == folded
+    })
+}
+
+/// `name-2`, `name-3`, ... — the first one no `taken` name folds to
+pub fn suggest_rename<'a, I>(name: &str, taken: I) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let taken = taken.into_iter().map(str::to_lowercase).collect::<Vec<_>>();
+    (2..)
+        .map(|n| format!("{name}-{n}"))
+        .find(|candidate| !taken.contains(&candidate.to_lowercase()))
+        .unwrap_or_default()
+}
+
+/// Entry names directly inside `dir`, as spelled on disk
+pub fn dir_entry_names(dir: &Path) -> Vec<String> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Refuse `name` when one of `existing` differs from it only by case
+pub fn ensure_no_case_collision(name: &str, existing: &[String]) -> Result<(), String> {
+    match find_case_collision(name, existing.iter().map(String::as_str)) {
+        Some(other) => Err(format!(
+            "{NAME_CASE_COLLISION}|{name}|{other}|{}",
+            suggest_rename(name, existing.iter().map(String::as_str))
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_only_differences_collide_with_a_free_suggestion() {
+        let existing = vec![
+            "MySkill".to_string(),
+            "myskill-2".to_string(),
+            "other".to_string(),
+        ];
+        assert_eq!(
+            ensure_no_case_collision("myskill", &existing).unwrap_err(),
+            "NAME_CASE_COLLISION|myskill|MySkill|myskill-3"
+        );
+        // Same spelling is the same skill (overwrite flow), not a collision
+        assert!(ensure_no_case_collision("MySkill", &existing).is_ok());
+        assert!(ensure_no_case_collision("brand-new", &existing).is_ok());
+    }
+}
@@ -0,0 +1,201 @@
+//! Per-target subpath rules for copy sync
+//!
+//! Some skills ship large `assets/` folders only one tool needs. A target's
+//! `SkillPathRules` decide which files its copy receives: a pattern is a path
+//! relative to the skill root (`/` separators) that matches itself and
+//! everything below it, and may use glob wildcards (`assets/*.png`). Empty
+//! `include` keeps everything, `exclude` is applied after it, and `SKILL.md`
+//! is always kept. A link cannot be filtered, so a target with rules is
+//! always synced as a copy; `content_hash::hash_dir_with_rules` hashes the
+//! same file set so a slim copy compares equal to its source. Entries left
+//! out by `skill_ignore` are never copied, whatever the rules say.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+
+use super::sync_engine::SyncContext;
+use super::target_marker::MARKER_FILE;
+use super::types::SkillPathRules;
+use super::write_protect::copy_file;
+
+const ALWAYS_KEPT: &str = "SKILL.md";
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+fn normalize_pattern(raw: &str) -> Result<String, String> {
+    let pattern = raw.trim().replace('\\', "/");
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    if pattern.is_empty() {
+        return Err("Path rule is empty".to_string());
+    }
+    if pattern.starts_with('/') || pattern.contains(':') {
+        return Err(format!("Path rule must be relative: {}", raw));
+    }
+    if pattern
+        .split('/')
+        .any(|part| part == ".." || part.is_empty())
+    {
+        return Err(format!("Path rule must stay inside the skill: {}", raw));
+    }
+    Pattern::new(pattern).map_err(|e| format!("Invalid path rule '{}': {}", raw, e))?;
+    Ok(pattern.to_string())
+}
+
+/// Trimmed, `/`-separated, de-duplicated rules; errors on patterns that are
+/// absolute, leave the skill dir or are not valid globs
+pub fn normalize_rules(include: &[String], exclude: &[String]) -> Result<SkillPathRules, String> {
+    let normalize_all = |patterns: &[String]| -> Result<Vec<String>, String> {
+        let mut normalized: Vec<String> = Vec::new();
+        for pattern in patterns {
+            let pattern = normalize_pattern(pattern)?;
+            if !normalized.contains(&pattern) {
+                normalized.push(pattern);
+            }
+        }
+        Ok(normalized)
+    };
+    Ok(SkillPathRules {
+        include: normalize_all(include)?,
+        exclude: normalize_all(exclude)?,
+    })
+}
+
+/// `pattern` matches `relative` or one of its parent directories
+fn matches(pattern: &str, relative: &str) -> bool {
+    let Ok(glob) = Pattern::new(pattern) else {
+        return false;
+    };
+    let mut prefix = relative;
+    loop {
+        if prefix == pattern || glob.matches_with(prefix, MATCH_OPTIONS) {
+            return true;
+        }
+        match prefix.rfind('/') {
+            Some(index) => prefix = &prefix[..index],
+            None => return false,
+        }
+    }
+}
+
+/// Whether the file at `relative` (skill-root relative, `/` separators) is
+/// part of a target with `rules`
+pub fn keeps(rules: &SkillPathRules, relative: &str) -> bool {
+    if relative == ALWAYS_KEPT {
+        return true;
+    }
+    let included = rules.include.is_empty() || rules.include.iter().any(|p| matches(p, relative));
+    included && !rules.exclude.iter().any(|p| matches(p, relative))
+}
+
+fn relative_key(root: &Path, path: &Path) -> Result<String> {
+    let relative = path
+        .strip_prefix(root)
+        .with_context(|| format!("strip prefix {:?}", path))?;
+    Ok(relative.to_string_lossy().replace('\\', "/"))
+}
+
+fn is_skipped(entry: &walkdir::DirEntry) -> bool {
+    entry.file_name() == ".git" || (entry.depth() == 1 && entry.file_name() == MARKER_FILE)
+}
+
+/// Copy the files of `source` kept by `rules` and not ignored into `target`.
+/// Directories are created only for kept files, like `copy_dir_recursive`
+/// symlinks are skipped.
+pub fn copy_dir_filtered(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    rules: &SkillPathRules,
+) -> Result<()> {
+    let ignore = ctx.ignore_for(source);
+    std::fs::create_dir_all(target).with_context(|| format!("create dir {:?}", target))?;
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_skipped(entry) && !ignore.ignores_entry(source, entry))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = relative_key(source, entry.path())?;
+        if !keeps(rules, &relative) {
+            continue;
+        }
+        let target_path = target.join(&relative);
+        if let Some(parent) = target_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        copy_file(entry.path(), &target_path)
+            .with_context(|| format!("copy file {:?} -> {:?}", entry.path(), target_path))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(include: &[&str], exclude: &[&str]) -> SkillPathRules {
+        let owned = |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        normalize_rules(&owned(include), &owned(exclude)).unwrap()
+    }
+
+    #[test]
+    fn include_and_exclude_match_subtrees_and_globs() {
+        let slim = rules(&[], &["assets"]);
+        assert!(keeps(&slim, "SKILL.md"));
+        assert!(keeps(&slim, "scripts/run.sh"));
+        assert!(!keeps(&slim, "assets/video/intro.mp4"));
+
+        let only = rules(&["scripts", "assets/*.png"], &["scripts/dev"]);
+        assert!(keeps(&only, "SKILL.md"));
+        assert!(keeps(&only, "scripts/run.sh"));
+        assert!(!keeps(&only, "scripts/dev/debug.sh"));
+        assert!(keeps(&only, "assets/logo.png"));
+        assert!(!keeps(&only, "assets/deep/logo.png"));
+        assert!(!keeps(&only, "README.md"));
+    }
+
+    #[test]
+    fn normalizes_and_rejects_rules() {
+        let normalized = rules(&["./assets/", "assets", "docs\\img"], &[]);
+        assert_eq!(normalized.include, vec!["assets", "docs/img"]);
+        for bad in ["", "/etc", "../x", "a//b", "C:/x", "a/[b"] {
+            assert!(normalize_rules(&[bad.to_string()], &[]).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn copies_only_kept_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("skill");
+        let target = temp.path().join("target");
+        for (path, content) in [
+            ("SKILL.md", "# skill"),
+            ("scripts/run.sh", "echo"),
+            ("assets/big.bin", "0000"),
+        ] {
+            let file = source.join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, content).unwrap();
+        }
+
+        copy_dir_filtered(
+            &SyncContext::default(),
+            &source,
+            &target,
+            &rules(&[], &["assets"]),
+        )
+        .unwrap();
+        assert!(target.join("SKILL.md").is_file());
+        assert!(target.join("scripts/run.sh").is_file());
+        assert!(!target.join("assets").exists());
+    }
+}
@@ -0,0 +1,221 @@
+//! Ignore patterns for copy sync
+//!
+//! Copies of a skill in tool directories (copy and hard-link sync, path-rule
+//! copies) leave out the files matched by the global list the caller passes
+//! in (`SyncContext::ignore_patterns`) and by a `.skillignore` file at the
+//! skill root, so build artifacts such as `node_modules` never reach a tool.
+//! Both use a gitignore subset: one pattern per line, `#` comments, `!` re-includes, a
+//! trailing `/` only matches directories, and a pattern containing `/` is
+//! anchored to the skill root while one without matches a name at any depth.
+//! `*`, `?`, `[...]` and `**` work as in gitignore, and nothing below an
+//! ignored directory can be re-included. The `.skillignore` file itself is
+//! never copied. Links expose the whole source directory and cannot leave
+//! anything out.
+
+use std::path::Path;
+
+use glob::{MatchOptions, Pattern};
+
+pub const SKILL_IGNORE_FILE: &str = ".skillignore";
+/// Global patterns used until the user saves their own list
+pub const DEFAULT_COPY_IGNORE_PATTERNS: &[&str] = &[
+    "node_modules/",
+    "__pycache__/",
+    "*.pyc",
+    ".venv/",
+    ".DS_Store",
+    "Thumbs.db",
+];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+#[derive(Clone, Debug)]
+struct IgnoreRule {
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Matched against the whole relative path instead of the name
+    anchored: bool,
+}
+
+/// Compiled ignore patterns of one skill directory
+#[derive(Clone, Debug, Default)]
+pub struct SkillIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+/// `None` for blank and comment lines
+fn parse_line(line: &str) -> Option<Result<IgnoreRule, String>> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+    let (negated, rest) = match trimmed.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    let (dir_only, rest) = match rest.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let (rooted, rest) = match rest.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    if rest.is_empty() {
+        return Some(Err(format!("Ignore pattern is empty: {}", trimmed)));
+    }
+    Some(
+        Pattern::new(rest)
+            .map(|pattern| IgnoreRule {
+                pattern,
+                negated,
+                dir_only,
+                anchored: rooted || rest.contains('/'),
+            })
+            .map_err(|e| format!("Invalid ignore pattern '{}': {}", trimmed, e)),
+    )
+}
+
+/// Trimmed, de-duplicated patterns without blank or comment lines; errors on
+/// patterns that are not valid globs
+pub fn normalize_patterns(patterns: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for pattern in patterns {
+        if parse_line(pattern).transpose()?.is_none() {
+            continue;
+        }
+        let pattern = pattern.trim().to_string();
+        if !normalized.contains(&pattern) {
+            normalized.push(pattern);
+        }
+    }
+    Ok(normalized)
+}
+
+/// `DEFAULT_COPY_IGNORE_PATTERNS` as owned strings
+pub fn default_patterns() -> Vec<String> {
+    DEFAULT_COPY_IGNORE_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+impl SkillIgnore {
+    /// Compile `lines`; invalid patterns are skipped with a warning
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let rules = lines
+            .into_iter()
+            .filter_map(parse_line)
+            .filter_map(|rule| {
+                rule.map_err(|err| log::warn!("Skipping skill ignore pattern: {}", err))
+                    .ok()
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// `global` patterns followed by the `.skillignore` of `root`, so the
+    /// skill's own file can re-include what the global list leaves out
+    pub fn for_dir(root: &Path, global: &[String]) -> Self {
+        let mut lines = global.to_vec();
+        if let Ok(content) = std::fs::read_to_string(root.join(SKILL_IGNORE_FILE)) {
+            lines.extend(content.lines().map(str::to_string));
+        }
+        Self::from_lines(lines.iter().map(String::as_str))
+    }
+
+    fn matches(&self, relative: &str, is_dir: bool) -> bool {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let subject = if rule.anchored { relative } else { name };
+            if rule.pattern.matches_with(subject, MATCH_OPTIONS) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+
+    /// Whether the entry at `relative` (skill-root relative, `/` separators)
+    /// or one of its parent directories is ignored
+    pub fn is_ignored(&self, relative: &str, is_dir: bool) -> bool {
+        if relative == SKILL_IGNORE_FILE {
+            return true;
+        }
+        let parts: Vec<&str> = relative.split('/').collect();
+        (1..=parts.len()).any(|len| {
+            let prefix = parts[..len].join("/");
+            self.matches(&prefix, len < parts.len() || is_dir)
+        })
+    }
+
+    /// `is_ignored` for an entry of a walk rooted at `root`; the root itself
+    /// is never ignored
+    pub fn ignores_entry(&self, root: &Path, entry: &walkdir::DirEntry) -> bool {
+        if entry.depth() == 0 {
+            return false;
+        }
+        entry.path().strip_prefix(root).is_ok_and(|relative| {
+            self.is_ignored(
+                &relative.to_string_lossy().replace('\\', "/"),
+                entry.file_type().is_dir(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignore(lines: &[&str]) -> SkillIgnore {
+        SkillIgnore::from_lines(lines.iter().copied())
+    }
+
+    #[test]
+    fn patterns_follow_gitignore_rules() {
+        let ignore = ignore(&[
+            "# build output",
+            "node_modules/",
+            "*.pyc",
+            "/dist",
+            "assets/raw/**",
+            "*.log",
+            "!keep.log",
+        ]);
+        assert!(ignore.is_ignored("node_modules", true));
+        assert!(ignore.is_ignored("scripts/node_modules/pkg/index.js", false));
+        assert!(!ignore.is_ignored("node_modules", false));
+        assert!(ignore.is_ignored("lib/__init__.pyc", false));
+        assert!(ignore.is_ignored("dist/app.js", false));
+        assert!(!ignore.is_ignored("src/dist/app.js", false));
+        assert!(ignore.is_ignored("assets/raw/a/b.png", false));
+        assert!(!ignore.is_ignored("assets/icon.png", false));
+        assert!(ignore.is_ignored("debug.log", false));
+        assert!(!ignore.is_ignored("keep.log", false));
+        assert!(ignore.is_ignored(SKILL_IGNORE_FILE, false));
+        assert!(!ignore.is_ignored("SKILL.md", false));
+    }
+
+    #[test]
+    fn normalizes_and_rejects_patterns() {
+        let patterns = normalize_patterns(&[
+            " node_modules/ ".to_string(),
+            "".to_string(),
+            "# comment".to_string(),
+            "node_modules/".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(patterns, vec!["node_modules/".to_string()]);
+        assert!(normalize_patterns(&["[".to_string()]).is_err());
+        assert!(normalize_patterns(&["!/".to_string()]).is_err());
+    }
+}
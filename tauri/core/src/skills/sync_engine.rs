@@ -0,0 +1,1894 @@
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+use super::content_hash::file_hashes;
+use super::skill_ignore::{default_patterns, SkillIgnore};
+use super::sync_journal::{keep_replaced, JournalOp};
+use super::target_marker::{read_marker, MARKER_FILE};
+use super::types::{
+    SyncDryRun, SyncFileChanges, SyncFileMismatch, SyncMismatchKind, SyncMode, SyncOutcome,
+};
+use super::write_protect::copy_file;
+
+/// Prefix of the hidden sibling directories copies are built in before they
+/// are renamed into place
+const STAGING_PREFIX: &str = ".ai-toolbox-staging-";
+/// Prefix an existing target is renamed to while its replacement moves in
+const BACKUP_PREFIX: &str = ".ai-toolbox-backup-";
+/// Existing copies of skills with at least this many files are updated
+/// incrementally: only changed files are copied into the staging directory
+const INCREMENTAL_MIN_FILES: usize = 200;
+
+/// Settings a sync runs with.
+///
+/// The engine keeps no process-wide state; callers build a context from
+/// their settings and pass it to every sync. The default makes absolute
+/// links, keeps nothing for a rollback and ignores
+/// `skill_ignore::DEFAULT_COPY_IGNORE_PATTERNS`.
+#[derive(Clone, Debug)]
+pub struct SyncContext {
+    /// New directory symlinks store the source path relative to the link's
+    /// directory instead of the absolute path
+    pub relative_symlinks: bool,
+    /// Sync trash replaced targets are moved into (`sync_journal`); without
+    /// one they are deleted
+    pub trash_dir: Option<PathBuf>,
+    /// Global copy ignore patterns, applied before each skill's
+    /// `.skillignore`
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for SyncContext {
+    fn default() -> Self {
+        Self {
+            relative_symlinks: false,
+            trash_dir: None,
+            ignore_patterns: default_patterns(),
+        }
+    }
+}
+
+impl SyncContext {
+    /// Ignore rules for copies of the skill at `root`
+    pub fn ignore_for(&self, root: &Path) -> SkillIgnore {
+        SkillIgnore::for_dir(root, &self.ignore_patterns)
+    }
+
+    pub fn trash(&self) -> Option<&Path> {
+        self.trash_dir.as_deref()
+    }
+}
+
+/// Sync directory using hybrid approach (try symlink, fallback to copy)
+pub fn sync_dir_hybrid(ctx: &SyncContext, source: &Path, target: &Path) -> Result<SyncOutcome> {
+    link_or_copy(ctx, source, target, ctx.relative_symlinks)
+}
+
+fn link_or_copy(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    relative: bool,
+) -> Result<SyncOutcome> {
+    ensure_source_dir(source)?;
+
+    if std::fs::symlink_metadata(target).is_ok() {
+        if let Some(mode_used) = existing_link_mode(target, source) {
+            return Ok(SyncOutcome {
+                mode_used,
+                target_path: target.to_path_buf(),
+                replaced: false,
+                verified: None,
+                mismatches: Vec::new(),
+                file_changes: None,
+            });
+        }
+
+        ensure_source_target_not_overlapping(source, target)?;
+        anyhow::bail!("target already exists: {:?}", target);
+    }
+
+    ensure_source_target_not_overlapping(source, target)?;
+
+    ensure_parent_dir(target)?;
+
+    if try_link_dir(source, target, relative).is_ok() {
+        return Ok(SyncOutcome {
+            mode_used: SyncMode::Symlink,
+            target_path: target.to_path_buf(),
+            replaced: false,
+            verified: None,
+            mismatches: Vec::new(),
+            file_changes: None,
+        });
+    }
+
+    #[cfg(windows)]
+    if try_junction(source, target).is_ok() {
+        return Ok(SyncOutcome {
+            mode_used: SyncMode::Junction,
+            target_path: target.to_path_buf(),
+            replaced: false,
+            verified: None,
+            mismatches: Vec::new(),
+            file_changes: None,
+        });
+    }
+
+    stage_and_swap(ctx, target, false, |staging| {
+        copy_dir_for_target(ctx, source, staging)?;
+        Ok(SyncMode::Copy)
+    })?;
+    Ok(sync_outcome(ctx, source, target, SyncMode::Copy, false))
+}
+
+/// Sync directory with overwrite option
+pub fn sync_dir_hybrid_with_overwrite(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<SyncOutcome> {
+    link_or_copy_with_overwrite(ctx, source, target, overwrite, ctx.relative_symlinks)
+}
+
+fn link_or_copy_with_overwrite(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    relative: bool,
+) -> Result<SyncOutcome> {
+    ensure_source_dir(source)?;
+
+    if std::fs::symlink_metadata(target).is_err() {
+        ensure_source_target_not_overlapping(source, target)?;
+        return link_or_copy(ctx, source, target, relative);
+    }
+
+    if let Some(mode_used) = existing_link_mode(target, source) {
+        return Ok(SyncOutcome {
+            mode_used,
+            target_path: target.to_path_buf(),
+            replaced: false,
+            verified: None,
+            mismatches: Vec::new(),
+            file_changes: None,
+        });
+    }
+
+    ensure_source_target_not_overlapping(source, target)?;
+    if !overwrite {
+        anyhow::bail!("target already exists: {:?}", target);
+    }
+
+    // Keep the previous target aside until the new link or copy is in place
+    let backup = move_aside(target)?;
+    match link_or_copy(ctx, source, target, relative) {
+        Ok(mut out) => {
+            discard_backup(ctx, &backup, target);
+            out.replaced = true;
+            Ok(out)
+        }
+        Err(err) => Err(restore_backup(&backup, target, err)),
+    }
+}
+
+/// Sync directory using copy only with overwrite option.
+///
+/// The new copy is always built in a staging directory and swapped in. For a
+/// managed copy of a large skill only the files that changed are copied into
+/// it; unchanged ones are hard-linked from the previous copy (see
+/// `stage_copy_update`).
+pub fn sync_dir_copy_with_overwrite(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<SyncOutcome> {
+    ensure_source_dir(source)?;
+    ensure_source_target_not_overlapping(source, target)?;
+
+    if overwrite && is_managed_copy(target) {
+        let plan = copy_plan(ctx, source)?;
+        if plan.files.len() >= INCREMENTAL_MIN_FILES {
+            let mut changes = SyncFileChanges::default();
+            stage_and_swap(ctx, target, true, |staging| {
+                changes = stage_copy_update(source, target, staging, &plan)?;
+                Ok(SyncMode::Copy)
+            })?;
+            log::info!(
+                "Updated copy {:?}: {} copied, {} deleted, {} unchanged",
+                target,
+                changes.copied,
+                changes.deleted,
+                changes.unchanged
+            );
+            let mut outcome = sync_outcome(ctx, source, target, SyncMode::Copy, true);
+            outcome.file_changes = Some(changes);
+            return Ok(outcome);
+        }
+    }
+
+    let (mode_used, replaced) = stage_and_swap(ctx, target, overwrite, |staging| {
+        copy_dir_for_target(ctx, source, staging)?;
+        Ok(SyncMode::Copy)
+    })?;
+
+    Ok(sync_outcome(ctx, source, target, mode_used, replaced))
+}
+
+/// Sync directory as per-file hard links with overwrite option.
+///
+/// The directory tree is recreated and every file is hard-linked to the one
+/// in `source`, so the target costs no extra disk space and follows in-place
+/// edits of the central repo. Falls back to a full copy when a link cannot be
+/// created, e.g. when `target` is on another filesystem.
+pub fn sync_dir_hardlink_with_overwrite(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<SyncOutcome> {
+    ensure_source_dir(source)?;
+    ensure_source_target_not_overlapping(source, target)?;
+
+    let (mode_used, replaced) =
+        stage_and_swap(
+            ctx,
+            target,
+            overwrite,
+            |staging| match hardlink_dir_recursive(ctx, source, staging) {
+                Ok(()) => Ok(SyncMode::Hardlink),
+                Err(err) => {
+                    log::info!(
+                        "Hard links unavailable for {:?}, copying instead: {:#}",
+                        target,
+                        err
+                    );
+                    remove_path(&staging.to_string_lossy())
+                        .map_err(anyhow::Error::msg)
+                        .with_context(|| {
+                            format!("remove partial hard link staging {:?}", staging)
+                        })?;
+                    copy_dir_for_target(ctx, source, staging)?;
+                    Ok(SyncMode::Copy)
+                }
+            },
+        )?;
+
+    Ok(sync_outcome(ctx, source, target, mode_used, replaced))
+}
+
+/// Sync directory as a directory link of exactly one kind (`Symlink` or
+/// `Junction`), with overwrite option.
+///
+/// Unlike `sync_dir_hybrid_with_overwrite` this never falls back to another
+/// link kind or a copy; a target that cannot be linked this way fails and an
+/// overwritten target is restored. `relative` only applies to symlinks;
+/// junctions always hold an absolute path.
+pub fn sync_dir_link_with_overwrite(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: SyncMode,
+    relative: bool,
+) -> Result<SyncOutcome> {
+    ensure_source_dir(source)?;
+    let exists = std::fs::symlink_metadata(target).is_ok();
+    if exists && existing_link_mode(target, source) == Some(mode) {
+        return Ok(SyncOutcome {
+            mode_used: mode,
+            target_path: target.to_path_buf(),
+            replaced: false,
+            verified: None,
+            mismatches: Vec::new(),
+            file_changes: None,
+        });
+    }
+
+    ensure_source_target_not_overlapping(source, target)?;
+    if exists && !overwrite {
+        anyhow::bail!("target already exists: {:?}", target);
+    }
+    ensure_parent_dir(target)?;
+
+    let backup = if exists {
+        Some(move_aside(target)?)
+    } else {
+        None
+    };
+    let linked = match mode {
+        SyncMode::Symlink => try_link_dir(source, target, relative),
+        SyncMode::Junction => try_junction(source, target),
+        _ => Err(anyhow::anyhow!(
+            "{} is not a directory link mode",
+            mode.as_str()
+        )),
+    };
+    match (linked, backup) {
+        (Ok(()), backup) => {
+            if let Some(backup) = backup {
+                discard_backup(ctx, &backup, target);
+            }
+            Ok(SyncOutcome {
+                mode_used: mode,
+                target_path: target.to_path_buf(),
+                replaced: exists,
+                verified: None,
+                mismatches: Vec::new(),
+                file_changes: None,
+            })
+        }
+        (Err(err), Some(backup)) => Err(restore_backup(&backup, target, err)),
+        (Err(err), None) => Err(err),
+    }
+}
+
+/// Sync directory with a tool's sync mode and overwrite option.
+///
+/// `Auto` links the directory and falls back to a copy; every other mode is
+/// used as is. The mode comes from the tool (`RuntimeToolAdapter::sync_mode`):
+/// Cursor doesn't support symlinks and defaults to `Copy`, and users can
+/// pick a mode per tool.
+pub fn sync_dir_for_tool_with_overwrite(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: SyncMode,
+) -> Result<SyncOutcome> {
+    sync_dir_for_tool_with_link_style(ctx, source, target, overwrite, mode, ctx.relative_symlinks)
+}
+
+/// `sync_dir_for_tool_with_overwrite` with the symlink style chosen for this
+/// call: `relative` links store the source path relative to the target's
+/// parent directory, so they keep working when the central repo and the tool
+/// dirs move together, e.g. on a synced or portable volume. Existing links
+/// to `source` are kept whatever their style.
+pub fn sync_dir_for_tool_with_link_style(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: SyncMode,
+    relative: bool,
+) -> Result<SyncOutcome> {
+    match mode {
+        SyncMode::Auto => link_or_copy_with_overwrite(ctx, source, target, overwrite, relative),
+        SyncMode::Copy => sync_dir_copy_with_overwrite(ctx, source, target, overwrite),
+        SyncMode::Hardlink => sync_dir_hardlink_with_overwrite(ctx, source, target, overwrite),
+        SyncMode::Symlink | SyncMode::Junction => {
+            sync_dir_link_with_overwrite(ctx, source, target, overwrite, mode, relative)
+        }
+    }
+}
+
+/// Compare a copied `target` file by file with `source`; the files
+/// `skill_ignore` leaves out of the copy are not expected in it. Paths are
+/// sorted, and only the files `content_hash::list_files` covers are checked.
+pub fn verify_copy(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+) -> Result<Vec<SyncFileMismatch>> {
+    let ignore = ctx.ignore_for(source);
+    let mut expected = file_hashes(source)?;
+    expected.retain(|relative, _| !ignore.is_ignored(relative, false));
+    let actual = file_hashes(target)?;
+
+    let mut mismatches: Vec<SyncFileMismatch> = expected
+        .iter()
+        .filter_map(|(relative, hash)| {
+            let kind = match actual.get(relative) {
+                None => SyncMismatchKind::Missing,
+                Some(copied) if copied != hash => SyncMismatchKind::Changed,
+                Some(_) => return None,
+            };
+            Some(SyncFileMismatch {
+                path: relative.clone(),
+                kind,
+            })
+        })
+        .collect();
+    mismatches.extend(
+        actual
+            .keys()
+            .filter(|relative| !expected.contains_key(*relative))
+            .map(|relative| SyncFileMismatch {
+                path: relative.clone(),
+                kind: SyncMismatchKind::Extra,
+            }),
+    );
+    mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(mismatches)
+}
+
+/// Outcome of a staged sync; a copy is verified against `source` first
+fn sync_outcome(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    mode_used: SyncMode,
+    replaced: bool,
+) -> SyncOutcome {
+    let (verified, mismatches) = if matches!(mode_used, SyncMode::Copy) {
+        match verify_copy(ctx, source, target) {
+            Ok(mismatches) => {
+                if !mismatches.is_empty() {
+                    log::warn!(
+                        "Copy {:?} differs from {:?} in {} file(s)",
+                        target,
+                        source,
+                        mismatches.len()
+                    );
+                }
+                (Some(mismatches.is_empty()), mismatches)
+            }
+            Err(err) => {
+                log::warn!("Could not verify copy {:?}: {:#}", target, err);
+                (None, Vec::new())
+            }
+        }
+    } else {
+        (None, Vec::new())
+    };
+    SyncOutcome {
+        mode_used,
+        target_path: target.to_path_buf(),
+        replaced,
+        verified,
+        mismatches,
+        file_changes: None,
+    }
+}
+
+/// Plan `sync_dir_for_tool_with_overwrite` without touching the filesystem.
+///
+/// Returns the outcome the sync would report and the absolute paths it would
+/// create, overwrite or remove; a link counts as one path. Links are planned
+/// as `Symlink` even though a real sync on Windows may fall back to a
+/// junction or a copy, and hard links as `Hardlink` although a real sync
+/// copies across filesystems. Fails with the same errors the real sync raises
+/// before writing anything (missing source, overlap, existing target).
+pub fn sync_dir_dry_run(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: SyncMode,
+) -> Result<SyncDryRun> {
+    sync_dir_dry_run_filtered(ctx, source, target, overwrite, mode, |_| true)
+}
+
+/// `sync_dir_dry_run` for a copy that only receives the files `keep` accepts
+/// (paths relative to `source`, `/` separators)
+pub fn sync_dir_dry_run_filtered(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: SyncMode,
+    keep: impl Fn(&str) -> bool,
+) -> Result<SyncDryRun> {
+    ensure_source_dir(source)?;
+    let copy_mode = mode.holds_own_files();
+    let target_meta = std::fs::symlink_metadata(target).ok();
+
+    if target_meta.is_some() && !copy_mode {
+        if let Some(mode_used) = existing_link_mode(target, source)
+            .filter(|existing| mode == SyncMode::Auto || *existing == mode)
+        {
+            return Ok(SyncDryRun {
+                outcome: SyncOutcome {
+                    mode_used,
+                    target_path: target.to_path_buf(),
+                    replaced: false,
+                    verified: None,
+                    mismatches: Vec::new(),
+                    file_changes: None,
+                },
+                created: Vec::new(),
+                overwritten: Vec::new(),
+                removed: Vec::new(),
+            });
+        }
+    }
+    ensure_source_target_not_overlapping(source, target)?;
+    if target_meta.is_some() && !overwrite {
+        anyhow::bail!("target already exists: {:?}", target);
+    }
+
+    let planned = if copy_mode {
+        planned_copy_files(ctx, source, target, &keep)?
+    } else {
+        vec![target.to_path_buf()]
+    };
+    let existing = match &target_meta {
+        Some(meta) if meta.is_dir() => existing_target_files(target)?,
+        Some(_) => vec![target.to_path_buf()],
+        None => Vec::new(),
+    };
+    let existing_set: HashSet<&PathBuf> = existing.iter().collect();
+    let planned_set: HashSet<&PathBuf> = planned.iter().collect();
+    let (overwritten, created) = planned
+        .iter()
+        .cloned()
+        .partition(|path| existing_set.contains(path));
+
+    Ok(SyncDryRun {
+        outcome: SyncOutcome {
+            mode_used: if mode == SyncMode::Auto {
+                SyncMode::Symlink
+            } else {
+                mode
+            },
+            target_path: target.to_path_buf(),
+            replaced: target_meta.is_some(),
+            verified: None,
+            mismatches: Vec::new(),
+            file_changes: None,
+        },
+        created,
+        overwritten,
+        removed: existing
+            .iter()
+            .filter(|path| !planned_set.contains(path))
+            .cloned()
+            .collect(),
+    })
+}
+
+/// Files `copy_dir_for_target` would write into `target`
+fn planned_copy_files(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    keep: &impl Fn(&str) -> bool,
+) -> Result<Vec<PathBuf>> {
+    let ignore = ctx.ignore_for(source);
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !should_skip_copy(entry) && !ignore.ignores_entry(source, entry))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(source)?;
+        if keep(&relative.to_string_lossy().replace('\\', "/")) {
+            files.push(target.join(relative));
+        }
+    }
+    Ok(files)
+}
+
+/// Files and links under an existing target directory, except the marker
+/// that is rewritten after every sync
+fn existing_target_files(target: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(target)
+        .follow_links(false)
+        .sort_by_file_name()
+    {
+        let entry = entry?;
+        if entry.file_type().is_dir() || (entry.depth() == 1 && entry.file_name() == MARKER_FILE) {
+            continue;
+        }
+        files.push(entry.into_path());
+    }
+    Ok(files)
+}
+
+pub fn validate_sync_target_preflight(
+    source: &Path,
+    target: &Path,
+    force_copy: bool,
+) -> Result<()> {
+    ensure_source_dir(source)?;
+
+    if !force_copy && std::fs::symlink_metadata(target).is_ok() && is_same_link(target, source) {
+        return Ok(());
+    }
+
+    ensure_source_target_not_overlapping(source, target)
+}
+
+/// Build `target` in a hidden sibling directory and rename it into place.
+///
+/// `fill` writes the new contents into the staging path it is given; if it
+/// fails the staging directory is removed and `target` is left untouched. An
+/// existing target (only allowed with `overwrite`) is moved aside before the
+/// rename and restored if the rename fails, so `target` never holds a
+/// half-written tree. Returns the mode `fill` reported and whether a previous
+/// target was replaced.
+fn stage_and_swap<F>(
+    ctx: &SyncContext,
+    target: &Path,
+    overwrite: bool,
+    fill: F,
+) -> Result<(SyncMode, bool)>
+where
+    F: FnOnce(&Path) -> Result<SyncMode>,
+{
+    let exists = std::fs::symlink_metadata(target).is_ok();
+    if exists && !overwrite {
+        anyhow::bail!("target already exists: {:?}", target);
+    }
+
+    ensure_parent_dir(target)?;
+    let staging = sibling_temp_path(target, STAGING_PREFIX)?;
+    let mode_used = match fill(&staging) {
+        Ok(mode_used) => mode_used,
+        Err(err) => {
+            let _ = remove_path(&staging.to_string_lossy());
+            return Err(err);
+        }
+    };
+
+    let backup = if exists {
+        match move_aside(target) {
+            Ok(backup) => Some(backup),
+            Err(err) => {
+                let _ = remove_path(&staging.to_string_lossy());
+                return Err(err);
+            }
+        }
+    } else {
+        None
+    };
+
+    if let Err(err) = std::fs::rename(&staging, target) {
+        let _ = remove_path(&staging.to_string_lossy());
+        let err =
+            anyhow::Error::new(err).context(format!("move staged {:?} into {:?}", staging, target));
+        return Err(match backup {
+            Some(backup) => restore_backup(&backup, target, err),
+            None => err,
+        });
+    }
+
+    if let Some(backup) = backup {
+        discard_backup(ctx, &backup, target);
+    }
+    Ok((mode_used, exists))
+}
+
+/// Hidden path next to `target` that no other sync picks
+fn sibling_temp_path(target: &Path, prefix: &str) -> Result<PathBuf> {
+    let parent = target
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("target has no parent: {:?}", target))?;
+    let name = target
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("target has no file name: {:?}", target))?;
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    Ok(parent.join(format!(
+        "{}{}-{}-{}",
+        prefix,
+        name.to_string_lossy(),
+        std::process::id(),
+        nanos
+    )))
+}
+
+/// Rename an existing target to a backup path next to it
+fn move_aside(target: &Path) -> Result<PathBuf> {
+    let backup = sibling_temp_path(target, BACKUP_PREFIX)?;
+    std::fs::rename(target, &backup)
+        .with_context(|| format!("move existing target {:?} aside", target))?;
+    Ok(backup)
+}
+
+/// Put the previous target back after its replacement failed
+fn restore_backup(backup: &Path, target: &Path, err: anyhow::Error) -> anyhow::Error {
+    let _ = remove_path(&target.to_string_lossy());
+    match std::fs::rename(backup, target) {
+        Ok(()) => err,
+        Err(restore_err) => err.context(format!(
+            "previous target could not be restored and is kept at {:?}: {}",
+            backup, restore_err
+        )),
+    }
+}
+
+/// Move the replaced target into the sync trash so the sync can be rolled
+/// back, or delete it when there is no trash. If the trash cannot take it the
+/// backup is left next to the target rather than lost.
+fn discard_backup(ctx: &SyncContext, backup: &Path, target: &Path) {
+    match keep_replaced(ctx.trash(), backup, target, JournalOp::Overwrite) {
+        Ok(true) => {}
+        Ok(false) => {
+            if let Err(err) = remove_path(&backup.to_string_lossy()) {
+                log::warn!("Failed to remove replaced target {:?}: {}", backup, err);
+            }
+        }
+        Err(err) => log::warn!(
+            "Replaced target kept at {:?}, the sync trash failed: {:#}",
+            backup,
+            err
+        ),
+    }
+}
+
+fn ensure_parent_dir(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    }
+    Ok(())
+}
+
+pub fn ensure_source_dir(source: &Path) -> Result<()> {
+    let meta = std::fs::metadata(source)
+        .with_context(|| format!("source path is not a resolvable directory: {:?}", source))?;
+    if !meta.is_dir() {
+        anyhow::bail!("source path is not a directory: {:?}", source);
+    }
+    Ok(())
+}
+
+pub fn ensure_source_target_not_overlapping(source: &Path, target: &Path) -> Result<()> {
+    let source_real = std::fs::canonicalize(source)
+        .with_context(|| format!("canonicalize source {:?}", source))?;
+    let target_real = resolve_target_write_path(target)
+        .with_context(|| format!("resolve target write path {:?}", target))?;
+
+    if source_real == target_real {
+        anyhow::bail!(
+            "source and target resolve to the same path: source={:?}, target={:?}, resolved={:?}",
+            source,
+            target,
+            source_real
+        );
+    }
+
+    if target_real.starts_with(&source_real) {
+        anyhow::bail!(
+            "target path is inside source directory after resolving symlinks: source={:?}, target={:?}, resolved_target={:?}",
+            source,
+            target,
+            target_real
+        );
+    }
+
+    if source_real.starts_with(&target_real) {
+        anyhow::bail!(
+            "source path is inside target directory after resolving symlinks: source={:?}, target={:?}, resolved_target={:?}",
+            source,
+            target,
+            target_real
+        );
+    }
+
+    Ok(())
+}
+
+fn resolve_target_write_path(target: &Path) -> Result<PathBuf> {
+    if let Ok(real) = std::fs::canonicalize(target) {
+        return Ok(real);
+    }
+
+    let mut suffix = Vec::new();
+    let mut cursor = target;
+
+    loop {
+        if let Ok(real) = std::fs::canonicalize(cursor) {
+            let mut out = real;
+            for part in suffix.iter().rev() {
+                out.push(part);
+            }
+            return Ok(out);
+        }
+
+        let name = cursor
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("target has no resolvable parent: {:?}", target))?;
+        suffix.push(name.to_os_string());
+        cursor = cursor
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("target has no parent: {:?}", target))?;
+    }
+}
+
+fn is_same_link(link_path: &Path, target: &Path) -> bool {
+    existing_link_mode(link_path, target).is_some()
+}
+
+/// How `link_path` already points at `target`, if it does.
+///
+/// On Windows `read_link` returns a junction's reparse target in its raw
+/// `\\?\` form, which never equals the path we created it from; resolve the
+/// junction target and compare it normalized so repeated syncs stay no-ops.
+fn existing_link_mode(link_path: &Path, target: &Path) -> Option<SyncMode> {
+    #[cfg(windows)]
+    if junction::exists(link_path).unwrap_or(false) {
+        return junction::get_target(link_path)
+            .ok()
+            .filter(|existing| same_reparse_target(existing, target))
+            .map(|_| SyncMode::Junction);
+    }
+
+    let existing = std::fs::read_link(link_path).ok()?;
+    let same = if existing.is_relative() {
+        let resolved = std::fs::canonicalize(link_path).ok();
+        resolved.is_some() && resolved == std::fs::canonicalize(target).ok()
+    } else {
+        existing == target
+    };
+    same.then_some(SyncMode::Symlink)
+}
+
+/// Where a link pointing at `stored` (as returned by `read_link`) leads: a
+/// relative path is resolved against the link's directory, without touching
+/// the filesystem
+pub fn resolve_link_target(link_path: &Path, stored: &Path) -> PathBuf {
+    if stored.is_absolute() {
+        return stored.to_path_buf();
+    }
+    let joined = link_path
+        .parent()
+        .map(|parent| parent.join(stored))
+        .unwrap_or_else(|| stored.to_path_buf());
+    let mut resolved = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other.as_os_str()),
+        }
+    }
+    resolved
+}
+
+/// Path of `source` relative to `dir` (`../../skills/foo`), or `None` when
+/// the two share no root, e.g. on different Windows drives
+fn relative_path_from(dir: &Path, source: &Path) -> Option<PathBuf> {
+    let dir: Vec<Component> = dir.components().collect();
+    let source: Vec<Component> = source.components().collect();
+    if dir.first() != source.first() {
+        return None;
+    }
+    let common = dir.iter().zip(&source).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..dir.len() {
+        relative.push("..");
+    }
+    for component in &source[common..] {
+        relative.push(component.as_os_str());
+    }
+    Some(relative)
+}
+
+/// What a new symlink at `target` stores. Relative paths are computed from
+/// the real paths, since the OS resolves them from the real directory of the
+/// link; without a common root the link stays absolute.
+fn symlink_source(source: &Path, target: &Path, relative: bool) -> PathBuf {
+    if !relative {
+        return source.to_path_buf();
+    }
+    let parent = target
+        .parent()
+        .and_then(|dir| std::fs::canonicalize(dir).ok());
+    let real_source = std::fs::canonicalize(source).ok();
+    parent
+        .zip(real_source)
+        .and_then(|(dir, real_source)| relative_path_from(&dir, &real_source))
+        .unwrap_or_else(|| source.to_path_buf())
+}
+
+/// Compare reparse-point targets the way NTFS does: ignoring the verbatim
+/// prefix, separator style, trailing separators and case.
+#[cfg(any(windows, test))]
+fn same_reparse_target(existing: &Path, target: &Path) -> bool {
+    fn normalize(path: &Path) -> String {
+        let raw = path.to_string_lossy().replace('/', "\\");
+        let raw = if let Some(unc) = raw.strip_prefix("\\\\?\\UNC\\") {
+            format!("\\\\{unc}")
+        } else {
+            raw.strip_prefix("\\\\?\\")
+                .or_else(|| raw.strip_prefix("\\??\\"))
+                .unwrap_or(&raw)
+                .to_string()
+        };
+        raw.trim_end_matches('\\').to_lowercase()
+    }
+    normalize(existing) == normalize(target)
+}
+
+fn try_link_dir(source: &Path, target: &Path, relative: bool) -> Result<()> {
+    let link = symlink_source(source, target, relative);
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&link, target)
+            .with_context(|| format!("symlink {:?} -> {:?}", target, link))?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_dir(&link, target)
+            .with_context(|| format!("symlink {:?} -> {:?}", target, link))?;
+        Ok(())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    anyhow::bail!("symlink not supported on this platform")
+}
+
+#[cfg(windows)]
+fn try_junction(source: &Path, target: &Path) -> Result<()> {
+    junction::create(source, target)
+        .with_context(|| format!("junction {:?} -> {:?}", target, source))?;
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn try_junction(_source: &Path, target: &Path) -> Result<()> {
+    anyhow::bail!("junctions are only supported on Windows: {:?}", target)
+}
+
+fn should_skip_copy(entry: &walkdir::DirEntry) -> bool {
+    entry.file_name() == ".git" || (entry.depth() == 1 && entry.file_name() == MARKER_FILE)
+}
+
+/// Copy a skill directory, resolving top-level symlinks.
+///
+/// Files and directories directly inside `source` (alongside SKILL.md) that are
+/// symlinks will be resolved first so the real content is copied into `target`.
+/// On Windows, Git stores symlinks as text files containing the target path;
+/// this function also handles that case.
+/// Symlinks deeper in the tree are left as-is (skipped by `copy_dir_recursive`).
+pub fn copy_skill_dir(source: &Path, target: &Path) -> Result<()> {
+    std::fs::create_dir_all(target).with_context(|| format!("create dir {:?}", target))?;
+
+    for entry in std::fs::read_dir(source).with_context(|| format!("read dir {:?}", source))? {
+        let entry = entry?;
+        let name = entry.file_name();
+
+        if name == ".git" || name == MARKER_FILE {
+            continue;
+        }
+
+        let entry_path = entry.path();
+        let dest = target.join(&name);
+
+        // Resolve symlinks at the top level
+        let real_path = match std::fs::symlink_metadata(&entry_path) {
+            Ok(meta) if meta.file_type().is_symlink() => std::fs::canonicalize(&entry_path)
+                .with_context(|| format!("resolve symlink {:?}", entry_path))?,
+            Ok(meta) if meta.is_file() => {
+                // On Windows, Git stores symlinks as small text files containing the target path.
+                // Check if this might be a git-style symlink (small file with path content).
+                if let Some(resolved) = try_resolve_git_symlink(&entry_path, source) {
+                    resolved
+                } else {
+                    entry_path.clone()
+                }
+            }
+            _ => entry_path.clone(),
+        };
+
+        let real_meta =
+            std::fs::metadata(&real_path).with_context(|| format!("stat {:?}", real_path))?;
+
+        if real_meta.is_dir() {
+            copy_dir_recursive(&real_path, &dest)?;
+        } else if real_meta.is_file() {
+            copy_file(&real_path, &dest)
+                .with_context(|| format!("copy file {:?} -> {:?}", real_path, dest))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Try to resolve a potential Git-style symlink file on Windows.
+/// Git stores symlinks as text files containing the relative path when core.symlinks is false.
+/// Returns Some(resolved_path) if the file looks like a symlink and target exists,
+/// None otherwise.
+fn try_resolve_git_symlink(file_path: &Path, _base_dir: &Path) -> Option<PathBuf> {
+    // Only check small files (symlink paths are typically short)
+    let meta = std::fs::metadata(file_path).ok()?;
+    if meta.len() > 512 {
+        return None;
+    }
+
+    let content = std::fs::read_to_string(file_path).ok()?;
+    let content = content.trim();
+
+    // Check if content looks like a relative path
+    if content.is_empty() || content.contains('\n') {
+        return None;
+    }
+
+    // Must start with . or contain path separators, and not contain spaces or special chars
+    if !content.starts_with('.') && !content.starts_with('/') {
+        return None;
+    }
+
+    // Resolve relative to the file's parent directory
+    let parent = file_path.parent()?;
+    let target = parent.join(content);
+
+    // Canonicalize to get absolute path and verify it exists
+    let resolved = std::fs::canonicalize(&target).ok()?;
+
+    // Safety check: make sure we're not escaping to completely unrelated paths
+    // The target should be within the same repository (base_dir or its parent tree)
+    Some(resolved)
+}
+
+/// Recursively copy directory contents
+pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !should_skip_copy(entry))
+    {
+        let entry = entry?;
+        if should_skip_copy(&entry) {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(source)?;
+        let target_path = target.join(relative);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target_path)
+                .with_context(|| format!("create dir {:?}", target_path))?;
+        } else if entry.file_type().is_file() {
+            if let Some(parent) = target_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            copy_file(entry.path(), &target_path)
+                .with_context(|| format!("copy file {:?} -> {:?}", entry.path(), target_path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy a skill into a tool target: like `copy_dir_recursive`, but the
+/// entries `skill_ignore` leaves out are not copied
+pub fn copy_dir_for_target(ctx: &SyncContext, source: &Path, target: &Path) -> Result<()> {
+    let ignore = ctx.ignore_for(source);
+    std::fs::create_dir_all(target).with_context(|| format!("create dir {:?}", target))?;
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !should_skip_copy(entry) && !ignore.ignores_entry(source, entry))
+    {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        let target_path = target.join(relative);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target_path)
+                .with_context(|| format!("create dir {:?}", target_path))?;
+        } else if entry.file_type().is_file() {
+            copy_file(entry.path(), &target_path)
+                .with_context(|| format!("copy file {:?} -> {:?}", entry.path(), target_path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Directories and files (relative to the source) a copy to a tool target
+/// receives, parents first
+struct CopyPlan {
+    dirs: Vec<PathBuf>,
+    files: Vec<PathBuf>,
+}
+
+fn copy_plan(ctx: &SyncContext, source: &Path) -> Result<CopyPlan> {
+    let ignore = ctx.ignore_for(source);
+    let mut plan = CopyPlan {
+        dirs: Vec::new(),
+        files: Vec::new(),
+    };
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !should_skip_copy(entry) && !ignore.ignores_entry(source, entry))
+    {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?.to_path_buf();
+        if entry.file_type().is_dir() {
+            plan.dirs.push(relative);
+        } else if entry.file_type().is_file() {
+            plan.files.push(relative);
+        }
+    }
+    Ok(plan)
+}
+
+/// A plain directory whose marker says an earlier sync copied it there.
+/// Hard-linked targets are excluded: writing into them would write into the
+/// central repo.
+fn is_managed_copy(target: &Path) -> bool {
+    let is_dir = std::fs::symlink_metadata(target)
+        .map(|meta| meta.is_dir())
+        .unwrap_or(false);
+    is_dir && read_marker(target).is_some_and(|marker| marker.mode == SyncMode::Copy.as_str())
+}
+
+/// Build the new copy in `staging` from an existing one like rsync: files
+/// that did not change are hard-linked from `target`, new or changed ones are
+/// copied from `source`, and the marker file is carried over. `target` itself
+/// is not written to; the caller swaps `staging` in. Entries the source no
+/// longer has are counted as deleted.
+fn stage_copy_update(
+    source: &Path,
+    target: &Path,
+    staging: &Path,
+    plan: &CopyPlan,
+) -> Result<SyncFileChanges> {
+    let mut changes = SyncFileChanges::default();
+    std::fs::create_dir_all(staging).with_context(|| format!("create dir {:?}", staging))?;
+    for dir in &plan.dirs {
+        let staged_dir = staging.join(dir);
+        std::fs::create_dir_all(&staged_dir)
+            .with_context(|| format!("create dir {:?}", staged_dir))?;
+    }
+    for relative in &plan.files {
+        let from = source.join(relative);
+        let existing = target.join(relative);
+        let to = staging.join(relative);
+        if is_unchanged_copy(&from, &existing)? && std::fs::hard_link(&existing, &to).is_ok() {
+            changes.unchanged += 1;
+            continue;
+        }
+        copy_file(&from, &to).with_context(|| format!("copy file {:?} -> {:?}", from, to))?;
+        copy_modified_time(&from, &to);
+        changes.copied += 1;
+    }
+    let marker = target.join(MARKER_FILE);
+    if marker.is_file() {
+        std::fs::copy(&marker, staging.join(MARKER_FILE))
+            .with_context(|| format!("copy marker {:?}", marker))?;
+    }
+
+    let files: HashSet<&Path> = plan.files.iter().map(PathBuf::as_path).collect();
+    for entry in walkdir::WalkDir::new(target)
+        .follow_links(false)
+        .min_depth(1)
+    {
+        let entry = entry?;
+        if entry.file_type().is_dir() || (entry.depth() == 1 && entry.file_name() == MARKER_FILE) {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(target)?;
+        if !(entry.file_type().is_file() && files.contains(relative)) {
+            changes.deleted += 1;
+        }
+    }
+    Ok(changes)
+}
+
+/// Same size and mtime count as unchanged, like rsync's quick check;
+/// otherwise files of the same size are compared by content. A file found
+/// equal takes the source mtime so the next sync skips reading it.
+fn is_unchanged_copy(from: &Path, to: &Path) -> Result<bool> {
+    let Ok(to_meta) = std::fs::metadata(to) else {
+        return Ok(false);
+    };
+    let from_meta = std::fs::metadata(from).with_context(|| format!("stat {:?}", from))?;
+    if !to_meta.is_file() || to_meta.len() != from_meta.len() {
+        return Ok(false);
+    }
+    let modified = from_meta.modified().ok();
+    if modified.is_some() && modified == to_meta.modified().ok() {
+        return Ok(true);
+    }
+    let same = std::fs::read(from).with_context(|| format!("read file {:?}", from))?
+        == std::fs::read(to).with_context(|| format!("read file {:?}", to))?;
+    if same {
+        copy_modified_time(from, to);
+    }
+    Ok(same)
+}
+
+/// Best effort: a file that cannot be opened for writing (read-only) is
+/// compared by content again next time
+fn copy_modified_time(from: &Path, to: &Path) {
+    let Ok(modified) = std::fs::metadata(from).and_then(|meta| meta.modified()) else {
+        return;
+    };
+    let result = std::fs::File::options()
+        .write(true)
+        .open(to)
+        .and_then(|file| file.set_modified(modified));
+    if let Err(err) = result {
+        log::debug!("Could not set mtime of {:?}: {}", to, err);
+    }
+}
+
+/// Recreate the tree of `source` in `target` with every file hard-linked;
+/// skips the same entries as `copy_dir_for_target`
+fn hardlink_dir_recursive(ctx: &SyncContext, source: &Path, target: &Path) -> Result<()> {
+    let ignore = ctx.ignore_for(source);
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !should_skip_copy(entry) && !ignore.ignores_entry(source, entry))
+    {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        let target_path = target.join(relative);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target_path)
+                .with_context(|| format!("create dir {:?}", target_path))?;
+        } else if entry.file_type().is_file() {
+            std::fs::hard_link(entry.path(), &target_path).with_context(|| {
+                format!("hard link file {:?} -> {:?}", entry.path(), target_path)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove path (file, dir, or symlink/junction)
+pub fn remove_path(path: &str) -> Result<(), String> {
+    let p = Path::new(path);
+
+    // Use symlink_metadata to check if path exists (works for broken symlinks too)
+    let meta = match std::fs::symlink_metadata(p) {
+        Ok(meta) => meta,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.to_string()),
+    };
+    let ft = meta.file_type();
+
+    // Handle symlinks and junctions
+    if ft.is_symlink() {
+        #[cfg(unix)]
+        {
+            // On Unix (macOS, Linux), always use remove_file for symlinks
+            // regardless of what they point to
+            std::fs::remove_file(p).map_err(|err| err.to_string())?;
+        }
+        #[cfg(windows)]
+        {
+            // On Windows, directory junctions need remove_dir, not remove_file
+            // Check if it's a directory link by checking if the path is a dir
+            // (symlink_metadata tells us it's a link, is_dir follows the link)
+            if p.is_dir() {
+                std::fs::remove_dir(p).map_err(|err| err.to_string())?;
+            } else {
+                std::fs::remove_file(p).map_err(|err| err.to_string())?;
+            }
+        }
+        return Ok(());
+    }
+
+    if ft.is_dir() {
+        std::fs::remove_dir_all(p).map_err(|err| err.to_string())?;
+        return Ok(());
+    }
+
+    std::fs::remove_file(p).map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn create_self_symlink(path: &Path) {
+        std::os::unix::fs::symlink(path, path).expect("create self symlink");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_rejects_self_symlink_source_without_creating_target() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        create_self_symlink(&source);
+
+        let result = sync_dir_hybrid(&SyncContext::default(), &source, &target);
+
+        assert!(result.is_err());
+        assert!(std::fs::symlink_metadata(&target).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_preserves_existing_target_when_overwrite_source_is_self_symlink() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        create_self_symlink(&source);
+        std::fs::create_dir(&target).expect("create target");
+        std::fs::write(target.join("keep.txt"), "keep").expect("write target file");
+
+        let result =
+            sync_dir_hybrid_with_overwrite(&SyncContext::default(), &source, &target, true);
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_to_string(target.join("keep.txt")).expect("read preserved target"),
+            "keep"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_rejects_broken_symlink_source_for_copy_without_removing_target() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let missing = temp.path().join("missing");
+        let target = temp.path().join("target");
+        std::os::unix::fs::symlink(&missing, &source).expect("create broken symlink");
+        std::fs::create_dir(&target).expect("create target");
+        std::fs::write(target.join("keep.txt"), "keep").expect("write target file");
+
+        let result = sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, true);
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_to_string(target.join("keep.txt")).expect("read preserved target"),
+            "keep"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_rejects_broken_symlink_target_without_replacing_it() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        let missing = temp.path().join("missing");
+        std::fs::create_dir(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: valid\n---\n")
+            .expect("write source file");
+        std::os::unix::fs::symlink(&missing, &target).expect("create broken target symlink");
+
+        let result = sync_dir_hybrid(&SyncContext::default(), &source, &target);
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_link(&target).expect("target remains symlink"),
+            missing
+        );
+        assert!(std::fs::metadata(&target).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_rejects_parent_symlink_target_resolving_to_source() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let central = temp.path().join("central");
+        let runtime_skills = temp.path().join("runtime-skills");
+        let source = central.join("drools-rule-dev");
+        let target = runtime_skills.join("drools-rule-dev");
+
+        std::fs::create_dir_all(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: drools-rule-dev\n---\n")
+            .expect("write source file");
+        std::os::unix::fs::symlink(&central, &runtime_skills)
+            .expect("link runtime skills to central repo");
+
+        let result =
+            sync_dir_hybrid_with_overwrite(&SyncContext::default(), &source, &target, true);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("same path"));
+        assert!(!source.is_symlink());
+        assert_eq!(
+            std::fs::read_to_string(source.join("SKILL.md")).expect("source survives"),
+            "---\nname: drools-rule-dev\n---\n"
+        );
+    }
+
+    #[test]
+    fn sync_engine_rejects_target_inside_source() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = source.join("nested-target");
+
+        std::fs::create_dir(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: valid\n---\n")
+            .expect("write source file");
+
+        let result = sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, true);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("inside source"));
+        assert!(!target.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_keeps_existing_direct_symlink_idempotent() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        std::fs::create_dir(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: valid\n---\n")
+            .expect("write source file");
+        std::os::unix::fs::symlink(&source, &target).expect("create target symlink");
+
+        let outcome =
+            sync_dir_hybrid_with_overwrite(&SyncContext::default(), &source, &target, true)
+                .expect("existing direct symlink is idempotent");
+
+        assert!(matches!(outcome.mode_used, SyncMode::Symlink));
+        assert!(!outcome.replaced);
+        assert_eq!(std::fs::read_link(&target).expect("target link"), source);
+    }
+
+    #[test]
+    fn sync_engine_matches_junction_targets_in_verbatim_form() {
+        let source = Path::new(r"C:\Users\me\.ai-toolbox\skills\demo");
+        assert!(same_reparse_target(
+            Path::new(r"\\?\C:\Users\me\.ai-toolbox\skills\demo\"),
+            source
+        ));
+        assert!(same_reparse_target(
+            Path::new(r"\??\c:\users\ME\.ai-toolbox\skills\demo"),
+            source
+        ));
+        assert!(same_reparse_target(
+            Path::new(r"\\?\UNC\server\share\skills"),
+            Path::new(r"\\server\share\skills")
+        ));
+        assert!(!same_reparse_target(
+            Path::new(r"\\?\C:\Users\me\.ai-toolbox\skills\other"),
+            source
+        ));
+    }
+
+    #[test]
+    fn sync_engine_dry_run_lists_changes_without_writing() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        std::fs::create_dir_all(source.join("scripts")).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "new").expect("write source file");
+        std::fs::write(source.join("scripts").join("run.sh"), "echo").expect("write script");
+        std::fs::create_dir(&target).expect("create target");
+        std::fs::write(target.join("SKILL.md"), "old").expect("write target file");
+        std::fs::write(target.join("stale.md"), "stale").expect("write stale file");
+        std::fs::write(target.join(MARKER_FILE), "{}").expect("write marker");
+
+        let err = sync_dir_dry_run(
+            &SyncContext::default(),
+            &source,
+            &target,
+            false,
+            SyncMode::Hardlink,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("target already exists"));
+
+        let plan = sync_dir_dry_run(
+            &SyncContext::default(),
+            &source,
+            &target,
+            true,
+            SyncMode::Hardlink,
+        )
+        .expect("dry run");
+        assert!(matches!(plan.outcome.mode_used, SyncMode::Hardlink));
+        assert!(plan.outcome.replaced);
+        assert_eq!(plan.created, vec![target.join("scripts").join("run.sh")]);
+        assert_eq!(plan.overwritten, vec![target.join("SKILL.md")]);
+        assert_eq!(plan.removed, vec![target.join("stale.md")]);
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).expect("target untouched"),
+            "old"
+        );
+        assert!(!target.join("scripts").exists());
+
+        let plan = sync_dir_dry_run_filtered(
+            &SyncContext::default(),
+            &source,
+            &target,
+            true,
+            SyncMode::Hardlink,
+            |path| path != "scripts/run.sh",
+        )
+        .expect("filtered dry run");
+        assert!(plan.created.is_empty());
+    }
+
+    #[test]
+    fn sync_engine_syncs_valid_source_dir() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        std::fs::create_dir(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: valid\n---\n")
+            .expect("write source file");
+
+        let outcome =
+            sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, false)
+                .expect("sync valid dir");
+
+        assert!(matches!(outcome.mode_used, SyncMode::Copy));
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).expect("read copied file"),
+            "---\nname: valid\n---\n"
+        );
+    }
+
+    #[test]
+    fn sync_engine_keeps_previous_copy_when_staging_fails() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let target = temp.path().join("target");
+        std::fs::create_dir(&target).expect("create target");
+        std::fs::write(target.join("SKILL.md"), "old").expect("write old file");
+
+        let result = stage_and_swap(&SyncContext::default(), &target, true, |staging| {
+            std::fs::create_dir(staging)?;
+            std::fs::write(staging.join("SKILL.md"), "half")?;
+            anyhow::bail!("disk full")
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).expect("read old file"),
+            "old"
+        );
+        let leftovers = std::fs::read_dir(temp.path()).expect("read temp").count();
+        assert_eq!(leftovers, 1, "staging dir should be cleaned up");
+
+        let source = temp.path().join("source");
+        std::fs::create_dir(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "new").expect("write source file");
+        let outcome = sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, true)
+            .expect("overwrite copy");
+
+        assert!(outcome.replaced);
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).expect("read new file"),
+            "new"
+        );
+        let leftovers = std::fs::read_dir(temp.path()).expect("read temp").count();
+        assert_eq!(leftovers, 2, "backup dir should be removed");
+    }
+
+    #[test]
+    fn sync_engine_hardlinks_files() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool/skill");
+        std::fs::create_dir_all(source.join("scripts")).expect("create source");
+        std::fs::create_dir(source.join(".git")).expect("create git dir");
+        std::fs::write(source.join("SKILL.md"), "v1").expect("write skill");
+        std::fs::write(source.join("scripts/run.sh"), "echo").expect("write script");
+
+        let outcome = sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            false,
+            SyncMode::Hardlink,
+        )
+        .expect("hard link sync");
+        assert!(matches!(outcome.mode_used, SyncMode::Hardlink));
+        assert!(target.is_dir() && !is_same_link(&target, &source));
+        assert!(target.join("scripts/run.sh").is_file());
+        assert!(!target.join(".git").exists());
+
+        // In-place edits of the central repo show up without a re-sync
+        std::fs::write(source.join("SKILL.md"), "v2").expect("edit skill");
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).expect("read target"),
+            "v2"
+        );
+
+        let outcome = sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            true,
+            SyncMode::Copy,
+        )
+        .expect("forced copy");
+        assert!(matches!(outcome.mode_used, SyncMode::Copy) && outcome.replaced);
+        std::fs::write(source.join("SKILL.md"), "v3").expect("edit skill");
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).expect("read target"),
+            "v2"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_forced_link_mode_never_falls_back_to_copy() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool/skill");
+        std::fs::create_dir_all(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "skill").expect("write skill");
+        std::fs::create_dir_all(&target).expect("create target");
+        std::fs::write(target.join("SKILL.md"), "old copy").expect("write old copy");
+
+        let err = sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            false,
+            SyncMode::Symlink,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("target already exists"));
+
+        let outcome = sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            true,
+            SyncMode::Symlink,
+        )
+        .expect("symlink sync");
+        assert!(outcome.mode_used == SyncMode::Symlink && outcome.replaced);
+        assert!(is_same_link(&target, &source));
+
+        let outcome = sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            false,
+            SyncMode::Symlink,
+        )
+        .expect("existing link");
+        assert!(!outcome.replaced);
+
+        // A junction cannot be created here; the link stays in place
+        assert!(sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            true,
+            SyncMode::Junction
+        )
+        .is_err());
+        assert!(is_same_link(&target, &source));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_engine_relative_symlink_survives_moving_the_whole_tree() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let root = temp.path().join("volume");
+        let source = root.join("central/skills/review");
+        let target = root.join("home/.claude/skills/review");
+        std::fs::create_dir_all(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "skill").expect("write skill");
+
+        let outcome = sync_dir_for_tool_with_link_style(
+            &SyncContext::default(),
+            &source,
+            &target,
+            false,
+            SyncMode::Symlink,
+            true,
+        )
+        .expect("relative symlink");
+        assert_eq!(outcome.mode_used, SyncMode::Symlink);
+        assert_eq!(
+            std::fs::read_link(&target).expect("read link"),
+            Path::new("../../../central/skills/review")
+        );
+        // Already linked, in whichever style
+        let outcome = sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            false,
+            SyncMode::Auto,
+        )
+        .expect("existing link");
+        assert!(!outcome.replaced);
+
+        let moved = temp.path().join("mounted-elsewhere");
+        std::fs::rename(&root, &moved).expect("move volume");
+        let moved_target = moved.join("home/.claude/skills/review");
+        assert_eq!(
+            std::fs::read_to_string(moved_target.join("SKILL.md")).expect("read through link"),
+            "skill"
+        );
+        assert_eq!(
+            resolve_link_target(&moved_target, Path::new("../../../central/skills/review")),
+            moved.join("central/skills/review")
+        );
+    }
+
+    #[test]
+    fn sync_engine_copy_leaves_out_ignored_entries() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool/skill");
+        std::fs::create_dir_all(source.join("scripts/node_modules/pkg")).expect("create source");
+        std::fs::create_dir_all(source.join("build")).expect("create build dir");
+        std::fs::write(source.join("SKILL.md"), "skill").expect("write skill");
+        std::fs::write(source.join("scripts/run.js"), "run").expect("write script");
+        std::fs::write(source.join("scripts/node_modules/pkg/index.js"), "x").expect("write dep");
+        std::fs::write(source.join("build/out.bin"), "x").expect("write build output");
+        std::fs::write(source.join(".skillignore"), "/build\n").expect("write ignore file");
+
+        let dry_run = sync_dir_dry_run(
+            &SyncContext::default(),
+            &source,
+            &target,
+            false,
+            SyncMode::Copy,
+        )
+        .expect("dry run");
+        assert_eq!(
+            dry_run.created,
+            vec![target.join("SKILL.md"), target.join("scripts/run.js")]
+        );
+
+        let outcome =
+            sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, false)
+                .expect("copy sync");
+        assert_eq!(outcome.verified, Some(true));
+        assert!(target.join("scripts/run.js").is_file());
+        assert!(!target.join("scripts/node_modules").exists());
+        assert!(!target.join("build").exists());
+        assert!(!target.join(".skillignore").exists());
+    }
+
+    #[test]
+    fn sync_engine_verify_copy_reports_mismatched_files() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool/skill");
+        std::fs::create_dir_all(source.join("scripts")).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "skill").expect("write skill");
+        std::fs::write(source.join("scripts/run.sh"), "echo").expect("write script");
+
+        let outcome =
+            sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, false)
+                .expect("copy sync");
+        assert_eq!(outcome.verified, Some(true));
+        assert!(outcome.mismatches.is_empty());
+
+        std::fs::write(target.join("SKILL.md"), "skill?").expect("corrupt skill");
+        std::fs::remove_file(target.join("scripts/run.sh")).expect("remove script");
+        std::fs::write(target.join("stray.txt"), "x").expect("write stray file");
+        let mismatches =
+            verify_copy(&SyncContext::default(), &source, &target).expect("verify copy");
+        let found: Vec<(&str, SyncMismatchKind)> = mismatches
+            .iter()
+            .map(|mismatch| (mismatch.path.as_str(), mismatch.kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("SKILL.md", SyncMismatchKind::Changed),
+                ("scripts/run.sh", SyncMismatchKind::Missing),
+                ("stray.txt", SyncMismatchKind::Extra),
+            ]
+        );
+
+        let outcome = sync_dir_for_tool_with_overwrite(
+            &SyncContext::default(),
+            &source,
+            &target,
+            true,
+            SyncMode::Hardlink,
+        )
+        .expect("hard link sync");
+        assert!(matches!(outcome.mode_used, SyncMode::Hardlink));
+        assert_eq!(outcome.verified, None);
+    }
+
+    #[test]
+    fn sync_engine_updates_large_managed_copy_incrementally() {
+        use super::super::target_marker::{write_marker, TargetMarker};
+
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool/skill");
+        std::fs::create_dir_all(source.join("docs")).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "v1").expect("write skill");
+        for index in 0..INCREMENTAL_MIN_FILES {
+            std::fs::write(source.join(format!("docs/page-{index}.md")), "page")
+                .expect("write page");
+        }
+        let outcome =
+            sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, false)
+                .expect("copy sync");
+        assert_eq!(outcome.file_changes, None);
+        let marker = TargetMarker {
+            skill_id: "skill-1".to_string(),
+            skill_name: "skill".to_string(),
+            tool: "codex".to_string(),
+            mode: "copy".to_string(),
+            synced_at: None,
+        };
+        write_marker(&target, &marker).expect("write marker");
+
+        std::fs::write(source.join("SKILL.md"), "v2 with more text").expect("edit skill");
+        std::fs::remove_file(source.join("docs/page-0.md")).expect("remove page");
+        std::fs::write(source.join("docs/new.md"), "new").expect("add page");
+        std::fs::create_dir_all(target.join("stale/nested")).expect("create stale dir");
+        std::fs::write(target.join("stale/nested/old.md"), "old").expect("write stale file");
+
+        let trash = temp.path().join("trash");
+        let ctx = SyncContext {
+            trash_dir: Some(trash.clone()),
+            ..SyncContext::default()
+        };
+        let outcome =
+            sync_dir_copy_with_overwrite(&ctx, &source, &target, true).expect("update copy");
+        assert!(outcome.replaced);
+        assert_eq!(outcome.verified, Some(true));
+        assert_eq!(
+            outcome.file_changes,
+            Some(SyncFileChanges {
+                copied: 2,
+                deleted: 2,
+                unchanged: INCREMENTAL_MIN_FILES - 1,
+            })
+        );
+        assert!(!target.join("stale").exists());
+        assert!(!target.join("docs/page-0.md").exists());
+        assert_eq!(read_marker(&target), Some(marker));
+
+        // The previous copy was swapped out whole and can be rolled back
+        let entries = crate::skills::sync_journal::list_entries(&trash, Some(&target));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].op, JournalOp::Overwrite);
+        let previous = trash.join(&entries[0].id).join("content");
+        assert_eq!(
+            std::fs::read_to_string(previous.join("SKILL.md")).expect("read old skill"),
+            "v1"
+        );
+        assert!(previous.join("stale/nested/old.md").is_file());
+
+        let outcome = sync_dir_copy_with_overwrite(&SyncContext::default(), &source, &target, true)
+            .expect("resync copy");
+        assert_eq!(
+            outcome.file_changes.map(|changes| changes.unchanged),
+            Some(INCREMENTAL_MIN_FILES + 1)
+        );
+    }
+
+    #[test]
+    fn copy_sync_uses_the_context_trash_and_ignore_patterns() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        std::fs::create_dir_all(source.join("build")).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "new").expect("write skill");
+        std::fs::write(source.join("build/out.js"), "x").expect("write build output");
+        std::fs::create_dir_all(&target).expect("create target");
+        std::fs::write(target.join("SKILL.md"), "old").expect("write target");
+
+        let trash = temp.path().join("trash");
+        let ctx = SyncContext {
+            relative_symlinks: false,
+            trash_dir: Some(trash.clone()),
+            ignore_patterns: vec!["build/".to_string()],
+        };
+        let outcome =
+            sync_dir_copy_with_overwrite(&ctx, &source, &target, true).expect("copy sync");
+        assert_eq!(outcome.verified, Some(true));
+        assert!(!target.join("build").exists());
+
+        let entries = crate::skills::sync_journal::list_entries(&trash, Some(&target));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].op, JournalOp::Overwrite);
+    }
+}
@@ -0,0 +1,404 @@
+//! Undo for syncs that replace or remove a tool target
+//!
+//! What a sync would delete is moved into `{app_data_dir}/trash/{entry id}/`
+//! instead, and a line is appended to `trash/journal.jsonl`: targets replaced
+//! by a new link or copy (`sync_engine`, `path_executor::convert_target_mode`)
+//! and removed targets (`path_executor::remove_skill_target_checked`).
+//! `rollback_last_sync` puts the newest entry of a target back; what it
+//! displaces is journaled in turn.
+//!
+//! Entries are purged after `RETENTION_DAYS`, beyond the newest
+//! `MAX_ENTRIES_PER_TARGET` of a target, and oldest first once the trash
+//! holds more than `MAX_TOTAL_BYTES`; the limits are applied after every
+//! write and at startup. `purge_all` empties the trash on request.
+//!
+//! The trash directory is passed in by the caller (`SyncContext::trash_dir`
+//! for syncs); without one nothing is kept and replaced targets are deleted
+//! as before.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::types::now_ms;
+
+const JOURNAL_FILE: &str = "journal.jsonl";
+const CONTENT_DIR: &str = "content";
+const RETENTION_DAYS: i64 = 30;
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const MAX_ENTRIES_PER_TARGET: usize = 10;
+/// The newest entry is kept even when it alone is larger
+const MAX_TOTAL_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Targets are synced in parallel; journal rewrites must not interleave
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+static ENTRY_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalOp {
+    /// The target was replaced by a new link or copy
+    Overwrite,
+    /// The target was removed
+    Remove,
+    /// What a rollback displaced
+    Rollback,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyncJournalEntry {
+    pub id: String,
+    pub target: String,
+    pub op: JournalOp,
+    pub created_at: i64,
+    #[serde(default)]
+    pub restored_at: Option<i64>,
+    /// Bytes the entry holds in the trash
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SyncTrashPurgeSummary {
+    pub removed: usize,
+    pub freed_bytes: u64,
+}
+
+/// Move `path` into `trash` as the previous content of `target`. Returns
+/// `false` without touching `path` when there is no trash.
+pub fn keep_replaced(
+    trash: Option<&Path>,
+    path: &Path,
+    target: &Path,
+    op: JournalOp,
+) -> Result<bool> {
+    let Some(trash) = trash else {
+        return Ok(false);
+    };
+    keep_in(trash, path, target, op)?;
+    Ok(true)
+}
+
+/// Journal entries, newest first; `target` limits them to one target
+pub fn list_entries(trash: &Path, target: Option<&Path>) -> Vec<SyncJournalEntry> {
+    let mut entries: Vec<SyncJournalEntry> = read_journal(trash)
+        .into_iter()
+        .filter(|entry| target.is_none_or(|target| Path::new(&entry.target) == target))
+        .collect();
+    entries.reverse();
+    entries
+}
+
+/// Restore what the most recent sync replaced or removed at `target`
+pub fn rollback_last_sync(trash: &Path, target: &Path) -> Result<SyncJournalEntry> {
+    rollback_last_in(trash, target)
+}
+
+/// Drop entries past the retention and size limits along with their content
+pub fn purge(trash: &Path) {
+    enforce_limits(trash);
+}
+
+/// Drop every entry; nothing can be rolled back afterwards
+pub fn purge_all(trash: &Path) -> Result<SyncTrashPurgeSummary> {
+    purge_in(trash, now_ms(), true)
+}
+
+fn new_entry(target: &Path, op: JournalOp) -> SyncJournalEntry {
+    let created_at = now_ms();
+    SyncJournalEntry {
+        id: format!(
+            "{}-{}-{}",
+            created_at,
+            std::process::id(),
+            ENTRY_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ),
+        target: target.to_string_lossy().to_string(),
+        op,
+        created_at,
+        restored_at: None,
+        size: 0,
+    }
+}
+
+fn keep_in(trash: &Path, path: &Path, target: &Path, op: JournalOp) -> Result<SyncJournalEntry> {
+    let mut entry = new_entry(target, op);
+    move_path(path, &trash.join(&entry.id).join(CONTENT_DIR))?;
+    entry.size = dir_size(&trash.join(&entry.id));
+    append(trash, &entry)?;
+    enforce_limits(trash);
+    Ok(entry)
+}
+
+fn rollback_last_in(trash: &Path, target: &Path) -> Result<SyncJournalEntry> {
+    let entry = read_journal(trash)
+        .into_iter()
+        .rev()
+        .find(|entry| {
+            entry.restored_at.is_none()
+                && entry.op != JournalOp::Rollback
+                && Path::new(&entry.target) == target
+        })
+        .with_context(|| format!("no sync to roll back for {:?}", target))?;
+    let content = trash.join(&entry.id).join(CONTENT_DIR);
+
+    if std::fs::symlink_metadata(&content).is_err() {
+        anyhow::bail!("the trash no longer holds {:?}", content);
+    }
+    if std::fs::symlink_metadata(target).is_ok() {
+        keep_in(trash, target, target, JournalOp::Rollback)?;
+    }
+    move_path(&content, target)?;
+
+    let restored_at = now_ms();
+    update_journal(trash, |entries| {
+        if let Some(restored) = entries.iter_mut().find(|e| e.id == entry.id) {
+            restored.restored_at = Some(restored_at);
+        }
+    })?;
+    let _ = std::fs::remove_dir_all(trash.join(&entry.id));
+    Ok(SyncJournalEntry {
+        restored_at: Some(restored_at),
+        ..entry
+    })
+}
+
+fn enforce_limits(trash: &Path) {
+    if let Err(err) = purge_in(trash, now_ms(), false) {
+        log::warn!("Failed to purge the sync trash: {:#}", err);
+    }
+}
+
+/// Drop expired entries, those beyond `MAX_ENTRIES_PER_TARGET` and, oldest
+/// first, those over `MAX_TOTAL_BYTES`; with `everything`, all of them
+fn purge_in(trash: &Path, now: i64, everything: bool) -> Result<SyncTrashPurgeSummary> {
+    let cutoff = now - RETENTION_DAYS * DAY_MS;
+    let mut dropped = Vec::new();
+    update_journal(trash, |entries| {
+        let mut keep = vec![false; entries.len()];
+        let mut per_target: HashMap<&str, usize> = HashMap::new();
+        let mut total = 0u64;
+        for (index, entry) in entries.iter().enumerate().rev() {
+            let count = per_target.entry(entry.target.as_str()).or_default();
+            *count += 1;
+            let size = entry_size(trash, entry);
+            let newest = index + 1 == entries.len();
+            keep[index] = !everything
+                && entry.created_at >= cutoff
+                && *count <= MAX_ENTRIES_PER_TARGET
+                && (newest || total + size <= MAX_TOTAL_BYTES);
+            if keep[index] {
+                total += size;
+            } else {
+                dropped.push((entry.id.clone(), size));
+            }
+        }
+        let mut index = 0;
+        entries.retain(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+    })?;
+
+    let mut summary = SyncTrashPurgeSummary::default();
+    for (id, size) in dropped {
+        let dir = trash.join(&id);
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).with_context(|| format!("remove {:?}", dir))?;
+        }
+        summary.removed += 1;
+        summary.freed_bytes += size;
+    }
+    Ok(summary)
+}
+
+/// Entries journaled before sizes were recorded are measured on disk
+fn entry_size(trash: &Path, entry: &SyncJournalEntry) -> u64 {
+    if entry.size > 0 || entry.restored_at.is_some() {
+        entry.size
+    } else {
+        dir_size(&trash.join(&entry.id))
+    }
+}
+
+fn dir_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|item| item.ok())
+        .filter_map(|item| item.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn read_journal(trash: &Path) -> Vec<SyncJournalEntry> {
+    std::fs::read_to_string(trash.join(JOURNAL_FILE))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn append(trash: &Path, entry: &SyncJournalEntry) -> Result<()> {
+    update_journal(trash, |entries| entries.push(entry.clone()))
+}
+
+fn update_journal(trash: &Path, update: impl FnOnce(&mut Vec<SyncJournalEntry>)) -> Result<()> {
+    let _guard = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = read_journal(trash);
+    update(&mut entries);
+    std::fs::create_dir_all(trash).with_context(|| format!("create {:?}", trash))?;
+    let mut text = String::new();
+    for entry in &entries {
+        text.push_str(&serde_json::to_string(entry)?);
+        text.push('\n');
+    }
+    let path = trash.join(JOURNAL_FILE);
+    let tmp = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp, text).with_context(|| format!("write {:?}", tmp))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("replace {:?}", path))?;
+    Ok(())
+}
+
+/// Rename `from` to `to`, copying and deleting when they are on different
+/// filesystems
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_any(from, to).with_context(|| format!("copy {:?} -> {:?}", from, to))?;
+    remove_any(from)
+}
+
+fn copy_any(from: &Path, to: &Path) -> Result<()> {
+    for item in walkdir::WalkDir::new(from).follow_links(false) {
+        let item = item?;
+        let dest = to.join(item.path().strip_prefix(from)?);
+        let file_type = item.file_type();
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&dest)?;
+        } else if file_type.is_symlink() {
+            copy_link(item.path(), &dest)?;
+        } else {
+            std::fs::copy(item.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_link(link: &Path, dest: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(link)?, dest)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_link(link: &Path, _dest: &Path) -> Result<()> {
+    anyhow::bail!("cannot move link {:?} to another volume", link)
+}
+
+fn remove_any(path: &Path) -> Result<()> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path).or_else(|_| std::fs::remove_dir(path))
+    }
+    .with_context(|| format!("remove {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, text: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, text).unwrap();
+    }
+
+    #[test]
+    fn rollback_restores_the_replaced_target_and_keeps_the_new_one() {
+        let temp = tempfile::tempdir().unwrap();
+        let trash = temp.path().join("trash");
+        let target = temp.path().join("tool/skills/review");
+        write(&target.join("SKILL.md"), "hand edited");
+
+        // A sync moves the old target aside and puts a new copy in place
+        let aside = temp.path().join("tool/skills/.backup-review");
+        std::fs::rename(&target, &aside).unwrap();
+        keep_in(&trash, &aside, &target, JournalOp::Overwrite).unwrap();
+        write(&target.join("SKILL.md"), "from central");
+
+        let entry = rollback_last_in(&trash, &target).unwrap();
+        assert_eq!(entry.op, JournalOp::Overwrite);
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "hand edited"
+        );
+
+        // The displaced copy is journaled but not picked by the next rollback
+        let entries = read_journal(&trash);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].op, JournalOp::Rollback);
+        assert!(rollback_last_in(&trash, &target).is_err());
+    }
+
+    #[test]
+    fn purge_drops_entries_past_retention() {
+        let temp = tempfile::tempdir().unwrap();
+        let trash = temp.path().join("trash");
+        let target = temp.path().join("review");
+        write(&target.join("SKILL.md"), "old");
+        let entry = keep_in(&trash, &target, &target, JournalOp::Remove).unwrap();
+
+        purge_in(
+            &trash,
+            entry.created_at + RETENTION_DAYS * DAY_MS + 1,
+            false,
+        )
+        .unwrap();
+        assert!(read_journal(&trash).is_empty());
+        assert!(!trash.join(&entry.id).exists());
+    }
+
+    #[test]
+    fn each_write_keeps_only_the_newest_entries_of_a_target() {
+        let temp = tempfile::tempdir().unwrap();
+        let trash = temp.path().join("trash");
+        let target = temp.path().join("review");
+        let other = temp.path().join("other");
+        write(&other.join("SKILL.md"), "other");
+        let other_entry = keep_in(&trash, &other, &other, JournalOp::Remove).unwrap();
+
+        let mut ids = Vec::new();
+        for round in 0..MAX_ENTRIES_PER_TARGET + 2 {
+            write(&target.join("SKILL.md"), &format!("round {round}"));
+            ids.push(
+                keep_in(&trash, &target, &target, JournalOp::Remove)
+                    .unwrap()
+                    .id,
+            );
+        }
+
+        let entries = read_journal(&trash);
+        assert_eq!(entries.len(), MAX_ENTRIES_PER_TARGET + 1);
+        assert_eq!(entries[0].id, other_entry.id);
+        assert_eq!(entries[0].size, "other".len() as u64);
+        assert!(!trash.join(&ids[0]).exists());
+        assert!(!trash.join(&ids[1]).exists());
+        assert!(trash.join(&ids[2]).exists());
+
+        let summary = purge_in(&trash, now_ms(), true).unwrap();
+        assert_eq!(summary.removed, MAX_ENTRIES_PER_TARGET + 1);
+        assert!(read_journal(&trash).is_empty());
+        assert!(!trash.join(&other_entry.id).exists());
+    }
+}
@@ -0,0 +1,252 @@
+//! Per-file report of an existing target a sync would replace
+//!
+//! A sync without overwrite stops at an existing target (`target already
+//! exists`). `target_conflict` tells the host what overwriting it changes:
+//! the files the sync adds, the files whose content differs and the files
+//! only the target has, each with the SHA-256 of both sides, so the user
+//! sees the diff before opting into overwrite. A target that already holds
+//! the sync's result (a link to the source, a copy with the same files) is
+//! no conflict. Only the files a copy receives are compared: the ignore
+//! patterns and the target's path rules (`keep`) apply as in a sync.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::content_hash::file_hashes;
+use super::sync_engine::{resolve_link_target, SyncContext};
+use super::target_marker::MARKER_FILE;
+
+/// What is in the way at the target path
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetConflictKind {
+    /// A directory whose files differ from the source
+    Directory,
+    /// A symlink or junction to somewhere other than the source
+    Link,
+    /// A file where the skill directory would go
+    File,
+}
+
+/// What overwriting does to one file, relative to the skill root
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictFileStatus {
+    /// Only in the source; the sync adds it
+    Added,
+    /// In both with different content; the sync replaces it
+    Modified,
+    /// Only in the target; the sync deletes it
+    Deleted,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TargetConflictFile {
+    /// Path relative to the skill root, with `/` separators
+    pub path: String,
+    pub status: ConflictFileStatus,
+    pub source_hash: Option<String>,
+    pub target_hash: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TargetConflictReport {
+    pub target_path: String,
+    pub kind: TargetConflictKind,
+    /// Where a `Link` target points
+    pub link_target: Option<String>,
+    /// Sorted by path; compared through the link for `Link` targets
+    pub files: Vec<TargetConflictFile>,
+}
+
+/// What syncing `source` over the existing `target` would change, or `None`
+/// when there is nothing at `target` or it already matches `source`.
+/// `keep` gets paths relative to `source` with `/` separators.
+pub fn target_conflict(
+    ctx: &SyncContext,
+    source: &Path,
+    target: &Path,
+    keep: impl Fn(&str) -> bool,
+) -> Result<Option<TargetConflictReport>> {
+    let Ok(meta) = std::fs::symlink_metadata(target) else {
+        return Ok(None);
+    };
+
+    let (kind, link_target, content) = match std::fs::read_link(target) {
+        Ok(stored) => {
+            let linked = resolve_link_target(target, &stored);
+            if same_dir(&linked, source) {
+                return Ok(None);
+            }
+            let content = linked.is_dir().then(|| linked.clone());
+            (
+                TargetConflictKind::Link,
+                Some(linked.to_string_lossy().to_string()),
+                content,
+            )
+        }
+        Err(_) if meta.is_dir() => (TargetConflictKind::Directory, None, Some(target.into())),
+        Err(_) => (TargetConflictKind::File, None, None),
+    };
+
+    let ignore = ctx.ignore_for(source);
+    let mut expected = file_hashes(source)?;
+    expected.retain(|relative, _| !ignore.is_ignored(relative, false) && keep(relative));
+    let mut actual = match content {
+        Some(dir) => file_hashes(&dir)?,
+        None => BTreeMap::new(),
+    };
+    actual.remove(MARKER_FILE);
+
+    let files = diff_hashes(&expected, &actual);
+    if kind == TargetConflictKind::Directory && files.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(TargetConflictReport {
+        target_path: target.to_string_lossy().to_string(),
+        kind,
+        link_target,
+        files,
+    }))
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn diff_hashes(
+    expected: &BTreeMap<String, String>,
+    actual: &BTreeMap<String, String>,
+) -> Vec<TargetConflictFile> {
+    let mut files: Vec<TargetConflictFile> = expected
+        .iter()
+        .filter_map(|(path, hash)| {
+            let status = match actual.get(path) {
+                None => ConflictFileStatus::Added,
+                Some(existing) if existing != hash => ConflictFileStatus::Modified,
+                Some(_) => return None,
+            };
+            Some(TargetConflictFile {
+                path: path.clone(),
+                status,
+                source_hash: Some(hash.clone()),
+                target_hash: actual.get(path).cloned(),
+            })
+        })
+        .collect();
+    files.extend(
+        actual
+            .iter()
+            .filter(|(path, _)| !expected.contains_key(*path))
+            .map(|(path, hash)| TargetConflictFile {
+                path: path.clone(),
+                status: ConflictFileStatus::Deleted,
+                source_hash: None,
+                target_hash: Some(hash.clone()),
+            }),
+    );
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn reports_each_file_an_overwrite_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("central/review");
+        let target = temp.path().join("tool/review");
+        write(&source, "SKILL.md", "# Review\n");
+        write(&source, "notes.md", "new notes\n");
+        write(&source, "scripts/run.sh", "echo run\n");
+        write(&target, "SKILL.md", "# Review\n");
+        write(&target, "notes.md", "old notes\n");
+        write(&target, "local.md", "edited in the tool\n");
+        write(&target, MARKER_FILE, "{}");
+
+        let report = target_conflict(&SyncContext::default(), &source, &target, |_| true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.kind, TargetConflictKind::Directory);
+        let files: Vec<(&str, ConflictFileStatus)> = report
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.status))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("local.md", ConflictFileStatus::Deleted),
+                ("notes.md", ConflictFileStatus::Modified),
+                ("scripts/run.sh", ConflictFileStatus::Added),
+            ]
+        );
+        let modified = &report.files[1];
+        assert!(modified.source_hash.is_some() && modified.target_hash.is_some());
+        assert_ne!(modified.source_hash, modified.target_hash);
+
+        // Files outside the path rules are not expected in the target
+        let kept = target_conflict(&SyncContext::default(), &source, &target, |path| {
+            !path.starts_with("scripts/")
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(kept.files.len(), 2);
+    }
+
+    #[test]
+    fn matching_targets_are_no_conflict() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("central/review");
+        let copy = temp.path().join("tool/review");
+        write(&source, "SKILL.md", "# Review\n");
+        write(&copy, "SKILL.md", "# Review\n");
+
+        assert!(
+            target_conflict(&SyncContext::default(), &source, &copy, |_| true)
+                .unwrap()
+                .is_none()
+        );
+        assert!(target_conflict(
+            &SyncContext::default(),
+            &source,
+            &temp.path().join("tool/missing"),
+            |_| true
+        )
+        .unwrap()
+        .is_none());
+
+        #[cfg(unix)]
+        {
+            let link = temp.path().join("tool/linked");
+            std::os::unix::fs::symlink(&source, &link).unwrap();
+            assert!(
+                target_conflict(&SyncContext::default(), &source, &link, |_| true)
+                    .unwrap()
+                    .is_none()
+            );
+
+            let elsewhere = temp.path().join("tool/elsewhere");
+            std::os::unix::fs::symlink(&copy, &elsewhere).unwrap();
+            let report = target_conflict(&SyncContext::default(), &source, &elsewhere, |_| true)
+                .unwrap()
+                .unwrap();
+            assert_eq!(report.kind, TargetConflictKind::Link);
+            assert!(report.files.is_empty());
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! "Managed by AI Toolbox" marker files in copy targets
+//!
+//! Copy-mode syncs write `.ai-toolbox.meta` (`{ skill_id, skill_name, tool,
+//! mode, synced_at }`) into the target, so a managed copy can be recognized
+//! without the app's database. The sync engine leaves the marker out of
+//! content hashes and copies.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const MARKER_FILE: &str = ".ai-toolbox.meta";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetMarker {
+    pub skill_id: String,
+    pub skill_name: String,
+    pub tool: String,
+    pub mode: String,
+    pub synced_at: Option<i64>,
+}
+
+pub fn read_marker(target: &Path) -> Option<TargetMarker> {
+    let raw = std::fs::read(target.join(MARKER_FILE)).ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+pub fn write_marker(target: &Path, marker: &TargetMarker) -> Result<()> {
+    let path = target.join(MARKER_FILE);
+    let json = serde_json::to_vec_pretty(marker)?;
+    std::fs::write(&path, json).with_context(|| format!("write {:?}", path))
+}
@@ -0,0 +1,229 @@
+//! Skill records and sync types
+//!
+//! `Skill` and `SkillTarget` are the records the app stores in SQLite (as
+//! JSON); the `Sync*` types describe one run of the sync engine.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Skill record stored in SQLite JSONB
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Skill {
+    pub id: String,
+    pub name: String,
+    pub source_type: String, // "local" | "linked_folder" | "git" | "import" | "central"
+    pub source_ref: Option<String>,
+    pub source_revision: Option<String>,
+    pub central_path: String,
+    pub content_hash: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_sync_at: Option<i64>,
+    pub status: String,
+
+    // Sort order for drag-and-drop reordering
+    pub sort_index: i32,
+
+    // User-managed local metadata for organization inside AI Toolbox.
+    pub user_group: Option<String>,
+    pub group_id: Option<String>,
+    pub user_note: Option<String>,
+    pub management_enabled: bool,
+    pub disabled_previous_tools: Vec<String>,
+
+    // Enabled tool keys list
+    pub enabled_tools: Vec<String>, // ["claude_code", "codex", "opencode"]
+
+    // Sync details JSON (per-tool target_path/mode/status etc.)
+    // Structure: { "claude_code": { "target_path": "...", "mode": "...", ... }, ... }
+    pub sync_details: Option<Value>,
+
+    // SPDX id (or frontmatter value) detected at install/update time
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub provenance: Option<SkillProvenance>,
+    /// Free-form labels, independent of the skill group
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Tool version constraints set by the user, by tool key; each overrides
+    /// the SKILL.md `compatibility` entry of the same tool
+    #[serde(default)]
+    pub compatibility: BTreeMap<String, String>,
+    /// Display names and descriptions by UI language (`zh-CN`, `en-US`);
+    /// the directory name in `name` stays the sync identity
+    #[serde(default)]
+    pub localizations: BTreeMap<String, SkillLocalization>,
+    /// Keep the central directory read-only; see `write_protect`
+    #[serde(default)]
+    pub write_protected: bool,
+}
+
+/// Localized text shown instead of the skill name and SKILL.md description
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillLocalization {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Where a skill's content came from, recorded at install / update time
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SkillProvenance {
+    /// Upstream URL or local source path
+    pub origin: Option<String>,
+    /// Actual fetch URL when it differs from `origin` (e.g. a mirror)
+    pub fetched_from: Option<String>,
+    pub revision: Option<String>,
+    /// `author` from the SKILL.md frontmatter
+    pub author: Option<String>,
+    pub recorded_at: i64,
+}
+
+/// Skill target info - used within sync_details (no longer a separate table)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillTarget {
+    pub tool: String,
+    pub target_path: String,
+    pub mode: String, // "symlink" | "copy" | "junction" | "hardlink"
+    pub status: String,
+    pub synced_at: Option<i64>,
+    pub error_message: Option<String>,
+    /// Subpaths this target receives; a target with rules is always a copy
+    #[serde(default, skip_serializing_if = "SkillPathRules::is_empty")]
+    pub path_rules: SkillPathRules,
+}
+
+/// Include/exclude rules of one target (see `path_rules.rs`)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillPathRules {
+    /// Kept subpaths; empty keeps everything
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Dropped subpaths, applied after `include`
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl SkillPathRules {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+}
+
+/// Sync mode used for skill syncing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncMode {
+    Auto,
+    Symlink,
+    Junction,
+    Hardlink,
+    Copy,
+}
+
+impl SyncMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncMode::Auto => "auto",
+            SyncMode::Symlink => "symlink",
+            SyncMode::Junction => "junction",
+            SyncMode::Hardlink => "hardlink",
+            SyncMode::Copy => "copy",
+        }
+    }
+
+    /// Parse a mode name as stored in tool preferences and sync records
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(SyncMode::Auto),
+            "symlink" => Some(SyncMode::Symlink),
+            "junction" => Some(SyncMode::Junction),
+            "hardlink" => Some(SyncMode::Hardlink),
+            "copy" => Some(SyncMode::Copy),
+            _ => None,
+        }
+    }
+
+    /// Whether a target synced this way holds its own files (a copy or hard
+    /// links) instead of linking the whole directory
+    pub fn holds_own_files(&self) -> bool {
+        matches!(self, SyncMode::Copy | SyncMode::Hardlink)
+    }
+}
+
+/// Sync outcome from sync operations
+#[derive(Clone, Debug)]
+pub struct SyncOutcome {
+    pub mode_used: SyncMode,
+    pub target_path: std::path::PathBuf,
+    pub replaced: bool,
+    /// Whether a copy made by this sync matched its source file by file;
+    /// `None` for links, hard links and copies that could not be checked
+    pub verified: Option<bool>,
+    /// Files of the copy that differ from the source
+    pub mismatches: Vec<SyncFileMismatch>,
+    /// Set when an existing copy was updated in place instead of replaced
+    pub file_changes: Option<SyncFileChanges>,
+}
+
+/// Per-file counts of a copy updated in place
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SyncFileChanges {
+    /// New files and files whose content changed
+    pub copied: usize,
+    /// Files no longer in the source
+    pub deleted: usize,
+    pub unchanged: usize,
+}
+
+/// How a copied file differs from the source
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMismatchKind {
+    /// In the source but not in the copy
+    Missing,
+    /// Content differs from the source
+    Changed,
+    /// In the copy but not in the source
+    Extra,
+}
+
+/// A file of a copied target that does not match the source
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SyncFileMismatch {
+    /// Path relative to the skill root, with `/` separators
+    pub path: String,
+    pub kind: SyncMismatchKind,
+}
+
+/// Planned sync from `sync_engine::sync_dir_dry_run`; paths are absolute
+#[derive(Clone, Debug)]
+pub struct SyncDryRun {
+    pub outcome: SyncOutcome,
+    pub created: Vec<std::path::PathBuf>,
+    pub overwritten: Vec<std::path::PathBuf>,
+    pub removed: Vec<std::path::PathBuf>,
+}
+
+/// One target of `path_executor::sync_to_targets`
+#[derive(Clone, Debug)]
+pub struct SyncTargetSpec {
+    pub tool_key: String,
+    pub target: std::path::PathBuf,
+    /// Synced from here instead of the shared source, e.g. a variant snapshot
+    pub source: Option<std::path::PathBuf>,
+    pub overwrite: bool,
+    pub mode: SyncMode,
+    pub rules: SkillPathRules,
+}
+
+/// Helper function to get current timestamp in milliseconds
+pub fn now_ms() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_millis() as i64
+}
@@ -0,0 +1,27 @@
+//! Unicode normalization of skill names and path keys
+//!
+//! macOS hands out file names in decomposed form (NFD) while SKILL.md files,
+//! git and other platforms mostly produce NFC, so one `café` skill can show up
+//! as two names. Skill names are stored and compared in NFC. Paths on disk are
+//! left untouched — Linux file systems treat the two forms as different files —
+//! only the keys used to compare paths are normalized.
+
+use unicode_normalization::UnicodeNormalization;
+
+/// `name` in NFC
+pub fn nfc_name(name: &str) -> String {
+    name.nfc().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decomposed_and_composed_names_normalize_equal() {
+        let decomposed = "cafe\u{301}";
+        assert_ne!(decomposed, "caf\u{e9}");
+        assert_eq!(nfc_name(decomposed), "caf\u{e9}");
+        assert_eq!(nfc_name("plain-skill"), "plain-skill");
+    }
+}
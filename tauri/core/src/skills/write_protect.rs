@@ -0,0 +1,134 @@
+//! Write protection of central repo content
+//!
+//! A tool that reaches a skill through a symlink can edit the central repo
+//! copy without noticing, e.g. an agent rewriting its own skill. With
+//! `Skill::write_protected` the skill's central directory is made read-only:
+//! files and directories lose their write bits on Unix, files get the
+//! read-only attribute on Windows (which ignores it on directories). The
+//! app's own writes (install with overwrite, update, delete) call `unlock`
+//! first and `protect` again afterwards.
+//!
+//! Copies made from a protected directory would inherit its permissions;
+//! `copy_file` gives them back write access so tool copies stay editable and
+//! can be refreshed in place. Hard links share the file's permissions and
+//! stay read-only.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Make every file and directory under `dir` read-only, or writable again
+pub fn set_read_only(dir: &Path, read_only: bool) -> Result<()> {
+    let entries: Vec<walkdir::DirEntry> = walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .collect::<std::result::Result<_, _>>()?;
+    for entry in entries {
+        let file_type = entry.file_type();
+        if file_type.is_symlink() || (cfg!(windows) && file_type.is_dir()) {
+            continue;
+        }
+        let path = entry.path();
+        let mut permissions = std::fs::symlink_metadata(path)
+            .with_context(|| format!("stat {:?}", path))?
+            .permissions();
+        if !set_writable_bits(&mut permissions, !read_only) {
+            continue;
+        }
+        std::fs::set_permissions(path, permissions)
+            .with_context(|| format!("change permissions of {:?}", path))?;
+    }
+    Ok(())
+}
+
+/// Protect `dir` when the skill asks for it; a missing directory is skipped
+pub fn protect(dir: &Path, write_protected: bool) -> Result<()> {
+    if !write_protected || !dir.exists() {
+        return Ok(());
+    }
+    set_read_only(dir, true)
+}
+
+/// Give a protected `dir` its write access back before the app writes to it
+pub fn unlock(dir: &Path, write_protected: bool) -> Result<()> {
+    if !write_protected || !dir.exists() {
+        return Ok(());
+    }
+    set_read_only(dir, false)
+}
+
+/// Like `protect`, for calls where the content is already written and a
+/// failure should not undo the operation
+pub fn protect_or_warn(dir: &Path, write_protected: bool) {
+    if let Err(err) = protect(dir, write_protected) {
+        log::warn!("Failed to write-protect {:?}: {:#}", dir, err);
+    }
+}
+
+/// `std::fs::copy`, leaving the copy writable when the source is read-only
+pub(crate) fn copy_file(from: &Path, to: &Path) -> std::io::Result<u64> {
+    let copied = std::fs::copy(from, to)?;
+    let mut permissions = std::fs::metadata(to)?.permissions();
+    if set_writable_bits(&mut permissions, true) {
+        std::fs::set_permissions(to, permissions)?;
+    }
+    Ok(copied)
+}
+
+/// Owner write access on Unix, the read-only attribute elsewhere. Returns
+/// whether `permissions` changed.
+#[cfg(unix)]
+fn set_writable_bits(permissions: &mut std::fs::Permissions, writable: bool) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = permissions.mode();
+    let next = if writable {
+        mode | 0o200
+    } else {
+        mode & !0o222
+    };
+    permissions.set_mode(next);
+    next != mode
+}
+
+#[cfg(not(unix))]
+fn set_writable_bits(permissions: &mut std::fs::Permissions, writable: bool) -> bool {
+    if permissions.readonly() != writable {
+        return false;
+    }
+    permissions.set_readonly(!writable);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_only(path: &Path) -> bool {
+        std::fs::metadata(path).unwrap().permissions().readonly()
+    }
+
+    #[test]
+    fn protects_the_tree_and_copies_stay_writable() {
+        let temp = tempfile::tempdir().unwrap();
+        let skill = temp.path().join("review");
+        std::fs::create_dir_all(skill.join("scripts")).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# Review").unwrap();
+        std::fs::write(skill.join("scripts/run.sh"), "echo").unwrap();
+
+        protect(&skill, true).unwrap();
+        assert!(read_only(&skill.join("SKILL.md")));
+        assert!(read_only(&skill.join("scripts/run.sh")));
+        #[cfg(unix)]
+        assert!(read_only(&skill.join("scripts")));
+
+        let copy = temp.path().join("SKILL.md");
+        copy_file(&skill.join("SKILL.md"), &copy).unwrap();
+        assert!(!read_only(&copy));
+
+        unlock(&skill, true).unwrap();
+        assert!(!read_only(&skill.join("SKILL.md")));
+        assert!(!read_only(&skill.join("scripts")));
+        std::fs::remove_dir_all(&skill).unwrap();
+    }
+}
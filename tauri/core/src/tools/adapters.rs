@@ -0,0 +1,198 @@
+//! Adapters of the built-in tools
+//!
+//! Each built-in tool is a [`ToolAdapter`] built from its `BUILTIN_TOOLS`
+//! entry. Tools that load, detect or locate skills like any other use
+//! `StandardTool`; a tool with quirks gets its own type here, so the quirk
+//! lives next to the tool instead of in `if key == ...` checks across the
+//! app. `ADAPTER_CONSTRUCTORS` maps those tools to their type.
+
+use super::builtin::{builtin_tool_by_key, BUILTIN_TOOLS};
+use super::load_rules::{ToolLoadRules, STANDARD_RULES};
+use super::types::BuiltinTool;
+use super::ToolAdapter;
+use crate::skills::types::SyncMode;
+
+pub type BoxedToolAdapter = Box<dyn ToolAdapter + Send + Sync>;
+
+type AdapterConstructor = fn(&'static BuiltinTool) -> BoxedToolAdapter;
+
+/// Tools whose adapter is not `StandardTool`
+const ADAPTER_CONSTRUCTORS: &[(&str, AdapterConstructor)] = &[
+    ("claude_code", |tool| Box::new(RelocatableTool(tool))),
+    ("codex", |tool| Box::new(Codex(tool))),
+    ("cursor", |tool| Box::new(Cursor(tool))),
+    ("github_copilot_intellij", |tool| {
+        Box::new(GithubCopilotIntellij(tool))
+    }),
+    ("grok", |tool| Box::new(RelocatableTool(tool))),
+    ("openclaw", |tool| Box::new(RelocatableTool(tool))),
+    ("opencode", |tool| Box::new(OpenCode(tool))),
+    ("pi", |tool| Box::new(RelocatableTool(tool))),
+];
+
+/// The adapter of a built-in tool
+pub fn adapter_for(tool: &'static BuiltinTool) -> BoxedToolAdapter {
+    ADAPTER_CONSTRUCTORS
+        .iter()
+        .find(|(key, _)| *key == tool.key)
+        .map(|(_, construct)| construct(tool))
+        .unwrap_or_else(|| Box::new(StandardTool(tool)))
+}
+
+/// The adapter of the built-in tool `key`; retired keys resolve to the
+/// renamed tool
+pub fn builtin_adapter(key: &str) -> Option<BoxedToolAdapter> {
+    builtin_tool_by_key(key).map(adapter_for)
+}
+
+/// Adapters of all built-in tools, in catalog order
+pub fn builtin_adapters() -> Vec<BoxedToolAdapter> {
+    BUILTIN_TOOLS.iter().map(adapter_for).collect()
+}
+
+/// Catalog accessors shared by the adapters below, which all wrap their
+/// `BuiltinTool` entry
+macro_rules! catalog_entry {
+    () => {
+        fn key(&self) -> &str {
+            self.0.key
+        }
+
+        fn display_name(&self) -> &str {
+            self.0.display_name
+        }
+
+        fn skills_dir(&self) -> Option<&str> {
+            self.0.relative_skills_dir
+        }
+
+        fn detect_dir(&self) -> Option<&str> {
+            self.0.relative_detect_dir
+        }
+
+        fn sync_mode(&self) -> SyncMode {
+            self.0
+                .preferred_sync_mode
+                .and_then(SyncMode::parse)
+                .unwrap_or(SyncMode::Auto)
+        }
+    };
+}
+
+/// A tool without quirks
+pub struct StandardTool(pub &'static BuiltinTool);
+
+impl ToolAdapter for StandardTool {
+    catalog_entry!();
+}
+
+/// A tool whose config dir the user can move (runtime location), taking its
+/// skills dir and MCP config along
+pub struct RelocatableTool(pub &'static BuiltinTool);
+
+impl ToolAdapter for RelocatableTool {
+    catalog_entry!();
+
+    fn has_runtime_location(&self) -> bool {
+        true
+    }
+}
+
+/// Codex ships its own skills in `.system` and finds skills in nested
+/// directories
+pub struct Codex(pub &'static BuiltinTool);
+
+impl ToolAdapter for Codex {
+    catalog_entry!();
+
+    fn has_runtime_location(&self) -> bool {
+        true
+    }
+
+    fn system_skill_dirs(&self) -> &'static [&'static str] {
+        &[".system"]
+    }
+
+    fn load_rules(&self) -> ToolLoadRules {
+        ToolLoadRules {
+            max_depth: 6,
+            ..STANDARD_RULES
+        }
+    }
+}
+
+/// Cursor does not load linked skill directories, hence its `copy` sync mode
+/// in the catalog; hard links would let edits made in Cursor write through to
+/// the central repo
+pub struct Cursor(pub &'static BuiltinTool);
+
+impl ToolAdapter for Cursor {
+    catalog_entry!();
+
+    fn load_rules(&self) -> ToolLoadRules {
+        ToolLoadRules {
+            follows_links: false,
+            ..STANDARD_RULES
+        }
+    }
+}
+
+/// OpenCode's config file can be anywhere `OPENCODE_CONFIG` points, so that
+/// file marks it installed, and the user can move its config dir
+pub struct OpenCode(pub &'static BuiltinTool);
+
+impl ToolAdapter for OpenCode {
+    catalog_entry!();
+
+    fn has_runtime_location(&self) -> bool {
+        true
+    }
+
+    fn detects_by_mcp_config(&self) -> bool {
+        true
+    }
+}
+
+/// Copilot for JetBrains keeps its MCP config in an OS-specific directory
+pub struct GithubCopilotIntellij(pub &'static BuiltinTool);
+
+impl ToolAdapter for GithubCopilotIntellij {
+    catalog_entry!();
+
+    fn detects_by_mcp_config(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quirks_belong_to_their_tool() {
+        let codex = builtin_adapter("codex").unwrap();
+        assert_eq!(codex.system_skill_dirs(), &[".system"]);
+        assert_eq!(codex.load_rules().max_depth, 6);
+        assert!(codex.has_runtime_location());
+
+        let cursor = builtin_adapter("cursor").unwrap();
+        assert!(!cursor.load_rules().follows_links);
+        assert_eq!(cursor.sync_mode(), SyncMode::Copy);
+        assert!(cursor.system_skill_dirs().is_empty());
+
+        let gemini = builtin_adapter("gemini_cli").unwrap();
+        assert_eq!(gemini.load_rules(), STANDARD_RULES);
+        assert!(!gemini.has_runtime_location());
+        assert!(!gemini.detects_by_mcp_config());
+        assert!(builtin_adapter("opencode").unwrap().detects_by_mcp_config());
+    }
+
+    #[test]
+    fn every_constructor_names_a_builtin_tool() {
+        for (key, _) in ADAPTER_CONSTRUCTORS {
+            let adapter = builtin_adapter(key).unwrap();
+            assert_eq!(adapter.key(), *key);
+        }
+        assert_eq!(builtin_adapters().len(), BUILTIN_TOOLS.len());
+    }
+}
@@ -7,6 +7,7 @@
 //! - `%APPDATA%/` - relative to config directory (APPDATA on Windows, ~/.config on Linux/macOS)
 //! - No prefix - absolute path
 
+use super::key_alias::canonical_tool_key;
 use super::types::BuiltinTool;
 
 /// All built-in tool configurations
@@ -18,6 +19,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Claude Code",
         relative_skills_dir: Some("~/.claude/skills"),
         relative_detect_dir: Some("~/.claude"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.claude.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -28,6 +30,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Codex",
         relative_skills_dir: Some("~/.codex/skills"),
         relative_detect_dir: Some("~/.codex"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.codex/config.toml"),
         mcp_config_format: Some("toml"),
         mcp_field: Some("mcp_servers"),
@@ -38,6 +41,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Grok",
         relative_skills_dir: Some("~/.grok/skills"),
         relative_detect_dir: Some("~/.grok"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.grok/config.toml"),
         mcp_config_format: Some("toml"),
         mcp_field: Some("mcp_servers"),
@@ -48,6 +52,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Gemini CLI",
         relative_skills_dir: Some("~/.gemini/skills"),
         relative_detect_dir: Some("~/.gemini"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.gemini/settings.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -58,6 +63,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Qwen Code",
         relative_skills_dir: Some("~/.qwen/skills"),
         relative_detect_dir: Some("~/.qwen"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.qwen/settings.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -68,6 +74,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Cursor",
         relative_skills_dir: Some("~/.cursor/skills"),
         relative_detect_dir: Some("~/.cursor"),
+        preferred_sync_mode: Some("copy"),
         mcp_config_path: Some("~/.cursor/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -78,6 +85,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "OpenCode",
         relative_skills_dir: Some("~/.config/opencode/skills"),
         relative_detect_dir: Some("~/.config/opencode"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.config/opencode/opencode.jsonc"), // Dynamic resolution in detection.rs
         mcp_config_format: Some("jsonc"),
         mcp_field: Some("mcp"),
@@ -88,6 +96,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Antigravity",
         relative_skills_dir: Some("~/.gemini/antigravity/skills"),
         relative_detect_dir: Some("~/.gemini/antigravity"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.gemini/antigravity/mcp_config.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -100,6 +109,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Amp",
         relative_skills_dir: Some("~/.config/agents/skills"),
         relative_detect_dir: Some("%APPDATA%/Code"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("%APPDATA%/Code/User/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("servers"),
@@ -112,6 +122,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Kilo Code",
         relative_skills_dir: Some("~/.kilocode/skills"),
         relative_detect_dir: Some("%APPDATA%/Code/User/globalStorage/kilocode.kilo-code"),
+        preferred_sync_mode: None,
         mcp_config_path: Some(
             "%APPDATA%/Code/User/globalStorage/kilocode.kilo-code/settings/mcp_settings.json",
         ),
@@ -126,6 +137,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Roo Code",
         relative_skills_dir: Some("~/.roo/skills"),
         relative_detect_dir: Some("%APPDATA%/Code/User/globalStorage/rooveterinaryinc.roo-cline"),
+        preferred_sync_mode: None,
         mcp_config_path: Some(
             "%APPDATA%/Code/User/globalStorage/rooveterinaryinc.roo-cline/settings/mcp_settings.json",
         ),
@@ -138,6 +150,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Goose",
         relative_skills_dir: Some("~/.config/goose/skills"),
         relative_detect_dir: Some("~/.config/goose"),
+        preferred_sync_mode: None,
         mcp_config_path: None,
         mcp_config_format: None,
         mcp_field: None,
@@ -150,6 +163,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "GitHub Copilot",
         relative_skills_dir: Some("~/.copilot/skills"),
         relative_detect_dir: Some("%APPDATA%/Code"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("%APPDATA%/Code/User/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("servers"),
@@ -161,6 +175,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "GitHub Copilot (IntelliJ)",
         relative_skills_dir: None,
         relative_detect_dir: Some("%APPDATA%/github-copilot/intellij"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("%APPDATA%/github-copilot/intellij/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("servers"),
@@ -171,6 +186,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "OpenClaw",
         relative_skills_dir: Some("~/.openclaw/skills"),
         relative_detect_dir: Some("~/.openclaw"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.openclaw/openclaw.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcp.servers"),
@@ -181,6 +197,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Pi",
         relative_skills_dir: Some("~/.pi/agent/skills"),
         relative_detect_dir: Some("~/.pi/agent"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.pi/agent/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -191,6 +208,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "QoderWork",
         relative_skills_dir: Some("~/.qoderwork/skills"),
         relative_detect_dir: Some("~/.qoderwork"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.qoderwork/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -201,6 +219,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Qoder",
         relative_skills_dir: Some("~/.qoder/skills"),
         relative_detect_dir: Some("%APPDATA%/Qoder"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("%APPDATA%/Qoder/SharedClientCache/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -211,6 +230,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Droid",
         relative_skills_dir: Some("~/.factory/skills"),
         relative_detect_dir: Some("~/.factory"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.factory/mcp.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -221,6 +241,7 @@ pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
         display_name: "Windsurf",
         relative_skills_dir: Some("~/.codeium/windsurf/skills"),
         relative_detect_dir: Some("~/.codeium/windsurf"),
+        preferred_sync_mode: None,
         mcp_config_path: Some("~/.codeium/mcp_config.json"),
         mcp_config_format: Some("json"),
         mcp_field: Some("mcpServers"),
@@ -248,7 +269,8 @@ pub fn get_mcp_builtin_tools() -> Vec<&'static BuiltinTool> {
         .collect()
 }
 
-/// Find a built-in tool by key
+/// Find a built-in tool by key; retired keys resolve to the renamed tool
 pub fn builtin_tool_by_key(key: &str) -> Option<&'static BuiltinTool> {
+    let key = canonical_tool_key(key);
     BUILTIN_TOOLS.iter().find(|t| t.key == key)
 }
@@ -0,0 +1,44 @@
+//! Historical tool keys
+//!
+//! A tool that rebrands gets a new builtin key, but stored data keeps the
+//! old one. `TOOL_KEY_ALIASES` maps retired keys to current ones, and key
+//! lookups (`builtin_tool_by_key`) resolve an old key to the renamed tool.
+
+/// Retired key -> current key. Append an entry when a builtin key changes and
+/// never remove one: old backups and portable exports still carry the key.
+pub const TOOL_KEY_ALIASES: &[(&str, &str)] = &[];
+
+/// Current key for `key`; keys that were never renamed come back unchanged
+pub fn canonical_tool_key(key: &str) -> &str {
+    canonical_in(TOOL_KEY_ALIASES, key)
+}
+
+/// `canonical_tool_key` against an explicit alias table
+pub fn canonical_in<'a>(aliases: &[(&'a str, &'a str)], key: &'a str) -> &'a str {
+    let mut current = key;
+    // Follow chains (a -> b -> c); the bound stops a cycle in the table
+    for _ in 0..aliases.len() {
+        match aliases.iter().find(|(old, _)| *old == current) {
+            Some((_, new)) => current = new,
+            None => break,
+        }
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALIASES: &[(&str, &str)] = &[
+        ("windsurf_ide", "windsurf_next"),
+        ("windsurf_next", "windsurf"),
+    ];
+
+    #[test]
+    fn follows_alias_chains() {
+        assert_eq!(canonical_in(ALIASES, "windsurf_ide"), "windsurf");
+        assert_eq!(canonical_in(ALIASES, "codex"), "codex");
+        assert_eq!(canonical_in(&[("a", "b"), ("b", "a")], "a"), "a");
+    }
+}
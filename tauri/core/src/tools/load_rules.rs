@@ -0,0 +1,25 @@
+//! How a tool's loader finds skills in its skills dir
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct ToolLoadRules {
+    /// File that makes a directory a skill, matched case-sensitively
+    pub entry_file: &'static str,
+    /// Levels below the skills dir searched for skill directories; 1 means
+    /// direct children only
+    pub max_depth: usize,
+    /// Skill directories reached through a symlink or junction are loaded
+    pub follows_links: bool,
+    /// Frontmatter keys the entry file must set
+    pub required_keys: &'static [&'static str],
+}
+
+/// The Agent Skills layout: `<skills dir>/<name>/SKILL.md` with a `name` and
+/// `description` in its frontmatter
+pub const STANDARD_RULES: ToolLoadRules = ToolLoadRules {
+    entry_file: "SKILL.md",
+    max_depth: 1,
+    follows_links: true,
+    required_keys: &["name", "description"],
+};
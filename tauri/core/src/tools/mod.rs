@@ -0,0 +1,87 @@
+//! Tool catalog
+//!
+//! The built-in AI coding tools, their key aliases and the [`ToolAdapter`]
+//! trait the sync engine needs from a tool, built-in or supplied by the
+//! host. Each built-in tool's adapter, quirks included, is in `adapters`.
+
+pub mod adapters;
+pub mod builtin;
+pub mod key_alias;
+pub mod load_rules;
+pub mod types;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm_plugin;
+
+pub use adapters::{builtin_adapter, builtin_adapters, BoxedToolAdapter};
+pub use builtin::*;
+pub use key_alias::canonical_tool_key;
+pub use load_rules::{ToolLoadRules, STANDARD_RULES};
+pub use types::*;
+
+use std::path::Path;
+
+use crate::skills::types::SyncMode;
+
+/// A tool skills can be synced into
+///
+/// Directories are storage paths: `~/` is the user's home, `%APPDATA%/` the
+/// config dir (APPDATA on Windows, `~/.config` elsewhere), anything else is
+/// absolute. The host resolves them for the user and machine it runs for.
+pub trait ToolAdapter {
+    /// Stable key, e.g. `claude_code`
+    fn key(&self) -> &str;
+
+    fn display_name(&self) -> &str;
+
+    /// Directory the tool loads skills from; `None` when the tool has no
+    /// skills
+    fn skills_dir(&self) -> Option<&str>;
+
+    /// Directory whose presence means the tool is installed
+    fn detect_dir(&self) -> Option<&str>;
+
+    /// Mode skills are synced to this tool with
+    fn sync_mode(&self) -> SyncMode {
+        SyncMode::Auto
+    }
+
+    /// Whether the tool also counts as installed when its MCP config file,
+    /// or the directory holding it, exists; the host resolves that path
+    fn detects_by_mcp_config(&self) -> bool {
+        false
+    }
+
+    /// Whether the user can move the tool's config dir, and its skills dir
+    /// with it; the host then asks its runtime location for the paths
+    fn has_runtime_location(&self) -> bool {
+        false
+    }
+
+    /// Dirs inside the skills dir holding skills the tool ships and manages
+    /// itself; never adopted or synced into
+    fn system_skill_dirs(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// How the tool's loader finds skills in its skills dir
+    fn load_rules(&self) -> ToolLoadRules {
+        STANDARD_RULES
+    }
+
+    /// Called after a skill was synced to `target` in this tool's skills dir
+    fn after_sync(&self, _target: &Path) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_tools_report_their_sync_mode() {
+        let mode = |key: &str| builtin_adapter(key).map(|adapter| adapter.sync_mode());
+        assert_eq!(mode("cursor"), Some(SyncMode::Copy));
+        assert_eq!(mode("claude_code"), Some(SyncMode::Auto));
+    }
+}
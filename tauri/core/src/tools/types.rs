@@ -0,0 +1,18 @@
+//! Tool catalog types
+
+/// Built-in tool configuration (compile-time constants)
+/// Contains both Skills and MCP related configuration
+#[derive(Clone, Debug)]
+pub struct BuiltinTool {
+    pub key: &'static str,
+    pub display_name: &'static str,
+    // Skills related (optional)
+    pub relative_skills_dir: Option<&'static str>,
+    pub relative_detect_dir: Option<&'static str>,
+    /// Sync mode skills always use for this tool; `None` links with a copy fallback
+    pub preferred_sync_mode: Option<&'static str>, // "copy" | "symlink" | "junction" | "hardlink"
+    // MCP related (optional)
+    pub mcp_config_path: Option<&'static str>,
+    pub mcp_config_format: Option<&'static str>, // "json" | "toml"
+    pub mcp_field: Option<&'static str>,         // field name in config file
+}
@@ -0,0 +1,589 @@
+//! Tool adapters supplied as WASM plugins
+//!
+//! A community tool whose paths or config format need logic (a config dir
+//! taken from an environment variable, an MCP config that is not JSON or
+//! TOML) can be supported by a WASM module instead of a change to
+//! `BUILTIN_TOOLS`. The host loads the module with [`WasmToolPlugin::load`]
+//! and uses it as any other [`ToolAdapter`].
+//!
+//! Plugins run sandboxed: the module may import nothing, so it has no file
+//! system, network, clock or environment of its own; each call gets a fresh
+//! instance with bounded memory and fuel. What it sees is what the host
+//! passes in as JSON, including only the environment variables its manifest
+//! asks for.
+//!
+//! The host writes to the paths a plugin names (skills sync, MCP config), so
+//! every path must stay in the plugin's tool dir ([`WasmToolPlugin::tool_dir`]):
+//! a `~/` or `%APPDATA%/` storage path without `.` / `..` segments or other
+//! variables. A plugin cannot point the host at `~/.bashrc`.
+//!
+//! The interface, all strings UTF-8 JSON:
+//!
+//! - `memory`: the exported linear memory
+//! - `alloc(len: i32) -> i32`: space for the host to write an input into
+//! - `manifest() -> i64`: a [`PluginManifest`]
+//! - `detect(ptr: i32, len: i32) -> i64`, optional: gets a
+//!   [`DetectContext`], returns a [`PluginDetection`]
+//! - `render_config(ptr: i32, len: i32) -> i64`, optional: gets a
+//!   [`RenderConfigRequest`], returns a [`RenderConfigResponse`]
+//!
+//! An `i64` result is a string in the instance's memory, its offset in the
+//! high 32 bits and its length in the low 32 bits.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::builtin::builtin_tool_by_key;
+use super::ToolAdapter;
+use crate::skills::types::SyncMode;
+
+/// File extension of plugin modules
+pub const PLUGIN_EXTENSION: &str = "wasm";
+/// Largest module accepted
+const MAX_MODULE_BYTES: u64 = 8 * 1024 * 1024;
+/// Linear memory of one instance
+const MAX_MEMORY_BYTES: usize = 32 * 1024 * 1024;
+/// Instructions (roughly) one call may run before it is stopped
+const FUEL_PER_CALL: u64 = 100_000_000;
+/// Largest string a call may return
+const MAX_OUTPUT_BYTES: usize = 4 * 1024 * 1024;
+
+/// What a plugin declares about its tool, returned by `manifest`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Tool key; lowercase letters, digits and `_`, not a built-in key
+    pub key: String,
+    pub display_name: String,
+    /// Storage path of the skills dir when `detect` does not return one
+    #[serde(default)]
+    pub skills_dir: Option<String>,
+    /// Storage path whose presence means the tool is installed, when
+    /// `detect` returns no `detect_paths`; also the tool dir every path of
+    /// the plugin must stay in (`~/.<key>` when unset)
+    #[serde(default)]
+    pub detect_dir: Option<String>,
+    /// `copy` | `symlink` | `junction` | `hardlink`; links with a copy
+    /// fallback when unset
+    #[serde(default)]
+    pub sync_mode: Option<String>,
+    /// Storage path of the MCP config when `detect` does not return one;
+    /// written through `render_config`
+    #[serde(default)]
+    pub mcp_config_path: Option<String>,
+    /// Environment variables passed to `detect`
+    #[serde(default)]
+    pub env: Vec<String>,
+}
+
+/// Input of `detect`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DetectContext {
+    /// `linux` | `macos` | `windows`
+    pub os: String,
+    /// The manifest's `env` variables that are set
+    pub env: BTreeMap<String, String>,
+}
+
+/// Output of `detect`; paths are storage paths, resolved and checked by the
+/// host
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginDetection {
+    #[serde(default)]
+    pub skills_dir: Option<String>,
+    #[serde(default)]
+    pub mcp_config_path: Option<String>,
+    /// The tool is installed when any of these exists
+    #[serde(default)]
+    pub detect_paths: Vec<String>,
+}
+
+/// Input of `render_config`: one server to write into or remove from the
+/// tool's MCP config
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RenderConfigRequest {
+    /// Current content of the config file, `None` when it does not exist
+    pub existing: Option<String>,
+    /// `upsert` | `remove`
+    pub op: String,
+    pub name: String,
+    /// The server in AI Toolbox's unified format, for `upsert`
+    #[serde(default)]
+    pub server: Option<serde_json::Value>,
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Output of `render_config`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RenderConfigResponse {
+    /// New content of the config file, `None` when nothing is to be written
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+/// Plugins keep nothing between calls; the store holds only its limits
+struct CallState {
+    limits: StoreLimits,
+}
+
+/// A loaded plugin module and its manifest
+pub struct WasmToolPlugin {
+    engine: Engine,
+    module: Module,
+    manifest: PluginManifest,
+    has_detect: bool,
+    has_render_config: bool,
+}
+
+impl std::fmt::Debug for WasmToolPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmToolPlugin")
+            .field("manifest", &self.manifest)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasmToolPlugin {
+    /// Load the module at `path`, check its interface and read its manifest
+    pub fn load(path: &Path) -> Result<Self> {
+        let size = std::fs::metadata(path)
+            .with_context(|| format!("read {:?}", path))?
+            .len();
+        if size > MAX_MODULE_BYTES {
+            bail!(
+                "plugin is {} bytes, over the {} limit",
+                size,
+                MAX_MODULE_BYTES
+            );
+        }
+        let wasm = std::fs::read(path).with_context(|| format!("read {:?}", path))?;
+        Self::from_bytes(&wasm)
+    }
+
+    pub fn from_bytes(wasm: &[u8]) -> Result<Self> {
+        if wasm.len() as u64 > MAX_MODULE_BYTES {
+            bail!(
+                "plugin is {} bytes, over the {} limit",
+                wasm.len(),
+                MAX_MODULE_BYTES
+            );
+        }
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm).map_err(|e| anyhow!("invalid module: {e}"))?;
+
+        if let Some(import) = module.imports().next() {
+            bail!(
+                "plugin imports {}.{}; plugins get no host functions",
+                import.module(),
+                import.name()
+            );
+        }
+        let exports: Vec<&str> = module.exports().map(|export| export.name()).collect();
+        for required in ["memory", "alloc", "manifest"] {
+            if !exports.contains(&required) {
+                bail!("plugin does not export `{}`", required);
+            }
+        }
+
+        let mut plugin = WasmToolPlugin {
+            has_detect: exports.contains(&"detect"),
+            has_render_config: exports.contains(&"render_config"),
+            engine,
+            module,
+            manifest: PluginManifest::default(),
+        };
+        let manifest: PluginManifest = plugin.call_json("manifest", None)?;
+        validate_manifest(&manifest)?;
+        plugin.manifest = manifest;
+        Ok(plugin)
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    /// Whether the plugin writes the tool's MCP config
+    pub fn renders_config(&self) -> bool {
+        self.has_render_config
+    }
+
+    /// The storage path every path of the plugin must stay in: the
+    /// manifest's `detect_dir`, or `~/.<key>`
+    pub fn tool_dir(&self) -> String {
+        tool_dir(&self.manifest)
+    }
+
+    /// Ask the plugin where the tool keeps its files; without a `detect`
+    /// export, the manifest's paths. Fails when a path leaves the tool dir.
+    pub fn detect(&self, context: &DetectContext) -> Result<PluginDetection> {
+        let mut detection = if self.has_detect {
+            let input = serde_json::to_vec(context)?;
+            self.call_json("detect", Some(&input))?
+        } else {
+            PluginDetection::default()
+        };
+        detection.skills_dir = detection
+            .skills_dir
+            .or_else(|| self.manifest.skills_dir.clone());
+        detection.mcp_config_path = detection
+            .mcp_config_path
+            .or_else(|| self.manifest.mcp_config_path.clone());
+        if detection.detect_paths.is_empty() {
+            detection.detect_paths = self.manifest.detect_dir.iter().cloned().collect();
+        }
+
+        let root = self.tool_dir();
+        let confine = |path: &String| confine_path(&root, path);
+        Ok(PluginDetection {
+            skills_dir: detection.skills_dir.as_ref().map(confine).transpose()?,
+            mcp_config_path: detection
+                .mcp_config_path
+                .as_ref()
+                .map(confine)
+                .transpose()?,
+            detect_paths: detection
+                .detect_paths
+                .iter()
+                .map(confine)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// The tool's MCP config after `request`, rendered by the plugin
+    pub fn render_config(&self, request: &RenderConfigRequest) -> Result<Option<String>> {
+        if !self.has_render_config {
+            bail!("plugin {} does not render MCP configs", self.manifest.key);
+        }
+        let input = serde_json::to_vec(request)?;
+        let response: RenderConfigResponse = self.call_json("render_config", Some(&input))?;
+        Ok(response.content)
+    }
+
+    /// The context `detect` gets on this machine: the manifest's
+    /// environment variables that are set
+    pub fn detect_context(&self) -> DetectContext {
+        DetectContext {
+            os: std::env::consts::OS.to_string(),
+            env: self
+                .manifest
+                .env
+                .iter()
+                .filter_map(|name| std::env::var(name).ok().map(|value| (name.clone(), value)))
+                .collect(),
+        }
+    }
+
+    fn call_json<T: DeserializeOwned>(&self, export: &str, input: Option<&[u8]>) -> Result<T> {
+        let output = self
+            .call(export, input)
+            .with_context(|| format!("plugin call `{}` failed", export))?;
+        serde_json::from_slice(&output)
+            .with_context(|| format!("plugin call `{}` returned invalid JSON", export))
+    }
+
+    /// Run `export` in a fresh instance, with `input` written to memory the
+    /// plugin allocated
+    fn call(&self, export: &str, input: Option<&[u8]>) -> Result<Vec<u8>> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .instances(1)
+            .memories(1)
+            .build();
+        let mut store = Store::new(&self.engine, CallState { limits });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(FUEL_PER_CALL).map_err(|e| anyhow!("{e}"))?;
+
+        let linker = Linker::<CallState>::new(&self.engine);
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| anyhow!("instantiate: {e}"))?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .context("`memory` is not a memory")?;
+
+        let packed = match input {
+            Some(input) => {
+                let len = i32::try_from(input.len()).context("input too large")?;
+                let alloc = instance
+                    .get_typed_func::<i32, i32>(&store, "alloc")
+                    .map_err(|e| anyhow!("`alloc`: {e}"))?;
+                let ptr = alloc
+                    .call(&mut store, len)
+                    .map_err(|e| anyhow!("`alloc`: {e}"))?;
+                memory
+                    .write(&mut store, ptr as u32 as usize, input)
+                    .map_err(|e| anyhow!("write input: {e}"))?;
+                instance
+                    .get_typed_func::<(i32, i32), i64>(&store, export)
+                    .map_err(|e| anyhow!("{e}"))?
+                    .call(&mut store, (ptr, len))
+            }
+            None => instance
+                .get_typed_func::<(), i64>(&store, export)
+                .map_err(|e| anyhow!("{e}"))?
+                .call(&mut store, ()),
+        }
+        .map_err(|e| anyhow!("{e}"))?;
+
+        let (ptr, len) = (
+            (packed as u64 >> 32) as usize,
+            packed as u64 as u32 as usize,
+        );
+        if len > MAX_OUTPUT_BYTES {
+            bail!(
+                "output is {} bytes, over the {} limit",
+                len,
+                MAX_OUTPUT_BYTES
+            );
+        }
+        let mut output = vec![0; len];
+        memory
+            .read(&store, ptr, &mut output)
+            .map_err(|e| anyhow!("read output: {e}"))?;
+        Ok(output)
+    }
+}
+
+fn tool_dir(manifest: &PluginManifest) -> String {
+    manifest
+        .detect_dir
+        .clone()
+        .unwrap_or_else(|| format!("~/.{}", manifest.key))
+}
+
+/// `path` normalized, when it is `root` or inside it
+fn confine_path(root: &str, path: &str) -> Result<String> {
+    let root = normalize_storage_path(root)?;
+    let normalized = normalize_storage_path(path)?;
+    if normalized == root || normalized.starts_with(&format!("{}/", root)) {
+        Ok(normalized)
+    } else {
+        bail!("path {:?} is outside the tool dir {:?}", path, root)
+    }
+}
+
+/// `path` as `~/a/b` or `%APPDATA%/a/b`; absolute paths, `.` / `..`
+/// segments and other variables are rejected
+fn normalize_storage_path(path: &str) -> Result<String> {
+    let path = path.trim().replace('\\', "/");
+    let (prefix, rest) = if let Some(rest) = path.strip_prefix("~/") {
+        ("~", rest)
+    } else if path
+        .get(..10)
+        .is_some_and(|head| head.eq_ignore_ascii_case("%APPDATA%/"))
+    {
+        ("%APPDATA%", &path[10..])
+    } else {
+        bail!("path {:?} is not under ~/ or %APPDATA%/", path);
+    };
+    let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() {
+        bail!("path {:?} names no directory below {}", path, prefix);
+    }
+    if let Some(segment) = segments
+        .iter()
+        .find(|s| **s == "." || **s == ".." || s.contains([':', '$', '%']))
+    {
+        bail!("path {:?} has an unsupported segment {:?}", path, segment);
+    }
+    Ok(format!("{}/{}", prefix, segments.join("/")))
+}
+
+fn validate_manifest(manifest: &PluginManifest) -> Result<()> {
+    let key = manifest.key.as_str();
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        bail!("invalid tool key {:?}", key);
+    }
+    if builtin_tool_by_key(key).is_some() {
+        bail!("tool key {:?} is a built-in tool", key);
+    }
+    if manifest.display_name.trim().is_empty() {
+        bail!("tool {:?} has no display name", key);
+    }
+    if let Some(mode) = &manifest.sync_mode {
+        if SyncMode::parse(mode).is_none() {
+            bail!("unsupported sync mode {:?}", mode);
+        }
+    }
+    let root = tool_dir(manifest);
+    normalize_storage_path(&root)?;
+    for path in [&manifest.skills_dir, &manifest.mcp_config_path]
+        .into_iter()
+        .flatten()
+    {
+        confine_path(&root, path)?;
+    }
+    Ok(())
+}
+
+impl ToolAdapter for WasmToolPlugin {
+    fn key(&self) -> &str {
+        &self.manifest.key
+    }
+
+    fn display_name(&self) -> &str {
+        &self.manifest.display_name
+    }
+
+    fn skills_dir(&self) -> Option<&str> {
+        self.manifest.skills_dir.as_deref()
+    }
+
+    fn detect_dir(&self) -> Option<&str> {
+        self.manifest.detect_dir.as_deref()
+    }
+
+    fn sync_mode(&self) -> SyncMode {
+        self.manifest
+            .sync_mode
+            .as_deref()
+            .and_then(SyncMode::parse)
+            .unwrap_or(SyncMode::Auto)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plugin answering each call with a fixed string from its data
+    /// segment; `render_config` echoes its input
+    fn plugin_wat(manifest: &str, imports: &str) -> Vec<u8> {
+        plugin_wat_detecting(
+            manifest,
+            imports,
+            r#"{"skills_dir":"~/.acme/skills","detect_paths":["~/.acme"]}"#,
+        )
+    }
+
+    fn plugin_wat_detecting(manifest: &str, imports: &str, detect: &str) -> Vec<u8> {
+        let escape = |s: &str| s.replace('"', "\\\"");
+        let source = format!(
+            r#"(module
+                {imports}
+                (memory (export "memory") 1)
+                (global $next (mut i32) (i32.const 4096))
+                (data (i32.const 0) "{manifest}")
+                (data (i32.const 1024) "{detect}")
+                (func (export "alloc") (param $len i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (global.get $next))
+                    (global.set $next (i32.add (global.get $next) (local.get $len)))
+                    (local.get $ptr))
+                (func (export "manifest") (result i64)
+                    (i64.const {manifest_len}))
+                (func (export "detect") (param i32 i32) (result i64)
+                    (i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const {detect_len})))
+                (func (export "render_config") (param $ptr i32) (param $len i32) (result i64)
+                    (i64.or
+                        (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                        (i64.extend_i32_u (local.get $len)))))"#,
+            manifest = escape(manifest),
+            manifest_len = manifest.len(),
+            detect = escape(detect),
+            detect_len = detect.len(),
+        );
+        wat::parse_str(source).unwrap()
+    }
+
+    const MANIFEST: &str =
+        r#"{"key":"acme","display_name":"Acme","sync_mode":"copy","env":["ACME_HOME"]}"#;
+
+    #[test]
+    fn loads_a_plugin_and_calls_its_interface() {
+        let plugin = WasmToolPlugin::from_bytes(&plugin_wat(MANIFEST, "")).unwrap();
+        assert_eq!(plugin.key(), "acme");
+        assert_eq!(plugin.sync_mode(), SyncMode::Copy);
+        assert!(plugin.renders_config());
+
+        let detection = plugin.detect(&DetectContext::default()).unwrap();
+        assert_eq!(detection.skills_dir.as_deref(), Some("~/.acme/skills"));
+        assert_eq!(detection.detect_paths, vec!["~/.acme".to_string()]);
+
+        // The echoing `render_config` gets the input the host wrote
+        assert_eq!(
+            plugin
+                .call("render_config", Some(b"{\"content\":null}"))
+                .unwrap(),
+            b"{\"content\":null}"
+        );
+        let request = RenderConfigRequest {
+            existing: None,
+            op: "remove".to_string(),
+            name: "github".to_string(),
+            server: None,
+            enabled: false,
+        };
+        assert_eq!(plugin.render_config(&request).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_plugins_that_import_or_claim_builtin_keys() {
+        let imports = r#"(import "env" "read_file" (func (param i32 i32) (result i32)))"#;
+        let error = WasmToolPlugin::from_bytes(&plugin_wat(MANIFEST, imports)).unwrap_err();
+        assert!(error.to_string().contains("env.read_file"));
+
+        let builtin = r#"{"key":"cursor","display_name":"Cursor"}"#;
+        let error = WasmToolPlugin::from_bytes(&plugin_wat(builtin, "")).unwrap_err();
+        assert!(error.to_string().contains("built-in"));
+    }
+
+    #[test]
+    fn keeps_plugin_paths_in_the_tool_dir() {
+        for detect in [
+            r#"{"mcp_config_path":"~/.bashrc"}"#,
+            r#"{"skills_dir":"~/.acme/../.ssh"}"#,
+            r#"{"skills_dir":"/etc/acme"}"#,
+            r#"{"detect_paths":["$HOME/.acme"]}"#,
+        ] {
+            let plugin =
+                WasmToolPlugin::from_bytes(&plugin_wat_detecting(MANIFEST, "", detect)).unwrap();
+            let error = plugin.detect(&DetectContext::default()).unwrap_err();
+            assert!(error.to_string().contains("path"), "{}: {}", detect, error);
+        }
+
+        let inside = r#"{"mcp_config_path":"~/.acme//mcp.json "}"#;
+        let plugin =
+            WasmToolPlugin::from_bytes(&plugin_wat_detecting(MANIFEST, "", inside)).unwrap();
+        let detection = plugin.detect(&DetectContext::default()).unwrap();
+        assert_eq!(
+            detection.mcp_config_path.as_deref(),
+            Some("~/.acme/mcp.json")
+        );
+
+        let escaping = r#"{"key":"acme","display_name":"Acme","detect_dir":"~/.acme","mcp_config_path":"~/.profile"}"#;
+        assert!(WasmToolPlugin::from_bytes(&plugin_wat(escaping, "")).is_err());
+        let home = r#"{"key":"acme","display_name":"Acme","detect_dir":"~"}"#;
+        assert!(WasmToolPlugin::from_bytes(&plugin_wat(home, "")).is_err());
+    }
+
+    #[test]
+    fn stops_plugins_that_run_out_of_fuel() {
+        let source = format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (func (export "alloc") (param i32) (result i32) (i32.const 4096))
+                (func (export "manifest") (result i64) (i64.const {}))
+                (func (export "detect") (param i32 i32) (result i64)
+                    (loop $spin (br $spin))
+                    (i64.const 0)))"#,
+            MANIFEST.replace('"', "\\\""),
+            MANIFEST.len()
+        );
+        let plugin = WasmToolPlugin::from_bytes(&wat::parse_str(source).unwrap()).unwrap();
+        assert!(plugin.detect(&DetectContext::default()).is_err());
+    }
+}
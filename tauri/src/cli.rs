@@ -0,0 +1,454 @@
+//! Headless listing commands for launcher extensions
+//!
+//! `ai-toolbox list <skills|tools|mcp> [--filter <expr>] [--json]` and
+//! `ai-toolbox status [--json]` read the app database and exit without
+//! starting the window, so Raycast / Alfred extensions can build pickers on
+//! top of them. Any other argument list falls through to the GUI.
+//!
+//! `--json` prints one `{"schema_version", "kind", "items" | "summary"}`
+//! object. The item types below are the stable contract: fields are only
+//! ever added, and `schema_version` is bumped if one is renamed or removed.
+//! Plain output is tab-separated and meant for humans, not parsers.
+//!
+//! `ai-toolbox mcp-proxy --log <file> -- <command> [args...]` is not meant
+//! to be typed: it is the stdio wrapper written into tool configs for MCP
+//! servers with traffic logging on (see `coding::mcp::traffic_log`).
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::coding::mcp::{mcp_store, traffic_log};
+use crate::coding::skills::skill_query::{query_skills, SkillQuery};
+use crate::coding::status_summary::{load_status_summary, StatusSummary};
+use crate::coding::tools::custom_store::get_custom_tools;
+use crate::coding::tools::{
+    get_all_runtime_tools, is_tool_installed_with_db, resolve_skills_path_with_db,
+};
+use crate::db::surreal_import::SQLITE_DATABASE_FILE;
+use crate::SqliteDbState;
+
+pub const CLI_SCHEMA_VERSION: u32 = 1;
+
+const EXIT_OK: i32 = 0;
+const EXIT_ERROR: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+const USAGE: &str = "Usage:
+  ai-toolbox list skills [--filter <expr>] [--json]
+  ai-toolbox list tools [--json]
+  ai-toolbox list mcp [--json]
+  ai-toolbox status [--json]";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CliCommand {
+    Skills { filter: Option<String> },
+    Tools,
+    Mcp,
+    Status,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CliInvocation {
+    pub command: CliCommand,
+    pub json: bool,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct CliSkill {
+    pub id: String,
+    pub name: String,
+    pub source_type: String,
+    pub source_ref: Option<String>,
+    pub status: String,
+    pub group_id: Option<String>,
+    pub management_enabled: bool,
+    pub enabled_tools: Vec<String>,
+    /// Targets whose last sync did not end `ok`
+    pub broken_targets: usize,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct CliTool {
+    pub key: String,
+    pub display_name: String,
+    pub is_custom: bool,
+    pub installed: bool,
+    pub supports_skills: bool,
+    pub supports_mcp: bool,
+    pub skills_path: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct CliMcpServer {
+    pub id: String,
+    pub name: String,
+    pub server_type: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub enabled_tools: Vec<String>,
+    /// Tool syncs that ended in `error`
+    pub broken_targets: usize,
+}
+
+#[derive(Serialize)]
+struct ListOutput<'a, T> {
+    schema_version: u32,
+    kind: &'a str,
+    items: Vec<T>,
+}
+
+#[derive(Serialize)]
+struct StatusOutput {
+    schema_version: u32,
+    kind: &'static str,
+    summary: StatusSummary,
+}
+
+/// Parse CLI args (without the program name).
+///
+/// `None` means the args are not a CLI invocation and the GUI should start.
+pub fn parse_args(args: &[String]) -> Option<Result<CliInvocation, String>> {
+    let (command, rest) = match args {
+        [first, second, rest @ ..] if first == "list" => (Some(second.as_str()), rest),
+        [first] if first == "list" => (None, &[][..]),
+        [first, rest @ ..] if first == "status" => (Some("status"), rest),
+        _ => return None,
+    };
+    Some(parse_invocation(command, rest))
+}
+
+fn parse_invocation(command: Option<&str>, rest: &[String]) -> Result<CliInvocation, String> {
+    let mut json = false;
+    let mut filter = None;
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--filter" => {
+                filter = Some(
+                    iter.next()
+                        .cloned()
+                        .ok_or_else(|| "Missing value for --filter".to_string())?,
+                )
+            }
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+    if filter.is_some() && command != Some("skills") {
+        return Err("--filter is only supported by `list skills`".to_string());
+    }
+    let command = match command {
+        Some("skills") => CliCommand::Skills { filter },
+        Some("tools") => CliCommand::Tools,
+        Some("mcp") => CliCommand::Mcp,
+        Some("status") => CliCommand::Status,
+        Some(other) => return Err(format!("Unknown list target: {}", other)),
+        None => return Err("Missing list target".to_string()),
+    };
+    Ok(CliInvocation { command, json })
+}
+
+/// Entry point used by `main`: run a CLI command and return its exit code,
+/// or `None` to continue with the GUI.
+pub fn run_from_env() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some(traffic_log::PROXY_SUBCOMMAND) {
+        return Some(traffic_log::run_proxy(&args[1..]));
+    }
+    let invocation = match parse_args(&args)? {
+        Ok(invocation) => invocation,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return Some(EXIT_USAGE);
+        }
+    };
+    crate::coding::tools::init_home_override_from_env();
+    let result = open_app_database().and_then(|db| execute(&db, &invocation));
+    Some(match result {
+        Ok(output) => {
+            println!("{}", output);
+            EXIT_OK
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            EXIT_ERROR
+        }
+    })
+}
+
+/// Same directory Tauri resolves as `app_data_dir` for `com.ai-toolbox`
+pub(crate) fn app_data_dir() -> Result<PathBuf, String> {
+    dirs::data_dir()
+        .map(|dir| dir.join("com.ai-toolbox"))
+        .ok_or_else(|| "Failed to resolve the app data directory".to_string())
+}
+
+fn app_database_path() -> Result<PathBuf, String> {
+    Ok(app_data_dir()?.join(SQLITE_DATABASE_FILE))
+}
+
+fn open_app_database() -> Result<SqliteDbState, String> {
+    let path = app_database_path()?;
+    if !path.exists() {
+        return Err(format!(
+            "AI Toolbox database not found at {}; launch the app once first",
+            path.display()
+        ));
+    }
+    SqliteDbState::open(path)
+}
+
+/// Run a parsed invocation and render its output
+pub fn execute(db: &SqliteDbState, invocation: &CliInvocation) -> Result<String, String> {
+    match &invocation.command {
+        CliCommand::Skills { filter } => {
+            let query = match filter {
+                Some(filter) => SkillQuery::parse_filter(filter)?,
+                None => SkillQuery::default(),
+            };
+            render_list(
+                invocation.json,
+                "skills",
+                list_skills(db, &query)?,
+                |skill| {
+                    format!(
+                        "{}\t{}\t{}\t{}",
+                        skill.name,
+                        skill.source_type,
+                        skill.enabled_tools.join(","),
+                        skill.status
+                    )
+                },
+            )
+        }
+        CliCommand::Tools => render_list(invocation.json, "tools", list_tools(db)?, |tool| {
+            format!(
+                "{}\t{}\t{}",
+                tool.key,
+                tool.display_name,
+                if tool.installed {
+                    "installed"
+                } else {
+                    "not installed"
+                }
+            )
+        }),
+        CliCommand::Mcp => render_list(invocation.json, "mcp", list_mcp_servers(db)?, |server| {
+            format!(
+                "{}\t{}\t{}",
+                server.name,
+                server.server_type,
+                server.enabled_tools.join(",")
+            )
+        }),
+        CliCommand::Status => {
+            let summary = load_status_summary(db)?;
+            if invocation.json {
+                return to_json(&StatusOutput {
+                    schema_version: CLI_SCHEMA_VERSION,
+                    kind: "status",
+                    summary,
+                });
+            }
+            let mut lines = vec![
+                format!("managed_skills\t{}", summary.managed_skills),
+                format!("broken_skill_targets\t{}", summary.broken_skill_targets),
+                format!("mcp_servers\t{}", summary.mcp_servers),
+                format!("broken_mcp_targets\t{}", summary.broken_mcp_targets),
+            ];
+            lines.extend(
+                summary
+                    .active_profiles
+                    .iter()
+                    .map(|(tool, profile)| format!("profile.{}\t{}", tool, profile)),
+            );
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+fn render_list<T: Serialize>(
+    json: bool,
+    kind: &str,
+    items: Vec<T>,
+    line: impl Fn(&T) -> String,
+) -> Result<String, String> {
+    if json {
+        return to_json(&ListOutput {
+            schema_version: CLI_SCHEMA_VERSION,
+            kind,
+            items,
+        });
+    }
+    Ok(items.iter().map(line).collect::<Vec<_>>().join("\n"))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|error| format!("Failed to serialize output: {error}"))
+}
+
+/// Targets in a `sync_details` map whose status `is_broken`
+fn count_targets(sync_details: Option<&Value>, is_broken: impl Fn(&str) -> bool) -> usize {
+    sync_details
+        .and_then(Value::as_object)
+        .map(|targets| {
+            targets
+                .values()
+                .filter(|target| {
+                    is_broken(target.get("status").and_then(Value::as_str).unwrap_or(""))
+                })
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+pub fn list_skills(db: &SqliteDbState, query: &SkillQuery) -> Result<Vec<CliSkill>, String> {
+    Ok(query_skills(db, query)?
+        .into_iter()
+        .map(|skill| CliSkill {
+            broken_targets: count_targets(skill.sync_details.as_ref(), |status| status != "ok"),
+            id: skill.id,
+            name: skill.name,
+            source_type: skill.source_type,
+            source_ref: skill.source_ref,
+            status: skill.status,
+            group_id: skill.group_id,
+            management_enabled: skill.management_enabled,
+            enabled_tools: skill.enabled_tools,
+        })
+        .collect())
+}
+
+pub fn list_tools(db: &SqliteDbState) -> Result<Vec<CliTool>, String> {
+    let custom_tools = tauri::async_runtime::block_on(get_custom_tools(db))?;
+    Ok(get_all_runtime_tools(&custom_tools)
+        .iter()
+        .map(|tool| CliTool {
+            key: tool.key.clone(),
+            display_name: tool.display_name.clone(),
+            is_custom: tool.is_custom,
+            installed: is_tool_installed_with_db(db, tool),
+            supports_skills: tool.relative_skills_dir.is_some(),
+            supports_mcp: tool.mcp_config_path.is_some(),
+            skills_path: resolve_skills_path_with_db(db, tool)
+                .map(|path| path.to_string_lossy().to_string()),
+        })
+        .collect())
+}
+
+pub fn list_mcp_servers(db: &SqliteDbState) -> Result<Vec<CliMcpServer>, String> {
+    Ok(
+        tauri::async_runtime::block_on(mcp_store::get_mcp_servers(db))?
+            .into_iter()
+            .map(|server| CliMcpServer {
+                broken_targets: count_targets(server.sync_details.as_ref(), |status| {
+                    status == "error"
+                }),
+                id: server.id,
+                name: server.name,
+                server_type: server.server_type,
+                description: server.description,
+                tags: server.tags,
+                enabled_tools: server.enabled_tools,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::helpers::db_put;
+    use crate::db::schema::DbTable;
+    use serde_json::json;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_list_commands_and_falls_through_for_gui_args() {
+        assert_eq!(parse_args(&args(&[])), None);
+        assert_eq!(parse_args(&args(&["--read-only"])), None);
+        assert_eq!(
+            parse_args(&args(&[
+                "list",
+                "skills",
+                "--json",
+                "--filter",
+                "tool:codex"
+            ])),
+            Some(Ok(CliInvocation {
+                command: CliCommand::Skills {
+                    filter: Some("tool:codex".to_string())
+                },
+                json: true,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args(&["status"])),
+            Some(Ok(CliInvocation {
+                command: CliCommand::Status,
+                json: false,
+            }))
+        );
+        assert!(matches!(parse_args(&args(&["list"])), Some(Err(_))));
+        assert!(matches!(
+            parse_args(&args(&["list", "agents"])),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            parse_args(&args(&["list", "mcp", "--filter", "x"])),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn json_output_uses_versioned_envelope() {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        state
+            .with_conn(|conn| {
+                db_put(
+                    conn,
+                    DbTable::McpServer,
+                    "m",
+                    &json!({
+                        "name": "fs",
+                        "server_type": "stdio",
+                        "server_config": {"command": "npx"},
+                        "enabled_tools": ["codex"],
+                        "sync_details": {"codex": {"status": "error"}},
+                    }),
+                )
+            })
+            .unwrap();
+
+        let output = execute(
+            &state,
+            &CliInvocation {
+                command: CliCommand::Mcp,
+                json: true,
+            },
+        )
+        .unwrap();
+        let value: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["schema_version"], json!(CLI_SCHEMA_VERSION));
+        assert_eq!(value["kind"], json!("mcp"));
+        assert_eq!(value["items"][0]["name"], json!("fs"));
+        assert_eq!(value["items"][0]["broken_targets"], json!(1));
+
+        let status = execute(
+            &state,
+            &CliInvocation {
+                command: CliCommand::Status,
+                json: true,
+            },
+        )
+        .unwrap();
+        let value: Value = serde_json::from_str(&status).unwrap();
+        assert_eq!(value["kind"], json!("status"));
+        assert_eq!(value["summary"]["mcp_servers"], json!(1));
+    }
+}
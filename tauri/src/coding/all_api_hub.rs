@@ -367,7 +367,7 @@ pub fn mask_api_key_preview(api_key: &str) -> String {
 fn get_chrome_base_dir() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        dirs::home_dir().map(|h| {
+        crate::coding::tools::home_dir().map(|h| {
             h.join("Library")
                 .join("Application Support")
                 .join("Google")
@@ -377,7 +377,7 @@ fn get_chrome_base_dir() -> Option<PathBuf> {
 
     #[cfg(target_os = "linux")]
     {
-        dirs::home_dir().map(|h| h.join(".config").join("google-chrome"))
+        crate::coding::tools::home_dir().map(|h| h.join(".config").join("google-chrome"))
     }
 
     #[cfg(target_os = "windows")]
@@ -389,7 +389,7 @@ fn get_chrome_base_dir() -> Option<PathBuf> {
 fn get_edge_base_dir() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        dirs::home_dir().map(|h| {
+        crate::coding::tools::home_dir().map(|h| {
             h.join("Library")
                 .join("Application Support")
                 .join("Microsoft Edge")
@@ -398,7 +398,7 @@ fn get_edge_base_dir() -> Option<PathBuf> {
 
     #[cfg(target_os = "linux")]
     {
-        dirs::home_dir().map(|h| h.join(".config").join("microsoft-edge"))
+        crate::coding::tools::home_dir().map(|h| h.join(".config").join("microsoft-edge"))
     }
 
     #[cfg(target_os = "windows")]
@@ -69,10 +69,7 @@ fn ensure_claude_provider_native_for_direct(
 }
 
 fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 pub fn get_claude_default_root_dir() -> Result<PathBuf, String> {
@@ -14,7 +14,7 @@ pub struct LocalCliProgram {
 pub fn resolve_local_claude_program() -> LocalCliProgram {
     let mut candidates = Vec::new();
 
-    if let Some(home_dir) = dirs::home_dir() {
+    if let Some(home_dir) = crate::coding::tools::home_dir() {
         push_command_candidate(
             &mut candidates,
             home_dir.join(".local").join("bin"),
@@ -42,7 +42,7 @@ pub fn resolve_local_claude_program() -> LocalCliProgram {
 pub fn resolve_local_opencode_program() -> LocalCliProgram {
     let mut candidates = Vec::new();
 
-    if let Some(home_dir) = dirs::home_dir() {
+    if let Some(home_dir) = crate::coding::tools::home_dir() {
         push_command_candidate(
             &mut candidates,
             home_dir.join(".opencode").join("bin"),
@@ -70,7 +70,7 @@ pub fn resolve_local_opencode_program() -> LocalCliProgram {
 pub fn resolve_local_pi_program() -> LocalCliProgram {
     let mut candidates = Vec::new();
 
-    if let Some(home_dir) = dirs::home_dir() {
+    if let Some(home_dir) = crate::coding::tools::home_dir() {
         push_command_candidate(&mut candidates, home_dir.join(".local").join("bin"), "pi");
     }
 
@@ -83,7 +83,7 @@ pub fn resolve_local_pi_program() -> LocalCliProgram {
 
 pub fn resolve_local_grok_program() -> LocalCliProgram {
     let mut candidates = Vec::new();
-    if let Some(home_dir) = dirs::home_dir() {
+    if let Some(home_dir) = crate::coding::tools::home_dir() {
         push_command_candidate(&mut candidates, home_dir.join(".local").join("bin"), "grok");
     }
     push_command_candidate(&mut candidates, "/opt/homebrew/bin", "grok");
@@ -102,6 +102,24 @@ pub fn resolve_local_npx_program() -> LocalCliProgram {
     resolve_local_cli_program("npx", candidates)
 }
 
+/// Resolve an arbitrary command (e.g. an MCP stdio server `command`) with the
+/// same PATH + node version manager lookup used for the known CLIs.
+pub fn resolve_local_command_program(command_name: &str) -> LocalCliProgram {
+    let command_path = Path::new(command_name);
+    if command_path.is_absolute() {
+        return LocalCliProgram {
+            path: command_path.to_path_buf(),
+        };
+    }
+
+    let mut candidates = Vec::new();
+    push_command_candidate(&mut candidates, "/opt/homebrew/bin", command_name);
+    push_command_candidate(&mut candidates, "/usr/local/bin", command_name);
+    append_node_global_candidates(&mut candidates, command_name);
+
+    resolve_local_cli_program(command_name, candidates)
+}
+
 pub fn build_local_std_command(program_path: &Path) -> Command {
     build_local_std_command_impl(program_path)
 }
@@ -205,7 +223,7 @@ fn windows_command_path_priority(path: &Path) -> usize {
 }
 
 fn append_node_global_candidates(candidates: &mut Vec<PathBuf>, command_name: &str) {
-    let home_dir = dirs::home_dir();
+    let home_dir = crate::coding::tools::home_dir();
     append_node_global_candidates_with_home(candidates, command_name, home_dir.as_deref());
 }
 
@@ -478,7 +496,7 @@ fn build_local_command_path(program_path: &Path, current_path: Option<&OsStr>) -
 }
 
 fn append_node_runtime_dirs(dirs: &mut Vec<PathBuf>) {
-    append_node_runtime_dirs_with_home(dirs, dirs::home_dir().as_deref());
+    append_node_runtime_dirs_with_home(dirs, crate::coding::tools::home_dir().as_deref());
 }
 
 fn append_node_runtime_dirs_with_home(dirs: &mut Vec<PathBuf>, home_dir: Option<&Path>) {
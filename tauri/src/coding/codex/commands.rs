@@ -158,10 +158,7 @@ struct RemoteCodexModel {
 
 /// Get Codex config directory path (~/.codex/)
 fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 pub fn get_codex_default_root_dir() -> Result<PathBuf, String> {
@@ -160,11 +160,7 @@ fn home_marketplace_path(
         ));
     }
 
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .ok()
-        .map(PathBuf::from)
-        .map(|home_dir| home_dir.join(MARKETPLACE_RELATIVE_PATH))
+    crate::coding::tools::env_home_dir().map(|home_dir| home_dir.join(MARKETPLACE_RELATIVE_PATH))
 }
 
 fn plugin_manifest_path(plugin_root: &Path) -> PathBuf {
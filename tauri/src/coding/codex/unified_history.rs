@@ -557,15 +557,15 @@ fn sqlite_home_from_env() -> Option<PathBuf> {
 
 fn resolve_user_path(raw: &str) -> PathBuf {
     if raw == "~" {
-        return dirs::home_dir().unwrap_or_else(|| PathBuf::from(raw));
+        return crate::coding::tools::home_dir().unwrap_or_else(|| PathBuf::from(raw));
     }
     if let Some(rest) = raw.strip_prefix("~/") {
-        return dirs::home_dir()
+        return crate::coding::tools::home_dir()
             .map(|home| home.join(rest))
             .unwrap_or_else(|| PathBuf::from(raw));
     }
     if let Some(rest) = raw.strip_prefix("~\\") {
-        return dirs::home_dir()
+        return crate::coding::tools::home_dir()
             .map(|home| home.join(rest))
             .unwrap_or_else(|| PathBuf::from(raw));
     }
@@ -0,0 +1,147 @@
+//! Content-addressed, zstd-compressed storage of snapshot file contents
+//!
+//! Every captured file is stored once as `{root}/blobs/{sha256}.zst`, keyed by
+//! the hash of its uncompressed content, so unchanged configs across many
+//! snapshots cost a single compressed copy. Blobs no snapshot references any
+//! more are removed by `remove_unreferenced_blobs` after retention runs.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use sha2::{Digest, Sha256};
+
+pub const BLOB_DIR_NAME: &str = "blobs";
+const BLOB_EXTENSION: &str = "zst";
+const ZSTD_LEVEL: i32 = 9;
+
+fn is_blob_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub fn blob_path(root: &Path, hash: &str) -> Result<PathBuf, String> {
+    if !is_blob_hash(hash) {
+        return Err(format!("Invalid snapshot blob hash: {}", hash));
+    }
+    Ok(root
+        .join(BLOB_DIR_NAME)
+        .join(format!("{}.{}", hash, BLOB_EXTENSION)))
+}
+
+/// Store `content` unless an identical blob exists.
+/// Returns the content hash and the compressed size on disk.
+pub fn put_blob(root: &Path, content: &[u8]) -> Result<(String, u64), String> {
+    let hash = hex::encode(Sha256::digest(content));
+    let path = blob_path(root, &hash)?;
+    if let Ok(meta) = std::fs::metadata(&path) {
+        // Refresh the mtime so garbage collection treats the reused blob as new
+        let _ = std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        return Ok((hash, meta.len()));
+    }
+
+    let compressed = zstd::encode_all(content, ZSTD_LEVEL)
+        .map_err(|e| format!("Failed to compress snapshot file: {}", e))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    // Write next to the blob and rename so a crash never leaves a torn blob
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, &compressed)
+        .and_then(|_| std::fs::rename(&tmp, &path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            format!("Failed to write {}: {}", path.display(), e)
+        })?;
+    Ok((hash, compressed.len() as u64))
+}
+
+/// Decompressed content of a blob, checked against its hash
+pub fn read_blob(root: &Path, hash: &str) -> Result<Vec<u8>, String> {
+    let path = blob_path(root, hash)?;
+    let compressed =
+        std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let content = zstd::decode_all(compressed.as_slice())
+        .map_err(|e| format!("Failed to decompress {}: {}", path.display(), e))?;
+    if hex::encode(Sha256::digest(&content)) != hash {
+        return Err(format!("Snapshot blob is corrupted: {}", path.display()));
+    }
+    Ok(content)
+}
+
+/// Number and total size of the stored blobs
+pub fn blob_stats(root: &Path) -> (usize, u64) {
+    std::fs::read_dir(root.join(BLOB_DIR_NAME))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .fold((0, 0), |(count, bytes), meta| {
+            (count + 1, bytes + meta.len())
+        })
+}
+
+/// Delete blobs older than `min_age` whose hash is not in `referenced`;
+/// returns the freed bytes
+pub fn remove_unreferenced_blobs(
+    root: &Path,
+    referenced: &HashSet<&str>,
+    min_age: Duration,
+) -> u64 {
+    let mut freed = 0;
+    for entry in std::fs::read_dir(root.join(BLOB_DIR_NAME))
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        let path = entry.path();
+        let hash = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let age = meta
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .unwrap_or_default();
+        if referenced.contains(hash.as_str()) || age < min_age {
+            continue;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            freed += meta.len();
+        }
+    }
+    freed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_content_is_stored_once_and_round_trips() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let content = "{\n  \"theme\": \"dark\"\n}\n".repeat(50);
+
+        let (hash, size) = put_blob(temp.path(), content.as_bytes()).unwrap();
+        assert!(size < content.len() as u64);
+        assert_eq!(put_blob(temp.path(), content.as_bytes()).unwrap().0, hash);
+        assert_eq!(blob_stats(temp.path()).0, 1);
+        assert_eq!(read_blob(temp.path(), &hash).unwrap(), content.as_bytes());
+
+        let (other, _) = put_blob(temp.path(), b"model = \"a\"").unwrap();
+        let referenced = HashSet::from([hash.as_str()]);
+        let day = Duration::from_secs(86_400);
+        assert_eq!(remove_unreferenced_blobs(temp.path(), &referenced, day), 0);
+        assert!(remove_unreferenced_blobs(temp.path(), &referenced, Duration::ZERO) > 0);
+        assert!(read_blob(temp.path(), &other).is_err());
+        assert!(blob_path(temp.path(), "../x").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::diff::{diff_config_snapshot_with_live, diff_config_snapshots};
+use super::snapshot::{
+    apply_retention, build_storage_report, collect_blob_garbage, collect_tool_config_files,
+    create_snapshot, delete_snapshot_files, restore_snapshot, snapshots_root,
+};
+use super::store;
+use super::types::{
+    ConfigFileDiffDto, ConfigSnapshotRecord, ConfigSnapshotRestoreResultDto,
+    ConfigSnapshotSettings, SnapshotStorageReportDto, TRIGGER_MANUAL,
+};
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+#[tauri::command]
+pub async fn config_snapshot_get_settings(
+    state: State<'_, SqliteDbState>,
+) -> Result<ConfigSnapshotSettings, String> {
+    store::load_settings(&state)
+}
+
+#[tauri::command]
+pub async fn config_snapshot_save_settings<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    settings: ConfigSnapshotSettings,
+) -> Result<ConfigSnapshotSettings, String> {
+    ensure_writable("config_snapshot_save_settings")?;
+    let mut settings = settings;
+    settings.interval_hours = settings.interval_hours.max(1);
+    // The scheduler owns `last_snapshot_at`; never let the UI rewind it.
+    settings.last_snapshot_at = store::load_settings(&state)?.last_snapshot_at;
+    store::save_settings(&state, &settings)?;
+    apply_retention(&app, &state, &settings)?;
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn config_snapshot_list(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<ConfigSnapshotRecord>, String> {
+    store::list_snapshots(&state)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn config_snapshot_create<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    toolKey: Option<String>,
+) -> Result<Option<ConfigSnapshotRecord>, String> {
+    ensure_writable("config_snapshot_create")?;
+    create_snapshot(&app, &state, TRIGGER_MANUAL, toolKey.as_deref()).await
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn config_snapshot_restore<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    snapshotId: String,
+    toolKey: Option<String>,
+) -> Result<ConfigSnapshotRestoreResultDto, String> {
+    ensure_writable("config_snapshot_restore")?;
+    let result = restore_snapshot(&app, &state, &snapshotId, toolKey.as_deref()).await?;
+    if !result.restored_files.is_empty() {
+        let _ = app.emit("config-changed", "window");
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn config_snapshot_delete<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    snapshotId: String,
+) -> Result<(), String> {
+    ensure_writable("config_snapshot_delete")?;
+    let record = store::get_snapshot(&state, &snapshotId)?
+        .ok_or_else(|| format!("Config snapshot not found: {}", snapshotId))?;
+    let root = snapshots_root(&app)?;
+    delete_snapshot_files(&root, &record.id)?;
+    store::delete_snapshot(&state, &record.id)?;
+    collect_blob_garbage(&root, &store::list_snapshots(&state)?);
+    Ok(())
+}
+
+/// Disk usage of config snapshots (compressed blobs, legacy plain copies),
+/// with the retention limits that bound them
+#[tauri::command]
+pub async fn get_snapshot_storage_report<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<SnapshotStorageReportDto, String> {
+    let root = snapshots_root(&app)?;
+    let records = store::list_snapshots(&state)?;
+    let settings = store::load_settings(&state)?;
+    tokio::task::spawn_blocking(move || build_storage_report(&root, &records, &settings))
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e))
+}
+
+/// Unified diffs of one tool's config files between two snapshots; without
+/// `toSnapshotId` the snapshot is compared with the current files on disk,
+/// including config files the tool gained since.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn config_snapshot_diff<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    toolKey: String,
+    fromSnapshotId: String,
+    toSnapshotId: Option<String>,
+) -> Result<Vec<ConfigFileDiffDto>, String> {
+    let from = store::get_snapshot(&state, &fromSnapshotId)?
+        .ok_or_else(|| format!("Config snapshot not found: {}", fromSnapshotId))?;
+    let root = snapshots_root(&app)?;
+    match toSnapshotId.as_deref() {
+        Some(snapshot_id) => {
+            let to = store::get_snapshot(&state, snapshot_id)?
+                .ok_or_else(|| format!("Config snapshot not found: {}", snapshot_id))?;
+            diff_config_snapshots(&root, &toolKey, &from, &to)
+        }
+        None => {
+            let live_files = collect_tool_config_files(&state).await;
+            diff_config_snapshot_with_live(&root, &toolKey, &from, &live_files)
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Unified diffs between config snapshots (or a snapshot and the live files)
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use similar::TextDiff;
+
+use super::snapshot::read_snapshot_file;
+use super::types::{ConfigFileDiffDto, ConfigSnapshotRecord};
+
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// One side of a comparison: source path -> file content (None = absent)
+type SideContents = BTreeMap<String, Option<Vec<u8>>>;
+
+/// A snapshot file that cannot be read is an error, not a removed file
+fn snapshot_contents(
+    root: &Path,
+    record: &ConfigSnapshotRecord,
+    tool_key: &str,
+) -> Result<SideContents, String> {
+    record
+        .files
+        .iter()
+        .filter(|file| file.tool_key == tool_key)
+        .map(|file| {
+            let content = read_snapshot_file(root, &record.id, file).map_err(|e| {
+                format!(
+                    "Config snapshot {} is damaged ({}): {}",
+                    record.id, file.source_path, e
+                )
+            })?;
+            Ok((file.source_path.clone(), Some(content)))
+        })
+        .collect()
+}
+
+/// Missing files are absent; any other read error fails the diff
+fn live_contents<'a>(paths: impl IntoIterator<Item = &'a String>) -> Result<SideContents, String> {
+    paths
+        .into_iter()
+        .map(|path| match std::fs::read(PathBuf::from(path)) {
+            Ok(content) => Ok((path.clone(), Some(content))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok((path.clone(), None)),
+            Err(e) => Err(format!("Failed to read {}: {}", path, e)),
+        })
+        .collect()
+}
+
+/// Render a unified diff for one file. Non-UTF-8 content is reported as binary.
+pub fn unified_file_diff(path: &str, old: Option<&[u8]>, new: Option<&[u8]>) -> String {
+    let old_text = old.map(std::str::from_utf8);
+    let new_text = new.map(std::str::from_utf8);
+    if matches!(old_text, Some(Err(_))) || matches!(new_text, Some(Err(_))) {
+        return format!("Binary files a/{path} and b/{path} differ\n");
+    }
+
+    let old_text = old_text.and_then(Result::ok).unwrap_or("");
+    let new_text = new_text.and_then(Result::ok).unwrap_or("");
+    let old_header = if old.is_some() {
+        format!("a/{}", path)
+    } else {
+        "/dev/null".to_string()
+    };
+    let new_header = if new.is_some() {
+        format!("b/{}", path)
+    } else {
+        "/dev/null".to_string()
+    };
+
+    TextDiff::from_lines(old_text, new_text)
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT_LINES)
+        .header(&old_header, &new_header)
+        .to_string()
+}
+
+fn diff_sides(old: &SideContents, new: &SideContents) -> Vec<ConfigFileDiffDto> {
+    let mut paths: Vec<&String> = old.keys().chain(new.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let old_content = old.get(path).cloned().flatten();
+            let new_content = new.get(path).cloned().flatten();
+            let status = match (&old_content, &new_content) {
+                (None, None) => return None,
+                (None, Some(_)) => "added",
+                (Some(_), None) => "removed",
+                (Some(a), Some(b)) if a == b => return None,
+                (Some(_), Some(_)) => "modified",
+            };
+            Some(ConfigFileDiffDto {
+                source_path: path.clone(),
+                status: status.to_string(),
+                diff: unified_file_diff(path, old_content.as_deref(), new_content.as_deref()),
+            })
+        })
+        .collect()
+}
+
+/// Diff one tool's files between two snapshots
+pub fn diff_config_snapshots(
+    root: &Path,
+    tool_key: &str,
+    from: &ConfigSnapshotRecord,
+    to: &ConfigSnapshotRecord,
+) -> Result<Vec<ConfigFileDiffDto>, String> {
+    let old = snapshot_contents(root, from, tool_key)?;
+    let new = snapshot_contents(root, to, tool_key)?;
+    Ok(diff_sides(&old, &new))
+}
+
+/// Diff one tool's files in `from` against the files on disk. `live_files`
+/// are the tool's config files a snapshot would take now
+/// (`collect_tool_config_files`), so files created since show as added.
+pub fn diff_config_snapshot_with_live(
+    root: &Path,
+    tool_key: &str,
+    from: &ConfigSnapshotRecord,
+    live_files: &[(String, PathBuf)],
+) -> Result<Vec<ConfigFileDiffDto>, String> {
+    let old = snapshot_contents(root, from, tool_key)?;
+    let live_paths: BTreeSet<String> = live_files
+        .iter()
+        .filter(|(key, _)| key == tool_key)
+        .map(|(_, path)| path.to_string_lossy().to_string())
+        .chain(old.keys().cloned())
+        .collect();
+    let new = live_contents(&live_paths)?;
+    Ok(diff_sides(&old, &new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::config_snapshot::snapshot::write_snapshot_files;
+    use crate::coding::config_snapshot::types::TRIGGER_MANUAL;
+
+    fn snapshot(root: &Path, id: &str, files: &[(String, PathBuf)]) -> ConfigSnapshotRecord {
+        ConfigSnapshotRecord {
+            id: id.to_string(),
+            trigger: TRIGGER_MANUAL.to_string(),
+            files: write_snapshot_files(root, files).expect("write snapshot"),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn diff_reports_modified_lines_as_unified_diff() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().join("snapshots");
+        let config = temp.path().join(".claude.json");
+        let files = vec![("claude_code".to_string(), config.clone())];
+
+        std::fs::write(&config, "{\n  \"theme\": \"dark\"\n}\n").unwrap();
+        let from = snapshot(&root, "from", &files);
+        std::fs::write(&config, "{\n  \"theme\": \"light\"\n}\n").unwrap();
+        let to = snapshot(&root, "to", &files);
+
+        let diffs = diff_config_snapshots(&root, "claude_code", &from, &to).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].status, "modified");
+        assert!(diffs[0].diff.contains("-  \"theme\": \"dark\""));
+        assert!(diffs[0].diff.contains("+  \"theme\": \"light\""));
+        assert!(diffs[0].diff.starts_with("--- a/"));
+
+        assert!(diff_config_snapshots(&root, "codex", &from, &to)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn diff_against_live_files_reports_removed_files() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().join("snapshots");
+        let config = temp.path().join("config.toml");
+        std::fs::write(&config, "model = \"a\"\n").unwrap();
+        let from = snapshot(&root, "from", &[("codex".to_string(), config.clone())]);
+
+        assert!(diff_config_snapshot_with_live(&root, "codex", &from, &[])
+            .unwrap()
+            .is_empty());
+
+        std::fs::remove_file(&config).unwrap();
+        let diffs = diff_config_snapshot_with_live(&root, "codex", &from, &[]).unwrap();
+        assert_eq!(diffs[0].status, "removed");
+        assert!(diffs[0].diff.contains("+++ /dev/null"));
+    }
+
+    #[test]
+    fn diff_against_live_files_reports_files_created_since() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().join("snapshots");
+        let config = temp.path().join("config.toml");
+        std::fs::write(&config, "model = \"a\"\n").unwrap();
+        let from = snapshot(&root, "from", &[("codex".to_string(), config.clone())]);
+
+        let created = temp.path().join("AGENTS.toml");
+        std::fs::write(&created, "x = 1\n").unwrap();
+        let live = vec![
+            ("codex".to_string(), config.clone()),
+            ("codex".to_string(), created.clone()),
+            ("grok".to_string(), temp.path().join("grok.toml")),
+        ];
+        let diffs = diff_config_snapshot_with_live(&root, "codex", &from, &live).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].source_path, created.to_string_lossy());
+        assert_eq!(diffs[0].status, "added");
+    }
+
+    #[test]
+    fn damaged_snapshots_fail_instead_of_showing_removed_files() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().join("snapshots");
+        let config = temp.path().join("config.toml");
+        std::fs::write(&config, "model = \"a\"\n").unwrap();
+        let from = snapshot(&root, "from", &[("codex".to_string(), config.clone())]);
+        std::fs::remove_dir_all(root.join("blobs")).unwrap();
+
+        assert!(diff_config_snapshot_with_live(&root, "codex", &from, &[]).is_err());
+    }
+}
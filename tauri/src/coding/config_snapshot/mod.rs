@@ -0,0 +1,15 @@
+//! Tool Config Snapshot Module
+//!
+//! Periodically backs up the key config files of detected tools into a
+//! rotating local archive, independent of app-initiated writes, diffs them
+//! over time and restores them on demand.
+
+pub mod blob_store;
+pub mod commands;
+pub mod diff;
+pub mod scheduler;
+pub mod snapshot;
+pub mod store;
+pub mod types;
+
+pub use commands::*;
@@ -0,0 +1,60 @@
+use log::{info, warn};
+use std::time::Duration;
+use tauri::Manager;
+
+use super::snapshot::{apply_retention, create_snapshot, is_snapshot_due};
+use super::store;
+use super::types::{now_ms, TRIGGER_SCHEDULED};
+use crate::db::SqliteDbState;
+
+/// Start the config snapshot scheduler as a background task
+pub fn start_config_snapshot_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Initial delay: let runtime locations and the DB settle first
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        info!("Config snapshot scheduler started");
+
+        loop {
+            // Check every 10 minutes
+            if let Err(e) = check_and_take_snapshot(&app_handle).await {
+                warn!("Config snapshot check failed: {}", e);
+            }
+
+            tokio::time::sleep(Duration::from_secs(600)).await;
+        }
+    });
+}
+
+async fn check_and_take_snapshot(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let state = app_handle.state::<SqliteDbState>();
+    let mut settings = store::load_settings(&state)?;
+
+    let now = now_ms();
+    if !settings.enabled
+        || !is_snapshot_due(settings.last_snapshot_at, settings.interval_hours, now)
+    {
+        return Ok(());
+    }
+
+    match create_snapshot(app_handle, &state, TRIGGER_SCHEDULED, None).await? {
+        Some(record) => info!(
+            "Config snapshot {} captured {} file(s)",
+            record.id,
+            record.files.len()
+        ),
+        None => info!("Config snapshot skipped: no changes since last snapshot"),
+    }
+
+    settings.last_snapshot_at = Some(now);
+    store::save_settings(&state, &settings)?;
+
+    let removed = apply_retention(app_handle, &state, &settings)?;
+    if removed > 0 {
+        info!(
+            "Config snapshot retention removed {} snapshot(s) or variant(s)",
+            removed
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,506 @@
+//! Tool config snapshot capture, retention and restore
+//!
+//! Snapshot file contents live as compressed blobs under
+//! `{app_data_dir}/config-snapshots/blobs/` (see `blob_store.rs`); snapshots
+//! taken before that keep plain copies in `{app_data_dir}/config-snapshots/{snapshot_id}/`.
+//! The SQLite `config_snapshot` table only keeps the index.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager, Runtime};
+
+use super::blob_store;
+use super::store;
+use super::types::{
+    now_ms, ConfigSnapshotFile, ConfigSnapshotRecord, ConfigSnapshotRestoreResultDto,
+    ConfigSnapshotSettings, SnapshotStorageReportDto, TRIGGER_PRE_RESTORE, TRIGGER_SCHEDULED,
+};
+use crate::coding::db_id::db_new_id;
+use crate::coding::runtime_location;
+use crate::coding::skills::variants;
+use crate::coding::tools::custom_store::get_custom_tools;
+use crate::coding::tools::{get_mcp_runtime_tools, resolve_mcp_config_path_with_db_async};
+use crate::SqliteDbState;
+
+const SNAPSHOT_DIR_NAME: &str = "config-snapshots";
+/// Blobs written this recently are never collected, so a snapshot being
+/// captured while retention runs keeps its content
+const BLOB_GC_MIN_AGE: Duration = Duration::from_secs(600);
+
+pub fn snapshots_root<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(SNAPSHOT_DIR_NAME))
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+}
+
+/// Collect the key config files of every detected tool (only files that
+/// currently exist). Auth/credential files are deliberately not included.
+pub async fn collect_tool_config_files(state: &SqliteDbState) -> Vec<(String, PathBuf)> {
+    let db = state.db();
+    let mut candidates: Vec<(String, Result<PathBuf, String>)> = vec![
+        (
+            "claude_code".to_string(),
+            runtime_location::get_claude_settings_path_async(db).await,
+        ),
+        (
+            "claude_code".to_string(),
+            runtime_location::get_claude_mcp_config_path_async(db).await,
+        ),
+        (
+            "codex".to_string(),
+            runtime_location::get_codex_config_path_async(db).await,
+        ),
+        (
+            "grok".to_string(),
+            runtime_location::get_grok_config_path_async(db).await,
+        ),
+        (
+            "gemini_cli".to_string(),
+            runtime_location::get_gemini_cli_settings_path_async(db).await,
+        ),
+        (
+            "opencode".to_string(),
+            runtime_location::get_opencode_runtime_location_async(db)
+                .await
+                .map(|location| location.host_path),
+        ),
+    ];
+
+    let custom_tools = get_custom_tools(state).await.unwrap_or_default();
+    for tool in get_mcp_runtime_tools(&custom_tools) {
+        if let Some(path) = resolve_mcp_config_path_with_db_async(db, &tool).await {
+            candidates.push((tool.key.clone(), Ok(path)));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter_map(|(tool_key, path)| path.ok().map(|path| (tool_key, path)))
+        .filter(|(_, path)| path.is_file())
+        .filter(|(_, path)| seen.insert(path.clone()))
+        .collect()
+}
+
+pub fn hash_file(path: &Path) -> Result<String, String> {
+    let content =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(hex::encode(Sha256::digest(&content)))
+}
+
+/// Store files in the blob store under `root`, naming them
+/// `{tool_key}/{file_name}` and de-duplicating file names within one tool
+/// (e.g. two `settings.json`).
+pub fn write_snapshot_files(
+    root: &Path,
+    files: &[(String, PathBuf)],
+) -> Result<Vec<ConfigSnapshotFile>, String> {
+    let mut used = HashSet::new();
+    let mut result = Vec::with_capacity(files.len());
+
+    for (tool_key, source) in files {
+        let file_name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "config".to_string());
+        let mut stored_path = format!("{}/{}", tool_key, file_name);
+        let mut index = 1;
+        while !used.insert(stored_path.clone()) {
+            index += 1;
+            stored_path = format!("{}/{}-{}", tool_key, index, file_name);
+        }
+
+        let content = std::fs::read(source)
+            .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        let (hash, stored_size) = blob_store::put_blob(root, &content)?;
+
+        result.push(ConfigSnapshotFile {
+            tool_key: tool_key.clone(),
+            source_path: source.to_string_lossy().to_string(),
+            stored_path,
+            size: content.len() as u64,
+            hash,
+            compressed: true,
+            stored_size,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Content of one file of snapshot `snapshot_id`
+pub fn read_snapshot_file(
+    root: &Path,
+    snapshot_id: &str,
+    file: &ConfigSnapshotFile,
+) -> Result<Vec<u8>, String> {
+    if file.compressed {
+        return blob_store::read_blob(root, &file.hash);
+    }
+    let path = snapshot_dir(root, snapshot_id)?.join(&file.stored_path);
+    std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// True when every file (path + content) matches the previous snapshot
+pub fn files_unchanged(previous: &ConfigSnapshotRecord, files: &[(String, PathBuf)]) -> bool {
+    if previous.files.len() != files.len() {
+        return false;
+    }
+    files.iter().all(|(_, path)| {
+        let source_path = path.to_string_lossy();
+        let Ok(hash) = hash_file(path) else {
+            return false;
+        };
+        previous
+            .files
+            .iter()
+            .any(|file| file.source_path == source_path && file.hash == hash)
+    })
+}
+
+/// Snapshot ids to delete so that only the newest `retain_count` remain.
+/// `records` must be sorted newest first.
+pub fn select_expired_snapshots(
+    records: &[ConfigSnapshotRecord],
+    retain_count: u32,
+) -> Vec<String> {
+    if retain_count == 0 {
+        return Vec::new();
+    }
+    records
+        .iter()
+        .skip(retain_count as usize)
+        .map(|record| record.id.clone())
+        .collect()
+}
+
+/// Snapshot ids to delete so that the store fits in `max_total_bytes`
+/// (0 = no cap), dropping the oldest first. A blob shared by several
+/// snapshots counts once and the newest snapshot is always kept. `records` must be sorted
+/// newest first.
+pub fn select_oversized_snapshots(
+    records: &[ConfigSnapshotRecord],
+    max_total_bytes: u64,
+) -> Vec<String> {
+    if max_total_bytes == 0 {
+        return Vec::new();
+    }
+    let mut seen = HashSet::new();
+    let mut total = 0;
+    for (index, record) in records.iter().enumerate() {
+        for file in &record.files {
+            if !file.compressed || seen.insert(file.hash.as_str()) {
+                total += file.stored_bytes();
+            }
+        }
+        if total > max_total_bytes && index > 0 {
+            return records[index..]
+                .iter()
+                .map(|record| record.id.clone())
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+pub fn is_snapshot_due(last_snapshot_at: Option<i64>, interval_hours: u32, now: i64) -> bool {
+    match last_snapshot_at {
+        None => true,
+        Some(last) => now - last >= i64::from(interval_hours.max(1)) * 3_600_000,
+    }
+}
+
+/// Directory of the legacy plain copies of `snapshot_id`. The id comes from
+/// the frontend, so it must be a bare record id: no separators, no `..` and
+/// none of the shared dirs under `root` (blobs, skill variants).
+pub fn snapshot_dir(root: &Path, snapshot_id: &str) -> Result<PathBuf, String> {
+    let valid = !snapshot_id.is_empty()
+        && snapshot_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && snapshot_id != blob_store::BLOB_DIR_NAME
+        && snapshot_id != variants::VARIANTS_DIR_NAME;
+    if !valid {
+        return Err(format!("Invalid config snapshot id: {}", snapshot_id));
+    }
+    Ok(root.join(snapshot_id))
+}
+
+pub fn delete_snapshot_files(root: &Path, snapshot_id: &str) -> Result<(), String> {
+    let dir = snapshot_dir(root, snapshot_id)?;
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to remove {}: {}", dir.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Capture a snapshot of the current tool config files.
+///
+/// Scheduled snapshots are skipped (`Ok(None)`) when nothing changed since the
+/// latest snapshot, so the retention window covers real history.
+pub async fn create_snapshot<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    trigger: &str,
+    tool_key: Option<&str>,
+) -> Result<Option<ConfigSnapshotRecord>, String> {
+    let files: Vec<(String, PathBuf)> = collect_tool_config_files(state)
+        .await
+        .into_iter()
+        .filter(|(key, _)| tool_key.map(|wanted| wanted == key).unwrap_or(true))
+        .collect();
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    if trigger == TRIGGER_SCHEDULED {
+        if let Some(latest) = store::list_snapshots(state)?
+            .into_iter()
+            .find(|record| record.trigger == TRIGGER_SCHEDULED)
+        {
+            if files_unchanged(&latest, &files) {
+                return Ok(None);
+            }
+        }
+    }
+
+    // Blobs of a failed capture are unreferenced and collected by retention
+    let stored_files = write_snapshot_files(&snapshots_root(app)?, &files)?;
+
+    let record = ConfigSnapshotRecord {
+        id: db_new_id(),
+        trigger: trigger.to_string(),
+        files: stored_files,
+        created_at: now_ms(),
+    };
+    store::put_snapshot(state, &record)?;
+    Ok(Some(record))
+}
+
+/// Apply the retention policy: keep the newest `retain_count` snapshots,
+/// drop the oldest while the store is larger than `max_total_mb` and remove
+/// blobs nothing references. Returns the number of removed snapshots.
+/// Skill variants have their own blob store and retention (see
+/// `skills::variants`).
+pub fn apply_retention<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    settings: &ConfigSnapshotSettings,
+) -> Result<usize, String> {
+    let root = snapshots_root(app)?;
+    let mut records = store::list_snapshots(state)?;
+    let mut expired = select_expired_snapshots(&records, settings.retain_count);
+    records.retain(|record| !expired.contains(&record.id));
+    let oversized =
+        select_oversized_snapshots(&records, u64::from(settings.max_total_mb) * 1024 * 1024);
+    records.retain(|record| !oversized.contains(&record.id));
+    expired.extend(oversized);
+
+    for snapshot_id in &expired {
+        delete_snapshot_files(&root, snapshot_id)?;
+        store::delete_snapshot(state, snapshot_id)?;
+    }
+    collect_blob_garbage(&root, &records);
+    Ok(expired.len())
+}
+
+/// Remove blobs no snapshot in `records` references; returns the freed bytes
+pub fn collect_blob_garbage(root: &Path, records: &[ConfigSnapshotRecord]) -> u64 {
+    let referenced: HashSet<&str> = records
+        .iter()
+        .flat_map(|record| &record.files)
+        .filter(|file| file.compressed)
+        .map(|file| file.hash.as_str())
+        .collect();
+    blob_store::remove_unreferenced_blobs(root, &referenced, BLOB_GC_MIN_AGE)
+}
+
+pub fn build_storage_report(
+    root: &Path,
+    records: &[ConfigSnapshotRecord],
+    settings: &ConfigSnapshotSettings,
+) -> SnapshotStorageReportDto {
+    let (blob_count, blob_bytes) = blob_store::blob_stats(root);
+    let files = records.iter().flat_map(|record| &record.files);
+    SnapshotStorageReportDto {
+        snapshot_count: records.len(),
+        file_count: files.clone().count(),
+        logical_bytes: files.clone().map(|file| file.size).sum(),
+        blob_bytes,
+        blob_count,
+        legacy_bytes: files
+            .filter(|file| !file.compressed)
+            .map(|file| file.size)
+            .sum(),
+        retain_count: settings.retain_count,
+        max_total_mb: settings.max_total_mb,
+    }
+}
+
+/// Restore files from a snapshot back to their original paths.
+///
+/// The current files are captured first as a `pre_restore` snapshot so a
+/// restore can itself be undone.
+pub async fn restore_snapshot<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    snapshot_id: &str,
+    tool_key: Option<&str>,
+) -> Result<ConfigSnapshotRestoreResultDto, String> {
+    let record = store::get_snapshot(state, snapshot_id)?
+        .ok_or_else(|| format!("Config snapshot not found: {}", snapshot_id))?;
+    let root = snapshots_root(app)?;
+
+    let pre_restore = create_snapshot(app, state, TRIGGER_PRE_RESTORE, tool_key).await?;
+
+    let mut restored_files = Vec::new();
+    let mut errors = Vec::new();
+    for file in record.files.iter().filter(|file| {
+        tool_key
+            .map(|wanted| wanted == file.tool_key)
+            .unwrap_or(true)
+    }) {
+        let target = PathBuf::from(&file.source_path);
+        let result = read_snapshot_file(&root, &record.id, file).and_then(|content| {
+            target
+                .parent()
+                .map(std::fs::create_dir_all)
+                .transpose()
+                .and_then(|_| std::fs::write(&target, content))
+                .map_err(|e| e.to_string())
+        });
+        match result {
+            Ok(_) => restored_files.push(file.source_path.clone()),
+            Err(e) => errors.push(format!("{}: {}", file.source_path, e)),
+        }
+    }
+
+    Ok(ConfigSnapshotRestoreResultDto {
+        restored_files,
+        pre_restore_snapshot_id: pre_restore.map(|record| record.id),
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, files: Vec<ConfigSnapshotFile>) -> ConfigSnapshotRecord {
+        ConfigSnapshotRecord {
+            id: id.to_string(),
+            trigger: TRIGGER_SCHEDULED.to_string(),
+            files,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn write_snapshot_files_dedupes_names_per_tool() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let a = temp.path().join("a").join("settings.json");
+        let b = temp.path().join("b").join("settings.json");
+        for path in [&a, &b] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "{}").unwrap();
+        }
+
+        let root = temp.path().join("snap");
+        let files = write_snapshot_files(
+            &root,
+            &[
+                ("claude_code".to_string(), a.clone()),
+                ("claude_code".to_string(), b.clone()),
+            ],
+        )
+        .expect("write snapshot");
+
+        assert_eq!(files[0].stored_path, "claude_code/settings.json");
+        assert_eq!(files[1].stored_path, "claude_code/2-settings.json");
+        assert_eq!(files[0].hash, hash_file(&a).unwrap());
+        // Identical content is stored once
+        assert_eq!(files[0].hash, files[1].hash);
+        assert_eq!(blob_store::blob_stats(&root).0, 1);
+        assert_eq!(read_snapshot_file(&root, "1", &files[1]).unwrap(), b"{}");
+    }
+
+    #[test]
+    fn files_unchanged_detects_content_changes() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config = temp.path().join("config.toml");
+        std::fs::write(&config, "model = \"a\"").unwrap();
+        let input = vec![("codex".to_string(), config.clone())];
+
+        let stored = write_snapshot_files(&temp.path().join("snap"), &input).unwrap();
+        let previous = record("1", stored);
+        assert!(files_unchanged(&previous, &input));
+
+        std::fs::write(&config, "model = \"b\"").unwrap();
+        assert!(!files_unchanged(&previous, &input));
+    }
+
+    #[test]
+    fn retention_keeps_newest_records() {
+        let records: Vec<_> = ["c", "b", "a"]
+            .iter()
+            .map(|id| record(id, vec![]))
+            .collect();
+        assert_eq!(select_expired_snapshots(&records, 2), vec!["a".to_string()]);
+        assert!(select_expired_snapshots(&records, 0).is_empty());
+        assert!(select_expired_snapshots(&records, 5).is_empty());
+    }
+
+    #[test]
+    fn size_cap_drops_oldest_and_counts_shared_blobs_once() {
+        let file = |hash: &str, stored_size: u64| ConfigSnapshotFile {
+            tool_key: "codex".to_string(),
+            source_path: "config.toml".to_string(),
+            stored_path: "codex/config.toml".to_string(),
+            size: stored_size * 4,
+            hash: hash.to_string(),
+            compressed: true,
+            stored_size,
+        };
+        let records = vec![
+            record("d", vec![file("x", 40)]),
+            record("c", vec![file("x", 40)]),
+            record("b", vec![file("y", 40)]),
+            record("a", vec![file("z", 40)]),
+        ];
+        assert_eq!(
+            select_oversized_snapshots(&records, 100),
+            vec!["a".to_string()]
+        );
+        assert!(select_oversized_snapshots(&records, 0).is_empty());
+        // The newest snapshot survives even when it alone exceeds the cap
+        assert_eq!(select_oversized_snapshots(&records, 10).len(), 3);
+    }
+
+    #[test]
+    fn delete_rejects_ids_outside_the_snapshot_dirs() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().join("snap");
+        let config = temp.path().join("config.toml");
+        std::fs::write(&config, "model = \"a\"").unwrap();
+        write_snapshot_files(&root, &[("codex".to_string(), config)]).unwrap();
+
+        for bogus in ["blobs", "skill-variants", "..", "../snap", "a/b", ""] {
+            assert!(delete_snapshot_files(&root, bogus).is_err(), "{}", bogus);
+        }
+        assert_eq!(blob_store::blob_stats(&root).0, 1);
+        assert!(delete_snapshot_files(&root, "0123abcd").is_ok());
+    }
+
+    #[test]
+    fn snapshot_due_respects_interval() {
+        let hour = 3_600_000;
+        assert!(is_snapshot_due(None, 24, 0));
+        assert!(!is_snapshot_due(Some(0), 24, 23 * hour));
+        assert!(is_snapshot_due(Some(0), 24, 24 * hour));
+    }
+}
@@ -0,0 +1,74 @@
+use super::types::{ConfigSnapshotRecord, ConfigSnapshotSettings};
+use crate::coding::db_id::db_clean_id;
+use crate::db::helpers::{db_delete, db_get, db_list, db_put};
+use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
+use crate::SqliteDbState;
+
+const SETTINGS_ID: &str = "settings";
+
+fn snapshot_order() -> Result<OrderSpec, String> {
+    Ok(OrderSpec::single(OrderField::json_integer(
+        "created_at",
+        OrderDirection::Desc,
+    )?))
+}
+
+fn sqlite_value_to_snapshot(value: serde_json::Value) -> Result<ConfigSnapshotRecord, String> {
+    serde_json::from_value(value)
+        .map(|mut record: ConfigSnapshotRecord| {
+            record.id = db_clean_id(&record.id);
+            record
+        })
+        .map_err(|error| format!("Failed to parse SQLite config snapshot: {error}"))
+}
+
+pub fn load_settings(state: &SqliteDbState) -> Result<ConfigSnapshotSettings, String> {
+    state.with_conn(|conn| {
+        Ok(db_get(conn, DbTable::ConfigSnapshotSettings, SETTINGS_ID)?
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default())
+    })
+}
+
+pub fn save_settings(
+    state: &SqliteDbState,
+    settings: &ConfigSnapshotSettings,
+) -> Result<(), String> {
+    let data = serde_json::to_value(settings)
+        .map_err(|error| format!("Failed to serialize config snapshot settings: {error}"))?;
+    state.with_conn(|conn| db_put(conn, DbTable::ConfigSnapshotSettings, SETTINGS_ID, &data))
+}
+
+/// List snapshots, newest first
+pub fn list_snapshots(state: &SqliteDbState) -> Result<Vec<ConfigSnapshotRecord>, String> {
+    let order = snapshot_order()?;
+    state.with_conn(|conn| {
+        db_list(conn, DbTable::ConfigSnapshot, Some(&order))?
+            .into_iter()
+            .map(sqlite_value_to_snapshot)
+            .collect()
+    })
+}
+
+pub fn get_snapshot(
+    state: &SqliteDbState,
+    snapshot_id: &str,
+) -> Result<Option<ConfigSnapshotRecord>, String> {
+    state.with_conn(|conn| {
+        db_get(conn, DbTable::ConfigSnapshot, &db_clean_id(snapshot_id))?
+            .map(sqlite_value_to_snapshot)
+            .transpose()
+    })
+}
+
+pub fn put_snapshot(state: &SqliteDbState, record: &ConfigSnapshotRecord) -> Result<(), String> {
+    let data = serde_json::to_value(record)
+        .map_err(|error| format!("Failed to serialize config snapshot: {error}"))?;
+    state.with_conn(|conn| db_put(conn, DbTable::ConfigSnapshot, &record.id, &data))
+}
+
+pub fn delete_snapshot(state: &SqliteDbState, snapshot_id: &str) -> Result<(), String> {
+    state.with_conn(|conn| {
+        db_delete(conn, DbTable::ConfigSnapshot, &db_clean_id(snapshot_id)).map(|_| ())
+    })
+}
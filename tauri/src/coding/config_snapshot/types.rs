@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+pub use crate::coding::tools::now_ms;
+
+pub const TRIGGER_SCHEDULED: &str = "scheduled";
+pub const TRIGGER_MANUAL: &str = "manual";
+pub const TRIGGER_PRE_RESTORE: &str = "pre_restore";
+
+fn default_interval_hours() -> u32 {
+    24
+}
+
+fn default_retain_count() -> u32 {
+    14
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_total_mb() -> u32 {
+    256
+}
+
+/// Scheduler settings for tool config snapshots
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfigSnapshotSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u32,
+    /// Number of snapshots to keep; 0 keeps everything
+    #[serde(default = "default_retain_count")]
+    pub retain_count: u32,
+    /// Cap on the stored (compressed, de-duplicated) size in MB; oldest
+    /// snapshots go first. 0 means no cap
+    #[serde(default = "default_max_total_mb")]
+    pub max_total_mb: u32,
+    #[serde(default)]
+    pub last_snapshot_at: Option<i64>,
+}
+
+impl Default for ConfigSnapshotSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            interval_hours: default_interval_hours(),
+            retain_count: default_retain_count(),
+            max_total_mb: default_max_total_mb(),
+            last_snapshot_at: None,
+        }
+    }
+}
+
+/// One tool config file captured in a snapshot
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfigSnapshotFile {
+    pub tool_key: String,
+    /// Host path the file was read from, used as the restore target
+    pub source_path: String,
+    /// Path relative to the snapshot directory; for compressed files only a
+    /// display name, the content lives in the blob store under `hash`
+    pub stored_path: String,
+    pub size: u64,
+    pub hash: String,
+    /// Stored as a zstd blob (see `blob_store.rs`); older snapshots keep
+    /// plain copies in their own directory
+    #[serde(default)]
+    pub compressed: bool,
+    /// Size of the compressed blob
+    #[serde(default)]
+    pub stored_size: u64,
+}
+
+impl ConfigSnapshotFile {
+    /// Bytes this file occupies in the snapshot store
+    pub fn stored_bytes(&self) -> u64 {
+        if self.compressed {
+            self.stored_size
+        } else {
+            self.size
+        }
+    }
+}
+
+/// Snapshot index record (SQLite `config_snapshot` table)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfigSnapshotRecord {
+    pub id: String,
+    /// "scheduled" | "manual" | "pre_restore"
+    pub trigger: String,
+    pub files: Vec<ConfigSnapshotFile>,
+    pub created_at: i64,
+}
+
+/// Result of restoring a snapshot
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigSnapshotRestoreResultDto {
+    pub restored_files: Vec<String>,
+    /// Snapshot taken of the current files right before they were overwritten
+    pub pre_restore_snapshot_id: Option<String>,
+    pub errors: Vec<String>,
+}
+
+/// Unified diff of one config file between two points in time
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigFileDiffDto {
+    pub source_path: String,
+    /// "added" | "removed" | "modified"
+    pub status: String,
+    pub diff: String,
+}
+
+/// Disk usage of the snapshot store (`get_snapshot_storage_report`)
+#[derive(Clone, Debug, Serialize)]
+pub struct SnapshotStorageReportDto {
+    pub snapshot_count: usize,
+    pub file_count: usize,
+    /// Uncompressed size of every file in every snapshot
+    pub logical_bytes: u64,
+    /// Size on disk of the compressed blobs
+    pub blob_bytes: u64,
+    pub blob_count: usize,
+    /// Size of plain copies kept by snapshots taken before compression
+    pub legacy_bytes: u64,
+    pub retain_count: u32,
+    pub max_total_mb: u32,
+}
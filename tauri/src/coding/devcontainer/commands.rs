@@ -0,0 +1,281 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Runtime, State};
+
+use super::generator::{
+    build_post_create_script, bundle_dir, container_home_path, find_devcontainer_json,
+    has_jsonc_comments, local_env_placeholder, merge_devcontainer_json, post_create_command,
+    read_selection, redact_server_secrets, write_selection, McpConfigInstallPlan, ProvisionPlan,
+    SkillInstallPlan, SCRIPT_FILE,
+};
+use super::types::{DevcontainerGenerateResultDto, DevcontainerSelection};
+use crate::coding::expand_local_path;
+use crate::coding::mcp::config_sync::write_servers_to_staged_config;
+use crate::coding::mcp::mcp_store;
+use crate::coding::mcp::types::McpServer;
+use crate::coding::skills::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
+use crate::coding::skills::skill_store;
+use crate::coding::skills::sync_engine::{copy_skill_dir, ensure_source_dir};
+use crate::coding::tools::custom_store::get_custom_tools;
+use crate::coding::tools::{now_ms, runtime_tool_by_key};
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+fn resolve_project_dir(project_path: &str) -> Result<PathBuf, String> {
+    let trimmed = project_path.trim();
+    if trimmed.is_empty() {
+        return Err("Project path is required".to_string());
+    }
+    let path = PathBuf::from(expand_local_path(trimmed)?);
+    if !path.is_dir() {
+        return Err(format!("Project directory not found: {}", path.display()));
+    }
+    Ok(path)
+}
+
+fn reset_dir(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        std::fs::remove_dir_all(path)
+            .map_err(|e| format!("Failed to clear {}: {}", path.display(), e))?;
+    }
+    std::fs::create_dir_all(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))
+}
+
+fn effective_tool_keys<'a>(
+    selection: &'a DevcontainerSelection,
+    own: &'a [String],
+) -> &'a [String] {
+    if selection.tool_keys.is_empty() {
+        own
+    } else {
+        &selection.tool_keys
+    }
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn devcontainer_get_selection(
+    projectPath: String,
+) -> Result<DevcontainerSelection, String> {
+    let project_dir = resolve_project_dir(&projectPath)?;
+    read_selection(&project_dir)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn devcontainer_save_selection(
+    projectPath: String,
+    selection: DevcontainerSelection,
+) -> Result<(), String> {
+    ensure_writable("devcontainer_save_selection")?;
+    let project_dir = resolve_project_dir(&projectPath)?;
+    let mut selection = selection;
+    selection.updated_at = now_ms();
+    write_selection(&project_dir, &selection)
+}
+
+/// Generate `.devcontainer/ai-toolbox/` from the project's saved selection and
+/// hook its provisioning script into devcontainer.json `postCreateCommand`.
+/// MCP env values are forwarded from the host through `remoteEnv` instead of
+/// being written into the bundle.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn devcontainer_generate<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    projectPath: String,
+) -> Result<DevcontainerGenerateResultDto, String> {
+    ensure_writable("devcontainer_generate")?;
+    let project_dir = resolve_project_dir(&projectPath)?;
+    let selection = read_selection(&project_dir)?;
+    let bundle = bundle_dir(&project_dir);
+    let custom_tools = get_custom_tools(&state).await?;
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(|e| format!("{:#}", e))?;
+
+    let skills_dir = bundle.join("skills");
+    let mcp_dir = bundle.join("mcp");
+    reset_dir(&skills_dir)?;
+    reset_dir(&mcp_dir)?;
+
+    let mut plan = ProvisionPlan::default();
+    let mut warnings = Vec::new();
+
+    for skill_id in &selection.skill_ids {
+        let Some(skill) = skill_store::get_skill_by_id(&state, skill_id).await? else {
+            warnings.push(format!("Skill {} no longer exists", skill_id));
+            continue;
+        };
+        // Disabled skills must never be written to a tool directory, the
+        // container home included.
+        if !skill.management_enabled {
+            warnings.push(format!("Skill {} is disabled, skipped", skill.name));
+            continue;
+        }
+        let source = resolve_skill_central_path(&skill.central_path, &central_dir);
+        if let Err(err) = ensure_source_dir(&source) {
+            warnings.push(format!("Skill {}: {:#}", skill.name, err));
+            continue;
+        }
+        copy_skill_dir(&source, &skills_dir.join(&skill.name))
+            .map_err(|e| format!("Failed to bundle skill {}: {:#}", skill.name, e))?;
+
+        let mut target_dirs = Vec::new();
+        for tool_key in effective_tool_keys(&selection, &skill.enabled_tools) {
+            let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) else {
+                continue;
+            };
+            let Some(skills_path) = tool.relative_skills_dir.as_deref() else {
+                continue;
+            };
+            match container_home_path(skills_path) {
+                Some(dir) => target_dirs.push(dir),
+                None => warnings.push(format!(
+                    "{} skills path {} is not home-relative, skipped",
+                    tool.display_name, skills_path
+                )),
+            }
+        }
+        plan.skills.push(SkillInstallPlan {
+            name: skill.name.clone(),
+            target_dirs,
+        });
+    }
+
+    let mut servers: Vec<McpServer> = Vec::new();
+    let mut env_names: BTreeSet<String> = BTreeSet::new();
+    for server_id in &selection.mcp_server_ids {
+        let Some(mut server) = mcp_store::get_mcp_server_by_id(&state, server_id).await? else {
+            warnings.push(format!("MCP server {} no longer exists", server_id));
+            continue;
+        };
+        // Like disabled skills, servers enabled for no tool stay out of the
+        // container.
+        if server.enabled_tools.is_empty() {
+            warnings.push(format!(
+                "MCP server {} is not enabled for any tool, skipped",
+                server.name
+            ));
+            continue;
+        }
+        let redacted = redact_server_secrets(&mut server.server_config);
+        for name in &redacted.dropped_env {
+            warnings.push(format!(
+                "MCP server {}: env {} is not a valid variable name, left out",
+                server.name, name
+            ));
+        }
+        for name in &redacted.dropped_headers {
+            warnings.push(format!(
+                "MCP server {}: header {} is left out, add it inside the container",
+                server.name, name
+            ));
+        }
+        env_names.extend(redacted.env_names);
+        servers.push(server);
+    }
+
+    let mcp_tool_keys: BTreeSet<String> = servers
+        .iter()
+        .flat_map(|server| server.enabled_tools.iter().cloned())
+        .filter(|key| selection.tool_keys.is_empty() || selection.tool_keys.contains(key))
+        .collect();
+
+    for tool_key in &mcp_tool_keys {
+        let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) else {
+            continue;
+        };
+        let Some(config_path) = tool.mcp_config_path.as_deref() else {
+            continue;
+        };
+        let Some(target_path) = container_home_path(config_path) else {
+            warnings.push(format!(
+                "{} MCP config {} is not home-relative, skipped",
+                tool.display_name, config_path
+            ));
+            continue;
+        };
+        let tool_servers: Vec<&McpServer> = servers
+            .iter()
+            .filter(|server| server.enabled_tools.contains(tool_key))
+            .collect();
+        if tool_servers.is_empty() {
+            continue;
+        }
+
+        let format = match tool.mcp_config_format.as_deref() {
+            Some("toml") => "toml",
+            _ => "json",
+        };
+        let staged_file = format!("mcp/{}.{}", tool.key, format);
+        write_servers_to_staged_config(&tool, &bundle.join(&staged_file), &tool_servers)?;
+        plan.mcp_configs.push(McpConfigInstallPlan {
+            staged_file,
+            target_path,
+            format: format.to_string(),
+        });
+    }
+
+    let script_path = bundle.join(SCRIPT_FILE);
+    std::fs::write(&script_path, build_post_create_script(&plan))
+        .map_err(|e| format!("Failed to write {}: {}", script_path.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to mark script executable: {}", e))?;
+    }
+
+    let command = post_create_command();
+    let env_names: Vec<String> = env_names.into_iter().collect();
+    let manual_steps = || {
+        let mut steps = format!("\"postCreateCommand\": \"{}\"", command);
+        if !env_names.is_empty() {
+            let entries: Vec<String> = env_names
+                .iter()
+                .map(|name| format!("\"{}\": \"{}\"", name, local_env_placeholder(name)))
+                .collect();
+            steps.push_str(&format!(" and \"remoteEnv\": {{ {} }}", entries.join(", ")));
+        }
+        steps
+    };
+    let devcontainer_json_path = match find_devcontainer_json(&project_dir) {
+        Some(path) => {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            if has_jsonc_comments(&content) {
+                // Rewriting would drop the comments; leave the file alone.
+                warnings.push(format!(
+                    "{} has comments and was not changed; add {} manually",
+                    path.display(),
+                    manual_steps()
+                ));
+                None
+            } else {
+                let merged = merge_devcontainer_json(&content, &command, &env_names)?;
+                std::fs::write(&path, merged)
+                    .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                Some(path.to_string_lossy().to_string())
+            }
+        }
+        None => {
+            warnings.push(format!(
+                "No devcontainer.json found; add {} manually",
+                manual_steps()
+            ));
+            None
+        }
+    };
+
+    Ok(DevcontainerGenerateResultDto {
+        bundle_dir: bundle.to_string_lossy().to_string(),
+        script_path: script_path.to_string_lossy().to_string(),
+        post_create_command: command,
+        devcontainer_json_path,
+        skill_count: plan.skills.len(),
+        mcp_config_count: plan.mcp_configs.len(),
+        warnings,
+    })
+}
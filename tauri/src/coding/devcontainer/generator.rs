@@ -0,0 +1,421 @@
+//! Dev container provisioning bundle generator
+//!
+//! Pure helpers that lay out `.devcontainer/ai-toolbox/`, render the
+//! `postCreateCommand` script and merge it into `devcontainer.json`.
+//! Everything here is filesystem/text only; DB access lives in `commands.rs`.
+//!
+//! The bundle lives in the project and usually gets committed, so no secret
+//! is written into it: MCP env values are replaced by `remoteEnv` entries
+//! that forward the host variable (`${localEnv:NAME}`) into the container,
+//! and MCP headers are left out.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::types::DevcontainerSelection;
+
+/// Bundle directory relative to the project root
+pub const BUNDLE_RELATIVE_DIR: &str = ".devcontainer/ai-toolbox";
+pub const SELECTION_FILE: &str = "selection.json";
+pub const SCRIPT_FILE: &str = "post-create.sh";
+/// Key used when `postCreateCommand` has to be converted to the object form
+pub const POST_CREATE_KEY: &str = "ai-toolbox";
+
+/// Skill to copy into the container central dir and link into tool dirs
+#[derive(Clone, Debug, Default)]
+pub struct SkillInstallPlan {
+    pub name: String,
+    /// Container-side skills directories (shell expressions rooted at `$HOME`)
+    pub target_dirs: Vec<String>,
+}
+
+/// Staged MCP config file to install into a container tool config path
+#[derive(Clone, Debug, Default)]
+pub struct McpConfigInstallPlan {
+    /// Path relative to the bundle directory, e.g. `mcp/claude_code.json`
+    pub staged_file: String,
+    /// Container-side config path (shell expression rooted at `$HOME`)
+    pub target_path: String,
+    /// "json" | "toml"
+    pub format: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProvisionPlan {
+    pub skills: Vec<SkillInstallPlan>,
+    pub mcp_configs: Vec<McpConfigInstallPlan>,
+}
+
+pub fn bundle_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(BUNDLE_RELATIVE_DIR)
+}
+
+pub fn post_create_command() -> String {
+    format!("sh {}/{}", BUNDLE_RELATIVE_DIR, SCRIPT_FILE)
+}
+
+pub fn read_selection(project_dir: &Path) -> Result<DevcontainerSelection, String> {
+    let path = bundle_dir(project_dir).join(SELECTION_FILE);
+    if !path.exists() {
+        return Ok(DevcontainerSelection::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+pub fn write_selection(
+    project_dir: &Path,
+    selection: &DevcontainerSelection,
+) -> Result<(), String> {
+    let dir = bundle_dir(project_dir);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let content = serde_json::to_string_pretty(selection)
+        .map_err(|e| format!("Failed to serialize selection: {}", e))?;
+    std::fs::write(dir.join(SELECTION_FILE), content)
+        .map_err(|e| format!("Failed to write selection: {}", e))
+}
+
+/// Locate an existing devcontainer.json (`.devcontainer/devcontainer.json`
+/// first, then the root-level `.devcontainer.json` variant).
+pub fn find_devcontainer_json(project_dir: &Path) -> Option<PathBuf> {
+    [
+        project_dir.join(".devcontainer").join("devcontainer.json"),
+        project_dir.join(".devcontainer.json"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+/// Map a tool storage path (`~/...` or `%APPDATA%/...`) to a container-side
+/// shell expression. Absolute host paths have no meaning inside the container.
+pub fn container_home_path(storage_path: &str) -> Option<String> {
+    let normalized = storage_path.replace('\\', "/");
+    if let Some(rest) = normalized.strip_prefix("~/") {
+        return Some(format!("$HOME/{}", escape_double_quoted(rest)));
+    }
+    if let Some(rest) = normalized.strip_prefix("%APPDATA%/") {
+        return Some(format!(
+            "${{XDG_CONFIG_HOME:-$HOME/.config}}/{}",
+            escape_double_quoted(rest)
+        ));
+    }
+    None
+}
+
+/// Escape a literal so it can be embedded inside a double-quoted shell word
+pub fn escape_double_quoted(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '\\' | '"' | '$' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+pub fn build_post_create_script(plan: &ProvisionPlan) -> String {
+    let mut script = String::from(
+        r#"#!/usr/bin/env sh
+# Generated by AI Toolbox. Regenerate from the app instead of editing by hand.
+set -eu
+
+BUNDLE_DIR="$(cd "$(dirname "$0")" && pwd)"
+CENTRAL_DIR="$HOME/.ai-toolbox/skills"
+
+install_skill() {
+  name="$1"
+  shift
+  mkdir -p "$CENTRAL_DIR"
+  rm -rf "$CENTRAL_DIR/$name"
+  cp -R "$BUNDLE_DIR/skills/$name" "$CENTRAL_DIR/$name"
+  for dir in "$@"; do
+    mkdir -p "$dir"
+    rm -rf "$dir/$name"
+    ln -s "$CENTRAL_DIR/$name" "$dir/$name"
+  done
+}
+
+install_mcp_config() {
+  staged="$BUNDLE_DIR/$1"
+  target="$2"
+  format="$3"
+  mkdir -p "$(dirname "$target")"
+  if [ ! -s "$target" ]; then
+    cp "$staged" "$target"
+  elif [ "$format" = "json" ] && command -v jq >/dev/null 2>&1; then
+    tmp="$target.ai-toolbox.tmp"
+    if jq -s '.[0] * .[1]' "$target" "$staged" > "$tmp"; then
+      mv "$tmp" "$target"
+    else
+      rm -f "$tmp"
+      echo "ai-toolbox: failed to merge $staged into $target" >&2
+    fi
+  else
+    echo "ai-toolbox: $target already exists, skipped $staged" >&2
+  fi
+}
+
+"#,
+    );
+
+    for skill in &plan.skills {
+        script.push_str(&format!(
+            "install_skill \"{}\"",
+            escape_double_quoted(&skill.name)
+        ));
+        for dir in &skill.target_dirs {
+            script.push_str(&format!(" \"{}\"", dir));
+        }
+        script.push('\n');
+    }
+
+    for config in &plan.mcp_configs {
+        script.push_str(&format!(
+            "install_mcp_config \"{}\" \"{}\" \"{}\"\n",
+            escape_double_quoted(&config.staged_file),
+            config.target_path,
+            escape_double_quoted(&config.format)
+        ));
+    }
+
+    script.push_str(&format!(
+        "\necho \"ai-toolbox: provisioned {} skill(s) and {} MCP config(s)\"\n",
+        plan.skills.len(),
+        plan.mcp_configs.len()
+    ));
+    script
+}
+
+/// Whether `name` can be forwarded with `${localEnv:...}`
+pub fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(ch) if ch == '_' || ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+}
+
+pub fn local_env_placeholder(name: &str) -> String {
+    format!("${{localEnv:{}}}", name)
+}
+
+/// Secrets removed from a server config before staging
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RedactedSecrets {
+    /// Env names the server now inherits from the container environment
+    pub env_names: Vec<String>,
+    /// Env names that cannot be forwarded and were dropped
+    pub dropped_env: Vec<String>,
+    pub dropped_headers: Vec<String>,
+}
+
+/// Strip env values and headers from an MCP `server_config`. The env keys
+/// are removed so the server inherits them from the container, where
+/// `remoteEnv` fills them from the host.
+pub fn redact_server_secrets(server_config: &mut Value) -> RedactedSecrets {
+    let mut redacted = RedactedSecrets::default();
+    let Some(config) = server_config.as_object_mut() else {
+        return redacted;
+    };
+    if let Some(Value::Object(env)) = config.remove("env") {
+        for name in env.keys() {
+            if is_env_name(name) {
+                redacted.env_names.push(name.clone());
+            } else {
+                redacted.dropped_env.push(name.clone());
+            }
+        }
+    }
+    if let Some(Value::Object(headers)) = config.remove("headers") {
+        redacted.dropped_headers = headers.keys().cloned().collect();
+    }
+    redacted
+}
+
+/// Whether a devcontainer.json document has `//` or `/* */` comments, which
+/// rewriting it would lose
+pub fn has_jsonc_comments(content: &str) -> bool {
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(ch) = chars.next() {
+        if in_string {
+            match ch {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '/' if matches!(chars.peek(), Some('/') | Some('*')) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Merge our command and forwarded env names into a devcontainer.json
+/// document. Callers refuse documents with comments (`has_jsonc_comments`)
+/// first; trailing commas and other JSON5 leniencies are accepted.
+///
+/// An existing `postCreateCommand` is preserved: string/array forms are moved
+/// into the object form under `existing`, and ours is added under `ai-toolbox`.
+/// Existing `remoteEnv` entries win over ours.
+pub fn merge_devcontainer_json(
+    content: &str,
+    command: &str,
+    env_names: &[String],
+) -> Result<String, String> {
+    let trimmed = content.trim();
+    let mut config: Value = if trimmed.is_empty() {
+        serde_json::json!({})
+    } else {
+        json5::from_str(trimmed).map_err(|e| format!("Failed to parse devcontainer.json: {}", e))?
+    };
+    let root = config
+        .as_object_mut()
+        .ok_or("devcontainer.json is not a JSON object")?;
+
+    let merged = match root.remove("postCreateCommand") {
+        None | Some(Value::Null) => Value::String(command.to_string()),
+        Some(Value::String(existing)) if existing == command => Value::String(existing),
+        Some(Value::Object(mut commands)) => {
+            commands.insert(
+                POST_CREATE_KEY.to_string(),
+                Value::String(command.to_string()),
+            );
+            Value::Object(commands)
+        }
+        Some(existing) => {
+            let mut commands = serde_json::Map::new();
+            commands.insert("existing".to_string(), existing);
+            commands.insert(
+                POST_CREATE_KEY.to_string(),
+                Value::String(command.to_string()),
+            );
+            Value::Object(commands)
+        }
+    };
+    root.insert("postCreateCommand".to_string(), merged);
+
+    if !env_names.is_empty() {
+        let remote_env = root
+            .entry("remoteEnv")
+            .or_insert_with(|| Value::Object(serde_json::Map::new()))
+            .as_object_mut()
+            .ok_or("devcontainer.json remoteEnv is not an object")?;
+        for name in env_names {
+            remote_env
+                .entry(name.clone())
+                .or_insert_with(|| Value::String(local_env_placeholder(name)));
+        }
+    }
+
+    serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize devcontainer.json: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn container_home_path_maps_portable_prefixes_only() {
+        assert_eq!(
+            container_home_path("~/.claude/skills").as_deref(),
+            Some("$HOME/.claude/skills")
+        );
+        assert_eq!(
+            container_home_path("%APPDATA%/Code/User/mcp.json").as_deref(),
+            Some("${XDG_CONFIG_HOME:-$HOME/.config}/Code/User/mcp.json")
+        );
+        assert_eq!(container_home_path("/opt/tool/skills"), None);
+        assert_eq!(container_home_path("C:\\tools\\skills"), None);
+    }
+
+    #[test]
+    fn script_escapes_skill_names() {
+        let plan = ProvisionPlan {
+            skills: vec![SkillInstallPlan {
+                name: "we$ird\"name".to_string(),
+                target_dirs: vec!["$HOME/.claude/skills".to_string()],
+            }],
+            mcp_configs: vec![McpConfigInstallPlan {
+                staged_file: "mcp/claude_code.json".to_string(),
+                target_path: "$HOME/.claude.json".to_string(),
+                format: "json".to_string(),
+            }],
+        };
+
+        let script = build_post_create_script(&plan);
+        assert!(script.contains("install_skill \"we\\$ird\\\"name\" \"$HOME/.claude/skills\"\n"));
+        assert!(script.contains(
+            "install_mcp_config \"mcp/claude_code.json\" \"$HOME/.claude.json\" \"json\"\n"
+        ));
+    }
+
+    #[test]
+    fn merge_post_create_command_preserves_existing_commands() {
+        let command = post_create_command();
+
+        let fresh = merge_devcontainer_json("{ \"image\": \"x\", }", &command, &[])
+            .expect("merge into empty");
+        let fresh: Value = serde_json::from_str(&fresh).unwrap();
+        assert_eq!(fresh["postCreateCommand"], Value::String(command.clone()));
+        assert_eq!(fresh["image"], "x");
+
+        let merged =
+            merge_devcontainer_json(r#"{"postCreateCommand": "npm ci"}"#, &command, &[]).unwrap();
+        let merged: Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(merged["postCreateCommand"]["existing"], "npm ci");
+        assert_eq!(
+            merged["postCreateCommand"][POST_CREATE_KEY],
+            command.as_str()
+        );
+
+        let again = merge_devcontainer_json(&merged.to_string(), &command, &[]).unwrap();
+        let again: Value = serde_json::from_str(&again).unwrap();
+        assert_eq!(again, merged);
+    }
+
+    #[test]
+    fn secrets_become_forwarded_env_names() {
+        let mut config = serde_json::json!({
+            "command": "npx",
+            "env": {"GITHUB_TOKEN": "ghp_secret", "bad-name": "x"},
+            "headers": {"Authorization": "Bearer secret"}
+        });
+        let redacted = redact_server_secrets(&mut config);
+        assert_eq!(redacted.env_names, vec!["GITHUB_TOKEN"]);
+        assert_eq!(redacted.dropped_env, vec!["bad-name"]);
+        assert_eq!(redacted.dropped_headers, vec!["Authorization"]);
+        assert!(!config.to_string().contains("secret"));
+
+        let merged = merge_devcontainer_json(
+            r#"{"remoteEnv": {"OTHER": "1"}}"#,
+            &post_create_command(),
+            &redacted.env_names,
+        )
+        .unwrap();
+        let merged: Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(
+            merged["remoteEnv"]["GITHUB_TOKEN"],
+            "${localEnv:GITHUB_TOKEN}"
+        );
+        assert_eq!(merged["remoteEnv"]["OTHER"], "1");
+    }
+
+    #[test]
+    fn detects_comments_outside_strings() {
+        assert!(has_jsonc_comments("{ // comment\n \"image\": \"x\" }"));
+        assert!(has_jsonc_comments("{ /* c */ }"));
+        assert!(!has_jsonc_comments(
+            r#"{"image": "mcr.microsoft.com/x", "a": "\"//"}"#
+        ));
+    }
+}
@@ -0,0 +1,11 @@
+//! Dev Container Provisioning Module
+//!
+//! Generates a per-project `.devcontainer/ai-toolbox/` bundle (selected skills,
+//! staged MCP configs and a `postCreateCommand` script) so dev containers start
+//! pre-provisioned with the toolbox state.
+
+pub mod commands;
+pub mod generator;
+pub mod types;
+
+pub use commands::*;
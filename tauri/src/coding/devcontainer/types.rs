@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-project provisioning selection, stored inside the project at
+/// `.devcontainer/ai-toolbox/selection.json` so it travels with the repo.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DevcontainerSelection {
+    #[serde(default)]
+    pub skill_ids: Vec<String>,
+    #[serde(default)]
+    pub mcp_server_ids: Vec<String>,
+    /// Tools to provision inside the container. Empty means each skill / MCP
+    /// server follows its own `enabled_tools`; otherwise MCP servers still go
+    /// only to the listed tools they are enabled for.
+    #[serde(default)]
+    pub tool_keys: Vec<String>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// Result returned to the frontend after generating the provisioning bundle
+#[derive(Clone, Debug, Serialize)]
+pub struct DevcontainerGenerateResultDto {
+    pub bundle_dir: String,
+    pub script_path: String,
+    pub post_create_command: String,
+    /// Path of the devcontainer.json that received `postCreateCommand`,
+    /// `None` when the project has no devcontainer.json yet.
+    pub devcontainer_json_path: Option<String>,
+    pub skill_count: usize,
+    pub mcp_config_count: usize,
+    pub warnings: Vec<String>,
+}
@@ -12,6 +12,7 @@ use crate::coding::open_code::shell_env;
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
 use crate::coding::proxy_gateway::{cli_proxy, paths::ProxyGatewayPaths, types::GatewayCliKey};
 use crate::coding::runtime_location;
+use crate::coding::tools::gemini_extensions;
 use crate::db::helpers::{
     db_count, db_delete, db_get, db_list, db_max_i64, db_patch_fields, db_put, db_query_by_bool,
     db_update_applied_status,
@@ -125,10 +126,7 @@ struct RemoteGeminiCliModel {
 }
 
 fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 pub fn get_gemini_cli_default_root_dir() -> Result<PathBuf, String> {
@@ -927,6 +925,47 @@ pub async fn get_gemini_cli_root_path_info(
     get_gemini_cli_root_path_info_from_db_async(&db).await
 }
 
+/// Installed extensions with their bundled MCP servers, skills and context
+/// files; MCP servers and skills are adopted through the MCP import and skill
+/// onboarding flows under `GeminiCliExtension::source_key`
+#[tauri::command]
+pub async fn list_gemini_cli_extensions(
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<Vec<GeminiCliExtension>, String> {
+    let db = state.db();
+    let extensions = gemini_extensions::get_installed_extensions(&db).await;
+    Ok(extensions
+        .into_iter()
+        .map(|extension| {
+            let skills = list_extension_skills(&extension.skills_dir());
+            GeminiCliExtension {
+                source_key: extension.source_key(),
+                install_path: extension.install_path.to_string_lossy().to_string(),
+                mcp_servers: extension.mcp_servers.keys().cloned().collect(),
+                name: extension.name,
+                version: extension.version,
+                linked: extension.linked,
+                skills,
+                context_files: extension.context_files,
+            }
+        })
+        .collect())
+}
+
+/// Names of the skill directories (those with a `SKILL.md`) in `skills_dir`
+fn list_extension_skills(skills_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(skills_dir) else {
+        return Vec::new();
+    };
+    let mut skills: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().join("SKILL.md").is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    skills.sort();
+    skills
+}
+
 #[tauri::command]
 pub async fn reveal_gemini_cli_config_folder(
     state: tauri::State<'_, SqliteDbState>,
@@ -408,3 +408,19 @@ pub struct GeminiCliPromptConfigContent {
     pub created_at: String,
     pub updated_at: String,
 }
+
+/// Installed Gemini CLI extension and what it contributes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliExtension {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub install_path: String,
+    pub linked: bool,
+    /// Source key for `mcp_import_from_tool` and skill onboarding
+    pub source_key: String,
+    pub mcp_servers: Vec<String>,
+    pub skills: Vec<String>,
+    pub context_files: Vec<String>,
+}
@@ -26,11 +26,9 @@ use crate::db::SqliteDbState;
 use tauri::Emitter;
 
 pub fn get_grok_default_root_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
+    crate::coding::tools::env_home_dir()
         .map(|home| home.join(".grok"))
-        .map_err(|_| "Failed to get home directory".to_string())
+        .ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 pub fn get_grok_root_dir_without_db() -> Result<PathBuf, String> {
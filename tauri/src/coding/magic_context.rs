@@ -114,8 +114,8 @@ async fn runtime_location_for_harness(
 }
 
 fn local_user_config_path() -> Result<PathBuf, String> {
-    let home_dir =
-        dirs::home_dir().ok_or_else(|| "Failed to resolve home directory".to_string())?;
+    let home_dir = crate::coding::tools::home_dir()
+        .ok_or_else(|| "Failed to resolve home directory".to_string())?;
     let xdg_config_home = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
     Ok(local_user_config_path_from_home(home_dir, xdg_config_home))
 }
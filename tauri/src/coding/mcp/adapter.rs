@@ -195,6 +195,32 @@ pub fn from_db_mcp_preferences(value: Value) -> McpPreferences {
             .get("limit_add_more_to_preferred_tools")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        remote_only_tools: value
+            .get("remote_only_tools")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        gateway_port: value
+            .get("gateway_port")
+            .and_then(|v| v.as_u64())
+            .and_then(|port| u16::try_from(port).ok()),
+        gateway_token: value
+            .get("gateway_token")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        traffic_log_servers: value
+            .get("traffic_log_servers")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
         updated_at: value
             .get("updated_at")
             .and_then(|v| v.as_i64())
@@ -210,6 +236,10 @@ pub fn to_mcp_preferences_payload(prefs: &McpPreferences) -> Value {
         "favorites_initialized": prefs.favorites_initialized,
         "sync_disabled_to_opencode": prefs.sync_disabled_to_opencode,
         "limit_add_more_to_preferred_tools": prefs.limit_add_more_to_preferred_tools,
+        "remote_only_tools": prefs.remote_only_tools,
+        "gateway_port": prefs.gateway_port,
+        "gateway_token": prefs.gateway_token,
+        "traffic_log_servers": prefs.traffic_log_servers,
         "updated_at": prefs.updated_at,
     })
 }
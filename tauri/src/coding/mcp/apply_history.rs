@@ -0,0 +1,325 @@
+//! History of MCP config writes
+//!
+//! Every write of a tool's MCP config, from single-tool syncs and two-phase
+//! applies alike, appends a record to `<app data>/mcp-apply-history.jsonl`:
+//! the tool, which servers were added, removed or modified, and the hashes of
+//! the file before and after. The pre-write content is kept once per hash in
+//! `<app data>/mcp-apply-backups/<sha256>` and referenced as `backup_ref`, so
+//! "when did this server disappear from Codex" can be traced and the file as
+//! it was can be recovered. Recording is best-effort and never fails a write.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::types::now_ms;
+
+const HISTORY_FILE: &str = "mcp-apply-history.jsonl";
+const BACKUP_DIR: &str = "mcp-apply-backups";
+pub const DEFAULT_HISTORY_LIMIT: usize = 200;
+/// Past this size the history is compacted to the newest `KEEP_ENTRIES`
+const MAX_HISTORY_BYTES: u64 = 2 * 1024 * 1024;
+const KEEP_ENTRIES: usize = 2000;
+
+/// Serializes appends and compaction across sync threads
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct McpApplyRecord {
+    pub ts: i64,
+    pub tool: String,
+    pub config_path: String,
+    #[serde(default)]
+    pub servers_added: Vec<String>,
+    #[serde(default)]
+    pub servers_removed: Vec<String>,
+    #[serde(default)]
+    pub servers_modified: Vec<String>,
+    /// Hash of the file before the write; `None` when it did not exist
+    pub previous_hash: Option<String>,
+    pub file_hash: String,
+    /// Backup of the pre-write content, see `backup_path`
+    pub backup_ref: Option<String>,
+}
+
+impl McpApplyRecord {
+    fn mentions(&self, server_name: &str) -> bool {
+        [
+            &self.servers_added,
+            &self.servers_removed,
+            &self.servers_modified,
+        ]
+        .iter()
+        .any(|names| names.iter().any(|name| name == server_name))
+    }
+}
+
+fn data_dir() -> Result<PathBuf, String> {
+    // Tests that commit MCP writes must not touch the real app data
+    #[cfg(test)]
+    return Ok(std::env::temp_dir().join("ai-toolbox-test-data"));
+    #[cfg(not(test))]
+    crate::cli::app_data_dir()
+}
+
+fn history_path() -> Result<PathBuf, String> {
+    Ok(data_dir()?.join(HISTORY_FILE))
+}
+
+/// Where the pre-write content referenced by `backup_ref` is stored
+pub fn backup_path(backup_ref: &str) -> Result<PathBuf, String> {
+    if backup_ref.len() != 64 || !backup_ref.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "Invalid MCP apply backup reference: {}",
+            backup_ref
+        ));
+    }
+    Ok(data_dir()?.join(BACKUP_DIR).join(backup_ref))
+}
+
+fn hash_content(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Server entries under `field` (dotted for nested JSON fields), by name
+fn server_entries(format: &str, field: &str, content: Option<&str>) -> BTreeMap<String, Value> {
+    let content = content.unwrap_or_default().trim();
+    if content.is_empty() {
+        return BTreeMap::new();
+    }
+    let config: Option<Value> = match format {
+        "toml" => toml::from_str::<toml::Value>(content)
+            .ok()
+            .and_then(|value| serde_json::to_value(value).ok()),
+        _ => json5::from_str(content).ok(),
+    };
+    let mut current = config.as_ref();
+    for segment in field.split('.').map(str::trim).filter(|s| !s.is_empty()) {
+        current = current.and_then(|value| value.get(segment));
+    }
+    current
+        .and_then(Value::as_object)
+        .map(|servers| {
+            servers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn build_record(
+    tool_key: &str,
+    format: &str,
+    field: &str,
+    config_path: &Path,
+    before: Option<&str>,
+    after: &str,
+) -> McpApplyRecord {
+    let old = server_entries(format, field, before);
+    let new = server_entries(format, field, Some(after));
+    let previous_hash = before.map(hash_content);
+    McpApplyRecord {
+        ts: now_ms(),
+        tool: tool_key.to_string(),
+        config_path: config_path.to_string_lossy().into_owned(),
+        servers_added: new
+            .keys()
+            .filter(|name| !old.contains_key(*name))
+            .cloned()
+            .collect(),
+        servers_removed: old
+            .keys()
+            .filter(|name| !new.contains_key(*name))
+            .cloned()
+            .collect(),
+        servers_modified: new
+            .iter()
+            .filter(|(name, value)| old.get(*name).is_some_and(|previous| previous != *value))
+            .map(|(name, _)| name.clone())
+            .collect(),
+        backup_ref: previous_hash.clone(),
+        previous_hash,
+        file_hash: hash_content(after),
+    }
+}
+
+/// Record a write of `config_path` from `before` (`None`: no file) to `after`
+pub fn record_apply(
+    tool_key: &str,
+    format: &str,
+    field: &str,
+    config_path: &Path,
+    before: Option<&str>,
+    after: &str,
+) {
+    if before == Some(after) {
+        return;
+    }
+    let record = build_record(tool_key, format, field, config_path, before, after);
+    if let Err(error) = append_record(&record, before) {
+        log::warn!(
+            "Failed to record MCP apply history for {}: {}",
+            tool_key,
+            error
+        );
+    }
+}
+
+fn append_record(record: &McpApplyRecord, before: Option<&str>) -> Result<(), String> {
+    let _guard = HISTORY_LOCK.lock().map_err(|e| e.to_string())?;
+    if let (Some(backup_ref), Some(content)) = (&record.backup_ref, before) {
+        let path = backup_path(backup_ref)?;
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create MCP backup dir: {}", e))?;
+            }
+            fs::write(&path, content).map_err(|e| format!("Failed to write MCP backup: {}", e))?;
+        }
+    }
+
+    let path = history_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    }
+    let mut line =
+        serde_json::to_string(record).map_err(|e| format!("Failed to serialize record: {}", e))?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| format!("Failed to append MCP apply history: {}", e))?;
+
+    if fs::metadata(&path).is_ok_and(|meta| meta.len() > MAX_HISTORY_BYTES) {
+        compact(&path)?;
+    }
+    Ok(())
+}
+
+fn read_records(path: &Path) -> Result<Vec<McpApplyRecord>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read MCP apply history: {}", e))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Keep the newest records and drop backups none of them reference
+fn compact(path: &Path) -> Result<(), String> {
+    let mut records = read_records(path)?;
+    let skip = records.len().saturating_sub(KEEP_ENTRIES);
+    records.drain(..skip);
+
+    let mut content = String::new();
+    for record in &records {
+        if let Ok(line) = serde_json::to_string(record) {
+            content.push_str(&line);
+            content.push('\n');
+        }
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to compact MCP apply history: {}", e))?;
+
+    let referenced: BTreeSet<&str> = records
+        .iter()
+        .filter_map(|record| record.backup_ref.as_deref())
+        .collect();
+    if let Some(backup_dir) = path.parent().map(|dir| dir.join(BACKUP_DIR)) {
+        for entry in fs::read_dir(backup_dir).into_iter().flatten().flatten() {
+            let name = entry.file_name();
+            if !referenced.contains(name.to_string_lossy().as_ref()) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Newest first, filtered by tool and/or by a server named in the change
+pub fn query_history(
+    tool_key: Option<&str>,
+    server_name: Option<&str>,
+    limit: usize,
+) -> Result<Vec<McpApplyRecord>, String> {
+    let records = {
+        let _guard = HISTORY_LOCK.lock().map_err(|e| e.to_string())?;
+        read_records(&history_path()?)?
+    };
+    Ok(records
+        .into_iter()
+        .rev()
+        .filter(|record| tool_key.is_none_or(|tool| record.tool == tool))
+        .filter(|record| server_name.is_none_or(|name| record.mentions(name)))
+        .take(limit)
+        .collect())
+}
+
+/// Content of a tool config before the write that recorded `backup_ref`
+pub fn read_backup(backup_ref: &str) -> Result<String, String> {
+    fs::read_to_string(backup_path(backup_ref)?)
+        .map_err(|e| format!("Failed to read MCP apply backup: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_json_servers() {
+        let before = r#"{ "mcpServers": { "a": { "command": "x" }, "b": { "command": "y" } } }"#;
+        let after = r#"{ "mcpServers": { "a": { "command": "z" }, "c": { "url": "u" } } }"#;
+        let record = build_record(
+            "claude_code",
+            "json",
+            "mcpServers",
+            Path::new("/tmp/.claude.json"),
+            Some(before),
+            after,
+        );
+        assert_eq!(record.servers_added, vec!["c"]);
+        assert_eq!(record.servers_removed, vec!["b"]);
+        assert_eq!(record.servers_modified, vec!["a"]);
+        assert_eq!(record.backup_ref, record.previous_hash);
+        assert_ne!(
+            record.previous_hash.as_deref(),
+            Some(record.file_hash.as_str())
+        );
+        assert!(record.mentions("b") && !record.mentions("d"));
+    }
+
+    #[test]
+    fn diffs_toml_and_nested_fields() {
+        let after = "[mcp_servers.fetch]\ncommand = \"uvx\"\n";
+        let record = build_record(
+            "codex",
+            "toml",
+            "mcp_servers",
+            Path::new("config.toml"),
+            None,
+            after,
+        );
+        assert_eq!(record.servers_added, vec!["fetch"]);
+        assert!(record.previous_hash.is_none() && record.backup_ref.is_none());
+
+        let nested = r#"{ "mcp": { "servers": { "s": {} } } }"#;
+        assert_eq!(
+            server_entries("jsonc", "mcp.servers", Some(nested))
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["s"]
+        );
+        assert!(backup_path("../etc").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! MCP tool-name collision analysis
+//!
+//! Groups the tool names reported by health-check handshakes across all
+//! servers enabled for the same client tool. A name exposed by more than one
+//! server is a collision; clients that namespace MCP tools by server name
+//! only get an informational entry, others get a warning with a suggestion.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::health::McpHealthResultDto;
+use super::types::McpServer;
+
+/// Clients that prefix MCP tool names with the server name
+/// (e.g. Claude Code `mcp__{server}__{tool}`), so duplicates stay addressable.
+const PREFIXING_TOOLS: &[&str] = &["claude_code", "codex", "gemini_cli", "opencode"];
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpCollisionServerDto {
+    pub server_id: String,
+    pub server_name: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpToolCollisionDto {
+    /// Client tool key the servers are enabled for
+    pub tool: String,
+    /// Colliding MCP tool name
+    pub tool_name: String,
+    pub servers: Vec<McpCollisionServerDto>,
+    /// "info" when the client prefixes tool names, otherwise "warning"
+    pub severity: String,
+    pub suggestion: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct McpCollisionReportDto {
+    pub collisions: Vec<McpToolCollisionDto>,
+    /// Enabled servers without a successful handshake (not analyzed)
+    pub unchecked_servers: Vec<McpCollisionServerDto>,
+    pub checked_at: i64,
+}
+
+fn server_ref(server: &McpServer) -> McpCollisionServerDto {
+    McpCollisionServerDto {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+    }
+}
+
+fn suggestion_for(tool: &str, tool_name: &str, servers: &[McpCollisionServerDto]) -> String {
+    let names = servers
+        .iter()
+        .map(|server| server.server_name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if PREFIXING_TOOLS.contains(&tool) {
+        format!(
+            "{} prefixes MCP tools with the server name; \"{}\" from {} stays distinguishable",
+            tool, tool_name, names
+        )
+    } else {
+        format!(
+            "Disable all but one of {} for {}, or drop \"{}\" from their enabled tools",
+            names, tool, tool_name
+        )
+    }
+}
+
+/// Detect tool-name collisions per client tool from handshake results
+pub fn analyze_collisions(
+    servers: &[McpServer],
+    health: &BTreeMap<String, McpHealthResultDto>,
+    checked_at: i64,
+) -> McpCollisionReportDto {
+    let mut report = McpCollisionReportDto {
+        checked_at,
+        ..Default::default()
+    };
+
+    // tool key -> mcp tool name -> servers exposing it
+    let mut by_tool: BTreeMap<&str, BTreeMap<&str, Vec<McpCollisionServerDto>>> = BTreeMap::new();
+    for server in servers
+        .iter()
+        .filter(|server| !server.enabled_tools.is_empty())
+    {
+        let Some(result) = health.get(&server.id).filter(|result| result.ok) else {
+            report.unchecked_servers.push(server_ref(server));
+            continue;
+        };
+        for tool in &server.enabled_tools {
+            let names = by_tool.entry(tool.as_str()).or_default();
+            for tool_name in &result.tools {
+                let entries = names.entry(tool_name.as_str()).or_default();
+                if !entries.iter().any(|entry| entry.server_id == server.id) {
+                    entries.push(server_ref(server));
+                }
+            }
+        }
+    }
+
+    for (tool, names) in by_tool {
+        for (tool_name, servers) in names.into_iter().filter(|(_, servers)| servers.len() > 1) {
+            let severity = if PREFIXING_TOOLS.contains(&tool) {
+                "info"
+            } else {
+                "warning"
+            };
+            report.collisions.push(McpToolCollisionDto {
+                tool: tool.to_string(),
+                tool_name: tool_name.to_string(),
+                suggestion: suggestion_for(tool, tool_name, &servers),
+                servers,
+                severity: severity.to_string(),
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(id: &str, enabled_tools: &[&str]) -> McpServer {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "server_type": "stdio",
+            "server_config": { "command": "npx" },
+            "enabled_tools": enabled_tools,
+            "created_at": 0,
+            "updated_at": 0,
+        }))
+        .expect("server")
+    }
+
+    fn health(id: &str, ok: bool, tools: &[&str]) -> (String, McpHealthResultDto) {
+        let result = McpHealthResultDto {
+            server_id: id.to_string(),
+            server_name: id.to_string(),
+            ok,
+            server_info: None,
+            tools: tools.iter().map(|tool| tool.to_string()).collect(),
+            error: None,
+            checked_at: 0,
+        };
+        (id.to_string(), result)
+    }
+
+    #[test]
+    fn collisions_are_reported_per_client_tool() {
+        let servers = vec![
+            server("fs-a", &["claude_code", "cursor"]),
+            server("fs-b", &["cursor"]),
+            server("broken", &["cursor"]),
+        ];
+        let health: BTreeMap<_, _> = [
+            health("fs-a", true, &["read_file", "search"]),
+            health("fs-b", true, &["read_file"]),
+            health("broken", false, &[]),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = analyze_collisions(&servers, &health, 1);
+        assert_eq!(report.collisions.len(), 1);
+        let collision = &report.collisions[0];
+        assert_eq!(collision.tool, "cursor");
+        assert_eq!(collision.tool_name, "read_file");
+        assert_eq!(collision.severity, "warning");
+        assert_eq!(collision.servers.len(), 2);
+        assert_eq!(report.unchecked_servers[0].server_id, "broken");
+    }
+
+    #[test]
+    fn prefixing_clients_get_info_severity() {
+        let servers = vec![server("a", &["claude_code"]), server("b", &["claude_code"])];
+        let health: BTreeMap<_, _> = [
+            health("a", true, &["search"]),
+            health("b", true, &["search"]),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = analyze_collisions(&servers, &health, 1);
+        assert_eq!(report.collisions[0].severity, "info");
+    }
+}
@@ -2,27 +2,41 @@
 //!
 //! Provides the public API for the MCP feature.
 
+use std::collections::BTreeMap;
+
 use tauri::{AppHandle, Emitter, Runtime, State};
 
 use super::adapter::parse_sync_details_dto;
+use super::apply_history::{self, McpApplyRecord};
+use super::collisions::{self, McpCollisionReportDto};
 use super::config_sync::{
-    import_servers_from_path, import_servers_from_plugin_mcp_json, import_servers_from_tool_async,
+    import_servers_from_gemini_extension, import_servers_from_path,
+    import_servers_from_plugin_mcp_json, import_servers_from_tool_async,
     remove_server_from_tool_async, sync_server_to_tool_async,
-    sync_server_to_tool_with_enabled_async,
+    sync_server_to_tool_with_enabled_async, McpConfigOp,
 };
+use super::gateway::{self, McpGatewayStatusDto};
+use super::health::{self, McpHealthResultDto};
 use super::mcp_store;
 use super::package_version;
+use super::prerequisites::{self, McpPrerequisiteReportDto};
+use super::staged_apply::{self, ToolApply};
+use super::templates::{self, McpTemplate, McpTemplateInput};
+use super::traffic_log::{self, McpTrafficDto};
 use super::types::{
     now_ms, CreateMcpServerInput, FavoriteMcp, FavoriteMcpDto, FavoriteMcpInput,
     McpDiscoveredServerDto, McpImportResultDto, McpPackageVersionResolveRequest,
     McpPackageVersionResolveResult, McpScanResultDto, McpServer, McpServerDto, McpSyncDetail,
     McpSyncResultDto, UpdateMcpServerInput,
 };
+use crate::coding::tools::gemini_extensions::{self, GEMINI_EXTENSION_KEY_PREFIX};
+use crate::coding::tools::post_sync::{schedule_post_sync, PostSyncScope};
 use crate::coding::tools::{
     custom_store, get_mcp_runtime_tools, is_tool_installed_with_db_async,
     resolve_mcp_config_path_with_db_async, runtime_tool_by_key, to_runtime_tool_dto_with_db_async,
     CustomTool, RuntimeToolDto,
 };
+use crate::settings::read_only::ensure_writable;
 use crate::SqliteDbState;
 
 fn normalize_optional_text(value: Option<String>) -> Option<String> {
@@ -82,6 +96,51 @@ pub async fn mcp_create_server<R: Runtime>(
     app: AppHandle<R>,
     state: State<'_, SqliteDbState>,
     input: CreateMcpServerInput,
+) -> Result<McpServerDto, String> {
+    ensure_writable("mcp_create_server")?;
+    create_server_internal(&app, &state, input).await
+}
+
+/// List the built-in server templates
+#[tauri::command]
+pub async fn mcp_list_templates() -> Result<Vec<McpTemplate>, String> {
+    Ok(templates::MCP_TEMPLATES.to_vec())
+}
+
+/// Create a server from a built-in template with the user's parameters,
+/// then sync it like `mcp_create_server`
+#[tauri::command]
+pub async fn mcp_add_server_from_template<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    input: McpTemplateInput,
+) -> Result<McpServerDto, String> {
+    ensure_writable("mcp_add_server_from_template")?;
+    let template = templates::template_by_id(&input.template_id)
+        .ok_or_else(|| format!("MCP template not found: {}", input.template_id))?;
+    let (server_type, server_config) =
+        templates::render_template(template, input.server_type.as_deref(), &input.params)?;
+    let name = normalize_optional_text(input.name).unwrap_or_else(|| template.name.to_string());
+    create_server_internal(
+        &app,
+        &state,
+        CreateMcpServerInput {
+            name,
+            server_type,
+            server_config,
+            enabled_tools: input.enabled_tools,
+            description: Some(template.description.to_string()),
+            tags: Vec::new(),
+            timeout: None,
+        },
+    )
+    .await
+}
+
+async fn create_server_internal<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    input: CreateMcpServerInput,
 ) -> Result<McpServerDto, String> {
     let now = now_ms();
     let server = McpServer {
@@ -101,39 +160,23 @@ pub async fn mcp_create_server<R: Runtime>(
         updated_at: now,
     };
 
-    let id = mcp_store::upsert_mcp_server(&state, &server).await?;
+    let id = mcp_store::upsert_mcp_server(state, &server).await?;
+    let server = McpServer {
+        id: id.clone(),
+        ..server
+    };
 
-    // Sync to all enabled tools
-    let custom_tools = custom_store::get_custom_tools(&state)
+    // Sync to all enabled tools (all or none)
+    let custom_tools = custom_store::get_custom_tools(state)
         .await
         .unwrap_or_default();
-    let db = state.db();
-    for tool_key in &input.enabled_tools {
-        if let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) {
-            if is_tool_installed_with_db_async(&db, &tool).await {
-                match sync_server_to_tool_async(&db, &server, &tool).await {
-                    Ok(detail) => {
-                        let _ = mcp_store::update_sync_detail(&state, &id, &detail).await;
-                    }
-                    Err(e) => {
-                        let detail = McpSyncDetail {
-                            tool: tool_key.clone(),
-                            status: "error".to_string(),
-                            synced_at: Some(now_ms()),
-                            error_message: Some(e),
-                        };
-                        let _ = mcp_store::update_sync_detail(&state, &id, &detail).await;
-                    }
-                }
-            }
-        }
-    }
+    sync_servers_two_phase(state, std::slice::from_ref(&server), &custom_tools, None).await?;
 
     // Sync disabled to opencode if the switch is ON and opencode is not in enabled_tools
-    maybe_sync_disabled_to_opencode(&state, &server, &custom_tools).await;
+    maybe_sync_disabled_to_opencode(state, &server, &custom_tools).await;
 
     // Get the created server with sync details
-    let created = mcp_store::get_mcp_server_by_id(&state, &id)
+    let created = mcp_store::get_mcp_server_by_id(state, &id)
         .await?
         .ok_or("Failed to get created server")?;
 
@@ -170,6 +213,7 @@ pub async fn mcp_update_server<R: Runtime>(
     serverId: String,
     input: UpdateMcpServerInput,
 ) -> Result<McpServerDto, String> {
+    ensure_writable("mcp_update_server")?;
     let mut server = mcp_store::get_mcp_server_by_id(&state, &serverId)
         .await?
         .ok_or_else(|| format!("MCP server not found: {}", serverId))?;
@@ -198,31 +242,11 @@ pub async fn mcp_update_server<R: Runtime>(
 
     mcp_store::upsert_mcp_server(&state, &server).await?;
 
-    // Re-sync to all enabled tools
+    // Re-sync to all enabled tools (all or none)
     let custom_tools = custom_store::get_custom_tools(&state)
         .await
         .unwrap_or_default();
-    let db = state.db();
-    for tool_key in &server.enabled_tools {
-        if let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) {
-            if is_tool_installed_with_db_async(&db, &tool).await {
-                match sync_server_to_tool_async(&db, &server, &tool).await {
-                    Ok(detail) => {
-                        let _ = mcp_store::update_sync_detail(&state, &serverId, &detail).await;
-                    }
-                    Err(e) => {
-                        let detail = McpSyncDetail {
-                            tool: tool_key.clone(),
-                            status: "error".to_string(),
-                            synced_at: Some(now_ms()),
-                            error_message: Some(e),
-                        };
-                        let _ = mcp_store::update_sync_detail(&state, &serverId, &detail).await;
-                    }
-                }
-            }
-        }
-    }
+    sync_servers_two_phase(&state, std::slice::from_ref(&server), &custom_tools, None).await?;
 
     // Sync disabled to opencode if the switch is ON and opencode is not in enabled_tools
     maybe_sync_disabled_to_opencode(&state, &server, &custom_tools).await;
@@ -263,6 +287,7 @@ pub async fn mcp_delete_server<R: Runtime>(
     state: State<'_, SqliteDbState>,
     serverId: String,
 ) -> Result<(), String> {
+    ensure_writable("mcp_delete_server")?;
     // Get the server first to remove from tool configs
     if let Some(server) = mcp_store::get_mcp_server_by_id(&state, &serverId).await? {
         // Remove from all enabled tools' configs
@@ -297,6 +322,7 @@ pub async fn mcp_toggle_tool<R: Runtime>(
     serverId: String,
     toolKey: String,
 ) -> Result<bool, String> {
+    ensure_writable("mcp_toggle_tool")?;
     let is_enabled = mcp_store::toggle_tool_enabled(&state, &serverId, &toolKey).await?;
 
     // Get the server
@@ -361,6 +387,7 @@ pub async fn mcp_reorder_servers(
     state: State<'_, SqliteDbState>,
     ids: Vec<String>,
 ) -> Result<(), String> {
+    ensure_writable("mcp_reorder_servers")?;
     mcp_store::reorder_mcp_servers(&state, &ids).await
 }
 
@@ -373,6 +400,7 @@ pub async fn mcp_update_metadata(
     userGroup: Option<String>,
     userNote: Option<String>,
 ) -> Result<(), String> {
+    ensure_writable("mcp_update_metadata")?;
     mcp_store::update_mcp_server_metadata(
         &state,
         &serverId,
@@ -392,6 +420,7 @@ pub async fn mcp_sync_to_tool<R: Runtime>(
     state: State<'_, SqliteDbState>,
     toolKey: String,
 ) -> Result<Vec<McpSyncResultDto>, String> {
+    ensure_writable("mcp_sync_to_tool")?;
     let custom_tools = custom_store::get_custom_tools(&state)
         .await
         .unwrap_or_default();
@@ -404,38 +433,8 @@ pub async fn mcp_sync_to_tool<R: Runtime>(
     }
 
     let servers = mcp_store::get_mcp_servers(&state).await?;
-    let mut results = Vec::new();
-
-    for server in servers {
-        if !server.enabled_tools.contains(&toolKey) {
-            continue;
-        }
-
-        match sync_server_to_tool_async(&db, &server, &tool).await {
-            Ok(detail) => {
-                mcp_store::update_sync_detail(&state, &server.id, &detail).await?;
-                results.push(McpSyncResultDto {
-                    tool: toolKey.clone(),
-                    success: true,
-                    error_message: None,
-                });
-            }
-            Err(e) => {
-                let detail = McpSyncDetail {
-                    tool: toolKey.clone(),
-                    status: "error".to_string(),
-                    synced_at: Some(now_ms()),
-                    error_message: Some(e.clone()),
-                };
-                mcp_store::update_sync_detail(&state, &server.id, &detail).await?;
-                results.push(McpSyncResultDto {
-                    tool: toolKey.clone(),
-                    success: false,
-                    error_message: Some(e),
-                });
-            }
-        }
-    }
+    let results =
+        sync_servers_two_phase(&state, &servers, &custom_tools, Some(toolKey.as_str())).await?;
 
     // Emit config-changed and mcp-changed events
     let _ = app.emit("config-changed", "window");
@@ -450,49 +449,13 @@ pub async fn mcp_sync_all<R: Runtime>(
     app: AppHandle<R>,
     state: State<'_, SqliteDbState>,
 ) -> Result<Vec<McpSyncResultDto>, String> {
+    ensure_writable("mcp_sync_all")?;
     let custom_tools = custom_store::get_custom_tools(&state)
         .await
         .unwrap_or_default();
     let db = state.db();
     let servers = mcp_store::get_mcp_servers(&state).await?;
-    let mut results = Vec::new();
-
-    for server in servers {
-        for tool_key in &server.enabled_tools {
-            let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) else {
-                continue;
-            };
-
-            if !is_tool_installed_with_db_async(&db, &tool).await {
-                continue;
-            }
-
-            match sync_server_to_tool_async(&db, &server, &tool).await {
-                Ok(detail) => {
-                    mcp_store::update_sync_detail(&state, &server.id, &detail).await?;
-                    results.push(McpSyncResultDto {
-                        tool: tool_key.clone(),
-                        success: true,
-                        error_message: None,
-                    });
-                }
-                Err(e) => {
-                    let detail = McpSyncDetail {
-                        tool: tool_key.clone(),
-                        status: "error".to_string(),
-                        synced_at: Some(now_ms()),
-                        error_message: Some(e.clone()),
-                    };
-                    mcp_store::update_sync_detail(&state, &server.id, &detail).await?;
-                    results.push(McpSyncResultDto {
-                        tool: tool_key.clone(),
-                        success: false,
-                        error_message: Some(e),
-                    });
-                }
-            }
-        }
-    }
+    let results = sync_servers_two_phase(&state, &servers, &custom_tools, None).await?;
 
     // Also sync disabled servers to opencode if switch is ON
     let prefs = mcp_store::get_mcp_preferences(&state)
@@ -520,6 +483,7 @@ pub async fn mcp_import_from_tool(
     toolKey: String,
     enabledTools: Option<Vec<String>>,
 ) -> Result<McpImportResultDto, String> {
+    ensure_writable("mcp_import_from_tool")?;
     let custom_tools = custom_store::get_custom_tools(&state)
         .await
         .unwrap_or_default();
@@ -535,15 +499,24 @@ pub async fn mcp_import_from_tool(
                 .find(|p| p.plugin_id == plugin_id)
                 .ok_or_else(|| format!("Plugin not found: {}", plugin_id))?;
             let mcp_json_path = plugin.install_path.join(".mcp.json");
-            let servers = import_servers_from_plugin_mcp_json(&mcp_json_path)?;
-            (servers, format!("Plugin: {}", plugin.display_name))
+            let imported = import_servers_from_plugin_mcp_json(&mcp_json_path)?;
+            (imported, format!("Plugin: {}", plugin.display_name))
+        } else if let Some(name) = toolKey.strip_prefix(GEMINI_EXTENSION_KEY_PREFIX) {
+            // Gemini CLI extension source: read the servers from its manifest
+            let extensions = gemini_extensions::get_installed_extensions(&state.db()).await;
+            let extension = extensions
+                .iter()
+                .find(|extension| extension.name == name)
+                .ok_or_else(|| format!("Gemini extension not found: {}", name))?;
+            let imported = import_servers_from_gemini_extension(extension)?;
+            (imported, extension.display_name())
         } else {
             // Standard tool source
             let tool = runtime_tool_by_key(&toolKey, &custom_tools)
                 .ok_or_else(|| format!("Tool not found: {}", toolKey))?;
-            let servers = import_servers_from_tool_async(&state.db(), &tool).await?;
+            let imported = import_servers_from_tool_async(&state.db(), &tool).await?;
             (
-                servers,
+                imported,
                 super::mcp_tool_display_name(&tool.key, &tool.display_name),
             )
         };
@@ -599,7 +572,7 @@ pub async fn mcp_import_from_tool(
     let mut servers_duplicated = Vec::new();
     let mut errors = Vec::new();
 
-    for mut server in imported_servers {
+    for mut server in imported_servers.servers {
         // Check if server with same name already exists
         if let Some(existing) = mcp_store::get_mcp_server_by_name(&state, &server.name).await? {
             // Compare configurations
@@ -659,6 +632,7 @@ pub async fn mcp_import_from_tool(
         servers_skipped,
         servers_duplicated,
         errors,
+        warnings: imported_servers.warnings,
     })
 }
 
@@ -713,6 +687,7 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
         existing_servers.iter().map(|s| s.name.clone()).collect();
     let claude_plugins =
         crate::coding::tools::claude_plugins::get_installed_plugins(&scan_db).await;
+    let gemini_extensions = gemini_extensions::get_installed_extensions(&scan_db).await;
 
     let mut scan_targets = Vec::new();
     for tool in &mcp_tools {
@@ -736,6 +711,7 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
     let scan_result = tokio::task::spawn_blocking(move || {
         let mut total_tools_scanned = 0;
         let mut servers: Vec<McpDiscoveredServerDto> = Vec::new();
+        let mut warnings = Vec::new();
 
         for (tool, config_path) in &scan_targets {
             eprintln!("[DEBUG][mcp_scan_servers] scanning tool: {}", tool.key);
@@ -747,9 +723,10 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
                     eprintln!(
                         "[DEBUG][mcp_scan_servers] {} imported {} servers",
                         tool.key,
-                        imported.len()
+                        imported.servers.len()
                     );
-                    for server in imported {
+                    warnings.extend(imported.warnings);
+                    for server in imported.servers {
                         // Skip servers that already exist in the database
                         if existing_names.contains(&server.name) {
                             continue;
@@ -783,7 +760,8 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
 
             match import_servers_from_plugin_mcp_json(&mcp_json_path) {
                 Ok(imported) => {
-                    for server in imported {
+                    warnings.extend(imported.warnings);
+                    for server in imported.servers {
                         if existing_names.contains(&server.name) {
                             continue;
                         }
@@ -802,10 +780,43 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
             }
         }
 
+        // Scan Gemini CLI extensions for MCP servers
+        for extension in &gemini_extensions {
+            if extension.mcp_servers.is_empty() {
+                continue;
+            }
+
+            let tool_key = extension.source_key();
+            let tool_name = extension.display_name();
+            total_tools_scanned += 1;
+
+            match import_servers_from_gemini_extension(extension) {
+                Ok(imported) => {
+                    warnings.extend(imported.warnings);
+                    for server in imported.servers {
+                        if existing_names.contains(&server.name) {
+                            continue;
+                        }
+                        servers.push(McpDiscoveredServerDto {
+                            name: server.name,
+                            tool_key: tool_key.clone(),
+                            tool_name: tool_name.clone(),
+                            server_type: server.server_type,
+                            server_config: server.server_config,
+                        });
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Failed to scan Gemini extension {}: {}", extension.name, e);
+                }
+            }
+        }
+
         McpScanResultDto {
             total_tools_scanned,
             total_servers_found: servers.len() as i32,
             servers,
+            warnings,
         }
     })
     .await
@@ -814,6 +825,207 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
     Ok(scan_result)
 }
 
+// ==================== Health Check ====================
+
+fn server_health_timeout(server: &McpServer) -> u64 {
+    server
+        .timeout
+        .filter(|secs| *secs > 0)
+        .map(|secs| secs as u64)
+        .unwrap_or(health::DEFAULT_HEALTH_TIMEOUT_SECS)
+}
+
+/// Run the MCP handshake against one server and list its tools
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_health_check(
+    state: State<'_, SqliteDbState>,
+    serverId: String,
+) -> Result<McpHealthResultDto, String> {
+    let server = mcp_store::get_mcp_server_by_id(&state, &serverId)
+        .await?
+        .ok_or_else(|| format!("MCP server not found: {}", serverId))?;
+    Ok(health::check_server_health(&state, &server, server_health_timeout(&server)).await)
+}
+
+/// Check that the runtimes a server launches through (`npx`, `uvx`,
+/// `docker`, ...) are installed; the frontend runs it when a server is added
+/// or enabled for a tool
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_check_prerequisites(
+    serverType: String,
+    serverConfig: serde_json::Value,
+) -> Result<McpPrerequisiteReportDto, String> {
+    Ok(prerequisites::check_prerequisites(&serverType, &serverConfig).await)
+}
+
+/// Detect tool-name collisions across servers enabled for the same tool.
+/// Uses cached handshake results unless `refresh` is set; servers without a
+/// cached result are probed.
+#[tauri::command]
+pub async fn mcp_check_tool_collisions(
+    state: State<'_, SqliteDbState>,
+    refresh: Option<bool>,
+) -> Result<McpCollisionReportDto, String> {
+    let refresh = refresh.unwrap_or(false);
+    let servers = mcp_store::get_mcp_servers(&state).await?;
+    let enabled: Vec<&McpServer> = servers
+        .iter()
+        .filter(|server| !server.enabled_tools.is_empty())
+        .collect();
+
+    let mut results = BTreeMap::new();
+    let mut pending = Vec::new();
+    for server in enabled {
+        match health::cached_health(server).filter(|_| !refresh) {
+            Some(result) => {
+                results.insert(server.id.clone(), result);
+            }
+            None => pending.push(server),
+        }
+    }
+    let probed =
+        futures_util::future::join_all(pending.into_iter().map(|server| {
+            let state = &*state;
+            async move {
+                health::check_server_health(state, server, server_health_timeout(server)).await
+            }
+        }))
+        .await;
+    for result in probed {
+        results.insert(result.server_id.clone(), result);
+    }
+
+    Ok(collisions::analyze_collisions(&servers, &results, now_ms()))
+}
+
+// ==================== Gateway ====================
+
+/// Gateway state: listening port, remote-only tools and open sessions
+#[tauri::command]
+pub async fn mcp_get_gateway_status() -> Result<McpGatewayStatusDto, String> {
+    Ok(gateway::gateway_status())
+}
+
+/// Set the tools that only take URL servers. Their stdio servers are resynced
+/// as SSE servers behind the local gateway, which runs while the list is not
+/// empty; tools removed from the list get the stdio form back.
+#[tauri::command]
+pub async fn mcp_set_remote_only_tools<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    tools: Vec<String>,
+) -> Result<McpGatewayStatusDto, String> {
+    ensure_writable("mcp_set_remote_only_tools")?;
+    let status = gateway::apply_remote_only_tools(&state, tools).await?;
+
+    let _ = app.emit("config-changed", "window");
+    let _ = app.emit("mcp-changed", "window");
+
+    Ok(status)
+}
+
+// ==================== Traffic Logging ====================
+
+/// Turn JSON-RPC traffic logging on or off for a stdio server and resync it
+/// to its tools, so they launch it through (or without) the logging proxy
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_set_traffic_logging<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    serverId: String,
+    enabled: bool,
+) -> Result<(), String> {
+    ensure_writable("mcp_set_traffic_logging")?;
+    let server = mcp_store::get_mcp_server_by_id(&state, &serverId)
+        .await?
+        .ok_or_else(|| format!("MCP server not found: {}", serverId))?;
+    if enabled && server.server_type != "stdio" {
+        return Err("Traffic logging only supports stdio servers".to_string());
+    }
+
+    let mut prefs = mcp_store::get_mcp_preferences(&state).await?;
+    prefs.traffic_log_servers.retain(|id| id != &serverId);
+    if enabled {
+        prefs.traffic_log_servers.push(serverId.clone());
+    }
+    prefs.updated_at = now_ms();
+    mcp_store::save_mcp_preferences(&state, &prefs).await?;
+    traffic_log::set_logged_servers(&prefs.traffic_log_servers);
+
+    let custom_tools = custom_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    sync_servers_two_phase(&state, std::slice::from_ref(&server), &custom_tools, None).await?;
+
+    let _ = app.emit("config-changed", "window");
+    let _ = app.emit("mcp-changed", "window");
+
+    Ok(())
+}
+
+/// Logged traffic of a server, newest `limit` entries oldest first
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_get_traffic(
+    serverId: String,
+    limit: Option<usize>,
+) -> Result<McpTrafficDto, String> {
+    let limit = limit.unwrap_or(traffic_log::DEFAULT_TRAFFIC_LIMIT);
+    let log_path = traffic_log::traffic_log_path(&serverId)?;
+    let entries = tokio::task::spawn_blocking({
+        let server_id = serverId.clone();
+        move || traffic_log::read_traffic(&server_id, limit)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))??;
+    Ok(McpTrafficDto {
+        enabled: traffic_log::is_logged(&serverId),
+        server_id: serverId,
+        log_path: log_path.to_string_lossy().into_owned(),
+        entries,
+    })
+}
+
+/// Delete a server's traffic log
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_clear_traffic(serverId: String) -> Result<(), String> {
+    ensure_writable("mcp_clear_traffic")?;
+    traffic_log::clear_traffic(&serverId)
+}
+
+// ==================== Apply History ====================
+
+/// Recorded writes of MCP tool configs, newest first. `toolKey` gives the
+/// history of one tool, `serverName` the changes that touched one server.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_get_apply_history(
+    toolKey: Option<String>,
+    serverName: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<McpApplyRecord>, String> {
+    tokio::task::spawn_blocking(move || {
+        apply_history::query_history(
+            toolKey.as_deref(),
+            serverName.as_deref(),
+            limit.unwrap_or(apply_history::DEFAULT_HISTORY_LIMIT),
+        )
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?
+}
+
+/// Tool config content as it was before the write that recorded `backupRef`
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_get_apply_backup(backupRef: String) -> Result<String, String> {
+    apply_history::read_backup(&backupRef)
+}
+
 // ==================== Preferences ====================
 
 /// Get MCP show in tray setting
@@ -895,6 +1107,7 @@ pub async fn mcp_set_sync_disabled_to_opencode<R: Runtime>(
     state: State<'_, SqliteDbState>,
     enabled: bool,
 ) -> Result<(), String> {
+    ensure_writable("mcp_set_sync_disabled_to_opencode")?;
     let mut prefs = mcp_store::get_mcp_preferences(&state).await?;
     prefs.sync_disabled_to_opencode = enabled;
     prefs.updated_at = now_ms();
@@ -918,6 +1131,92 @@ pub async fn mcp_set_sync_disabled_to_opencode<R: Runtime>(
     Ok(())
 }
 
+/// Upsert servers into their enabled, installed tools with a two-phase apply
+/// (see `staged_apply`): every tool's config is staged and validated before
+/// any file is written, and written files are restored if a later write
+/// fails. Records one sync detail per (server, tool). Tools without an MCP
+/// config path cannot take part and only get an error detail.
+pub(super) async fn sync_servers_two_phase(
+    state: &SqliteDbState,
+    servers: &[McpServer],
+    custom_tools: &[CustomTool],
+    only_tool: Option<&str>,
+) -> Result<Vec<McpSyncResultDto>, String> {
+    let db = state.db();
+    // tool key -> servers enabled for it
+    let mut by_tool: BTreeMap<&str, Vec<&McpServer>> = BTreeMap::new();
+    for server in servers {
+        for tool_key in &server.enabled_tools {
+            if only_tool.is_none_or(|only| only == tool_key) {
+                by_tool.entry(tool_key.as_str()).or_default().push(server);
+            }
+        }
+    }
+
+    let mut outcomes: Vec<(&McpServer, String, Result<(), String>)> = Vec::new();
+    let mut targets = Vec::new();
+    for (tool_key, tool_servers) in by_tool {
+        let Some(tool) = runtime_tool_by_key(tool_key, custom_tools) else {
+            continue;
+        };
+        if !is_tool_installed_with_db_async(&db, &tool).await {
+            continue;
+        }
+        match resolve_mcp_config_path_with_db_async(&db, &tool).await {
+            Some(config_path) => targets.push((tool, config_path, tool_servers)),
+            None => {
+                let error = format!("Tool {} does not support MCP", tool.key);
+                for server in tool_servers {
+                    outcomes.push((server, tool.key.clone(), Err(error.clone())));
+                }
+            }
+        }
+    }
+
+    let entries: Vec<ToolApply<'_>> = targets
+        .iter()
+        .map(|(tool, config_path, tool_servers)| ToolApply {
+            tool,
+            config_path: config_path.clone(),
+            ops: tool_servers
+                .iter()
+                .map(|server| McpConfigOp::Upsert {
+                    server,
+                    enabled: true,
+                })
+                .collect(),
+        })
+        .collect();
+    let applied = staged_apply::apply_two_phase(&entries).map_err(|e| e.to_string());
+    if applied.is_ok() {
+        for (tool, config_path, _) in &targets {
+            schedule_post_sync(state, &tool.key, PostSyncScope::Mcp, config_path);
+        }
+    }
+    for (tool, _, tool_servers) in &targets {
+        for server in tool_servers {
+            outcomes.push((server, tool.key.clone(), applied.clone()));
+        }
+    }
+
+    let mut results = Vec::new();
+    for (server, tool_key, outcome) in outcomes {
+        let detail = McpSyncDetail {
+            tool: tool_key.clone(),
+            status: if outcome.is_ok() { "ok" } else { "error" }.to_string(),
+            synced_at: Some(now_ms()),
+            error_message: outcome.clone().err(),
+        };
+        mcp_store::update_sync_detail(state, &server.id, &detail).await?;
+        results.push(McpSyncResultDto {
+            tool: tool_key,
+            success: outcome.is_ok(),
+            error_message: outcome.err(),
+        });
+    }
+    Ok(results)
+}
+
 /// If sync_disabled_to_opencode is ON and server is not linked to opencode,
 /// write it to opencode config with enabled=false.
 async fn maybe_sync_disabled_to_opencode(
@@ -1007,6 +1306,7 @@ pub async fn mcp_add_custom_tool(
     mcpConfigFormat: String,
     mcpField: String,
 ) -> Result<(), String> {
+    ensure_writable("mcp_add_custom_tool")?;
     use crate::coding::tools::path_utils::{normalize_path, to_storage_path};
 
     // Trim whitespace from all inputs
@@ -1059,6 +1359,7 @@ pub async fn mcp_remove_custom_tool(
     state: State<'_, SqliteDbState>,
     key: String,
 ) -> Result<(), String> {
+    ensure_writable("mcp_remove_custom_tool")?;
     // Get the existing tool
     let existing = custom_store::get_custom_tool_by_key(&state, &key).await?;
 
@@ -1116,6 +1417,7 @@ pub async fn mcp_upsert_favorite(
     state: State<'_, SqliteDbState>,
     input: FavoriteMcpInput,
 ) -> Result<FavoriteMcpDto, String> {
+    ensure_writable("mcp_upsert_favorite")?;
     let now = now_ms();
 
     // Check if a favorite with the same name exists
@@ -1171,6 +1473,7 @@ pub async fn mcp_delete_favorite(
     state: State<'_, SqliteDbState>,
     favoriteId: String,
 ) -> Result<(), String> {
+    ensure_writable("mcp_delete_favorite")?;
     mcp_store::delete_favorite_mcp(&state, &favoriteId).await
 }
 
@@ -1215,6 +1518,7 @@ const DEFAULT_FAVORITE_MCP_PRESETS: &[(&str, &str, &str)] = &[
 
 #[tauri::command]
 pub async fn mcp_init_default_favorites(state: State<'_, SqliteDbState>) -> Result<usize, String> {
+    ensure_writable("mcp_init_default_favorites")?;
     let prefs = mcp_store::get_mcp_preferences(&state).await?;
     let now = now_ms();
     let mut inserted_count = 0;
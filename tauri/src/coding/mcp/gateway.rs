@@ -0,0 +1,623 @@
+//! Local stdio -> SSE gateway for tools that only take remote servers
+//!
+//! Tools listed in `McpPreferences.remote_only_tools` cannot spawn stdio
+//! servers, so stdio servers are synced to them as
+//! `{"type": "sse", "url": "http://127.0.0.1:<port>/<token>/<server id>/sse"}`
+//! and this gateway serves that URL with the legacy SSE transport:
+//!
+//! - `GET /<token>/<server id>/sse` starts the server's stdio process for
+//!   this connection, sends the `endpoint` event, then relays every stdout
+//!   line as a `message` event
+//! - `POST /<token>/<server id>/message?sessionId=...` writes the JSON-RPC
+//!   body to that session's stdin
+//!
+//! Starting a server runs a local command with its env secrets, so a request
+//! must carry the per-install `McpPreferences.gateway_token` and a loopback
+//! `Host` (no DNS rebinding), responses send no CORS headers, only servers
+//! enabled for a remote-only tool are served and at most `MAX_SESSIONS` run
+//! at once.
+//!
+//! Each SSE connection owns its process (a stdio server serves one client);
+//! the process is killed when the connection closes or the gateway stops.
+//! The gateway only listens while at least one tool is remote-only. It keeps
+//! `DEFAULT_PORT` when free; the bound port and the token are saved in
+//! preferences and a change resyncs the affected tools so their URLs stay
+//! valid.
+
+use std::collections::{BTreeSet, HashMap};
+use std::net::IpAddr;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::Manager;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, watch};
+
+use super::health::string_pairs;
+use super::mcp_store;
+use super::traffic_log;
+use super::types::{McpServer, StdioConfig};
+use crate::coding::cli_resolver::{build_local_tokio_command, resolve_local_command_program};
+use crate::coding::tools::custom_store;
+use crate::SqliteDbState;
+
+const DEFAULT_PORT: u16 = 39217;
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(25);
+const MAX_HEADER_LINES: usize = 100;
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+/// Server processes the gateway runs at once
+const MAX_SESSIONS: usize = 16;
+
+struct Session {
+    server_id: String,
+    stdin: mpsc::UnboundedSender<String>,
+}
+
+#[derive(Default)]
+struct Gateway {
+    port: Option<u16>,
+    /// Secret first path segment of every gateway URL
+    token: Option<String>,
+    remote_only_tools: BTreeSet<String>,
+    /// Dropping (or sending on) it stops the listener and every session
+    stop: Option<watch::Sender<bool>>,
+    sessions: HashMap<String, Session>,
+}
+
+fn gateway() -> &'static Mutex<Gateway> {
+    static GATEWAY: OnceLock<Mutex<Gateway>> = OnceLock::new();
+    GATEWAY.get_or_init(|| Mutex::new(Gateway::default()))
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct McpGatewayStatusDto {
+    pub running: bool,
+    pub port: Option<u16>,
+    pub remote_only_tools: Vec<String>,
+    /// Open SSE connections, one server process each
+    pub sessions: usize,
+}
+
+pub fn gateway_status() -> McpGatewayStatusDto {
+    let Ok(gateway) = gateway().lock() else {
+        return McpGatewayStatusDto::default();
+    };
+    McpGatewayStatusDto {
+        running: gateway.stop.is_some(),
+        port: gateway.port,
+        remote_only_tools: gateway.remote_only_tools.iter().cloned().collect(),
+        sessions: gateway.sessions.len(),
+    }
+}
+
+fn gateway_url(port: u16, token: &str, server_id: &str) -> String {
+    format!("http://127.0.0.1:{}/{}/{}/sse", port, token, server_id)
+}
+
+/// The SSE form of a stdio `server` when `tool_key` is remote-only and the
+/// gateway is listening; `None` means sync the server unchanged
+pub fn route_for_tool(server: &McpServer, tool_key: &str) -> Option<McpServer> {
+    if server.server_type != "stdio" {
+        return None;
+    }
+    let gateway = gateway().lock().ok()?;
+    if !gateway.remote_only_tools.contains(tool_key) {
+        return None;
+    }
+    let (Some(port), Some(token)) = (gateway.port, gateway.token.as_deref()) else {
+        warn!(
+            "MCP gateway is not running, syncing '{}' to {} as stdio",
+            server.name, tool_key
+        );
+        return None;
+    };
+    let mut routed = server.clone();
+    routed.server_type = "sse".to_string();
+    routed.server_config = json!({ "url": gateway_url(port, token, &server.id) });
+    Some(routed)
+}
+
+/// Start at app launch when any tool is remote-only. Also loads the
+/// traffic-logged servers first, so every sync after startup wraps them.
+pub fn start_mcp_gateway(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<SqliteDbState>();
+        let prefs = match mcp_store::get_mcp_preferences(&state).await {
+            Ok(prefs) => prefs,
+            Err(e) => {
+                warn!("Failed to read MCP preferences for the gateway: {}", e);
+                return;
+            }
+        };
+        traffic_log::set_logged_servers(&prefs.traffic_log_servers);
+        if let Err(e) = apply_remote_only_tools(&state, prefs.remote_only_tools).await {
+            warn!("Failed to start MCP gateway: {}", e);
+        }
+    });
+}
+
+/// Set the remote-only tools, start or stop the listener accordingly, and
+/// resync stdio servers of every tool whose URLs or transport changed
+pub async fn apply_remote_only_tools(
+    state: &SqliteDbState,
+    tools: Vec<String>,
+) -> Result<McpGatewayStatusDto, String> {
+    let tools: BTreeSet<String> = tools.into_iter().collect();
+    let mut prefs = mcp_store::get_mcp_preferences(state).await?;
+
+    let (previous_tools, running_port) = {
+        let gateway = gateway().lock().map_err(|e| e.to_string())?;
+        (
+            gateway.remote_only_tools.clone(),
+            gateway.stop.as_ref().and(gateway.port),
+        )
+    };
+    let token_created = !tools.is_empty() && prefs.gateway_token.is_none();
+    if token_created {
+        prefs.gateway_token = Some(uuid::Uuid::new_v4().simple().to_string());
+    }
+    {
+        // Set before the listener starts so no request sees the old state
+        let mut gateway = gateway().lock().map_err(|e| e.to_string())?;
+        gateway.remote_only_tools = tools.clone();
+        gateway.token = prefs.gateway_token.clone();
+    }
+    let port = if tools.is_empty() {
+        stop_listener();
+        None
+    } else if let Some(port) = running_port {
+        Some(port)
+    } else {
+        Some(start_listener(state.clone(), prefs.gateway_port).await?)
+    };
+    gateway().lock().map_err(|e| e.to_string())?.port = port;
+
+    // Tools entering or leaving the set switch transport; a new port or token
+    // changes every remote-only tool's URLs
+    let port_changed = port.is_some() && (port != prefs.gateway_port || token_created);
+    let resync: BTreeSet<&String> = if port_changed {
+        tools.union(&previous_tools).collect()
+    } else {
+        tools.symmetric_difference(&previous_tools).collect()
+    };
+
+    let remote_only_tools: Vec<String> = tools.iter().cloned().collect();
+    if prefs.remote_only_tools != remote_only_tools || port_changed {
+        prefs.remote_only_tools = remote_only_tools;
+        if port.is_some() {
+            prefs.gateway_port = port;
+        }
+        prefs.updated_at = super::types::now_ms();
+        mcp_store::save_mcp_preferences(state, &prefs).await?;
+    }
+
+    if !resync.is_empty() {
+        let servers: Vec<McpServer> = mcp_store::get_mcp_servers(state)
+            .await?
+            .into_iter()
+            .filter(|server| server.server_type == "stdio")
+            .collect();
+        let custom_tools = custom_store::get_custom_tools(state)
+            .await
+            .unwrap_or_default();
+        for tool in resync {
+            if let Err(e) = super::commands::sync_servers_two_phase(
+                state,
+                &servers,
+                &custom_tools,
+                Some(tool.as_str()),
+            )
+            .await
+            {
+                warn!("Failed to resync MCP servers to {}: {}", tool, e);
+            }
+        }
+    }
+
+    Ok(gateway_status())
+}
+
+async fn start_listener(state: SqliteDbState, preferred_port: Option<u16>) -> Result<u16, String> {
+    let mut listener = None;
+    for port in [preferred_port.unwrap_or(DEFAULT_PORT), DEFAULT_PORT, 0] {
+        if let Ok(bound) = TcpListener::bind(("127.0.0.1", port)).await {
+            listener = Some(bound);
+            break;
+        }
+    }
+    let listener = listener.ok_or("Failed to bind the MCP gateway port")?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read the MCP gateway port: {}", e))?
+        .port();
+
+    let (stop_tx, stop_rx) = watch::channel(false);
+    gateway().lock().map_err(|e| e.to_string())?.stop = Some(stop_tx);
+    info!("MCP gateway listening on 127.0.0.1:{}", port);
+
+    tauri::async_runtime::spawn(async move {
+        let mut stop = stop_rx.clone();
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        let state = state.clone();
+                        let stop = stop_rx.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = handle_connection(stream, state, stop).await {
+                                warn!("MCP gateway request failed: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => warn!("MCP gateway accept failed: {}", e),
+                },
+                _ = stop.changed() => break,
+            }
+        }
+        info!("MCP gateway stopped");
+    });
+    Ok(port)
+}
+
+fn stop_listener() {
+    if let Ok(mut gateway) = gateway().lock() {
+        if let Some(stop) = gateway.stop.take() {
+            let _ = stop.send(true);
+        }
+        gateway.sessions.clear();
+        gateway.port = None;
+    }
+}
+
+/// Whether a `Host` header names this machine; anything else is a page on
+/// another origin reaching the gateway through DNS rebinding
+fn is_loopback_host(host: &str) -> bool {
+    let host = host.trim();
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(""),
+        None => host.split(':').next().unwrap_or(""),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Compare in constant time so response timing does not leak the token
+fn token_matches(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Sse {
+        server_id: String,
+    },
+    Message {
+        server_id: String,
+        session_id: String,
+    },
+}
+
+/// The route of `target`, when it starts with the gateway `token`
+fn parse_route(method: &str, target: &str, token: &str) -> Option<Route> {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut segments = path.trim_start_matches('/').split('/');
+    if !token_matches(segments.next()?, token) {
+        return None;
+    }
+    let server_id = segments.next().filter(|id| !id.is_empty())?.to_string();
+    let endpoint = segments.next()?;
+    if segments.next().is_some() {
+        return None;
+    }
+    match (method, endpoint) {
+        ("GET", "sse") => Some(Route::Sse { server_id }),
+        ("POST", "message") => {
+            let session_id = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("sessionId="))
+                .filter(|id| !id.is_empty())?
+                .to_string();
+            Some(Route::Message {
+                server_id,
+                session_id,
+            })
+        }
+        _ => None,
+    }
+}
+
+async fn respond(write: &mut OwnedWriteHalf, status: &str, body: &str) -> Result<(), String> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    write
+        .write_all(response.as_bytes())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    state: SqliteDbState,
+    stop: watch::Receiver<bool>,
+) -> Result<(), String> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let mut content_length = 0usize;
+    let mut host = None;
+    for _ in 0..MAX_HEADER_LINES {
+        let mut line = String::new();
+        if reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| e.to_string())?
+            == 0
+        {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            } else if name.trim().eq_ignore_ascii_case("host") {
+                host = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    if !host.as_deref().is_some_and(is_loopback_host) {
+        return respond(&mut write, "403 Forbidden", "Forbidden").await;
+    }
+    let token = gateway()
+        .lock()
+        .map_err(|e| e.to_string())?
+        .token
+        .clone()
+        .unwrap_or_default();
+    let route = if token.is_empty() {
+        None
+    } else {
+        parse_route(method, target, &token)
+    };
+    match route {
+        Some(Route::Sse { server_id }) => {
+            serve_sse(reader, write, &state, &token, &server_id, stop).await
+        }
+        Some(Route::Message {
+            server_id,
+            session_id,
+        }) => {
+            if content_length > MAX_BODY_BYTES {
+                return respond(&mut write, "413 Payload Too Large", "Body too large").await;
+            }
+            let mut body = vec![0; content_length];
+            reader
+                .read_exact(&mut body)
+                .await
+                .map_err(|e| e.to_string())?;
+            let (status, text) = forward_message(&server_id, &session_id, &body);
+            respond(&mut write, status, text).await
+        }
+        None => respond(&mut write, "404 Not Found", "Not found").await,
+    }
+}
+
+fn forward_message(server_id: &str, session_id: &str, body: &[u8]) -> (&'static str, &'static str) {
+    // Re-serialize so the message is one line on the server's stdin
+    let Ok(message) = serde_json::from_slice::<Value>(body) else {
+        return ("400 Bad Request", "Invalid JSON-RPC message");
+    };
+    let Ok(gateway) = gateway().lock() else {
+        return ("500 Internal Server Error", "Gateway unavailable");
+    };
+    match gateway.sessions.get(session_id) {
+        Some(session) if session.server_id == server_id => {
+            if session.stdin.send(message.to_string()).is_ok() {
+                ("202 Accepted", "Accepted")
+            } else {
+                ("410 Gone", "Session closed")
+            }
+        }
+        _ => ("404 Not Found", "Unknown session"),
+    }
+}
+
+/// Whether `server` is synced to a remote-only tool, i.e. reached through
+/// the gateway at all
+fn is_routed(server: &McpServer) -> bool {
+    gateway().lock().is_ok_and(|gateway| {
+        server
+            .enabled_tools
+            .iter()
+            .any(|tool| gateway.remote_only_tools.contains(tool))
+    })
+}
+
+async fn send_event(write: &mut OwnedWriteHalf, event: &str, data: &str) -> bool {
+    let frame = format!("event: {}\ndata: {}\n\n", event, data);
+    write.write_all(frame.as_bytes()).await.is_ok()
+}
+
+async fn serve_sse(
+    mut reader: BufReader<OwnedReadHalf>,
+    mut write: OwnedWriteHalf,
+    state: &SqliteDbState,
+    token: &str,
+    server_id: &str,
+    mut stop: watch::Receiver<bool>,
+) -> Result<(), String> {
+    let server = mcp_store::get_mcp_server_by_id(state, server_id)
+        .await?
+        .filter(|server| server.server_type == "stdio" && is_routed(server));
+    let Some(server) = server else {
+        return respond(&mut write, "404 Not Found", "MCP server not found").await;
+    };
+    let server = traffic_log::logged_server(&server).unwrap_or(server);
+    let config: StdioConfig = serde_json::from_value(server.server_config.clone())
+        .map_err(|e| format!("Invalid stdio config: {}", e))?;
+    let program = resolve_local_command_program(&config.command);
+    let mut command = build_local_tokio_command(&program.path);
+    command
+        .args(&config.args)
+        .envs(string_pairs(config.env.as_ref()))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+
+    // Reserve the session before spawning so concurrent requests cannot
+    // exceed the cap
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let (stdin_tx, mut stdin_rx) = mpsc::unbounded_channel::<String>();
+    let reserved = {
+        let mut gateway = gateway().lock().map_err(|e| e.to_string())?;
+        let reserved = gateway.sessions.len() < MAX_SESSIONS;
+        if reserved {
+            gateway.sessions.insert(
+                session_id.clone(),
+                Session {
+                    server_id: server_id.to_string(),
+                    stdin: stdin_tx,
+                },
+            );
+        }
+        reserved
+    };
+    if !reserved {
+        return respond(
+            &mut write,
+            "503 Service Unavailable",
+            "Too many MCP gateway sessions",
+        )
+        .await;
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            if let Ok(mut gateway) = gateway().lock() {
+                gateway.sessions.remove(&session_id);
+            }
+            let error = format!("Failed to start {}: {}", config.command, e);
+            respond(&mut write, "502 Bad Gateway", &error).await?;
+            return Err(error);
+        }
+    };
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or("Failed to open MCP server stdin")?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or("Failed to open MCP server stdout")?;
+
+    tauri::async_runtime::spawn(async move {
+        while let Some(line) = stdin_rx.recv().await {
+            let written = async {
+                stdin.write_all(line.as_bytes()).await?;
+                stdin.write_all(b"\n").await?;
+                stdin.flush().await
+            };
+            if written.await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let headers = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+    let endpoint = format!("/{}/{}/message?sessionId={}", token, server_id, session_id);
+    let mut open = write.write_all(headers.as_bytes()).await.is_ok()
+        && send_event(&mut write, "endpoint", &endpoint).await;
+
+    let mut lines = BufReader::new(stdout).lines();
+    let mut client_bytes = [0u8; 256];
+    while open {
+        tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) if line.trim().is_empty() => {}
+                Ok(Some(line)) => open = send_event(&mut write, "message", line.trim()).await,
+                _ => open = false,
+            },
+            _ = tokio::time::sleep(KEEPALIVE_INTERVAL) => {
+                open = write.write_all(b": keepalive\n\n").await.is_ok();
+            }
+            // The client sends nothing after the request, so a read only
+            // returns when it disconnects
+            read = reader.read(&mut client_bytes) => open = matches!(read, Ok(n) if n > 0),
+            _ = stop.changed() => open = false,
+        }
+    }
+
+    // Dropping the sender ends the stdin task; dropping the child kills it
+    if let Ok(mut gateway) = gateway().lock() {
+        gateway.sessions.remove(&session_id);
+    }
+    drop(child);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sse_and_message_routes() {
+        let route = |method, target| parse_route(method, target, "t0k");
+        assert_eq!(
+            route("GET", "/t0k/abc/sse"),
+            Some(Route::Sse {
+                server_id: "abc".to_string()
+            })
+        );
+        assert_eq!(
+            route("POST", "/t0k/abc/message?sessionId=s1&x=1"),
+            Some(Route::Message {
+                server_id: "abc".to_string(),
+                session_id: "s1".to_string(),
+            })
+        );
+        assert_eq!(route("POST", "/t0k/abc/message"), None);
+        assert_eq!(route("GET", "/t0k/abc/message?sessionId=s1"), None);
+        assert_eq!(route("GET", "/t0k//sse"), None);
+        assert_eq!(route("GET", "/t0k/abc/sse/extra"), None);
+        // Without the token nothing is routed
+        assert_eq!(route("GET", "/abc/sse"), None);
+        assert_eq!(route("GET", "/t0x/abc/sse"), None);
+    }
+
+    #[test]
+    fn accepts_only_loopback_hosts() {
+        for host in ["127.0.0.1:39217", "localhost", "LOCALHOST:1", "[::1]:39217"] {
+            assert!(is_loopback_host(host), "{}", host);
+        }
+        for host in ["evil.example:39217", "192.168.1.2", "[fe80::1]", ""] {
+            assert!(!is_loopback_host(host), "{}", host);
+        }
+    }
+}
@@ -0,0 +1,412 @@
+//! MCP server health check
+//!
+//! Performs the MCP protocol handshake (`initialize` + `tools/list`) against a
+//! configured server and caches the result in memory. The cache entry is keyed
+//! by server id and dropped automatically when the server config changes.
+//! Legacy SSE servers are not probed.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{ChildStdin, ChildStdout};
+
+use super::types::{now_ms, HttpConfig, McpServer, McpServerType, StdioConfig};
+use crate::coding::cli_resolver::{build_local_tokio_command, resolve_local_command_program};
+use crate::SqliteDbState;
+
+const PROTOCOL_VERSION: &str = "2025-03-26";
+pub const DEFAULT_HEALTH_TIMEOUT_SECS: u64 = 20;
+const MAX_TOOL_PAGES: usize = 20;
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Result of a handshake with one MCP server
+#[derive(Clone, Debug, Serialize)]
+pub struct McpHealthResultDto {
+    pub server_id: String,
+    pub server_name: String,
+    pub ok: bool,
+    /// `serverInfo.name` + version reported by the server
+    pub server_info: Option<String>,
+    pub tools: Vec<String>,
+    pub error: Option<String>,
+    pub checked_at: i64,
+}
+
+/// server id -> (config fingerprint, last result)
+type HealthCache = HashMap<String, (String, McpHealthResultDto)>;
+
+fn health_cache() -> &'static Mutex<HealthCache> {
+    static CACHE: OnceLock<Mutex<HealthCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn config_fingerprint(server: &McpServer) -> String {
+    format!("{}|{}", server.server_type, server.server_config)
+}
+
+/// Cached result for a server, ignored when its config changed since
+pub fn cached_health(server: &McpServer) -> Option<McpHealthResultDto> {
+    let cache = health_cache().lock().ok()?;
+    cache
+        .get(&server.id)
+        .filter(|(fingerprint, _)| *fingerprint == config_fingerprint(server))
+        .map(|(_, result)| result.clone())
+}
+
+fn jsonrpc_request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn jsonrpc_notification(method: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method })
+}
+
+fn initialize_params() -> Value {
+    json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "capabilities": {},
+        "clientInfo": { "name": "ai-toolbox", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+/// Extract the `result` of a JSON-RPC response, turning `error` into Err
+fn response_result(response: Value) -> Result<Value, String> {
+    if let Some(error) = response.get("error") {
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(format!("MCP error: {}", message));
+    }
+    Ok(response.get("result").cloned().unwrap_or(Value::Null))
+}
+
+fn server_info_label(initialize_result: &Value) -> Option<String> {
+    let info = initialize_result.get("serverInfo")?;
+    let name = info.get("name").and_then(Value::as_str)?;
+    Some(match info.get("version").and_then(Value::as_str) {
+        Some(version) => format!("{} {}", name, version),
+        None => name.to_string(),
+    })
+}
+
+/// Tool names and the next page cursor of a `tools/list` result
+pub fn parse_tools_page(result: &Value) -> (Vec<String>, Option<String>) {
+    let names = result
+        .get("tools")
+        .and_then(Value::as_array)
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let next_cursor = result
+        .get("nextCursor")
+        .and_then(Value::as_str)
+        .filter(|cursor| !cursor.is_empty())
+        .map(str::to_string);
+    (names, next_cursor)
+}
+
+/// Find the response with `id` among SSE `data:` events of a streamable
+/// HTTP response body.
+pub fn find_sse_response(body: &str, id: u64) -> Option<Value> {
+    let mut data = String::new();
+    let mut events = Vec::new();
+    for line in body.lines().chain(std::iter::once("")) {
+        if let Some(chunk) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(chunk.trim_start());
+        } else if line.trim().is_empty() && !data.is_empty() {
+            events.push(std::mem::take(&mut data));
+        }
+    }
+    events
+        .iter()
+        .filter_map(|event| serde_json::from_str::<Value>(event).ok())
+        .find(|value| value.get("id").and_then(Value::as_u64) == Some(id))
+}
+
+trait McpSession {
+    async fn call(&mut self, id: u64, method: &str, params: Value) -> Result<Value, String>;
+    async fn notify(&mut self, method: &str) -> Result<(), String>;
+}
+
+struct StdioSession {
+    stdin: ChildStdin,
+    lines: Lines<BufReader<ChildStdout>>,
+}
+
+impl StdioSession {
+    async fn send(&mut self, message: &Value) -> Result<(), String> {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to MCP server: {}", e))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to write to MCP server: {}", e))
+    }
+}
+
+impl McpSession for StdioSession {
+    async fn call(&mut self, id: u64, method: &str, params: Value) -> Result<Value, String> {
+        self.send(&jsonrpc_request(id, method, params)).await?;
+        loop {
+            let line = self
+                .lines
+                .next_line()
+                .await
+                .map_err(|e| format!("Failed to read from MCP server: {}", e))?
+                .ok_or_else(|| "MCP server exited before responding".to_string())?;
+            // Servers sometimes log to stdout; skip anything that is not our response
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if message.get("id").and_then(Value::as_u64) == Some(id) {
+                return response_result(message);
+            }
+        }
+    }
+
+    async fn notify(&mut self, method: &str) -> Result<(), String> {
+        self.send(&jsonrpc_notification(method)).await
+    }
+}
+
+struct HttpSession {
+    client: reqwest::Client,
+    url: String,
+    headers: Vec<(String, String)>,
+    session_id: Option<String>,
+}
+
+impl HttpSession {
+    async fn post(&mut self, message: &Value) -> Result<reqwest::Response, String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(message);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some(session_id) = &self.session_id {
+            request = request.header(SESSION_HEADER, session_id);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("MCP request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("MCP server returned HTTP {}", response.status()));
+        }
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+        Ok(response)
+    }
+}
+
+impl McpSession for HttpSession {
+    async fn call(&mut self, id: u64, method: &str, params: Value) -> Result<Value, String> {
+        let response = self.post(&jsonrpc_request(id, method, params)).await?;
+        let is_event_stream = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.contains("text/event-stream"))
+            .unwrap_or(false);
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read MCP response: {}", e))?;
+        let message = if is_event_stream {
+            find_sse_response(&body, id)
+                .ok_or_else(|| "MCP server did not answer the request".to_string())?
+        } else {
+            serde_json::from_str(&body).map_err(|e| format!("Invalid MCP response JSON: {}", e))?
+        };
+        response_result(message)
+    }
+
+    async fn notify(&mut self, method: &str) -> Result<(), String> {
+        self.post(&jsonrpc_notification(method)).await.map(|_| ())
+    }
+}
+
+/// `initialize` → `notifications/initialized` → paginated `tools/list`
+async fn run_handshake<S: McpSession>(
+    session: &mut S,
+) -> Result<(Option<String>, Vec<String>), String> {
+    let initialize = session.call(1, "initialize", initialize_params()).await?;
+    session.notify("notifications/initialized").await?;
+
+    let mut tools = Vec::new();
+    let mut cursor: Option<String> = None;
+    for page in 0..MAX_TOOL_PAGES {
+        let params = match &cursor {
+            Some(cursor) => json!({ "cursor": cursor }),
+            None => json!({}),
+        };
+        let result = session.call(2 + page as u64, "tools/list", params).await?;
+        let (names, next_cursor) = parse_tools_page(&result);
+        tools.extend(names);
+        cursor = next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    Ok((server_info_label(&initialize), tools))
+}
+
+pub(super) fn string_pairs(value: Option<&Value>) -> Vec<(String, String)> {
+    value
+        .and_then(Value::as_object)
+        .map(|object| {
+            object
+                .iter()
+                .filter_map(|(key, value)| value.as_str().map(|v| (key.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn handshake_stdio(server: &McpServer) -> Result<(Option<String>, Vec<String>), String> {
+    let config: StdioConfig = serde_json::from_value(server.server_config.clone())
+        .map_err(|e| format!("Invalid stdio config: {}", e))?;
+    let program = resolve_local_command_program(&config.command);
+    let mut command = build_local_tokio_command(&program.path);
+    command
+        .args(&config.args)
+        .envs(string_pairs(config.env.as_ref()))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", config.command, e))?;
+
+    let stdin = child
+        .stdin
+        .take()
+        .ok_or("Failed to open MCP server stdin")?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or("Failed to open MCP server stdout")?;
+    let mut session = StdioSession {
+        stdin,
+        lines: BufReader::new(stdout).lines(),
+    };
+    let result = run_handshake(&mut session).await;
+    let _ = child.kill().await;
+    result
+}
+
+async fn handshake_http(
+    state: &SqliteDbState,
+    server: &McpServer,
+    timeout_secs: u64,
+) -> Result<(Option<String>, Vec<String>), String> {
+    let config: HttpConfig = serde_json::from_value(server.server_config.clone())
+        .map_err(|e| format!("Invalid HTTP config: {}", e))?;
+    let mut session = HttpSession {
+        client: crate::http_client::client_with_timeout(state, timeout_secs).await?,
+        url: config.url,
+        headers: string_pairs(config.headers.as_ref()),
+        session_id: None,
+    };
+    run_handshake(&mut session).await
+}
+
+/// Run the handshake against one server and cache the result
+pub async fn check_server_health(
+    state: &SqliteDbState,
+    server: &McpServer,
+    timeout_secs: u64,
+) -> McpHealthResultDto {
+    let handshake = async {
+        match McpServerType::from_str(&server.server_type) {
+            McpServerType::Stdio => handshake_stdio(server).await,
+            McpServerType::Http => handshake_http(state, server, timeout_secs).await,
+            McpServerType::Sse => Err("SSE servers are not probed".to_string()),
+        }
+    };
+    let outcome = match tokio::time::timeout(Duration::from_secs(timeout_secs), handshake).await {
+        Ok(outcome) => outcome,
+        Err(_) => Err(format!("Handshake timed out after {}s", timeout_secs)),
+    };
+
+    let (ok, server_info, tools, error) = match outcome {
+        Ok((server_info, tools)) => (true, server_info, tools, None),
+        Err(error) => (false, None, Vec::new(), Some(error)),
+    };
+    let result = McpHealthResultDto {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        ok,
+        server_info,
+        tools,
+        error,
+        checked_at: now_ms(),
+    };
+    if let Ok(mut cache) = health_cache().lock() {
+        cache.insert(
+            server.id.clone(),
+            (config_fingerprint(server), result.clone()),
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tools_page_reads_names_and_cursor() {
+        let (names, cursor) = parse_tools_page(&json!({
+            "tools": [{ "name": "read_file" }, { "name": "search" }, { "title": "x" }],
+            "nextCursor": "page-2",
+        }));
+        assert_eq!(names, vec!["read_file", "search"]);
+        assert_eq!(cursor.as_deref(), Some("page-2"));
+
+        let (_, cursor) = parse_tools_page(&json!({ "tools": [], "nextCursor": "" }));
+        assert!(cursor.is_none());
+    }
+
+    #[test]
+    fn sse_body_response_is_matched_by_id() {
+        let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n\
+                    event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{\"tools\":[]}}\n";
+        let response = find_sse_response(body, 2).expect("response");
+        assert_eq!(response["result"]["tools"], json!([]));
+        assert!(find_sse_response(body, 3).is_none());
+    }
+
+    #[test]
+    fn error_responses_become_errors() {
+        let error = response_result(json!({ "id": 1, "error": { "message": "boom" } }));
+        assert_eq!(error.unwrap_err(), "MCP error: boom");
+    }
+}
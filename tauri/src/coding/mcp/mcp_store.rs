@@ -309,6 +309,10 @@ mod tests {
             favorites_initialized: true,
             sync_disabled_to_opencode: true,
             limit_add_more_to_preferred_tools: true,
+            remote_only_tools: vec!["windsurf".to_string()],
+            gateway_port: Some(39217),
+            gateway_token: Some("t0k".to_string()),
+            traffic_log_servers: vec!["server-a".to_string()],
             updated_at: 9,
         };
         save_mcp_preferences(&sqlite_state, &prefs)
@@ -319,6 +323,10 @@ mod tests {
             .expect("read preferences");
         assert!(prefs.show_in_tray);
         assert!(prefs.limit_add_more_to_preferred_tools);
+        assert_eq!(prefs.remote_only_tools, vec!["windsurf"]);
+        assert_eq!(prefs.gateway_port, Some(39217));
+        assert_eq!(prefs.gateway_token.as_deref(), Some("t0k"));
+        assert_eq!(prefs.traffic_log_servers, vec!["server-a"]);
 
         let favorite = FavoriteMcp {
             id: String::new(),
@@ -4,16 +4,24 @@
 //! It allows users to configure and sync MCP servers across multiple AI coding tools.
 
 pub mod adapter;
-pub mod command_normalize;
+pub mod apply_history;
+pub mod collisions;
 pub mod commands;
 pub mod config_sync;
-pub mod format_configs;
+pub mod gateway;
+pub mod health;
 pub mod mcp_store;
 pub mod opencode_path;
 pub mod package_version;
+pub mod prerequisites;
+pub mod staged_apply;
+pub mod templates;
+pub mod traffic_log;
 pub mod tray_support;
 pub mod types;
 
+// Config file formats live in the Tauri-free core crate
+pub use ai_toolbox_core::mcp::{command_normalize, config_file, format_configs, lenient_json};
 pub use commands::*;
 
 pub(crate) fn mcp_tool_display_name(tool_key: &str, fallback: &str) -> String {
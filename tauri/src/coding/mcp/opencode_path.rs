@@ -38,11 +38,9 @@ pub fn get_opencode_mcp_config_path_sync() -> Option<PathBuf> {
 /// Get the default OpenCode config path
 /// Checks for .jsonc first, then .json, then defaults to .jsonc for new files
 fn get_default_opencode_config_path() -> Option<PathBuf> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .ok()?;
+    let home_dir = crate::coding::tools::env_home_dir()?;
 
-    let config_dir = PathBuf::from(&home_dir).join(".config").join("opencode");
+    let config_dir = home_dir.join(".config").join("opencode");
 
     // Check for .jsonc first, then .json
     let jsonc_path = config_dir.join("opencode.jsonc");
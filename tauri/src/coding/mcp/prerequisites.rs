@@ -0,0 +1,291 @@
+//! Runtime prerequisites of stdio MCP servers
+//!
+//! Most servers are launched through `npx`, `uvx` or `docker` and fail with a
+//! bare spawn error when that runtime is missing. The checker maps a stdio
+//! `command` to the runtimes it needs, runs each with `--version` (resolved
+//! like the health check, so nvm/volta/fnm installs are found), and reports
+//! the version found plus an install command for the current OS when a
+//! runtime is missing or too old. HTTP/SSE servers need nothing locally.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::command_normalize::unwrap_cmd_c;
+use crate::coding::cli_resolver::{build_local_tokio_command, resolve_local_command_program};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Requirement {
+    /// Program probed with `--version`
+    program: &'static str,
+    min_version: Option<&'static str>,
+}
+
+const NODE: Requirement = Requirement {
+    program: "node",
+    min_version: Some("18"),
+};
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpPrerequisiteDto {
+    pub runtime: String,
+    pub min_version: Option<String>,
+    pub found: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    pub satisfied: bool,
+    /// Install command for the current OS, set when not satisfied
+    pub install_hint: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct McpPrerequisiteReportDto {
+    pub satisfied: bool,
+    pub checks: Vec<McpPrerequisiteDto>,
+}
+
+/// Runtimes needed to launch `command`; empty for unknown launchers
+fn requirements_for(command: &str) -> Vec<Requirement> {
+    let name = std::path::Path::new(command)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(command)
+        .to_ascii_lowercase();
+    let needs = |program, min_version| Requirement {
+        program,
+        min_version,
+    };
+    match name.as_str() {
+        "npx" => vec![NODE, needs("npx", None)],
+        "node" => vec![NODE],
+        "uvx" => vec![needs("uvx", None)],
+        "uv" => vec![needs("uv", None)],
+        "docker" => vec![needs("docker", None)],
+        "bun" | "bunx" => vec![needs("bun", None)],
+        "deno" => vec![needs("deno", None)],
+        "python" => vec![needs("python", Some("3.10"))],
+        "python3" => vec![needs("python3", Some("3.10"))],
+        _ => Vec::new(),
+    }
+}
+
+/// First version-looking token of `--version` output:
+/// "v20.11.1", "Docker version 27.0.3, build 7d4bcd8", "uv 0.4.18 (7b55e97)"
+pub(crate) fn parse_version(output: &str) -> Option<String> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|token| token.trim_start_matches('v'))
+        .find_map(|token| {
+            let version: String = token
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            let version = version.trim_end_matches('.');
+            (version.contains('.') && version.starts_with(|c: char| c.is_ascii_digit()))
+                .then(|| version.to_string())
+        })
+}
+
+pub(crate) fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+fn version_at_least(found: &str, min: &str) -> bool {
+    let found = version_parts(found);
+    let min = version_parts(min);
+    for i in 0..found.len().max(min.len()) {
+        let (a, b) = (
+            found.get(i).copied().unwrap_or(0),
+            min.get(i).copied().unwrap_or(0),
+        );
+        if a != b {
+            return a > b;
+        }
+    }
+    true
+}
+
+fn install_hint(program: &str, os: &str) -> Option<&'static str> {
+    let hint = match (program, os) {
+        ("node" | "npx", "macos") => "brew install node",
+        ("node" | "npx", "windows") => "winget install OpenJS.NodeJS.LTS",
+        ("node" | "npx", _) => {
+            "Install Node.js 18+ from https://nodejs.org or with nvm: nvm install --lts"
+        }
+        ("uv" | "uvx", "macos") => "brew install uv",
+        ("uv" | "uvx", "windows") => {
+            "powershell -ExecutionPolicy ByPass -c \"irm https://astral.sh/uv/install.ps1 | iex\""
+        }
+        ("uv" | "uvx", _) => "curl -LsSf https://astral.sh/uv/install.sh | sh",
+        ("docker", "macos") => "brew install --cask docker",
+        ("docker", "windows") => "winget install Docker.DockerDesktop",
+        ("docker", _) => "Install Docker Engine: https://docs.docker.com/engine/install/",
+        ("bun", "windows") => "powershell -c \"irm bun.sh/install.ps1 | iex\"",
+        ("bun", _) => "curl -fsSL https://bun.sh/install | bash",
+        ("deno", "windows") => "winget install DenoLand.Deno",
+        ("deno", _) => "curl -fsSL https://deno.land/install.sh | sh",
+        ("python" | "python3", "macos") => "brew install python@3.12",
+        ("python" | "python3", "windows") => "winget install Python.Python.3.12",
+        ("python" | "python3", _) => {
+            "Install Python 3.10+ with your package manager, e.g. sudo apt install python3"
+        }
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// Run `program --version`; returns the resolved path and its output
+async fn probe(program: &str) -> Option<(String, String)> {
+    let resolved = resolve_local_command_program(program);
+    let mut command = build_local_tokio_command(&resolved.path);
+    command
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(PROBE_TIMEOUT, command.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // Older Pythons print the version to stderr
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push('\n');
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some((resolved.path.to_string_lossy().into_owned(), text))
+}
+
+async fn check_requirement(requirement: Requirement) -> McpPrerequisiteDto {
+    let probed = probe(requirement.program).await;
+    let found = probed.is_some();
+    let (path, version) = match probed {
+        Some((path, output)) => (Some(path), parse_version(&output)),
+        None => (None, None),
+    };
+    // A version that cannot be parsed does not fail the check
+    let satisfied = found
+        && match (requirement.min_version, &version) {
+            (Some(min), Some(version)) => version_at_least(version, min),
+            _ => true,
+        };
+    McpPrerequisiteDto {
+        runtime: requirement.program.to_string(),
+        min_version: requirement.min_version.map(str::to_string),
+        found,
+        path,
+        version,
+        satisfied,
+        install_hint: if satisfied {
+            None
+        } else {
+            install_hint(requirement.program, std::env::consts::OS).map(str::to_string)
+        },
+    }
+}
+
+/// Check the runtimes a server config needs on this machine
+pub async fn check_prerequisites(
+    server_type: &str,
+    server_config: &Value,
+) -> McpPrerequisiteReportDto {
+    if server_type != "stdio" {
+        return McpPrerequisiteReportDto {
+            satisfied: true,
+            checks: Vec::new(),
+        };
+    }
+    let config = unwrap_cmd_c(server_config);
+    let command = config
+        .get("command")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim();
+    if command.is_empty() {
+        return McpPrerequisiteReportDto {
+            satisfied: true,
+            checks: Vec::new(),
+        };
+    }
+
+    let requirements = requirements_for(command);
+    if requirements.is_empty() {
+        // Unknown launchers have no `--version` contract: only check they
+        // resolve to an existing file (an unresolved name stays relative)
+        let program = resolve_local_command_program(command);
+        let found = program.path.is_absolute() && program.path.exists();
+        return McpPrerequisiteReportDto {
+            satisfied: found,
+            checks: vec![McpPrerequisiteDto {
+                runtime: command.to_string(),
+                min_version: None,
+                found,
+                path: found.then(|| program.path.to_string_lossy().into_owned()),
+                version: None,
+                satisfied: found,
+                install_hint: None,
+            }],
+        };
+    }
+
+    let checks =
+        futures_util::future::join_all(requirements.into_iter().map(check_requirement)).await;
+    McpPrerequisiteReportDto {
+        satisfied: checks.iter().all(|check| check.satisfied),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_version_outputs() {
+        assert_eq!(parse_version("v20.11.1\n").as_deref(), Some("20.11.1"));
+        assert_eq!(
+            parse_version("Docker version 27.0.3, build 7d4bcd8").as_deref(),
+            Some("27.0.3")
+        );
+        assert_eq!(
+            parse_version("uv 0.4.18 (7b55e9790 2024-10-01)").as_deref(),
+            Some("0.4.18")
+        );
+        assert_eq!(parse_version("\nPython 3.9.6").as_deref(), Some("3.9.6"));
+        assert_eq!(parse_version("no version here"), None);
+    }
+
+    #[test]
+    fn compares_against_minimum_versions() {
+        assert!(version_at_least("20.11.1", "18"));
+        assert!(version_at_least("18.0.0", "18"));
+        assert!(!version_at_least("16.20.2", "18"));
+        assert!(!version_at_least("3.9.6", "3.10"));
+        assert!(version_at_least("3.12", "3.10"));
+    }
+
+    #[test]
+    fn maps_launchers_to_runtimes() {
+        let programs = |command| {
+            requirements_for(command)
+                .into_iter()
+                .map(|requirement| requirement.program)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(programs("npx"), vec!["node", "npx"]);
+        assert_eq!(programs("/usr/local/bin/uvx"), vec!["uvx"]);
+        assert_eq!(programs("Docker.exe"), vec!["docker"]);
+        assert!(programs("my-server").is_empty());
+        assert!(install_hint("docker", "linux").is_some());
+        assert!(install_hint("my-server", "macos").is_none());
+    }
+}
@@ -0,0 +1,333 @@
+//! Two-phase apply of MCP changes across many tools
+//!
+//! Stage: for every tool, read its current config, render the new content in
+//! memory (`config_sync::render_tool_config`) and validate it. If any tool
+//! fails to stage, nothing is written.
+//!
+//! Commit: write the staged files one by one. If a write fails, or a file
+//! changed on disk since it was staged, every file already written is restored
+//! from its pre-write snapshot (or removed when it did not exist before), so
+//! tools never end up with mismatched server sets.
+
+use std::path::{Path, PathBuf};
+
+use super::apply_history;
+use super::config_sync::{render_tool_config, validate_rendered_config, McpConfigOp};
+use crate::coding::tools::RuntimeTool;
+
+/// Changes for one tool's config file
+pub struct ToolApply<'a> {
+    pub tool: &'a RuntimeTool,
+    pub config_path: PathBuf,
+    pub ops: Vec<McpConfigOp<'a>>,
+}
+
+/// Rendered and validated config content, ready to be written
+#[derive(Debug)]
+pub struct StagedToolConfig {
+    pub tool_key: String,
+    pub config_path: PathBuf,
+    /// Tool's MCP config format and servers field, for the apply history
+    format: String,
+    field: String,
+    /// Pre-write snapshot; `None` when the file did not exist
+    original: Option<String>,
+    content: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum McpApplyError {
+    /// A tool's config could not be rendered or validated; nothing was written
+    Stage { tool_key: String, error: String },
+    /// A write failed; files written before it were restored
+    Commit {
+        tool_key: String,
+        error: String,
+        rollback_errors: Vec<String>,
+    },
+}
+
+impl McpApplyError {
+    pub fn tool_key(&self) -> &str {
+        match self {
+            McpApplyError::Stage { tool_key, .. } | McpApplyError::Commit { tool_key, .. } => {
+                tool_key
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for McpApplyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpApplyError::Stage { tool_key, error } => {
+                write!(
+                    f,
+                    "Staging {} failed, no tool was changed: {}",
+                    tool_key, error
+                )
+            }
+            McpApplyError::Commit {
+                tool_key,
+                error,
+                rollback_errors,
+            } if rollback_errors.is_empty() => write!(
+                f,
+                "Writing {} failed, all tools were restored: {}",
+                tool_key, error
+            ),
+            McpApplyError::Commit {
+                tool_key,
+                error,
+                rollback_errors,
+            } => write!(
+                f,
+                "Writing {} failed: {}; restore failed: {}",
+                tool_key,
+                error,
+                rollback_errors.join("; ")
+            ),
+        }
+    }
+}
+
+fn read_snapshot(path: &Path) -> Result<Option<String>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(path)
+        .map(Some)
+        .map_err(|e| format!("Failed to read config file: {}", e))
+}
+
+/// Phase 1: render and validate every tool's config without writing anything.
+/// Tools whose ops leave the file untouched are skipped.
+pub fn stage_tool_configs(
+    entries: &[ToolApply<'_>],
+) -> Result<Vec<StagedToolConfig>, McpApplyError> {
+    let mut staged = Vec::new();
+    for entry in entries {
+        let stage_error = |error: String| McpApplyError::Stage {
+            tool_key: entry.tool.key.clone(),
+            error,
+        };
+        let original = read_snapshot(&entry.config_path).map_err(stage_error)?;
+        let Some(content) = render_tool_config(
+            entry.tool,
+            &entry.config_path,
+            original.as_deref(),
+            &entry.ops,
+        )
+        .map_err(stage_error)?
+        else {
+            continue;
+        };
+        validate_rendered_config(entry.tool, &content).map_err(stage_error)?;
+        staged.push(StagedToolConfig {
+            tool_key: entry.tool.key.clone(),
+            config_path: entry.config_path.clone(),
+            format: entry
+                .tool
+                .mcp_config_format
+                .clone()
+                .unwrap_or_else(|| "json".to_string()),
+            field: entry
+                .tool
+                .mcp_field
+                .clone()
+                .unwrap_or_else(|| "mcpServers".to_string()),
+            original,
+            content,
+        });
+    }
+    Ok(staged)
+}
+
+/// Write next to the target and rename into place
+fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let tmp = path.with_extension("ai-toolbox-staged");
+    std::fs::write(&tmp, content).map_err(|e| format!("Failed to write config file: {}", e))?;
+    std::fs::rename(&tmp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        format!("Failed to replace config file: {}", e)
+    })
+}
+
+fn restore(staged: &StagedToolConfig) -> Result<(), String> {
+    let result = match &staged.original {
+        Some(original) => write_atomic(&staged.config_path, original),
+        None => std::fs::remove_file(&staged.config_path).map_err(|e| e.to_string()),
+    };
+    result.map_err(|e| format!("{}: {}", staged.config_path.display(), e))
+}
+
+/// Phase 2: write every staged config, restoring all written files on failure
+pub fn commit_staged_configs(staged: &[StagedToolConfig]) -> Result<(), McpApplyError> {
+    for (index, entry) in staged.iter().enumerate() {
+        // Refuse to overwrite edits made between staging and commit
+        let result = match read_snapshot(&entry.config_path) {
+            Ok(current) if current == entry.original => {
+                write_atomic(&entry.config_path, &entry.content)
+            }
+            Ok(_) => Err("Config file changed on disk after staging".to_string()),
+            Err(e) => Err(e),
+        };
+        if let Err(error) = result {
+            let rollback_errors = staged[..index]
+                .iter()
+                .rev()
+                .filter_map(|written| restore(written).err())
+                .collect();
+            return Err(McpApplyError::Commit {
+                tool_key: entry.tool_key.clone(),
+                error,
+                rollback_errors,
+            });
+        }
+    }
+    // Only a fully committed apply is history; a rolled-back one changed nothing
+    for entry in staged {
+        apply_history::record_apply(
+            &entry.tool_key,
+            &entry.format,
+            &entry.field,
+            &entry.config_path,
+            entry.original.as_deref(),
+            &entry.content,
+        );
+    }
+    Ok(())
+}
+
+/// Stage all tools, then commit all writes
+pub fn apply_two_phase(entries: &[ToolApply<'_>]) -> Result<(), McpApplyError> {
+    let staged = stage_tool_configs(entries)?;
+    commit_staged_configs(&staged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::mcp::types::McpServer;
+    use serde_json::json;
+
+    fn tool(key: &str, format: &str) -> RuntimeTool {
+        RuntimeTool {
+            key: key.to_string(),
+            display_name: key.to_string(),
+            is_custom: true,
+            relative_skills_dir: None,
+            relative_detect_dir: None,
+            force_copy: false,
+            preferred_sync_mode: None,
+            mcp_config_path: None,
+            mcp_config_format: Some(format.to_string()),
+            mcp_field: Some("mcpServers".to_string()),
+        }
+    }
+
+    fn server() -> McpServer {
+        McpServer {
+            id: String::new(),
+            name: "remote".to_string(),
+            server_type: "http".to_string(),
+            server_config: json!({ "url": "https://example.com/mcp" }),
+            enabled_tools: vec![],
+            sync_details: None,
+            description: None,
+            user_group: None,
+            user_note: None,
+            tags: vec![],
+            timeout: None,
+            sort_index: 0,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn staging_failure_writes_nothing() {
+        let temp = tempfile::tempdir().unwrap();
+        let good = temp.path().join("good.json");
+        let broken = temp.path().join("broken.json");
+        std::fs::write(&broken, "{ not json").unwrap();
+        let (json_a, json_b) = (tool("a", "json"), tool("b", "json"));
+        let server = server();
+        let op = McpConfigOp::Upsert {
+            server: &server,
+            enabled: true,
+        };
+
+        let err = apply_two_phase(&[
+            ToolApply {
+                tool: &json_a,
+                config_path: good.clone(),
+                ops: vec![op],
+            },
+            ToolApply {
+                tool: &json_b,
+                config_path: broken.clone(),
+                ops: vec![op],
+            },
+        ])
+        .unwrap_err();
+        assert_eq!(err.tool_key(), "b");
+        assert!(matches!(err, McpApplyError::Stage { .. }));
+        assert!(!good.exists());
+        assert_eq!(std::fs::read_to_string(&broken).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn commit_failure_restores_written_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let first = temp.path().join("first.json");
+        let created = temp.path().join("created.toml");
+        let second = temp.path().join("second.json");
+        std::fs::write(&first, "{\"mcpServers\": {}}").unwrap();
+        let (json_a, toml_b, json_c) = (tool("a", "json"), tool("b", "toml"), tool("c", "json"));
+        let server = server();
+        let op = McpConfigOp::Upsert {
+            server: &server,
+            enabled: true,
+        };
+
+        let staged = stage_tool_configs(&[
+            ToolApply {
+                tool: &json_a,
+                config_path: first.clone(),
+                ops: vec![op],
+            },
+            ToolApply {
+                tool: &toml_b,
+                config_path: created.clone(),
+                ops: vec![op],
+            },
+            ToolApply {
+                tool: &json_c,
+                config_path: second.clone(),
+                ops: vec![op],
+            },
+        ])
+        .unwrap();
+        assert_eq!(staged.len(), 3);
+
+        // Simulate a concurrent edit to the last file after staging
+        std::fs::write(&second, "{}").unwrap();
+        let err = commit_staged_configs(&staged).unwrap_err();
+        assert_eq!(err.tool_key(), "c");
+        assert_eq!(
+            std::fs::read_to_string(&first).unwrap(),
+            "{\"mcpServers\": {}}"
+        );
+        assert!(!created.exists());
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "{}");
+
+        std::fs::remove_file(&second).unwrap();
+        commit_staged_configs(&staged).unwrap();
+        assert!(std::fs::read_to_string(&second).unwrap().contains("remote"));
+    }
+}
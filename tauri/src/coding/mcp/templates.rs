@@ -0,0 +1,410 @@
+//! Built-in MCP server templates
+//!
+//! Each template holds one config per transport, written as a JSON server
+//! config with `{{param}}` placeholders, plus the parameters the user is asked
+//! for. `render_template` fills the placeholders:
+//!
+//! - a string that is only `{{param}}` takes the value; a `paths` parameter
+//!   inside an array expands into one item per path
+//! - placeholders inside a longer string are substituted in place
+//! - an entry referring to an optional parameter left empty is dropped, so
+//!   `"env": { "MEMORY_FILE_PATH": "{{memory_file_path}}" }` disappears
+//!   instead of passing an empty variable
+//!
+//! `path` and `paths` values get `~` and environment variables expanded,
+//! since the servers receive them verbatim.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::coding::tools::path_utils;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpTemplateParamKind {
+    Text,
+    /// Token or key; the UI masks it
+    Secret,
+    Path,
+    /// One path per line
+    Paths,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpTemplateParam {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: McpTemplateParamKind,
+    pub required: bool,
+    pub placeholder: &'static str,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpTemplateTransport {
+    /// `stdio`, `http` or `sse`, as in `McpServer.server_type`
+    pub server_type: &'static str,
+    /// Server config JSON with `{{param}}` placeholders
+    pub config: &'static str,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpTemplate {
+    pub id: &'static str,
+    /// Default server name
+    pub name: &'static str,
+    pub description: &'static str,
+    pub params: &'static [McpTemplateParam],
+    /// The first transport is the default
+    pub transports: &'static [McpTemplateTransport],
+}
+
+/// Input for creating a server from a template
+#[derive(Clone, Debug, Deserialize)]
+pub struct McpTemplateInput {
+    pub template_id: String,
+    /// Defaults to the template's first transport
+    pub server_type: Option<String>,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    /// Defaults to the template name
+    pub name: Option<String>,
+    #[serde(default)]
+    pub enabled_tools: Vec<String>,
+}
+
+const fn param(
+    key: &'static str,
+    label: &'static str,
+    kind: McpTemplateParamKind,
+    required: bool,
+    placeholder: &'static str,
+) -> McpTemplateParam {
+    McpTemplateParam {
+        key,
+        label,
+        kind,
+        required,
+        placeholder,
+    }
+}
+
+const fn stdio(config: &'static str) -> McpTemplateTransport {
+    McpTemplateTransport {
+        server_type: "stdio",
+        config,
+    }
+}
+
+pub const MCP_TEMPLATES: &[McpTemplate] = &[
+    McpTemplate {
+        id: "filesystem",
+        name: "filesystem",
+        description: "Read and write files inside the allowed directories",
+        params: &[param(
+            "paths",
+            "Allowed directories",
+            McpTemplateParamKind::Paths,
+            true,
+            "~/projects",
+        )],
+        transports: &[stdio(
+            r#"{"command":"npx","args":["-y","@modelcontextprotocol/server-filesystem","{{paths}}"]}"#,
+        )],
+    },
+    McpTemplate {
+        id: "fetch",
+        name: "fetch",
+        description: "Fetch web pages and convert them to markdown",
+        params: &[param(
+            "user_agent",
+            "User agent",
+            McpTemplateParamKind::Text,
+            false,
+            "",
+        )],
+        transports: &[stdio(
+            r#"{"command":"uvx","args":["mcp-server-fetch","--user-agent={{user_agent}}"]}"#,
+        )],
+    },
+    McpTemplate {
+        id: "playwright",
+        name: "playwright",
+        description: "Drive a browser with Playwright",
+        params: &[param(
+            "output_dir",
+            "Output directory",
+            McpTemplateParamKind::Path,
+            false,
+            "",
+        )],
+        transports: &[stdio(
+            r#"{"command":"npx","args":["@playwright/mcp@latest","--output-dir={{output_dir}}"]}"#,
+        )],
+    },
+    McpTemplate {
+        id: "github",
+        name: "github",
+        description: "GitHub repositories, issues and pull requests",
+        params: &[param(
+            "token",
+            "Personal access token",
+            McpTemplateParamKind::Secret,
+            true,
+            "ghp_...",
+        )],
+        transports: &[
+            McpTemplateTransport {
+                server_type: "http",
+                config: r#"{"url":"https://api.githubcopilot.com/mcp/","headers":{"Authorization":"Bearer {{token}}"}}"#,
+            },
+            stdio(
+                r#"{"command":"docker","args":["run","-i","--rm","-e","GITHUB_PERSONAL_ACCESS_TOKEN","ghcr.io/github/github-mcp-server"],"env":{"GITHUB_PERSONAL_ACCESS_TOKEN":"{{token}}"}}"#,
+            ),
+        ],
+    },
+    McpTemplate {
+        id: "memory",
+        name: "memory",
+        description: "Knowledge graph memory kept across sessions",
+        params: &[param(
+            "memory_file_path",
+            "Memory file",
+            McpTemplateParamKind::Path,
+            false,
+            "~/.mcp-memory.json",
+        )],
+        transports: &[stdio(
+            r#"{"command":"npx","args":["-y","@modelcontextprotocol/server-memory"],"env":{"MEMORY_FILE_PATH":"{{memory_file_path}}"}}"#,
+        )],
+    },
+    McpTemplate {
+        id: "sequential-thinking",
+        name: "sequential-thinking",
+        description: "Structured step-by-step problem solving",
+        params: &[],
+        transports: &[stdio(
+            r#"{"command":"npx","args":["-y","@modelcontextprotocol/server-sequential-thinking"]}"#,
+        )],
+    },
+];
+
+pub fn template_by_id(id: &str) -> Option<&'static McpTemplate> {
+    MCP_TEMPLATES.iter().find(|template| template.id == id)
+}
+
+enum ParamValue {
+    One(String),
+    Many(Vec<String>),
+}
+
+enum Rendered {
+    Value(Value),
+    Spread(Vec<String>),
+    Drop,
+}
+
+/// Server type and config for `template` over `server_type` (default: the
+/// first transport) with the user's `params`
+pub fn render_template(
+    template: &McpTemplate,
+    server_type: Option<&str>,
+    params: &BTreeMap<String, String>,
+) -> Result<(String, Value), String> {
+    let transport = match server_type {
+        Some(server_type) => template
+            .transports
+            .iter()
+            .find(|transport| transport.server_type == server_type)
+            .ok_or_else(|| {
+                format!(
+                    "Template '{}' has no {} transport",
+                    template.id, server_type
+                )
+            })?,
+        None => &template.transports[0],
+    };
+
+    let mut values = BTreeMap::new();
+    for param in template.params {
+        let raw = params
+            .get(param.key)
+            .map(|value| value.trim())
+            .unwrap_or("");
+        let value = match param.kind {
+            McpTemplateParamKind::Paths => {
+                let paths = raw
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(expand_path)
+                    .collect::<Vec<_>>();
+                (!paths.is_empty()).then_some(ParamValue::Many(paths))
+            }
+            McpTemplateParamKind::Path if !raw.is_empty() => {
+                Some(ParamValue::One(expand_path(raw)))
+            }
+            _ if !raw.is_empty() => Some(ParamValue::One(raw.to_string())),
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                values.insert(param.key, value);
+            }
+            None if param.required => {
+                return Err(format!("Missing template parameter: {}", param.label));
+            }
+            None => {}
+        }
+    }
+
+    let config: Value = serde_json::from_str(transport.config)
+        .map_err(|e| format!("Invalid template '{}': {}", template.id, e))?;
+    match render_value(&config, &values) {
+        Rendered::Value(config) => Ok((transport.server_type.to_string(), config)),
+        _ => Err(format!("Invalid template '{}'", template.id)),
+    }
+}
+
+fn expand_path(path: &str) -> String {
+    path_utils::resolve_storage_path(path)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn render_value(value: &Value, values: &BTreeMap<&str, ParamValue>) -> Rendered {
+    match value {
+        Value::String(text) => render_string(text, values),
+        Value::Array(items) => {
+            let mut rendered = Vec::with_capacity(items.len());
+            for item in items {
+                match render_value(item, values) {
+                    Rendered::Value(value) => rendered.push(value),
+                    Rendered::Spread(items) => {
+                        rendered.extend(items.into_iter().map(Value::String))
+                    }
+                    Rendered::Drop => {}
+                }
+            }
+            Rendered::Value(Value::Array(rendered))
+        }
+        Value::Object(entries) => {
+            let mut rendered = Map::new();
+            for (key, item) in entries {
+                match render_value(item, values) {
+                    Rendered::Value(value) => {
+                        rendered.insert(key.clone(), value);
+                    }
+                    Rendered::Spread(items) => {
+                        rendered.insert(key.clone(), Value::String(items.join(" ")));
+                    }
+                    Rendered::Drop => {}
+                }
+            }
+            // `env` / `headers` left empty are dropped with their last entry
+            if rendered.is_empty() && !entries.is_empty() {
+                return Rendered::Drop;
+            }
+            Rendered::Value(Value::Object(rendered))
+        }
+        other => Rendered::Value(other.clone()),
+    }
+}
+
+fn render_string(text: &str, values: &BTreeMap<&str, ParamValue>) -> Rendered {
+    if let Some(key) = text
+        .strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+    {
+        return match values.get(key) {
+            Some(ParamValue::One(value)) => Rendered::Value(Value::String(value.clone())),
+            Some(ParamValue::Many(items)) => Rendered::Spread(items.clone()),
+            None => Rendered::Drop,
+        };
+    }
+
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let key = &rest[start + 2..start + end];
+        let value = match values.get(key) {
+            Some(ParamValue::One(value)) => value.clone(),
+            Some(ParamValue::Many(items)) => items.join(" "),
+            None => return Rendered::Drop,
+        };
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&value);
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+    Rendered::Value(Value::String(rendered))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn params(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn every_template_parses_and_has_a_transport() {
+        for template in MCP_TEMPLATES {
+            assert!(!template.transports.is_empty(), "{}", template.id);
+            for transport in template.transports {
+                serde_json::from_str::<Value>(transport.config)
+                    .unwrap_or_else(|e| panic!("{}: {}", template.id, e));
+            }
+        }
+    }
+
+    #[test]
+    fn renders_params_per_transport_and_drops_empty_optionals() {
+        let filesystem = template_by_id("filesystem").unwrap();
+        let (server_type, config) = render_template(
+            filesystem,
+            None,
+            &params(&[("paths", "/srv/a\n\n  /srv/b  ")]),
+        )
+        .unwrap();
+        assert_eq!(server_type, "stdio");
+        assert_eq!(
+            config["args"],
+            json!([
+                "-y",
+                "@modelcontextprotocol/server-filesystem",
+                "/srv/a",
+                "/srv/b"
+            ])
+        );
+        assert!(render_template(filesystem, None, &params(&[])).is_err());
+
+        let github = template_by_id("github").unwrap();
+        let token = params(&[("token", "ghp_x")]);
+        let (server_type, config) = render_template(github, None, &token).unwrap();
+        assert_eq!(server_type, "http");
+        assert_eq!(config["headers"]["Authorization"], "Bearer ghp_x");
+        let (_, config) = render_template(github, Some("stdio"), &token).unwrap();
+        assert_eq!(config["env"]["GITHUB_PERSONAL_ACCESS_TOKEN"], "ghp_x");
+        assert!(render_template(github, Some("sse"), &token).is_err());
+
+        let (_, config) =
+            render_template(template_by_id("memory").unwrap(), None, &params(&[])).unwrap();
+        assert_eq!(
+            config,
+            json!({ "command": "npx", "args": ["-y", "@modelcontextprotocol/server-memory"] })
+        );
+        let (_, config) =
+            render_template(template_by_id("fetch").unwrap(), None, &params(&[])).unwrap();
+        assert_eq!(config["args"], json!(["mcp-server-fetch"]));
+    }
+}
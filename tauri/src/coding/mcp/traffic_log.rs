@@ -0,0 +1,469 @@
+//! JSON-RPC traffic logging for stdio MCP servers
+//!
+//! A debugging aid for "why does my client not see this tool". A server with
+//! logging on (`McpPreferences.traffic_log_servers`) is synced to its tools
+//! as `<shim> --log <file> -- <command> [args...]` (`cmd /c <shim> ...` on
+//! Windows). The shim is a script at `<app data>/mcp-traffic/` that runs
+//! `<ai-toolbox exe> mcp-proxy`; the app rewrites it at every startup
+//! (`install_proxy_shim`), so tool configs keep working after the app is
+//! moved or updated, and an AppImage is run through its file rather than its
+//! temporary mount. The proxy (`run_proxy`, dispatched by `cli::run_from_env`
+//! before the GUI starts) spawns the real server, relays stdin / stdout /
+//! stderr unchanged and appends every line to
+//! `<app data>/mcp-traffic/<server id>.jsonl`, which `mcp_get_traffic` reads
+//! back. The gateway spawns the same wrapper, so remote-only tools are
+//! covered too. Only stdio servers can be logged.
+
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::types::{now_ms, McpServer};
+use crate::coding::cli_resolver::{build_local_std_command, resolve_local_command_program};
+
+pub const PROXY_SUBCOMMAND: &str = "mcp-proxy";
+pub const DEFAULT_TRAFFIC_LIMIT: usize = 500;
+const TRAFFIC_DIR: &str = "mcp-traffic";
+/// A log is moved to `<file>.1` once a write takes it past this size
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+#[cfg(windows)]
+const PROXY_SHIM_NAME: &str = "ai-toolbox-mcp-proxy.cmd";
+#[cfg(not(windows))]
+const PROXY_SHIM_NAME: &str = "ai-toolbox-mcp-proxy";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct McpTrafficEntry {
+    pub ts: i64,
+    /// Proxy process id; tells apart tools running the same server
+    pub pid: u32,
+    /// `client` (tool -> server), `server` (server -> tool), `stderr` or `exit`
+    pub direction: String,
+    /// The JSON-RPC message, or the raw line when it is not JSON
+    pub message: Value,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpTrafficDto {
+    pub server_id: String,
+    pub enabled: bool,
+    pub log_path: String,
+    /// Oldest first
+    pub entries: Vec<McpTrafficEntry>,
+}
+
+fn logged_servers() -> &'static Mutex<BTreeSet<String>> {
+    static LOGGED: OnceLock<Mutex<BTreeSet<String>>> = OnceLock::new();
+    LOGGED.get_or_init(|| Mutex::new(BTreeSet::new()))
+}
+
+/// Replace the in-memory set read by `logged_server`; loaded from
+/// preferences at startup and updated by `mcp_set_traffic_logging`
+pub fn set_logged_servers(server_ids: &[String]) {
+    if let Ok(mut logged) = logged_servers().lock() {
+        *logged = server_ids.iter().cloned().collect();
+    }
+}
+
+pub fn is_logged(server_id: &str) -> bool {
+    logged_servers()
+        .lock()
+        .map(|logged| logged.contains(server_id))
+        .unwrap_or(false)
+}
+
+pub fn traffic_log_path(server_id: &str) -> Result<PathBuf, String> {
+    // Ids are generated, but one must never name a path outside the dir
+    let valid = !server_id.is_empty()
+        && server_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid MCP server id: {}", server_id));
+    }
+    Ok(crate::cli::app_data_dir()?
+        .join(TRAFFIC_DIR)
+        .join(format!("{}.jsonl", server_id)))
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+/// Move `path` to `<path>.1` when it is larger than `max_bytes`
+fn rotate_if_full(path: &Path, max_bytes: u64) {
+    if fs::metadata(path).is_ok_and(|meta| meta.len() > max_bytes) {
+        let _ = fs::rename(path, rotated_path(path));
+    }
+}
+
+pub fn proxy_shim_path() -> Result<PathBuf, String> {
+    Ok(crate::cli::app_data_dir()?
+        .join(TRAFFIC_DIR)
+        .join(PROXY_SHIM_NAME))
+}
+
+/// The executable the shim runs. Inside an AppImage `current_exe` is in a
+/// mount that disappears when the app exits, so the AppImage file is used.
+fn launch_executable() -> Result<PathBuf, String> {
+    #[cfg(target_os = "linux")]
+    if let Some(appimage) = std::env::var_os("APPIMAGE")
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+    {
+        return Ok(appimage);
+    }
+    std::env::current_exe().map_err(|e| format!("Failed to resolve current executable: {}", e))
+}
+
+#[cfg(windows)]
+fn shim_script(exe: &Path) -> String {
+    format!(
+        "@echo off\r\n\"{}\" {} %*\r\n",
+        exe.display(),
+        PROXY_SUBCOMMAND
+    )
+}
+
+#[cfg(not(windows))]
+fn shim_script(exe: &Path) -> String {
+    let quoted = exe.to_string_lossy().replace('\'', "'\\''");
+    format!("#!/bin/sh\nexec '{}' {} \"$@\"\n", quoted, PROXY_SUBCOMMAND)
+}
+
+/// Point the proxy shim at the running executable; called at startup so the
+/// configs written by a moved or older install reach this one
+pub fn install_proxy_shim() -> Result<PathBuf, String> {
+    let shim = proxy_shim_path()?;
+    let script = shim_script(&launch_executable()?);
+    if fs::read_to_string(&shim).is_ok_and(|existing| existing == script) {
+        return Ok(shim);
+    }
+    if let Some(parent) = shim.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create MCP traffic dir: {}", e))?;
+    }
+    fs::write(&shim, script).map_err(|e| format!("Failed to write MCP proxy shim: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&shim, fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to make MCP proxy shim executable: {}", e))?;
+    }
+    Ok(shim)
+}
+
+/// `server` with its command wrapped by the logging proxy, or `None` when
+/// logging is off for it or the shim is missing
+pub fn logged_server(server: &McpServer) -> Option<McpServer> {
+    if server.server_type != "stdio" || !is_logged(&server.id) {
+        return None;
+    }
+    let log_path = traffic_log_path(&server.id).ok()?;
+    let shim = proxy_shim_path().ok().filter(|shim| shim.is_file())?;
+    wrap_with_proxy(server, &shim, &log_path)
+}
+
+fn wrap_with_proxy(server: &McpServer, shim: &Path, log_path: &Path) -> Option<McpServer> {
+    let command = server.server_config.get("command")?.as_str()?.to_string();
+    let shim = Value::from(shim.to_string_lossy().into_owned());
+    // Batch files cannot be spawned directly by most Windows clients
+    let (program, mut args) = if cfg!(windows) {
+        (Value::from("cmd"), vec![Value::from("/c"), shim])
+    } else {
+        (shim, Vec::new())
+    };
+    args.extend([
+        Value::from("--log"),
+        Value::from(log_path.to_string_lossy().into_owned()),
+        Value::from("--"),
+        Value::from(command),
+    ]);
+    if let Some(Value::Array(original)) = server.server_config.get("args") {
+        args.extend(original.iter().cloned());
+    }
+
+    let mut logged = server.clone();
+    let config = logged.server_config.as_object_mut()?;
+    config.insert("command".to_string(), program);
+    config.insert("args".to_string(), Value::Array(args));
+    Some(logged)
+}
+
+/// Oldest-first, at most `limit` of the newest entries
+pub fn read_traffic(server_id: &str, limit: usize) -> Result<Vec<McpTrafficEntry>, String> {
+    let path = traffic_log_path(server_id)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read MCP traffic log: {}", e))?;
+    let mut entries: Vec<McpTrafficEntry> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    entries.drain(..skip);
+    Ok(entries)
+}
+
+pub fn clear_traffic(server_id: &str) -> Result<(), String> {
+    let path = traffic_log_path(server_id)?;
+    for file in [rotated_path(&path), path] {
+        if file.exists() {
+            fs::remove_file(&file)
+                .map_err(|e| format!("Failed to remove MCP traffic log: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+// ==================== Proxy ====================
+
+/// Appends to the log by path, so a proxy keeps writing to the current file
+/// after another proxy of the same server rotated it
+#[derive(Clone)]
+struct TrafficLogger {
+    path: Option<Arc<Mutex<PathBuf>>>,
+}
+
+impl TrafficLogger {
+    fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create MCP traffic dir: {}", e))?;
+        }
+        rotate_if_full(path, MAX_LOG_BYTES);
+        open_append(path)?;
+        Ok(Self {
+            path: Some(Arc::new(Mutex::new(path.to_path_buf()))),
+        })
+    }
+
+    fn log(&self, direction: &str, message: Value) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let entry = McpTrafficEntry {
+            ts: now_ms(),
+            pid: std::process::id(),
+            direction: direction.to_string(),
+            message,
+        };
+        let Ok(mut line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        line.push('\n');
+        // One write per entry keeps lines whole when several proxies append
+        if let Ok(path) = path.lock() {
+            if let Ok(mut file) = open_append(&path) {
+                let _ = file.write_all(line.as_bytes());
+            }
+            rotate_if_full(&path, MAX_LOG_BYTES);
+        }
+    }
+}
+
+fn open_append(path: &Path) -> Result<File, String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open MCP traffic log: {}", e))
+}
+
+fn parse_line(line: &[u8]) -> Option<Value> {
+    let text = String::from_utf8_lossy(line);
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(serde_json::from_str(text).unwrap_or_else(|_| Value::from(text)))
+}
+
+/// Copy `input` to `output` line by line, logging each line
+fn relay(input: impl Read, mut output: impl Write, logger: &TrafficLogger, direction: &str) {
+    let mut reader = BufReader::new(input);
+    loop {
+        let mut line = Vec::new();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        if let Some(message) = parse_line(&line) {
+            logger.log(direction, message);
+        }
+        if output
+            .write_all(&line)
+            .and_then(|_| output.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// `--log <file> -- <command> [args...]`
+fn parse_proxy_args(args: &[String]) -> Result<(PathBuf, String, Vec<String>), String> {
+    let usage =
+        || format!("Usage: ai-toolbox {PROXY_SUBCOMMAND} --log <file> -- <command> [args...]");
+    match args {
+        [flag, log, separator, command, rest @ ..] if flag == "--log" && separator == "--" => {
+            Ok((PathBuf::from(log), command.clone(), rest.to_vec()))
+        }
+        _ => Err(usage()),
+    }
+}
+
+/// Run the wrapped server and relay its stdio; returns its exit code
+pub fn run_proxy(args: &[String]) -> i32 {
+    let (log_path, command, command_args) = match parse_proxy_args(args) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("{}", error);
+            return 2;
+        }
+    };
+    // A log that cannot be opened must not keep the server from starting
+    let logger = TrafficLogger::open(&log_path).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        TrafficLogger { path: None }
+    });
+
+    let program = resolve_local_command_program(&command);
+    let mut child = match build_local_std_command(&program.path)
+        .args(&command_args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            let error = format!("Failed to start {}: {}", command, e);
+            logger.log("exit", Value::from(error.clone()));
+            eprintln!("{}", error);
+            return 1;
+        }
+    };
+    let (Some(child_stdin), Some(child_stdout), Some(child_stderr)) =
+        (child.stdin.take(), child.stdout.take(), child.stderr.take())
+    else {
+        return 1;
+    };
+
+    // Not joined: it blocks on our stdin until the client closes it
+    let client_logger = logger.clone();
+    thread::spawn(move || relay(std::io::stdin(), child_stdin, &client_logger, "client"));
+    let server_logger = logger.clone();
+    let server =
+        thread::spawn(move || relay(child_stdout, std::io::stdout(), &server_logger, "server"));
+    let stderr_logger = logger.clone();
+    let errors =
+        thread::spawn(move || relay(child_stderr, std::io::stderr(), &stderr_logger, "stderr"));
+
+    let code = child
+        .wait()
+        .ok()
+        .and_then(|status| status.code())
+        .unwrap_or(1);
+    let _ = server.join();
+    let _ = errors.join();
+    logger.log("exit", json!({ "code": code }));
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_only_logged_stdio_servers() {
+        let server = |id: &str, server_type: &str| -> McpServer {
+            serde_json::from_value(json!({
+                "id": id,
+                "name": id,
+                "server_type": server_type,
+                "server_config": { "command": "npx", "args": ["-y", "pkg"], "env": { "A": "1" } },
+                "enabled_tools": [],
+                "created_at": 0,
+                "updated_at": 0,
+            }))
+            .unwrap()
+        };
+        set_logged_servers(&["logged".to_string(), "remote".to_string()]);
+
+        let shim = Path::new("/data/mcp-traffic").join(PROXY_SHIM_NAME);
+        let log = Path::new("/data/mcp-traffic/logged.jsonl");
+        let wrapped = wrap_with_proxy(&server("logged", "stdio"), &shim, log).unwrap();
+        let args = wrapped.server_config["args"].as_array().unwrap();
+        let shim = json!(shim.to_string_lossy());
+        if cfg!(windows) {
+            assert_eq!(wrapped.server_config["command"], "cmd");
+            assert_eq!(args[..2], [json!("/c"), shim]);
+        } else {
+            assert_eq!(wrapped.server_config["command"], shim);
+        }
+        assert_eq!(
+            args[args.len() - 6..],
+            [
+                json!("--log"),
+                json!(log.to_string_lossy()),
+                json!("--"),
+                json!("npx"),
+                json!("-y"),
+                json!("pkg")
+            ]
+        );
+        assert_eq!(wrapped.server_config["env"]["A"], "1");
+        assert!(logged_server(&server("plain", "stdio")).is_none());
+        assert!(logged_server(&server("remote", "http")).is_none());
+        set_logged_servers(&[]);
+    }
+
+    #[test]
+    fn rotates_a_full_log_and_quotes_the_shim_target() {
+        let temp = tempfile::tempdir().unwrap();
+        let log = temp.path().join("server.jsonl");
+        fs::write(&log, "0123456789").unwrap();
+        rotate_if_full(&log, 10);
+        assert!(log.exists());
+        rotate_if_full(&log, 9);
+        assert!(!log.exists());
+        assert_eq!(
+            fs::read_to_string(rotated_path(&log)).unwrap(),
+            "0123456789"
+        );
+
+        #[cfg(not(windows))]
+        assert_eq!(
+            shim_script(Path::new("/opt/it's/ai-toolbox")),
+            "#!/bin/sh\nexec '/opt/it'\\''s/ai-toolbox' mcp-proxy \"$@\"\n"
+        );
+    }
+
+    #[test]
+    fn parses_proxy_args_and_lines() {
+        let args = ["--log", "/tmp/x.jsonl", "--", "uvx", "mcp-server-fetch"].map(String::from);
+        let (log, command, rest) = parse_proxy_args(&args).unwrap();
+        assert_eq!(log, PathBuf::from("/tmp/x.jsonl"));
+        assert_eq!(command, "uvx");
+        assert_eq!(rest, vec!["mcp-server-fetch"]);
+        assert!(parse_proxy_args(&args[..3]).is_err());
+
+        assert_eq!(
+            parse_line(b"{\"jsonrpc\":\"2.0\",\"id\":1}\n"),
+            Some(json!({ "jsonrpc": "2.0", "id": 1 }))
+        );
+        assert_eq!(parse_line(b"starting up\r\n"), Some(json!("starting up")));
+        assert_eq!(parse_line(b"\n"), None);
+    }
+}
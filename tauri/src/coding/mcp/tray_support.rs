@@ -91,6 +91,7 @@ pub async fn apply_mcp_tool_toggle<R: Runtime>(
     server_id: &str,
     tool_key: &str,
 ) -> Result<(), String> {
+    crate::settings::read_only::ensure_writable("mcp_tray_toggle")?;
     let state = app.state::<SqliteDbState>();
 
     // Toggle the tool
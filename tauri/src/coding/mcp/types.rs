@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub use ai_toolbox_core::mcp::types::McpServer;
+
 /// MCP Server type
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -56,32 +58,6 @@ pub struct HttpConfig {
     pub headers: Option<Value>,
 }
 
-/// MCP Server record stored in SQLite JSONB
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct McpServer {
-    pub id: String,
-    pub name: String,
-    pub server_type: String,
-    pub server_config: Value,
-    pub enabled_tools: Vec<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub sync_details: Option<Value>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub user_group: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub user_note: Option<String>,
-    #[serde(default)]
-    pub tags: Vec<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub timeout: Option<i64>,
-    #[serde(default)]
-    pub sort_index: i32,
-    pub created_at: i64,
-    pub updated_at: i64,
-}
-
 /// MCP Server sync detail for a specific tool
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct McpSyncDetail {
@@ -158,6 +134,19 @@ pub struct McpPreferences {
     pub sync_disabled_to_opencode: bool,
     #[serde(default)]
     pub limit_add_more_to_preferred_tools: bool,
+    /// Tools that only take URL servers; stdio servers reach them through
+    /// the local gateway (see `gateway`)
+    #[serde(default)]
+    pub remote_only_tools: Vec<String>,
+    /// Port the gateway last listened on, reused so synced URLs stay valid
+    #[serde(default)]
+    pub gateway_port: Option<u16>,
+    /// Secret path prefix of the gateway URLs, created on first use
+    #[serde(default)]
+    pub gateway_token: Option<String>,
+    /// Servers whose stdio traffic is logged (see `traffic_log`)
+    #[serde(default)]
+    pub traffic_log_servers: Vec<String>,
     pub updated_at: i64,
 }
 
@@ -170,6 +159,10 @@ impl Default for McpPreferences {
             favorites_initialized: false,
             sync_disabled_to_opencode: false,
             limit_add_more_to_preferred_tools: false,
+            remote_only_tools: Vec::new(),
+            gateway_port: None,
+            gateway_token: None,
+            traffic_log_servers: Vec::new(),
             updated_at: 0,
         }
     }
@@ -190,6 +183,8 @@ pub struct McpImportResultDto {
     pub servers_skipped: i32,
     pub servers_duplicated: Vec<String>, // Names of servers created with suffix due to config differences
     pub errors: Vec<String>,
+    /// Recoveries made while reading the source config (BOM, comments, duplicate keys)
+    pub warnings: Vec<String>,
 }
 
 /// Discovered MCP server info (for scan results)
@@ -208,6 +203,7 @@ pub struct McpScanResultDto {
     pub total_tools_scanned: i32,
     pub total_servers_found: i32,
     pub servers: Vec<McpDiscoveredServerDto>,
+    pub warnings: Vec<String>,
 }
 
 /// Package manager family used to resolve an MCP stdio runner package version.
@@ -3,6 +3,8 @@ pub mod claude_code;
 pub mod cli_resolver;
 pub mod codex;
 pub mod config_cleanup;
+pub mod config_snapshot;
+pub mod devcontainer;
 pub mod gemini_cli;
 pub mod grok;
 pub mod image;
@@ -19,6 +21,7 @@ pub mod runtime_location;
 pub mod session_manager;
 pub mod skills;
 pub mod ssh;
+pub mod status_summary;
 pub mod tools;
 pub(crate) mod url_utils;
 pub mod wsl;
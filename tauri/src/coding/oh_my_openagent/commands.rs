@@ -219,7 +219,7 @@ fn normalize_agents_keys(agents: &mut Value) {
 }
 
 fn get_default_oh_my_openagent_dir() -> Result<std::path::PathBuf, String> {
-    let home_dir = dirs::home_dir().ok_or("Failed to get home directory")?;
+    let home_dir = crate::coding::tools::home_dir().ok_or("Failed to get home directory")?;
     Ok(home_dir.join(".config").join("opencode"))
 }
 
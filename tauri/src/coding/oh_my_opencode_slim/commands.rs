@@ -81,7 +81,7 @@ fn put_global_config_to_sqlite(sqlite_state: &SqliteDbState, data: &Value) -> Re
 }
 
 fn get_default_oh_my_opencode_slim_dir() -> Result<std::path::PathBuf, String> {
-    let home_dir = dirs::home_dir().ok_or("Failed to get home directory")?;
+    let home_dir = crate::coding::tools::home_dir().ok_or("Failed to get home directory")?;
     Ok(home_dir.join(".config").join("opencode"))
 }
 
@@ -17,11 +17,10 @@ use crate::db::SqliteDbState;
 
 /// Get default config path: ~/.openclaw/openclaw.json
 pub fn get_default_config_path_for_runtime() -> Result<String, String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+    let home_dir = crate::coding::tools::env_home_dir()
+        .ok_or_else(|| "Failed to get home directory".to_string())?;
 
-    let config_path = Path::new(&home_dir).join(".openclaw").join("openclaw.json");
+    let config_path = home_dir.join(".openclaw").join("openclaw.json");
 
     Ok(config_path.to_string_lossy().to_string())
 }
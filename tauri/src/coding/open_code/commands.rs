@@ -592,11 +592,10 @@ pub async fn get_opencode_config_path_info(
 /// Helper function to get default config path
 /// Returns the actual config file path (checks .jsonc first, then .json)
 pub fn get_default_config_path() -> Result<String, String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+    let home_dir = crate::coding::tools::env_home_dir()
+        .ok_or_else(|| "Failed to get home directory".to_string())?;
 
-    let config_dir = Path::new(&home_dir).join(".config").join("opencode");
+    let config_dir = home_dir.join(".config").join("opencode");
 
     // Check for .jsonc first, then .json
     let jsonc_path = config_dir.join("opencode.jsonc");
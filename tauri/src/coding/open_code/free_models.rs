@@ -574,7 +574,8 @@ struct AuthEntry {
 }
 
 fn get_auth_json_path() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir().ok_or_else(|| "Failed to get home directory".to_string())?;
+    let home_dir = crate::coding::tools::home_dir()
+        .ok_or_else(|| "Failed to get home directory".to_string())?;
     Ok(home_dir.join(".local/share/opencode/auth.json"))
 }
 
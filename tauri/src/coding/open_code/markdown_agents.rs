@@ -57,6 +57,9 @@ fn content_hash(content: &str) -> String {
 }
 
 fn local_home_dir() -> Result<PathBuf, String> {
+    if let Some(home) = crate::coding::tools::home_override() {
+        return Ok(home);
+    }
     #[cfg(target_os = "windows")]
     let home = std::env::var("USERPROFILE").or_else(|_| std::env::var("HOME"));
     #[cfg(not(target_os = "windows"))]
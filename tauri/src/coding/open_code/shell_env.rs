@@ -20,11 +20,7 @@ pub fn get_env_from_shell_config(var_name: &str) -> Option<String> {
 /// Get list of shell configuration files to check (in priority order)
 #[allow(unused_variables)]
 fn get_shell_config_files() -> Option<Vec<PathBuf>> {
-    let home_dir = std::env::var("HOME")
-        .or_else(|_| std::env::var("USERPROFILE"))
-        .ok()?;
-
-    let home_path = PathBuf::from(home_dir);
+    let home_path = crate::coding::tools::home_dir()?;
 
     // Platform-specific configuration files in priority order
     #[cfg(target_os = "macos")]
@@ -154,7 +150,8 @@ fn expand_env_vars(value: &str) -> String {
     let mut result = value.to_string();
 
     // Expand $HOME
-    if let Ok(home) = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")) {
+    if let Some(home) = crate::coding::tools::home_dir() {
+        let home = home.to_string_lossy();
         result = result.replace("$HOME", &home);
         result = result.replace("${HOME}", &home);
         result = result.replace("$USERPROFILE", &home);
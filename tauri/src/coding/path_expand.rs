@@ -1,7 +1,7 @@
 //! Common Path Expansion Utilities
 //!
 //! Provides standardized path expansion for local file paths across modules (WSL, SSH, etc.):
-//! - `~` expands to home directory via `dirs::home_dir()`
+//! - `~` expands to home directory via `tools::home_dir()` (honors the home override)
 //! - `%USERPROFILE%`, `%APPDATA%`, `%LOCALAPPDATA%` expand to Windows env vars
 //! - `$HOME`, `$USERPROFILE` expand to Unix-style env vars
 //!
@@ -22,24 +22,43 @@ pub fn expand_local_path(path: &str) -> Result<String, String> {
 
     // Expand ~ to home directory
     if result.starts_with("~/") || result == "~" {
-        if let Some(home) = dirs::home_dir() {
+        if let Some(home) = crate::coding::tools::home_dir() {
             result = result.replacen("~", &home.to_string_lossy(), 1);
         }
     }
 
-    // Common environment variables (Windows and Unix)
+    // Common environment variables (Windows and Unix); a home override replaces
+    // the home-derived ones
+    let home_override = crate::coding::tools::home_override();
+    let from_home = |path: Option<std::path::PathBuf>| {
+        home_override
+            .as_ref()
+            .and(path)
+            .map(|path| path.to_string_lossy().to_string())
+    };
     let vars = [
-        ("USERPROFILE", std::env::var("USERPROFILE")),
-        ("APPDATA", std::env::var("APPDATA")),
-        ("LOCALAPPDATA", std::env::var("LOCALAPPDATA")),
+        (
+            "USERPROFILE",
+            from_home(crate::coding::tools::home_dir())
+                .or_else(|| std::env::var("USERPROFILE").ok()),
+        ),
+        (
+            "APPDATA",
+            from_home(crate::coding::tools::config_dir()).or_else(|| std::env::var("APPDATA").ok()),
+        ),
+        ("LOCALAPPDATA", std::env::var("LOCALAPPDATA").ok()),
         (
             "HOME",
-            std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")),
+            from_home(crate::coding::tools::home_dir()).or_else(|| {
+                std::env::var("HOME")
+                    .or_else(|_| std::env::var("USERPROFILE"))
+                    .ok()
+            }),
         ),
     ];
 
     for (var, value) in vars {
-        if let Ok(val) = value {
+        if let Some(val) = value {
             // Windows style: %VAR%
             result = result.replace(&format!("%{}%", var), &val);
             // Unix style: $VAR
@@ -28,10 +28,7 @@ const PI_EXTENDED_THINKING_LEVEL_KEYS: [&str; 2] = ["xhigh", "max"];
 const PI_OTHER_SETTINGS_PROTECTED_KEYS: [&str; 1] = ["packages"];
 
 fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 pub fn get_pi_default_root_dir() -> Result<PathBuf, String> {
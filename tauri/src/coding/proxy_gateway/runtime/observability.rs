@@ -164,7 +164,7 @@ fn emit_usage_recorded_event(context: &GatewayRuntimeContext, summary: &GatewayR
 }
 
 fn should_skip_observability(method: &str, request_path: &str) -> bool {
-    if method == "GET" && request_path == "/health" {
+    if method == "GET" && matches!(request_path, "/health" | "/status") {
         return true;
     }
     matches!(method, "GET" | "HEAD")
@@ -33,6 +33,7 @@ use crate::coding::proxy_gateway::types::{
 use crate::coding::proxy_gateway::usage_parser::{
     from_response_body_with_provider_type, TokenUsage,
 };
+use crate::coding::status_summary;
 use crate::db::SqliteDbState;
 use crate::http_client::{self, ProxyMode};
 use futures_util::StreamExt;
@@ -541,6 +542,9 @@ pub(super) async fn route_request_with_options(
             "local health endpoint",
         );
     }
+    if request.method == "GET" && request_path == "/status" {
+        return status_summary_response(context);
+    }
 
     let Some(route) = match_gateway_route(&request.path) else {
         return json_response(
@@ -581,6 +585,33 @@ pub(super) async fn route_request_with_options(
     forward_to_upstream(request, db, context, &route, options).await
 }
 
+/// Skill / MCP status summary for external status bar integrations
+fn status_summary_response(context: &GatewayRuntimeContext) -> DebugHttpResponse {
+    let summary = match (context.db.as_ref(), context.app_handle.as_ref()) {
+        (Some(db), Some(app_handle)) => status_summary::status_summary_for_app(app_handle, db),
+        (Some(db), None) => status_summary::load_status_summary(db),
+        (None, _) => Err("gateway runtime has no database handle".to_string()),
+    };
+    match summary {
+        Ok(summary) => json_response(
+            200,
+            "OK",
+            json!(summary),
+            "status",
+            None,
+            "local status summary endpoint",
+        ),
+        Err(error) => json_response(
+            503,
+            "Service Unavailable",
+            json!({"error": "status_unavailable", "message": error}),
+            "status",
+            None,
+            "status summary could not be loaded",
+        ),
+    }
+}
+
 fn is_cli_route_probe(request: &DebugHttpRequest, route: &GatewayRoute) -> bool {
     if !matches!(request.method.as_str(), "GET" | "HEAD") {
         return false;
@@ -96,7 +96,7 @@ fn import_cli_session_usage(
 }
 
 fn default_session_roots(cli_key: GatewayCliKey) -> Vec<PathBuf> {
-    let Some(home) = dirs::home_dir() else {
+    let Some(home) = crate::coding::tools::home_dir() else {
         return Vec::new();
     };
     match cli_key {
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::coding::open_code::shell_env;
-use crate::coding::{claude_code, codex, gemini_cli, grok, open_claw, open_code, pi};
+use crate::coding::{claude_code, codex, gemini_cli, grok, open_claw, open_code, pi, tools};
 use crate::db::helpers::{db_get, db_patch_fields};
 use crate::db::schema::DbTable;
 
@@ -252,7 +252,7 @@ pub async fn refresh_runtime_location_cache_for_module_async(
     db: &crate::db::SqliteDbState,
     module: &str,
 ) -> Result<RuntimeLocationInfo, String> {
-    match normalize_module_key(module) {
+    let location = match normalize_module_key(module) {
         Some("opencode") => {
             let location = resolve_opencode_runtime_location_uncached_async(db).await?;
             set_cached_runtime_location("opencode", location.clone());
@@ -291,7 +291,10 @@ pub async fn refresh_runtime_location_cache_for_module_async(
             Ok(location)
         }
         Some(_) | None => Err(format!("Unsupported runtime module: {}", module)),
-    }
+    };
+    // The tool's paths move with its runtime location
+    tools::detection_cache::invalidate_detection_cache();
+    location
 }
 
 pub async fn refresh_runtime_location_cache_async(
@@ -710,10 +713,7 @@ fn resolve_claude_plugins_dir_uncached(location: &RuntimeLocationInfo) -> PathBu
 }
 
 fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 pub fn get_claude_wsl_target_path(db: &crate::db::SqliteDbState, file_name: &str) -> String {
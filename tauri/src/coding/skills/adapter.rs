@@ -1,7 +1,10 @@
 use serde_json::Value;
 
 use super::tool_adapters::CustomTool;
-use super::types::{Skill, SkillGroupRecord, SkillPreferences, SkillRepo, SkillTarget};
+use super::types::{
+    Skill, SkillGroupRecord, SkillPathRules, SkillPreferences, SkillRepo, SkillTarget,
+};
+use super::unicode_names::nfc_name;
 use crate::coding::db_extract_id;
 
 // ==================== Skill ====================
@@ -78,6 +81,29 @@ pub fn from_db_skill(value: Value) -> Skill {
         disabled_previous_tools,
         enabled_tools,
         sync_details: value.get("sync_details").cloned().filter(|v| !v.is_null()),
+        license: value
+            .get("license")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        provenance: value
+            .get("provenance")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok()),
+        tags: parse_string_array(value.get("tags")),
+        compatibility: value
+            .get("compatibility")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
+        localizations: value
+            .get("localizations")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
+        write_protected: value
+            .get("write_protected")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
     }
 }
 
@@ -95,7 +121,7 @@ fn parse_string_array(value: Option<&Value>) -> Vec<String> {
 /// Convert Skill to clean database payload (without id)
 pub fn to_clean_skill_payload(skill: &Skill) -> Value {
     serde_json::json!({
-        "name": skill.name,
+        "name": nfc_name(&skill.name),
         "source_type": skill.source_type,
         "source_ref": skill.source_ref,
         "source_revision": skill.source_revision,
@@ -113,6 +139,12 @@ pub fn to_clean_skill_payload(skill: &Skill) -> Value {
         "disabled_previous_tools": skill.disabled_previous_tools,
         "enabled_tools": skill.enabled_tools,
         "sync_details": skill.sync_details,
+        "license": skill.license,
+        "provenance": skill.provenance,
+        "tags": skill.tags,
+        "compatibility": skill.compatibility,
+        "localizations": skill.localizations,
+        "write_protected": skill.write_protected,
     })
 }
 
@@ -187,10 +219,18 @@ pub fn parse_sync_details(skill: &Skill) -> Vec<SkillTarget> {
                 .get("error_message")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
+            path_rules: parse_path_rules(entry),
         })
         .collect()
 }
 
+fn parse_path_rules(entry: &Value) -> SkillPathRules {
+    entry
+        .get("path_rules")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
 /// Set a SkillTarget in sync_details JSON (upsert single tool)
 pub fn set_sync_detail(existing: &Option<Value>, tool: &str, target: &SkillTarget) -> Value {
     let mut obj = existing
@@ -208,6 +248,14 @@ pub fn set_sync_detail(existing: &Option<Value>, tool: &str, target: &SkillTarge
             "error_message": target.error_message,
         }),
     );
+    if !target.path_rules.is_empty() {
+        if let Some(entry) = obj.get_mut(tool).and_then(|v| v.as_object_mut()) {
+            entry.insert(
+                "path_rules".to_string(),
+                serde_json::json!(target.path_rules),
+            );
+        }
+    }
 
     Value::Object(obj)
 }
@@ -250,6 +298,7 @@ pub fn get_sync_detail(existing: &Option<Value>, tool: &str) -> Option<SkillTarg
             .get("error_message")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
+        path_rules: parse_path_rules(entry),
     })
 }
 
@@ -399,6 +448,11 @@ pub fn from_db_custom_tool(value: Value) -> CustomTool {
             .get("force_copy")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        preferred_sync_mode: value
+            .get("preferred_sync_mode")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
     }
 }
 
@@ -410,6 +464,7 @@ pub fn to_custom_tool_payload(tool: &CustomTool) -> Value {
         "relative_detect_dir": tool.relative_detect_dir,
         "created_at": tool.created_at,
         "force_copy": tool.force_copy,
+        "preferred_sync_mode": tool.preferred_sync_mode,
     })
 }
 
@@ -0,0 +1,202 @@
+//! Commands for batch onboarding imports (`adopt_policy.rs`,
+//! `adopt_simulation.rs`, `plan_export.rs`)
+
+use std::path::Path;
+use std::time::Duration;
+
+use tauri::{Emitter, State};
+
+use super::adopt_policy::{
+    self, AdoptOnboardingResultDto, AdoptSkippedDto, AdoptedGroupDto, VariantPolicy,
+};
+use super::adopt_simulation::{simulate_adoption, AdoptionSelection, AdoptionSimulationDto};
+use super::auto_adopt;
+use super::commands::{format_error, onboarding_plan_with_timeout, resolve_local_source_path};
+use super::installer::{install_linked_folder_skill, install_local_skill};
+use super::onboarding::{
+    build_onboarding_plan_with_diagnostics, load_source_priority, save_source_priority,
+};
+use super::plan_export::{OnboardingPlanExport, PathAnonymizer, PLAN_EXPORT_VERSION};
+use super::skill_store;
+use super::tool_adapters::get_all_tool_adapters;
+use super::types::{now_ms, OnboardingVariant};
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Import every onboarding group in one step, picking each group's variant by
+/// `policy`. `names` limits the run to those groups; `None` adopts all of them.
+#[tauri::command]
+pub async fn skills_adopt_onboarding_plan(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    policy: VariantPolicy,
+    names: Option<Vec<String>>,
+    overwrite: Option<bool>,
+) -> Result<AdoptOnboardingResultDto, String> {
+    ensure_writable("skills_adopt_onboarding_plan")?;
+    let (plan, _) = onboarding_plan_with_timeout(&app, &state, true).await?;
+    let picks = tokio::task::spawn_blocking(move || {
+        plan.groups
+            .iter()
+            .filter(|group| {
+                names
+                    .as_ref()
+                    .is_none_or(|names| names.contains(&group.name))
+            })
+            .filter_map(|group| adopt_policy::pick_variant(group, &policy).cloned())
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?;
+
+    let result = adopt_variants(&app, &state, picks, overwrite.unwrap_or(false)).await;
+    if !result.adopted.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(result)
+}
+
+/// Adopt the low-risk groups of a fresh scan (see `auto_adopt`); used by the
+/// scheduled rescan
+pub(crate) async fn auto_adopt_low_risk_groups(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+) -> Result<AdoptOnboardingResultDto, String> {
+    let (plan, _) = onboarding_plan_with_timeout(app, state, true).await?;
+    let picks = tokio::task::spawn_blocking(move || auto_adopt::low_risk_variants(&plan))
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e))?;
+
+    let result = adopt_variants(app, state, picks, false).await;
+    if !result.adopted.is_empty() {
+        let _ = app.emit("skills-changed", "auto_adopt");
+    }
+    Ok(result)
+}
+
+async fn adopt_variants(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    picks: Vec<OnboardingVariant>,
+    overwrite: bool,
+) -> AdoptOnboardingResultDto {
+    let mut result = AdoptOnboardingResultDto::default();
+    for variant in picks {
+        let path = Path::new(&variant.path);
+        let installed = match &variant.linked_folder {
+            Some(folder) => {
+                install_linked_folder_skill(app, state, path, Path::new(folder), overwrite).await
+            }
+            None => install_local_skill(app, state, path, overwrite).await,
+        };
+        match installed {
+            Ok(installed) => result.adopted.push(AdoptedGroupDto {
+                name: variant.name,
+                tool: variant.tool,
+                path: variant.path,
+                skill_id: installed.skill_id,
+                central_path: installed.central_path.to_string_lossy().to_string(),
+            }),
+            Err(err) => result.skipped.push(AdoptSkippedDto {
+                name: variant.name,
+                error: format_error(err),
+            }),
+        }
+    }
+    result
+}
+
+/// Whether the scheduled rescan adopts low-risk onboarding groups on its own
+#[tauri::command]
+pub async fn skills_get_auto_adopt_onboarding(
+    state: State<'_, SqliteDbState>,
+) -> Result<bool, String> {
+    Ok(auto_adopt::is_auto_adopt_enabled(&state))
+}
+
+#[tauri::command]
+pub async fn skills_set_auto_adopt_onboarding(
+    state: State<'_, SqliteDbState>,
+    enabled: bool,
+) -> Result<(), String> {
+    ensure_writable("skills_set_auto_adopt_onboarding")?;
+    auto_adopt::set_auto_adopt_enabled(&state, enabled)
+}
+
+/// Replay adopting `selection` (usually an exported plan) in a temporary
+/// sandbox and report every operation; no real tool dir is touched
+#[tauri::command]
+pub async fn skills_simulate_adoption(
+    state: State<'_, SqliteDbState>,
+    selection: AdoptionSelection,
+) -> Result<AdoptionSimulationDto, String> {
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let adapters = get_all_tool_adapters(&custom_tools);
+    tokio::task::spawn_blocking(move || simulate_adoption(&selection, &adapters))
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e))?
+        .map_err(format_error)
+}
+
+/// Source order that decides which of identical variants becomes the import
+/// source (tool keys; `plugin::*` matches every plugin)
+#[tauri::command]
+pub async fn skills_get_source_priority(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<String>, String> {
+    Ok(load_source_priority(&state))
+}
+
+/// Save the source priority; an empty list restores the default
+#[tauri::command]
+pub async fn skills_set_source_priority(
+    state: State<'_, SqliteDbState>,
+    priority: Vec<String>,
+) -> Result<(), String> {
+    ensure_writable("skills_set_source_priority")?;
+    save_source_priority(&state, &priority)
+}
+
+/// Write the full onboarding plan with scan diagnostics to `path` (default
+/// `~/onboarding-plan-{timestamp}.json`) for bug reports. User paths are
+/// anonymized; the scan index is not updated.
+#[tauri::command]
+pub async fn skills_export_onboarding_plan(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let scan = build_onboarding_plan_with_diagnostics(&app, &state, false);
+    let (plan, _, diagnostics) = match tokio::time::timeout(Duration::from_secs(30), scan).await {
+        Ok(result) => result.map_err(format_error)?,
+        Err(_) => {
+            return Err(
+                "Scan timed out after 30 seconds. Please check your custom tool paths.".to_string(),
+            )
+        }
+    };
+    let home = crate::coding::tools::home_dir();
+    let mut export = OnboardingPlanExport {
+        version: PLAN_EXPORT_VERSION,
+        exported_at: now_ms(),
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        plan,
+        diagnostics,
+    };
+    PathAnonymizer::new(home.as_deref()).anonymize_export(&mut export);
+    let json = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize onboarding plan: {}", e))?;
+
+    let path = match path {
+        Some(path) => resolve_local_source_path(&path)?,
+        None => home
+            .ok_or_else(|| "Failed to resolve home directory".to_string())?
+            .join(format!("onboarding-plan-{}.json", export.exported_at)),
+    };
+    std::fs::write(&path, json)
+        .map_err(|e| format!("Failed to write onboarding plan file: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,153 @@
+//! Bulk adoption of an onboarding plan
+//!
+//! Instead of picking a variant per group in the import dialog, the user sets
+//! one policy that chooses the variant for every group, conflicting or not.
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use super::consolidate::dir_size;
+use super::types::{OnboardingGroup, OnboardingVariant};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VariantPolicy {
+    /// Variant found in `tool`; groups without one fall back to `NewestMtime`
+    PreferTool { tool: String },
+    /// Variant whose most recently modified file is newest
+    NewestMtime,
+    /// Variant with the most bytes on disk
+    Largest,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AdoptedGroupDto {
+    pub name: String,
+    pub tool: String,
+    pub path: String,
+    pub skill_id: String,
+    pub central_path: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AdoptSkippedDto {
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AdoptOnboardingResultDto {
+    pub adopted: Vec<AdoptedGroupDto>,
+    pub skipped: Vec<AdoptSkippedDto>,
+}
+
+/// Choose the variant of `group` to adopt under `policy`.
+///
+/// Identical variants only differ in where they live, so a group without a
+/// conflict adopts its first variant: the plan lists them in source priority
+/// order (see `onboarding::sort_variants_by_priority`).
+pub fn pick_variant<'a>(
+    group: &'a OnboardingGroup,
+    policy: &VariantPolicy,
+) -> Option<&'a OnboardingVariant> {
+    match policy {
+        VariantPolicy::PreferTool { tool } => group
+            .variants
+            .iter()
+            .find(|v| &v.tool == tool)
+            .or_else(|| pick_variant(group, &VariantPolicy::NewestMtime)),
+        _ if !group.has_conflict => group.variants.first(),
+        VariantPolicy::NewestMtime => group
+            .variants
+            .iter()
+            .max_by_key(|v| latest_mtime(Path::new(&v.path))),
+        VariantPolicy::Largest => group
+            .variants
+            .iter()
+            .max_by_key(|v| dir_size(Path::new(&v.path))),
+    }
+}
+
+/// Newest modification time of any file under `path` (links are followed,
+/// since a variant is often a link into another tool's dir)
+fn latest_mtime(path: &Path) -> Option<SystemTime> {
+    WalkDir::new(path)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn variant(tool: &str, path: &Path) -> OnboardingVariant {
+        OnboardingVariant {
+            tool: tool.to_string(),
+            tool_display: tool.to_string(),
+            name: "demo".to_string(),
+            path: path.to_string_lossy().to_string(),
+            fingerprint: None,
+            is_link: false,
+            link_target: None,
+            linked_folder: None,
+            conflicting_tools: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn policies_pick_expected_variant() {
+        let temp = tempfile::tempdir().unwrap();
+        let old_big = temp.path().join("codex/demo");
+        let new_small = temp.path().join("cursor/demo");
+        std::fs::create_dir_all(&old_big).unwrap();
+        std::fs::create_dir_all(&new_small).unwrap();
+        std::fs::write(old_big.join("SKILL.md"), "a much longer body").unwrap();
+        std::fs::write(new_small.join("SKILL.md"), "short").unwrap();
+        let file = std::fs::File::options()
+            .write(true)
+            .open(old_big.join("SKILL.md"))
+            .unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        let group = OnboardingGroup {
+            name: "demo".to_string(),
+            variants: vec![variant("codex", &old_big), variant("cursor", &new_small)],
+            has_conflict: true,
+        };
+        let picked = |policy: VariantPolicy| pick_variant(&group, &policy).unwrap().tool.clone();
+
+        assert_eq!(picked(VariantPolicy::Largest), "codex");
+        assert_eq!(picked(VariantPolicy::NewestMtime), "cursor");
+        assert_eq!(
+            picked(VariantPolicy::PreferTool {
+                tool: "codex".to_string()
+            }),
+            "codex"
+        );
+        // Missing preferred tool falls back to the newest variant
+        assert_eq!(
+            picked(VariantPolicy::PreferTool {
+                tool: "claude_code".to_string()
+            }),
+            "cursor"
+        );
+
+        // Identical copies adopt the first (highest priority) variant
+        let identical = OnboardingGroup {
+            has_conflict: false,
+            ..group.clone()
+        };
+        for policy in [VariantPolicy::Largest, VariantPolicy::NewestMtime] {
+            assert_eq!(pick_variant(&identical, &policy).unwrap().tool, "codex");
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Dry run of onboarding adoption in a throwaway sandbox
+//!
+//! `simulate_adoption` replays what `skills_adopt_onboarding_plan` and the
+//! follow-up tool syncs do for a plan, typically one exported by a user who
+//! reported an onboarding bug: pick each group's variant, import it into a
+//! sandbox central repo, then sync it into sandbox tool dirs through the same
+//! sync engine calls. Variant directories are only read and the sandbox is
+//! removed afterwards, so no real tool dir or database record is touched.
+//!
+//! The sandbox starts with an empty library: collisions with skills already
+//! managed on the reporting machine do not show up.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::adopt_policy::{pick_variant, VariantPolicy};
+use super::name_collision::{dir_entry_names, ensure_no_case_collision};
+use super::sync_context::{self, SyncContext};
+use super::sync_engine::{
+    copy_skill_dir, ensure_source_target_not_overlapping, sync_dir_for_tool_with_overwrite,
+};
+use super::tool_adapters::RuntimeToolAdapter;
+use super::types::{OnboardingPlan, SyncMode};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AdoptionSelection {
+    pub plan: OnboardingPlan,
+    pub policy: VariantPolicy,
+    /// Groups to adopt; `None` adopts all of them
+    #[serde(default)]
+    pub names: Option<Vec<String>>,
+    /// Tools the adopted skills are synced to
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedOperationKind {
+    /// Variant chosen by the policy
+    Pick,
+    /// Copy into the central repo
+    Import,
+    /// Sync from the central repo into a tool dir
+    Sync,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulatedOperation {
+    pub group: String,
+    pub kind: SimulatedOperationKind,
+    pub tool: Option<String>,
+    pub source: Option<String>,
+    /// Target inside the sandbox, relative to its root
+    pub target: Option<String>,
+    /// Sync mode used: `symlink`, `junction` or `copy`
+    pub mode: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AdoptionSimulationDto {
+    pub operations: Vec<SimulatedOperation>,
+    /// Groups imported into the sandbox central repo
+    pub adopted: usize,
+    /// Operations that failed
+    pub failed: usize,
+}
+
+/// Run the adoption of `selection` in a fresh temporary sandbox
+pub fn simulate_adoption(
+    selection: &AdoptionSelection,
+    adapters: &[RuntimeToolAdapter],
+) -> Result<AdoptionSimulationDto> {
+    let sandbox = tempfile::tempdir().context("failed to create adoption sandbox")?;
+    Ok(simulate_adoption_in(sandbox.path(), selection, adapters))
+}
+
+fn simulate_adoption_in(
+    sandbox: &Path,
+    selection: &AdoptionSelection,
+    adapters: &[RuntimeToolAdapter],
+) -> AdoptionSimulationDto {
+    let central_dir = sandbox.join("central");
+    // Nothing replaced in the sandbox belongs in the real sync trash
+    let ctx = SyncContext {
+        trash_dir: None,
+        ..sync_context::current()
+    };
+    let mut result = AdoptionSimulationDto::default();
+    let relative = |path: &Path| {
+        path.strip_prefix(sandbox)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    for group in selection.plan.groups.iter().filter(|group| {
+        selection
+            .names
+            .as_ref()
+            .is_none_or(|names| names.contains(&group.name))
+    }) {
+        let op = |kind| SimulatedOperation {
+            group: group.name.clone(),
+            kind,
+            tool: None,
+            source: None,
+            target: None,
+            mode: None,
+            error: None,
+        };
+
+        let Some(variant) = pick_variant(group, &selection.policy) else {
+            result.operations.push(SimulatedOperation {
+                error: Some("No variant to adopt".to_string()),
+                ..op(SimulatedOperationKind::Pick)
+            });
+            result.failed += 1;
+            continue;
+        };
+        result.operations.push(SimulatedOperation {
+            tool: Some(variant.tool.clone()),
+            source: Some(variant.path.clone()),
+            ..op(SimulatedOperationKind::Pick)
+        });
+
+        // Same naming as install_local_skill / install_linked_folder_skill
+        let name = Path::new(&variant.path)
+            .file_name()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed-skill".to_string());
+        let source = Path::new(
+            variant
+                .linked_folder
+                .as_deref()
+                .unwrap_or(variant.path.as_str()),
+        );
+        let central_path = central_dir.join(&name);
+        let imported = simulate_import(source, &central_dir, &central_path, selection.overwrite);
+        let failed = imported.is_err();
+        result.operations.push(SimulatedOperation {
+            source: Some(source.to_string_lossy().to_string()),
+            target: Some(relative(&central_path)),
+            error: imported.err(),
+            ..op(SimulatedOperationKind::Import)
+        });
+        if failed {
+            result.failed += 1;
+            continue;
+        }
+        result.adopted += 1;
+
+        for tool in &selection.tools {
+            let mode = adapters
+                .iter()
+                .find(|adapter| &adapter.key == tool)
+                .map_or(SyncMode::Auto, |adapter| adapter.sync_mode());
+            let target = sandbox.join("tools").join(tool).join(&name);
+            let synced = sync_dir_for_tool_with_overwrite(
+                &ctx,
+                &central_path,
+                &target,
+                selection.overwrite,
+                mode,
+            );
+            if synced.is_err() {
+                result.failed += 1;
+            }
+            result.operations.push(SimulatedOperation {
+                tool: Some(tool.clone()),
+                source: Some(relative(&central_path)),
+                target: Some(relative(&target)),
+                mode: synced
+                    .as_ref()
+                    .ok()
+                    .map(|outcome| outcome.mode_used.as_str().to_string()),
+                error: synced.err().map(|e| format!("{:#}", e)),
+                ..op(SimulatedOperationKind::Sync)
+            });
+        }
+    }
+    result
+}
+
+/// The file system checks and copy `install_local_skill` performs
+fn simulate_import(
+    source: &Path,
+    central_dir: &Path,
+    central_path: &Path,
+    overwrite: bool,
+) -> Result<(), String> {
+    if !source.exists() {
+        return Err(format!("source path not found: {:?}", source));
+    }
+    let name = central_path
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default();
+    ensure_no_case_collision(&name, &dir_entry_names(central_dir))?;
+    ensure_source_target_not_overlapping(source, central_path).map_err(|e| format!("{:#}", e))?;
+    if central_path.exists() {
+        if !overwrite {
+            return Err(format!("SKILL_EXISTS|{}", name));
+        }
+        std::fs::remove_dir_all(central_path)
+            .map_err(|e| format!("failed to remove existing skill: {:?}: {}", central_path, e))?;
+    }
+    copy_skill_dir(source, central_path)
+        .map_err(|e| format!("copy {:?} -> {:?}: {:#}", source, central_path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::types::{OnboardingGroup, OnboardingVariant};
+
+    fn variant(tool: &str, path: &Path) -> OnboardingVariant {
+        OnboardingVariant {
+            tool: tool.to_string(),
+            tool_display: tool.to_string(),
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path: path.to_string_lossy().to_string(),
+            fingerprint: None,
+            is_link: false,
+            link_target: None,
+            linked_folder: None,
+            conflicting_tools: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn simulation_records_imports_syncs_and_failures() {
+        let temp = tempfile::tempdir().unwrap();
+        let review = temp.path().join("claude/review");
+        std::fs::create_dir_all(&review).unwrap();
+        std::fs::write(review.join("SKILL.md"), "review").unwrap();
+        let group = |name: &str, variants| OnboardingGroup {
+            name: name.to_string(),
+            variants,
+            has_conflict: false,
+        };
+        let selection = AdoptionSelection {
+            plan: OnboardingPlan {
+                total_tools_scanned: 1,
+                total_skills_found: 3,
+                groups: vec![
+                    group("review", vec![variant("claude_code", &review)]),
+                    // Only differs by case from an imported skill
+                    group(
+                        "Review",
+                        vec![variant("codex", &temp.path().join("codex/Review"))],
+                    ),
+                    group(
+                        "gone",
+                        vec![variant("codex", &temp.path().join("codex/gone"))],
+                    ),
+                ],
+                previous_scan_at: None,
+                new_since_last_scan: Vec::new(),
+                rehashed_dirs: 0,
+                system_skills: Vec::new(),
+            },
+            policy: VariantPolicy::NewestMtime,
+            names: None,
+            tools: vec!["cursor".to_string()],
+            overwrite: false,
+        };
+        std::fs::create_dir_all(temp.path().join("codex/Review")).unwrap();
+
+        let sandbox = temp.path().join("sandbox");
+        let result = simulate_adoption_in(&sandbox, &selection, &[]);
+        assert_eq!(result.adopted, 1);
+        assert_eq!(result.failed, 2);
+        let kinds: Vec<(SimulatedOperationKind, bool)> = result
+            .operations
+            .iter()
+            .map(|op| (op.kind, op.error.is_none()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (SimulatedOperationKind::Pick, true),
+                (SimulatedOperationKind::Import, true),
+                (SimulatedOperationKind::Sync, true),
+                (SimulatedOperationKind::Pick, true),
+                (SimulatedOperationKind::Import, false),
+                (SimulatedOperationKind::Pick, true),
+                (SimulatedOperationKind::Import, false),
+            ]
+        );
+        let sync = &result.operations[2];
+        assert_eq!(sync.target.as_deref(), Some("tools/cursor/review"));
+        assert_eq!(sync.mode.as_deref(), Some("copy"));
+        assert!(result.operations[4]
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("NAME_CASE_COLLISION|"));
+        // Variant directories are only read
+        assert!(review.join("SKILL.md").is_file());
+    }
+}
@@ -0,0 +1,99 @@
+//! Cold storage for rarely-used skills
+//!
+//! Archiving packs the skill's central directory into
+//! `{app_data_dir}/skill-archives/{skill_id}/content.tar.gz`, removes its
+//! targets and central directory, and marks the record `archived`. A plain copy
+//! of SKILL.md is kept next to the archive so the library can still show the
+//! description. Unarchiving unpacks back into the central repo.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tauri::{AppHandle, Manager, Runtime};
+
+pub const ARCHIVED_STATUS: &str = "archived";
+
+const ARCHIVES_DIR_NAME: &str = "skill-archives";
+const CONTENT_FILE_NAME: &str = "content.tar.gz";
+const SKILL_MD: &str = "SKILL.md";
+
+pub fn archive_dir<R: Runtime>(app: &AppHandle<R>, skill_id: &str) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(ARCHIVES_DIR_NAME).join(skill_id))
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+}
+
+/// Pack `source` into `archive_dir` and copy its SKILL.md alongside
+pub fn pack_skill(source: &Path, archive_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(archive_dir)
+        .with_context(|| format!("create archive dir {:?}", archive_dir))?;
+    let content = archive_dir.join(CONTENT_FILE_NAME);
+    let tmp = content.with_extension("partial");
+    {
+        let file = File::create(&tmp).with_context(|| format!("create {:?}", tmp))?;
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        // Store links as links; following them could pull in files outside the skill
+        builder.follow_symlinks(false);
+        builder
+            .append_dir_all(".", source)
+            .with_context(|| format!("pack {:?}", source))?;
+        builder.into_inner()?.finish()?;
+    }
+    std::fs::rename(&tmp, &content).with_context(|| format!("finalize {:?}", content))?;
+
+    let skill_md = source.join(SKILL_MD);
+    if skill_md.is_file() {
+        std::fs::copy(&skill_md, archive_dir.join(SKILL_MD))
+            .with_context(|| format!("copy {:?}", skill_md))?;
+    }
+    Ok(())
+}
+
+/// Unpack an archive into `target`, which must not exist yet
+pub fn unpack_skill(archive_dir: &Path, target: &Path) -> Result<()> {
+    if target.exists() {
+        anyhow::bail!("target already exists: {:?}", target);
+    }
+    let content = archive_dir.join(CONTENT_FILE_NAME);
+    let file = File::open(&content).with_context(|| format!("open {:?}", content))?;
+    std::fs::create_dir_all(target).with_context(|| format!("create {:?}", target))?;
+    // `unpack` skips entries that would escape the target directory
+    if let Err(err) = tar::Archive::new(GzDecoder::new(file)).unpack(target) {
+        let _ = std::fs::remove_dir_all(target);
+        return Err(err).with_context(|| format!("unpack {:?}", content));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::content_hash::hash_dir;
+
+    #[test]
+    fn pack_and_unpack_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("demo");
+        std::fs::create_dir_all(source.join("scripts")).unwrap();
+        std::fs::write(source.join(SKILL_MD), "---\nname: demo\n---\nbody").unwrap();
+        std::fs::write(source.join("scripts/run.sh"), "echo hi").unwrap();
+
+        let archive = temp.path().join("archive");
+        pack_skill(&source, &archive).unwrap();
+        assert!(archive.join(SKILL_MD).is_file());
+
+        let restored = temp.path().join("restored");
+        unpack_skill(&archive, &restored).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(restored.join("scripts/run.sh")).unwrap(),
+            "echo hi"
+        );
+        assert_eq!(hash_dir(&source).unwrap(), hash_dir(&restored).unwrap());
+        assert!(unpack_skill(&archive, &restored).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! Commands for archiving skills to cold storage (`archive.rs`)
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::archive::{self, ARCHIVED_STATUS};
+use super::commands::{
+    disabled_previous_tools_for_skill, format_error, remove_skill_targets_best_effort,
+    resolve_skill_source_path,
+};
+use super::skill_store;
+use super::sync_engine::remove_path;
+use super::write_protect;
+use crate::settings::policy;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Move a skill to cold storage: pack its central directory into the app data
+/// area, remove its targets and central copy, and mark it archived.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_archive_skill<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<(), String> {
+    ensure_writable("skills_archive_skill")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    policy::ensure_skill_removable(&skill.name)?;
+    if skill.status == ARCHIVED_STATUS {
+        return Err(format!("SKILL_ARCHIVED|{}", skillId));
+    }
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    if !source_path.is_dir() {
+        return Err(format!(
+            "Skill source path is missing or not a directory: {}",
+            source_path.display()
+        ));
+    }
+    let archive_dir = archive::archive_dir(&app, &skill.id)?;
+    // The archive keeps writable permissions; unarchiving protects again
+    write_protect::unlock(&source_path, skill.write_protected).map_err(format_error)?;
+    if let Err(e) = archive::pack_skill(&source_path, &archive_dir) {
+        write_protect::protect_or_warn(&source_path, skill.write_protected);
+        return Err(format!("Failed to archive {}: {:#}", skill.name, e));
+    }
+
+    let previous_tools = disabled_previous_tools_for_skill(&skill);
+    remove_skill_targets_best_effort(&state, &skill, Some(&source_path)).await?;
+    skill_store::archive_skill_record(&state, &skillId, previous_tools).await?;
+    remove_path(&source_path.to_string_lossy())?;
+
+    let _ = app.emit("skills-changed", "window");
+    Ok(())
+}
+
+/// Restore an archived skill into the central repo. Returns the tools it was
+/// synced to before archiving so the caller can re-sync them.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_unarchive_skill<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<Vec<String>, String> {
+    ensure_writable("skills_unarchive_skill")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    if skill.status != ARCHIVED_STATUS {
+        return Err(format!("Skill is not archived: {}", skill.name));
+    }
+    let target = resolve_skill_source_path(&app, &state, &skill).await?;
+    let archive_dir = archive::archive_dir(&app, &skill.id)?;
+    archive::unpack_skill(&archive_dir, &target)
+        .map_err(|e| format!("Failed to restore {}: {:#}", skill.name, e))?;
+    write_protect::protect_or_warn(&target, skill.write_protected);
+
+    let previous_tools = skill_store::unarchive_skill_record(&state, &skillId).await?;
+    remove_path(&archive_dir.to_string_lossy())?;
+
+    let _ = app.emit("skills-changed", "window");
+    Ok(previous_tools)
+}
@@ -0,0 +1,369 @@
+//! Archive (zip / tarball) skill sources
+//!
+//! Downloads an archive from a URL, verifies an optional SHA-256 checksum and
+//! extracts it into a staging directory. Extraction rejects entries that
+//! would escape the destination (zip-slip) and skips symlinks / hardlinks.
+//! Skills installed this way use `source_type = "archive"`, keep the URL in
+//! `source_ref` and the archive checksum (`sha256:<hex>`) in
+//! `source_revision`, so updates can re-download and detect changes. An
+//! update only adopts a new archive whose checksum is known up front: the one
+//! configured in the source mirror policy, else the `<url>.sha256` file
+//! published next to the archive.
+
+use std::io::{Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::http_client;
+use crate::SqliteDbState;
+
+pub const ARCHIVE_SOURCE_TYPE: &str = "archive";
+const DOWNLOAD_TIMEOUT_SECS: u64 = 120;
+/// Upper bound for downloaded and extracted bytes
+const MAX_ARCHIVE_BYTES: u64 = 200 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    TarGz,
+    Tar,
+}
+
+/// Detect the archive format from magic bytes, falling back to the URL suffix
+pub fn detect_archive_kind(url: &str, bytes: &[u8]) -> Option<ArchiveKind> {
+    if bytes.starts_with(b"PK\x03\x04") {
+        return Some(ArchiveKind::Zip);
+    }
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        return Some(ArchiveKind::TarGz);
+    }
+    if bytes.len() > 262 && &bytes[257..262] == b"ustar" {
+        return Some(ArchiveKind::Tar);
+    }
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    if path.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if path.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else {
+        None
+    }
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Normalize `sha256:<hex>` / `<hex>` to lowercase hex
+pub fn normalize_checksum(checksum: &str) -> String {
+    let trimmed = checksum.trim();
+    let hex = trimmed
+        .strip_prefix("sha256:")
+        .or_else(|| trimmed.strip_prefix("SHA256:"))
+        .unwrap_or(trimmed);
+    hex.trim().to_lowercase()
+}
+
+/// Verify `bytes` against an optional expected checksum.
+/// Returns the actual checksum as `sha256:<hex>`.
+pub fn verify_checksum(bytes: &[u8], expected: Option<&str>) -> Result<String> {
+    let actual = sha256_hex(bytes);
+    if let Some(expected) = expected.map(normalize_checksum).filter(|v| !v.is_empty()) {
+        if expected != actual {
+            anyhow::bail!("CHECKSUM_MISMATCH|{}|{}", expected, actual);
+        }
+    }
+    Ok(format!("sha256:{}", actual))
+}
+
+/// URL of the checksum file published next to an archive: `<url>.sha256`,
+/// keeping any query string
+pub fn published_checksum_url(url: &str) -> String {
+    let (path, rest) = url
+        .find(['?', '#'])
+        .map_or((url, ""), |index| url.split_at(index));
+    format!("{}.sha256{}", path, rest)
+}
+
+/// The checksum in a published `.sha256` file: bare hex or `sha256sum`
+/// output (`<hex>  <file>`)
+pub fn parse_published_checksum(text: &str) -> Option<String> {
+    let hex = normalize_checksum(text.split_whitespace().next()?);
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then_some(hex)
+}
+
+/// Fetch the checksum published next to the archive at `url`, from the
+/// upstream host. Fails with `CHECKSUM_REQUIRED|url` when there is none.
+pub async fn fetch_published_checksum(state: &SqliteDbState, url: &str) -> Result<String> {
+    let required = || anyhow::anyhow!("CHECKSUM_REQUIRED|{}", url);
+    let client = http_client::client_with_timeout(state, DOWNLOAD_TIMEOUT_SECS)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let response = client
+        .get(published_checksum_url(url))
+        .send()
+        .await
+        .map_err(|_| required())?;
+    if !response.status().is_success() {
+        return Err(required());
+    }
+    let text = response.text().await.map_err(|_| required())?;
+    parse_published_checksum(&text).ok_or_else(required)
+}
+
+pub async fn download_archive(state: &SqliteDbState, url: &str) -> Result<Vec<u8>> {
+    let client = http_client::client_with_timeout(state, DOWNLOAD_TIMEOUT_SECS)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("failed to download {}", url))?;
+    if !response.status().is_success() {
+        anyhow::bail!("failed to download {}: HTTP {}", url, response.status());
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_ARCHIVE_BYTES)
+    {
+        anyhow::bail!("archive is larger than {} bytes", MAX_ARCHIVE_BYTES);
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .with_context(|| format!("failed to read {}", url))?;
+    if bytes.len() as u64 > MAX_ARCHIVE_BYTES {
+        anyhow::bail!("archive is larger than {} bytes", MAX_ARCHIVE_BYTES);
+    }
+    Ok(bytes.to_vec())
+}
+
+/// Relative path of an archive entry, or `None` when it is absolute or
+/// contains `..` (zip-slip).
+pub fn safe_entry_path(name: &Path) -> Option<PathBuf> {
+    let mut out = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    if out.as_os_str().is_empty() {
+        None
+    } else {
+        Some(out)
+    }
+}
+
+fn write_entry(
+    dest: &Path,
+    relative: &Path,
+    reader: &mut dyn Read,
+    written: &mut u64,
+) -> Result<()> {
+    let target = dest.join(relative);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {:?}", parent))?;
+    }
+    let mut file =
+        std::fs::File::create(&target).with_context(|| format!("failed to create {:?}", target))?;
+    let budget = MAX_ARCHIVE_BYTES.saturating_sub(*written);
+    let copied = std::io::copy(&mut reader.take(budget + 1), &mut file)
+        .with_context(|| format!("failed to write {:?}", target))?;
+    *written += copied;
+    if *written > MAX_ARCHIVE_BYTES {
+        anyhow::bail!("archive expands to more than {} bytes", MAX_ARCHIVE_BYTES);
+    }
+    Ok(())
+}
+
+fn extract_zip(bytes: &[u8], dest: &Path) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).context("invalid zip archive")?;
+    let mut written = 0u64;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).context("invalid zip entry")?;
+        let relative = entry
+            .enclosed_name()
+            .as_deref()
+            .and_then(safe_entry_path)
+            .ok_or_else(|| anyhow::anyhow!("unsafe path in archive: {}", entry.name()))?;
+        if entry.is_dir() {
+            std::fs::create_dir_all(dest.join(&relative))?;
+        } else if entry.is_symlink() {
+            continue;
+        } else {
+            write_entry(dest, &relative, &mut entry, &mut written)?;
+        }
+    }
+    Ok(())
+}
+
+fn extract_tar<R: Read>(reader: R, dest: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    let mut written = 0u64;
+    for entry in archive.entries().context("invalid tar archive")? {
+        let mut entry = entry.context("invalid tar entry")?;
+        let path = entry.path().context("invalid tar entry path")?.into_owned();
+        let relative = safe_entry_path(&path)
+            .ok_or_else(|| anyhow::anyhow!("unsafe path in archive: {}", path.display()))?;
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            std::fs::create_dir_all(dest.join(&relative))?;
+        } else if entry_type.is_file() {
+            write_entry(dest, &relative, &mut entry, &mut written)?;
+        }
+        // Symlinks, hardlinks and special files are skipped
+    }
+    Ok(())
+}
+
+/// Extract `bytes` into `dest` (created if missing)
+pub fn extract_archive(kind: ArchiveKind, bytes: &[u8], dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest).with_context(|| format!("failed to create {:?}", dest))?;
+    match kind {
+        ArchiveKind::Zip => extract_zip(bytes, dest),
+        ArchiveKind::TarGz => extract_tar(flate2::read::GzDecoder::new(bytes), dest),
+        ArchiveKind::Tar => extract_tar(bytes, dest),
+    }
+}
+
+/// Archives from GitHub & co. wrap everything in one top-level directory;
+/// descend into it when it is the only entry.
+pub fn unwrap_single_root(dir: &Path) -> PathBuf {
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    match entries.as_slice() {
+        [only] if only.is_dir() && !dir.join("SKILL.md").exists() => only.clone(),
+        _ => dir.to_path_buf(),
+    }
+}
+
+/// Skill name fallback derived from the archive file name
+pub fn derive_name_from_archive_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let file = path.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+    let stem = [".tar.gz", ".tgz", ".zip", ".tar"]
+        .iter()
+        .find_map(|suffix| {
+            file.to_lowercase()
+                .ends_with(suffix)
+                .then(|| &file[..file.len() - suffix.len()])
+        })
+        .unwrap_or(file);
+    if stem.is_empty() {
+        "skill".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn safe_entry_path_rejects_escapes() {
+        assert_eq!(
+            safe_entry_path(Path::new("skill/./SKILL.md")),
+            Some(PathBuf::from("skill/SKILL.md"))
+        );
+        assert!(safe_entry_path(Path::new("../evil")).is_none());
+        assert!(safe_entry_path(Path::new("skill/../../evil")).is_none());
+        assert!(safe_entry_path(Path::new("/etc/passwd")).is_none());
+    }
+
+    #[test]
+    fn checksum_accepts_prefixed_and_bare_hex() {
+        let bytes = b"hello";
+        let hex = sha256_hex(bytes);
+        assert_eq!(
+            verify_checksum(bytes, Some(&format!("sha256:{}", hex.to_uppercase()))).unwrap(),
+            format!("sha256:{}", hex)
+        );
+        assert!(verify_checksum(bytes, Some(&hex)).is_ok());
+        assert!(verify_checksum(bytes, None).is_ok());
+        let error = verify_checksum(bytes, Some("deadbeef")).unwrap_err();
+        assert!(error.to_string().starts_with("CHECKSUM_MISMATCH|deadbeef|"));
+    }
+
+    #[test]
+    fn published_checksum_sits_next_to_the_archive() {
+        assert_eq!(
+            published_checksum_url("https://example.com/dl/pdf-tools.zip?token=1"),
+            "https://example.com/dl/pdf-tools.zip.sha256?token=1"
+        );
+        assert_eq!(
+            published_checksum_url("https://example.com/a.tar.gz"),
+            "https://example.com/a.tar.gz.sha256"
+        );
+
+        let hex = sha256_hex(b"hello");
+        assert_eq!(
+            parse_published_checksum(&format!("{}\n", hex)),
+            Some(hex.clone())
+        );
+        assert_eq!(
+            parse_published_checksum(&format!("{}  pdf-tools.zip\n", hex.to_uppercase())),
+            Some(hex.clone())
+        );
+        assert_eq!(
+            parse_published_checksum(&format!("sha256:{}", hex)),
+            Some(hex)
+        );
+        assert_eq!(parse_published_checksum("<html>Not Found</html>"), None);
+        assert_eq!(parse_published_checksum(""), None);
+    }
+
+    #[test]
+    fn zip_extracts_and_unwraps_root_dir() {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut writer = zip::ZipWriter::new(&mut buffer);
+            let options = zip::write::SimpleFileOptions::default();
+            writer
+                .start_file("my-skill-main/SKILL.md", options)
+                .unwrap();
+            writer.write_all(b"---\nname: my-skill\n---\n").unwrap();
+            writer.finish().unwrap();
+        }
+        let bytes = buffer.into_inner();
+        assert_eq!(
+            detect_archive_kind("https://x/y", &bytes),
+            Some(ArchiveKind::Zip)
+        );
+
+        let temp = tempfile::tempdir().unwrap();
+        extract_archive(ArchiveKind::Zip, &bytes, temp.path()).unwrap();
+        let root = unwrap_single_root(temp.path());
+        assert!(root.ends_with("my-skill-main"));
+        assert!(root.join("SKILL.md").exists());
+    }
+
+    #[test]
+    fn archive_name_falls_back_to_file_stem() {
+        assert_eq!(
+            derive_name_from_archive_url("https://example.com/dl/pdf-tools.tar.gz?token=1"),
+            "pdf-tools"
+        );
+        assert_eq!(
+            derive_name_from_archive_url("https://example.com/a.zip"),
+            "a"
+        );
+        assert_eq!(
+            derive_name_from_archive_url("https://example.com/"),
+            "example.com"
+        );
+    }
+}
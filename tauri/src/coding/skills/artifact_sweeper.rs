@@ -0,0 +1,242 @@
+//! Stale tool artifact sweeper
+//!
+//! After a tool is uninstalled (or its skills dir moved), links created by
+//! the app can linger in the old dotfolder. The sweeper scans every skills
+//! directory the app has known about — all built-in and custom adapters
+//! (a superset of the tools recorded in `known_tool_versions` /
+//! `installed_tools`) plus the parent dirs of every target recorded in
+//! `sync_details`, which keeps paths of removed custom tools and moved
+//! skills dirs reachable — for symlinks / junctions pointing into the
+//! central repo or the variant store. A link is stale when its target is gone
+//! (`dangling`) or its tool is no longer installed (`tool_not_installed`).
+//! Plain directories and links pointing elsewhere are never reported.
+//!
+//! `find_dangling_links` is the owner-agnostic variant used by the tools
+//! overview: any broken link in a tool's skills dir (e.g. left by another
+//! skills manager), since some CLIs warn or crash on them.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::adapter::parse_sync_details;
+use super::tool_adapters::{
+    get_all_tool_adapters, is_tool_installed_with_state_async, resolve_runtime_skills_path_async,
+    resolve_runtime_skills_path_with_state_async, CustomTool,
+};
+use super::types::{now_ms, Skill};
+use crate::coding::runtime_location;
+use crate::SqliteDbState;
+
+pub const REASON_DANGLING: &str = "dangling";
+pub const REASON_TOOL_NOT_INSTALLED: &str = "tool_not_installed";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StaleArtifactDto {
+    pub tool: String,
+    /// Link path inside the tool's skills dir
+    pub path: String,
+    /// Where the link points (may no longer exist)
+    pub link_target: String,
+    /// `dangling` | `tool_not_installed`
+    pub reason: String,
+    /// Skill whose `sync_details` still records this path
+    pub skill_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StaleArtifactReportDto {
+    pub artifacts: Vec<StaleArtifactDto>,
+    pub scanned_dirs: Vec<String>,
+    pub checked_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SweepResultDto {
+    pub removed: Vec<String>,
+    /// Requested paths that failed or were no longer stale
+    pub skipped: Vec<String>,
+}
+
+/// Skills dirs to scan: dir -> tool key
+async fn candidate_dirs(
+    state: &SqliteDbState,
+    custom_tools: &[CustomTool],
+    skills: &[Skill],
+) -> BTreeMap<PathBuf, String> {
+    let mut dirs = BTreeMap::new();
+    for adapter in get_all_tool_adapters(custom_tools) {
+        // Both the default and the user-overridden path, so links left in
+        // the default dir after a path change are found too
+        if let Ok(path) = resolve_runtime_skills_path_async(&adapter).await {
+            dirs.entry(path).or_insert_with(|| adapter.key.clone());
+        }
+        if let Ok(path) = resolve_runtime_skills_path_with_state_async(state, &adapter).await {
+            dirs.entry(path).or_insert_with(|| adapter.key.clone());
+        }
+    }
+
+    for skill in skills {
+        for target in parse_sync_details(skill) {
+            if runtime_location::parse_wsl_unc_path(&target.target_path).is_some() {
+                continue;
+            }
+            if let Some(parent) = Path::new(&target.target_path).parent() {
+                dirs.entry(parent.to_path_buf())
+                    .or_insert_with(|| target.tool.clone());
+            }
+        }
+    }
+    dirs
+}
+
+fn absolute_link_target(link: &Path, target: PathBuf) -> PathBuf {
+    if target.is_absolute() {
+        return target;
+    }
+    link.parent()
+        .map(|parent| parent.join(&target))
+        .unwrap_or(target)
+}
+
+/// Every symlink / junction directly inside `dir` whose target is missing
+pub fn find_dangling_links(dir: &Path) -> Vec<PathBuf> {
+    let mut out: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            std::fs::read_link(path)
+                .map(|target| !absolute_link_target(path, target).exists())
+                .unwrap_or(false)
+        })
+        .collect();
+    out.sort();
+    out
+}
+
+/// Scan one skills dir for app-owned links that are stale
+pub fn scan_dir_for_stale_links(
+    tool: &str,
+    dir: &Path,
+    owned_roots: &[PathBuf],
+    tool_installed: bool,
+) -> Vec<StaleArtifactDto> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        // read_link succeeds for symlinks and Windows junctions only
+        let Ok(raw_target) = std::fs::read_link(&path) else {
+            continue;
+        };
+        let link_target = absolute_link_target(&path, raw_target);
+        if !owned_roots.iter().any(|root| link_target.starts_with(root)) {
+            continue;
+        }
+        let reason = if !link_target.exists() {
+            REASON_DANGLING
+        } else if !tool_installed {
+            REASON_TOOL_NOT_INSTALLED
+        } else {
+            continue;
+        };
+        out.push(StaleArtifactDto {
+            tool: tool.to_string(),
+            path: path.to_string_lossy().to_string(),
+            link_target: link_target.to_string_lossy().to_string(),
+            reason: reason.to_string(),
+            skill_id: None,
+        });
+    }
+    out
+}
+
+pub async fn build_stale_artifact_report(
+    state: &SqliteDbState,
+    custom_tools: &[CustomTool],
+    skills: &[Skill],
+    owned_roots: &[PathBuf],
+) -> StaleArtifactReportDto {
+    let adapters = get_all_tool_adapters(custom_tools);
+    // target path -> (skill id, tool) as recorded in sync_details
+    let recorded: BTreeMap<String, (String, String)> = skills
+        .iter()
+        .flat_map(|skill| {
+            parse_sync_details(skill)
+                .into_iter()
+                .map(move |target| (target.target_path, (skill.id.clone(), target.tool)))
+        })
+        .collect();
+
+    let mut report = StaleArtifactReportDto {
+        checked_at: now_ms(),
+        ..Default::default()
+    };
+    for (dir, tool) in candidate_dirs(state, custom_tools, skills).await {
+        if !dir.is_dir() {
+            continue;
+        }
+        // Tools without an adapter (e.g. deleted custom tools) count as uninstalled
+        let installed = match adapters.iter().find(|adapter| adapter.key == tool) {
+            Some(adapter) => is_tool_installed_with_state_async(state, adapter)
+                .await
+                .unwrap_or(false),
+            None => false,
+        };
+        for mut artifact in scan_dir_for_stale_links(&tool, &dir, owned_roots, installed) {
+            if let Some((skill_id, tool)) = recorded.get(&artifact.path) {
+                artifact.skill_id = Some(skill_id.clone());
+                artifact.tool = tool.clone();
+            }
+            report.artifacts.push(artifact);
+        }
+        report.scanned_dirs.push(dir.to_string_lossy().to_string());
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn only_owned_stale_links_are_reported() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let elsewhere = temp.path().join("elsewhere");
+        let tool_dir = temp.path().join("tool");
+        for dir in [&central, &elsewhere, &tool_dir] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::create_dir_all(central.join("alive")).unwrap();
+        std::fs::create_dir_all(elsewhere.join("foreign")).unwrap();
+        std::os::unix::fs::symlink(central.join("alive"), tool_dir.join("alive")).unwrap();
+        std::os::unix::fs::symlink(central.join("gone"), tool_dir.join("gone")).unwrap();
+        std::os::unix::fs::symlink(elsewhere.join("missing"), tool_dir.join("foreign")).unwrap();
+        std::fs::create_dir_all(tool_dir.join("plain")).unwrap();
+
+        let roots = vec![central.clone()];
+        let installed = scan_dir_for_stale_links("t", &tool_dir, &roots, true);
+        assert_eq!(installed.len(), 1);
+        assert!(installed[0].path.ends_with("gone"));
+        assert_eq!(installed[0].reason, REASON_DANGLING);
+
+        // Owner-agnostic scan also reports the foreign broken link
+        let dangling = find_dangling_links(&tool_dir);
+        assert_eq!(
+            dangling,
+            vec![tool_dir.join("foreign"), tool_dir.join("gone")]
+        );
+
+        let mut uninstalled = scan_dir_for_stale_links("t", &tool_dir, &roots, false);
+        uninstalled.sort_by(|a, b| a.path.cmp(&b.path));
+        let reasons: Vec<_> = uninstalled
+            .iter()
+            .map(|artifact| artifact.reason.as_str())
+            .collect();
+        assert_eq!(reasons, vec![REASON_TOOL_NOT_INSTALLED, REASON_DANGLING]);
+    }
+}
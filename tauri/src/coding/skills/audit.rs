@@ -0,0 +1,417 @@
+//! Library consistency audit
+//!
+//! Cross-checks the skill records, the central repo directories and the tool
+//! targets recorded in `sync_details` in one pass. Every inconsistency is
+//! reported as a `LibraryAuditIssue` carrying the fix that resolves it, so the
+//! report can be reviewed, filtered and sent back as-is to apply fixes:
+//! - `ghost_skill`: record whose central directory is gone (archived skills
+//!   are expected to have none) -> `delete_record`
+//! - `orphan_dir`: skill directory in the central repo no record points at
+//!   -> `adopt_dir`
+//! - `duplicate_target`: several skills claim the same target path; the
+//!   owner is the one the link resolves to, else the latest sync -> `drop_target`
+//! - `mismatched_mode`: recorded mode disagrees with the disk (link vs copy)
+//!   -> `record_mode`
+//! - `missing_target`: recorded target no longer exists -> `drop_target`
+//!
+//! WSL targets are only checked for duplicates; their disk state is not probed.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::adapter::parse_sync_details;
+use super::archive::ARCHIVED_STATUS;
+use super::central_repo::resolve_skill_central_path;
+use super::types::{DetectedCentralSkillDto, Skill, SkillTarget};
+use crate::coding::runtime_location;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditIssueKind {
+    GhostSkill,
+    OrphanDir,
+    DuplicateTarget,
+    MismatchedMode,
+    MissingTarget,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditFixAction {
+    /// Delete the skill record, its dangling links, variants and memberships
+    DeleteRecord,
+    /// Adopt the directory as a `central` skill
+    AdoptDir,
+    /// Remove the target row; the path on disk is left alone
+    DropTarget,
+    /// Record the mode found on disk
+    RecordMode,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LibraryAuditIssue {
+    pub kind: AuditIssueKind,
+    pub fix: AuditFixAction,
+    pub skill_id: Option<String>,
+    pub skill_name: Option<String>,
+    pub tool: Option<String>,
+    /// Central dir (relative path for `orphan_dir`) or target path
+    pub path: String,
+    pub detail: String,
+}
+
+impl LibraryAuditIssue {
+    /// Same inconsistency, ignoring the human-readable detail
+    pub fn same_issue(&self, other: &LibraryAuditIssue) -> bool {
+        self.kind == other.kind
+            && self.fix == other.fix
+            && self.skill_id == other.skill_id
+            && self.tool == other.tool
+            && self.path == other.path
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LibraryAuditDto {
+    pub central_path: String,
+    pub checked_skills: usize,
+    pub checked_targets: usize,
+    pub issues: Vec<LibraryAuditIssue>,
+    pub checked_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LibraryAuditFixResultDto {
+    pub fixed: Vec<LibraryAuditIssue>,
+    /// Requested issues no longer reported by a fresh audit
+    pub skipped: Vec<LibraryAuditIssue>,
+    pub errors: Vec<String>,
+}
+
+/// Mode of a target as found on disk; `None` when the path is missing
+pub fn target_disk_mode(path: &Path) -> Option<&'static str> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    if metadata.file_type().is_symlink() {
+        return Some("symlink");
+    }
+
+    #[cfg(windows)]
+    if junction::exists(path).unwrap_or(false) {
+        return Some("junction");
+    }
+
+    metadata.is_dir().then_some("copy")
+}
+
+fn is_link_mode(mode: &str) -> bool {
+    matches!(mode, "symlink" | "junction")
+}
+
+fn target_key(target_path: &str) -> String {
+    target_path.trim().to_ascii_lowercase()
+}
+
+/// Whether the link at `target` resolves to `central_path`
+fn links_to(target: &Path, central_path: &Path) -> bool {
+    match (
+        std::fs::canonicalize(target),
+        std::fs::canonicalize(central_path),
+    ) {
+        (Ok(left), Ok(right)) => left == right,
+        _ => false,
+    }
+}
+
+fn issue(
+    kind: AuditIssueKind,
+    fix: AuditFixAction,
+    skill: Option<&Skill>,
+    tool: Option<&str>,
+    path: String,
+    detail: String,
+) -> LibraryAuditIssue {
+    LibraryAuditIssue {
+        kind,
+        fix,
+        skill_id: skill.map(|skill| skill.id.clone()),
+        skill_name: skill.map(|skill| skill.name.clone()),
+        tool: tool.map(str::to_string),
+        path,
+        detail,
+    }
+}
+
+/// Audit `skills` against the central repo at `central_dir`, whose skill
+/// directories were found by a scan (`detected`).
+pub fn audit_library(
+    skills: &[Skill],
+    central_dir: &Path,
+    detected: &[DetectedCentralSkillDto],
+    checked_at: i64,
+) -> LibraryAuditDto {
+    let mut issues = Vec::new();
+    let central_paths: BTreeMap<&str, PathBuf> = skills
+        .iter()
+        .map(|skill| {
+            (
+                skill.id.as_str(),
+                resolve_skill_central_path(&skill.central_path, central_dir),
+            )
+        })
+        .collect();
+
+    for skill in skills {
+        let central_path = &central_paths[skill.id.as_str()];
+        if skill.status != ARCHIVED_STATUS && !central_path.is_dir() {
+            issues.push(issue(
+                AuditIssueKind::GhostSkill,
+                AuditFixAction::DeleteRecord,
+                Some(skill),
+                None,
+                central_path.to_string_lossy().to_string(),
+                "central directory is missing".to_string(),
+            ));
+        }
+    }
+
+    for dir in detected {
+        let path = central_dir.join(&dir.relative_path);
+        if !central_paths
+            .values()
+            .any(|central_path| *central_path == path)
+        {
+            issues.push(issue(
+                AuditIssueKind::OrphanDir,
+                AuditFixAction::AdoptDir,
+                None,
+                None,
+                dir.relative_path.clone(),
+                format!("no record points at skill '{}'", dir.name),
+            ));
+        }
+    }
+
+    let mut checked_targets = 0;
+    let mut by_path: BTreeMap<String, Vec<(&Skill, SkillTarget)>> = BTreeMap::new();
+    for skill in skills {
+        for target in parse_sync_details(skill) {
+            if target.target_path.trim().is_empty() {
+                continue;
+            }
+            checked_targets += 1;
+            by_path
+                .entry(target_key(&target.target_path))
+                .or_default()
+                .push((skill, target));
+        }
+    }
+
+    for entries in by_path.values() {
+        let path = Path::new(&entries[0].1.target_path);
+        let is_wsl = runtime_location::parse_wsl_unc_path(&entries[0].1.target_path).is_some();
+        let disk_mode = if is_wsl { None } else { target_disk_mode(path) };
+
+        let mut owners = entries
+            .iter()
+            .map(|(skill, _)| skill.id.as_str())
+            .collect::<Vec<_>>();
+        owners.dedup();
+        let owner_id = if owners.len() > 1 {
+            let linked = entries.iter().find(|(skill, _)| {
+                disk_mode.is_some_and(is_link_mode)
+                    && links_to(path, &central_paths[skill.id.as_str()])
+            });
+            let latest = entries
+                .iter()
+                .max_by_key(|(_, target)| target.synced_at.unwrap_or_default());
+            linked.or(latest).map(|(skill, _)| skill.id.as_str())
+        } else {
+            None
+        };
+
+        for (skill, target) in entries {
+            if owner_id.is_some_and(|owner| owner != skill.id) {
+                issues.push(issue(
+                    AuditIssueKind::DuplicateTarget,
+                    AuditFixAction::DropTarget,
+                    Some(skill),
+                    Some(&target.tool),
+                    target.target_path.clone(),
+                    format!("target is owned by skill {}", owner_id.unwrap_or_default()),
+                ));
+                continue;
+            }
+            if is_wsl {
+                continue;
+            }
+            match disk_mode {
+                None => issues.push(issue(
+                    AuditIssueKind::MissingTarget,
+                    AuditFixAction::DropTarget,
+                    Some(skill),
+                    Some(&target.tool),
+                    target.target_path.clone(),
+                    "target path does not exist".to_string(),
+                )),
+                Some(actual) if is_link_mode(actual) != is_link_mode(&target.mode) => {
+                    issues.push(issue(
+                        AuditIssueKind::MismatchedMode,
+                        AuditFixAction::RecordMode,
+                        Some(skill),
+                        Some(&target.tool),
+                        target.target_path.clone(),
+                        format!("recorded {}, found {}", target.mode, actual),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    LibraryAuditDto {
+        central_path: central_dir.to_string_lossy().to_string(),
+        checked_skills: skills.len(),
+        checked_targets,
+        issues,
+        checked_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+
+    fn skill(id: &str, central_path: &str, targets: &[(&str, &Path, &str, i64)]) -> Skill {
+        let sync_details = targets
+            .iter()
+            .map(|(tool, path, mode, synced_at)| {
+                (
+                    tool.to_string(),
+                    serde_json::json!({
+                        "target_path": path.to_string_lossy(),
+                        "mode": mode,
+                        "status": "ok",
+                        "synced_at": synced_at,
+                    }),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        from_db_skill(serde_json::json!({
+            "id": id,
+            "name": id,
+            "source_type": "central",
+            "central_path": central_path,
+            "status": "ok",
+            "sync_details": sync_details,
+        }))
+    }
+
+    fn detected(relative_path: &str) -> DetectedCentralSkillDto {
+        DetectedCentralSkillDto {
+            name: relative_path.to_string(),
+            description: None,
+            relative_path: relative_path.to_string(),
+            absolute_path: String::new(),
+            content_hash: None,
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn audit_categorizes_every_inconsistency() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let tools = temp.path().join("tools");
+        for dir in ["alpha", "beta", "orphan"] {
+            std::fs::create_dir_all(central.join(dir)).unwrap();
+        }
+        std::fs::create_dir_all(&tools).unwrap();
+        let linked = tools.join("alpha");
+        std::os::unix::fs::symlink(central.join("alpha"), &linked).unwrap();
+        let copied = tools.join("beta");
+        std::fs::create_dir_all(&copied).unwrap();
+        let missing = tools.join("gone");
+
+        let mut archived = skill("archived", "archived", &[]);
+        archived.status = ARCHIVED_STATUS.to_string();
+        let skills = vec![
+            skill(
+                "alpha",
+                "alpha",
+                &[
+                    ("claude", &linked, "symlink", 1),
+                    ("codex", &missing, "copy", 1),
+                ],
+            ),
+            // Claims alpha's link with a newer sync, and records beta's copy as a link
+            skill(
+                "beta",
+                "beta",
+                &[
+                    ("claude", &linked, "symlink", 9),
+                    ("codex", &copied, "symlink", 1),
+                ],
+            ),
+            skill("ghost", "ghost", &[]),
+            archived,
+        ];
+        let report = audit_library(
+            &skills,
+            &central,
+            &[detected("alpha"), detected("beta"), detected("orphan")],
+            0,
+        );
+
+        let found = report
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.kind,
+                    issue.fix,
+                    issue.skill_id.as_deref().unwrap_or(issue.path.as_str()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    AuditIssueKind::GhostSkill,
+                    AuditFixAction::DeleteRecord,
+                    "ghost"
+                ),
+                (
+                    AuditIssueKind::OrphanDir,
+                    AuditFixAction::AdoptDir,
+                    "orphan"
+                ),
+                (
+                    AuditIssueKind::DuplicateTarget,
+                    AuditFixAction::DropTarget,
+                    "beta"
+                ),
+                (
+                    AuditIssueKind::MismatchedMode,
+                    AuditFixAction::RecordMode,
+                    "beta"
+                ),
+                (
+                    AuditIssueKind::MissingTarget,
+                    AuditFixAction::DropTarget,
+                    "alpha"
+                ),
+            ]
+        );
+        assert_eq!(report.checked_targets, 4);
+
+        // Issues survive a JSON round trip and still match the fresh report
+        let raw = serde_json::to_string(&report.issues).unwrap();
+        let parsed: Vec<LibraryAuditIssue> = serde_json::from_str(&raw).unwrap();
+        assert!(parsed[0].same_issue(&report.issues[0]));
+        assert!(raw.contains("\"kind\":\"duplicate_target\""));
+    }
+}
@@ -0,0 +1,137 @@
+//! Commands for the library consistency audit (`audit.rs`,
+//! `startup_check.rs`)
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::adapter::parse_sync_details;
+use super::audit::{
+    self, AuditFixAction, LibraryAuditDto, LibraryAuditFixResultDto, LibraryAuditIssue,
+};
+use super::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
+use super::collections;
+use super::commands::{adopt_detected_central_skill, format_error, scan_central_dir};
+use super::path_executor::remove_skill_target_checked;
+use super::skill_store;
+use super::types::now_ms;
+use super::variants;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+pub(super) async fn build_library_audit<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+) -> Result<(PathBuf, LibraryAuditDto), String> {
+    let central_dir = resolve_central_repo_path(app, state)
+        .await
+        .map_err(format_error)?;
+    let (detected, _, _) = scan_central_dir(&central_dir)?;
+    let skills = skill_store::get_managed_skills(state).await?;
+    let report = audit::audit_library(&skills, &central_dir, &detected, now_ms());
+    Ok((central_dir, report))
+}
+
+pub(super) async fn apply_library_audit_fix(
+    state: &SqliteDbState,
+    central_dir: &Path,
+    issue: &LibraryAuditIssue,
+) -> Result<(), String> {
+    let skill_id = issue.skill_id.as_deref().unwrap_or_default();
+    let tool = issue.tool.as_deref().unwrap_or_default();
+    match issue.fix {
+        AuditFixAction::DeleteRecord => delete_ghost_skill(state, central_dir, skill_id).await,
+        AuditFixAction::AdoptDir => {
+            if adopt_detected_central_skill(state, central_dir, &issue.path).await? {
+                Ok(())
+            } else {
+                Err("a skill with the same name already exists".to_string())
+            }
+        }
+        AuditFixAction::DropTarget => skill_store::delete_skill_target(state, skill_id, tool).await,
+        AuditFixAction::RecordMode => {
+            let mut target = skill_store::get_skill_target(state, skill_id, tool)
+                .await?
+                .ok_or_else(|| format!("Target not found: {} / {}", skill_id, tool))?;
+            let mode = audit::target_disk_mode(Path::new(&target.target_path))
+                .ok_or_else(|| "target path does not exist".to_string())?;
+            target.mode = mode.to_string();
+            skill_store::upsert_skill_target(state, skill_id, &target).await
+        }
+    }
+}
+
+/// Delete a ghost record. Only links are removed from the tools: a copy
+/// target may be the last remaining copy of the skill.
+async fn delete_ghost_skill(
+    state: &SqliteDbState,
+    central_dir: &Path,
+    skill_id: &str,
+) -> Result<(), String> {
+    let Some(skill) = skill_store::get_skill_by_id(state, skill_id).await? else {
+        return Ok(());
+    };
+    let central_path = resolve_skill_central_path(&skill.central_path, central_dir);
+    for target in parse_sync_details(&skill) {
+        let is_link = audit::target_disk_mode(Path::new(&target.target_path))
+            .is_some_and(|mode| mode != "copy");
+        if is_link {
+            remove_skill_target_checked(&central_path, &target.target_path)
+                .map_err(format_error)?;
+        }
+    }
+    for variant in variants::list_skill_variants(state, &skill.id)? {
+        variants::remove_variant_content(&variant)?;
+        variants::delete_skill_variant(state, &variant.id)?;
+    }
+    collections::remove_skill_from_collections(state, &skill.id)?;
+    skill_store::delete_skill(state, &skill.id).await
+}
+
+/// Cross-check skill records, central repo directories and tool targets.
+#[tauri::command]
+pub async fn skills_audit_library<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<LibraryAuditDto, String> {
+    build_library_audit(&app, &state)
+        .await
+        .map(|(_, report)| report)
+}
+
+/// Apply the fixes of the given audit issues. Issues are re-validated against
+/// a fresh audit, so only inconsistencies that still exist are touched.
+#[tauri::command]
+pub async fn skills_fix_library_audit_issues<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    issues: Vec<LibraryAuditIssue>,
+) -> Result<LibraryAuditFixResultDto, String> {
+    ensure_writable("skills_fix_library_audit_issues")?;
+    let (central_dir, report) = build_library_audit(&app, &state).await?;
+    let mut result = LibraryAuditFixResultDto::default();
+    for requested in issues {
+        let Some(issue) = report
+            .issues
+            .iter()
+            .find(|issue| issue.same_issue(&requested))
+        else {
+            result.skipped.push(requested);
+            continue;
+        };
+        match apply_library_audit_fix(&state, &central_dir, issue).await {
+            Ok(()) => result.fixed.push(issue.clone()),
+            Err(err) => result.errors.push(format!("{}: {}", issue.path, err)),
+        }
+    }
+    if !result.fixed.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(result)
+}
+
+/// Summary of the consistency check that ran at startup, `None` until it has run
+#[tauri::command]
+pub fn skills_get_startup_check_summary() -> Option<super::startup_check::StartupCheckSummary> {
+    super::startup_check::last_summary()
+}
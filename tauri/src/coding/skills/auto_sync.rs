@@ -0,0 +1,281 @@
+//! Scheduled background re-sync of skill targets
+//!
+//! Users who never open the Skills tab keep stale copies for weeks, and a
+//! moved central repo leaves broken links behind. With
+//! `skill_settings:skills.auto_sync_interval_mins` set, a background run
+//! every that many minutes repairs broken links
+//! (`auto_sync_commands::repair_links`), re-syncs the recorded targets that
+//! drifted from their source (`target_drifted`) and records itself in the
+//! `skill_sync_run` table. The run itself is
+//! `auto_sync_commands::run_skill_sync`.
+//!
+//! `0`, the default, turns the schedule off; a run can still be started by
+//! hand. Skipped in read-only mode. WSL targets are not probed.
+
+use std::path::Path;
+use std::time::Duration;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::sync_context;
+use super::sync_engine::{resolve_link_target, verify_copy};
+use super::target_pull::diff_target;
+use super::types::{now_ms, SkillTarget};
+use crate::coding::db_id::db_clean_id;
+use crate::coding::runtime_location;
+use crate::db::helpers::{db_delete, db_list, db_put};
+use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
+use crate::db::SqliteDbState;
+
+const INTERVAL_SETTINGS_KEY: &str = "auto_sync_interval_mins";
+const LAST_RUN_SETTINGS_KEY: &str = "last_auto_sync_at";
+/// Shortest interval accepted besides `0`
+pub const MIN_INTERVAL_MINS: u32 = 5;
+/// How often the scheduler checks whether a run is due, so a changed
+/// interval applies without a restart
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Runs kept in `skill_sync_run`, newest first
+const HISTORY_LIMIT: usize = 100;
+
+pub const TRIGGER_SCHEDULED: &str = "scheduled";
+pub const TRIGGER_MANUAL: &str = "manual";
+
+/// One re-sync run, stored in `skill_sync_run`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SkillSyncRun {
+    pub id: String,
+    /// `scheduled` | `manual`
+    pub trigger: String,
+    pub started_at: i64,
+    pub finished_at: i64,
+    /// Recorded targets checked for drift
+    pub checked: usize,
+    /// Drifted targets that were re-synced, as `skill:tool`
+    pub resynced: Vec<String>,
+    /// Broken links that were re-created, as `skill:tool`
+    pub repaired: Vec<String>,
+    /// `skill:tool: error` for targets that could not be fixed
+    pub errors: Vec<String>,
+}
+
+pub fn load_interval_mins(state: &SqliteDbState) -> u32 {
+    read_skill_settings_value_from_sqlite(state, INTERVAL_SETTINGS_KEY)
+        .and_then(|value| value.as_u64())
+        .map(|mins| mins.min(u32::MAX as u64) as u32)
+        .unwrap_or(0)
+}
+
+pub fn save_interval_mins(state: &SqliteDbState, mins: u32) -> Result<(), String> {
+    if mins != 0 && mins < MIN_INTERVAL_MINS {
+        return Err(format!(
+            "Auto sync interval must be 0 (off) or at least {} minutes",
+            MIN_INTERVAL_MINS
+        ));
+    }
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            INTERVAL_SETTINGS_KEY: mins,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+pub fn save_last_run_at(state: &SqliteDbState, at: i64) -> Result<(), String> {
+    merge_skill_settings_sqlite(state, serde_json::json!({ LAST_RUN_SETTINGS_KEY: at }))
+}
+
+fn last_run_at(state: &SqliteDbState) -> Option<i64> {
+    read_skill_settings_value_from_sqlite(state, LAST_RUN_SETTINGS_KEY)
+        .and_then(|value| value.as_i64())
+}
+
+/// Whether a run is due `interval_mins` after the last one
+fn is_sync_due(last_run_at: Option<i64>, interval_mins: u32, now: i64) -> bool {
+    if interval_mins == 0 {
+        return false;
+    }
+    last_run_at.is_none_or(|last| now - last >= i64::from(interval_mins) * 60_000)
+}
+
+/// Whether `target` no longer holds what a sync from `source` leaves there:
+/// it is missing, a link points elsewhere, or a copy's files differ. Links
+/// whose source is missing count as drifted; `repair_links` handles them
+/// first.
+pub fn target_drifted(source: &Path, target: &SkillTarget) -> bool {
+    let path = Path::new(&target.target_path);
+    if std::fs::symlink_metadata(path).is_err() {
+        return true;
+    }
+    match target.mode.as_str() {
+        "symlink" | "junction" => {
+            let Ok(stored) = std::fs::read_link(path) else {
+                return true;
+            };
+            let linked = resolve_link_target(path, &stored);
+            match (linked.canonicalize(), source.canonicalize()) {
+                (Ok(linked), Ok(source)) => linked != source,
+                _ => true,
+            }
+        }
+        "copy" | "hardlink" if target.path_rules.is_empty() => {
+            verify_copy(&sync_context::current(), source, path)
+                .map(|mismatches| !mismatches.is_empty())
+                .unwrap_or(true)
+        }
+        "copy" | "hardlink" => diff_target(source, path, &target.path_rules)
+            .map(|files| !files.is_empty())
+            .unwrap_or(true),
+        _ => false,
+    }
+}
+
+/// Targets the run looks at: recorded, not on WSL
+pub fn is_checked_target(target: &SkillTarget) -> bool {
+    runtime_location::parse_wsl_unc_path(&target.target_path).is_none()
+}
+
+fn run_order() -> Result<OrderSpec, String> {
+    Ok(OrderSpec::single(OrderField::json_integer(
+        "started_at",
+        OrderDirection::Desc,
+    )?))
+}
+
+/// Recorded runs, newest first
+pub fn list_runs(state: &SqliteDbState, limit: usize) -> Result<Vec<SkillSyncRun>, String> {
+    let order = run_order()?;
+    state.with_conn(|conn| {
+        db_list(conn, DbTable::SkillSyncRun, Some(&order))?
+            .into_iter()
+            .take(limit)
+            .map(|value| {
+                serde_json::from_value(value)
+                    .map(|mut run: SkillSyncRun| {
+                        run.id = db_clean_id(&run.id);
+                        run
+                    })
+                    .map_err(|error| format!("Failed to parse SQLite skill sync run: {error}"))
+            })
+            .collect()
+    })
+}
+
+/// Store `run` and drop the runs past `HISTORY_LIMIT`
+pub fn record_run(state: &SqliteDbState, run: &SkillSyncRun) -> Result<(), String> {
+    let data = serde_json::to_value(run)
+        .map_err(|error| format!("Failed to serialize skill sync run: {error}"))?;
+    state.with_conn(|conn| db_put(conn, DbTable::SkillSyncRun, &run.id, &data))?;
+    for old in list_runs(state, usize::MAX)?
+        .into_iter()
+        .skip(HISTORY_LIMIT)
+    {
+        state.with_conn(|conn| db_delete(conn, DbTable::SkillSyncRun, &old.id).map(|_| ()))?;
+    }
+    Ok(())
+}
+
+/// Start the scheduled re-sync as a background task
+pub fn start_auto_sync_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Initial delay: let startup checks and the central watcher settle
+        tokio::time::sleep(Duration::from_secs(90)).await;
+
+        loop {
+            if let Err(e) = run_if_due(&app_handle).await {
+                warn!("Scheduled skill sync failed: {}", e);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn run_if_due(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    if crate::settings::read_only::is_read_only() {
+        return Ok(());
+    }
+    let state = app_handle.state::<SqliteDbState>();
+    if !is_sync_due(last_run_at(&state), load_interval_mins(&state), now_ms()) {
+        return Ok(());
+    }
+
+    let run =
+        super::auto_sync_commands::run_skill_sync(app_handle, &state, TRIGGER_SCHEDULED).await?;
+    info!(
+        "Scheduled skill sync: {} checked, {} re-synced, {} repaired, {} failed",
+        run.checked,
+        run.resynced.len(),
+        run.repaired.len(),
+        run.errors.len()
+    );
+    if !run.resynced.is_empty() || !run.repaired.is_empty() {
+        let _ = app_handle.emit("skills-changed", "auto_sync");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(path: &Path, mode: &str) -> SkillTarget {
+        SkillTarget {
+            tool: "cursor".to_string(),
+            target_path: path.to_string_lossy().to_string(),
+            mode: mode.to_string(),
+            status: "ok".to_string(),
+            synced_at: None,
+            error_message: None,
+            path_rules: Default::default(),
+        }
+    }
+
+    #[test]
+    fn runs_when_the_interval_has_passed() {
+        let hour = 60 * 60_000;
+        assert!(!is_sync_due(None, 0, hour));
+        assert!(is_sync_due(None, 60, hour));
+        assert!(!is_sync_due(Some(hour), 60, hour + hour - 1));
+        assert!(is_sync_due(Some(hour), 60, hour + hour));
+    }
+
+    #[test]
+    fn detects_drifted_copies() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("central/review");
+        let copy = temp.path().join("cursor/review");
+        for dir in [&source, &copy] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("SKILL.md"), "# Review\n").unwrap();
+        }
+        assert!(!target_drifted(&source, &target(&copy, "copy")));
+
+        std::fs::write(source.join("SKILL.md"), "# Review v2\n").unwrap();
+        assert!(target_drifted(&source, &target(&copy, "copy")));
+        assert!(target_drifted(
+            &source,
+            &target(&temp.path().join("missing"), "copy")
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detects_links_that_point_elsewhere() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("central/review");
+        let old = temp.path().join("old/review");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::create_dir_all(&old).unwrap();
+        let current = temp.path().join("claude/current");
+        let stale = temp.path().join("claude/stale");
+        std::fs::create_dir_all(temp.path().join("claude")).unwrap();
+        std::os::unix::fs::symlink(&source, &current).unwrap();
+        std::os::unix::fs::symlink(&old, &stale).unwrap();
+
+        assert!(!target_drifted(&source, &target(&current, "symlink")));
+        assert!(target_drifted(&source, &target(&stale, "symlink")));
+    }
+}
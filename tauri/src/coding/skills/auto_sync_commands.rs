@@ -0,0 +1,247 @@
+//! Commands for scheduled resyncs and broken link repair (`auto_sync.rs`,
+//! `link_repair.rs`)
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::archive::ARCHIVED_STATUS;
+use super::auto_sync::{self, SkillSyncRun};
+use super::commands::{
+    format_error, refresh_central_skill_hash_if_needed, resolve_skill_source_path,
+    sync_skill_to_tool_records,
+};
+use super::link_repair::{self, LinkRepairEntryDto, LinkRepairResultDto};
+use super::path_executor::sync_skill_to_target;
+use super::skill_store;
+use super::types::{now_ms, Skill, SkillTarget, SyncMode};
+use super::variants;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Re-create the broken symlink / junction targets of the managed skills
+/// from their records; targets whose source is gone are marked `broken`
+#[tauri::command]
+pub async fn skills_repair_links<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<LinkRepairResultDto, String> {
+    ensure_writable("skills_repair_links")?;
+    let result = repair_links(&app, &state).await?;
+    log::info!(
+        "Link repair: {} checked, {} repaired, {} broken",
+        result.checked,
+        result.repaired.len(),
+        result.broken.len()
+    );
+    if !result.repaired.is_empty() || !result.broken.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(result)
+}
+
+pub(crate) async fn repair_links<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+) -> Result<LinkRepairResultDto, String> {
+    let skills: Vec<Skill> = skill_store::get_managed_skills(state)
+        .await?
+        .into_iter()
+        .filter(|skill| skill.status != ARCHIVED_STATUS)
+        .collect();
+    let (checked, broken_links) = link_repair::find_broken_links(&skills);
+    let mut result = LinkRepairResultDto {
+        checked,
+        ..Default::default()
+    };
+
+    for link in broken_links {
+        let Some(skill) = skills.iter().find(|skill| skill.id == link.skill_id) else {
+            continue;
+        };
+        let target = link.target;
+        // Tools assigned to a variant link to the variant snapshot
+        let source = match variants::resolve_variant_source(state, &skill.id, &target.tool)? {
+            Some(variant) => variant,
+            None => resolve_skill_source_path(app, state, skill).await?,
+        };
+        let target_path = PathBuf::from(&target.target_path);
+        let relinked = if source.is_dir() {
+            link_repair::remove_broken_link(&target_path)
+                .and_then(|_| {
+                    sync_skill_to_target(
+                        &source,
+                        &target_path,
+                        false,
+                        SyncMode::parse(&target.mode).unwrap_or(SyncMode::Symlink),
+                        &target.path_rules,
+                    )
+                })
+                .map_err(format_error)
+        } else {
+            Err(format!("Skill source is missing: {}", source.display()))
+        };
+
+        let (record, detail) = match relinked {
+            Ok(outcome) => (
+                SkillTarget {
+                    target_path: outcome.target_path.to_string_lossy().to_string(),
+                    mode: outcome.mode_used.as_str().to_string(),
+                    status: "ok".to_string(),
+                    error_message: None,
+                    synced_at: Some(now_ms()),
+                    ..target.clone()
+                },
+                source.to_string_lossy().to_string(),
+            ),
+            Err(err) => (
+                SkillTarget {
+                    status: link_repair::BROKEN_STATUS.to_string(),
+                    error_message: Some(err.clone()),
+                    ..target.clone()
+                },
+                err,
+            ),
+        };
+        skill_store::upsert_skill_target(state, &skill.id, &record).await?;
+        let entry = LinkRepairEntryDto {
+            skill_id: skill.id.clone(),
+            skill_name: skill.name.clone(),
+            tool: target.tool.clone(),
+            target_path: target.target_path.clone(),
+            detail,
+        };
+        if record.status == link_repair::BROKEN_STATUS {
+            result.broken.push(entry);
+        } else {
+            result.repaired.push(entry);
+        }
+    }
+    Ok(result)
+}
+
+/// Interval of the scheduled re-sync in minutes; `0` when off
+#[tauri::command]
+pub async fn skills_get_auto_sync_interval(state: State<'_, SqliteDbState>) -> Result<u32, String> {
+    Ok(auto_sync::load_interval_mins(&state))
+}
+
+/// Set the interval of the scheduled re-sync; `0` turns it off
+#[tauri::command]
+pub async fn skills_set_auto_sync_interval(
+    state: State<'_, SqliteDbState>,
+    minutes: u32,
+) -> Result<(), String> {
+    ensure_writable("skills_set_auto_sync_interval")?;
+    auto_sync::save_interval_mins(&state, minutes)
+}
+
+/// Run the scheduled re-sync now
+#[tauri::command]
+pub async fn skills_run_skill_sync_now<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<SkillSyncRun, String> {
+    ensure_writable("skills_run_skill_sync_now")?;
+    let run = run_skill_sync(&app, &state, auto_sync::TRIGGER_MANUAL).await?;
+    if !run.resynced.is_empty() || !run.repaired.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(run)
+}
+
+/// Recorded re-sync runs, newest first
+#[tauri::command]
+pub async fn skills_list_sync_runs(
+    state: State<'_, SqliteDbState>,
+    limit: Option<usize>,
+) -> Result<Vec<SkillSyncRun>, String> {
+    auto_sync::list_runs(&state, limit.unwrap_or(20))
+}
+
+/// Repair broken links, re-sync the enabled targets that drifted from their
+/// source and record the run
+pub(crate) async fn run_skill_sync<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    trigger: &str,
+) -> Result<SkillSyncRun, String> {
+    let mut run = SkillSyncRun {
+        id: crate::coding::db_id::db_new_id(),
+        trigger: trigger.to_string(),
+        started_at: now_ms(),
+        ..Default::default()
+    };
+
+    match repair_links(app, state).await {
+        Ok(repair) => {
+            run.repaired = repair
+                .repaired
+                .iter()
+                .map(|entry| format!("{}:{}", entry.skill_name, entry.tool))
+                .collect();
+            run.errors.extend(
+                repair
+                    .broken
+                    .iter()
+                    .map(|entry| format!("{}:{}: {}", entry.skill_name, entry.tool, entry.detail)),
+            );
+        }
+        Err(err) => run.errors.push(format!("link repair: {}", err)),
+    }
+
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    for mut skill in skill_store::get_managed_skills(state).await? {
+        if !skill.management_enabled || skill.status == ARCHIVED_STATUS {
+            continue;
+        }
+        let central_path = match resolve_skill_source_path(app, state, &skill).await {
+            Ok(path) if path.is_dir() => path,
+            _ => continue,
+        };
+
+        let mut drifted_tools = Vec::new();
+        for target in skill_store::get_skill_targets(state, &skill.id).await? {
+            if !skill.enabled_tools.contains(&target.tool)
+                || target.status == link_repair::BROKEN_STATUS
+                || !auto_sync::is_checked_target(&target)
+            {
+                continue;
+            }
+            run.checked += 1;
+            // Tools assigned to a variant sync from the variant snapshot
+            let source = variants::resolve_variant_source(state, &skill.id, &target.tool)?
+                .unwrap_or_else(|| central_path.clone());
+            if auto_sync::target_drifted(&source, &target) {
+                drifted_tools.push(target.tool);
+            }
+        }
+        if drifted_tools.is_empty() {
+            continue;
+        }
+
+        refresh_central_skill_hash_if_needed(state, &mut skill, &central_path).await?;
+        let results = sync_skill_to_tool_records(
+            state,
+            &skill,
+            &drifted_tools,
+            &central_path,
+            true,
+            &custom_tools,
+        )
+        .await;
+        for (tool, result) in results {
+            match result {
+                Ok(_) => run.resynced.push(format!("{}:{}", skill.name, tool)),
+                Err(err) => run.errors.push(format!("{}:{}: {}", skill.name, tool, err)),
+            }
+        }
+    }
+
+    run.finished_at = now_ms();
+    auto_sync::record_run(state, &run)?;
+    auto_sync::save_last_run_at(state, run.started_at)?;
+    Ok(run)
+}
@@ -4,12 +4,14 @@ use anyhow::{Context, Result};
 use serde_json::{Map, Value};
 use tauri::Manager;
 
+use super::sync_context::set_relative_symlinks;
 use crate::coding::tools::resolve_storage_path;
 use crate::db::helpers::{db_get, db_put};
 use crate::db::schema::DbTable;
 
 const CENTRAL_DIR_NAME: &str = "skills";
 const SKILL_SETTINGS_ID: &str = "skills";
+const RELATIVE_SYMLINKS_KEY: &str = "relative_symlinks";
 
 fn central_repo_path_from_settings_record(record: &Value) -> Option<PathBuf> {
     record
@@ -20,9 +22,15 @@ fn central_repo_path_from_settings_record(record: &Value) -> Option<PathBuf> {
         .map(resolve_portable_central_repo_path)
 }
 
-fn load_authoritative_central_repo_path_sync(
+/// The group policy path, when set, wins over the settings record.
+pub(crate) fn load_authoritative_central_repo_path_sync(
     state: &crate::SqliteDbState,
 ) -> std::result::Result<Option<PathBuf>, String> {
+    if let Some(locked) = crate::settings::policy::locked_central_repo_path() {
+        return expand_home_path(locked)
+            .map(Some)
+            .map_err(|e| format!("invalid central_repo_path in group policy: {:#}", e));
+    }
     state.with_conn(|conn| {
         Ok(db_get(conn, DbTable::SkillSettings, SKILL_SETTINGS_ID)?
             .and_then(|record| central_repo_path_from_settings_record(&record)))
@@ -111,6 +119,20 @@ pub(crate) fn read_skill_settings_i64_from_sqlite(
         .flatten()
 }
 
+pub(crate) fn read_skill_settings_value_from_sqlite(
+    state: &crate::SqliteDbState,
+    key: &str,
+) -> Option<Value> {
+    state
+        .with_conn(|conn| {
+            Ok(db_get(conn, DbTable::SkillSettings, SKILL_SETTINGS_ID)?
+                .and_then(|record| record.get(key).cloned())
+                .filter(|value| !value.is_null()))
+        })
+        .ok()
+        .flatten()
+}
+
 pub(crate) fn merge_skill_settings_sqlite(
     state: &crate::SqliteDbState,
     patch: Value,
@@ -131,6 +153,35 @@ pub(crate) fn merge_skill_settings_sqlite(
     })
 }
 
+/// Whether new symlinks into the central repo are relative, for a central
+/// repo on a synced or portable volume mounted at different paths
+pub fn load_relative_symlinks(state: &crate::SqliteDbState) -> bool {
+    read_skill_settings_value_from_sqlite(state, RELATIVE_SYMLINKS_KEY)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Load the saved symlink style into the app's `SyncContext`
+pub fn init_link_style_from_settings(state: &crate::SqliteDbState) {
+    set_relative_symlinks(load_relative_symlinks(state));
+}
+
+/// Save the symlink style; applies to links created from now on
+pub fn save_relative_symlinks(
+    state: &crate::SqliteDbState,
+    relative: bool,
+) -> std::result::Result<(), String> {
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            RELATIVE_SYMLINKS_KEY: relative,
+            "updated_at": super::types::now_ms(),
+        }),
+    )?;
+    set_relative_symlinks(relative);
+    Ok(())
+}
+
 /// Ensure the central repo directory exists
 pub fn ensure_central_repo(path: &Path) -> Result<()> {
     std::fs::create_dir_all(path).with_context(|| format!("create {:?}", path))?;
@@ -181,13 +232,13 @@ fn with_storage_prefix(prefix: &str, relative_path: &str) -> String {
 pub fn to_portable_central_repo_path(path: &Path) -> String {
     let candidate = normalize_for_storage(&path.to_string_lossy());
 
-    if let Some(config_dir) = dirs::config_dir() {
+    if let Some(config_dir) = crate::coding::tools::config_dir() {
         if let Some(relative_path) = strip_storage_base(&candidate, &config_dir) {
             return with_storage_prefix("%APPDATA%", &relative_path);
         }
     }
 
-    if let Some(home_dir) = dirs::home_dir() {
+    if let Some(home_dir) = crate::coding::tools::home_dir() {
         if let Some(relative_path) = strip_storage_base(&candidate, &home_dir) {
             return with_storage_prefix("~", &relative_path);
         }
@@ -259,7 +310,7 @@ fn resolve_legacy_cross_platform_user_path(raw_path: &str) -> Option<PathBuf> {
     };
 
     if let Some(start_index) = appdata_relative_start {
-        return dirs::config_dir().map(|base| {
+        return crate::coding::tools::config_dir().map(|base| {
             parts
                 .iter()
                 .skip(start_index)
@@ -271,7 +322,7 @@ fn resolve_legacy_cross_platform_user_path(raw_path: &str) -> Option<PathBuf> {
         if !is_legacy_home_central_repo_tail(&parts, start_index) {
             return None;
         }
-        dirs::home_dir().map(|base| {
+        crate::coding::tools::home_dir().map(|base| {
             parts
                 .iter()
                 .skip(start_index)
@@ -0,0 +1,203 @@
+//! Automatic re-sync of copy targets after central repo edits
+//!
+//! Linked tools see an edit of the central repo right away; copy-mode
+//! targets keep the content of their last sync. The watcher (notify crate)
+//! follows the central repo recursively, waits until edits have been quiet
+//! for `DEBOUNCE`, and re-syncs the recorded copy targets of the skill
+//! directories that changed (`commands::resync_edited_copy_targets`).
+//! Targets that already match their source are left alone, so the app's own
+//! installs and updates don't sync twice. `AUTO_RESYNCED_EVENT` lists the
+//! refreshed targets.
+//!
+//! On unless `skill_settings:skills.auto_resync_copies` is `false`; skipped
+//! in read-only mode. The central repo path is re-read every
+//! `RECHECK_INTERVAL` so a moved repo is followed.
+
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use log::{info, warn};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use super::central_repo::{
+    merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite, resolve_central_repo_path,
+};
+use super::types::now_ms;
+use crate::db::SqliteDbState;
+
+const AUTO_RESYNC_SETTINGS_KEY: &str = "auto_resync_copies";
+const DEBOUNCE: Duration = Duration::from_secs(2);
+const RECHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Emitted with an `AutoResyncedEventDto` after a re-sync refreshed targets
+pub const AUTO_RESYNCED_EVENT: &str = "skills-auto-resynced";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AutoResyncedEventDto {
+    /// Skill directories under the central repo that were edited
+    pub skills: Vec<String>,
+    /// Refreshed targets as `skill:tool`
+    pub targets: Vec<String>,
+}
+
+pub fn is_auto_resync_enabled(state: &SqliteDbState) -> bool {
+    read_skill_settings_value_from_sqlite(state, AUTO_RESYNC_SETTINGS_KEY)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true)
+}
+
+pub fn set_auto_resync_enabled(state: &SqliteDbState, enabled: bool) -> Result<(), String> {
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            AUTO_RESYNC_SETTINGS_KEY: enabled,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Name of the top-level entry of `central_dir` that `path` lies in. Hidden
+/// entries (`.git`, staging dirs of the app's own writes) are not skills.
+pub(crate) fn skill_dir_name(central_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(central_dir).ok()?;
+    let Some(Component::Normal(name)) = relative.components().next() else {
+        return None;
+    };
+    let name = name.to_string_lossy();
+    (!name.starts_with('.')).then(|| name.to_string())
+}
+
+/// Skill directories touched by `paths`, matched against the central repo
+/// as configured and as resolved (the OS may report either)
+fn edited_skill_dirs(central_dirs: &[PathBuf], paths: &[PathBuf]) -> BTreeSet<String> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            central_dirs
+                .iter()
+                .find_map(|central_dir| skill_dir_name(central_dir, path))
+        })
+        .collect()
+}
+
+/// Start watching the central repo as a background task
+pub fn start_central_repo_watcher(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let (tx, mut rx) = unbounded_channel::<PathBuf>();
+        let mut central_dirs: Vec<PathBuf> = Vec::new();
+        let mut _watcher: Option<RecommendedWatcher> = None;
+        loop {
+            let state = app_handle.state::<SqliteDbState>();
+            match resolve_central_repo_path(&app_handle, &state).await {
+                Ok(central_dir) if central_dirs.first() != Some(&central_dir) => {
+                    _watcher = match watch(&central_dir, tx.clone()) {
+                        Ok(watcher) => Some(watcher),
+                        Err(e) => {
+                            warn!("Failed to watch central repo {:?}: {}", central_dir, e);
+                            None
+                        }
+                    };
+                    let resolved = std::fs::canonicalize(&central_dir).ok();
+                    central_dirs = std::iter::once(central_dir).chain(resolved).collect();
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to resolve central repo for watching: {:#}", e),
+            }
+
+            // Wait for an edit; without one, re-check the central repo path
+            let first = match tokio::time::timeout(RECHECK_INTERVAL, rx.recv()).await {
+                Ok(Some(path)) => path,
+                Ok(None) => return,
+                Err(_) => continue,
+            };
+            let mut paths = vec![first];
+            while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                paths.push(path);
+            }
+
+            let dirs = edited_skill_dirs(&central_dirs, &paths);
+            if dirs.is_empty() {
+                continue;
+            }
+            if let Err(e) = resync_edited(&app_handle, dirs).await {
+                warn!("Re-sync after central repo edit failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Forward the paths of every change below `dir`; reads are not changes
+fn watch(dir: &Path, tx: UnboundedSender<PathBuf>) -> notify::Result<RecommendedWatcher> {
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if !event.kind.is_access() => {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Central repo watch error: {}", e),
+        })?;
+    watcher.watch(dir, RecursiveMode::Recursive)?;
+    Ok(watcher)
+}
+
+async fn resync_edited(
+    app_handle: &tauri::AppHandle,
+    dirs: BTreeSet<String>,
+) -> Result<(), String> {
+    if crate::settings::read_only::is_read_only() {
+        return Ok(());
+    }
+    let state = app_handle.state::<SqliteDbState>();
+    if !is_auto_resync_enabled(&state) {
+        return Ok(());
+    }
+
+    let targets = super::commands::resync_edited_copy_targets(app_handle, &state, &dirs).await?;
+    if !targets.is_empty() {
+        info!(
+            "Re-synced {} copy target(s) after central repo edit",
+            targets.len()
+        );
+        let _ = app_handle.emit("skills-changed", "auto_resync");
+        let _ = app_handle.emit(
+            AUTO_RESYNCED_EVENT,
+            AutoResyncedEventDto {
+                skills: dirs.into_iter().collect(),
+                targets,
+            },
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_edited_paths_to_skill_dirs() {
+        let central = PathBuf::from("/data/skills");
+        let resolved = PathBuf::from("/private/data/skills");
+        let paths = vec![
+            central.join("review/SKILL.md"),
+            resolved.join("deploy/scripts/run.sh"),
+            central.join("review"),
+            central.join(".git/index"),
+            central.join(".skills-revert-1/SKILL.md"),
+            PathBuf::from("/data/other/SKILL.md"),
+            central.clone(),
+        ];
+
+        let dirs = edited_skill_dirs(&[central, resolved], &paths);
+        assert_eq!(
+            dirs.into_iter().collect::<Vec<_>>(),
+            vec!["deploy", "review"]
+        );
+    }
+}
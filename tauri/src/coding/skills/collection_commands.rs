@@ -0,0 +1,272 @@
+//! Commands for skill collections (`collections.rs`)
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::archive::ARCHIVED_STATUS;
+use super::cache_cleanup::get_git_cache_ttl_secs;
+use super::collections::{
+    self, GitCollectionCandidate, SkillCollectionImportResultDto, SkillCollectionToolResultDto,
+};
+use super::commands::{
+    format_error, normalize_optional_id, normalize_optional_text,
+    refresh_central_skill_hash_if_needed, remove_skill_target_best_effort,
+    resolve_skill_source_path, resolve_skill_source_path_for_cleanup, sync_skill_to_tool_record,
+};
+use super::installer::list_git_collections;
+use super::skill_store;
+use super::source_mirror;
+use super::types::{now_ms, SkillCollection};
+use crate::coding::tools::post_sync::{schedule_post_sync, PostSyncScope};
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+#[tauri::command]
+pub async fn skills_get_collections(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<SkillCollection>, String> {
+    collections::list_collections(&state)
+}
+
+/// Create (`id` empty) or update a collection. Unknown skill ids are dropped.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_save_collection(
+    state: State<'_, SqliteDbState>,
+    id: Option<String>,
+    name: String,
+    note: Option<String>,
+    skillIds: Vec<String>,
+    source: Option<String>,
+) -> Result<String, String> {
+    ensure_writable("skills_save_collection")?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Collection name is required".to_string());
+    }
+    let normalized_id = normalize_optional_id(id);
+    let existing = collections::list_collections(&state)?;
+    if collections::find_by_name(&existing, &name, normalized_id.as_deref()).is_some() {
+        return Err(format!("Duplicate collection name: {}", name));
+    }
+    let known_ids: HashSet<String> = skill_store::get_managed_skills(&state)
+        .await?
+        .into_iter()
+        .map(|skill| skill.id)
+        .collect();
+    let mut seen = HashSet::new();
+    let skill_ids = skillIds
+        .into_iter()
+        .filter(|id| known_ids.contains(id) && seen.insert(id.clone()))
+        .collect();
+
+    let now = now_ms();
+    let previous = normalized_id
+        .as_ref()
+        .and_then(|collection_id| existing.iter().find(|c| c.id == *collection_id));
+    let collection = SkillCollection {
+        id: normalized_id.unwrap_or_else(crate::coding::db_id::db_new_id),
+        name,
+        note: normalize_optional_text(note),
+        skill_ids,
+        source: normalize_optional_text(source).or_else(|| previous.and_then(|c| c.source.clone())),
+        created_at: previous.map(|c| c.created_at).unwrap_or(now),
+        updated_at: now,
+    };
+    collections::put_collection(&state, &collection)?;
+    Ok(collection.id)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_delete_collection(
+    state: State<'_, SqliteDbState>,
+    collectionId: String,
+) -> Result<(), String> {
+    ensure_writable("skills_delete_collection")?;
+    collections::delete_collection(&state, &collectionId)
+}
+
+/// Sync every member of a collection to `tool`, or remove them from it.
+/// Members that are disabled, archived or already in the wanted state are
+/// left alone; per-member failures are collected instead of aborting.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_collection_tool_enabled<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    collectionId: String,
+    tool: String,
+    enabled: bool,
+    overwrite: Option<bool>,
+) -> Result<SkillCollectionToolResultDto, String> {
+    ensure_writable("skills_set_collection_tool_enabled")?;
+    let collection = collections::get_collection(&state, &collectionId)?
+        .ok_or_else(|| format!("Skill collection not found: {}", collectionId))?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let mut result = SkillCollectionToolResultDto {
+        collection_id: collection.id.clone(),
+        tool: tool.clone(),
+        ..Default::default()
+    };
+
+    for skill_id in &collection.skill_ids {
+        let Some(mut skill) = skill_store::get_skill_by_id(&state, skill_id).await? else {
+            result.skipped.push(skill_id.clone());
+            continue;
+        };
+        let target = skill_store::get_skill_target(&state, &skill.id, &tool).await?;
+        if enabled {
+            if !skill.management_enabled || skill.status == ARCHIVED_STATUS {
+                result.skipped.push(skill.id.clone());
+                continue;
+            }
+            if target.is_some() {
+                continue;
+            }
+            let source_path = match resolve_skill_source_path(&app, &state, &skill).await {
+                Ok(path) => path,
+                Err(err) => {
+                    result.errors.push(format!("{}: {}", skill.name, err));
+                    continue;
+                }
+            };
+            refresh_central_skill_hash_if_needed(&state, &mut skill, &source_path).await?;
+            match sync_skill_to_tool_record(
+                &state,
+                &skill,
+                &tool,
+                &source_path,
+                overwrite.unwrap_or(false),
+                &custom_tools,
+            )
+            .await
+            {
+                Ok(_) => result.changed.push(skill.id.clone()),
+                Err(err) => result.errors.push(format!("{}: {}", skill.name, err)),
+            }
+        } else if let Some(target) = target {
+            if let Some(source_path) =
+                resolve_skill_source_path_for_cleanup(&app, &state, &skill).await
+            {
+                remove_skill_target_best_effort(&skill, &source_path, &target);
+            }
+            skill_store::delete_skill_target(&state, &skill.id, &tool).await?;
+            if let Some(tool_root) = Path::new(&target.target_path).parent() {
+                schedule_post_sync(&state, &tool, PostSyncScope::Skills, tool_root);
+            }
+            result.changed.push(skill.id.clone());
+        }
+    }
+
+    let _ = app.emit("skills-changed", "window");
+    Ok(result)
+}
+
+/// Write the given collections (all when `collectionIds` is empty) to
+/// `~/skill-collections-{timestamp}.json` and return the path
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_export_collections_file(
+    state: State<'_, SqliteDbState>,
+    collectionIds: Option<Vec<String>>,
+) -> Result<String, String> {
+    let wanted = collectionIds.unwrap_or_default();
+    let selected: Vec<SkillCollection> = collections::list_collections(&state)?
+        .into_iter()
+        .filter(|collection| wanted.is_empty() || wanted.contains(&collection.id))
+        .collect();
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let json = serde_json::to_string_pretty(&collections::export_collections(&selected, &skills))
+        .map_err(|e| format!("Failed to serialize collections: {}", e))?;
+
+    let home = crate::coding::tools::home_dir()
+        .ok_or_else(|| "Failed to resolve home directory".to_string())?;
+    let path = home.join(format!("skill-collections-{}.json", now_ms()));
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write collections file: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Import collections from an exported file. Collections are matched by name
+/// and their members replaced with the installed skills the file refers to.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_import_collections_file(
+    state: State<'_, SqliteDbState>,
+    filePath: String,
+) -> Result<SkillCollectionImportResultDto, String> {
+    ensure_writable("skills_import_collections_file")?;
+    let raw = std::fs::read_to_string(&filePath)
+        .map_err(|e| format!("Failed to read collections file: {}", e))?;
+    let parsed = collections::parse_collections_json(&raw)?;
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let mut existing = collections::list_collections(&state)?;
+    let mut result = SkillCollectionImportResultDto::default();
+    let now = now_ms();
+
+    for entry in parsed.collections {
+        let name = entry.name.trim().to_string();
+        if name.is_empty() {
+            continue;
+        }
+        let mut skill_ids: Vec<String> = Vec::new();
+        for member in &entry.skills {
+            match collections::resolve_member(&skills, member) {
+                Some(skill) if !skill_ids.contains(&skill.id) => skill_ids.push(skill.id.clone()),
+                Some(_) => {}
+                None => result.unresolved.push(format!("{}: {}", name, member.name)),
+            }
+        }
+        let collection = match collections::find_by_name(&existing, &name, None) {
+            Some(previous) => {
+                result.updated += 1;
+                SkillCollection {
+                    note: entry.note.or_else(|| previous.note.clone()),
+                    source: entry.source.or_else(|| previous.source.clone()),
+                    skill_ids,
+                    updated_at: now,
+                    ..previous.clone()
+                }
+            }
+            None => {
+                result.created += 1;
+                SkillCollection {
+                    id: crate::coding::db_id::db_new_id(),
+                    name,
+                    note: entry.note,
+                    skill_ids,
+                    source: entry.source,
+                    created_at: now,
+                    updated_at: now,
+                }
+            }
+        };
+        collections::put_collection(&state, &collection)?;
+        existing.retain(|c| c.id != collection.id);
+        existing.push(collection);
+    }
+    Ok(result)
+}
+
+/// Collections a git repo defines in its `skill-collections.json`
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_list_git_collections(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    repoUrl: String,
+    branch: Option<String>,
+) -> Result<Vec<GitCollectionCandidate>, String> {
+    let ttl = get_git_cache_ttl_secs(&state).await;
+    let mirror = source_mirror::load_source_mirror(&state);
+    tokio::task::spawn_blocking(move || {
+        list_git_collections(&app, ttl, &mirror, &repoUrl, branch.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(format_error)
+}
@@ -0,0 +1,365 @@
+//! Skill collections (bundles)
+//!
+//! A collection is a named set of skills that can be enabled for a tool in
+//! one step. Membership is many-to-many and does not touch groups. The
+//! exported JSON references members by name + source so it can be imported on
+//! another machine; skill repos can ship the same kind of definitions in a
+//! `skill-collections.json` file at the repo root, with members given as skill
+//! subpaths or names inside the repo.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::types::{GitSkillCandidate, Skill, SkillCollection};
+use crate::coding::db_id::db_clean_id;
+use crate::db::helpers::{db_delete, db_get, db_list, db_put};
+use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
+use crate::SqliteDbState;
+
+/// Collection definitions a skill repo can ship at its root
+pub const REGISTRY_MANIFEST_FILE: &str = "skill-collections.json";
+
+const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Exported collections file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillCollectionsJson {
+    pub schema_version: u32,
+    pub collections: Vec<SkillCollectionJson>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillCollectionJson {
+    pub name: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub skills: Vec<SkillCollectionMemberJson>,
+}
+
+/// Portable member reference; skill ids are local to one installation
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillCollectionMemberJson {
+    pub name: String,
+    #[serde(default)]
+    pub source_ref: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SkillCollectionImportResultDto {
+    pub created: usize,
+    pub updated: usize,
+    /// "collection: skill" for members with no matching installed skill
+    pub unresolved: Vec<String>,
+}
+
+/// Outcome of enabling or disabling a collection for one tool
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SkillCollectionToolResultDto {
+    pub collection_id: String,
+    pub tool: String,
+    /// Member skill ids synced to (or removed from) the tool
+    pub changed: Vec<String>,
+    /// Members left alone: disabled, archived or missing
+    pub skipped: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Collection defined by a skill repo, resolved against its candidates
+#[derive(Clone, Debug, Serialize)]
+pub struct GitCollectionCandidate {
+    pub name: String,
+    pub note: Option<String>,
+    /// Candidate subpaths of the members
+    pub subpaths: Vec<String>,
+    /// Members listed in the manifest that the repo does not contain
+    pub missing: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RegistryManifest {
+    #[serde(default)]
+    collections: Vec<RegistryCollection>,
+}
+
+#[derive(Deserialize)]
+struct RegistryCollection {
+    name: String,
+    #[serde(default)]
+    note: Option<String>,
+    #[serde(default)]
+    skills: Vec<String>,
+}
+
+fn collection_order() -> Result<OrderSpec, String> {
+    Ok(OrderSpec::single(OrderField::json_integer(
+        "created_at",
+        OrderDirection::Asc,
+    )?))
+}
+
+fn sqlite_value_to_collection(value: serde_json::Value) -> Result<SkillCollection, String> {
+    serde_json::from_value(value)
+        .map(|mut collection: SkillCollection| {
+            collection.id = db_clean_id(&collection.id);
+            collection
+        })
+        .map_err(|error| format!("Failed to parse SQLite skill collection: {error}"))
+}
+
+/// All collections, oldest first
+pub fn list_collections(state: &SqliteDbState) -> Result<Vec<SkillCollection>, String> {
+    let order = collection_order()?;
+    state.with_conn(|conn| {
+        db_list(conn, DbTable::SkillCollection, Some(&order))?
+            .into_iter()
+            .map(sqlite_value_to_collection)
+            .collect()
+    })
+}
+
+pub fn get_collection(
+    state: &SqliteDbState,
+    collection_id: &str,
+) -> Result<Option<SkillCollection>, String> {
+    state.with_conn(|conn| {
+        db_get(conn, DbTable::SkillCollection, &db_clean_id(collection_id))?
+            .map(sqlite_value_to_collection)
+            .transpose()
+    })
+}
+
+pub fn put_collection(state: &SqliteDbState, collection: &SkillCollection) -> Result<(), String> {
+    let data = serde_json::to_value(collection)
+        .map_err(|error| format!("Failed to serialize skill collection: {error}"))?;
+    state.with_conn(|conn| db_put(conn, DbTable::SkillCollection, &collection.id, &data))
+}
+
+pub fn delete_collection(state: &SqliteDbState, collection_id: &str) -> Result<(), String> {
+    state.with_conn(|conn| {
+        db_delete(conn, DbTable::SkillCollection, &db_clean_id(collection_id)).map(|_| ())
+    })
+}
+
+/// Drop a deleted skill from every collection it belonged to
+pub fn remove_skill_from_collections(state: &SqliteDbState, skill_id: &str) -> Result<(), String> {
+    for mut collection in list_collections(state)? {
+        let before = collection.skill_ids.len();
+        collection.skill_ids.retain(|id| id != skill_id);
+        if collection.skill_ids.len() != before {
+            put_collection(state, &collection)?;
+        }
+    }
+    Ok(())
+}
+
+/// Case-insensitive name lookup, ignoring `except_id`
+pub fn find_by_name<'a>(
+    collections: &'a [SkillCollection],
+    name: &str,
+    except_id: Option<&str>,
+) -> Option<&'a SkillCollection> {
+    collections.iter().find(|collection| {
+        collection.name.trim().eq_ignore_ascii_case(name.trim())
+            && except_id != Some(collection.id.as_str())
+    })
+}
+
+pub fn export_collections(
+    collections: &[SkillCollection],
+    skills: &[Skill],
+) -> SkillCollectionsJson {
+    SkillCollectionsJson {
+        schema_version: EXPORT_SCHEMA_VERSION,
+        collections: collections
+            .iter()
+            .map(|collection| SkillCollectionJson {
+                name: collection.name.clone(),
+                note: collection.note.clone(),
+                source: collection.source.clone(),
+                skills: collection
+                    .skill_ids
+                    .iter()
+                    .filter_map(|id| skills.iter().find(|skill| skill.id == *id))
+                    .map(|skill| SkillCollectionMemberJson {
+                        name: skill.name.clone(),
+                        source_ref: skill.source_ref.clone(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+pub fn parse_collections_json(raw: &str) -> Result<SkillCollectionsJson, String> {
+    let parsed: SkillCollectionsJson = serde_json::from_str(raw)
+        .map_err(|error| format!("Invalid skill collections JSON: {error}"))?;
+    if parsed.schema_version != EXPORT_SCHEMA_VERSION {
+        return Err(format!(
+            "Unsupported skill collections schema_version: {}",
+            parsed.schema_version
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Installed skill a portable member reference points at: same source first,
+/// then same name
+pub fn resolve_member<'a>(
+    skills: &'a [Skill],
+    member: &SkillCollectionMemberJson,
+) -> Option<&'a Skill> {
+    member
+        .source_ref
+        .as_ref()
+        .and_then(|source_ref| {
+            skills
+                .iter()
+                .find(|skill| skill.source_ref.as_ref() == Some(source_ref))
+        })
+        .or_else(|| skills.iter().find(|skill| skill.name == member.name))
+}
+
+fn normalize_member_path(member: &str) -> &str {
+    let member = member.trim().trim_end_matches('/');
+    member.strip_prefix("./").unwrap_or(member)
+}
+
+/// Collections defined by the repo at `repo_dir`; members match candidates
+/// by subpath or by skill name
+pub fn registry_collections(
+    repo_dir: &Path,
+    candidates: &[GitSkillCandidate],
+) -> Result<Vec<GitCollectionCandidate>> {
+    let manifest_path = repo_dir.join(REGISTRY_MANIFEST_FILE);
+    if !manifest_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("read {:?}", manifest_path))?;
+    let manifest: RegistryManifest = serde_json::from_str(&raw)
+        .with_context(|| format!("invalid {}", REGISTRY_MANIFEST_FILE))?;
+
+    Ok(manifest
+        .collections
+        .into_iter()
+        .filter(|collection| !collection.name.trim().is_empty())
+        .map(|collection| {
+            let mut subpaths = Vec::new();
+            let mut missing = Vec::new();
+            for member in &collection.skills {
+                let key = normalize_member_path(member);
+                match candidates
+                    .iter()
+                    .find(|candidate| candidate.subpath == key || candidate.name == key)
+                {
+                    Some(candidate) if !subpaths.contains(&candidate.subpath) => {
+                        subpaths.push(candidate.subpath.clone())
+                    }
+                    Some(_) => {}
+                    None => missing.push(member.clone()),
+                }
+            }
+            GitCollectionCandidate {
+                name: collection.name.trim().to_string(),
+                note: collection.note,
+                subpaths,
+                missing,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use serde_json::json;
+
+    fn skill(id: &str, name: &str, source_ref: Option<&str>) -> Skill {
+        from_db_skill(json!({
+            "id": id,
+            "name": name,
+            "source_type": "git",
+            "source_ref": source_ref,
+            "central_path": name,
+            "status": "ok",
+        }))
+    }
+
+    #[test]
+    fn export_round_trips_through_member_resolution() {
+        let skills = vec![
+            skill("a", "react", Some("https://github.com/o/r/tree/main/react")),
+            skill("b", "css", None),
+        ];
+        let collection = SkillCollection {
+            id: "c1".to_string(),
+            name: "frontend".to_string(),
+            note: None,
+            skill_ids: vec!["a".to_string(), "b".to_string(), "gone".to_string()],
+            source: None,
+            created_at: 0,
+            updated_at: 0,
+        };
+        let exported = export_collections(&[collection], &skills);
+        let raw = serde_json::to_string(&exported).unwrap();
+        let parsed = parse_collections_json(&raw).unwrap();
+        let members = &parsed.collections[0].skills;
+        assert_eq!(members.len(), 2);
+
+        // Another machine: same source under a different name, and a name match
+        let other = vec![
+            skill(
+                "x",
+                "react-renamed",
+                Some("https://github.com/o/r/tree/main/react"),
+            ),
+            skill("y", "css", Some("/local/css")),
+        ];
+        let resolved = members
+            .iter()
+            .map(|member| resolve_member(&other, member).map(|skill| skill.id.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(resolved, vec![Some("x"), Some("y")]);
+        assert!(parse_collections_json(r#"{"schema_version":2,"collections":[]}"#).is_err());
+    }
+
+    #[test]
+    fn registry_manifest_resolves_members_by_subpath_or_name() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join(REGISTRY_MANIFEST_FILE),
+            r#"{"collections":[
+                {"name":"frontend","skills":["./skills/react/","css","skills/react","nope"]},
+                {"name":" ","skills":["css"]}
+            ]}"#,
+        )
+        .unwrap();
+        let candidates = vec![
+            GitSkillCandidate {
+                name: "react".to_string(),
+                description: None,
+                subpath: "skills/react".to_string(),
+            },
+            GitSkillCandidate {
+                name: "css".to_string(),
+                description: None,
+                subpath: "skills/css".to_string(),
+            },
+        ];
+        let collections = registry_collections(temp.path(), &candidates).unwrap();
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].subpaths, vec!["skills/react", "skills/css"]);
+        assert_eq!(collections[0].missing, vec!["nope"]);
+
+        let empty = tempfile::tempdir().unwrap();
+        assert!(registry_collections(empty.path(), &candidates)
+            .unwrap()
+            .is_empty());
+    }
+}
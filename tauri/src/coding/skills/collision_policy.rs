@@ -0,0 +1,361 @@
+//! Per-tool policy for skill names that collide in a tool dir
+//!
+//! Managed skills whose names differ only by case or Unicode form share one
+//! directory of a case-insensitive tool dir (see `name_collision`). When a
+//! skill is synced into a tool, its name is checked against the entries of
+//! the tool dir and the other managed skills, and the tool's policy decides:
+//!
+//! - `block`: refuse with `NAME_CASE_COLLISION|name|existing|suggestion`
+//! - `auto_suffix`: sync into `name-2`, `name-3`, ... in that tool only; the
+//!   skill keeps its name in the central repo and in other tools, and
+//!   re-syncs keep the suffixed target
+//! - `priority`: the skill listed first (`sort_index`) keeps the name. A
+//!   lower one is refused with `NAME_COLLISION_PRIORITY|name|winner`; a
+//!   higher one replaces the lower one's target in that tool.
+//!
+//! Entries in the tool dir that belong to no managed skill are always
+//! refused. Policies live in `skill_settings:skills.name_collision_policies`
+//! as a default plus per-tool overrides; `block` is the default.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::name_collision::{suggest_rename, NAME_CASE_COLLISION};
+use super::types::now_ms;
+use super::unicode_names::nfc_name;
+use crate::db::SqliteDbState;
+
+const POLICIES_SETTINGS_KEY: &str = "name_collision_policies";
+
+/// Error prefix: `NAME_COLLISION_PRIORITY|name|winner`
+pub const NAME_COLLISION_PRIORITY: &str = "NAME_COLLISION_PRIORITY";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionPolicy {
+    #[default]
+    Block,
+    AutoSuffix,
+    Priority,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NameCollisionPolicies {
+    #[serde(default)]
+    pub default: CollisionPolicy,
+    /// Tool key -> policy replacing `default` for that tool
+    #[serde(default)]
+    pub per_tool: BTreeMap<String, CollisionPolicy>,
+}
+
+impl NameCollisionPolicies {
+    pub fn policy_for(&self, tool: &str) -> CollisionPolicy {
+        self.per_tool.get(tool).copied().unwrap_or(self.default)
+    }
+}
+
+pub fn load_policies(state: &SqliteDbState) -> NameCollisionPolicies {
+    read_skill_settings_value_from_sqlite(state, POLICIES_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_policies(
+    state: &SqliteDbState,
+    policies: &NameCollisionPolicies,
+) -> Result<(), String> {
+    let value = serde_json::to_value(policies)
+        .map_err(|e| format!("Failed to serialize name collision policies: {}", e))?;
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            POLICIES_SETTINGS_KEY: value,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Another managed skill competing for names in a tool dir
+#[derive(Clone, Debug)]
+pub struct NameClaim {
+    pub skill_id: String,
+    pub name: String,
+    pub sort_index: i32,
+    /// Entry name of its target in the tool dir, when synced there
+    pub target_name: Option<String>,
+}
+
+/// Where a skill goes in the tool dir
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TargetName {
+    /// Under the skill's own name
+    Own,
+    /// Under this name instead (`auto_suffix`)
+    Renamed(String),
+    /// Under the own name, after the targets of these skills are removed
+    /// (`priority`)
+    Displace(Vec<String>),
+}
+
+fn folded(name: &str) -> String {
+    nfc_name(name).to_lowercase()
+}
+
+/// Same directory on a case-insensitive file system, spelled differently
+fn differs_by_case(name: &str, other: &str) -> bool {
+    nfc_name(name) != nfc_name(other) && folded(name) == folded(other)
+}
+
+/// `claim` holds `name`: its skill name differs only by case, or its target
+/// already sits at that entry
+fn claims_name(claim: &NameClaim, name: &str) -> bool {
+    differs_by_case(name, &claim.name)
+        || claim
+            .target_name
+            .as_deref()
+            .is_some_and(|target| folded(target) == folded(name))
+}
+
+/// Entries of the tool dir in the way of `name` that are no claim's target.
+/// An entry spelled exactly like `name` is the skill's own (overwrite flow).
+fn unclaimed_entries<'a>(name: &str, entries: &'a [String], claims: &[NameClaim]) -> Vec<&'a str> {
+    entries
+        .iter()
+        .map(String::as_str)
+        .filter(|entry| differs_by_case(name, entry))
+        .filter(|entry| {
+            !claims.iter().any(|claim| {
+                claim
+                    .target_name
+                    .as_deref()
+                    .is_some_and(|target| folded(target) == folded(entry))
+            })
+        })
+        .collect()
+}
+
+/// First entry or claim in the way of `name`
+fn first_collision<'a>(
+    name: &str,
+    entries: &'a [String],
+    claims: &'a [NameClaim],
+) -> Option<&'a str> {
+    unclaimed_entries(name, entries, claims)
+        .into_iter()
+        .next()
+        .or_else(|| {
+            claims
+                .iter()
+                .find(|claim| claims_name(claim, name))
+                .map(|claim| claim.name.as_str())
+        })
+}
+
+/// `name-<digits>`
+fn is_suffixed(name: &str, entry: &str) -> bool {
+    entry
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Entry name of `previous_target` when it is an `auto_suffix` rename of
+/// `name` directly inside `tool_root`
+pub fn renamed_target_name(name: &str, tool_root: &Path, previous_target: &str) -> Option<String> {
+    let path = Path::new(previous_target);
+    if path.parent()? != tool_root {
+        return None;
+    }
+    let entry = path.file_name()?.to_string_lossy();
+    is_suffixed(name, &entry).then(|| entry.to_string())
+}
+
+/// The `auto_suffix` rename of `target`, the skill's own entry, that a
+/// previous sync left at `previous_target`; re-syncs keep it where it is
+pub fn renamed_target(name: &str, target: &Path, previous_target: &str) -> Option<PathBuf> {
+    let tool_root = target.parent()?;
+    renamed_target_name(name, tool_root, previous_target).map(|renamed| tool_root.join(renamed))
+}
+
+/// Decide where `name` goes in a tool dir holding `entries` (minus the
+/// skill's own target), with `claims` from the other managed skills.
+/// `previous_name` is the skill's current suffixed entry, kept while free.
+pub fn resolve_target_name(
+    name: &str,
+    sort_index: i32,
+    policy: CollisionPolicy,
+    entries: &[String],
+    claims: &[NameClaim],
+    previous_name: Option<&str>,
+) -> Result<TargetName, String> {
+    let Some(other) = first_collision(name, entries, claims) else {
+        return Ok(TargetName::Own);
+    };
+    let taken = entries
+        .iter()
+        .map(String::as_str)
+        .chain(claims.iter().map(|claim| claim.name.as_str()))
+        .chain(
+            claims
+                .iter()
+                .filter_map(|claim| claim.target_name.as_deref()),
+        )
+        .collect::<Vec<_>>();
+    let blocked = |other: &str| {
+        format!(
+            "{NAME_CASE_COLLISION}|{name}|{other}|{}",
+            suggest_rename(name, taken.iter().copied())
+        )
+    };
+
+    match policy {
+        CollisionPolicy::Block => Err(blocked(other)),
+        CollisionPolicy::AutoSuffix => {
+            let kept = previous_name.filter(|previous| {
+                is_suffixed(name, previous)
+                    && first_collision(previous, entries, claims).is_none()
+                    && !taken.iter().any(|entry| folded(entry) == folded(previous))
+            });
+            Ok(TargetName::Renamed(match kept {
+                Some(previous) => previous.to_string(),
+                None => suggest_rename(name, taken.iter().copied()),
+            }))
+        }
+        CollisionPolicy::Priority => {
+            if let Some(entry) = unclaimed_entries(name, entries, claims).first() {
+                return Err(blocked(entry));
+            }
+            let rivals = claims
+                .iter()
+                .filter(|claim| claims_name(claim, name))
+                .collect::<Vec<_>>();
+            if let Some(winner) = rivals
+                .iter()
+                .find(|claim| (claim.sort_index, claim.name.as_str()) < (sort_index, name))
+            {
+                return Err(format!("{NAME_COLLISION_PRIORITY}|{name}|{}", winner.name));
+            }
+            let displaced = rivals
+                .iter()
+                .filter(|claim| claim.target_name.is_some())
+                .map(|claim| claim.skill_id.clone())
+                .collect::<Vec<_>>();
+            Ok(if displaced.is_empty() {
+                TargetName::Own
+            } else {
+                TargetName::Displace(displaced)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(id: &str, name: &str, sort_index: i32, target_name: Option<&str>) -> NameClaim {
+        NameClaim {
+            skill_id: id.to_string(),
+            name: name.to_string(),
+            sort_index,
+            target_name: target_name.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn policies_resolve_a_case_collision() {
+        let entries = vec!["MySkill".to_string(), "other".to_string()];
+        let claims = vec![claim("upper", "MySkill", 1, Some("MySkill"))];
+        let resolve = |policy, sort_index, previous| {
+            resolve_target_name("myskill", sort_index, policy, &entries, &claims, previous)
+        };
+
+        assert_eq!(
+            resolve(CollisionPolicy::Block, 0, None).unwrap_err(),
+            "NAME_CASE_COLLISION|myskill|MySkill|myskill-2"
+        );
+        assert_eq!(
+            resolve(CollisionPolicy::AutoSuffix, 0, None).unwrap(),
+            TargetName::Renamed("myskill-2".to_string())
+        );
+        assert_eq!(
+            resolve(CollisionPolicy::AutoSuffix, 0, Some("myskill-5")).unwrap(),
+            TargetName::Renamed("myskill-5".to_string())
+        );
+        assert_eq!(
+            resolve(CollisionPolicy::Priority, 0, None).unwrap(),
+            TargetName::Displace(vec!["upper".to_string()])
+        );
+        assert_eq!(
+            resolve(CollisionPolicy::Priority, 2, None).unwrap_err(),
+            "NAME_COLLISION_PRIORITY|myskill|MySkill"
+        );
+        // No collision: every policy keeps the name
+        assert_eq!(
+            resolve_target_name(
+                "fresh",
+                0,
+                CollisionPolicy::Priority,
+                &entries,
+                &claims,
+                None
+            )
+            .unwrap(),
+            TargetName::Own
+        );
+    }
+
+    #[test]
+    fn priority_never_displaces_unmanaged_entries() {
+        let entries = vec!["MySkill".to_string()];
+        assert!(
+            resolve_target_name("myskill", 0, CollisionPolicy::Priority, &entries, &[], None)
+                .unwrap_err()
+                .starts_with("NAME_CASE_COLLISION|")
+        );
+    }
+
+    #[test]
+    fn suffixed_target_of_another_skill_is_taken() {
+        // `review` synced to codex as `review-2`; a skill named `review-2`
+        // must not overwrite it
+        let entries = vec!["review-2".to_string()];
+        let claims = vec![claim("first", "review", 0, Some("review-2"))];
+        assert_eq!(
+            resolve_target_name(
+                "review-2",
+                1,
+                CollisionPolicy::Block,
+                &entries,
+                &claims,
+                None
+            )
+            .unwrap_err(),
+            "NAME_CASE_COLLISION|review-2|review|review-2-2"
+        );
+    }
+
+    #[test]
+    fn renamed_target_is_recognised_only_inside_the_tool_dir() {
+        let root = Path::new("/home/me/.codex/skills");
+        assert_eq!(
+            renamed_target_name("myskill", root, "/home/me/.codex/skills/myskill-2").as_deref(),
+            Some("myskill-2")
+        );
+        assert_eq!(
+            renamed_target_name("myskill", root, "/home/me/.codex/skills/myskill"),
+            None
+        );
+        assert_eq!(
+            renamed_target_name("myskill", root, "/old/skills/myskill-2"),
+            None
+        );
+        assert_eq!(
+            renamed_target_name("myskill", root, "/home/me/.codex/skills/myskill-beta"),
+            None
+        );
+    }
+}
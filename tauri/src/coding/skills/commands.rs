@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Component, Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
@@ -6,32 +6,60 @@ use std::time::Duration;
 use tauri::{AppHandle, Emitter, Runtime, State};
 
 use super::adapter::parse_sync_details;
+use super::archive::{self, ARCHIVED_STATUS};
+use super::artifact_sweeper;
 use super::cache_cleanup::{
     cleanup_git_cache_dirs, get_git_cache_cleanup_days, get_git_cache_ttl_secs,
     set_git_cache_cleanup_days as set_cleanup_days,
 };
 use super::central_repo::{
-    clear_central_repo_path, ensure_central_repo, expand_home_path, resolve_central_repo_path,
-    resolve_default_central_repo_path, resolve_skill_central_path, save_central_repo_path,
-    to_relative_central_path,
+    clear_central_repo_path, ensure_central_repo, expand_home_path, load_relative_symlinks,
+    resolve_central_repo_path, resolve_default_central_repo_path, resolve_skill_central_path,
+    save_central_repo_path, save_relative_symlinks, to_relative_central_path,
 };
+use super::central_watcher;
+use super::collections;
+use super::collision_policy::{self, NameClaim, NameCollisionPolicies, TargetName};
+use super::compatibility;
+use super::confirmation::{self, ConfirmationDto, DangerousOp, OVERWRITE_TARGETS_THRESHOLD};
 use super::content_hash::hash_dir;
 use super::git_fetcher::{set_proxy, GitProxyMode};
 use super::installer::{
-    install_git_skill, install_git_skill_from_selection, install_local_skill,
-    install_local_skill_from_selection, list_git_skills, list_local_skills,
-    update_managed_skill_from_source,
+    install_git_skill, install_git_skill_from_selection, install_linked_folder_skill,
+    install_local_skill, install_local_skill_from_selection, install_skill_from_archive_url,
+    list_git_skills, list_local_skills, stage_update_source, update_managed_skill_from_source,
+};
+use super::linked_folder::{self, LinkedFolderStatusDto, LINKED_FOLDER_SOURCE_TYPE};
+use super::local_changes::{self, LocalChangesResolution};
+use super::name_collision;
+use super::onboarding::{
+    build_onboarding_plan, list_system_skills, save_scan_index, scan_extra_roots, ExtraRootScanDto,
+    OnboardingScanIndex,
 };
-use super::onboarding::build_onboarding_plan;
 use super::path_executor::{
-    remove_skill_target_checked, sync_skill_to_target, target_path_changed,
-    validate_skill_sync_target,
+    convert_target_mode, plan_skill_sync, remove_skill_target_checked, sync_skill_to_target,
+    sync_to_targets, target_path_changed, validate_skill_sync_target,
 };
+use super::path_rules;
+use super::project_vars::{self, ProjectRenderResultDto};
+use super::provenance;
+use super::rotation;
+use super::skill_ignore;
 use super::skill_store;
-use super::sync_engine::{copy_dir_recursive, ensure_source_target_not_overlapping};
+use super::source_mirror::{self, SkillSourceMirror};
+use super::storage_report::{self, StorageReportDto, StorageRoots};
+use super::sync_context::{self, relative_symlinks};
+use super::sync_engine::{
+    copy_dir_recursive, ensure_source_target_not_overlapping, remove_path, verify_copy,
+};
+use super::sync_journal;
+use super::target_conflict::{target_conflict, TargetConflictReport};
+use super::target_guard;
+use super::target_marker;
 use super::tool_adapters::{
     adapter_by_key, get_all_tool_adapters, is_tool_installed_with_state_async,
-    resolve_runtime_skills_path_with_state_async, runtime_adapter_by_key,
+    normalize_preferred_sync_mode, resolve_runtime_skills_path_with_state_async,
+    runtime_adapter_by_key, RuntimeToolAdapter,
 };
 use super::types::{
     now_ms, AdoptCentralSkillsResultDto, ApplyCentralRepoPathOptionsDto,
@@ -39,29 +67,42 @@ use super::types::{
     CentralRepoPathPreviewDto, CentralRepoPathStatusDto, CentralRepoScanDto,
     CentralRepoTargetImpactDto, CentralSkillMatchDto, CentralSkillRepairCandidateDto, CustomTool,
     CustomToolDto, DeleteManagedSkillOptionsDto, DetectedCentralSkillDto, GitSkillCandidate,
-    InstallResultDto, ManagedSkillDto, ManagedSkillSummaryDto, OnboardingPlan, Skill,
-    SkillGroupDto, SkillGroupRecord, SkillInventoryGroupJson, SkillInventoryJson,
-    SkillInventoryPreviewDto, SkillInventorySkillJson, SkillRepo, SkillRepoDto, SkillTarget,
-    SkillTargetDto, SyncResultDto, ToolInfoDto, ToolStatusDto, UpdateResultDto,
+    InstallResult, InstallResultDto, ManagedSkillDto, ManagedSkillSummaryDto, OnboardingPlan,
+    Skill, SkillGroupDto, SkillGroupRecord, SkillInventoryGroupJson, SkillInventoryJson,
+    SkillInventoryPreviewDto, SkillInventorySkillJson, SkillPathRules, SkillRepo, SkillRepoDto,
+    SkillTarget, SkillTargetDto, SyncMode, SyncOutcome, SyncPreviewDto, SyncResultDto,
+    SyncTargetSpec, ToolInfoDto, ToolStatusDto, UpdateResultDto,
 };
+use super::update_preview::{self, SkillUpdatePreviewDto};
+use super::variants;
+use super::write_protect;
+use crate::coding::config_snapshot::snapshot::snapshots_root;
 use crate::coding::runtime_location;
+use crate::coding::tools::builtin_adapter;
+use crate::coding::tools::post_sync::{schedule_post_sync, PostSyncScope};
+use crate::coding::tools::wasm_plugins::is_plugin_tool;
 use crate::http_client;
+use crate::settings::policy;
+use crate::settings::read_only::ensure_writable;
 use crate::SqliteDbState;
 
-fn format_error(err: anyhow::Error) -> String {
+pub(super) fn format_error(err: anyhow::Error) -> String {
     let first = err.to_string();
     // Frontend relies on these prefixes for special flows
     if first.starts_with("MULTI_SKILLS|")
         || first.starts_with("TARGET_EXISTS|")
         || first.starts_with("TOOL_NOT_INSTALLED|")
         || first.starts_with("SKILL_DISABLED|")
+        || first.starts_with("NAME_CASE_COLLISION|")
+        || first.starts_with("NAME_COLLISION_PRIORITY|")
+        || first.starts_with(policy::POLICY_ERROR_PREFIX)
     {
         return first;
     }
     format!("{:#}", err)
 }
 
-fn normalize_optional_text(value: Option<String>) -> Option<String> {
+pub(super) fn normalize_optional_text(value: Option<String>) -> Option<String> {
     value.and_then(|text| {
         let trimmed = text.trim();
         if trimmed.is_empty() {
@@ -72,7 +113,7 @@ fn normalize_optional_text(value: Option<String>) -> Option<String> {
     })
 }
 
-fn normalize_optional_id(value: Option<String>) -> Option<String> {
+pub(super) fn normalize_optional_id(value: Option<String>) -> Option<String> {
     normalize_optional_text(value)
 }
 
@@ -225,11 +266,20 @@ pub async fn skills_get_tool_status(
             .await
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_default();
+        let dangling_links = if skills_path.is_empty() {
+            Vec::new()
+        } else {
+            artifact_sweeper::find_dangling_links(Path::new(&skills_path))
+                .into_iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect()
+        };
         tools.push(ToolInfoDto {
             key: adapter.key.clone(),
             label: adapter.display_name.clone(),
             installed: ok,
             skills_dir: skills_path,
+            dangling_links,
         });
         // Only track built-in tools for "installed" detection
         // Custom tools are always "installed" but shouldn't trigger save
@@ -501,7 +551,7 @@ fn scan_central_dir_recursive(
     Ok(())
 }
 
-fn scan_central_dir(
+pub(super) fn scan_central_dir(
     base: &Path,
 ) -> Result<
     (
@@ -755,7 +805,7 @@ async fn build_central_repo_path_preview<R: Runtime>(
     })
 }
 
-async fn refresh_central_skill_hash_if_needed(
+pub(super) async fn refresh_central_skill_hash_if_needed(
     state: &SqliteDbState,
     skill: &mut Skill,
     source_path: &Path,
@@ -821,7 +871,7 @@ fn central_path_update_for_existing_match(
     (relative_path.to_string(), hash_dir(&source_path).ok())
 }
 
-async fn adopt_detected_central_skill(
+pub(super) async fn adopt_detected_central_skill(
     state: &SqliteDbState,
     central_dir: &Path,
     relative_path: &str,
@@ -837,8 +887,15 @@ async fn adopt_detected_central_skill(
     {
         return Ok(false);
     }
+    let managed_names = skill_store::get_managed_skills(state)
+        .await?
+        .into_iter()
+        .map(|skill| skill.name)
+        .collect::<Vec<_>>();
+    name_collision::ensure_no_case_collision(&detected.name, &managed_names)?;
 
     let now = now_ms();
+    let license = provenance::detect_license(&source_path, None);
     let skill = Skill {
         id: String::new(),
         name: detected.name,
@@ -859,6 +916,12 @@ async fn adopt_detected_central_skill(
         disabled_previous_tools: Vec::new(),
         enabled_tools: Vec::new(),
         sync_details: Some(serde_json::Value::Object(serde_json::Map::new())),
+        license,
+        provenance: None,
+        tags: Vec::new(),
+        compatibility: Default::default(),
+        localizations: Default::default(),
+        write_protected: false,
     };
     skill_store::upsert_skill(state, &skill).await?;
     Ok(true)
@@ -881,6 +944,8 @@ pub async fn skills_set_central_repo_path(
     state: State<'_, SqliteDbState>,
     path: String,
 ) -> Result<String, String> {
+    ensure_writable("skills_set_central_repo_path")?;
+    policy::ensure_central_repo_path_unlocked()?;
     let new_base = expand_home_path(&path).map_err(|e| format_error(e))?;
     if !new_base.is_absolute() {
         return Err("storage path must be absolute".to_string());
@@ -950,6 +1015,7 @@ pub async fn skills_adopt_central_repo_skills<R: Runtime>(
     state: State<'_, SqliteDbState>,
     relativePaths: Vec<String>,
 ) -> Result<AdoptCentralSkillsResultDto, String> {
+    ensure_writable("skills_adopt_central_repo_skills")?;
     let central_dir = resolve_central_repo_path(&app, &state)
         .await
         .map_err(|e| format_error(e))?;
@@ -974,6 +1040,7 @@ pub async fn skills_repair_central_repo_skill<R: Runtime>(
     skillId: String,
     relativePath: String,
 ) -> Result<AdoptCentralSkillsResultDto, String> {
+    ensure_writable("skills_repair_central_repo_skill")?;
     let central_dir = resolve_central_repo_path(&app, &state)
         .await
         .map_err(|e| format_error(e))?;
@@ -1000,6 +1067,8 @@ pub async fn skills_apply_central_repo_path_change<R: Runtime>(
     path: String,
     options: ApplyCentralRepoPathOptionsDto,
 ) -> Result<ApplyCentralRepoPathResultDto, String> {
+    ensure_writable("skills_apply_central_repo_path_change")?;
+    policy::ensure_central_repo_path_unlocked()?;
     let requested_path = if options.use_default_path {
         resolve_default_central_repo_path(&app)
             .map_err(|e| format_error(e))?
@@ -1072,6 +1141,7 @@ pub async fn skills_apply_central_repo_path_change<R: Runtime>(
         let target_path = target_dir.join(&relative_path);
         match copy_skill_source_for_migration(&source_path, &target_path) {
             Ok(warning) => {
+                write_protect::protect_or_warn(&target_path, skill.write_protected);
                 migrated_count += 1;
                 central_path_updates.insert(
                     skill.id.clone(),
@@ -1186,12 +1256,20 @@ pub async fn skills_get_managed_skills(
     state: State<'_, SqliteDbState>,
 ) -> Result<Vec<ManagedSkillDto>, String> {
     let skills = skill_store::get_managed_skills(&state).await?;
-    let groups = skill_store::get_skill_groups(&state).await?;
+    managed_skill_dtos(&app, &state, skills).await
+}
+
+pub(super) async fn managed_skill_dtos<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    skills: Vec<Skill>,
+) -> Result<Vec<ManagedSkillDto>, String> {
+    let groups = skill_store::get_skill_groups(state).await?;
     let group_names: HashMap<String, String> = groups
         .into_iter()
         .map(|group| (group.id, group.name))
         .collect();
-    let central_dir = resolve_central_repo_path(&app, &state)
+    let central_dir = resolve_central_repo_path(app, state)
         .await
         .map_err(|e| format_error(e))?;
 
@@ -1205,6 +1283,7 @@ pub async fn skills_get_managed_skills(
                 status: t.status,
                 target_path: t.target_path,
                 synced_at: t.synced_at,
+                path_rules: t.path_rules,
             })
             .collect();
 
@@ -1215,8 +1294,22 @@ pub async fn skills_get_managed_skills(
             .as_ref()
             .and_then(|group_id| group_names.get(group_id).cloned())
             .or(skill.user_group.clone());
-        let description = read_skill_description(&resolved_path, &skill.content_hash);
-        let source_diagnosis = diagnose_skill_source_path(&resolved_path);
+        // Archived skills have no central directory; read from the archive's SKILL.md copy
+        let (description, source_diagnosis) = if skill.status == ARCHIVED_STATUS {
+            let description = archive::archive_dir(app, &skill.id)
+                .ok()
+                .and_then(|dir| read_skill_description(&dir, &skill.content_hash));
+            let diagnosis = SkillSourceDiagnosis {
+                health: "ok".to_string(),
+                error: None,
+            };
+            (description, diagnosis)
+        } else {
+            (
+                read_skill_description(&resolved_path, &skill.content_hash),
+                diagnose_skill_source_path(&resolved_path),
+            )
+        };
 
         result.push(ManagedSkillDto {
             id: skill.id,
@@ -1240,6 +1333,12 @@ pub async fn skills_get_managed_skills(
             source_error: source_diagnosis.error,
             enabled_tools: skill.enabled_tools,
             targets,
+            license: skill.license,
+            provenance: skill.provenance,
+            tags: skill.tags,
+            compatibility: skill.compatibility,
+            localizations: skill.localizations,
+            write_protected: skill.write_protected,
         });
     }
 
@@ -1442,9 +1541,24 @@ mod skill_source_tests {
 
 // --- Install Skills ---
 
+pub(super) fn install_result_dto(state: &SqliteDbState, result: InstallResult) -> InstallResultDto {
+    let license_warning = provenance::license_warning(
+        result.license.as_deref(),
+        &provenance::load_disallowed_licenses(state),
+    );
+    InstallResultDto {
+        skill_id: result.skill_id,
+        name: result.name,
+        central_path: result.central_path.to_string_lossy().to_string(),
+        content_hash: result.content_hash,
+        license: result.license,
+        license_warning,
+    }
+}
+
 /// Resolve local skill source paths so `~/...` / `%APPDATA%/...` aliases work
 /// the same way as central-repo path inputs.
-fn resolve_local_source_path(source_path: &str) -> Result<PathBuf, String> {
+pub(super) fn resolve_local_source_path(source_path: &str) -> Result<PathBuf, String> {
     expand_home_path(source_path).map_err(|e| format_error(e))
 }
 
@@ -1456,6 +1570,7 @@ pub async fn skills_install_local(
     sourcePath: String,
     overwrite: Option<bool>,
 ) -> Result<InstallResultDto, String> {
+    ensure_writable("skills_install_local")?;
     let source_path = resolve_local_source_path(&sourcePath)?;
     let result = install_local_skill(
         &app,
@@ -1466,12 +1581,7 @@ pub async fn skills_install_local(
     .await
     .map_err(|e| format_error(e))?;
 
-    Ok(InstallResultDto {
-        skill_id: result.skill_id,
-        name: result.name,
-        central_path: result.central_path.to_string_lossy().to_string(),
-        content_hash: result.content_hash,
-    })
+    Ok(install_result_dto(&state, result))
 }
 
 #[tauri::command]
@@ -1492,6 +1602,7 @@ pub async fn skills_install_local_selection(
     subpath: String,
     overwrite: Option<bool>,
 ) -> Result<InstallResultDto, String> {
+    ensure_writable("skills_install_local_selection")?;
     let source_path = resolve_local_source_path(&sourcePath)?;
     let result = install_local_skill_from_selection(
         &app,
@@ -1503,12 +1614,7 @@ pub async fn skills_install_local_selection(
     .await
     .map_err(|e| format_error(e))?;
 
-    Ok(InstallResultDto {
-        skill_id: result.skill_id,
-        name: result.name,
-        central_path: result.central_path.to_string_lossy().to_string(),
-        content_hash: result.content_hash,
-    })
+    Ok(install_result_dto(&state, result))
 }
 
 #[tauri::command]
@@ -1520,6 +1626,7 @@ pub async fn skills_install_git(
     branch: Option<String>,
     overwrite: Option<bool>,
 ) -> Result<InstallResultDto, String> {
+    ensure_writable("skills_install_git")?;
     let result = install_git_skill(
         &app,
         &state,
@@ -1530,12 +1637,31 @@ pub async fn skills_install_git(
     .await
     .map_err(|e| format_error(e))?;
 
-    Ok(InstallResultDto {
-        skill_id: result.skill_id,
-        name: result.name,
-        central_path: result.central_path.to_string_lossy().to_string(),
-        content_hash: result.content_hash,
-    })
+    Ok(install_result_dto(&state, result))
+}
+
+/// Install a skill from a zip / tarball URL, verifying `checksum` when given
+#[tauri::command]
+pub async fn skills_install_archive_url(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    url: String,
+    checksum: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<InstallResultDto, String> {
+    ensure_writable("skills_install_archive_url")?;
+    let checksum = normalize_optional_text(checksum);
+    let result = install_skill_from_archive_url(
+        &app,
+        &state,
+        url.trim(),
+        checksum.as_deref(),
+        overwrite.unwrap_or(false),
+    )
+    .await
+    .map_err(format_error)?;
+
+    Ok(install_result_dto(&state, result))
 }
 
 #[tauri::command]
@@ -1546,6 +1672,7 @@ pub async fn skills_list_git_skills(
     repoUrl: String,
     branch: Option<String>,
 ) -> Result<Vec<GitSkillCandidate>, String> {
+    policy::ensure_source_allowed(&repoUrl)?;
     // Initialize proxy from app settings
     let proxy_result = http_client::get_proxy_from_settings(&state).await.ok();
     let proxy_mode = match proxy_result {
@@ -1556,10 +1683,11 @@ pub async fn skills_list_git_skills(
     set_proxy(proxy_mode);
 
     let ttl = get_git_cache_ttl_secs(&state).await;
+    let mirror = source_mirror::load_source_mirror(&state);
     let branch_clone = branch.clone();
 
     tokio::task::spawn_blocking(move || {
-        list_git_skills(&app, ttl, &repoUrl, branch_clone.as_deref())
+        list_git_skills(&app, ttl, &mirror, &repoUrl, branch_clone.as_deref())
     })
     .await
     .map_err(|e| e.to_string())?
@@ -1576,6 +1704,7 @@ pub async fn skills_install_git_selection(
     branch: Option<String>,
     overwrite: Option<bool>,
 ) -> Result<InstallResultDto, String> {
+    ensure_writable("skills_install_git_selection")?;
     let result = install_git_skill_from_selection(
         &app,
         &state,
@@ -1587,17 +1716,12 @@ pub async fn skills_install_git_selection(
     .await
     .map_err(|e| format_error(e))?;
 
-    Ok(InstallResultDto {
-        skill_id: result.skill_id,
-        name: result.name,
-        central_path: result.central_path.to_string_lossy().to_string(),
-        content_hash: result.content_hash,
-    })
+    Ok(install_result_dto(&state, result))
 }
 
 // --- Sync Skills ---
 
-async fn resolve_skill_source_path<R: Runtime>(
+pub(super) async fn resolve_skill_source_path<R: Runtime>(
     app: &AppHandle<R>,
     state: &SqliteDbState,
     skill: &Skill,
@@ -1611,14 +1735,171 @@ async fn resolve_skill_source_path<R: Runtime>(
     ))
 }
 
-async fn sync_skill_to_tool_record(
+/// Entries of `tool_root` and other managed skills competing with `skill` for
+/// a name there. Entries that are this skill's own earlier target, or already
+/// resolve to its source, are left out.
+async fn tool_name_claims(
+    state: &SqliteDbState,
+    skill: &Skill,
+    tool: &str,
+    tool_root: &Path,
+    source_path: &Path,
+    previous_target: Option<&SkillTarget>,
+) -> Result<(Vec<String>, Vec<(Skill, NameClaim)>), String> {
+    let canonical_source = std::fs::canonicalize(source_path).ok();
+    let entries = name_collision::dir_entry_names(tool_root)
+        .into_iter()
+        .filter(|entry| {
+            let path = tool_root.join(entry);
+            let is_previous = previous_target
+                .is_some_and(|target| paths_equivalent(Path::new(&target.target_path), &path));
+            let is_source =
+                canonical_source.is_some() && std::fs::canonicalize(&path).ok() == canonical_source;
+            !is_previous && !is_source
+        })
+        .collect::<Vec<_>>();
+    let claims = skill_store::get_managed_skills(state)
+        .await?
+        .into_iter()
+        .filter(|other| other.id != skill.id)
+        .map(|other| {
+            let target_name = parse_sync_details(&other)
+                .into_iter()
+                .find(|target| target.tool == tool)
+                .and_then(|target| {
+                    let path = PathBuf::from(&target.target_path);
+                    if path.parent() != Some(tool_root) {
+                        return None;
+                    }
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                });
+            let claim = NameClaim {
+                skill_id: other.id.clone(),
+                name: other.name.clone(),
+                sort_index: other.sort_index,
+                target_name,
+            };
+            (other, claim)
+        })
+        .collect();
+    Ok((entries, claims))
+}
+
+/// Tool dir and target of `skill` in the tool of `adapter`, after the tool's
+/// name collision policy (`collision_policy`). Targets of lower-priority
+/// skills that the `priority` policy displaces are returned, not removed.
+async fn plan_tool_target(
+    state: &SqliteDbState,
+    skill: &Skill,
+    adapter: &RuntimeToolAdapter,
+    source_path: &Path,
+    previous_target: Option<&SkillTarget>,
+) -> Result<(PathBuf, PathBuf, Vec<(Skill, SkillTarget)>), String> {
+    let (tool_root, target) =
+        target_guard::resolve_checked_target(state, adapter, &skill.name).await?;
+    let (entries, claims) = tool_name_claims(
+        state,
+        skill,
+        &adapter.key,
+        &tool_root,
+        source_path,
+        previous_target,
+    )
+    .await?;
+    let rival_claims: Vec<NameClaim> = claims.iter().map(|(_, claim)| claim.clone()).collect();
+    let previous_name = previous_target.and_then(|previous| {
+        collision_policy::renamed_target_name(&skill.name, &tool_root, &previous.target_path)
+    });
+    let resolved = collision_policy::resolve_target_name(
+        &skill.name,
+        skill.sort_index,
+        collision_policy::load_policies(state).policy_for(&adapter.key),
+        &entries,
+        &rival_claims,
+        previous_name.as_deref(),
+    )?;
+    match resolved {
+        TargetName::Own => Ok((tool_root, target, Vec::new())),
+        TargetName::Renamed(name) => {
+            let (tool_root, target) =
+                target_guard::resolve_checked_target(state, adapter, &name).await?;
+            Ok((tool_root, target, Vec::new()))
+        }
+        TargetName::Displace(skill_ids) => {
+            let displaced = claims
+                .into_iter()
+                .filter(|(other, _)| skill_ids.contains(&other.id))
+                .filter_map(|(other, _)| {
+                    let target = parse_sync_details(&other)
+                        .into_iter()
+                        .find(|target| target.tool == adapter.key)?;
+                    Some((other, target))
+                })
+                .collect();
+            Ok((tool_root, target, displaced))
+        }
+    }
+}
+
+/// `plan_tool_target`, removing the displaced targets of lower-priority
+/// skills. A recorded target was synced apart from its own source, so the
+/// overlap check runs against this skill's source.
+pub(crate) async fn resolve_tool_target(
+    state: &SqliteDbState,
+    skill: &Skill,
+    adapter: &RuntimeToolAdapter,
+    source_path: &Path,
+    previous_target: Option<&SkillTarget>,
+) -> Result<(PathBuf, PathBuf), String> {
+    let (tool_root, target, displaced) =
+        plan_tool_target(state, skill, adapter, source_path, previous_target).await?;
+    for (other, other_target) in displaced {
+        log::info!(
+            "[skills] '{}' takes '{}' from lower-priority '{}' on '{}'",
+            skill.name,
+            other_target.target_path,
+            other.name,
+            adapter.key
+        );
+        remove_skill_target_best_effort(&other, source_path, &other_target);
+        skill_store::delete_skill_target(state, &other.id, &adapter.key).await?;
+    }
+    Ok((tool_root, target))
+}
+
+/// A tool sync resolved against the database, ready to touch the filesystem
+struct PreparedToolSync {
+    tool: String,
+    tool_root: PathBuf,
+    target: PathBuf,
+    /// The skill source, or the tool's variant snapshot
+    source_path: PathBuf,
+    mode: SyncMode,
+    previous_target: Option<SkillTarget>,
+    path_rules: SkillPathRules,
+}
+
+impl PreparedToolSync {
+    fn spec(&self, overwrite: bool) -> SyncTargetSpec {
+        SyncTargetSpec {
+            tool_key: self.tool.clone(),
+            target: self.target.clone(),
+            source: Some(self.source_path.clone()),
+            overwrite,
+            mode: self.mode,
+            rules: self.path_rules.clone(),
+        }
+    }
+}
+
+async fn prepare_tool_sync(
     state: &SqliteDbState,
     skill: &Skill,
     tool: &str,
     source_path: &Path,
-    overwrite: bool,
     custom_tools: &[CustomTool],
-) -> Result<SyncResultDto, String> {
+) -> Result<PreparedToolSync, String> {
     let runtime_adapter =
         runtime_adapter_by_key(tool, custom_tools).ok_or_else(|| "unknown tool".to_string())?;
 
@@ -1639,51 +1920,186 @@ async fn sync_skill_to_tool_record(
         ));
     }
 
-    let tool_root = resolve_runtime_skills_path_with_state_async(state.db(), &runtime_adapter)
-        .await
-        .map_err(|e| format_error(e))?;
-    let target = tool_root.join(&skill.name);
+    // Tools assigned to a skill variant sync from the variant snapshot instead
+    let variant_source = variants::resolve_variant_source(state, &skill.id, tool)?;
+    let source_path = variant_source.unwrap_or_else(|| source_path.to_path_buf());
+
     let previous_target = skill_store::get_skill_target(state, &skill.id, tool).await?;
+    let (tool_root, target) = resolve_tool_target(
+        state,
+        skill,
+        &runtime_adapter,
+        &source_path,
+        previous_target.as_ref(),
+    )
+    .await?;
 
-    let result = sync_skill_to_target(
-        tool,
+    // Path rules belong to the tool target and survive re-syncs
+    let path_rules = previous_target
+        .as_ref()
+        .map(|existing| existing.path_rules.clone())
+        .unwrap_or_default();
+    Ok(PreparedToolSync {
+        tool: tool.to_string(),
+        tool_root,
+        target,
         source_path,
-        &target,
-        overwrite,
-        runtime_adapter.force_copy,
-    )
-    .map_err(|err| {
-        let msg = err.to_string();
-        if msg.contains("target already exists") {
-            format!("TARGET_EXISTS|{}", target.to_string_lossy())
-        } else {
-            format_error(err)
-        }
-    })?;
+        mode: runtime_adapter.sync_mode(),
+        previous_target,
+        path_rules,
+    })
+}
+
+fn tool_sync_error(target: &Path, err: anyhow::Error) -> String {
+    let msg = err.to_string();
+    if msg.contains("target already exists") {
+        format!("TARGET_EXISTS|{}", target.to_string_lossy())
+    } else {
+        format_error(err)
+    }
+}
 
-    if let Some(existing_target) = previous_target.as_ref() {
-        if target_path_changed(&existing_target.target_path, &target) {
-            remove_skill_target_best_effort(skill, source_path, existing_target);
+/// Record a finished tool sync: clean up a moved target, mark and store it
+async fn record_tool_sync(
+    state: &SqliteDbState,
+    skill: &Skill,
+    prepared: PreparedToolSync,
+    result: SyncOutcome,
+) -> Result<SyncResultDto, String> {
+    if let Some(existing_target) = prepared.previous_target.as_ref() {
+        if target_path_changed(&existing_target.target_path, &prepared.target) {
+            remove_skill_target_best_effort(skill, &prepared.source_path, existing_target);
         }
     }
 
     let record = SkillTarget {
-        tool: tool.to_string(),
+        tool: prepared.tool.clone(),
         target_path: result.target_path.to_string_lossy().to_string(),
         mode: result.mode_used.as_str().to_string(),
         status: "ok".to_string(),
-        error_message: None,
+        error_message: copy_verification_error(&result),
         synced_at: Some(now_ms()),
+        path_rules: prepared.path_rules,
     };
+    target_marker::mark_target(skill, &record);
     skill_store::upsert_skill_target(state, &skill.id, &record).await?;
+    if let Some(adapter) = builtin_adapter(&prepared.tool) {
+        if let Err(err) = adapter.after_sync(&result.target_path) {
+            log::warn!(
+                "[skills] after-sync step of '{}' for '{}' failed: {}",
+                prepared.tool,
+                skill.name,
+                err
+            );
+        }
+    }
+    schedule_post_sync(
+        state,
+        &prepared.tool,
+        PostSyncScope::Skills,
+        &prepared.tool_root,
+    );
 
     Ok(SyncResultDto {
         mode_used: result.mode_used.as_str().to_string(),
         target_path: result.target_path.to_string_lossy().to_string(),
+        verified: result.verified,
+        mismatches: result.mismatches,
+        file_changes: result.file_changes,
     })
 }
 
-fn disabled_previous_tools_for_skill(skill: &Skill) -> Vec<String> {
+pub(super) async fn sync_skill_to_tool_record(
+    state: &SqliteDbState,
+    skill: &Skill,
+    tool: &str,
+    source_path: &Path,
+    overwrite: bool,
+    custom_tools: &[CustomTool],
+) -> Result<SyncResultDto, String> {
+    let prepared = prepare_tool_sync(state, skill, tool, source_path, custom_tools).await?;
+    let result = sync_skill_to_target(
+        &prepared.source_path,
+        &prepared.target,
+        overwrite,
+        prepared.mode,
+        &prepared.path_rules,
+    )
+    .map_err(|err| tool_sync_error(&prepared.target, err))?;
+    record_tool_sync(state, skill, prepared, result).await
+}
+
+/// `sync_skill_to_tool_record` for several tools, with the filesystem syncs
+/// running in parallel (`sync_to_targets`). Database reads and writes stay
+/// sequential. Returns one result per tool, in the order of `tools`.
+pub(super) async fn sync_skill_to_tool_records(
+    state: &SqliteDbState,
+    skill: &Skill,
+    tools: &[String],
+    source_path: &Path,
+    overwrite: bool,
+    custom_tools: &[CustomTool],
+) -> Vec<(String, Result<SyncResultDto, String>)> {
+    let mut results: Vec<(String, Result<SyncResultDto, String>)> = Vec::new();
+    let mut prepared = Vec::new();
+    for tool in tools {
+        match prepare_tool_sync(state, skill, tool, source_path, custom_tools).await {
+            Ok(ready) => prepared.push(ready),
+            Err(err) => results.push((tool.clone(), Err(err))),
+        }
+    }
+
+    let specs: Vec<SyncTargetSpec> = prepared.iter().map(|ready| ready.spec(overwrite)).collect();
+    let source = source_path.to_path_buf();
+    let outcomes = match tokio::task::spawn_blocking(move || sync_to_targets(&source, specs)).await
+    {
+        Ok(outcomes) => outcomes,
+        Err(err) => {
+            let message = format!("Parallel sync failed: {}", err);
+            results.extend(
+                prepared
+                    .into_iter()
+                    .map(|ready| (ready.tool, Err(message.clone()))),
+            );
+            return sort_by_tools(results, tools);
+        }
+    };
+    for (ready, outcome) in prepared.into_iter().zip(outcomes) {
+        let tool = ready.tool.clone();
+        let result = match outcome {
+            Ok(outcome) => record_tool_sync(state, skill, ready, outcome).await,
+            Err(err) => Err(tool_sync_error(&ready.target, err)),
+        };
+        results.push((tool, result));
+    }
+    sort_by_tools(results, tools)
+}
+
+fn sort_by_tools<T>(mut results: Vec<(String, T)>, tools: &[String]) -> Vec<(String, T)> {
+    results.sort_by_key(|(tool, _)| tools.iter().position(|key| key == tool));
+    results
+}
+
+/// Error message recorded on a target whose copy did not match the source
+/// after the sync; the target stays in place so the user can re-sync it
+pub(super) fn copy_verification_error(outcome: &SyncOutcome) -> Option<String> {
+    if outcome.verified != Some(false) {
+        return None;
+    }
+    let paths: Vec<&str> = outcome
+        .mismatches
+        .iter()
+        .take(5)
+        .map(|mismatch| mismatch.path.as_str())
+        .collect();
+    Some(format!(
+        "Copy differs from the source in {} file(s): {}",
+        outcome.mismatches.len(),
+        paths.join(", ")
+    ))
+}
+
+pub(super) fn disabled_previous_tools_for_skill(skill: &Skill) -> Vec<String> {
     if skill.enabled_tools.is_empty() {
         skill.disabled_previous_tools.clone()
     } else {
@@ -1691,7 +2107,11 @@ fn disabled_previous_tools_for_skill(skill: &Skill) -> Vec<String> {
     }
 }
 
-fn remove_skill_target_best_effort(skill: &Skill, source_path: &Path, target: &SkillTarget) {
+pub(super) fn remove_skill_target_best_effort(
+    skill: &Skill,
+    source_path: &Path,
+    target: &SkillTarget,
+) {
     if let Err(err) = remove_skill_target_checked(source_path, &target.target_path) {
         log::warn!(
             "Failed to clean Skills target '{}' for skill '{}' on '{}': {}",
@@ -1703,7 +2123,7 @@ fn remove_skill_target_best_effort(skill: &Skill, source_path: &Path, target: &S
     }
 }
 
-async fn remove_skill_targets_best_effort(
+pub(super) async fn remove_skill_targets_best_effort(
     state: &SqliteDbState,
     skill: &Skill,
     source_path: Option<&Path>,
@@ -1726,7 +2146,7 @@ async fn remove_skill_targets_best_effort(
     Ok(())
 }
 
-async fn resolve_skill_source_path_for_cleanup<R: Runtime>(
+pub(super) async fn resolve_skill_source_path_for_cleanup<R: Runtime>(
     app: &AppHandle<R>,
     state: &SqliteDbState,
     skill: &Skill,
@@ -1754,7 +2174,9 @@ pub async fn skills_sync_to_tool<R: Runtime>(
     tool: String,
     name: String,
     overwrite: Option<bool>,
+    ignoreCompatibility: Option<bool>,
 ) -> Result<SyncResultDto, String> {
+    ensure_writable("skills_sync_to_tool")?;
     let mut skill = skill_store::get_skill_by_id(&state, &skillId)
         .await?
         .ok_or_else(|| format!("Skill not found: {}", skillId))?;
@@ -1768,6 +2190,15 @@ pub async fn skills_sync_to_tool<R: Runtime>(
     let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
     refresh_central_skill_hash_if_needed(&state, &mut skill, &source_path).await?;
 
+    // Refuse a known-incompatible tool version unless the user overrides
+    if !ignoreCompatibility.unwrap_or(false) {
+        if let Some(check) = compatibility::check_tool(&skill, &source_path, &tool).await {
+            if check.satisfied == Some(false) {
+                return Err(compatibility::incompatible_error(&check));
+            }
+        }
+    }
+
     // Get custom tools for runtime adapter lookup
     let custom_tools = skill_store::get_custom_tools(&state)
         .await
@@ -1789,6 +2220,99 @@ pub async fn skills_sync_to_tool<R: Runtime>(
     Ok(result)
 }
 
+/// Preview what `skills_sync_to_tool` would do to the tool directory: the
+/// mode it would use and the files it would create, overwrite or remove.
+/// Nothing is written.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_preview_sync<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+    overwrite: Option<bool>,
+) -> Result<SyncPreviewDto, String> {
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let runtime_adapter =
+        runtime_adapter_by_key(&tool, &custom_tools).ok_or_else(|| "unknown tool".to_string())?;
+
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    let variant_source = variants::resolve_variant_source(&state, &skill.id, &tool)?;
+    let source_path = variant_source.unwrap_or(source_path);
+    let previous_target = skill_store::get_skill_target(&state, &skill.id, &tool).await?;
+    let (_, target, _) = plan_tool_target(
+        &state,
+        &skill,
+        &runtime_adapter,
+        &source_path,
+        previous_target.as_ref(),
+    )
+    .await?;
+    let path_rules = previous_target
+        .map(|existing| existing.path_rules)
+        .unwrap_or_default();
+    let mode = runtime_adapter.sync_mode();
+    let overwrite = overwrite.unwrap_or(false);
+
+    let plan = tokio::task::spawn_blocking(move || {
+        plan_skill_sync(&source_path, &target, overwrite, mode, &path_rules)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?
+    .map_err(format_error)?;
+
+    let to_strings = |paths: Vec<PathBuf>| {
+        paths
+            .into_iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect()
+    };
+    Ok(SyncPreviewDto {
+        mode_used: plan.outcome.mode_used.as_str().to_string(),
+        target_path: plan.outcome.target_path.to_string_lossy().to_string(),
+        replaced: plan.outcome.replaced,
+        created: to_strings(plan.created),
+        overwritten: to_strings(plan.overwritten),
+        removed: to_strings(plan.removed),
+    })
+}
+
+/// Per-file report of what overwriting the existing target of `skillId` in
+/// `tool` would change (`target_conflict`), for a confirmation before a sync
+/// that failed with `TARGET_EXISTS`; `None` when nothing would be lost
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_preview_target_conflict<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+) -> Result<Option<TargetConflictReport>, String> {
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    let prepared = prepare_tool_sync(&state, &skill, &tool, &source_path, &custom_tools).await?;
+
+    let ctx = sync_context::current();
+    tokio::task::spawn_blocking(move || {
+        target_conflict(&ctx, &prepared.source_path, &prepared.target, |path| {
+            path_rules::keeps(&prepared.path_rules, path)
+        })
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?
+    .map_err(format_error)
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn skills_unsync_from_tool<R: Runtime>(
@@ -1797,15 +2321,20 @@ pub async fn skills_unsync_from_tool<R: Runtime>(
     skillId: String,
     tool: String,
 ) -> Result<(), String> {
+    ensure_writable("skills_unsync_from_tool")?;
     if let Some(target) = skill_store::get_skill_target(&state, &skillId, &tool).await? {
         let skill = skill_store::get_skill_by_id(&state, &skillId)
             .await?
             .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+        policy::ensure_skill_removable(&skill.name)?;
         let source_path = resolve_skill_source_path_for_cleanup(&app, &state, &skill).await;
         if let Some(source_path) = source_path.as_deref() {
             remove_skill_target_best_effort(&skill, source_path, &target);
         }
         skill_store::delete_skill_target(&state, &skillId, &tool).await?;
+        if let Some(tool_root) = Path::new(&target.target_path).parent() {
+            schedule_post_sync(&state, &tool, PostSyncScope::Skills, tool_root);
+        }
     }
 
     // Emit skills-changed for WSL sync
@@ -1814,45 +2343,196 @@ pub async fn skills_unsync_from_tool<R: Runtime>(
     Ok(())
 }
 
-// --- Update/Delete Skills ---
-
+/// Switch an existing tool target between link and copy mode in place.
+/// The choice lasts until the tool is synced again with its default mode.
+/// `relativeLinks` overrides the saved symlink style for this conversion.
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn skills_update_managed(
-    app: tauri::AppHandle,
+pub async fn skills_convert_target_mode<R: Runtime>(
+    app: AppHandle<R>,
     state: State<'_, SqliteDbState>,
     skillId: String,
-) -> Result<UpdateResultDto, String> {
-    if let Some(mut skill) = skill_store::get_skill_by_id(&state, &skillId).await? {
-        if skill.source_type == "central" {
-            let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
-            if !source_path.is_dir() {
-                return Err(format!(
-                    "Central Skill source path is missing or not a directory: {}",
-                    source_path.display()
-                ));
-            }
-            refresh_central_skill_hash_if_needed(&state, &mut skill, &source_path).await?;
-
-            let custom_tools = skill_store::get_custom_tools(&state)
-                .await
-                .unwrap_or_default();
-            let mut updated_targets = Vec::new();
-            let mut sync_errors = Vec::new();
-            for tool in skill.enabled_tools.clone() {
-                match sync_skill_to_tool_record(
-                    &state,
-                    &skill,
-                    &tool,
-                    &source_path,
-                    true,
-                    &custom_tools,
-                )
-                .await
-                {
-                    Ok(_) => updated_targets.push(tool),
-                    Err(error) => sync_errors.push(format!("{}: {}", tool, error)),
-                }
+    tool: String,
+    mode: String,
+    relativeLinks: Option<bool>,
+) -> Result<SyncResultDto, String> {
+    ensure_writable("skills_convert_target_mode")?;
+    let force_copy = match mode.as_str() {
+        "copy" => true,
+        "symlink" => false,
+        _ => return Err(format!("Unsupported sync mode: {}", mode)),
+    };
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    if !skill.management_enabled {
+        return Err(format!("SKILL_DISABLED|{}", skillId));
+    }
+    let target = skill_store::get_skill_target(&state, &skillId, &tool)
+        .await?
+        .ok_or_else(|| format!("Skill {} is not synced to {}", skill.name, tool))?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let runtime_adapter =
+        runtime_adapter_by_key(&tool, &custom_tools).ok_or_else(|| "unknown tool".to_string())?;
+    let target_path = PathBuf::from(&target.target_path);
+    // Links use the tool's link mode; hard links are rejected after the attempt
+    let tool_mode = runtime_adapter.sync_mode();
+    if !force_copy && tool_mode == SyncMode::Copy {
+        return Err(format!("LINK_UNSUPPORTED|{}", target_path.display()));
+    }
+    let convert_mode = if force_copy {
+        SyncMode::Copy
+    } else {
+        tool_mode
+    };
+
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    let variant_source = variants::resolve_variant_source(&state, &skill.id, &tool)?;
+    let source_path = variant_source.as_deref().unwrap_or(&source_path);
+    let outcome = convert_target_mode(
+        source_path,
+        &target_path,
+        convert_mode,
+        &target.path_rules,
+        relativeLinks.unwrap_or_else(relative_symlinks),
+    )
+    .map_err(format_error)?;
+
+    // A link always exposes the full skill directory
+    let path_rules = if force_copy {
+        target.path_rules.clone()
+    } else {
+        SkillPathRules::default()
+    };
+    let record = SkillTarget {
+        mode: outcome.mode_used.as_str().to_string(),
+        status: "ok".to_string(),
+        error_message: copy_verification_error(&outcome),
+        synced_at: Some(now_ms()),
+        path_rules,
+        ..target
+    };
+    target_marker::mark_target(&skill, &record);
+    skill_store::upsert_skill_target(&state, &skill.id, &record).await?;
+    if let Some(tool_root) = target_path.parent() {
+        schedule_post_sync(&state, &tool, PostSyncScope::Skills, tool_root);
+    }
+    let _ = app.emit("skills-changed", "window");
+
+    Ok(SyncResultDto {
+        mode_used: record.mode,
+        target_path: record.target_path,
+        verified: outcome.verified,
+        mismatches: outcome.mismatches,
+        file_changes: outcome.file_changes,
+    })
+}
+
+// --- Skill Variants ---
+
+/// Render a project-aware copy of a skill into `<projectRoot>/<tool skills dir>/<name>`,
+/// filling placeholders from the project's `.ai-toolbox.toml`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_render_to_project<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    projectRoot: String,
+    tool: String,
+    overwrite: Option<bool>,
+) -> Result<ProjectRenderResultDto, String> {
+    ensure_writable("skills_render_to_project")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    if !skill.management_enabled {
+        return Err(format!("Skill is disabled: {}", skill.name));
+    }
+    let project_root = resolve_local_source_path(&projectRoot)?;
+    if !project_root.is_dir() {
+        return Err(format!(
+            "Project directory not found: {}",
+            project_root.display()
+        ));
+    }
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .map_err(|e| e.to_string())?;
+    let adapter = runtime_adapter_by_key(&tool, &custom_tools)
+        .ok_or_else(|| format!("Unknown tool: {}", tool))?;
+
+    let source = match variants::resolve_variant_source(&state, &skill.id, &tool)? {
+        Some(path) => path,
+        None => resolve_skill_source_path(&app, &state, &skill).await?,
+    };
+    let target =
+        project_vars::project_target_path(&project_root, &adapter.relative_skills_dir, &skill.name);
+    if std::fs::symlink_metadata(&target).is_ok() {
+        if !overwrite.unwrap_or(false) {
+            return Err(format!("TARGET_EXISTS|{}", target.to_string_lossy()));
+        }
+        remove_path(&target.to_string_lossy())?;
+    }
+
+    let metadata = project_vars::load_project_metadata(&project_root)?;
+    let variables = project_vars::project_template_vars(&project_root, metadata.as_ref());
+    let rendered_files = project_vars::render_skill_dir(&source, &target, &variables)?;
+    Ok(ProjectRenderResultDto {
+        target_path: target.to_string_lossy().to_string(),
+        variables,
+        rendered_files,
+        metadata_found: metadata.is_some(),
+    })
+}
+
+// --- Update/Delete Skills ---
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_update_managed(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    resolution: Option<LocalChangesResolution>,
+    checksum: Option<String>,
+) -> Result<UpdateResultDto, String> {
+    ensure_writable("skills_update_managed")?;
+    if let Some(mut skill) = skill_store::get_skill_by_id(&state, &skillId).await? {
+        if skill.status == ARCHIVED_STATUS {
+            return Err(format!("SKILL_ARCHIVED|{}", skillId));
+        }
+        if skill.source_type == "central" {
+            let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+            if !source_path.is_dir() {
+                return Err(format!(
+                    "Central Skill source path is missing or not a directory: {}",
+                    source_path.display()
+                ));
+            }
+            refresh_central_skill_hash_if_needed(&state, &mut skill, &source_path).await?;
+
+            let custom_tools = skill_store::get_custom_tools(&state)
+                .await
+                .unwrap_or_default();
+            let mut updated_targets = Vec::new();
+            let mut sync_errors = Vec::new();
+            let results = sync_skill_to_tool_records(
+                &state,
+                &skill,
+                &skill.enabled_tools,
+                &source_path,
+                true,
+                &custom_tools,
+            )
+            .await;
+            for (tool, result) in results {
+                match result {
+                    Ok(_) => updated_targets.push(tool),
+                    Err(error) => sync_errors.push(format!("{}: {}", tool, error)),
+                }
             }
             if !sync_errors.is_empty() {
                 return Err(format!(
@@ -1872,9 +2552,10 @@ pub async fn skills_update_managed(
         }
     }
 
-    let res = update_managed_skill_from_source(&app, &state, &skillId)
-        .await
-        .map_err(|e| format_error(e))?;
+    let res =
+        update_managed_skill_from_source(&app, &state, &skillId, resolution, checksum.as_deref())
+            .await
+            .map_err(|e| format_error(e))?;
 
     // Emit skills-changed for WSL sync
     let _ = app.emit("skills-changed", "window");
@@ -1888,6 +2569,107 @@ pub async fn skills_update_managed(
     })
 }
 
+/// Ids of git skills whose central copy was edited since install or the last
+/// update; `skills_update_managed` refuses to update these without a resolution
+#[tauri::command]
+pub async fn skills_get_locally_modified(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<String>, String> {
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(format_error)?;
+    let skills = skill_store::get_managed_skills(&state)
+        .await?
+        .into_iter()
+        .filter(|skill| skill.source_type == "git" && skill.status != ARCHIVED_STATUS)
+        .collect::<Vec<_>>();
+    tauri::async_runtime::spawn_blocking(move || {
+        skills
+            .into_iter()
+            .filter(|skill| {
+                let central_path = resolve_skill_central_path(&skill.central_path, &central_dir);
+                local_changes::is_locally_modified(skill, &central_path)
+            })
+            .map(|skill| skill.id)
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Show what `skills_update_managed` would change without touching the
+/// central copy: upstream changelog, per-file diff and local edits at risk
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_preview_update(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    checksum: Option<String>,
+) -> Result<SkillUpdatePreviewDto, String> {
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    if skill.status == ARCHIVED_STATUS {
+        return Err(format!("SKILL_ARCHIVED|{}", skillId));
+    }
+    if skill.source_type == "central" {
+        return Err(format!("UPDATE_PREVIEW_UNSUPPORTED|{}", skill.source_type));
+    }
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(format_error)?;
+    let central_path = resolve_skill_central_path(&skill.central_path, &central_dir);
+    if !central_path.is_dir() {
+        return Err(format!(
+            "central path not found: {}",
+            central_path.display()
+        ));
+    }
+
+    let staging = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let upstream_path = staging.path().join(&skill.name);
+    let staged = stage_update_source(&app, &state, &skill, &upstream_path, checksum.as_deref())
+        .await
+        .map_err(format_error)?;
+
+    let baseline = match &staged.git_checkout {
+        Some(checkout) => update_preview::git_baseline(
+            checkout,
+            skill.source_revision.as_deref(),
+            staged.new_revision.as_deref(),
+            &central_path,
+        ),
+        None => update_preview::GitBaseline::default(),
+    };
+    let files = update_preview::diff_against_upstream(
+        &central_path,
+        &upstream_path,
+        baseline.local_changes.as_ref(),
+    )
+    .map_err(format_error)?;
+    let has_local_modifications = match &baseline.local_changes {
+        Some(changes) => !changes.is_empty(),
+        None => update_preview::central_copy_modified(&central_path, skill.content_hash.as_deref()),
+    };
+
+    Ok(SkillUpdatePreviewDto {
+        overwritten_local_files: update_preview::overwritten_local_files(
+            &files,
+            has_local_modifications,
+        ),
+        skill_id: skill.id,
+        name: skill.name,
+        source_type: skill.source_type,
+        current_revision: skill.source_revision,
+        new_revision: staged.new_revision,
+        changelog: baseline.changelog,
+        files,
+        has_local_modifications,
+    })
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn skills_delete_managed(
@@ -1895,10 +2677,13 @@ pub async fn skills_delete_managed(
     state: State<'_, SqliteDbState>,
     skillId: String,
     options: Option<DeleteManagedSkillOptionsDto>,
+    confirmationToken: Option<String>,
 ) -> Result<(), String> {
+    ensure_writable("skills_delete_managed")?;
     let record = skill_store::get_skill_by_id(&state, &skillId).await?;
     let mut remove_failures: Vec<String> = Vec::new();
     if let Some(skill) = record {
+        policy::ensure_skill_removable(&skill.name)?;
         // Resolve central_path (handles cross-platform legacy paths)
         let central_dir = resolve_central_repo_path(&app, &state)
             .await
@@ -1906,6 +2691,20 @@ pub async fn skills_delete_managed(
         let default_dir = resolve_default_central_repo_path(&app).map_err(|e| format_error(e))?;
         let uses_default_central_dir = paths_equivalent(&central_dir, &default_dir);
         let path = resolve_skill_central_path(&skill.central_path, &central_dir);
+
+        let default_delete_source = skill.source_type != "central" && uses_default_central_dir;
+        let delete_source_files = options
+            .map(|options| options.delete_source_files)
+            .unwrap_or(default_delete_source);
+        // Deleting central repo content is a dangerous operation; check before touching anything
+        if delete_source_files && !source_path_missing_for_delete(&path) {
+            confirmation::consume(
+                DangerousOp::DeleteCentralContent,
+                Some(&skillId),
+                confirmationToken.as_deref(),
+            )?;
+        }
+
         let targets = skill_store::get_skill_targets(&state, &skillId).await?;
         for target in targets {
             if let Err(err) = remove_skill_target_checked(&path, &target.target_path) {
@@ -1913,15 +2712,12 @@ pub async fn skills_delete_managed(
             }
         }
 
-        let default_delete_source = skill.source_type != "central" && uses_default_central_dir;
-        let delete_source_files = options
-            .map(|options| options.delete_source_files)
-            .unwrap_or(default_delete_source);
         if delete_source_files {
             if source_path_missing_for_delete(&path) {
                 // The DB record can still be removed if the user already deleted the source dir.
             } else if safe_source_delete_allowed(&path, &central_dir) {
                 if path.exists() {
+                    write_protect::unlock(&path, skill.write_protected).map_err(format_error)?;
                     std::fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
                 }
             } else {
@@ -1930,7 +2726,20 @@ pub async fn skills_delete_managed(
                     path.display()
                 ));
             }
+            if skill.status == ARCHIVED_STATUS {
+                let archive_dir = archive::archive_dir(&app, &skill.id)?;
+                if let Err(err) = remove_path(&archive_dir.to_string_lossy()) {
+                    remove_failures.push(format!("{}: {}", archive_dir.display(), err));
+                }
+            }
         }
+        for variant in variants::list_skill_variants(&state, &skill.id)? {
+            if let Err(err) = variants::remove_variant_content(&variant) {
+                remove_failures.push(err);
+            }
+            variants::delete_skill_variant(&state, &variant.id)?;
+        }
+        collections::remove_skill_from_collections(&state, &skill.id)?;
         skill_store::delete_skill(&state, &skillId).await?;
     }
 
@@ -1949,20 +2758,64 @@ pub async fn skills_delete_managed(
 
 // --- Onboarding ---
 
+pub(super) async fn onboarding_plan_with_timeout(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    differential: bool,
+) -> Result<(OnboardingPlan, OnboardingScanIndex), String> {
+    // Add 30 second timeout to prevent hanging on large directories
+    let plan = build_onboarding_plan(app, state, differential);
+    match tokio::time::timeout(Duration::from_secs(30), plan).await {
+        Ok(result) => result.map_err(|e| format_error(e)),
+        Err(_) => {
+            Err("Scan timed out after 30 seconds. Please check your custom tool paths.".to_string())
+        }
+    }
+}
+
+/// Scan tools for unmanaged skills. `differential` reuses the fingerprints of
+/// directories unchanged since the previous scan. Only this scan is recorded
+/// as "the previous scan"; internal scans (adopt, consolidation) just read it.
+/// `includeSystem` also lists the tools' own system skills, read-only.
 #[tauri::command]
+#[allow(non_snake_case)]
 pub async fn skills_get_onboarding_plan(
     app: tauri::AppHandle,
     state: State<'_, SqliteDbState>,
+    differential: Option<bool>,
+    includeSystem: Option<bool>,
 ) -> Result<OnboardingPlan, String> {
-    // Add 30 second timeout to prevent hanging on large directories
-    match tokio::time::timeout(Duration::from_secs(30), build_onboarding_plan(&app, &state)).await {
-        Ok(result) => result.map_err(|e| format_error(e)),
-        Err(_) => {
-            Err("Scan timed out after 30 seconds. Please check your custom tool paths.".to_string())
+    let (mut plan, index) =
+        onboarding_plan_with_timeout(&app, &state, differential.unwrap_or(false)).await?;
+    if includeSystem.unwrap_or(false) {
+        plan.system_skills = list_system_skills(&state).await;
+    }
+    // The index is only a cache; read-only mode keeps scanning from scratch
+    if !crate::settings::read_only::is_read_only() {
+        if let Err(err) = save_scan_index(&state, &index) {
+            log::warn!("[onboarding] failed to save scan index: {}", err);
         }
     }
+    Ok(plan)
+}
+
+/// Scan other home directories (other accounts, mounted backups) read-only.
+/// Each root gets its own plan; nothing is imported or recorded.
+#[tauri::command]
+pub async fn skills_scan_extra_roots(
+    state: State<'_, SqliteDbState>,
+    roots: Vec<String>,
+) -> Result<Vec<ExtraRootScanDto>, String> {
+    let roots = roots
+        .iter()
+        .map(|root| resolve_local_source_path(root).map(|path| path.to_string_lossy().to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    scan_extra_roots(&state, roots).await.map_err(format_error)
 }
 
+/// Import an onboarding variant. `linkedFolder` is the variant's resolved
+/// external folder from the plan: the skill is imported from there as a
+/// `linked_folder` source, and `trackOrigin` watches that folder for changes.
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn skills_import_existing(
@@ -1970,23 +2823,94 @@ pub async fn skills_import_existing(
     state: State<'_, SqliteDbState>,
     sourcePath: String,
     overwrite: Option<bool>,
+    linkedFolder: Option<String>,
+    trackOrigin: Option<bool>,
 ) -> Result<InstallResultDto, String> {
+    ensure_writable("skills_import_existing")?;
     let source_path = resolve_local_source_path(&sourcePath)?;
-    let result = install_local_skill(
-        &app,
-        &state,
-        &source_path,
-        overwrite.unwrap_or(false),
-    )
-    .await
-    .map_err(|e| format_error(e))?;
+    let overwrite = overwrite.unwrap_or(false);
+    let result = match linkedFolder {
+        Some(folder) => {
+            let folder = PathBuf::from(folder);
+            if !paths_equivalent(&source_path, &folder) {
+                return Err(format!(
+                    "Link {} no longer points to {}",
+                    source_path.display(),
+                    folder.display()
+                ));
+            }
+            let result =
+                install_linked_folder_skill(&app, &state, &source_path, &folder, overwrite)
+                    .await
+                    .map_err(format_error)?;
+            linked_folder::set_tracked(&state, &result.skill_id, trackOrigin.unwrap_or(false))?;
+            result
+        }
+        None => install_local_skill(&app, &state, &source_path, overwrite)
+            .await
+            .map_err(|e| format_error(e))?,
+    };
 
-    Ok(InstallResultDto {
-        skill_id: result.skill_id,
-        name: result.name,
-        central_path: result.central_path.to_string_lossy().to_string(),
-        content_hash: result.content_hash,
+    Ok(install_result_dto(&state, result))
+}
+
+/// Linked-folder skills whose origin is tracked, with their change status
+#[tauri::command]
+pub async fn skills_get_linked_folder_changes(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<LinkedFolderStatusDto>, String> {
+    let tracked = linked_folder::load_tracked(&state);
+    let skills = skill_store::get_managed_skills(&state)
+        .await?
+        .into_iter()
+        .filter(|skill| tracked.contains(&skill.id) && skill.status != ARCHIVED_STATUS)
+        .collect::<Vec<_>>();
+    tauri::async_runtime::spawn_blocking(move || {
+        skills
+            .iter()
+            .filter_map(linked_folder::linked_folder_status)
+            .collect()
     })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Start or stop watching a linked-folder skill's origin for changes
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_linked_folder_tracking(
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tracked: bool,
+) -> Result<(), String> {
+    ensure_writable("skills_set_linked_folder_tracking")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    if tracked && skill.source_type != LINKED_FOLDER_SOURCE_TYPE {
+        return Err(format!(
+            "Skill {} is not imported from a linked folder",
+            skill.name
+        ));
+    }
+    linked_folder::set_tracked(&state, &skill.id, tracked)
+}
+
+/// Whether edits of the central repo are re-synced to copy targets on their own
+#[tauri::command]
+pub async fn skills_get_auto_resync_copies(
+    state: State<'_, SqliteDbState>,
+) -> Result<bool, String> {
+    Ok(central_watcher::is_auto_resync_enabled(&state))
+}
+
+#[tauri::command]
+pub async fn skills_set_auto_resync_copies(
+    state: State<'_, SqliteDbState>,
+    enabled: bool,
+) -> Result<(), String> {
+    ensure_writable("skills_set_auto_resync_copies")?;
+    central_watcher::set_auto_resync_enabled(&state, enabled)
 }
 
 // --- Git Cache ---
@@ -2003,6 +2927,7 @@ pub async fn skills_set_git_cache_cleanup_days(
     state: State<'_, SqliteDbState>,
     days: i64,
 ) -> Result<i64, String> {
+    ensure_writable("skills_set_git_cache_cleanup_days")?;
     set_cleanup_days(&state, days)
         .await
         .map_err(|e| format_error(e))
@@ -2015,6 +2940,7 @@ pub async fn skills_get_git_cache_ttl_secs(state: State<'_, SqliteDbState>) -> R
 
 #[tauri::command]
 pub async fn skills_clear_git_cache(app: tauri::AppHandle) -> Result<usize, String> {
+    ensure_writable("skills_clear_git_cache")?;
     cleanup_git_cache_dirs(&app, Duration::from_secs(0)).map_err(|e| format_error(e))
 }
 
@@ -2029,6 +2955,35 @@ pub async fn skills_get_git_cache_path(app: tauri::AppHandle) -> Result<String,
     Ok(cache_path.to_string_lossy().to_string())
 }
 
+// --- Storage Report ---
+
+/// Disk usage of the central repo, copy-mode targets, git cache and snapshots
+#[tauri::command]
+pub async fn skills_get_storage_report(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<StorageReportDto, String> {
+    use tauri::Manager;
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(format_error)?;
+    let roots = StorageRoots {
+        git_cache: app
+            .path()
+            .app_cache_dir()
+            .ok()
+            .map(|dir| dir.join("skills-git-cache")),
+        snapshots: snapshots_root(&app).ok(),
+        variants: variants::variants_root(&app).ok(),
+    };
+    tokio::task::spawn_blocking(move || {
+        storage_report::build_storage_report(&skills, &central_dir, &roots)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))
+}
+
 // --- Preferred Tools ---
 
 #[tauri::command]
@@ -2118,6 +3073,8 @@ pub async fn skills_get_custom_tools(
     let tools = skill_store::get_custom_tools(&state).await?;
     Ok(tools
         .into_iter()
+        // Plugin tools (never stored, so no `created_at`) are managed as files
+        .filter(|t| !(t.created_at == 0 && is_plugin_tool(&t.key)))
         .map(|t| CustomToolDto {
             key: t.key,
             display_name: t.display_name,
@@ -2125,6 +3082,7 @@ pub async fn skills_get_custom_tools(
             relative_detect_dir: t.relative_detect_dir,
             created_at: t.created_at,
             force_copy: t.force_copy,
+            preferred_sync_mode: t.preferred_sync_mode,
         })
         .collect())
 }
@@ -2138,7 +3096,9 @@ pub async fn skills_add_custom_tool(
     relativeSkillsDir: String,
     relativeDetectDir: String,
     forceCopy: Option<bool>,
+    preferredSyncMode: Option<String>,
 ) -> Result<(), String> {
+    ensure_writable("skills_add_custom_tool")?;
     use crate::coding::tools::path_utils::{normalize_path, to_storage_path};
 
     // Trim whitespace from all inputs
@@ -2159,6 +3119,7 @@ pub async fn skills_add_custom_tool(
     if adapter_by_key(&key).is_some() {
         return Err(format!("Key '{}' conflicts with a built-in tool", key));
     }
+    let preferred_sync_mode = normalize_preferred_sync_mode(preferredSyncMode.as_deref())?;
 
     let tool = CustomTool {
         key,
@@ -2167,6 +3128,7 @@ pub async fn skills_add_custom_tool(
         relative_detect_dir,
         created_at: now_ms(),
         force_copy: forceCopy.unwrap_or(false),
+        preferred_sync_mode,
     };
     skill_store::save_custom_tool(&state, &tool).await
 }
@@ -2176,6 +3138,7 @@ pub async fn skills_remove_custom_tool(
     state: State<'_, SqliteDbState>,
     key: String,
 ) -> Result<(), String> {
+    ensure_writable("skills_remove_custom_tool")?;
     skill_store::delete_custom_tool(&state, &key).await
 }
 
@@ -2197,6 +3160,7 @@ pub async fn skills_check_custom_tool_path(relativeSkillsDir: String) -> Result<
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn skills_create_custom_tool_path(relativeSkillsDir: String) -> Result<(), String> {
+    ensure_writable("skills_create_custom_tool_path")?;
     use crate::coding::tools::path_utils::{normalize_path, resolve_storage_path, to_storage_path};
 
     // Normalize the path first to get the storage format
@@ -2210,6 +3174,24 @@ pub async fn skills_create_custom_tool_path(relativeSkillsDir: String) -> Result
     Ok(())
 }
 
+/// Directories outside the home dir (or with unexpected names) that skills
+/// may still be synced into
+#[tauri::command]
+pub async fn skills_get_sync_target_whitelist(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<String>, String> {
+    Ok(target_guard::load_whitelist(&state))
+}
+
+#[tauri::command]
+pub async fn skills_set_sync_target_whitelist(
+    state: State<'_, SqliteDbState>,
+    dirs: Vec<String>,
+) -> Result<(), String> {
+    ensure_writable("skills_set_sync_target_whitelist")?;
+    target_guard::save_whitelist(&state, &dirs)
+}
+
 // --- Skill Repos ---
 
 // --- Reorder Skills ---
@@ -2231,6 +3213,7 @@ pub async fn skills_save_group(
     note: Option<String>,
     sortIndex: Option<i32>,
 ) -> Result<String, String> {
+    ensure_writable("skills_save_group")?;
     let name = name.trim().to_string();
     if name.is_empty() {
         return Err("Group name is required".to_string());
@@ -2264,14 +3247,25 @@ pub async fn skills_delete_group(
     state: State<'_, SqliteDbState>,
     groupId: String,
 ) -> Result<(), String> {
-    skill_store::delete_skill_group(&state, &groupId).await
+    ensure_writable("skills_delete_group")?;
+    skill_store::delete_skill_group(&state, &groupId).await?;
+    let mut policies = rotation::load_rotation_policies(&state);
+    if policies.remove(&groupId).is_some() {
+        rotation::save_rotation_policies(&state, &policies)?;
+    }
+    Ok(())
 }
 
+// --- Skill Collections ---
+
+// --- Team Mirrors ---
+
 #[tauri::command]
 pub async fn skills_reorder(
     state: State<'_, SqliteDbState>,
     ids: Vec<String>,
 ) -> Result<(), String> {
+    ensure_writable("skills_reorder")?;
     skill_store::reorder_skills(&state, &ids).await
 }
 
@@ -2283,6 +3277,7 @@ pub async fn skills_update_metadata(
     groupId: Option<String>,
     userNote: Option<String>,
 ) -> Result<(), String> {
+    ensure_writable("skills_update_metadata")?;
     skill_store::update_skill_metadata(
         &state,
         &skillId,
@@ -2299,6 +3294,7 @@ pub async fn skills_batch_update_group(
     skillIds: Vec<String>,
     groupId: Option<String>,
 ) -> Result<(), String> {
+    ensure_writable("skills_batch_update_group")?;
     skill_store::update_skills_group(&state, &skillIds, normalize_optional_id(groupId)).await
 }
 
@@ -2310,10 +3306,12 @@ pub async fn skills_set_management_enabled<R: Runtime>(
     skillId: String,
     enabled: bool,
 ) -> Result<Vec<String>, String> {
+    ensure_writable("skills_set_management_enabled")?;
     if !enabled {
         let skill = skill_store::get_skill_by_id(&state, &skillId)
             .await?
             .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+        policy::ensure_skill_removable(&skill.name)?;
         let previous_tools = disabled_previous_tools_for_skill(&skill);
         let source_path = resolve_skill_source_path_for_cleanup(&app, &state, &skill).await;
         remove_skill_targets_best_effort(&state, &skill, source_path.as_deref()).await?;
@@ -2404,7 +3402,8 @@ async fn build_inventory_json(
 }
 
 fn default_inventory_export_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or_else(|| "Failed to resolve home directory".to_string())?;
+    let home = crate::coding::tools::home_dir()
+        .ok_or_else(|| "Failed to resolve home directory".to_string())?;
     Ok(home.join(format!("skill-group-{}.json", now_ms())))
 }
 
@@ -2518,11 +3517,16 @@ async fn preflight_inventory_tool_sync(
         ));
     }
 
-    let tool_root = resolve_runtime_skills_path_with_state_async(state.db(), &runtime_adapter)
-        .await
-        .map_err(|e| format_error(e))?;
-    let target = tool_root.join(&skill.name);
-    let force_copy = tool.eq_ignore_ascii_case("cursor") || runtime_adapter.force_copy;
+    let previous_target = skill_store::get_skill_target(state, &skill.id, tool).await?;
+    let (_, target, _) = plan_tool_target(
+        state,
+        skill,
+        &runtime_adapter,
+        source_path,
+        previous_target.as_ref(),
+    )
+    .await?;
+    let force_copy = runtime_adapter.sync_mode().holds_own_files();
     validate_skill_sync_target(source_path, &target, force_copy).map_err(format_error)
 }
 
@@ -2574,6 +3578,7 @@ pub async fn skills_apply_inventory_import<R: Runtime>(
     state: State<'_, SqliteDbState>,
     inventoryJson: String,
 ) -> Result<SkillInventoryPreviewDto, String> {
+    ensure_writable("skills_apply_inventory_import")?;
     let preview = preview_inventory_import(&state, &inventoryJson).await?;
     if !preview.valid {
         return Ok(preview);
@@ -2672,6 +3677,7 @@ pub async fn skills_apply_inventory_import_file<R: Runtime>(
     state: State<'_, SqliteDbState>,
     filePath: String,
 ) -> Result<SkillInventoryPreviewDto, String> {
+    ensure_writable("skills_apply_inventory_import_file")?;
     let raw = read_inventory_file(&filePath)?;
     skills_apply_inventory_import(app, state, raw).await
 }
@@ -2812,6 +3818,7 @@ pub async fn skills_add_repo(
     name: String,
     branch: Option<String>,
 ) -> Result<(), String> {
+    ensure_writable("skills_add_repo")?;
     let repo = SkillRepo {
         id: format!("{}/{}", owner, name),
         owner,
@@ -2829,11 +3836,13 @@ pub async fn skills_remove_repo(
     owner: String,
     name: String,
 ) -> Result<(), String> {
+    ensure_writable("skills_remove_repo")?;
     skill_store::delete_skill_repo(&state, &owner, &name).await
 }
 
 #[tauri::command]
 pub async fn skills_init_default_repos(state: State<'_, SqliteDbState>) -> Result<usize, String> {
+    ensure_writable("skills_init_default_repos")?;
     let existing = skill_store::get_skill_repos(&state).await?;
     if !existing.is_empty() {
         return Ok(0);
@@ -2862,6 +3871,107 @@ pub async fn skills_init_default_repos(state: State<'_, SqliteDbState>) -> Resul
     Ok(default_repos.len())
 }
 
+// --- Context Budget ---
+
+// --- Quality ---
+
+// --- Licenses ---
+
+// --- Copy Ignore Patterns ---
+
+#[tauri::command]
+pub async fn skills_get_copy_ignore_patterns(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<String>, String> {
+    Ok(skill_ignore::load_copy_ignore_patterns(&state))
+}
+
+/// Global gitignore-style patterns left out of copy and hard-link targets.
+/// Returns the normalized list that was saved.
+#[tauri::command]
+pub async fn skills_save_copy_ignore_patterns(
+    state: State<'_, SqliteDbState>,
+    patterns: Vec<String>,
+) -> Result<Vec<String>, String> {
+    ensure_writable("skills_save_copy_ignore_patterns")?;
+    skill_ignore::save_copy_ignore_patterns(&state, &patterns)
+}
+
+// --- Symlink Style ---
+
+#[tauri::command]
+pub async fn skills_get_relative_symlinks(state: State<'_, SqliteDbState>) -> Result<bool, String> {
+    Ok(load_relative_symlinks(&state))
+}
+
+/// Create new symlinks relative to the target's directory instead of with the
+/// absolute central repo path. Existing links keep their style until they are
+/// re-created, e.g. by `skills_convert_target_mode`.
+#[tauri::command]
+pub async fn skills_set_relative_symlinks(
+    state: State<'_, SqliteDbState>,
+    enabled: bool,
+) -> Result<(), String> {
+    ensure_writable("skills_set_relative_symlinks")?;
+    save_relative_symlinks(&state, enabled)
+}
+
+// --- Sync Journal ---
+
+// --- Stale Tool Artifacts ---
+
+// --- Scheduled Sync ---
+
+// --- Library Audit ---
+
+// --- File-based Skills ---
+
+// --- Source Mirror ---
+
+#[tauri::command]
+pub async fn skills_get_source_mirror(
+    state: State<'_, SqliteDbState>,
+) -> Result<SkillSourceMirror, String> {
+    Ok(source_mirror::load_source_mirror(&state))
+}
+
+/// Save the mirror base URL and expected checksums for remote skill sources.
+#[tauri::command]
+pub async fn skills_save_source_mirror(
+    state: State<'_, SqliteDbState>,
+    config: SkillSourceMirror,
+) -> Result<(), String> {
+    ensure_writable("skills_save_source_mirror")?;
+    let mut config = config;
+    config.base_url = config.base_url.trim().to_string();
+    if config.enabled && !config.base_url.is_empty() && !config.base_url.contains("://") {
+        return Err(format!("Invalid mirror base URL: {}", config.base_url));
+    }
+    source_mirror::save_source_mirror(&state, &config)
+}
+
+// --- Skill Rotation ---
+
+// --- Name Collision Policies ---
+
+#[tauri::command]
+pub async fn skills_get_name_collision_policies(
+    state: State<'_, SqliteDbState>,
+) -> Result<NameCollisionPolicies, String> {
+    Ok(collision_policy::load_policies(&state))
+}
+
+#[tauri::command]
+pub async fn skills_save_name_collision_policies(
+    state: State<'_, SqliteDbState>,
+    policies: NameCollisionPolicies,
+) -> Result<(), String> {
+    ensure_writable("skills_save_name_collision_policies")?;
+    collision_policy::save_policies(&state, &policies)
+}
+
+// --- Default Skill Sets ---
+
 // --- Resync All Skills ---
 
 pub async fn resync_all_skills_internal<R: Runtime>(
@@ -2906,25 +4016,43 @@ pub async fn resync_all_skills_internal<R: Runtime>(
                 continue;
             }
 
-            let tool_root =
-                match resolve_runtime_skills_path_with_state_async(state, &runtime_adapter).await {
-                    Ok(p) => p,
-                    Err(_) => continue,
+            let target =
+                match target_guard::resolve_checked_target(state, &runtime_adapter, &skill.name)
+                    .await
+                {
+                    Ok((_, target)) => target,
+                    Err(err) => {
+                        log::warn!("[skills] skip sync of '{}': {}", skill.name, err);
+                        continue;
+                    }
                 };
-
-            let target = tool_root.join(&skill.name);
             let previous_target = skill_store::get_skill_target(&state, &skill.id, tool_key)
                 .await
                 .ok()
                 .flatten();
+            let target = previous_target
+                .as_ref()
+                .and_then(|previous| {
+                    collision_policy::renamed_target(&skill.name, &target, &previous.target_path)
+                })
+                .unwrap_or(target);
+            let source_path = variants::resolve_variant_source(state, &skill.id, tool_key)
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| central_path.clone());
+
+            let path_rules = previous_target
+                .as_ref()
+                .map(|existing| existing.path_rules.clone())
+                .unwrap_or_default();
 
             // Sync with overwrite
             if let Ok(result) = sync_skill_to_target(
-                tool_key,
-                &central_path,
+                &source_path,
                 &target,
                 true,
-                runtime_adapter.force_copy,
+                runtime_adapter.sync_mode(),
+                &path_rules,
             ) {
                 if let Some(existing_target) = previous_target.as_ref() {
                     if target_path_changed(&existing_target.target_path, &target) {
@@ -2941,7 +4069,9 @@ pub async fn resync_all_skills_internal<R: Runtime>(
                     status: "ok".to_string(),
                     error_message: None,
                     synced_at: Some(now_ms()),
+                    path_rules,
                 };
+                target_marker::mark_target(&skill, &record);
                 let _ = skill_store::upsert_skill_target(&state, &skill.id, &record).await;
                 synced.push(format!("{}:{}", skill.name, tool_key));
             }
@@ -2951,12 +4081,93 @@ pub async fn resync_all_skills_internal<R: Runtime>(
     Ok(synced)
 }
 
+/// Re-sync the copy-mode targets of the skills stored in the central
+/// directories `dir_names` (see `central_watcher`). Targets that already
+/// match their source and tools assigned to a variant are left alone.
+/// Returns the refreshed targets as `skill:tool`.
+pub(crate) async fn resync_edited_copy_targets(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    dir_names: &BTreeSet<String>,
+) -> Result<Vec<String>, String> {
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    let central_dir = resolve_central_repo_path(app, state)
+        .await
+        .map_err(|e| format_error(e))?;
+
+    let ctx = sync_context::current();
+    let mut refreshed = Vec::new();
+    for mut skill in skill_store::get_managed_skills(state).await? {
+        if !skill.management_enabled || skill.status == ARCHIVED_STATUS {
+            continue;
+        }
+        let central_path = resolve_skill_central_path(&skill.central_path, &central_dir);
+        let edited = central_watcher::skill_dir_name(&central_dir, &central_path)
+            .is_some_and(|name| dir_names.contains(&name));
+        if !edited || !central_path.is_dir() {
+            continue;
+        }
+
+        let mut stale_tools = Vec::new();
+        for target in skill_store::get_skill_targets(state, &skill.id).await? {
+            if target.mode != SyncMode::Copy.as_str()
+                || !skill.enabled_tools.contains(&target.tool)
+                || variants::resolve_variant_source(state, &skill.id, &target.tool)?.is_some()
+            {
+                continue;
+            }
+            // A copy with path rules is a slim copy and never matches the source
+            let current = target.path_rules.is_empty()
+                && verify_copy(&ctx, &central_path, Path::new(&target.target_path))
+                    .is_ok_and(|mismatches| mismatches.is_empty());
+            if !current {
+                stale_tools.push(target.tool);
+            }
+        }
+        if stale_tools.is_empty() {
+            continue;
+        }
+
+        refresh_central_skill_hash_if_needed(state, &mut skill, &central_path).await?;
+        let results = sync_skill_to_tool_records(
+            state,
+            &skill,
+            &stale_tools,
+            &central_path,
+            true,
+            &custom_tools,
+        )
+        .await;
+        for (tool, result) in results {
+            match result {
+                Ok(_) => refreshed.push(format!("{}:{}", skill.name, tool)),
+                Err(err) => log::warn!(
+                    "[skills] re-sync of '{}' to '{}' after edit failed: {}",
+                    skill.name,
+                    tool,
+                    err
+                ),
+            }
+        }
+    }
+    Ok(refreshed)
+}
+
 pub async fn resync_all_skills_if_tool_path_changed(
     app: tauri::AppHandle,
     state: &SqliteDbState,
     tool_key: &str,
     previous_skills_path: Option<PathBuf>,
 ) {
+    if crate::settings::read_only::is_read_only() {
+        log::info!(
+            "Skipping skills resync for {} because read-only mode is enabled",
+            tool_key
+        );
+        return;
+    }
     let current_skills_path =
         runtime_location::get_tool_skills_path_async(&state.db(), tool_key).await;
 
@@ -2981,11 +4192,76 @@ pub async fn resync_all_skills_if_tool_path_changed(
     }
 }
 
-/// Re-sync all skills to installed tools (used after restore)
+/// Re-sync all skills to installed tools (used after restore).
+/// Needs a confirmation token when more than
+/// `OVERWRITE_TARGETS_THRESHOLD` targets would be overwritten.
 #[tauri::command]
+#[allow(non_snake_case)]
 pub async fn skills_resync_all(
     app: tauri::AppHandle,
     state: State<'_, SqliteDbState>,
+    confirmationToken: Option<String>,
 ) -> Result<Vec<String>, String> {
+    ensure_writable("skills_resync_all")?;
+    let skills = skill_store::get_managed_skills(&state).await?;
+    if confirmation::count_resync_targets(&skills) > OVERWRITE_TARGETS_THRESHOLD {
+        confirmation::consume(
+            DangerousOp::OverwriteTargets,
+            None,
+            confirmationToken.as_deref(),
+        )?;
+    }
     resync_all_skills_internal(app, state.inner()).await
 }
+
+// --- Dangerous operation confirmation ---
+
+/// Issue a confirmation token with a human-readable summary for a dangerous
+/// operation. `skillIds` scopes `delete_central_content` to those skills.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_request_confirmation(
+    state: State<'_, SqliteDbState>,
+    op: DangerousOp,
+    skillIds: Option<Vec<String>>,
+) -> Result<ConfirmationDto, String> {
+    match op {
+        DangerousOp::DeleteCentralContent => {
+            let skill_ids = skillIds.unwrap_or_default();
+            if skill_ids.is_empty() {
+                return Err("skillIds is required for delete_central_content".to_string());
+            }
+            let mut summary_items = Vec::new();
+            for skill_id in &skill_ids {
+                let skill = skill_store::get_skill_by_id(&state, skill_id)
+                    .await?
+                    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+                let targets = skill_store::get_skill_targets(&state, skill_id).await?;
+                summary_items.push((skill.name, targets.len()));
+            }
+            let summary = confirmation::delete_summary(&summary_items);
+            Ok(confirmation::issue(op, skill_ids, summary))
+        }
+        DangerousOp::OverwriteTargets => {
+            let skills = skill_store::get_managed_skills(&state).await?;
+            let enabled = skills
+                .iter()
+                .filter(|skill| skill.management_enabled)
+                .count();
+            let summary = format!(
+                "Re-sync {} skill(s) and overwrite {} tool target(s)",
+                enabled,
+                confirmation::count_resync_targets(&skills)
+            );
+            Ok(confirmation::issue(op, Vec::new(), summary))
+        }
+        DangerousOp::PurgeTrash => {
+            let entries = sync_context::trash_dir()
+                .map(|trash| sync_journal::list_entries(&trash, None))
+                .unwrap_or_default();
+            let bytes = entries.iter().map(|entry| entry.size).sum();
+            let summary = confirmation::purge_trash_summary(entries.len(), bytes);
+            Ok(confirmation::issue(op, Vec::new(), summary))
+        }
+    }
+}
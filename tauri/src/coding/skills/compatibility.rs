@@ -0,0 +1,329 @@
+//! Tool version compatibility constraints
+//!
+//! A skill can state which tool versions it works with in its SKILL.md
+//! frontmatter:
+//!
+//! ```yaml
+//! compatibility:
+//!   claude_code: ">=2.0"
+//!   codex: ">=0.40 <1"
+//! ```
+//!
+//! or per skill from the UI (`Skill.compatibility`), which replaces the
+//! frontmatter entry of the same tool. A constraint is a list of comparators
+//! (`>=`, `>`, `<=`, `<`, `=`) separated by spaces or commas; a bare version
+//! means `>=`, and `=2` matches any 2.x. Enabling a skill for a tool runs the
+//! tool CLI with `--version` and refuses with
+//! `INCOMPATIBLE_TOOL_VERSION|tool|constraint|version` until the user
+//! overrides. Tools without a known CLI, CLIs that are not found and
+//! constraints that cannot be parsed never block.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::types::{now_ms, Skill};
+use crate::coding::cli_resolver::{
+    build_local_tokio_command, resolve_local_claude_program, resolve_local_command_program,
+    resolve_local_grok_program, resolve_local_opencode_program, resolve_local_pi_program,
+    LocalCliProgram,
+};
+use crate::coding::mcp::prerequisites::{parse_version, version_parts};
+use crate::coding::tools::canonical_tool_key;
+
+pub const INCOMPATIBLE_ERROR_PREFIX: &str = "INCOMPATIBLE_TOOL_VERSION|";
+const FRONTMATTER_KEY: &str = "compatibility";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Detected versions are reused for this long, so toggling several skills
+/// does not start the CLI every time
+const VERSION_CACHE_TTL_MS: i64 = 10 * 60 * 1000;
+
+/// Tool key -> (checked at, version)
+type VersionCache = HashMap<String, (i64, Option<String>)>;
+
+static VERSION_CACHE: OnceLock<Mutex<VersionCache>> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Ge,
+    Gt,
+    Le,
+    Lt,
+    Eq,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillCompatibilityDto {
+    pub tool: String,
+    pub constraint: String,
+    /// `user` when set on the skill, `frontmatter` when read from SKILL.md
+    pub source: String,
+    pub detected_version: Option<String>,
+    /// `None` when the version is unknown or the constraint is not valid
+    pub satisfied: Option<bool>,
+}
+
+/// `tool: constraint` entries of the `compatibility:` block in SKILL.md
+pub fn frontmatter_constraints(skill_dir: &Path) -> BTreeMap<String, String> {
+    std::fs::read_to_string(skill_dir.join("SKILL.md"))
+        .map(|content| parse_frontmatter_constraints(&content))
+        .unwrap_or_default()
+}
+
+fn parse_frontmatter_constraints(content: &str) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    let mut lines = content.lines();
+    if lines.next().map(str::trim) != Some("---") {
+        return result;
+    }
+    let mut in_block = false;
+    for line in lines.take_while(|line| line.trim() != "---") {
+        if !line.starts_with([' ', '\t']) {
+            in_block = line.trim_end() == format!("{}:", FRONTMATTER_KEY);
+            continue;
+        }
+        if !in_block {
+            continue;
+        }
+        if let Some((tool, constraint)) = line.trim().split_once(':') {
+            let constraint = constraint.trim().trim_matches('"').trim_matches('\'');
+            if !tool.trim().is_empty() && !constraint.is_empty() {
+                result.insert(
+                    canonical_tool_key(tool.trim()).to_string(),
+                    constraint.to_string(),
+                );
+            }
+        }
+    }
+    result
+}
+
+/// Effective constraints of `skill` by tool, with where each one came from
+pub fn skill_constraints(skill: &Skill, skill_dir: &Path) -> BTreeMap<String, (String, String)> {
+    let mut result: BTreeMap<String, (String, String)> = frontmatter_constraints(skill_dir)
+        .into_iter()
+        .map(|(tool, constraint)| (tool, (constraint, "frontmatter".to_string())))
+        .collect();
+    for (tool, constraint) in &skill.compatibility {
+        result.insert(
+            canonical_tool_key(tool).to_string(),
+            (constraint.clone(), "user".to_string()),
+        );
+    }
+    result
+}
+
+fn parse_constraint(constraint: &str) -> Option<Vec<(Op, String)>> {
+    let comparators: Vec<(Op, String)> = constraint
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (op, version) = if let Some(rest) = part.strip_prefix(">=") {
+                (Op::Ge, rest)
+            } else if let Some(rest) = part.strip_prefix("<=") {
+                (Op::Le, rest)
+            } else if let Some(rest) = part.strip_prefix('>') {
+                (Op::Gt, rest)
+            } else if let Some(rest) = part.strip_prefix('<') {
+                (Op::Lt, rest)
+            } else if let Some(rest) = part.strip_prefix('=') {
+                (Op::Eq, rest)
+            } else {
+                (Op::Ge, part)
+            };
+            let version = version.trim_start_matches('v');
+            let valid = !version.is_empty()
+                && version
+                    .split('.')
+                    .all(|piece| !piece.is_empty() && piece.chars().all(|c| c.is_ascii_digit()));
+            valid.then(|| (op, version.to_string()))
+        })
+        .collect::<Option<_>>()?;
+    (!comparators.is_empty()).then_some(comparators)
+}
+
+/// Whether `constraint` is valid, for validating user input
+pub fn is_valid_constraint(constraint: &str) -> bool {
+    parse_constraint(constraint).is_some()
+}
+
+fn compare_versions(found: &[u64], wanted: &[u64]) -> Ordering {
+    for i in 0..found.len().max(wanted.len()) {
+        let ordering = found
+            .get(i)
+            .copied()
+            .unwrap_or(0)
+            .cmp(&wanted.get(i).copied().unwrap_or(0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// `None` when `constraint` cannot be parsed
+pub fn version_satisfies(version: &str, constraint: &str) -> Option<bool> {
+    let found = version_parts(version);
+    let satisfied = parse_constraint(constraint)?.iter().all(|(op, wanted)| {
+        let wanted = version_parts(wanted);
+        match op {
+            Op::Ge => compare_versions(&found, &wanted) != Ordering::Less,
+            Op::Gt => compare_versions(&found, &wanted) == Ordering::Greater,
+            Op::Le => compare_versions(&found, &wanted) != Ordering::Greater,
+            Op::Lt => compare_versions(&found, &wanted) == Ordering::Less,
+            // Only the parts given count: `=2` matches 2.4.1
+            Op::Eq => wanted
+                .iter()
+                .enumerate()
+                .all(|(i, part)| found.get(i).copied().unwrap_or(0) == *part),
+        }
+    });
+    Some(satisfied)
+}
+
+fn tool_program(tool_key: &str) -> Option<LocalCliProgram> {
+    let command = match tool_key {
+        "claude_code" => return Some(resolve_local_claude_program()),
+        "opencode" => return Some(resolve_local_opencode_program()),
+        "grok" => return Some(resolve_local_grok_program()),
+        "pi" => return Some(resolve_local_pi_program()),
+        "codex" => "codex",
+        "gemini_cli" => "gemini",
+        "qwen_code" => "qwen",
+        "cursor" => "cursor-agent",
+        "amp" => "amp",
+        "goose" => "goose",
+        "github_copilot" => "copilot",
+        "openclaw" => "openclaw",
+        "droid" => "droid",
+        _ => return None,
+    };
+    Some(resolve_local_command_program(command))
+}
+
+async fn probe_version(program: &LocalCliProgram) -> Option<String> {
+    let mut command = build_local_tokio_command(&program.path);
+    command
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(PROBE_TIMEOUT, command.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push('\n');
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    parse_version(&text)
+}
+
+/// Installed version of a tool's CLI, `None` when unknown
+pub async fn detect_tool_version(tool_key: &str) -> Option<String> {
+    let tool_key = canonical_tool_key(tool_key);
+    let cache = VERSION_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let now = now_ms();
+    if let Some((checked_at, version)) = cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(tool_key)
+    {
+        if now - checked_at < VERSION_CACHE_TTL_MS {
+            return version.clone();
+        }
+    }
+
+    let version = match tool_program(tool_key) {
+        Some(program) => probe_version(&program).await,
+        None => None,
+    };
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(tool_key.to_string(), (now, version.clone()));
+    version
+}
+
+async fn check_constraint(
+    tool: String,
+    constraint: String,
+    source: String,
+) -> SkillCompatibilityDto {
+    let detected_version = detect_tool_version(&tool).await;
+    let satisfied = detected_version
+        .as_deref()
+        .and_then(|version| version_satisfies(version, &constraint));
+    SkillCompatibilityDto {
+        tool,
+        constraint,
+        source,
+        detected_version,
+        satisfied,
+    }
+}
+
+/// Check the constraint `skill` has for `tool`, if any
+pub async fn check_tool(
+    skill: &Skill,
+    skill_dir: &Path,
+    tool: &str,
+) -> Option<SkillCompatibilityDto> {
+    let tool = canonical_tool_key(tool);
+    let (constraint, source) = skill_constraints(skill, skill_dir).remove(tool)?;
+    Some(check_constraint(tool.to_string(), constraint, source).await)
+}
+
+/// Check every constraint of `skill`
+pub async fn check_all(skill: &Skill, skill_dir: &Path) -> Vec<SkillCompatibilityDto> {
+    let mut result = Vec::new();
+    for (tool, (constraint, source)) in skill_constraints(skill, skill_dir) {
+        result.push(check_constraint(tool, constraint, source).await);
+    }
+    result
+}
+
+pub fn incompatible_error(check: &SkillCompatibilityDto) -> String {
+    format!(
+        "{}{}|{}|{}",
+        INCOMPATIBLE_ERROR_PREFIX,
+        check.tool,
+        check.constraint,
+        check.detected_version.as_deref().unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constraints_compare_versions() {
+        assert_eq!(version_satisfies("2.0.14", ">=2.0"), Some(true));
+        assert_eq!(version_satisfies("1.9.9", ">=2"), Some(false));
+        assert_eq!(version_satisfies("0.45.0", ">=0.40 <1"), Some(true));
+        assert_eq!(version_satisfies("1.0.0", ">=0.40, <1"), Some(false));
+        assert_eq!(version_satisfies("2.4.1", "=2"), Some(true));
+        assert_eq!(version_satisfies("3.0", "=2"), Some(false));
+        assert_eq!(version_satisfies("2.1", "2.1"), Some(true));
+        assert_eq!(version_satisfies("2.1", ">=two"), None);
+        assert_eq!(version_satisfies("2.1", ""), None);
+    }
+
+    #[test]
+    fn frontmatter_block_is_read_until_the_next_key() {
+        let content = "---\nname: demo\ncompatibility:\n  claude_code: \">=2.0\"\n  codex: '>=0.40 <1'\ndescription: x\n  ignored: '>=9'\n---\nbody\n";
+        let constraints = parse_frontmatter_constraints(content);
+        assert_eq!(constraints.len(), 2);
+        assert_eq!(constraints["claude_code"], ">=2.0");
+        assert_eq!(constraints["codex"], ">=0.40 <1");
+    }
+}
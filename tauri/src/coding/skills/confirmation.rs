@@ -0,0 +1,191 @@
+//! Confirmation tokens for dangerous skill operations
+//!
+//! Operations that can destroy data in bulk (deleting central repo content,
+//! overwriting many tool targets at once, emptying the sync trash) require a short-lived token issued
+//! by `skills_request_confirmation`. The token carries a human-readable
+//! summary for the UI and is bound to the operation and, when given, to the
+//! exact skill ids it covers, so a stray frontend call cannot reuse it for
+//! other skills. Tokens live in memory only.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{now_ms, Skill};
+use crate::coding::db_new_id;
+
+/// Re-syncs touching more targets than this need confirmation
+pub const OVERWRITE_TARGETS_THRESHOLD: usize = 20;
+const TOKEN_TTL_MS: i64 = 5 * 60 * 1000;
+pub const CONFIRMATION_ERROR_PREFIX: &str = "CONFIRMATION_REQUIRED|";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DangerousOp {
+    /// Delete skill directories from the central repository
+    DeleteCentralContent,
+    /// Overwrite more than `OVERWRITE_TARGETS_THRESHOLD` tool targets
+    OverwriteTargets,
+    /// Empty the sync trash, dropping every rollback
+    PurgeTrash,
+}
+
+impl DangerousOp {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DangerousOp::DeleteCentralContent => "delete_central_content",
+            DangerousOp::OverwriteTargets => "overwrite_targets",
+            DangerousOp::PurgeTrash => "purge_trash",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfirmationDto {
+    pub token: String,
+    pub op: DangerousOp,
+    pub summary: String,
+    /// Skill ids the token is valid for (empty = the whole operation)
+    pub skill_ids: Vec<String>,
+    pub expires_at: i64,
+}
+
+struct PendingConfirmation {
+    op: DangerousOp,
+    remaining: HashSet<String>,
+    scoped: bool,
+    expires_at: i64,
+}
+
+fn pending() -> &'static Mutex<HashMap<String, PendingConfirmation>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, PendingConfirmation>>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn confirmation_required(op: DangerousOp) -> String {
+    format!("{}{}", CONFIRMATION_ERROR_PREFIX, op.as_str())
+}
+
+/// Issue a token for `op` covering `skill_ids` (empty = unscoped)
+pub fn issue(op: DangerousOp, skill_ids: Vec<String>, summary: String) -> ConfirmationDto {
+    let now = now_ms();
+    let token = db_new_id();
+    if let Ok(mut pending) = pending().lock() {
+        pending.retain(|_, entry| entry.expires_at > now);
+        pending.insert(
+            token.clone(),
+            PendingConfirmation {
+                op,
+                remaining: skill_ids.iter().cloned().collect(),
+                scoped: !skill_ids.is_empty(),
+                expires_at: now + TOKEN_TTL_MS,
+            },
+        );
+    }
+    ConfirmationDto {
+        token,
+        op,
+        summary,
+        skill_ids,
+        expires_at: now + TOKEN_TTL_MS,
+    }
+}
+
+/// Validate and consume a token. Scoped tokens are spent one skill id at a
+/// time and dropped once every id was used; unscoped tokens are single-use.
+pub fn consume(op: DangerousOp, skill_id: Option<&str>, token: Option<&str>) -> Result<(), String> {
+    let token = token
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| confirmation_required(op))?;
+    let mut pending = pending().lock().map_err(|e| e.to_string())?;
+    let now = now_ms();
+    let entry = pending
+        .get_mut(token)
+        .filter(|entry| entry.op == op && entry.expires_at > now)
+        .ok_or_else(|| confirmation_required(op))?;
+
+    if entry.scoped {
+        let skill_id = skill_id.ok_or_else(|| confirmation_required(op))?;
+        if !entry.remaining.remove(skill_id) {
+            return Err(confirmation_required(op));
+        }
+        if !entry.remaining.is_empty() {
+            return Ok(());
+        }
+    }
+    pending.remove(token);
+    Ok(())
+}
+
+/// Number of tool targets a full re-sync would overwrite
+pub fn count_resync_targets(skills: &[Skill]) -> usize {
+    skills
+        .iter()
+        .filter(|skill| skill.management_enabled)
+        .map(|skill| skill.enabled_tools.len())
+        .sum()
+}
+
+/// Human-readable summary for deleting central content of `skills`
+pub fn delete_summary(skills: &[(String, usize)]) -> String {
+    let names = skills
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let targets: usize = skills.iter().map(|(_, targets)| targets).sum();
+    format!(
+        "Delete {} skill(s) from the central repository ({}) and remove {} tool target(s)",
+        skills.len(),
+        names,
+        targets
+    )
+}
+
+/// Human-readable summary for emptying the sync trash
+pub fn purge_trash_summary(entries: usize, bytes: u64) -> String {
+    format!(
+        "Empty the sync trash: {} entr{} ({:.1} MiB) can no longer be rolled back",
+        entries,
+        if entries == 1 { "y" } else { "ies" },
+        bytes as f64 / (1024.0 * 1024.0)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_token_only_covers_its_skills() {
+        let dto = issue(
+            DangerousOp::DeleteCentralContent,
+            vec!["a".to_string(), "b".to_string()],
+            String::new(),
+        );
+        let token = Some(dto.token.as_str());
+        let op = DangerousOp::DeleteCentralContent;
+
+        assert!(consume(op, Some("c"), token).is_err());
+        assert!(consume(DangerousOp::OverwriteTargets, None, token).is_err());
+        assert!(consume(op, Some("a"), token).is_ok());
+        assert!(consume(op, Some("a"), token).is_err());
+        assert!(consume(op, Some("b"), token).is_ok());
+        // Fully spent
+        assert!(consume(op, Some("b"), token).is_err());
+    }
+
+    #[test]
+    fn missing_token_reports_required_op() {
+        assert_eq!(
+            consume(DangerousOp::OverwriteTargets, None, None).unwrap_err(),
+            "CONFIRMATION_REQUIRED|overwrite_targets"
+        );
+
+        let dto = issue(DangerousOp::OverwriteTargets, Vec::new(), String::new());
+        let token = Some(dto.token.as_str());
+        assert!(consume(DangerousOp::OverwriteTargets, None, token).is_ok());
+        assert!(consume(DangerousOp::OverwriteTargets, None, token).is_err());
+    }
+}
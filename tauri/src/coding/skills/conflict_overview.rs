@@ -0,0 +1,282 @@
+//! Standing overview of skills whose content differs between tools
+//!
+//! The onboarding plan reports conflicts once, among unmanaged skills only.
+//! This overview looks at everything the tools hold right now, managed
+//! targets included, and lists per skill name which tools carry which
+//! version. Fingerprints come from an unfiltered scan with its own cached
+//! index (`onboarding::build_unfiltered_plan`), so only directories that
+//! changed since the last overview are hashed again.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::adapter::parse_sync_details;
+use super::onboarding::build_unfiltered_plan;
+use super::path_executor::target_path_changed;
+use super::skill_store;
+use super::types::{now_ms, OnboardingPlan, Skill};
+use super::unicode_names::nfc_name;
+use crate::db::SqliteDbState;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConflictCellDto {
+    pub tool: String,
+    pub tool_display: String,
+    pub path: String,
+    pub is_link: bool,
+    /// A sync target recorded for the managed skill of this name
+    pub managed: bool,
+    /// `None` when the directory could not be hashed
+    pub fingerprint: Option<String>,
+    /// Index into the row's versions: the central repo's content first when
+    /// it is among them, then the most widespread
+    pub version: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillConflictDto {
+    pub name: String,
+    /// Managed skill with this name, if any
+    pub skill_id: Option<String>,
+    /// Distinct contents among the cells
+    pub versions: usize,
+    pub cells: Vec<ConflictCellDto>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConflictToolDto {
+    pub key: String,
+    pub display: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ConflictOverviewDto {
+    /// Skills held in divergent versions, most versions first
+    pub skills: Vec<SkillConflictDto>,
+    /// Tools that hold at least one of those skills, the heat map's columns
+    pub tools: Vec<ConflictToolDto>,
+    /// Skill directories looked at
+    pub scanned: usize,
+    /// Directories hashed again because they changed since the last overview
+    pub rehashed_dirs: usize,
+    pub generated_at: i64,
+}
+
+/// Scan the installed tools and report the skills whose content diverges
+pub async fn get_conflict_overview(state: &SqliteDbState) -> Result<ConflictOverviewDto, String> {
+    let plan = build_unfiltered_plan(state)
+        .await
+        .map_err(|e| format!("{:#}", e))?;
+    let skills = skill_store::get_managed_skills(state).await?;
+    Ok(conflict_overview(&plan, &skills))
+}
+
+/// Rows for the names of `plan` whose variants hold more than one content
+pub fn conflict_overview(plan: &OnboardingPlan, skills: &[Skill]) -> ConflictOverviewDto {
+    let managed: HashMap<String, &Skill> = skills
+        .iter()
+        .map(|skill| (nfc_name(&skill.name), skill))
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut tools = BTreeMap::new();
+    for group in &plan.groups {
+        let skill = managed.get(&group.name).copied();
+        let fingerprints: BTreeSet<&str> = group
+            .variants
+            .iter()
+            .filter_map(|variant| variant.fingerprint.as_deref())
+            .collect();
+        if fingerprints.len() < 2 {
+            continue;
+        }
+        let versions = rank_versions(
+            &fingerprints,
+            group
+                .variants
+                .iter()
+                .filter_map(|variant| variant.fingerprint.as_deref()),
+            skill.and_then(|skill| skill.content_hash.as_deref()),
+        );
+        let targets = skill.map(parse_sync_details).unwrap_or_default();
+
+        let cells = group
+            .variants
+            .iter()
+            .map(|variant| {
+                tools
+                    .entry(variant.tool.clone())
+                    .or_insert_with(|| variant.tool_display.clone());
+                ConflictCellDto {
+                    tool: variant.tool.clone(),
+                    tool_display: variant.tool_display.clone(),
+                    path: variant.path.clone(),
+                    is_link: variant.is_link,
+                    managed: targets.iter().any(|target| {
+                        target.tool == variant.tool
+                            && !target_path_changed(&target.target_path, Path::new(&variant.path))
+                    }),
+                    fingerprint: variant.fingerprint.clone(),
+                    version: variant
+                        .fingerprint
+                        .as_deref()
+                        .and_then(|fingerprint| versions.get(fingerprint).copied()),
+                }
+            })
+            .collect();
+        rows.push(SkillConflictDto {
+            name: group.name.clone(),
+            skill_id: skill.map(|skill| skill.id.clone()),
+            versions: fingerprints.len(),
+            cells,
+        });
+    }
+    rows.sort_by(|a, b| {
+        (Reverse(a.versions), Reverse(a.cells.len()), &a.name).cmp(&(
+            Reverse(b.versions),
+            Reverse(b.cells.len()),
+            &b.name,
+        ))
+    });
+
+    ConflictOverviewDto {
+        skills: rows,
+        tools: tools
+            .into_iter()
+            .map(|(key, display)| ConflictToolDto { key, display })
+            .collect(),
+        scanned: plan.total_skills_found,
+        rehashed_dirs: plan.rehashed_dirs,
+        generated_at: now_ms(),
+    }
+}
+
+/// Number the distinct fingerprints: `central` first when present, then by
+/// how many cells hold each one
+fn rank_versions<'a>(
+    distinct: &BTreeSet<&'a str>,
+    all: impl Iterator<Item = &'a str>,
+    central: Option<&str>,
+) -> HashMap<&'a str, usize> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for fingerprint in all {
+        *counts.entry(fingerprint).or_default() += 1;
+    }
+    let mut ordered: Vec<&str> = distinct.iter().copied().collect();
+    ordered
+        .sort_by_key(|fingerprint| (Some(*fingerprint) != central, Reverse(counts[fingerprint])));
+    ordered
+        .into_iter()
+        .enumerate()
+        .map(|(index, fingerprint)| (fingerprint, index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use crate::coding::skills::types::{OnboardingGroup, OnboardingVariant};
+    use serde_json::json;
+
+    fn variant(tool: &str, name: &str, fingerprint: &str) -> OnboardingVariant {
+        OnboardingVariant {
+            tool: tool.to_string(),
+            tool_display: tool.to_uppercase(),
+            name: name.to_string(),
+            path: format!("/home/u/.{tool}/skills/{name}"),
+            fingerprint: Some(fingerprint.to_string()),
+            is_link: false,
+            link_target: None,
+            linked_folder: None,
+            conflicting_tools: Vec::new(),
+        }
+    }
+
+    fn group(name: &str, variants: Vec<OnboardingVariant>) -> OnboardingGroup {
+        OnboardingGroup {
+            name: name.to_string(),
+            has_conflict: false,
+            variants,
+        }
+    }
+
+    #[test]
+    fn reports_divergent_names_with_managed_targets_and_central_first() {
+        let plan = OnboardingPlan {
+            total_tools_scanned: 3,
+            total_skills_found: 6,
+            groups: vec![
+                group(
+                    "review",
+                    vec![
+                        variant("claude", "review", "edited"),
+                        variant("codex", "review", "central"),
+                        variant("cursor", "review", "edited"),
+                    ],
+                ),
+                group(
+                    "lint",
+                    vec![
+                        variant("claude", "lint", "a"),
+                        variant("codex", "lint", "b"),
+                    ],
+                ),
+                group(
+                    "same",
+                    vec![
+                        variant("claude", "same", "x"),
+                        variant("codex", "same", "x"),
+                    ],
+                ),
+            ],
+            previous_scan_at: None,
+            new_since_last_scan: Vec::new(),
+            rehashed_dirs: 0,
+            system_skills: Vec::new(),
+        };
+        let skill = from_db_skill(json!({
+            "id": "skill-1",
+            "name": "review",
+            "central_path": "/central/review",
+            "content_hash": "central",
+            "sync_details": {
+                "codex": { "target_path": "/home/u/.codex/skills/review", "mode": "copy" },
+            },
+        }));
+
+        let overview = conflict_overview(&plan, &[skill]);
+
+        let names: Vec<&str> = overview
+            .skills
+            .iter()
+            .map(|row| row.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["review", "lint"]);
+        let review = &overview.skills[0];
+        assert_eq!(review.skill_id.as_deref(), Some("skill-1"));
+        assert_eq!(review.versions, 2);
+        let cells: Vec<(&str, bool, Option<usize>)> = review
+            .cells
+            .iter()
+            .map(|cell| (cell.tool.as_str(), cell.managed, cell.version))
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                ("claude", false, Some(1)),
+                ("codex", true, Some(0)),
+                ("cursor", false, Some(1)),
+            ]
+        );
+        let tools: Vec<&str> = overview
+            .tools
+            .iter()
+            .map(|tool| tool.key.as_str())
+            .collect();
+        assert_eq!(tools, vec!["claude", "codex", "cursor"]);
+    }
+}
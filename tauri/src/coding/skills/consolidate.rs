@@ -0,0 +1,197 @@
+//! Consolidation of legacy per-tool copies
+//!
+//! Users often copied the same skill into several tools by hand before using
+//! the app. A group from the onboarding plan is a consolidation candidate when
+//! at least two tools hold a plain copy of it at `<tool skills dir>/<name>`
+//! (exactly where a sync would place it) and all those copies share one
+//! fingerprint. Consolidating adopts the skill once into the central repo and
+//! replaces every copy with a managed link target.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::types::OnboardingPlan;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConsolidationCopyDto {
+    pub tool: String,
+    pub tool_display: String,
+    pub path: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConsolidationCandidateDto {
+    pub name: String,
+    pub fingerprint: String,
+    pub copies: Vec<ConsolidationCopyDto>,
+    /// Size of a single copy on disk
+    pub bytes_per_copy: u64,
+    /// Bytes freed if every copy becomes a link (one copy stays in the central repo)
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConsolidatedSkillDto {
+    pub name: String,
+    pub skill_id: String,
+    /// Tools whose copy was replaced by a managed target
+    pub synced_tools: Vec<String>,
+    /// Tools left untouched (copy changed since scan, or sync failed)
+    pub skipped_tools: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConsolidationFailureDto {
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ConsolidationResultDto {
+    pub consolidated: Vec<ConsolidatedSkillDto>,
+    pub failed: Vec<ConsolidationFailureDto>,
+    pub bytes_reclaimed: u64,
+}
+
+/// Pick consolidation candidates from an onboarding plan.
+///
+/// `skills_dirs` maps adapter keys to their resolved skills dir; variants from
+/// other sources (plugins, extra stores, file-based skills) are never in it and
+/// are ignored, as are existing links.
+pub fn find_candidates(
+    plan: &OnboardingPlan,
+    skills_dirs: &HashMap<String, PathBuf>,
+) -> Vec<ConsolidationCandidateDto> {
+    let mut candidates = Vec::new();
+    for group in &plan.groups {
+        let copies = group
+            .variants
+            .iter()
+            .filter(|v| !v.is_link)
+            .filter(|v| {
+                skills_dirs
+                    .get(&v.tool)
+                    .is_some_and(|dir| dir.join(&group.name) == Path::new(&v.path))
+            })
+            .collect::<Vec<_>>();
+        if copies.len() < 2 {
+            continue;
+        }
+        let Some(fingerprint) = copies[0].fingerprint.clone() else {
+            continue;
+        };
+        // Differing copies need a manual pick in the import dialog
+        if copies
+            .iter()
+            .any(|v| v.fingerprint.as_deref() != Some(fingerprint.as_str()))
+        {
+            continue;
+        }
+        let bytes_per_copy = dir_size(Path::new(&copies[0].path));
+        candidates.push(ConsolidationCandidateDto {
+            name: group.name.clone(),
+            fingerprint,
+            bytes_per_copy,
+            reclaimable_bytes: bytes_per_copy * (copies.len() as u64 - 1),
+            copies: copies
+                .into_iter()
+                .map(|v| ConsolidationCopyDto {
+                    tool: v.tool.clone(),
+                    tool_display: v.tool_display.clone(),
+                    path: v.path.clone(),
+                })
+                .collect(),
+        });
+    }
+    candidates.sort_by(|a, b| a.name.cmp(&b.name));
+    candidates
+}
+
+/// Total size of regular files under `path` (links are not followed)
+pub fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::types::{OnboardingGroup, OnboardingVariant};
+
+    fn variant(tool: &str, path: &Path, fingerprint: &str, is_link: bool) -> OnboardingVariant {
+        OnboardingVariant {
+            tool: tool.to_string(),
+            tool_display: tool.to_string(),
+            name: "demo".to_string(),
+            path: path.to_string_lossy().to_string(),
+            fingerprint: Some(fingerprint.to_string()),
+            is_link,
+            link_target: None,
+            linked_folder: None,
+            conflicting_tools: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn candidates_require_identical_copies_in_tool_dirs() {
+        let temp = tempfile::tempdir().unwrap();
+        let dirs: HashMap<String, PathBuf> = ["a", "b", "c"]
+            .iter()
+            .map(|tool| (tool.to_string(), temp.path().join(tool)))
+            .collect();
+        for tool in ["a", "b"] {
+            let skill = temp.path().join(tool).join("demo");
+            std::fs::create_dir_all(&skill).unwrap();
+            std::fs::write(skill.join("SKILL.md"), "0123456789").unwrap();
+        }
+        let at = |tool: &str| temp.path().join(tool).join("demo");
+        let plan = |variants| OnboardingPlan {
+            total_tools_scanned: 3,
+            total_skills_found: 3,
+            groups: vec![OnboardingGroup {
+                name: "demo".to_string(),
+                variants,
+                has_conflict: false,
+            }],
+            previous_scan_at: None,
+            new_since_last_scan: Vec::new(),
+            rehashed_dirs: 0,
+            system_skills: Vec::new(),
+        };
+
+        let found = find_candidates(
+            &plan(vec![
+                variant("a", &at("a"), "fp", false),
+                variant("b", &at("b"), "fp", false),
+                // Links and unknown sources are not copies to consolidate
+                variant("c", &at("c"), "fp", true),
+                variant("cc_switch", &at("a"), "fp", false),
+            ]),
+            &dirs,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].copies.len(), 2);
+        assert_eq!(found[0].bytes_per_copy, 10);
+        assert_eq!(found[0].reclaimable_bytes, 10);
+
+        let conflicting = find_candidates(
+            &plan(vec![
+                variant("a", &at("a"), "fp", false),
+                variant("b", &at("b"), "other", false),
+            ]),
+            &dirs,
+        );
+        assert!(conflicting.is_empty());
+
+        let single = find_candidates(&plan(vec![variant("a", &at("a"), "fp", false)]), &dirs);
+        assert!(single.is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+//! Commands for merging duplicate copies and the cross-tool content reports
+//! (`consolidate.rs`, `duplicate_content.rs`, `conflict_overview.rs`)
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use tauri::{Emitter, State};
+
+use super::commands::{
+    format_error, onboarding_plan_with_timeout, resolve_skill_source_path,
+    sync_skill_to_tool_record,
+};
+use super::conflict_overview::{self, ConflictOverviewDto};
+use super::consolidate::{
+    self, ConsolidatedSkillDto, ConsolidationCandidateDto, ConsolidationFailureDto,
+    ConsolidationResultDto,
+};
+use super::content_hash::hash_dir;
+use super::duplicate_content::{self, DuplicateContentReportDto};
+use super::installer::install_local_skill;
+use super::skill_store;
+use super::tool_adapters::{get_all_tool_adapters, resolve_runtime_skills_path_with_state_async};
+use super::types::CustomTool;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+async fn consolidation_candidates(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    custom_tools: &[CustomTool],
+) -> Result<Vec<ConsolidationCandidateDto>, String> {
+    let (plan, _) = onboarding_plan_with_timeout(app, state, true).await?;
+    let mut skills_dirs = HashMap::new();
+    for adapter in get_all_tool_adapters(custom_tools) {
+        if let Ok(dir) = resolve_runtime_skills_path_with_state_async(state.db(), &adapter).await {
+            skills_dirs.insert(adapter.key.clone(), dir);
+        }
+    }
+    Ok(consolidate::find_candidates(&plan, &skills_dirs))
+}
+
+/// Skills copied by hand into several tools with identical content
+#[tauri::command]
+pub async fn skills_get_consolidation_candidates(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<ConsolidationCandidateDto>, String> {
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    consolidation_candidates(&app, &state, &custom_tools).await
+}
+
+/// Clusters of identical content among unmanaged skills and copy-mode targets,
+/// whatever their names; see `duplicate_content`
+#[tauri::command]
+pub async fn skills_get_duplicate_content(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<DuplicateContentReportDto, String> {
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let (plan, _) = onboarding_plan_with_timeout(&app, &state, true).await?;
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let tool_names = get_all_tool_adapters(&custom_tools)
+        .into_iter()
+        .map(|adapter| (adapter.key, adapter.display_name))
+        .collect::<HashMap<_, _>>();
+    tokio::task::spawn_blocking(move || {
+        duplicate_content::find_duplicate_content(&plan, &skills, &tool_names)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))
+}
+
+/// Per skill name, which tools hold which content right now, managed targets
+/// included; see `conflict_overview`
+#[tauri::command]
+pub async fn skills_get_conflict_overview(
+    state: State<'_, SqliteDbState>,
+) -> Result<ConflictOverviewDto, String> {
+    conflict_overview::get_conflict_overview(&state).await
+}
+
+/// Adopt each candidate once and turn every per-tool copy into a managed target.
+/// `names` limits the run to those candidates; `None` consolidates all of them.
+#[tauri::command]
+pub async fn skills_consolidate_copies(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    names: Option<Vec<String>>,
+) -> Result<ConsolidationResultDto, String> {
+    ensure_writable("skills_consolidate_copies")?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let candidates = consolidation_candidates(&app, &state, &custom_tools).await?;
+
+    let mut result = ConsolidationResultDto::default();
+    for candidate in candidates {
+        if let Some(names) = names.as_ref() {
+            if !names.contains(&candidate.name) {
+                continue;
+            }
+        }
+        match consolidate_candidate(&app, &state, &candidate, &custom_tools).await {
+            Ok((consolidated, linked)) => {
+                // The central repo keeps one copy; only linked copies free space
+                result.bytes_reclaimed += candidate
+                    .bytes_per_copy
+                    .saturating_mul(linked)
+                    .saturating_sub(candidate.bytes_per_copy);
+                result.consolidated.push(consolidated);
+            }
+            Err(error) => result.failed.push(ConsolidationFailureDto {
+                name: candidate.name.clone(),
+                error,
+            }),
+        }
+    }
+
+    if !result.consolidated.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(result)
+}
+
+/// Returns the consolidated skill and how many copies became links
+async fn consolidate_candidate(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    candidate: &ConsolidationCandidateDto,
+    custom_tools: &[CustomTool],
+) -> Result<(ConsolidatedSkillDto, u64), String> {
+    let unchanged = |path: &str| {
+        hash_dir(Path::new(path)).is_ok_and(|fingerprint| fingerprint == candidate.fingerprint)
+    };
+    let origin = candidate
+        .copies
+        .iter()
+        .find(|copy| unchanged(&copy.path))
+        .ok_or_else(|| "All copies changed since the scan".to_string())?;
+
+    let installed = install_local_skill(app, state, Path::new(&origin.path), false)
+        .await
+        .map_err(format_error)?;
+    let skill = skill_store::get_skill_by_id(state, &installed.skill_id)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", installed.skill_id))?;
+    let source_path = resolve_skill_source_path(app, state, &skill).await?;
+
+    let mut consolidated = ConsolidatedSkillDto {
+        name: skill.name.clone(),
+        skill_id: skill.id.clone(),
+        synced_tools: Vec::new(),
+        skipped_tools: Vec::new(),
+    };
+    let mut linked = 0;
+    for copy in &candidate.copies {
+        if !unchanged(&copy.path) {
+            consolidated.skipped_tools.push(copy.tool.clone());
+            continue;
+        }
+        match sync_skill_to_tool_record(state, &skill, &copy.tool, &source_path, true, custom_tools)
+            .await
+        {
+            Ok(synced) => {
+                if synced.mode_used != "copy" {
+                    linked += 1;
+                }
+                consolidated.synced_tools.push(copy.tool.clone());
+            }
+            Err(err) => {
+                log::warn!(
+                    "[consolidate] failed to sync {} to {}: {}",
+                    skill.name,
+                    copy.tool,
+                    err
+                );
+                consolidated.skipped_tools.push(copy.tool.clone());
+            }
+        }
+    }
+    Ok((consolidated, linked))
+}
@@ -0,0 +1,229 @@
+//! Context budget estimation per tool
+//!
+//! Sums estimated tokens of the global instructions file and every enabled
+//! skill's SKILL.md for one tool, and suggests skills to disable when the
+//! total exceeds the tool's budget. Budgets are stored in
+//! `skill_settings:skills.context_budgets` keyed by tool key.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+
+use super::central_repo::{
+    merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite, resolve_skill_central_path,
+};
+use super::types::{now_ms, Skill};
+use super::variants;
+use crate::coding::runtime_location;
+use crate::SqliteDbState;
+
+const BUDGETS_SETTINGS_KEY: &str = "context_budgets";
+pub const DEFAULT_CONTEXT_BUDGET_TOKENS: u64 = 20_000;
+
+/// One instructions file or skill counted against the budget
+#[derive(Clone, Debug, Serialize)]
+pub struct ContextBudgetItemDto {
+    /// Skill id, or the file path for instructions
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub tokens: u64,
+    /// Last access time of the file (ms), used as a usage signal
+    pub last_accessed_at: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ContextBudgetDto {
+    pub tool: String,
+    pub budget_tokens: u64,
+    pub total_tokens: u64,
+    pub over_budget: bool,
+    pub instructions: Vec<ContextBudgetItemDto>,
+    pub skills: Vec<ContextBudgetItemDto>,
+    /// Skill ids that would bring the total back under budget, least used first
+    pub suggested_disables: Vec<String>,
+}
+
+/// Rough token estimate: ~4 ASCII characters per token, one token per
+/// non-ASCII character (CJK text is close to 1:1).
+pub fn estimate_tokens(text: &str) -> u64 {
+    let (ascii, other) = text.chars().fold((0u64, 0u64), |(ascii, other), ch| {
+        if ch.is_ascii() {
+            (ascii + 1, other)
+        } else {
+            (ascii, other + 1)
+        }
+    });
+    ascii.div_ceil(4) + other
+}
+
+pub fn load_budgets(state: &SqliteDbState) -> BTreeMap<String, u64> {
+    read_skill_settings_value_from_sqlite(state, BUDGETS_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_budget(state: &SqliteDbState, tool: &str, budget: Option<u64>) -> Result<(), String> {
+    let mut budgets = load_budgets(state);
+    match budget {
+        Some(budget) => budgets.insert(tool.to_string(), budget),
+        None => budgets.remove(tool),
+    };
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            BUDGETS_SETTINGS_KEY: budgets,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+fn file_item(id: String, name: String, path: &Path) -> Option<ContextBudgetItemDto> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let last_accessed_at = std::fs::metadata(path)
+        .and_then(|meta| meta.accessed())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64);
+    Some(ContextBudgetItemDto {
+        id,
+        name,
+        path: path.to_string_lossy().to_string(),
+        tokens: estimate_tokens(&content),
+        last_accessed_at,
+    })
+}
+
+async fn instructions_path(state: &SqliteDbState, tool: &str) -> Option<PathBuf> {
+    let db = state.db();
+    let path = match tool {
+        "claude_code" => runtime_location::get_claude_prompt_path_async(db).await,
+        "codex" => runtime_location::get_codex_prompt_path_async(db).await,
+        "gemini_cli" => runtime_location::get_gemini_cli_prompt_path_async(db).await,
+        "grok" => runtime_location::get_grok_prompt_path_async(db).await,
+        "opencode" => runtime_location::get_opencode_prompt_path_async(db).await,
+        _ => return None,
+    };
+    path.ok()
+}
+
+/// Pick skills to disable, least recently used first (never-accessed first,
+/// larger skills first on ties), until `total` fits in `budget`.
+pub fn suggest_disables(skills: &[ContextBudgetItemDto], total: u64, budget: u64) -> Vec<String> {
+    if total <= budget {
+        return Vec::new();
+    }
+    let mut candidates: Vec<&ContextBudgetItemDto> = skills.iter().collect();
+    candidates.sort_by(|a, b| {
+        a.last_accessed_at
+            .cmp(&b.last_accessed_at)
+            .then(b.tokens.cmp(&a.tokens))
+    });
+
+    let mut remaining = total;
+    let mut suggested = Vec::new();
+    for skill in candidates {
+        if remaining <= budget {
+            break;
+        }
+        remaining = remaining.saturating_sub(skill.tokens);
+        suggested.push(skill.id.clone());
+    }
+    suggested
+}
+
+/// Build the context budget report for one tool
+pub async fn build_context_budget(
+    state: &SqliteDbState,
+    tool: &str,
+    skills: &[Skill],
+    central_dir: &Path,
+) -> Result<ContextBudgetDto, String> {
+    let mut instructions = Vec::new();
+    if let Some(path) = instructions_path(state, tool).await {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if let Some(item) = file_item(path.to_string_lossy().to_string(), name, &path) {
+            instructions.push(item);
+        }
+    }
+
+    let mut skill_items = Vec::new();
+    for skill in skills.iter().filter(|skill| {
+        skill.management_enabled && skill.enabled_tools.iter().any(|key| key == tool)
+    }) {
+        let source = match variants::resolve_variant_source(state, &skill.id, tool)? {
+            Some(path) => path,
+            None => resolve_skill_central_path(&skill.central_path, central_dir),
+        };
+        if let Some(item) = file_item(
+            skill.id.clone(),
+            skill.name.clone(),
+            &source.join("SKILL.md"),
+        ) {
+            skill_items.push(item);
+        }
+    }
+
+    let budget_tokens = load_budgets(state)
+        .get(tool)
+        .copied()
+        .unwrap_or(DEFAULT_CONTEXT_BUDGET_TOKENS);
+    let total_tokens = instructions
+        .iter()
+        .chain(skill_items.iter())
+        .map(|item| item.tokens)
+        .sum();
+    let suggested_disables = suggest_disables(&skill_items, total_tokens, budget_tokens);
+
+    Ok(ContextBudgetDto {
+        tool: tool.to_string(),
+        budget_tokens,
+        total_tokens,
+        over_budget: total_tokens > budget_tokens,
+        instructions,
+        skills: skill_items,
+        suggested_disables,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, tokens: u64, last_accessed_at: Option<i64>) -> ContextBudgetItemDto {
+        ContextBudgetItemDto {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: String::new(),
+            tokens,
+            last_accessed_at,
+        }
+    }
+
+    #[test]
+    fn estimate_tokens_counts_ascii_and_cjk() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens("技能"), 2);
+    }
+
+    #[test]
+    fn suggestions_prefer_least_recently_used() {
+        let skills = vec![
+            item("recent", 500, Some(300)),
+            item("old", 200, Some(100)),
+            item("never", 100, None),
+        ];
+        assert!(suggest_disables(&skills, 800, 1_000).is_empty());
+        assert_eq!(
+            suggest_disables(&skills, 1_000, 750),
+            vec!["never".to_string(), "old".to_string()]
+        );
+    }
+}
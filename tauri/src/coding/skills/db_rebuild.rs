@@ -0,0 +1,164 @@
+//! Rebuild skill records after the database was lost
+//!
+//! The central repo and the tool skills dirs still describe the library when
+//! the database file is gone or was reset: every central skill dir becomes a
+//! `central` skill again (name from the SKILL.md frontmatter, content hash,
+//! license), links in the tool skills dirs that resolve into one of those dirs
+//! become its link targets, and marked copies (`target_marker`) its copy
+//! targets. Existing records are never changed, so a rebuild can also be run
+//! on a partly populated database.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::adapter::parse_sync_details;
+use super::audit::target_disk_mode;
+use super::central_repo::resolve_skill_central_path;
+use super::target_marker::RecoveredTargetDto;
+use super::types::{Skill, SkillTarget};
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DatabaseRebuildDto {
+    /// Central skill dirs recorded as skills again
+    pub adopted: Vec<String>,
+    /// Central skill dirs left out (name already recorded or clashing)
+    pub skipped: Vec<String>,
+    pub recovered_targets: Vec<RecoveredTargetDto>,
+    /// Marked copies whose skill is not in the library
+    pub unmatched_markers: Vec<String>,
+}
+
+/// Link targets to restore: links directly inside the skills dirs `dirs`
+/// (dir -> tool key) that resolve to the central dir of a skill without a
+/// target for that tool
+pub fn plan_link_targets(
+    skills: &[Skill],
+    central_dir: &Path,
+    dirs: &[(PathBuf, String)],
+) -> (Vec<(String, SkillTarget)>, Vec<RecoveredTargetDto>) {
+    let by_central_dir: BTreeMap<PathBuf, &Skill> = skills
+        .iter()
+        .filter_map(|skill| {
+            let path = resolve_skill_central_path(&skill.central_path, central_dir);
+            std::fs::canonicalize(path).ok().map(|real| (real, skill))
+        })
+        .collect();
+
+    let mut records: Vec<(String, SkillTarget)> = Vec::new();
+    let mut recovered = Vec::new();
+    for (dir, tool) in dirs {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            let Some(mode) = target_disk_mode(&path).filter(|mode| *mode != "copy") else {
+                continue;
+            };
+            let Some(skill) = std::fs::canonicalize(&path)
+                .ok()
+                .and_then(|real| by_central_dir.get(&real).copied())
+            else {
+                continue;
+            };
+            let recorded = parse_sync_details(skill)
+                .iter()
+                .any(|target| &target.tool == tool)
+                || records
+                    .iter()
+                    .any(|(skill_id, target)| skill_id == &skill.id && &target.tool == tool);
+            if recorded {
+                continue;
+            }
+            let target_path = path.to_string_lossy().to_string();
+            records.push((
+                skill.id.clone(),
+                SkillTarget {
+                    tool: tool.clone(),
+                    target_path: target_path.clone(),
+                    mode: mode.to_string(),
+                    status: "ok".to_string(),
+                    synced_at: None,
+                    error_message: None,
+                    path_rules: Default::default(),
+                },
+            ));
+            recovered.push(RecoveredTargetDto {
+                skill_id: skill.id.clone(),
+                skill_name: skill.name.clone(),
+                tool: tool.clone(),
+                target_path,
+            });
+        }
+    }
+    (records, recovered)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+
+    fn skill(id: &str, name: &str, sync_details: serde_json::Value) -> Skill {
+        from_db_skill(serde_json::json!({
+            "id": id,
+            "name": name,
+            "source_type": "central",
+            "central_path": name,
+            "status": "ok",
+            "sync_details": sync_details,
+        }))
+    }
+
+    #[test]
+    fn restores_links_into_the_central_repo_only() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let tool_dir = temp.path().join("tool");
+        let elsewhere = temp.path().join("elsewhere");
+        for dir in [
+            central.join("review"),
+            central.join("deploy"),
+            tool_dir.join("copied"),
+            elsewhere.clone(),
+        ] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        for (link, target) in [
+            ("review", central.join("review")),
+            ("deploy", central.join("deploy")),
+            ("foreign", elsewhere),
+        ] {
+            std::os::unix::fs::symlink(target, tool_dir.join(link)).unwrap();
+        }
+
+        let skills = vec![
+            skill("review-id", "review", serde_json::json!({})),
+            skill(
+                "deploy-id",
+                "deploy",
+                serde_json::json!({
+                    "claude": { "target_path": "x", "mode": "copy", "status": "ok" }
+                }),
+            ),
+        ];
+        let (records, recovered) = plan_link_targets(
+            &skills,
+            &central,
+            &[(tool_dir.clone(), "claude".to_string())],
+        );
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0, "review-id");
+        assert_eq!(records[0].1.mode, "symlink");
+        assert_eq!(
+            recovered[0].target_path,
+            tool_dir.join("review").to_string_lossy()
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Commands for the default skill sets offered to new tools
+//! (`default_sets.rs`)
+
+use std::collections::HashMap;
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::commands::{
+    refresh_central_skill_hash_if_needed, resolve_skill_source_path, sync_skill_to_tool_record,
+};
+use super::default_sets::{self, DefaultSetApplyResultDto, DefaultSetOfferDto, DefaultSkillSets};
+use super::skill_store;
+use super::tool_adapters::{get_all_tool_adapters, is_tool_installed_with_state_async};
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+#[tauri::command]
+pub async fn skills_get_default_skill_sets(
+    state: State<'_, SqliteDbState>,
+) -> Result<DefaultSkillSets, String> {
+    Ok(default_sets::load_default_skill_sets(&state))
+}
+
+#[tauri::command]
+pub async fn skills_save_default_skill_sets(
+    state: State<'_, SqliteDbState>,
+    sets: DefaultSkillSets,
+) -> Result<(), String> {
+    ensure_writable("skills_save_default_skill_sets")?;
+    default_sets::save_default_skill_sets(&state, &sets)
+}
+
+/// Built-in tools installed for the first time since the last check, each
+/// with the default-set skills it would receive. Nothing is synced here.
+#[tauri::command]
+pub async fn skills_check_new_tools(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<DefaultSetOfferDto>, String> {
+    // Keep new tools pending until they can actually be synced
+    if crate::settings::read_only::is_read_only() {
+        return Ok(Vec::new());
+    }
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let mut installed = Vec::new();
+    let mut display_names = HashMap::new();
+    for adapter in get_all_tool_adapters(&custom_tools) {
+        if adapter.is_custom {
+            continue;
+        }
+        if is_tool_installed_with_state_async(state.db(), &adapter)
+            .await
+            .unwrap_or(false)
+        {
+            installed.push(adapter.key.clone());
+            display_names.insert(adapter.key, adapter.display_name);
+        }
+    }
+
+    let new_tools = default_sets::take_new_tools(&state, &installed)?;
+    if new_tools.is_empty() {
+        return Ok(Vec::new());
+    }
+    let sets = default_sets::load_default_skill_sets(&state);
+    let skills = skill_store::get_managed_skills(&state).await?;
+    Ok(new_tools
+        .into_iter()
+        .filter_map(|tool| {
+            let skill_ids = default_sets::pending_skill_ids(&sets, &tool, &skills);
+            if skill_ids.is_empty() {
+                return None;
+            }
+            Some(DefaultSetOfferDto {
+                tool_display: display_names.remove(&tool).unwrap_or_else(|| tool.clone()),
+                tool,
+                skill_ids,
+            })
+        })
+        .collect())
+}
+
+/// Sync the default-set skills of `tool` that are not there yet
+#[tauri::command]
+pub async fn skills_apply_default_skill_set<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<DefaultSetApplyResultDto, String> {
+    ensure_writable("skills_apply_default_skill_set")?;
+    let sets = default_sets::load_default_skill_sets(&state);
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+
+    let mut result = DefaultSetApplyResultDto {
+        tool: tool.clone(),
+        ..Default::default()
+    };
+    for skill_id in default_sets::pending_skill_ids(&sets, &tool, &skills) {
+        let Some(mut skill) = skills.iter().find(|skill| skill.id == skill_id).cloned() else {
+            continue;
+        };
+        let synced = async {
+            let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+            refresh_central_skill_hash_if_needed(&state, &mut skill, &source_path).await?;
+            sync_skill_to_tool_record(&state, &skill, &tool, &source_path, false, &custom_tools)
+                .await
+        }
+        .await;
+        match synced {
+            Ok(_) => result.synced.push(skill.id.clone()),
+            Err(err) => result.errors.push(format!("{}: {}", skill.name, err)),
+        }
+    }
+
+    log::info!(
+        "Applied default skill set to '{}': {} synced, {} failed",
+        tool,
+        result.synced.len(),
+        result.errors.len()
+    );
+    if !result.synced.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(result)
+}
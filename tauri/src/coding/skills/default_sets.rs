@@ -0,0 +1,171 @@
+//! Default skill sets for newly detected tools
+//!
+//! Installing a new tool (say Windsurf) leaves it without skills until the
+//! user syncs them by hand. A default set names the skills a new tool should
+//! get: `global` applies to every tool, a `per_tool` entry replaces it for
+//! that tool (an empty entry opts the tool out). Nothing is synced on
+//! detection: `skills_check_new_tools` returns one offer per new tool, the
+//! frontend asks for confirmation, and `skills_apply_default_skill_set` does
+//! the sync.
+//!
+//! Tools seen so far are kept in `skill_settings:skills.default_set_known_tools`,
+//! separate from `installed_tools_v1` so the tool status refresh cannot eat a
+//! detection. The first check only records the installed tools.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::archive::ARCHIVED_STATUS;
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::types::{now_ms, Skill};
+use crate::db::SqliteDbState;
+
+const DEFAULT_SETS_SETTINGS_KEY: &str = "default_skill_sets";
+const KNOWN_TOOLS_SETTINGS_KEY: &str = "default_set_known_tools";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DefaultSkillSets {
+    /// Skill ids offered to a new tool without its own entry
+    #[serde(default)]
+    pub global: Vec<String>,
+    /// Tool key -> skill ids, replacing `global` for that tool
+    #[serde(default)]
+    pub per_tool: BTreeMap<String, Vec<String>>,
+}
+
+impl DefaultSkillSets {
+    pub fn skills_for_tool(&self, tool: &str) -> &[String] {
+        self.per_tool.get(tool).unwrap_or(&self.global)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DefaultSetOfferDto {
+    pub tool: String,
+    pub tool_display: String,
+    /// Skills of the set still to sync (see `pending_skill_ids`)
+    pub skill_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DefaultSetApplyResultDto {
+    pub tool: String,
+    pub synced: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+pub fn load_default_skill_sets(state: &SqliteDbState) -> DefaultSkillSets {
+    read_skill_settings_value_from_sqlite(state, DEFAULT_SETS_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_default_skill_sets(
+    state: &SqliteDbState,
+    sets: &DefaultSkillSets,
+) -> Result<(), String> {
+    let value = serde_json::to_value(sets)
+        .map_err(|e| format!("Failed to serialize default skill sets: {}", e))?;
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            DEFAULT_SETS_SETTINGS_KEY: value,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Record `installed` as seen and return the tools not seen before. The
+/// first call returns nothing, so existing installs are not offered.
+/// Uninstalled tools stay known: reinstalling one is not a first detection.
+pub fn take_new_tools(state: &SqliteDbState, installed: &[String]) -> Result<Vec<String>, String> {
+    let known: Option<BTreeSet<String>> =
+        read_skill_settings_value_from_sqlite(state, KNOWN_TOOLS_SETTINGS_KEY)
+            .and_then(|value| serde_json::from_value(value).ok());
+    let new_tools = match &known {
+        Some(known) => installed
+            .iter()
+            .filter(|tool| !known.contains(*tool))
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    };
+    if known.is_some() && new_tools.is_empty() {
+        return Ok(new_tools);
+    }
+
+    let mut known = known.unwrap_or_default();
+    known.extend(installed.iter().cloned());
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            KNOWN_TOOLS_SETTINGS_KEY: known,
+            "updated_at": now_ms(),
+        }),
+    )?;
+    Ok(new_tools)
+}
+
+/// Skills of the set for `tool` that are enabled, not archived and not yet
+/// synced there
+pub fn pending_skill_ids(sets: &DefaultSkillSets, tool: &str, skills: &[Skill]) -> Vec<String> {
+    sets.skills_for_tool(tool)
+        .iter()
+        .filter(|id| {
+            skills.iter().any(|skill| {
+                skill.id == **id
+                    && skill.management_enabled
+                    && skill.status != ARCHIVED_STATUS
+                    && !skill.enabled_tools.iter().any(|enabled| enabled == tool)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use serde_json::json;
+
+    #[test]
+    fn first_check_only_records_installed_tools() {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        let installed = vec!["claude_code".to_string(), "codex".to_string()];
+
+        assert!(take_new_tools(&state, &installed).unwrap().is_empty());
+        assert!(take_new_tools(&state, &installed).unwrap().is_empty());
+
+        let with_windsurf = vec!["claude_code".to_string(), "windsurf".to_string()];
+        assert_eq!(
+            take_new_tools(&state, &with_windsurf).unwrap(),
+            vec!["windsurf"]
+        );
+        assert!(take_new_tools(&state, &installed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn per_tool_set_replaces_global_and_skips_synced_skills() {
+        let sets = DefaultSkillSets {
+            global: ["review", "lint", "off", "cold"].map(String::from).to_vec(),
+            per_tool: BTreeMap::from([("cursor".to_string(), Vec::new())]),
+        };
+        let skills = vec![
+            from_db_skill(
+                json!({ "id": "review", "name": "review", "enabled_tools": ["windsurf"] }),
+            ),
+            from_db_skill(json!({ "id": "lint", "name": "lint" })),
+            from_db_skill(json!({ "id": "off", "name": "off", "management_enabled": false })),
+            from_db_skill(json!({ "id": "cold", "name": "cold", "status": ARCHIVED_STATUS })),
+        ];
+
+        assert_eq!(pending_skill_ids(&sets, "windsurf", &skills), vec!["lint"]);
+        assert_eq!(
+            pending_skill_ids(&sets, "codex", &skills),
+            vec!["review", "lint"]
+        );
+        assert!(pending_skill_ids(&sets, "cursor", &skills).is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+//! Duplicate content report across tools
+//!
+//! Consolidation (`consolidate.rs`) only pairs copies that share a name and sit
+//! where a sync would place them. Users who adopted selectively still end up
+//! with the same content under other names, in extra stores, or as copy-mode
+//! targets of a managed skill next to an unmanaged duplicate. This report
+//! hashes every unmanaged skill and every copy-mode target and clusters them
+//! by content. A cluster made only of one managed skill's own targets is
+//! already consolidated and left out.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::adapter::parse_sync_details;
+use super::consolidate::dir_size;
+use super::content_hash::hash_dir;
+use super::types::{now_ms, OnboardingPlan, Skill};
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DuplicateCopyDto {
+    /// Tool or source key
+    pub tool: String,
+    pub tool_display: String,
+    pub name: String,
+    pub path: String,
+    /// Managed skill owning this copy-mode target; `None` for an unmanaged skill
+    pub skill_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DuplicateClusterDto {
+    pub fingerprint: String,
+    pub copies: Vec<DuplicateCopyDto>,
+    pub bytes_per_copy: u64,
+    /// Bytes freed if a single copy remained
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DuplicateContentReportDto {
+    /// Sorted by `reclaimable_bytes`, largest first
+    pub clusters: Vec<DuplicateClusterDto>,
+    /// Directories hashed (unmanaged skills and copy-mode targets)
+    pub scanned: usize,
+    pub reclaimable_bytes: u64,
+    pub generated_at: i64,
+}
+
+/// Cluster the unmanaged skills of `plan` and the copy-mode targets of
+/// `skills` by content. `tool_names` maps tool keys to display names.
+pub fn find_duplicate_content(
+    plan: &OnboardingPlan,
+    skills: &[Skill],
+    tool_names: &HashMap<String, String>,
+) -> DuplicateContentReportDto {
+    let mut by_fingerprint: BTreeMap<String, Vec<DuplicateCopyDto>> = BTreeMap::new();
+    let mut scanned = 0;
+
+    // Links share their target's content without storing it twice
+    for variant in plan.groups.iter().flat_map(|group| &group.variants) {
+        if variant.is_link {
+            continue;
+        }
+        scanned += 1;
+        let Some(fingerprint) = variant.fingerprint.clone() else {
+            continue;
+        };
+        by_fingerprint
+            .entry(fingerprint)
+            .or_default()
+            .push(DuplicateCopyDto {
+                tool: variant.tool.clone(),
+                tool_display: variant.tool_display.clone(),
+                name: variant.name.clone(),
+                path: variant.path.clone(),
+                skill_id: None,
+            });
+    }
+
+    for skill in skills {
+        for target in parse_sync_details(skill) {
+            if target.mode != "copy" {
+                continue;
+            }
+            scanned += 1;
+            let Ok(fingerprint) = hash_dir(Path::new(&target.target_path)) else {
+                continue;
+            };
+            by_fingerprint
+                .entry(fingerprint)
+                .or_default()
+                .push(DuplicateCopyDto {
+                    tool_display: tool_names
+                        .get(&target.tool)
+                        .cloned()
+                        .unwrap_or_else(|| target.tool.clone()),
+                    tool: target.tool,
+                    name: skill.name.clone(),
+                    path: target.target_path,
+                    skill_id: Some(skill.id.clone()),
+                });
+        }
+    }
+
+    let mut clusters = by_fingerprint
+        .into_iter()
+        .filter(|(_, copies)| copies.len() >= 2 && !is_consolidated(copies))
+        .map(|(fingerprint, copies)| {
+            let bytes_per_copy = dir_size(Path::new(&copies[0].path));
+            DuplicateClusterDto {
+                fingerprint,
+                bytes_per_copy,
+                reclaimable_bytes: bytes_per_copy * (copies.len() as u64 - 1),
+                copies,
+            }
+        })
+        .collect::<Vec<_>>();
+    clusters.sort_by_key(|cluster| Reverse(cluster.reclaimable_bytes));
+
+    DuplicateContentReportDto {
+        reclaimable_bytes: clusters.iter().map(|c| c.reclaimable_bytes).sum(),
+        clusters,
+        scanned,
+        generated_at: now_ms(),
+    }
+}
+
+/// Every copy is a target of the same managed skill
+fn is_consolidated(copies: &[DuplicateCopyDto]) -> bool {
+    let owner = copies[0].skill_id.as_deref();
+    owner.is_some() && copies.iter().all(|copy| copy.skill_id.as_deref() == owner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use crate::coding::skills::types::{OnboardingGroup, OnboardingVariant};
+    use serde_json::json;
+
+    fn write_skill(dir: &Path, body: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), body).unwrap();
+    }
+
+    fn variant(tool: &str, name: &str, path: &Path, is_link: bool) -> OnboardingVariant {
+        OnboardingVariant {
+            tool: tool.to_string(),
+            tool_display: tool.to_string(),
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            fingerprint: hash_dir(path).ok(),
+            is_link,
+            link_target: None,
+            linked_folder: None,
+            conflicting_tools: Vec::new(),
+        }
+    }
+
+    fn group(name: &str, variants: Vec<OnboardingVariant>) -> OnboardingGroup {
+        OnboardingGroup {
+            name: name.to_string(),
+            variants,
+            has_conflict: false,
+        }
+    }
+
+    #[test]
+    fn clusters_identical_content_across_names_and_copy_targets() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        write_skill(&root.join("claude/review"), "review");
+        write_skill(&root.join("cursor/code-review"), "review");
+        write_skill(&root.join("codex/lint"), "lint");
+        write_skill(&root.join("codex/managed-lint"), "lint");
+        write_skill(&root.join("gemini/managed-lint"), "lint");
+        write_skill(&root.join("gemini/solo"), "solo");
+        write_skill(&root.join("qwen/solo"), "solo");
+
+        let plan = OnboardingPlan {
+            total_tools_scanned: 3,
+            total_skills_found: 4,
+            groups: vec![
+                group(
+                    "review",
+                    vec![variant(
+                        "claude_code",
+                        "review",
+                        &root.join("claude/review"),
+                        false,
+                    )],
+                ),
+                group(
+                    "code-review",
+                    vec![
+                        variant(
+                            "cursor",
+                            "code-review",
+                            &root.join("cursor/code-review"),
+                            false,
+                        ),
+                        variant("codex", "code-review", &root.join("claude/review"), true),
+                    ],
+                ),
+                group(
+                    "lint",
+                    vec![variant("codex", "lint", &root.join("codex/lint"), false)],
+                ),
+            ],
+            previous_scan_at: None,
+            new_since_last_scan: Vec::new(),
+            rehashed_dirs: 0,
+            system_skills: Vec::new(),
+        };
+        let skills = vec![
+            from_db_skill(json!({
+                "id": "managed-lint",
+                "name": "managed-lint",
+                "sync_details": {
+                    "codex": { "target_path": root.join("codex/managed-lint"), "mode": "copy" },
+                    "gemini_cli": { "target_path": root.join("gemini/managed-lint"), "mode": "copy" },
+                },
+            })),
+            from_db_skill(json!({
+                "id": "solo",
+                "name": "solo",
+                "sync_details": {
+                    "gemini_cli": { "target_path": root.join("gemini/solo"), "mode": "copy" },
+                    "qwen_code": { "target_path": root.join("qwen/solo"), "mode": "copy" },
+                    "claude_code": { "target_path": root.join("claude/solo"), "mode": "symlink" },
+                },
+            })),
+        ];
+        let tool_names = HashMap::from([("gemini_cli".to_string(), "Gemini CLI".to_string())]);
+
+        let report = find_duplicate_content(&plan, &skills, &tool_names);
+
+        assert_eq!(report.scanned, 7);
+        let mut clusters = report
+            .clusters
+            .iter()
+            .map(|cluster| {
+                let mut names = cluster
+                    .copies
+                    .iter()
+                    .map(|copy| format!("{}:{}", copy.tool_display, copy.name))
+                    .collect::<Vec<_>>();
+                names.sort();
+                names
+            })
+            .collect::<Vec<_>>();
+        clusters.sort();
+        assert_eq!(
+            clusters,
+            vec![
+                vec![
+                    "Gemini CLI:managed-lint",
+                    "codex:lint",
+                    "codex:managed-lint"
+                ],
+                vec!["claude_code:review", "cursor:code-review"],
+            ]
+        );
+        assert_eq!(report.reclaimable_bytes, 4 * 2 + 6);
+    }
+}
@@ -0,0 +1,206 @@
+//! Commands for edits made outside the app: in the central repo
+//! (`external_changes.rs`) and in copy targets (`target_pull.rs`)
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::commands::{format_error, resolve_skill_source_path, sync_skill_to_tool_records};
+use super::content_hash::hash_dir;
+use super::external_changes::{self, ExternalChangeDto};
+use super::skill_store;
+use super::target_pull::{self, TargetPullPreviewDto, TargetPullResultDto};
+use super::types::{Skill, SkillTarget, SyncMode};
+use super::variants;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Skills whose central directory was changed outside the app, with the
+/// files changed since the app's last write
+#[tauri::command]
+pub async fn skills_scan_external_changes(
+    app: AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<ExternalChangeDto>, String> {
+    external_changes::scan_external_changes(&app, &state).await
+}
+
+/// Keep an external change: record the current content as the app's own
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_accept_external_change(
+    app: AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<(), String> {
+    ensure_writable("skills_accept_external_change")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    let hash = hash_dir(&source_path).map_err(format_error)?;
+    skill_store::update_skill_content_hash(&state, &skill.id, Some(hash.clone())).await?;
+    let baseline = external_changes::baselines_root(&app)?.join(&skill.id);
+    if let Err(err) = external_changes::save_baseline(&baseline, &source_path, &hash) {
+        log::warn!("Failed to snapshot '{}': {:#}", skill.name, err);
+    }
+    let _ = app.emit("skills-changed", "window");
+    Ok(())
+}
+
+/// Undo an external change: put back the content the app last wrote. Copy
+/// targets follow at their next sync.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_revert_external_change(
+    app: AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<(), String> {
+    ensure_writable("skills_revert_external_change")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    let root = external_changes::baselines_root(&app)?;
+    tokio::task::spawn_blocking(move || {
+        external_changes::revert_to_baseline(&root, &skill, &source_path)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?
+    .map_err(format_error)?;
+    let _ = app.emit("skills-changed", "window");
+    Ok(())
+}
+
+/// Recorded copy target of a skill in `tool`, with the central directory it
+/// is pulled into; see `target_pull`
+async fn pull_target<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    skill_id: &str,
+    tool: &str,
+) -> Result<(Skill, SkillTarget, PathBuf), String> {
+    let skill = skill_store::get_skill_by_id(state, skill_id)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    let target = skill_store::get_skill_targets(state, &skill.id)
+        .await?
+        .into_iter()
+        .find(|target| target.tool == tool)
+        .ok_or_else(|| format!("Skill '{}' is not synced to {}", skill.name, tool))?;
+    if target.mode != SyncMode::Copy.as_str() {
+        return Err(format!("PULL_TARGET_NOT_COPY|{}|{}", tool, target.mode));
+    }
+    // A variant target holds the variant, not the central content
+    if variants::resolve_variant_source(state, &skill.id, tool)?.is_some() {
+        return Err(format!("PULL_TARGET_VARIANT|{}", tool));
+    }
+    if !Path::new(&target.target_path).is_dir() {
+        return Err(format!("Target not found: {}", target.target_path));
+    }
+    let source_path = resolve_skill_source_path(app, state, &skill).await?;
+    Ok((skill, target, source_path))
+}
+
+/// Files a copy target was edited in, compared with the central repo
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_preview_pull_from_target(
+    app: AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+) -> Result<TargetPullPreviewDto, String> {
+    let (skill, target, source_path) = pull_target(&app, &state, &skillId, &tool).await?;
+    let target_path = PathBuf::from(&target.target_path);
+    let rules = target.path_rules.clone();
+    let files = tokio::task::spawn_blocking(move || {
+        target_pull::diff_target(&source_path, &target_path, &rules)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?
+    .map_err(format_error)?;
+    Ok(TargetPullPreviewDto {
+        skill_id: skill.id,
+        name: skill.name,
+        tool,
+        target_path: target.target_path,
+        files,
+    })
+}
+
+/// Copy edits made in a copy target back into the central repo, then re-sync
+/// the skill's other targets. `paths` limits the pull to those files; `None`
+/// pulls every file that differs.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_pull_from_target(
+    app: AppHandle,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+    paths: Option<Vec<String>>,
+) -> Result<TargetPullResultDto, String> {
+    ensure_writable("skills_pull_from_target")?;
+    let (mut skill, target, source_path) = pull_target(&app, &state, &skillId, &tool).await?;
+    let pulled = {
+        let skill = skill.clone();
+        let source_path = source_path.clone();
+        let target_path = PathBuf::from(&target.target_path);
+        tokio::task::spawn_blocking(move || {
+            target_pull::pull_into_central(
+                &skill,
+                &source_path,
+                &target_path,
+                &target.path_rules,
+                paths.as_deref(),
+            )
+        })
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e))?
+        .map_err(format_error)?
+    };
+    let mut result = TargetPullResultDto {
+        pulled,
+        ..Default::default()
+    };
+    if result.pulled.is_empty() {
+        return Ok(result);
+    }
+
+    let hash = hash_dir(&source_path).map_err(format_error)?;
+    skill_store::update_skill_content_hash(&state, &skill.id, Some(hash.clone())).await?;
+    skill.content_hash = Some(hash.clone());
+    let baseline = external_changes::baselines_root(&app)?.join(&skill.id);
+    if let Err(err) = external_changes::save_baseline(&baseline, &source_path, &hash) {
+        log::warn!("Failed to snapshot '{}': {:#}", skill.name, err);
+    }
+    result.content_hash = Some(hash);
+
+    // Links show the new content already; copies and hard links are re-synced
+    let mut tools = Vec::new();
+    for other in skill_store::get_skill_targets(&state, &skill.id).await? {
+        if other.tool == tool
+            || !skill.enabled_tools.contains(&other.tool)
+            || matches!(other.mode.as_str(), "symlink" | "junction")
+            || variants::resolve_variant_source(&state, &skill.id, &other.tool)?.is_some()
+        {
+            continue;
+        }
+        tools.push(other.tool);
+    }
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let results =
+        sync_skill_to_tool_records(&state, &skill, &tools, &source_path, true, &custom_tools).await;
+    for (other, synced) in results {
+        match synced {
+            Ok(_) => result.resynced.push(other),
+            Err(err) => result.errors.push(format!("{}: {}", other, err)),
+        }
+    }
+    let _ = app.emit("skills-changed", "window");
+    Ok(result)
+}
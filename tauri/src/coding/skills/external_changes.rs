@@ -0,0 +1,290 @@
+//! Detection of central repo edits made outside the app
+//!
+//! Every app operation that writes a skill's central directory (install,
+//! update, migration, accepting a change) records its `content_hash`, so the
+//! recorded hash is the log of what the app itself wrote. A directory that
+//! no longer hashes to it was rewritten by something else: an agent editing
+//! its own skill through a symlink, a script, an editor.
+//!
+//! While a skill matches its recorded hash a snapshot of it is kept in
+//! `{app_data_dir}/skill-baselines/{skill_id}/`; a changed skill is diffed
+//! against that snapshot and can be reverted to it. The watcher scans every
+//! `SCAN_INTERVAL` and emits `EXTERNAL_CHANGE_EVENT` for changes it has not
+//! reported yet. Linked folders belong to the user and are not watched.
+//! Skills with a `central` source take their current hash at the next sync
+//! (`refresh_central_skill_hash_if_needed`), which accepts the edit.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use log::{info, warn};
+use serde::Serialize;
+use tauri::{Emitter, Manager, Runtime};
+
+use super::archive::ARCHIVED_STATUS;
+use super::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
+use super::content_hash::hash_dir;
+use super::skill_store;
+use super::sync_engine::copy_dir_recursive;
+use super::types::Skill;
+use super::update_preview::{diff_against_upstream, SkillUpdateFileDto};
+use super::write_protect;
+use crate::db::SqliteDbState;
+
+pub const EXTERNAL_CHANGE_EVENT: &str = "skills-externally-modified";
+
+const BASELINES_DIR_NAME: &str = "skill-baselines";
+const CONTENT_DIR: &str = "content";
+const HASH_FILE: &str = "hash";
+const SCAN_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExternalChangeDto {
+    pub skill_id: String,
+    pub name: String,
+    pub central_path: String,
+    /// Hash the app recorded at its last write
+    pub recorded_hash: String,
+    pub current_hash: String,
+    /// A snapshot of the recorded content exists to diff and revert to
+    pub has_baseline: bool,
+    /// Files changed since the recorded content; empty without a baseline
+    pub files: Vec<SkillUpdateFileDto>,
+}
+
+pub fn baselines_root<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(BASELINES_DIR_NAME))
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+}
+
+/// Managed skills whose central directory differs from the recorded hash
+pub async fn scan_external_changes<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    state: &SqliteDbState,
+) -> Result<Vec<ExternalChangeDto>, String> {
+    let root = baselines_root(app)?;
+    let central_dir = resolve_central_repo_path(app, state)
+        .await
+        .map_err(|e| format!("{:#}", e))?;
+    let skills = skill_store::get_managed_skills(state).await?;
+    tauri::async_runtime::spawn_blocking(move || scan(&root, &central_dir, &skills))
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e))
+}
+
+fn is_watched(skill: &Skill) -> bool {
+    skill.status != ARCHIVED_STATUS && skill.source_type != "linked_folder"
+}
+
+fn scan(root: &Path, central_dir: &Path, skills: &[Skill]) -> Vec<ExternalChangeDto> {
+    let mut changes = Vec::new();
+    for skill in skills.iter().filter(|skill| is_watched(skill)) {
+        let central_path = resolve_skill_central_path(&skill.central_path, central_dir);
+        if !central_path.is_dir() {
+            continue;
+        }
+        match check_skill(root, skill, &central_path) {
+            Ok(Some(change)) => changes.push(change),
+            Ok(None) => {}
+            Err(err) => warn!(
+                "External change check of '{}' failed: {:#}",
+                skill.name, err
+            ),
+        }
+    }
+    prune_baselines(root, skills);
+    changes
+}
+
+/// Compare one skill with its recorded hash, refreshing the baseline while
+/// they match
+fn check_skill(
+    root: &Path,
+    skill: &Skill,
+    central_path: &Path,
+) -> Result<Option<ExternalChangeDto>> {
+    let Some(recorded) = skill.content_hash.as_deref() else {
+        return Ok(None);
+    };
+    let current = hash_dir(central_path)?;
+    let baseline = root.join(&skill.id);
+    let has_baseline = baseline_hash(&baseline).as_deref() == Some(recorded);
+    if current == recorded {
+        if !has_baseline {
+            save_baseline(&baseline, central_path, recorded)?;
+        }
+        return Ok(None);
+    }
+
+    let files = if has_baseline {
+        diff_against_upstream(&baseline.join(CONTENT_DIR), central_path, None)?
+    } else {
+        Vec::new()
+    };
+    Ok(Some(ExternalChangeDto {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        central_path: central_path.to_string_lossy().to_string(),
+        recorded_hash: recorded.to_string(),
+        current_hash: current,
+        has_baseline,
+        files,
+    }))
+}
+
+fn baseline_hash(baseline: &Path) -> Option<String> {
+    std::fs::read_to_string(baseline.join(HASH_FILE))
+        .ok()
+        .map(|hash| hash.trim().to_string())
+}
+
+/// Snapshot `central_path` as the content that hashes to `hash`. The hash
+/// file is written last, so an interrupted copy is never trusted.
+pub fn save_baseline(baseline: &Path, central_path: &Path, hash: &str) -> Result<()> {
+    if baseline.exists() {
+        std::fs::remove_dir_all(baseline).with_context(|| format!("remove {:?}", baseline))?;
+    }
+    let content = baseline.join(CONTENT_DIR);
+    std::fs::create_dir_all(&content).with_context(|| format!("create {:?}", content))?;
+    copy_dir_recursive(central_path, &content)?;
+    std::fs::write(baseline.join(HASH_FILE), hash)
+        .with_context(|| format!("write {:?}", baseline.join(HASH_FILE)))?;
+    Ok(())
+}
+
+/// Put the recorded content back into the central directory
+pub fn revert_to_baseline(root: &Path, skill: &Skill, central_path: &Path) -> Result<()> {
+    let baseline = root.join(&skill.id);
+    if skill.content_hash.is_none() || baseline_hash(&baseline) != skill.content_hash {
+        anyhow::bail!("NO_BASELINE|{}", skill.name);
+    }
+    let parent = central_path
+        .parent()
+        .with_context(|| format!("invalid central path {:?}", central_path))?;
+    let staging = parent.join(format!(".skills-revert-{}", skill.id));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).with_context(|| format!("remove {:?}", staging))?;
+    }
+    copy_dir_recursive(&baseline.join(CONTENT_DIR), &staging)?;
+
+    write_protect::unlock(central_path, skill.write_protected)?;
+    if let Err(err) = std::fs::remove_dir_all(central_path) {
+        let _ = std::fs::remove_dir_all(&staging);
+        write_protect::protect_or_warn(central_path, skill.write_protected);
+        return Err(anyhow::Error::new(err).context(format!("remove {:?}", central_path)));
+    }
+    std::fs::rename(&staging, central_path)
+        .with_context(|| format!("move {:?} -> {:?}", staging, central_path))?;
+    write_protect::protect_or_warn(central_path, skill.write_protected);
+    Ok(())
+}
+
+/// Drop the baselines of skills that are gone or no longer watched
+fn prune_baselines(root: &Path, skills: &[Skill]) {
+    let watched: HashSet<&str> = skills
+        .iter()
+        .filter(|skill| is_watched(skill))
+        .map(|skill| skill.id.as_str())
+        .collect();
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if !watched.contains(name.to_string_lossy().as_ref()) {
+            let _ = std::fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Start the periodic scan as a background task
+pub fn start_external_change_watcher(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Initial delay: let startup syncs record their hashes first
+        tokio::time::sleep(Duration::from_secs(90)).await;
+
+        // Skill id -> hash already reported, so each change alerts once
+        let mut reported: HashMap<String, String> = HashMap::new();
+        loop {
+            let state = app_handle.state::<SqliteDbState>();
+            match scan_external_changes(&app_handle, &state).await {
+                Ok(changes) => {
+                    let fresh = unreported(&mut reported, changes);
+                    if !fresh.is_empty() {
+                        info!("{} skill(s) modified outside the app", fresh.len());
+                        let _ = app_handle.emit(EXTERNAL_CHANGE_EVENT, &fresh);
+                    }
+                }
+                Err(e) => warn!("External change scan failed: {}", e),
+            }
+            tokio::time::sleep(SCAN_INTERVAL).await;
+        }
+    });
+}
+
+/// Changes not reported with the same hash before; forgets skills that are
+/// no longer changed
+fn unreported(
+    reported: &mut HashMap<String, String>,
+    changes: Vec<ExternalChangeDto>,
+) -> Vec<ExternalChangeDto> {
+    reported.retain(|id, _| changes.iter().any(|change| &change.skill_id == id));
+    changes
+        .into_iter()
+        .filter(|change| {
+            reported.insert(change.skill_id.clone(), change.current_hash.clone())
+                != Some(change.current_hash.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use serde_json::json;
+
+    #[test]
+    fn flags_edits_against_the_recorded_hash_and_reverts_them() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("baselines");
+        let central_dir = temp.path().join("central");
+        let central_path = central_dir.join("review");
+        std::fs::create_dir_all(&central_path).unwrap();
+        std::fs::write(central_path.join("SKILL.md"), "# Review\n").unwrap();
+        let skill = from_db_skill(json!({
+            "id": "skill-1",
+            "name": "review",
+            "source_type": "git",
+            "central_path": "review",
+            "content_hash": hash_dir(&central_path).unwrap(),
+        }));
+
+        // Unchanged: nothing reported, the baseline is taken
+        assert!(scan(&root, &central_dir, std::slice::from_ref(&skill)).is_empty());
+
+        std::fs::write(central_path.join("SKILL.md"), "# Review\nAlso push.\n").unwrap();
+        std::fs::write(central_path.join("notes.md"), "agent notes").unwrap();
+        let changes = scan(&root, &central_dir, std::slice::from_ref(&skill));
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].has_baseline);
+        let files: Vec<(&str, &str)> = changes[0]
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.status.as_str()))
+            .collect();
+        assert_eq!(files, vec![("SKILL.md", "modified"), ("notes.md", "added")]);
+
+        let mut reported = HashMap::new();
+        assert_eq!(unreported(&mut reported, changes.clone()).len(), 1);
+        assert!(unreported(&mut reported, changes).is_empty());
+
+        revert_to_baseline(&root, &skill, &central_path).unwrap();
+        assert!(scan(&root, &central_dir, std::slice::from_ref(&skill)).is_empty());
+        assert!(!central_path.join("notes.md").exists());
+    }
+}
@@ -0,0 +1,85 @@
+//! Commands for skills stored in a single config file (`file_skills.rs`)
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Runtime, State};
+
+use super::commands::{format_error, resolve_skill_source_path};
+use super::file_skills::{self, FileSkillEntry, FileSkillSource, FileSkillToolDto};
+use super::skill_store;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+#[tauri::command]
+pub async fn skills_list_file_skill_tools() -> Result<Vec<FileSkillToolDto>, String> {
+    Ok(file_skills::list_file_skill_tools())
+}
+
+/// Read a managed skill's SKILL.md as a file-skill entry, plus the config path of `tool`.
+async fn file_skill_entry_for<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    skill_id: &str,
+    tool: &str,
+) -> Result<(&'static FileSkillSource, PathBuf, FileSkillEntry), String> {
+    let source = file_skills::file_skill_source(tool)
+        .ok_or_else(|| format!("Unknown file-based skill tool: {}", tool))?;
+    let config_path = file_skills::resolve_config_path(source)
+        .ok_or_else(|| format!("Failed to resolve config path: {}", source.config_path))?;
+    let skill = skill_store::get_skill_by_id(state, skill_id)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    let skill_dir = resolve_skill_source_path(app, state, &skill).await?;
+    let content = std::fs::read_to_string(skill_dir.join("SKILL.md"))
+        .map_err(|e| format!("Failed to read SKILL.md of {}: {}", skill.name, e))?;
+    let entry = file_skills::skill_md_to_entry(&skill.name, &content);
+    Ok((source, config_path, entry))
+}
+
+/// Upsert a managed skill into a tool's config file (matched by name).
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_write_file_skill<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+) -> Result<(), String> {
+    ensure_writable("skills_write_file_skill")?;
+    let (source, config_path, entry) = file_skill_entry_for(&app, &state, &skillId, &tool).await?;
+    let content = std::fs::read_to_string(&config_path).unwrap_or_default();
+    let updated = source
+        .format
+        .inject(&content, &entry)
+        .map_err(format_error)?;
+    file_skills::write_config_atomic(&config_path, &updated).map_err(format_error)
+}
+
+/// Remove a managed skill's entry from a tool's config file; false when absent.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_remove_file_skill<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+) -> Result<bool, String> {
+    ensure_writable("skills_remove_file_skill")?;
+    let (source, config_path, entry) = file_skill_entry_for(&app, &state, &skillId, &tool).await?;
+    if !config_path.is_file() {
+        return Ok(false);
+    }
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path.display(), e))?;
+    match source
+        .format
+        .remove(&content, &entry.name)
+        .map_err(format_error)?
+    {
+        Some(updated) => {
+            file_skills::write_config_atomic(&config_path, &updated).map_err(format_error)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,418 @@
+//! File-based skills: tools that keep prompt / "skill" definitions in one
+//! JSON or YAML config file instead of a directory per skill.
+//!
+//! Each source in `FILE_SKILL_SOURCES` names the config file and a
+//! `FileSkillFormat` describing where the entries live. Onboarding extracts
+//! the entries and materializes each one as a virtual skill directory
+//! (`SKILL.md` only) under `staging_root()`, so the regular import flow can
+//! adopt them unchanged. Writing goes the other way: a managed skill's
+//! SKILL.md is turned back into an entry and upserted into the file by name.
+//! To support a new tool, append an entry to `FILE_SKILL_SOURCES`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::types::DetectedSkill;
+use crate::coding::tools::path_utils::resolve_storage_path;
+
+/// Where entries live inside the config file and which keys they use
+#[derive(Clone, Copy, Debug)]
+pub enum FileSkillFormat {
+    /// Array of objects at `path` in a JSON document
+    JsonList(ListLayout),
+    /// Array of objects at `path` in a YAML document (comments are not kept on write)
+    YamlList(ListLayout),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ListLayout {
+    pub path: &'static [&'static str],
+    pub name_key: &'static str,
+    pub description_key: &'static str,
+    pub body_key: &'static str,
+}
+
+pub struct FileSkillSource {
+    pub key: &'static str,
+    pub display_name: &'static str,
+    /// Config file path (supports ~/, %APPDATA%/ and env vars)
+    pub config_path: &'static str,
+    pub format: FileSkillFormat,
+}
+
+const CONTINUE_PROMPTS: ListLayout = ListLayout {
+    path: &["prompts"],
+    name_key: "name",
+    description_key: "description",
+    body_key: "prompt",
+};
+
+const CONTINUE_CUSTOM_COMMANDS: ListLayout = ListLayout {
+    path: &["customCommands"],
+    name_key: "name",
+    description_key: "description",
+    body_key: "prompt",
+};
+
+pub const FILE_SKILL_SOURCES: &[FileSkillSource] = &[
+    FileSkillSource {
+        key: "continue",
+        display_name: "Continue",
+        config_path: "~/.continue/config.yaml",
+        format: FileSkillFormat::YamlList(CONTINUE_PROMPTS),
+    },
+    FileSkillSource {
+        key: "continue_json",
+        display_name: "Continue (config.json)",
+        config_path: "~/.continue/config.json",
+        format: FileSkillFormat::JsonList(CONTINUE_CUSTOM_COMMANDS),
+    },
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileSkillEntry {
+    pub name: String,
+    pub description: Option<String>,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FileSkillToolDto {
+    pub key: String,
+    pub display_name: String,
+    pub config_path: String,
+    pub exists: bool,
+    pub skill_names: Vec<String>,
+    pub error: Option<String>,
+}
+
+pub fn file_skill_source(key: &str) -> Option<&'static FileSkillSource> {
+    FILE_SKILL_SOURCES.iter().find(|source| source.key == key)
+}
+
+pub fn resolve_config_path(source: &FileSkillSource) -> Option<PathBuf> {
+    resolve_storage_path(source.config_path)
+}
+
+/// Directory holding materialized virtual skills from the last scan
+pub fn staging_root() -> PathBuf {
+    std::env::temp_dir().join("ai-toolbox-file-skills")
+}
+
+impl FileSkillFormat {
+    fn layout(&self) -> &ListLayout {
+        match self {
+            FileSkillFormat::JsonList(layout) | FileSkillFormat::YamlList(layout) => layout,
+        }
+    }
+
+    fn parse(&self, content: &str) -> Result<Value> {
+        if content.trim().is_empty() {
+            return Ok(Value::Object(Map::new()));
+        }
+        match self {
+            // config.json files are often hand-edited with comments
+            FileSkillFormat::JsonList(_) => serde_json::from_str(content)
+                .or_else(|_| json5::from_str(content))
+                .context("invalid JSON config"),
+            FileSkillFormat::YamlList(_) => {
+                serde_yaml::from_str(content).context("invalid YAML config")
+            }
+        }
+    }
+
+    fn serialize(&self, root: &Value) -> Result<String> {
+        match self {
+            FileSkillFormat::JsonList(_) => Ok(serde_json::to_string_pretty(root)? + "\n"),
+            FileSkillFormat::YamlList(_) => Ok(serde_yaml::to_string(root)?),
+        }
+    }
+
+    pub fn extract(&self, content: &str) -> Result<Vec<FileSkillEntry>> {
+        let root = self.parse(content)?;
+        let layout = self.layout();
+        let mut node = &root;
+        for key in layout.path {
+            match node.get(key) {
+                Some(next) => node = next,
+                None => return Ok(Vec::new()),
+            }
+        }
+        let text =
+            |item: &Value, key: &str| item.get(key).and_then(Value::as_str).map(str::to_string);
+        Ok(node
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| {
+                Some(FileSkillEntry {
+                    name: text(item, layout.name_key)?,
+                    description: text(item, layout.description_key),
+                    body: text(item, layout.body_key)?,
+                })
+            })
+            .collect())
+    }
+
+    fn entries_mut<'a>(&self, root: &'a mut Value) -> Result<&'a mut Vec<Value>> {
+        let mut node = root;
+        for key in self.layout().path {
+            let object = node
+                .as_object_mut()
+                .ok_or_else(|| anyhow::anyhow!("config path {} is not an object", key))?;
+            node = object
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Array(Vec::new()));
+        }
+        node.as_array_mut()
+            .ok_or_else(|| anyhow::anyhow!("config entry list is not an array"))
+    }
+
+    /// Upsert `entry` by name, keeping unrelated fields of an existing item
+    pub fn inject(&self, content: &str, entry: &FileSkillEntry) -> Result<String> {
+        let mut root = self.parse(content)?;
+        let layout = *self.layout();
+        let items = self.entries_mut(&mut root)?;
+        let position = items.iter().position(|item| {
+            item.get(layout.name_key).and_then(Value::as_str) == Some(entry.name.as_str())
+        });
+        let mut item = match position {
+            Some(index) => items.remove(index),
+            None => Value::Object(Map::new()),
+        };
+        let object = item
+            .as_object_mut()
+            .ok_or_else(|| anyhow::anyhow!("config entry {} is not an object", entry.name))?;
+        object.insert(
+            layout.name_key.to_string(),
+            Value::String(entry.name.clone()),
+        );
+        match &entry.description {
+            Some(description) => {
+                object.insert(
+                    layout.description_key.to_string(),
+                    Value::String(description.clone()),
+                );
+            }
+            None => {
+                object.remove(layout.description_key);
+            }
+        }
+        object.insert(
+            layout.body_key.to_string(),
+            Value::String(entry.body.clone()),
+        );
+        items.insert(position.unwrap_or(items.len()), item);
+        self.serialize(&root)
+    }
+
+    /// Remove the entry named `name`; `None` when it was not present
+    pub fn remove(&self, content: &str, name: &str) -> Result<Option<String>> {
+        let mut root = self.parse(content)?;
+        let name_key = self.layout().name_key;
+        let items = self.entries_mut(&mut root)?;
+        let before = items.len();
+        items.retain(|item| item.get(name_key).and_then(Value::as_str) != Some(name));
+        if items.len() == before {
+            return Ok(None);
+        }
+        self.serialize(&root).map(Some)
+    }
+}
+
+/// Render an entry as SKILL.md
+pub fn entry_to_skill_md(entry: &FileSkillEntry) -> String {
+    let mut out = format!("---\nname: {}\n", entry.name);
+    if let Some(description) = &entry.description {
+        out.push_str(&format!(
+            "description: {}\n",
+            serde_json::to_string(description).unwrap_or_default()
+        ));
+    }
+    out.push_str("---\n\n");
+    out.push_str(entry.body.trim_end());
+    out.push('\n');
+    out
+}
+
+/// Parse a SKILL.md back into an entry (frontmatter name / description + body)
+pub fn skill_md_to_entry(fallback_name: &str, content: &str) -> FileSkillEntry {
+    let mut name = fallback_name.to_string();
+    let mut description = None;
+    let mut body = content;
+    if let Some(rest) = content.strip_prefix("---") {
+        if let Some(end) = rest.find("\n---") {
+            for line in rest[..end].lines() {
+                let Some((key, value)) = line.split_once(':') else {
+                    continue;
+                };
+                let value = value.trim();
+                let value = serde_json::from_str::<String>(value)
+                    .unwrap_or_else(|_| value.trim_matches('\'').to_string());
+                match key.trim() {
+                    "name" if !value.is_empty() => name = value,
+                    "description" if !value.is_empty() => description = Some(value),
+                    _ => {}
+                }
+            }
+            body = rest[end + 4..].trim_start_matches(['-', '\r', '\n']);
+        }
+    }
+    FileSkillEntry {
+        name,
+        description,
+        body: body.trim().to_string(),
+    }
+}
+
+fn sanitize_dir_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches(['-', '.']).to_string();
+    if cleaned.is_empty() {
+        "skill".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Extract a source's entries and materialize them as virtual skill dirs
+pub fn scan_file_source(
+    source: &FileSkillSource,
+    config_path: &Path,
+    staging: &Path,
+) -> Result<Vec<DetectedSkill>> {
+    let content =
+        std::fs::read_to_string(config_path).with_context(|| format!("read {:?}", config_path))?;
+    let entries = source.format.extract(&content)?;
+    let tool_dir = staging.join(source.key);
+    if tool_dir.exists() {
+        let _ = std::fs::remove_dir_all(&tool_dir);
+    }
+
+    let mut detected = Vec::new();
+    for entry in entries {
+        let dir = tool_dir.join(sanitize_dir_name(&entry.name));
+        std::fs::create_dir_all(&dir).with_context(|| format!("create {:?}", dir))?;
+        std::fs::write(dir.join("SKILL.md"), entry_to_skill_md(&entry))
+            .with_context(|| format!("write {:?}", dir))?;
+        detected.push(DetectedSkill {
+            tool: source.key.to_string(),
+            tool_display: source.display_name.to_string(),
+            name: sanitize_dir_name(&entry.name),
+            path: dir,
+            is_link: false,
+            link_target: None,
+        });
+    }
+    Ok(detected)
+}
+
+pub fn list_file_skill_tools() -> Vec<FileSkillToolDto> {
+    FILE_SKILL_SOURCES
+        .iter()
+        .map(|source| {
+            let path = resolve_config_path(source);
+            let exists = path.as_ref().is_some_and(|path| path.is_file());
+            let (skill_names, error) = match path.as_ref().filter(|_| exists) {
+                Some(path) => match std::fs::read_to_string(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| source.format.extract(&content))
+                {
+                    Ok(entries) => (entries.into_iter().map(|entry| entry.name).collect(), None),
+                    Err(err) => (Vec::new(), Some(err.to_string())),
+                },
+                None => (Vec::new(), None),
+            };
+            FileSkillToolDto {
+                key: source.key.to_string(),
+                display_name: source.display_name.to_string(),
+                config_path: path
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_else(|| source.config_path.to_string()),
+                exists,
+                skill_names,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// Write `content` next to `path` and rename it into place
+pub fn write_config_atomic(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+    let tmp = path.with_extension("ai-toolbox.tmp");
+    std::fs::write(&tmp, content).with_context(|| format!("write {:?}", tmp))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("rename {:?} -> {:?}", tmp, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_entries_round_trip_through_inject() {
+        let format = FileSkillFormat::YamlList(CONTINUE_PROMPTS);
+        let content = "name: cfg\nprompts:\n  - name: review\n    description: Review code\n    prompt: Review it\n    extra: keep\n";
+        let entries = format.extract(content).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].description.as_deref(), Some("Review code"));
+
+        let updated = format
+            .inject(
+                content,
+                &FileSkillEntry {
+                    name: "review".to_string(),
+                    description: None,
+                    body: "New body".to_string(),
+                },
+            )
+            .unwrap();
+        let root: Value = serde_yaml::from_str(&updated).unwrap();
+        assert_eq!(root["prompts"][0]["prompt"], "New body");
+        assert_eq!(root["prompts"][0]["extra"], "keep");
+        assert!(root["prompts"][0].get("description").is_none());
+        assert_eq!(root["name"], "cfg");
+
+        let removed = format.remove(&updated, "review").unwrap().unwrap();
+        assert!(format.extract(&removed).unwrap().is_empty());
+        assert!(format.remove(&removed, "review").unwrap().is_none());
+    }
+
+    #[test]
+    fn json_inject_creates_missing_list() {
+        let format = FileSkillFormat::JsonList(CONTINUE_CUSTOM_COMMANDS);
+        let entry = FileSkillEntry {
+            name: "test".to_string(),
+            description: Some("Write tests".to_string()),
+            body: "Write unit tests".to_string(),
+        };
+        let updated = format.inject("{\"models\": []}", &entry).unwrap();
+        assert_eq!(format.extract(&updated).unwrap(), vec![entry]);
+    }
+
+    #[test]
+    fn skill_md_conversion_round_trips() {
+        let entry = FileSkillEntry {
+            name: "review".to_string(),
+            description: Some("Review: code".to_string()),
+            body: "Line 1\nLine 2".to_string(),
+        };
+        let md = entry_to_skill_md(&entry);
+        assert_eq!(skill_md_to_entry("fallback", &md), entry);
+        assert_eq!(skill_md_to_entry("plain", "just text").name, "plain");
+    }
+}
@@ -1,4 +1,5 @@
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::process::Stdio;
 use std::sync::OnceLock;
@@ -306,3 +307,183 @@ fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>)
     }
     Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
 }
+
+/// A commit in a skill's upstream history
+#[derive(Debug, Clone)]
+pub struct GitCommitInfo {
+    pub revision: String,
+    pub author: String,
+    /// Commit time in epoch milliseconds
+    pub committed_at: i64,
+    pub summary: String,
+}
+
+fn run_git_in(repo_dir: &Path, args: &[&str]) -> Result<std::process::Output> {
+    let mut cmd = git_cmd();
+    cmd.arg("-C").arg(repo_dir).args(args);
+    run_cmd_with_timeout(
+        cmd,
+        git_fetch_timeout(),
+        format!("git {} in {:?}", args.join(" "), repo_dir),
+    )
+}
+
+/// Whether `revision` is a commit present in the local clone. Shallow caches
+/// that were re-cloned after install no longer contain the installed revision.
+pub fn has_commit(repo_dir: &Path, revision: &str) -> bool {
+    run_git_in(
+        repo_dir,
+        &["cat-file", "-e", &format!("{}^{{commit}}", revision)],
+    )
+    .map(|out| out.status.success())
+    .unwrap_or(false)
+}
+
+/// Commits after `from` up to `to` that touch `subpath`, newest first.
+/// Returns `None` when `from` is not an ancestor of `to` in the local clone.
+pub fn commits_between(
+    repo_dir: &Path,
+    from: &str,
+    to: &str,
+    subpath: Option<&str>,
+    limit: usize,
+) -> Result<Option<Vec<GitCommitInfo>>> {
+    if !has_commit(repo_dir, from) {
+        return Ok(None);
+    }
+    let ancestor = run_git_in(repo_dir, &["merge-base", "--is-ancestor", from, to])?;
+    if !ancestor.status.success() {
+        return Ok(None);
+    }
+
+    let range = format!("{}..{}", from, to);
+    let max_count = format!("--max-count={}", limit);
+    let mut args = vec![
+        "log",
+        "--format=%H%x1f%an%x1f%ct%x1f%s",
+        max_count.as_str(),
+        range.as_str(),
+        "--",
+    ];
+    args.push(subpath.unwrap_or("."));
+    let out = run_git_in(repo_dir, &args)?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        anyhow::bail!("GIT_LOG_FAILED|{}", stderr);
+    }
+    let commits = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, '\x1f');
+            let revision = parts.next()?.to_string();
+            let author = parts.next()?.to_string();
+            let committed_at = parts.next()?.parse::<i64>().ok()? * 1000;
+            let summary = parts.next().unwrap_or_default().to_string();
+            Some(GitCommitInfo {
+                revision,
+                author,
+                committed_at,
+                summary,
+            })
+        })
+        .collect();
+    Ok(Some(commits))
+}
+
+/// Blob ids of the files under `subpath` at `revision`, keyed by path
+/// relative to `subpath`. Only tree objects are read, so this works on
+/// blob-less partial clones.
+pub fn tree_blob_ids(
+    repo_dir: &Path,
+    revision: &str,
+    subpath: Option<&str>,
+) -> Result<BTreeMap<String, String>> {
+    let mut args = vec!["ls-tree", "-r", "-z", "--full-tree", revision];
+    if let Some(subpath) = subpath {
+        args.push("--");
+        args.push(subpath);
+    }
+    let out = run_git_in(repo_dir, &args)?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        anyhow::bail!("GIT_LS_TREE_FAILED|{}", stderr);
+    }
+    let prefix = subpath
+        .map(|subpath| format!("{}/", subpath.trim_matches('/')))
+        .unwrap_or_default();
+    let mut blobs = BTreeMap::new();
+    for entry in String::from_utf8_lossy(&out.stdout).split('\0') {
+        // "<mode> <type> <oid>\t<path>"
+        let Some((meta, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        let mut meta = meta.split(' ');
+        let (Some(_mode), Some("blob"), Some(oid)) = (meta.next(), meta.next(), meta.next()) else {
+            continue;
+        };
+        if let Some(relative) = path.strip_prefix(&prefix) {
+            blobs.insert(relative.to_string(), oid.to_string());
+        }
+    }
+    Ok(blobs)
+}
+
+/// Git blob ids of local files, in the order given. Filters (e.g. autocrlf)
+/// are skipped so ids match what a checkout of the committed bytes produces.
+pub fn hash_blob_ids(repo_dir: &Path, files: &[PathBuf]) -> Result<Vec<String>> {
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut cmd = git_cmd();
+    cmd.arg("-C")
+        .arg(repo_dir)
+        .args(["hash-object", "--no-filters", "--"])
+        .args(files);
+    let out = run_cmd_with_timeout(
+        cmd,
+        git_fetch_timeout(),
+        format!("git hash-object in {:?}", repo_dir),
+    )?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        anyhow::bail!("GIT_HASH_OBJECT_FAILED|{}", stderr);
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect())
+}
+
+/// Content of a blob. On blob-less partial clones git fetches it on demand.
+pub fn read_blob(repo_dir: &Path, oid: &str) -> Result<Vec<u8>> {
+    let out = run_git_in(repo_dir, &["cat-file", "blob", oid])?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        anyhow::bail!("GIT_CAT_FILE_FAILED|{}", stderr);
+    }
+    Ok(out.stdout)
+}
+
+/// Three-way merge of one file with `git merge-file`. Returns the merged
+/// content and whether it contains conflict markers.
+pub fn merge_file(ours: &Path, base: &Path, theirs: &Path) -> Result<(Vec<u8>, bool)> {
+    let mut cmd = git_cmd();
+    cmd.args(["merge-file", "-p", "--"])
+        .arg(ours)
+        .arg(base)
+        .arg(theirs);
+    let out = run_cmd_with_timeout(
+        cmd,
+        git_fetch_timeout(),
+        format!("git merge-file {:?}", ours),
+    )?;
+    // Exit code is the number of conflicts; negative (>127 on Unix) means failure
+    match out.status.code() {
+        Some(0) => Ok((out.stdout, false)),
+        Some(code) if (1..=127).contains(&code) => Ok((out.stdout, true)),
+        _ => {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            anyhow::bail!("GIT_MERGE_FILE_FAILED|{}", stderr);
+        }
+    }
+}
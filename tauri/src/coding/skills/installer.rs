@@ -5,26 +5,39 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::archive_source;
 use super::cache_cleanup::get_git_cache_ttl_secs;
 use super::central_repo::{
     ensure_central_repo, resolve_central_repo_path, resolve_skill_central_path,
     to_relative_central_path,
 };
+use super::collections::{self, GitCollectionCandidate};
+use super::collision_policy;
 use super::content_hash::hash_dir;
 use super::git_fetcher::{clone_or_pull, set_proxy, GitProxyMode};
+use super::linked_folder;
+use super::local_changes::{self, LocalChangesResolution};
+use super::name_collision;
 use super::path_executor::{
     remove_skill_target_checked, sync_copy_target_path, sync_skill_to_target, target_path_changed,
 };
+use super::provenance::{build_provenance, detect_license};
 use super::skill_store;
+use super::source_mirror::{load_source_mirror, SkillSourceMirror};
 use super::sync_engine::{
     copy_dir_recursive, copy_skill_dir, ensure_source_target_not_overlapping,
 };
+use super::target_guard;
+use super::target_marker;
+use super::team_mirror;
 use super::tool_adapters::{
-    adapter_by_key, is_tool_installed_with_state_async,
-    resolve_runtime_skills_path_with_state_async, runtime_adapter_by_key, RuntimeToolAdapter,
+    adapter_by_key, is_tool_installed_with_state_async, runtime_adapter_by_key, RuntimeToolAdapter,
 };
-use super::types::{now_ms, GitSkillCandidate, InstallResult, Skill, UpdateResult};
+use super::types::{now_ms, GitSkillCandidate, InstallResult, Skill, SyncMode, UpdateResult};
+use super::variants;
+use super::write_protect;
 use crate::http_client;
+use crate::settings::policy;
 use crate::SqliteDbState;
 
 /// Install a skill from a local folder
@@ -34,18 +47,101 @@ pub async fn install_local_skill(
     source_path: &Path,
     overwrite: bool,
 ) -> Result<InstallResult> {
-    if !source_path.exists() {
-        anyhow::bail!("source path not found: {:?}", source_path);
-    }
-
     let name = source_path
         .file_name()
         .map(|v| v.to_string_lossy().to_string())
         .unwrap_or_else(|| "unnamed-skill".to_string());
+    let source_ref = source_path.to_string_lossy().to_string();
+    install_local_dir(
+        app,
+        state,
+        source_path,
+        name,
+        "local",
+        source_ref,
+        None,
+        overwrite,
+    )
+    .await
+}
+
+/// Install the folder a tool-dir link points to, keeping the link's name and
+/// recording the resolved folder as a `linked_folder` source
+pub async fn install_linked_folder_skill(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    link_path: &Path,
+    source_path: &Path,
+    overwrite: bool,
+) -> Result<InstallResult> {
+    let name = link_path
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unnamed-skill".to_string());
+    install_local_dir(
+        app,
+        state,
+        source_path,
+        name,
+        linked_folder::LINKED_FOLDER_SOURCE_TYPE,
+        source_path.to_string_lossy().to_string(),
+        None,
+        overwrite,
+    )
+    .await
+}
+
+/// Install skill `name` published by the team mirror at `location`
+pub async fn install_team_mirror_skill(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    location: &str,
+    name: &str,
+    overwrite: bool,
+) -> Result<InstallResult> {
+    ensure_source_allowed(location)?;
+    let mirror = team_mirror::MirrorLocation::parse(location)?;
+    let manifest = team_mirror::fetch_manifest(state, &mirror).await?;
+    let entry = manifest
+        .skills
+        .iter()
+        .find(|entry| entry.name == name)
+        .ok_or_else(|| anyhow::anyhow!("skill {} is not published by {}", name, location))?;
+    let staging = tempfile::tempdir().context("failed to create temp dir")?;
+    let staged_dir = staging.path().join(&entry.name);
+    team_mirror::fetch_skill(state, &mirror, &manifest, entry, &staged_dir).await?;
+    install_local_dir(
+        app,
+        state,
+        &staged_dir,
+        entry.name.clone(),
+        team_mirror::TEAM_MIRROR_SOURCE_TYPE,
+        team_mirror::mirror_source_ref(location, &entry.name),
+        Some(entry.content_hash.clone()),
+        overwrite,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn install_local_dir(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    source_path: &Path,
+    name: String,
+    source_type: &str,
+    source_ref: String,
+    source_revision: Option<String>,
+    overwrite: bool,
+) -> Result<InstallResult> {
+    if !source_path.exists() {
+        anyhow::bail!("source path not found: {:?}", source_path);
+    }
 
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
     let central_path = central_dir.join(&name);
+    ensure_name_case_unique(state, &central_dir, &name).await?;
     ensure_source_target_not_overlapping(source_path, &central_path)?;
 
     // Check if skill already exists and get its ID for update
@@ -56,6 +152,10 @@ pub async fn install_local_skill(
                 .await
                 .ok()
                 .flatten();
+            write_protect::unlock(
+                &central_path,
+                existing.as_ref().is_some_and(|skill| skill.write_protected),
+            )?;
             std::fs::remove_dir_all(&central_path)
                 .with_context(|| format!("failed to remove existing skill: {:?}", central_path))?;
             existing
@@ -72,15 +172,22 @@ pub async fn install_local_skill(
     let now = now_ms();
     let content_hash = compute_content_hash(&central_path);
 
+    let license = detect_license(&central_path, None);
+    let provenance = build_provenance(
+        &central_path,
+        Some(source_ref.clone()),
+        None,
+        source_revision.clone(),
+    );
     let record = Skill {
         id: existing_skill
             .as_ref()
             .map(|skill| skill.id.clone())
             .unwrap_or_default(), // Use existing ID if overwriting
         name: name.clone(),
-        source_type: "local".to_string(),
-        source_ref: Some(source_path.to_string_lossy().to_string()),
-        source_revision: None,
+        source_type: source_type.to_string(),
+        source_ref: Some(source_ref),
+        source_revision,
         central_path: to_relative_central_path(&central_path, &central_dir),
         content_hash: content_hash.clone(),
         created_at: now,
@@ -107,17 +214,36 @@ pub async fn install_local_skill(
             .unwrap_or_default(),
         enabled_tools: Vec::new(),
         sync_details: None,
+        license: license.clone(),
+        provenance: Some(provenance),
+        tags: existing_skill
+            .as_ref()
+            .map(|skill| skill.tags.clone())
+            .unwrap_or_default(),
+        compatibility: existing_skill
+            .as_ref()
+            .map(|skill| skill.compatibility.clone())
+            .unwrap_or_default(),
+        localizations: existing_skill
+            .as_ref()
+            .map(|skill| skill.localizations.clone())
+            .unwrap_or_default(),
+        write_protected: existing_skill
+            .as_ref()
+            .is_some_and(|skill| skill.write_protected),
     };
 
     let skill_id = skill_store::upsert_skill(state, &record)
         .await
         .map_err(|e| anyhow::anyhow!(e))?;
+    write_protect::protect_or_warn(&central_path, record.write_protected);
 
     Ok(InstallResult {
         skill_id,
         name,
         central_path,
         content_hash,
+        license,
     })
 }
 
@@ -180,6 +306,7 @@ pub async fn install_local_skill_from_selection(
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
     let central_path = central_dir.join(&name);
+    ensure_name_case_unique(state, &central_dir, &name).await?;
     ensure_source_target_not_overlapping(&copy_src, &central_path)?;
 
     // Check if skill already exists and get its ID for update
@@ -189,6 +316,10 @@ pub async fn install_local_skill_from_selection(
                 .await
                 .ok()
                 .flatten();
+            write_protect::unlock(
+                &central_path,
+                existing.as_ref().is_some_and(|skill| skill.write_protected),
+            )?;
             std::fs::remove_dir_all(&central_path)
                 .with_context(|| format!("failed to remove existing skill: {:?}", central_path))?;
             existing
@@ -212,6 +343,8 @@ pub async fn install_local_skill_from_selection(
     let now = now_ms();
     let content_hash = compute_content_hash(&central_path);
 
+    let license = detect_license(&central_path, Some(source_path));
+    let provenance = build_provenance(&central_path, Some(full_source_ref.clone()), None, None);
     let record = Skill {
         id: existing_skill
             .as_ref()
@@ -247,17 +380,36 @@ pub async fn install_local_skill_from_selection(
             .unwrap_or_default(),
         enabled_tools: Vec::new(),
         sync_details: None,
+        license: license.clone(),
+        provenance: Some(provenance),
+        tags: existing_skill
+            .as_ref()
+            .map(|skill| skill.tags.clone())
+            .unwrap_or_default(),
+        compatibility: existing_skill
+            .as_ref()
+            .map(|skill| skill.compatibility.clone())
+            .unwrap_or_default(),
+        localizations: existing_skill
+            .as_ref()
+            .map(|skill| skill.localizations.clone())
+            .unwrap_or_default(),
+        write_protected: existing_skill
+            .as_ref()
+            .is_some_and(|skill| skill.write_protected),
     };
 
     let skill_id = skill_store::upsert_skill(state, &record)
         .await
         .map_err(|e| anyhow::anyhow!(e))?;
+    write_protect::protect_or_warn(&central_path, record.write_protected);
 
     Ok(InstallResult {
         skill_id,
         name,
         central_path,
         content_hash,
+        license,
     })
 }
 
@@ -269,6 +421,7 @@ pub async fn install_git_skill(
     branch: Option<&str>,
     overwrite: bool,
 ) -> Result<InstallResult> {
+    ensure_source_allowed(repo_url)?;
     // Initialize proxy from app settings
     init_proxy_from_settings(state).await;
 
@@ -278,7 +431,13 @@ pub async fn install_git_skill(
 
     // Clone first, then read skill name from SKILL.md
     let ttl = get_git_cache_ttl_secs(state).await;
-    let (repo_dir, rev) = clone_to_cache(app, ttl, &parsed.clone_url, effective_branch)?;
+    let mirror = load_source_mirror(state);
+    let (repo_dir, rev) = clone_to_cache(
+        app,
+        ttl,
+        &mirror.mirror_url(&parsed.clone_url),
+        effective_branch,
+    )?;
 
     let copy_src = if let Some(subpath) = &parsed.subpath {
         let sub_src = repo_dir.join(subpath);
@@ -316,6 +475,27 @@ pub async fn install_git_skill(
         }
     };
 
+    // Build full source_ref URL including subpath for later updates
+    let full_source_ref = if copy_src == repo_dir {
+        // Using repo root
+        repo_url.to_string()
+    } else {
+        // Using a subdirectory - build GitHub tree URL
+        let subpath = copy_src
+            .strip_prefix(&repo_dir)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        let branch_for_url = effective_branch.unwrap_or("main");
+        format!(
+            "{}/tree/{}/{}",
+            parsed.clone_url.trim_end_matches(".git"),
+            branch_for_url,
+            subpath
+        )
+    };
+
+    verify_skill_content(&copy_src, &mirror, &full_source_ref)?;
+
     // Try to read name from SKILL.md, fallback to URL-derived name
     let name = read_skill_name_from_dir(&copy_src)
         .unwrap_or_else(|| derive_name_from_repo_url(&parsed.clone_url));
@@ -323,6 +503,7 @@ pub async fn install_git_skill(
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
     let central_path = central_dir.join(&name);
+    ensure_name_case_unique(state, &central_dir, &name).await?;
 
     // Check if skill already exists and get its ID for update
     let existing_skill = if central_path.exists() {
@@ -332,6 +513,10 @@ pub async fn install_git_skill(
                 .await
                 .ok()
                 .flatten();
+            write_protect::unlock(
+                &central_path,
+                existing.as_ref().is_some_and(|skill| skill.write_protected),
+            )?;
             std::fs::remove_dir_all(&central_path)
                 .with_context(|| format!("failed to remove existing skill: {:?}", central_path))?;
             existing
@@ -345,28 +530,16 @@ pub async fn install_git_skill(
     copy_skill_dir(&copy_src, &central_path)
         .with_context(|| format!("copy {:?} -> {:?}", copy_src, central_path))?;
 
-    // Build full source_ref URL including subpath for later updates
-    let full_source_ref = if copy_src == repo_dir {
-        // Using repo root
-        repo_url.to_string()
-    } else {
-        // Using a subdirectory - build GitHub tree URL
-        let subpath = copy_src
-            .strip_prefix(&repo_dir)
-            .map(|p| p.to_string_lossy().replace('\\', "/"))
-            .unwrap_or_default();
-        let branch_for_url = effective_branch.unwrap_or("main");
-        format!(
-            "{}/tree/{}/{}",
-            parsed.clone_url.trim_end_matches(".git"),
-            branch_for_url,
-            subpath
-        )
-    };
-
     let now = now_ms();
     let content_hash = compute_content_hash(&central_path);
 
+    let license = detect_license(&central_path, Some(&repo_dir));
+    let provenance = build_provenance(
+        &central_path,
+        Some(full_source_ref.clone()),
+        mirror_fetch_url(&mirror, &parsed.clone_url),
+        Some(rev.clone()),
+    );
     let record = Skill {
         id: existing_skill
             .as_ref()
@@ -402,17 +575,179 @@ pub async fn install_git_skill(
             .unwrap_or_default(),
         enabled_tools: Vec::new(),
         sync_details: None,
+        license: license.clone(),
+        provenance: Some(provenance),
+        tags: existing_skill
+            .as_ref()
+            .map(|skill| skill.tags.clone())
+            .unwrap_or_default(),
+        compatibility: existing_skill
+            .as_ref()
+            .map(|skill| skill.compatibility.clone())
+            .unwrap_or_default(),
+        localizations: existing_skill
+            .as_ref()
+            .map(|skill| skill.localizations.clone())
+            .unwrap_or_default(),
+        write_protected: existing_skill
+            .as_ref()
+            .is_some_and(|skill| skill.write_protected),
+    };
+
+    let skill_id = skill_store::upsert_skill(state, &record)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    write_protect::protect_or_warn(&central_path, record.write_protected);
+
+    Ok(InstallResult {
+        skill_id,
+        name,
+        central_path,
+        content_hash,
+        license,
+    })
+}
+
+/// Install a skill from a zip / tarball URL.
+/// `checksum` (`sha256:<hex>` or bare hex) is verified before extraction.
+pub async fn install_skill_from_archive_url(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    url: &str,
+    checksum: Option<&str>,
+    overwrite: bool,
+) -> Result<InstallResult> {
+    ensure_source_allowed(url)?;
+    let mirror = load_source_mirror(state);
+    let expected = mirror
+        .expected_checksum(url, checksum)
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let bytes = archive_source::download_archive(state, &mirror.mirror_url(url)).await?;
+    let archive_checksum = archive_source::verify_checksum(&bytes, expected.as_deref())?;
+    let kind = archive_source::detect_archive_kind(url, &bytes)
+        .ok_or_else(|| anyhow::anyhow!("unsupported archive format: {}", url))?;
+
+    let extract_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    archive_source::extract_archive(kind, &bytes, extract_dir.path())?;
+    let root = archive_source::unwrap_single_root(extract_dir.path());
+
+    let mut candidates = Vec::new();
+    scan_skills_recursive_paths(&root, &root, &mut candidates);
+    let copy_src = match candidates.len() {
+        0 => root.clone(),
+        1 => candidates.into_iter().next().unwrap(),
+        _ => anyhow::bail!(
+            "MULTI_SKILLS|This archive contains multiple Skills. Please provide an archive with a single Skill."
+        ),
+    };
+
+    let name = read_skill_name_from_dir(&copy_src)
+        .unwrap_or_else(|| archive_source::derive_name_from_archive_url(url));
+
+    let central_dir = resolve_central_repo_path(app, state).await?;
+    ensure_central_repo(&central_dir)?;
+    let central_path = central_dir.join(&name);
+    ensure_name_case_unique(state, &central_dir, &name).await?;
+
+    // Check if skill already exists and get its ID for update
+    let existing_skill = if central_path.exists() {
+        if overwrite {
+            let existing = skill_store::get_skill_by_name(state, &name)
+                .await
+                .ok()
+                .flatten();
+            write_protect::unlock(
+                &central_path,
+                existing.as_ref().is_some_and(|skill| skill.write_protected),
+            )?;
+            std::fs::remove_dir_all(&central_path)
+                .with_context(|| format!("failed to remove existing skill: {:?}", central_path))?;
+            existing
+        } else {
+            anyhow::bail!("SKILL_EXISTS|{}", name);
+        }
+    } else {
+        None
+    };
+
+    copy_skill_dir(&copy_src, &central_path)
+        .with_context(|| format!("copy {:?} -> {:?}", copy_src, central_path))?;
+
+    let now = now_ms();
+    let content_hash = compute_content_hash(&central_path);
+
+    let license = detect_license(&central_path, Some(&root));
+    let provenance = build_provenance(
+        &central_path,
+        Some(url.to_string()),
+        Some(mirror.mirror_url(url)),
+        Some(archive_checksum.clone()),
+    );
+    let record = Skill {
+        id: existing_skill
+            .as_ref()
+            .map(|skill| skill.id.clone())
+            .unwrap_or_default(),
+        name: name.clone(),
+        source_type: archive_source::ARCHIVE_SOURCE_TYPE.to_string(),
+        source_ref: Some(url.to_string()),
+        source_revision: Some(archive_checksum),
+        central_path: to_relative_central_path(&central_path, &central_dir),
+        content_hash: content_hash.clone(),
+        created_at: now,
+        updated_at: now,
+        last_sync_at: None,
+        status: "ok".to_string(),
+        sort_index: 0,
+        user_group: existing_skill
+            .as_ref()
+            .and_then(|skill| skill.user_group.clone()),
+        group_id: existing_skill
+            .as_ref()
+            .and_then(|skill| skill.group_id.clone()),
+        user_note: existing_skill
+            .as_ref()
+            .and_then(|skill| skill.user_note.clone()),
+        management_enabled: existing_skill
+            .as_ref()
+            .map(|skill| skill.management_enabled)
+            .unwrap_or(true),
+        disabled_previous_tools: existing_skill
+            .as_ref()
+            .map(|skill| skill.disabled_previous_tools.clone())
+            .unwrap_or_default(),
+        enabled_tools: Vec::new(),
+        sync_details: None,
+        license: license.clone(),
+        provenance: Some(provenance),
+        tags: existing_skill
+            .as_ref()
+            .map(|skill| skill.tags.clone())
+            .unwrap_or_default(),
+        compatibility: existing_skill
+            .as_ref()
+            .map(|skill| skill.compatibility.clone())
+            .unwrap_or_default(),
+        localizations: existing_skill
+            .as_ref()
+            .map(|skill| skill.localizations.clone())
+            .unwrap_or_default(),
+        write_protected: existing_skill
+            .as_ref()
+            .is_some_and(|skill| skill.write_protected),
     };
 
     let skill_id = skill_store::upsert_skill(state, &record)
         .await
         .map_err(|e| anyhow::anyhow!(e))?;
+    write_protect::protect_or_warn(&central_path, record.write_protected);
 
     Ok(InstallResult {
         skill_id,
         name,
         central_path,
         content_hash,
+        license,
     })
 }
 
@@ -420,19 +755,48 @@ pub async fn install_git_skill(
 pub fn list_git_skills(
     app: &tauri::AppHandle,
     cache_ttl_secs: i64,
+    mirror: &SkillSourceMirror,
     repo_url: &str,
     branch: Option<&str>,
 ) -> Result<Vec<GitSkillCandidate>> {
     let parsed = parse_github_url(repo_url);
     // Use provided branch, or fall back to parsed branch from URL
     let effective_branch = branch.or(parsed.branch.as_deref());
-    let (repo_dir, _rev) =
-        clone_to_cache(app, cache_ttl_secs, &parsed.clone_url, effective_branch)?;
+    let (repo_dir, _rev) = clone_to_cache(
+        app,
+        cache_ttl_secs,
+        &mirror.mirror_url(&parsed.clone_url),
+        effective_branch,
+    )?;
+    Ok(scan_git_candidates(&repo_dir, parsed.subpath.as_deref()))
+}
 
+/// Collections the repo defines in its `skill-collections.json`, resolved
+/// against the skills the repo contains
+pub fn list_git_collections(
+    app: &tauri::AppHandle,
+    cache_ttl_secs: i64,
+    mirror: &SkillSourceMirror,
+    repo_url: &str,
+    branch: Option<&str>,
+) -> Result<Vec<GitCollectionCandidate>> {
+    let parsed = parse_github_url(repo_url);
+    let effective_branch = branch.or(parsed.branch.as_deref());
+    let (repo_dir, _rev) = clone_to_cache(
+        app,
+        cache_ttl_secs,
+        &mirror.mirror_url(&parsed.clone_url),
+        effective_branch,
+    )?;
+    let candidates = scan_git_candidates(&repo_dir, None);
+    collections::registry_collections(&repo_dir, &candidates)
+}
+
+fn scan_git_candidates(repo_dir: &Path, subpath: Option<&str>) -> Vec<GitSkillCandidate> {
     let mut out: Vec<GitSkillCandidate> = Vec::new();
 
     // If user provided a folder URL, treat as single candidate
-    if let Some(subpath) = &parsed.subpath {
+    if let Some(subpath) = subpath {
         let dir = repo_dir.join(subpath);
         if dir.is_dir() && dir.join("SKILL.md").exists() {
             let (name, desc) = parse_skill_md(&dir.join("SKILL.md")).unwrap_or((
@@ -448,7 +812,7 @@ pub fn list_git_skills(
                 subpath: subpath.to_string(),
             });
         }
-        return Ok(out);
+        return out;
     }
 
     // Root-level skill
@@ -462,13 +826,13 @@ pub fn list_git_skills(
         });
     } else {
         // Recursively scan entire repo for skills (including hidden dirs like .claude, .cursor)
-        scan_skills_recursive(&repo_dir, &repo_dir, &mut out);
+        scan_skills_recursive(repo_dir, repo_dir, &mut out);
     }
 
     out.sort_by(|a, b| a.name.cmp(&b.name));
     out.dedup_by(|a, b| a.subpath == b.subpath);
 
-    Ok(out)
+    out
 }
 
 /// Install a specific skill from a Git repo selection
@@ -480,6 +844,7 @@ pub async fn install_git_skill_from_selection(
     branch: Option<&str>,
     overwrite: bool,
 ) -> Result<InstallResult> {
+    ensure_source_allowed(repo_url)?;
     // Initialize proxy from app settings
     init_proxy_from_settings(state).await;
 
@@ -489,7 +854,13 @@ pub async fn install_git_skill_from_selection(
 
     // Clone first, then read skill name from SKILL.md
     let ttl = get_git_cache_ttl_secs(state).await;
-    let (repo_dir, revision) = clone_to_cache(app, ttl, &parsed.clone_url, effective_branch)?;
+    let mirror = load_source_mirror(state);
+    let (repo_dir, revision) = clone_to_cache(
+        app,
+        ttl,
+        &mirror.mirror_url(&parsed.clone_url),
+        effective_branch,
+    )?;
 
     let copy_src = if subpath == "." {
         repo_dir.clone()
@@ -500,6 +871,22 @@ pub async fn install_git_skill_from_selection(
         anyhow::bail!("path not found in repo: {:?}", copy_src);
     }
 
+    // Build full source_ref URL including subpath for later updates
+    let branch_for_url = effective_branch.unwrap_or("main");
+    let full_source_ref = if subpath == "." {
+        repo_url.to_string()
+    } else {
+        // Build GitHub tree URL: https://github.com/owner/repo/tree/branch/subpath
+        format!(
+            "{}/tree/{}/{}",
+            parsed.clone_url.trim_end_matches(".git"),
+            branch_for_url,
+            subpath
+        )
+    };
+
+    verify_skill_content(&copy_src, &mirror, &full_source_ref)?;
+
     // Try to read name from SKILL.md, fallback to subpath or URL-derived name
     let display_name = read_skill_name_from_dir(&copy_src).unwrap_or_else(|| {
         Path::new(subpath)
@@ -511,6 +898,7 @@ pub async fn install_git_skill_from_selection(
     let central_dir = resolve_central_repo_path(app, state).await?;
     ensure_central_repo(&central_dir)?;
     let central_path = central_dir.join(&display_name);
+    ensure_name_case_unique(state, &central_dir, &display_name).await?;
 
     // Check if skill already exists and get its ID for update
     let existing_skill = if central_path.exists() {
@@ -520,6 +908,10 @@ pub async fn install_git_skill_from_selection(
                 .await
                 .ok()
                 .flatten();
+            write_protect::unlock(
+                &central_path,
+                existing.as_ref().is_some_and(|skill| skill.write_protected),
+            )?;
             std::fs::remove_dir_all(&central_path)
                 .with_context(|| format!("failed to remove existing skill: {:?}", central_path))?;
             existing
@@ -533,22 +925,15 @@ pub async fn install_git_skill_from_selection(
     copy_skill_dir(&copy_src, &central_path)
         .with_context(|| format!("copy {:?} -> {:?}", copy_src, central_path))?;
 
-    // Build full source_ref URL including subpath for later updates
-    let branch_for_url = effective_branch.unwrap_or("main");
-    let full_source_ref = if subpath == "." {
-        repo_url.to_string()
-    } else {
-        // Build GitHub tree URL: https://github.com/owner/repo/tree/branch/subpath
-        format!(
-            "{}/tree/{}/{}",
-            parsed.clone_url.trim_end_matches(".git"),
-            branch_for_url,
-            subpath
-        )
-    };
-
     let now = now_ms();
     let content_hash = compute_content_hash(&central_path);
+    let license = detect_license(&central_path, Some(&repo_dir));
+    let provenance = build_provenance(
+        &central_path,
+        Some(full_source_ref.clone()),
+        mirror_fetch_url(&mirror, &parsed.clone_url),
+        Some(revision.clone()),
+    );
     let record = Skill {
         id: existing_skill
             .as_ref()
@@ -584,65 +969,85 @@ pub async fn install_git_skill_from_selection(
             .unwrap_or_default(),
         enabled_tools: Vec::new(),
         sync_details: None,
+        license: license.clone(),
+        provenance: Some(provenance),
+        tags: existing_skill
+            .as_ref()
+            .map(|skill| skill.tags.clone())
+            .unwrap_or_default(),
+        compatibility: existing_skill
+            .as_ref()
+            .map(|skill| skill.compatibility.clone())
+            .unwrap_or_default(),
+        localizations: existing_skill
+            .as_ref()
+            .map(|skill| skill.localizations.clone())
+            .unwrap_or_default(),
+        write_protected: existing_skill
+            .as_ref()
+            .is_some_and(|skill| skill.write_protected),
     };
     let skill_id = skill_store::upsert_skill(state, &record)
         .await
         .map_err(|e| anyhow::anyhow!(e))?;
+    write_protect::protect_or_warn(&central_path, record.write_protected);
 
     Ok(InstallResult {
         skill_id,
         name: display_name,
         central_path,
         content_hash,
+        license,
     })
 }
 
-/// Update a managed skill from its source
-pub async fn update_managed_skill_from_source(
-    app: &tauri::AppHandle,
-    state: &SqliteDbState,
-    skill_id: &str,
-) -> Result<UpdateResult> {
-    // Initialize proxy from app settings (for git source types)
-    init_proxy_from_settings(state).await;
-
-    let record = skill_store::get_skill_by_id(state, skill_id)
-        .await
-        .map_err(|e| anyhow::anyhow!(e))?
-        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
-
-    // Resolve central_path: supports both relative (new) and legacy absolute paths
-    let central_dir = resolve_central_repo_path(app, state).await?;
-    let central_path = resolve_skill_central_path(&record.central_path, &central_dir);
-    if !central_path.exists() {
-        anyhow::bail!("central path not found: {:?}", central_path);
-    }
-    let central_parent = central_path
-        .parent()
-        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?
-        .to_path_buf();
-
-    let now = now_ms();
+/// Git checkout that an update was staged from
+#[derive(Debug)]
+pub struct GitCheckout {
+    pub repo_dir: PathBuf,
+    pub subpath: Option<String>,
+}
 
-    // Build new content in a staging dir
-    let staging_dir = central_parent.join(format!(".skills-update-{}", Uuid::new_v4()));
-    if staging_dir.exists() {
-        let _ = std::fs::remove_dir_all(&staging_dir);
-    }
+/// Result of copying a skill's current upstream content into a staging dir
+#[derive(Debug, Default)]
+pub struct StagedUpdate {
+    pub new_revision: Option<String>,
+    pub fetched_from: Option<String>,
+    /// Set for git sources; the cached repo stays checked out at `new_revision`
+    pub git_checkout: Option<GitCheckout>,
+}
 
-    let mut new_revision: Option<String> = None;
+/// Fetch the upstream content of `record` and copy it into `staging_dir`.
+/// `checksum` is the expected archive checksum the user supplied for an
+/// archive skill whose host publishes none.
+pub async fn stage_update_source(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    record: &Skill,
+    staging_dir: &Path,
+    checksum: Option<&str>,
+) -> Result<StagedUpdate> {
+    let mut staged = StagedUpdate::default();
+    let mirror = load_source_mirror(state);
 
     if record.source_type == "git" {
         let repo_url = record
             .source_ref
             .as_deref()
             .ok_or_else(|| anyhow::anyhow!("missing source_ref for git skill"))?;
+        ensure_source_allowed(repo_url)?;
         let parsed = parse_github_url(repo_url);
 
+        init_proxy_from_settings(state).await;
         let ttl = get_git_cache_ttl_secs(state).await;
-        let (repo_dir, rev) =
-            clone_to_cache(app, ttl, &parsed.clone_url, parsed.branch.as_deref())?;
-        new_revision = Some(rev);
+        let (repo_dir, rev) = clone_to_cache(
+            app,
+            ttl,
+            &mirror.mirror_url(&parsed.clone_url),
+            parsed.branch.as_deref(),
+        )?;
+        staged.new_revision = Some(rev);
+        staged.fetched_from = mirror_fetch_url(&mirror, &parsed.clone_url);
 
         let copy_src = if let Some(subpath) = &parsed.subpath {
             repo_dir.join(subpath)
@@ -652,10 +1057,48 @@ pub async fn update_managed_skill_from_source(
         if !copy_src.exists() {
             anyhow::bail!("path not found in repo: {:?}", copy_src);
         }
+        verify_skill_content(&copy_src, &mirror, repo_url)?;
 
-        copy_skill_dir(&copy_src, &staging_dir)
+        copy_skill_dir(&copy_src, staging_dir)
             .with_context(|| format!("copy {:?} -> {:?}", copy_src, staging_dir))?;
-    } else if record.source_type == "local" {
+        staged.git_checkout = Some(GitCheckout {
+            repo_dir,
+            subpath: parsed.subpath,
+        });
+    } else if record.source_type == archive_source::ARCHIVE_SOURCE_TYPE {
+        let url = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for archive skill"))?;
+        ensure_source_allowed(url)?;
+        let expected = match mirror
+            .expected_checksum(url, checksum)
+            .map_err(|e| anyhow::anyhow!(e))?
+        {
+            Some(configured) => configured,
+            None => archive_source::fetch_published_checksum(state, url).await?,
+        };
+        let bytes = archive_source::download_archive(state, &mirror.mirror_url(url)).await?;
+        let archive_checksum = archive_source::verify_checksum(&bytes, Some(&expected))?;
+        staged.fetched_from = mirror_fetch_url(&mirror, url);
+        let kind = archive_source::detect_archive_kind(url, &bytes)
+            .ok_or_else(|| anyhow::anyhow!("unsupported archive format: {}", url))?;
+        let extract_dir = tempfile::tempdir().context("failed to create temp dir")?;
+        archive_source::extract_archive(kind, &bytes, extract_dir.path())?;
+        let root = archive_source::unwrap_single_root(extract_dir.path());
+        let mut candidates = Vec::new();
+        scan_skills_recursive_paths(&root, &root, &mut candidates);
+        let copy_src = if candidates.len() == 1 {
+            candidates.remove(0)
+        } else {
+            root
+        };
+        copy_skill_dir(&copy_src, staging_dir)
+            .with_context(|| format!("copy {:?} -> {:?}", copy_src, staging_dir))?;
+        staged.new_revision = Some(archive_checksum);
+    } else if record.source_type == "local"
+        || record.source_type == linked_folder::LINKED_FOLDER_SOURCE_TYPE
+    {
         let source = record
             .source_ref
             .as_deref()
@@ -664,15 +1107,128 @@ pub async fn update_managed_skill_from_source(
         if !source_path.exists() {
             anyhow::bail!("source path not found: {:?}", source_path);
         }
-        copy_skill_dir(&source_path, &staging_dir)
+        copy_skill_dir(&source_path, staging_dir)
             .with_context(|| format!("copy {:?} -> {:?}", source_path, staging_dir))?;
+    } else if record.source_type == team_mirror::TEAM_MIRROR_SOURCE_TYPE {
+        let source_ref = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for team mirror skill"))?;
+        let (location, name) = team_mirror::parse_mirror_source_ref(source_ref)
+            .ok_or_else(|| anyhow::anyhow!("invalid team mirror source: {}", source_ref))?;
+        ensure_source_allowed(location)?;
+        let mirror = team_mirror::MirrorLocation::parse(location)?;
+        let manifest = team_mirror::fetch_manifest(state, &mirror).await?;
+        let entry = manifest
+            .skills
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| {
+                anyhow::anyhow!("skill {} is no longer published by {}", name, location)
+            })?;
+        team_mirror::fetch_skill(state, &mirror, &manifest, entry, staging_dir).await?;
+        staged.new_revision = Some(entry.content_hash.clone());
     } else {
         anyhow::bail!("unsupported source_type for update: {}", record.source_type);
     }
+    Ok(staged)
+}
+
+/// Update a managed skill from its source
+pub async fn update_managed_skill_from_source(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    skill_id: &str,
+    resolution: Option<LocalChangesResolution>,
+    checksum: Option<&str>,
+) -> Result<UpdateResult> {
+    let record = skill_store::get_skill_by_id(state, skill_id)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+
+    // Resolve central_path: supports both relative (new) and legacy absolute paths
+    let central_dir = resolve_central_repo_path(app, state).await?;
+    let central_path = resolve_skill_central_path(&record.central_path, &central_dir);
+    if !central_path.exists() {
+        anyhow::bail!("central path not found: {:?}", central_path);
+    }
+    let central_parent = central_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?
+        .to_path_buf();
+
+    let now = now_ms();
+
+    // Local edits of git skills are never overwritten silently
+    let locally_modified = local_changes::is_locally_modified(&record, &central_path);
+    if locally_modified {
+        match resolution {
+            None => anyhow::bail!("LOCAL_MODIFIED|{}", record.id),
+            Some(LocalChangesResolution::KeepLocal) => {
+                let content_hash = compute_content_hash(&central_path);
+                let forked = local_changes::fork_record(&record, content_hash.clone(), now);
+                skill_store::upsert_skill(state, &forked)
+                    .await
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                return Ok(UpdateResult {
+                    skill_id: record.id,
+                    name: record.name,
+                    central_path,
+                    content_hash,
+                    source_revision: None,
+                    updated_targets: Vec::new(),
+                });
+            }
+            Some(LocalChangesResolution::Overwrite | LocalChangesResolution::Merge) => {}
+        }
+    }
+
+    // Build new content in a staging dir
+    let staging_dir = central_parent.join(format!(".skills-update-{}", Uuid::new_v4()));
+    if staging_dir.exists() {
+        let _ = std::fs::remove_dir_all(&staging_dir);
+    }
+
+    let StagedUpdate {
+        new_revision,
+        fetched_from,
+        git_checkout,
+    } = stage_update_source(app, state, &record, &staging_dir, checksum).await?;
+
+    if locally_modified && resolution == Some(LocalChangesResolution::Merge) {
+        let merged = match git_checkout.as_ref() {
+            Some(checkout) => local_changes::merge_into_staging(
+                checkout,
+                record.source_revision.as_deref(),
+                &central_path,
+                &staging_dir,
+            ),
+            None => Err(anyhow::anyhow!("MERGE_BASE_UNAVAILABLE|")),
+        };
+        match merged {
+            Ok(outcome) => log::info!(
+                "[update] merged local edits of {}: merged={:?} kept_local={:?}",
+                record.name,
+                outcome.merged,
+                outcome.kept_local
+            ),
+            Err(err) => {
+                let _ = std::fs::remove_dir_all(&staging_dir);
+                return Err(err);
+            }
+        }
+    }
 
     // Swap: remove old dir and rename staging into place
-    std::fs::remove_dir_all(&central_path)
-        .with_context(|| format!("failed to remove old central dir {:?}", central_path))?;
+    write_protect::unlock(&central_path, record.write_protected)?;
+    if let Err(err) = std::fs::remove_dir_all(&central_path) {
+        write_protect::protect_or_warn(&central_path, record.write_protected);
+        return Err(anyhow::Error::new(err).context(format!(
+            "failed to remove old central dir {:?}",
+            central_path
+        )));
+    }
     if let Err(err) = std::fs::rename(&staging_dir, &central_path) {
         copy_dir_recursive(&staging_dir, &central_path)
             .with_context(|| format!("fallback copy {:?} -> {:?}", staging_dir, central_path))?;
@@ -681,6 +1237,14 @@ pub async fn update_managed_skill_from_source(
     }
 
     let content_hash = compute_content_hash(&central_path);
+    // Repo-root licenses are not copied; keep the previous value then
+    let license = detect_license(&central_path, None).or(record.license.clone());
+    let provenance = build_provenance(
+        &central_path,
+        record.source_ref.clone(),
+        fetched_from,
+        new_revision.clone().or(record.source_revision.clone()),
+    );
 
     // Update DB skill row (store relative central_path)
     let relative_central_path = to_relative_central_path(&central_path, &central_dir);
@@ -704,10 +1268,17 @@ pub async fn update_managed_skill_from_source(
         disabled_previous_tools: record.disabled_previous_tools.clone(),
         enabled_tools: record.enabled_tools.clone(),
         sync_details: record.sync_details.clone(),
+        license,
+        provenance: Some(provenance),
+        tags: record.tags.clone(),
+        compatibility: record.compatibility.clone(),
+        localizations: record.localizations.clone(),
+        write_protected: record.write_protected,
     };
     skill_store::upsert_skill(state, &updated)
         .await
         .map_err(|e| anyhow::anyhow!(e))?;
+    write_protect::protect_or_warn(&central_path, record.write_protected);
 
     if !record.management_enabled {
         return Ok(UpdateResult {
@@ -729,12 +1300,6 @@ pub async fn update_managed_skill_from_source(
         .unwrap_or_default();
     let mut updated_targets: Vec<String> = Vec::new();
     for t in targets {
-        // Check if custom tool has force_copy enabled
-        let custom_tool_force_copy = custom_tools
-            .iter()
-            .find(|ct| ct.key == t.tool)
-            .map(|ct| ct.force_copy)
-            .unwrap_or(false);
         let runtime_adapter = if let Some(adapter) = runtime_adapter_by_key(&t.tool, &custom_tools)
         {
             adapter
@@ -752,22 +1317,40 @@ pub async fn update_managed_skill_from_source(
             continue;
         }
 
-        let tool_root =
-            match resolve_runtime_skills_path_with_state_async(state, &runtime_adapter).await {
-                Ok(path) => path,
-                Err(_) => continue,
+        let current_target =
+            match target_guard::resolve_checked_target(state, &runtime_adapter, &record.name).await
+            {
+                Ok((_, target)) => target,
+                Err(err) => {
+                    log::warn!("Skip re-sync of '{}' ({}): {}", record.name, t.tool, err);
+                    continue;
+                }
             };
-        let current_target = tool_root.join(&record.name);
+        let current_target =
+            collision_policy::renamed_target(&record.name, &current_target, &t.target_path)
+                .unwrap_or(current_target);
         let target_path_moved = target_path_changed(&t.target_path, &current_target);
-        let force_copy = t.mode == "copy" || t.tool == "cursor" || custom_tool_force_copy;
+        // Copies and hard links keep the replaced files and need a refresh;
+        // targets with path rules are always filtered copies
+        let tool_mode = runtime_adapter.sync_mode();
+        let refresh_mode = if t.mode == "copy" && tool_mode != SyncMode::Hardlink {
+            SyncMode::Copy
+        } else {
+            tool_mode
+        };
+        let holds_own_files =
+            refresh_mode.holds_own_files() || t.mode == "hardlink" || !t.path_rules.is_empty();
+        // Tools pinned to a skill variant keep the variant snapshot content
+        let variant_source = variants::resolve_variant_source(state, skill_id, &t.tool)
+            .map_err(anyhow::Error::msg)?;
 
         let sync_result = if target_path_moved {
             let sync_result = sync_skill_to_target(
-                &t.tool,
-                &central_path,
+                variant_source.as_deref().unwrap_or(&central_path),
                 &current_target,
                 true,
-                runtime_adapter.force_copy,
+                tool_mode,
+                &t.path_rules,
             )?;
             if let Err(err) = remove_skill_target_checked(&central_path, &t.target_path) {
                 log::warn!(
@@ -778,8 +1361,8 @@ pub async fn update_managed_skill_from_source(
                 );
             }
             sync_result
-        } else if force_copy {
-            sync_copy_target_path(&central_path, &t.target_path)?
+        } else if holds_own_files && variant_source.is_none() {
+            sync_copy_target_path(&central_path, &t.target_path, refresh_mode, &t.path_rules)?
         } else {
             continue;
         };
@@ -791,7 +1374,9 @@ pub async fn update_managed_skill_from_source(
             status: "ok".to_string(),
             synced_at: Some(now),
             error_message: None,
+            path_rules: t.path_rules.clone(),
         };
+        target_marker::mark_target(&record, &target_record);
         let _ = skill_store::upsert_skill_target(state, skill_id, &target_record).await;
 
         if !updated_targets.iter().any(|tool| tool == &t.tool) {
@@ -941,6 +1526,62 @@ fn derive_name_from_repo_url(repo_url: &str) -> String {
     }
 }
 
+/// Refuse a name that differs only by case from a managed skill or an entry
+/// already in the central repo; on case-insensitive file systems both would
+/// share one directory.
+async fn ensure_name_case_unique(
+    state: &SqliteDbState,
+    central_dir: &Path,
+    name: &str,
+) -> Result<()> {
+    let mut existing = name_collision::dir_entry_names(central_dir);
+    existing.extend(
+        skill_store::get_managed_skills(state)
+            .await
+            .map_err(anyhow::Error::msg)?
+            .into_iter()
+            .map(|skill| skill.name),
+    );
+    name_collision::ensure_no_case_collision(name, &existing).map_err(anyhow::Error::msg)
+}
+
+/// Mirror URL used for `upstream`, or `None` when fetched directly
+/// Refuse sources the group policy lists in `forbidden_registries`
+fn ensure_source_allowed(source: &str) -> Result<()> {
+    policy::ensure_source_allowed(source).map_err(|e| anyhow::anyhow!(e))
+}
+
+fn mirror_fetch_url(mirror: &SkillSourceMirror, upstream: &str) -> Option<String> {
+    Some(mirror.mirror_url(upstream)).filter(|url| url != upstream)
+}
+
+/// Verify the content a remote skill would have in the central repo against
+/// the checksum configured in the source mirror policy, before it is adopted.
+fn verify_skill_content(
+    copy_src: &Path,
+    mirror: &SkillSourceMirror,
+    source_ref: &str,
+) -> Result<()> {
+    let Some(expected) = mirror
+        .expected_checksum(source_ref, None)
+        .map_err(|e| anyhow::anyhow!(e))?
+    else {
+        return Ok(());
+    };
+    // Hash a staged copy so symlink resolution and skipped files match
+    // what `copy_skill_dir` would put into the central repo
+    let staging = tempfile::tempdir().context("failed to create temp dir")?;
+    let staged = staging.path().join("skill");
+    copy_skill_dir(copy_src, &staged)
+        .with_context(|| format!("copy {:?} -> {:?}", copy_src, staged))?;
+    let actual = hash_dir(&staged)?;
+    let expected = archive_source::normalize_checksum(&expected);
+    if expected != actual {
+        anyhow::bail!("CHECKSUM_MISMATCH|{}|{}", expected, actual);
+    }
+    Ok(())
+}
+
 fn compute_content_hash(path: &Path) -> Option<String> {
     hash_dir(path).ok()
 }
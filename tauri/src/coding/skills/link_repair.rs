@@ -0,0 +1,134 @@
+//! Repair of broken link targets
+//!
+//! A symlink or junction target breaks when the central repo moves or a
+//! skill directory is deleted outside the app. `find_broken_links` checks
+//! every recorded `symlink` / `junction` target of the given skills;
+//! `auto_sync_commands::repair_links` re-creates each broken link from its
+//! record when the skill's source still exists, and otherwise records the
+//! target as `BROKEN_STATUS` with the reason. WSL targets are not probed.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::adapter::parse_sync_details;
+use super::types::{Skill, SkillTarget};
+use crate::coding::runtime_location;
+
+/// `SkillTarget::status` of a link whose source could not be restored
+pub const BROKEN_STATUS: &str = "broken";
+
+#[derive(Clone, Debug)]
+pub struct BrokenLink {
+    pub skill_id: String,
+    pub target: SkillTarget,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LinkRepairEntryDto {
+    pub skill_id: String,
+    pub skill_name: String,
+    pub tool: String,
+    pub target_path: String,
+    /// Source the link points at again, or why it could not be restored
+    pub detail: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LinkRepairResultDto {
+    /// Link targets checked
+    pub checked: usize,
+    pub repaired: Vec<LinkRepairEntryDto>,
+    /// Targets now recorded as `broken`
+    pub broken: Vec<LinkRepairEntryDto>,
+}
+
+/// A symlink or junction at `path` whose target is missing
+pub fn is_broken_link(path: &Path) -> bool {
+    std::fs::read_link(path).is_ok() && !path.exists()
+}
+
+/// Broken link targets of `skills`, and how many link targets were checked
+pub fn find_broken_links(skills: &[Skill]) -> (usize, Vec<BrokenLink>) {
+    let mut checked = 0;
+    let mut broken = Vec::new();
+    for skill in skills {
+        for target in parse_sync_details(skill) {
+            if !matches!(target.mode.as_str(), "symlink" | "junction")
+                || runtime_location::parse_wsl_unc_path(&target.target_path).is_some()
+            {
+                continue;
+            }
+            checked += 1;
+            if is_broken_link(Path::new(&target.target_path)) {
+                broken.push(BrokenLink {
+                    skill_id: skill.id.clone(),
+                    target,
+                });
+            }
+        }
+    }
+    (checked, broken)
+}
+
+/// Remove the broken link at `path` so it can be created again; the link
+/// itself is removed, never what it pointed at
+pub fn remove_broken_link(path: &Path) -> Result<()> {
+    // A directory junction or symlink on Windows is removed as a directory
+    #[cfg(windows)]
+    if std::fs::remove_dir(path).is_ok() {
+        return Ok(());
+    }
+    std::fs::remove_file(path).with_context(|| format!("remove broken link {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use serde_json::json;
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_only_link_targets_whose_source_is_gone() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let tools = temp.path().join("tools");
+        std::fs::create_dir_all(central.join("kept")).unwrap();
+        std::fs::create_dir_all(central.join("moved")).unwrap();
+        std::fs::create_dir_all(tools.join("copy")).unwrap();
+        for name in ["kept", "moved"] {
+            std::os::unix::fs::symlink(central.join(name), tools.join(name)).unwrap();
+        }
+        std::fs::remove_dir_all(central.join("moved")).unwrap();
+
+        let target = |name: &str, mode: &str| {
+            json!({
+                "target_path": tools.join(name).to_string_lossy(),
+                "mode": mode,
+                "status": "ok",
+            })
+        };
+        let skill = from_db_skill(json!({
+            "id": "skill-1",
+            "name": "review",
+            "sync_details": {
+                "claude_code": target("kept", "symlink"),
+                "codex": target("moved", "symlink"),
+                "cursor": target("copy", "copy"),
+            },
+        }));
+
+        let (checked, broken) = find_broken_links(std::slice::from_ref(&skill));
+        assert_eq!(checked, 2);
+        let tools_found: Vec<&str> = broken
+            .iter()
+            .map(|link| link.target.tool.as_str())
+            .collect();
+        assert_eq!(tools_found, vec!["codex"]);
+
+        remove_broken_link(&tools.join("moved")).unwrap();
+        assert!(std::fs::symlink_metadata(tools.join("moved")).is_err());
+    }
+}
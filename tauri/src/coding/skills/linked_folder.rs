@@ -0,0 +1,139 @@
+//! Skills linked in from folders outside every tool dir
+//!
+//! A tool's skills dir may hold a link into an arbitrary folder, e.g.
+//! `~/.claude/skills/review -> ~/dev/my-skills/review`. Importing such a
+//! variant copies the folder the link resolves to and records it with
+//! `source_type = "linked_folder"` and `source_ref` = that folder, so updating
+//! the skill re-imports from the original rather than from the tool dir.
+//!
+//! Tracked origins (skill ids in `skill_settings:skills.tracked_linked_folders`)
+//! are compared against the skill's `content_hash` to report changes.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::content_hash::hash_dir;
+use super::types::{now_ms, Skill};
+use crate::SqliteDbState;
+
+pub const LINKED_FOLDER_SOURCE_TYPE: &str = "linked_folder";
+
+const TRACKED_SETTINGS_KEY: &str = "tracked_linked_folders";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LinkedFolderStatusDto {
+    pub skill_id: String,
+    pub name: String,
+    pub source_path: String,
+    pub exists: bool,
+    /// Folder content differs from what was last imported
+    pub changed: bool,
+}
+
+/// Folder behind `path` when it is a link (symlink or junction) resolving
+/// outside all `known_roots`; links between tool dirs are not external
+pub fn external_link_source(path: &Path, known_roots: &[PathBuf]) -> Option<PathBuf> {
+    std::fs::read_link(path).ok()?;
+    let target = std::fs::canonicalize(path).ok()?;
+    if !target.is_dir() {
+        return None;
+    }
+    let inside_known = known_roots.iter().any(|root| {
+        let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.clone());
+        target.starts_with(root)
+    });
+    (!inside_known).then_some(target)
+}
+
+pub fn load_tracked(state: &SqliteDbState) -> Vec<String> {
+    read_skill_settings_value_from_sqlite(state, TRACKED_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Start or stop tracking the origin of `skill_id`
+pub fn set_tracked(state: &SqliteDbState, skill_id: &str, tracked: bool) -> Result<(), String> {
+    let mut ids = load_tracked(state);
+    ids.retain(|id| id != skill_id);
+    if tracked {
+        ids.push(skill_id.to_string());
+    }
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            TRACKED_SETTINGS_KEY: ids,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Compare a linked-folder skill with its origin folder
+pub fn linked_folder_status(skill: &Skill) -> Option<LinkedFolderStatusDto> {
+    if skill.source_type != LINKED_FOLDER_SOURCE_TYPE {
+        return None;
+    }
+    let source_path = skill.source_ref.clone()?;
+    let source = Path::new(&source_path);
+    let exists = source.is_dir();
+    let changed = exists && hash_dir(source).ok() != skill.content_hash;
+    Some(LinkedFolderStatusDto {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        source_path,
+        exists,
+        changed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn only_links_leaving_known_roots_are_external() {
+        let temp = tempfile::tempdir().unwrap();
+        let tool_dir = temp.path().join("tool/skills");
+        let other_tool = temp.path().join("other/skills");
+        let personal = temp.path().join("dev/review");
+        for dir in [&tool_dir, &other_tool.join("lint"), &personal] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::os::unix::fs::symlink(&personal, tool_dir.join("review")).unwrap();
+        std::os::unix::fs::symlink(other_tool.join("lint"), tool_dir.join("lint")).unwrap();
+        std::fs::create_dir_all(tool_dir.join("plain")).unwrap();
+
+        let roots = vec![tool_dir.clone(), other_tool.clone()];
+        assert_eq!(
+            external_link_source(&tool_dir.join("review"), &roots),
+            Some(std::fs::canonicalize(&personal).unwrap())
+        );
+        assert_eq!(external_link_source(&tool_dir.join("lint"), &roots), None);
+        assert_eq!(external_link_source(&tool_dir.join("plain"), &roots), None);
+    }
+
+    #[test]
+    fn status_reports_changed_origin() {
+        let temp = tempfile::tempdir().unwrap();
+        let origin = temp.path().join("review");
+        std::fs::create_dir_all(&origin).unwrap();
+        std::fs::write(origin.join("SKILL.md"), "v1").unwrap();
+        let mut skill = crate::coding::skills::adapter::from_db_skill(serde_json::json!({
+            "id": "s1",
+            "name": "review",
+            "source_type": LINKED_FOLDER_SOURCE_TYPE,
+            "source_ref": origin.to_string_lossy(),
+        }));
+        skill.content_hash = hash_dir(&origin).ok();
+        assert!(!linked_folder_status(&skill).unwrap().changed);
+
+        std::fs::write(origin.join("SKILL.md"), "v2").unwrap();
+        let status = linked_folder_status(&skill).unwrap();
+        assert!(status.exists && status.changed);
+
+        skill.source_type = "local".to_string();
+        assert_eq!(linked_folder_status(&skill), None);
+    }
+}
@@ -0,0 +1,345 @@
+//! Per-tool dry run of what a tool loads from its skills dir
+//!
+//! Tools differ in what they pick up: how deep they look for skill
+//! directories, whether they follow linked directories, and what the entry
+//! file must contain. `ToolLoadRules` models one tool's loader and each
+//! tool's adapter (`ToolAdapter::load_rules`) says where it differs from
+//! `STANDARD_RULES`.
+//! `preview_tool_load` walks a skills dir with those rules and reports each
+//! skill the tool loads, with its files, and each entry it passes over, with
+//! the reason, so "why doesn't Codex see my skill" can be answered in-app.
+//! To correct a tool's loader, edit its adapter in `ai_toolbox_core`.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::content_hash::list_files;
+use super::tool_adapters::is_system_skill_dir;
+use crate::coding::tools::builtin_adapter;
+pub use ai_toolbox_core::tools::{ToolLoadRules, STANDARD_RULES};
+
+/// Rules of `tool`'s loader; custom tools load the standard layout
+pub fn rules_for_tool(tool: &str) -> ToolLoadRules {
+    builtin_adapter(tool)
+        .map(|adapter| adapter.load_rules())
+        .unwrap_or(STANDARD_RULES)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LoadedSkillDto {
+    /// Frontmatter `name`, or the directory name without one
+    pub name: String,
+    /// Skill directory relative to the skills dir
+    pub path: String,
+    pub is_link: bool,
+    /// Shipped and managed by the tool itself (e.g. Codex's `.system`)
+    pub system: bool,
+    /// Files relative to the skill directory, entry file first
+    pub files: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkippedEntryDto {
+    /// Entry relative to the skills dir
+    pub path: String,
+    /// `hidden`, `not_a_directory`, `broken_link`, `link_not_followed`,
+    /// `no_entry_file`, `entry_file_case`, `too_deep` or `missing_keys`
+    pub reason: String,
+    /// The misnamed entry file, or the missing frontmatter keys
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ToolLoadPreviewDto {
+    pub tool: String,
+    pub tool_display: String,
+    pub skills_dir: String,
+    pub exists: bool,
+    pub rules: ToolLoadRules,
+    pub loaded: Vec<LoadedSkillDto>,
+    pub skipped: Vec<SkippedEntryDto>,
+}
+
+/// What `tool` loads from `skills_dir` under its `ToolLoadRules`
+pub fn preview_tool_load(tool: &str, tool_display: &str, skills_dir: &Path) -> ToolLoadPreviewDto {
+    let rules = rules_for_tool(tool);
+    let mut preview = ToolLoadPreviewDto {
+        tool: tool.to_string(),
+        tool_display: tool_display.to_string(),
+        skills_dir: skills_dir.to_string_lossy().to_string(),
+        exists: skills_dir.is_dir(),
+        rules,
+        loaded: Vec::new(),
+        skipped: Vec::new(),
+    };
+    if preview.exists {
+        let mut walk = LoadWalk {
+            tool,
+            rules: &rules,
+            root: skills_dir,
+            preview: &mut preview,
+        };
+        walk.scan(skills_dir, 1, false);
+    }
+    preview
+}
+
+struct LoadWalk<'a> {
+    tool: &'a str,
+    rules: &'a ToolLoadRules,
+    root: &'a Path,
+    preview: &'a mut ToolLoadPreviewDto,
+}
+
+impl LoadWalk<'_> {
+    /// Scan the entries of `dir`, `depth` levels below the skills dir.
+    /// Returns how many entries were reported.
+    fn scan(&mut self, dir: &Path, depth: usize, system: bool) -> usize {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return 0;
+        };
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        let reported = self.preview.loaded.len() + self.preview.skipped.len();
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_link = std::fs::read_link(&path).is_ok();
+            if name.starts_with('.') {
+                if depth == 1 && is_system_skill_dir(self.tool, &name) {
+                    self.scan(&path, depth, true);
+                } else if path.is_dir() {
+                    self.skip(&path, "hidden", None);
+                }
+                continue;
+            }
+            if is_link && !path.exists() {
+                self.skip(&path, "broken_link", None);
+                continue;
+            }
+            if !path.is_dir() {
+                // Loose files only matter at the top, where a skill dir is expected
+                if depth == 1 {
+                    self.skip(&path, "not_a_directory", None);
+                }
+                continue;
+            }
+            if is_link && !self.rules.follows_links {
+                self.skip(&path, "link_not_followed", None);
+                continue;
+            }
+            self.check_dir(&path, depth, is_link, system);
+        }
+        self.preview.loaded.len() + self.preview.skipped.len() - reported
+    }
+
+    fn check_dir(&mut self, dir: &Path, depth: usize, is_link: bool, system: bool) {
+        let names: Vec<String> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let entry_file = self.rules.entry_file;
+        if names.iter().any(|name| name == entry_file) {
+            self.load(dir, is_link, system);
+            return;
+        }
+        if let Some(misnamed) = names
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(entry_file))
+        {
+            self.skip(dir, "entry_file_case", Some(misnamed.clone()));
+            return;
+        }
+        if depth < self.rules.max_depth {
+            if self.scan(dir, depth + 1, system) == 0 {
+                self.skip(dir, "no_entry_file", None);
+            }
+            return;
+        }
+        let reason = if has_nested_entry(dir, entry_file) {
+            "too_deep"
+        } else {
+            "no_entry_file"
+        };
+        self.skip(dir, reason, None);
+    }
+
+    fn load(&mut self, dir: &Path, is_link: bool, system: bool) {
+        let entry_file = self.rules.entry_file;
+        let content = std::fs::read_to_string(dir.join(entry_file)).unwrap_or_default();
+        let missing: Vec<&str> = self
+            .rules
+            .required_keys
+            .iter()
+            .copied()
+            .filter(|key| frontmatter_value(&content, key).is_none())
+            .collect();
+        if !missing.is_empty() {
+            self.skip(dir, "missing_keys", Some(missing.join(", ")));
+            return;
+        }
+
+        let mut files = list_files(dir).unwrap_or_default();
+        files.retain(|file| file != entry_file);
+        files.insert(0, entry_file.to_string());
+        let name = frontmatter_value(&content, "name").unwrap_or_else(|| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        self.preview.loaded.push(LoadedSkillDto {
+            name,
+            path: self.relative(dir),
+            is_link,
+            system,
+            files,
+        });
+    }
+
+    fn skip(&mut self, path: &Path, reason: &str, detail: Option<String>) {
+        self.preview.skipped.push(SkippedEntryDto {
+            path: self.relative(path),
+            reason: reason.to_string(),
+            detail,
+        });
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+/// Whether any directory below `dir` has an entry file
+fn has_nested_entry(dir: &Path, entry_file: &str) -> bool {
+    walkdir::WalkDir::new(dir)
+        .min_depth(2)
+        .max_depth(8)
+        .into_iter()
+        .flatten()
+        .any(|entry| entry.file_type().is_file() && entry.file_name() == entry_file)
+}
+
+/// Value of a top-level `key:` in the frontmatter of `content`
+fn frontmatter_value(content: &str, key: &str) -> Option<String> {
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    let prefix = format!("{}:", key);
+    lines
+        .take_while(|line| line.trim() != "---")
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| {
+            value
+                .trim()
+                .trim_matches('"')
+                .trim_matches('\'')
+                .to_string()
+        })
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, entry_file: &str, body: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(entry_file), body).unwrap();
+    }
+
+    const VALID: &str = "---\nname: review\ndescription: Review diffs\n---\n# Review\n";
+
+    fn reasons(preview: &ToolLoadPreviewDto) -> Vec<(&str, &str)> {
+        preview
+            .skipped
+            .iter()
+            .map(|skipped| (skipped.path.as_str(), skipped.reason.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn reports_loaded_skills_and_why_others_are_skipped() {
+        let temp = tempfile::tempdir().unwrap();
+        let skills = temp.path();
+        write_skill(&skills.join("review"), "SKILL.md", VALID);
+        std::fs::write(skills.join("review/checklist.md"), "- tests").unwrap();
+        write_skill(&skills.join("lower"), "skill.md", VALID);
+        write_skill(&skills.join("bare"), "SKILL.md", "# No frontmatter\n");
+        write_skill(&skills.join("group/nested"), "SKILL.md", VALID);
+        write_skill(&skills.join(".hidden"), "SKILL.md", VALID);
+        std::fs::write(skills.join("notes.md"), "loose").unwrap();
+
+        let preview = preview_tool_load("claude_code", "Claude Code", skills);
+        let loaded: Vec<(&str, Vec<String>)> = preview
+            .loaded
+            .iter()
+            .map(|skill| (skill.path.as_str(), skill.files.clone()))
+            .collect();
+        assert_eq!(
+            loaded,
+            vec![(
+                "review",
+                vec!["SKILL.md".to_string(), "checklist.md".to_string()]
+            )]
+        );
+        assert_eq!(
+            reasons(&preview),
+            vec![
+                (".hidden", "hidden"),
+                ("bare", "missing_keys"),
+                ("group", "too_deep"),
+                ("lower", "entry_file_case"),
+                ("notes.md", "not_a_directory"),
+            ]
+        );
+        assert_eq!(
+            preview.skipped[1].detail.as_deref(),
+            Some("name, description")
+        );
+
+        // Codex searches nested directories and loads its system skills
+        write_skill(&skills.join(".system/creator"), "SKILL.md", VALID);
+        let codex = preview_tool_load("codex", "Codex", skills);
+        let loaded: Vec<(&str, bool)> = codex
+            .loaded
+            .iter()
+            .map(|skill| (skill.path.as_str(), skill.system))
+            .collect();
+        assert_eq!(
+            loaded,
+            vec![
+                (".system/creator", true),
+                ("group/nested", false),
+                ("review", false)
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cursor_passes_over_linked_skill_dirs() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central/review");
+        write_skill(&central, "SKILL.md", VALID);
+        let skills = temp.path().join("skills");
+        std::fs::create_dir_all(&skills).unwrap();
+        std::os::unix::fs::symlink(&central, skills.join("review")).unwrap();
+
+        let cursor = preview_tool_load("cursor", "Cursor", &skills);
+        assert!(cursor.loaded.is_empty());
+        assert_eq!(reasons(&cursor), vec![("review", "link_not_followed")]);
+
+        let claude = preview_tool_load("claude_code", "Claude Code", &skills);
+        assert!(claude.loaded[0].is_link);
+    }
+}
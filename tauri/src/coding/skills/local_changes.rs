@@ -0,0 +1,246 @@
+//! Protection for local edits of git-sourced skills
+//!
+//! A git skill is locally modified when its central copy no longer hashes to
+//! the `content_hash` recorded at the last import or update. Updating such a
+//! skill requires an explicit resolution:
+//! - `overwrite`: replace the central copy with upstream (the old behaviour)
+//! - `keep_local`: convert the skill into a fork (`central` source) that no
+//!   longer follows upstream; provenance still records where it came from
+//! - `merge`: three-way merge per file against the installed revision, using
+//!   the git cache as base. Any conflict aborts the update untouched.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::content_hash::{is_ignored_path, list_files};
+use super::git_fetcher;
+use super::installer::GitCheckout;
+use super::types::Skill;
+use super::update_preview::central_copy_modified;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalChangesResolution {
+    Overwrite,
+    KeepLocal,
+    Merge,
+}
+
+/// Files merged or kept while applying an update with `Merge`
+#[derive(Debug, Default)]
+pub struct MergeOutcome {
+    /// Changed on both sides and merged cleanly
+    pub merged: Vec<String>,
+    /// Changed only locally; the local version was kept
+    pub kept_local: Vec<String>,
+}
+
+/// Whether a git skill's central copy was edited since it was installed
+pub fn is_locally_modified(skill: &Skill, central_path: &Path) -> bool {
+    skill.source_type == "git" && central_copy_modified(central_path, skill.content_hash.as_deref())
+}
+
+/// Record of `skill` turned into a fork of its current central content
+pub fn fork_record(skill: &Skill, content_hash: Option<String>, now: i64) -> Skill {
+    Skill {
+        source_type: "central".to_string(),
+        source_ref: None,
+        source_revision: None,
+        content_hash,
+        updated_at: now,
+        ..skill.clone()
+    }
+}
+
+fn blob_ids(repo_dir: &Path, root: &Path) -> Result<BTreeMap<String, String>> {
+    let files = list_files(root)?;
+    let paths = files
+        .iter()
+        .map(|file| root.join(file))
+        .collect::<Vec<PathBuf>>();
+    Ok(files
+        .into_iter()
+        .zip(git_fetcher::hash_blob_ids(repo_dir, &paths)?)
+        .collect())
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+    std::fs::copy(from, to).with_context(|| format!("copy {:?} -> {:?}", from, to))?;
+    Ok(())
+}
+
+/// Merge the local edits of `central_path` into the staged upstream content.
+///
+/// `staging_dir` holds upstream on entry and the merged result on success.
+/// Fails with `MERGE_BASE_UNAVAILABLE|<rev>` when the installed revision is
+/// not in the git cache, or `MERGE_CONFLICT|<paths>` when any file conflicts;
+/// the caller discards the staging dir in both cases.
+pub fn merge_into_staging(
+    checkout: &GitCheckout,
+    base_revision: Option<&str>,
+    central_path: &Path,
+    staging_dir: &Path,
+) -> Result<MergeOutcome> {
+    let repo_dir = &checkout.repo_dir;
+    let base_revision = base_revision.unwrap_or_default();
+    if base_revision.is_empty() || !git_fetcher::has_commit(repo_dir, base_revision) {
+        anyhow::bail!("MERGE_BASE_UNAVAILABLE|{}", base_revision);
+    }
+    let base = git_fetcher::tree_blob_ids(repo_dir, base_revision, checkout.subpath.as_deref())?
+        .into_iter()
+        .filter(|(path, _)| !is_ignored_path(path))
+        .collect::<BTreeMap<_, _>>();
+    let ours = blob_ids(repo_dir, central_path)?;
+    let theirs = blob_ids(repo_dir, staging_dir)?;
+    let paths = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+    let base_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let mut outcome = MergeOutcome::default();
+    let mut conflicts = Vec::new();
+    for path in paths {
+        let (b, o, t) = (base.get(&path), ours.get(&path), theirs.get(&path));
+        if o == b || o == t {
+            // No local change, or both sides made the same change
+            continue;
+        }
+        let staged = staging_dir.join(&path);
+        if t == b {
+            // Only changed locally: keep the local version (or deletion)
+            match o {
+                Some(_) => copy_file(&central_path.join(&path), &staged)?,
+                None => {
+                    std::fs::remove_file(&staged).with_context(|| format!("remove {:?}", staged))?
+                }
+            }
+            outcome.kept_local.push(path);
+            continue;
+        }
+        let (Some(base_oid), Some(_), Some(_)) = (b, o, t) else {
+            // Added on both sides, or deleted on one side and edited on the other
+            conflicts.push(path);
+            continue;
+        };
+        let base_file = base_dir.path().join("base");
+        std::fs::write(&base_file, git_fetcher::read_blob(repo_dir, base_oid)?)
+            .with_context(|| format!("write base of {}", path))?;
+        let (merged, conflicted) =
+            git_fetcher::merge_file(&central_path.join(&path), &base_file, &staged)?;
+        if conflicted {
+            conflicts.push(path);
+            continue;
+        }
+        std::fs::write(&staged, merged).with_context(|| format!("write {:?}", staged))?;
+        outcome.merged.push(path);
+    }
+
+    if !conflicts.is_empty() {
+        anyhow::bail!("MERGE_CONFLICT|{}", conflicts.join(","));
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let out = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args([
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn merge_keeps_local_edits_and_reports_conflicts() {
+        if std::process::Command::new("git")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path().join("repo");
+        write(&repo, "SKILL.md", "title\n\none\ntwo\nthree\n");
+        write(&repo, "local.txt", "base");
+        write(&repo, "both.txt", "base");
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["add", "-A"]);
+        git(&repo, &["commit", "-qm", "initial"]);
+        let installed = git(&repo, &["rev-parse", "HEAD"]);
+
+        let central = temp.path().join("central");
+        write(&central, "SKILL.md", "title\n\none\ntwo\nthree local\n");
+        write(&central, "local.txt", "local only");
+        write(&central, "both.txt", "local");
+        write(&central, "notes.md", "added locally");
+        let upstream = |dir: &Path| {
+            write(dir, "SKILL.md", "title upstream\n\none\ntwo\nthree\n");
+            write(dir, "local.txt", "base");
+            write(dir, "both.txt", "upstream");
+        };
+        let checkout = GitCheckout {
+            repo_dir: repo.clone(),
+            subpath: None,
+        };
+
+        let staging = temp.path().join("staging");
+        upstream(&staging);
+        let err = merge_into_staging(&checkout, Some(&installed), &central, &staging)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "MERGE_CONFLICT|both.txt");
+
+        // Resolve the conflicting file locally, then merge again
+        write(&central, "both.txt", "base");
+        let staging = temp.path().join("staging2");
+        upstream(&staging);
+        let outcome = merge_into_staging(&checkout, Some(&installed), &central, &staging).unwrap();
+        assert_eq!(outcome.merged, vec!["SKILL.md"]);
+        assert_eq!(outcome.kept_local, vec!["local.txt", "notes.md"]);
+        assert_eq!(
+            std::fs::read_to_string(staging.join("SKILL.md")).unwrap(),
+            "title upstream\n\none\ntwo\nthree local\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(staging.join("both.txt")).unwrap(),
+            "upstream"
+        );
+
+        let missing = merge_into_staging(&checkout, None, &central, &staging).unwrap_err();
+        assert!(missing.to_string().starts_with("MERGE_BASE_UNAVAILABLE|"));
+    }
+}
@@ -0,0 +1,259 @@
+//! Localized display names and descriptions
+//!
+//! Skills from Chinese and English repos end up in one list, so each skill
+//! can carry a display name and description per UI language
+//! (`Skill.localizations`, keyed `zh-CN` / `en-US`). They are only shown in
+//! the app: the directory name stays the identity used for syncing, tool
+//! targets and collisions. Users edit them per skill; common skills can be
+//! filled from a small built-in dictionary, which never overwrites text the
+//! user has entered.
+
+use std::collections::BTreeMap;
+
+use super::types::SkillLocalization;
+
+pub const SUPPORTED_LOCALES: &[&str] = &["zh-CN", "en-US"];
+const MAX_DISPLAY_NAME_CHARS: usize = 80;
+const MAX_DESCRIPTION_CHARS: usize = 1000;
+
+/// Skill name, English display name, Chinese display name, Chinese description
+const DICTIONARY: &[(&str, &str, &str, &str)] = &[
+    (
+        "algorithmic-art",
+        "Algorithmic Art",
+        "算法艺术",
+        "用 p5.js 编写带随机种子的生成艺术作品",
+    ),
+    (
+        "brand-guidelines",
+        "Brand Guidelines",
+        "品牌规范",
+        "按品牌的配色与字体规范调整产出物的样式",
+    ),
+    (
+        "canvas-design",
+        "Canvas Design",
+        "画布设计",
+        "按设计理念创作 PNG / PDF 格式的海报与视觉作品",
+    ),
+    (
+        "doc-coauthoring",
+        "Doc Co-authoring",
+        "文档协作撰写",
+        "按结构化流程与用户共同撰写文档、提案和技术方案",
+    ),
+    (
+        "docx",
+        "Word Documents",
+        "Word 文档",
+        "创建、编辑和分析 .docx 文档，支持修订与批注",
+    ),
+    (
+        "frontend-design",
+        "Frontend Design",
+        "前端设计",
+        "制作有设计感、可直接上线的前端页面与组件",
+    ),
+    (
+        "internal-comms",
+        "Internal Communications",
+        "内部沟通",
+        "按公司常用格式撰写周报、通知、FAQ 等内部沟通材料",
+    ),
+    (
+        "mcp-builder",
+        "MCP Server Builder",
+        "MCP 服务器构建",
+        "设计并实现高质量的 MCP 服务器，让模型调用外部服务",
+    ),
+    (
+        "pdf",
+        "PDF",
+        "PDF 处理",
+        "提取 PDF 文本与表格，创建、合并、拆分 PDF 并填写表单",
+    ),
+    (
+        "pptx",
+        "PowerPoint Presentations",
+        "PowerPoint 演示文稿",
+        "创建、编辑和分析 .pptx 演示文稿的版式与内容",
+    ),
+    (
+        "skill-creator",
+        "Skill Creator",
+        "Skill 创建向导",
+        "指导创建和改进 Skill，包括结构、说明与评估",
+    ),
+    (
+        "slack-gif-creator",
+        "Slack GIF Creator",
+        "Slack 动图制作",
+        "制作符合 Slack 尺寸与大小限制的 GIF 动图",
+    ),
+    (
+        "theme-factory",
+        "Theme Factory",
+        "主题工厂",
+        "为幻灯片、文档和网页套用预设或自定义的配色字体主题",
+    ),
+    (
+        "web-artifacts-builder",
+        "Web Artifacts Builder",
+        "网页 Artifact 构建",
+        "用 React、Tailwind 和 shadcn/ui 构建多组件的网页 Artifact",
+    ),
+    (
+        "webapp-testing",
+        "Web App Testing",
+        "网页应用测试",
+        "用 Playwright 测试本地网页应用，截图并查看浏览器日志",
+    ),
+    (
+        "xlsx",
+        "Excel Spreadsheets",
+        "Excel 表格",
+        "创建、编辑和分析 .xlsx 表格，支持公式、格式与图表",
+    ),
+];
+
+fn normalize_text(
+    value: Option<&str>,
+    max_chars: usize,
+    field: &str,
+) -> Result<Option<String>, String> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    if value.chars().count() > max_chars {
+        return Err(format!(
+            "Localized {} is longer than {} characters",
+            field, max_chars
+        ));
+    }
+    Ok(Some(value.to_string()))
+}
+
+/// Trimmed localizations without empty entries; errors on unknown locales
+/// and overlong text
+pub fn normalize_localizations(
+    localizations: BTreeMap<String, SkillLocalization>,
+) -> Result<BTreeMap<String, SkillLocalization>, String> {
+    let mut normalized = BTreeMap::new();
+    for (locale, localization) in localizations {
+        let locale = locale.trim();
+        if !SUPPORTED_LOCALES.contains(&locale) {
+            return Err(format!("Unsupported locale: {}", locale));
+        }
+        let localization = SkillLocalization {
+            display_name: normalize_text(
+                localization.display_name.as_deref(),
+                MAX_DISPLAY_NAME_CHARS,
+                "display name",
+            )?,
+            description: normalize_text(
+                localization.description.as_deref(),
+                MAX_DESCRIPTION_CHARS,
+                "description",
+            )?,
+        };
+        if localization != SkillLocalization::default() {
+            normalized.insert(locale.to_string(), localization);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Dictionary localizations for a skill name, if it is a known skill
+pub fn dictionary_localizations(skill_name: &str) -> BTreeMap<String, SkillLocalization> {
+    let key = skill_name.trim().to_lowercase().replace(['_', ' '], "-");
+    let Some((_, en_name, zh_name, zh_description)) =
+        DICTIONARY.iter().find(|(name, ..)| *name == key)
+    else {
+        return BTreeMap::new();
+    };
+    BTreeMap::from([
+        (
+            "en-US".to_string(),
+            SkillLocalization {
+                display_name: Some(en_name.to_string()),
+                description: None,
+            },
+        ),
+        (
+            "zh-CN".to_string(),
+            SkillLocalization {
+                display_name: Some(zh_name.to_string()),
+                description: Some(zh_description.to_string()),
+            },
+        ),
+    ])
+}
+
+/// Fill the fields of `current` that are empty from the dictionary entry of
+/// `skill_name`; returns whether anything was added
+pub fn fill_from_dictionary(
+    skill_name: &str,
+    current: &mut BTreeMap<String, SkillLocalization>,
+) -> bool {
+    let mut changed = false;
+    for (locale, suggested) in dictionary_localizations(skill_name) {
+        let entry = current.entry(locale).or_default();
+        if entry.display_name.is_none() && suggested.display_name.is_some() {
+            entry.display_name = suggested.display_name;
+            changed = true;
+        }
+        if entry.description.is_none() && suggested.description.is_some() {
+            entry.description = suggested.description;
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dictionary_fills_only_missing_fields() {
+        let mut current = BTreeMap::from([(
+            "zh-CN".to_string(),
+            SkillLocalization {
+                display_name: Some("我的 PDF".to_string()),
+                description: None,
+            },
+        )]);
+        assert!(fill_from_dictionary("PDF", &mut current));
+        assert_eq!(current["zh-CN"].display_name.as_deref(), Some("我的 PDF"));
+        assert!(current["zh-CN"].description.is_some());
+        assert_eq!(current["en-US"].display_name.as_deref(), Some("PDF"));
+        assert!(!fill_from_dictionary("pdf", &mut current));
+        assert!(!fill_from_dictionary("my-private-skill", &mut current));
+    }
+
+    #[test]
+    fn normalizes_and_rejects_localizations() {
+        let normalized = normalize_localizations(BTreeMap::from([
+            (
+                "en-US".to_string(),
+                SkillLocalization {
+                    display_name: Some("  Reviewer ".to_string()),
+                    description: Some(" ".to_string()),
+                },
+            ),
+            ("zh-CN".to_string(), SkillLocalization::default()),
+        ]))
+        .unwrap();
+        assert_eq!(normalized.len(), 1);
+        assert_eq!(
+            normalized["en-US"].display_name.as_deref(),
+            Some("Reviewer")
+        );
+        assert_eq!(normalized["en-US"].description, None);
+        assert!(normalize_localizations(BTreeMap::from([(
+            "fr-FR".to_string(),
+            SkillLocalization::default()
+        )]))
+        .is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Commands for repairing the library after it got out of step: stale tool
+//! links, lost database, dangling links (`artifact_sweeper.rs`,
+//! `target_marker.rs`, `db_rebuild.rs`, `load_preview.rs`)
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::artifact_sweeper::{self, StaleArtifactReportDto, SweepResultDto};
+use super::central_repo::resolve_central_repo_path;
+use super::commands::{adopt_detected_central_skill, format_error, scan_central_dir};
+use super::db_rebuild::{self, DatabaseRebuildDto};
+use super::load_preview::{self, ToolLoadPreviewDto};
+use super::skill_store;
+use super::sync_engine::remove_path;
+use super::target_marker::{self, MarkerRecoveryDto};
+use super::tool_adapters::{resolve_runtime_skills_path_with_state_async, runtime_adapter_by_key};
+use super::variants;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+async fn build_stale_artifact_report<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+) -> Result<StaleArtifactReportDto, String> {
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    let skills = skill_store::get_managed_skills(state).await?;
+    let owned_roots = vec![
+        resolve_central_repo_path(app, state)
+            .await
+            .map_err(format_error)?,
+        variants::variants_root(app)?,
+    ];
+    let report =
+        artifact_sweeper::build_stale_artifact_report(state, &custom_tools, &skills, &owned_roots)
+            .await;
+    Ok(report)
+}
+
+/// Find app-created links left behind in skills dirs of removed/moved tools.
+#[tauri::command]
+pub async fn skills_scan_stale_tool_artifacts<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<StaleArtifactReportDto, String> {
+    build_stale_artifact_report(&app, &state).await
+}
+
+/// Remove the given stale links. Paths are re-validated against a fresh scan,
+/// so only links that are still stale and app-owned are touched.
+#[tauri::command]
+pub async fn skills_sweep_stale_tool_artifacts<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    paths: Vec<String>,
+) -> Result<SweepResultDto, String> {
+    ensure_writable("skills_sweep_stale_tool_artifacts")?;
+    let report = build_stale_artifact_report(&app, &state).await?;
+    let mut result = SweepResultDto::default();
+    for path in paths {
+        let Some(artifact) = report
+            .artifacts
+            .iter()
+            .find(|artifact| artifact.path == path)
+        else {
+            result.skipped.push(path);
+            continue;
+        };
+        if let Err(err) = remove_path(&artifact.path) {
+            log::warn!("[skills] failed to sweep {}: {}", artifact.path, err);
+            result.skipped.push(path);
+            continue;
+        }
+        if let Some(skill_id) = &artifact.skill_id {
+            skill_store::delete_skill_target(&state, skill_id, &artifact.tool).await?;
+        }
+        result.removed.push(path);
+    }
+    Ok(result)
+}
+
+/// Rebuild missing copy-target records from the `.ai-toolbox.meta` markers in
+/// the tools' skills dirs. Targets already recorded are left alone.
+#[tauri::command]
+pub async fn skills_recover_targets_from_markers<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<MarkerRecoveryDto, String> {
+    ensure_writable("skills_recover_targets_from_markers")?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let dirs = target_marker::marker_dirs(&state, &custom_tools).await;
+    let (records, report) = target_marker::plan_recovery(&skills, &dirs);
+    for (skill_id, target) in &records {
+        skill_store::upsert_skill_target(&state, skill_id, target).await?;
+    }
+    if !records.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(report)
+}
+
+/// Recreate skill records from the central repo and their targets from the
+/// links and marked copies in the tools' skills dirs, e.g. after the database
+/// file was lost. Existing records are left as they are.
+#[tauri::command]
+pub async fn skills_rebuild_database<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<DatabaseRebuildDto, String> {
+    ensure_writable("skills_rebuild_database")?;
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(|e| format_error(e))?;
+    let (detected, _, _) = scan_central_dir(&central_dir)?;
+    let mut result = DatabaseRebuildDto::default();
+    for skill in detected {
+        match adopt_detected_central_skill(&state, &central_dir, &skill.relative_path).await {
+            Ok(true) => result.adopted.push(skill.relative_path),
+            Ok(false) => result.skipped.push(skill.relative_path),
+            Err(err) => {
+                log::warn!("[skills] rebuild skipped {}: {}", skill.relative_path, err);
+                result.skipped.push(skill.relative_path);
+            }
+        }
+    }
+
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let dirs = target_marker::marker_dirs(&state, &custom_tools).await;
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let (records, recovered) = db_rebuild::plan_link_targets(&skills, &central_dir, &dirs);
+    for (skill_id, target) in &records {
+        skill_store::upsert_skill_target(&state, skill_id, target).await?;
+    }
+    result.recovered_targets = recovered;
+
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let (records, report) = target_marker::plan_recovery(&skills, &dirs);
+    for (skill_id, target) in &records {
+        skill_store::upsert_skill_target(&state, skill_id, target).await?;
+    }
+    result.recovered_targets.extend(report.recovered);
+    result.unmatched_markers = report.unmatched;
+
+    if !result.adopted.is_empty() || !result.recovered_targets.is_empty() {
+        let _ = app.emit("skills-changed", "window");
+    }
+    Ok(result)
+}
+
+/// Remove every broken link in a tool's skills dir; returns the removed paths.
+#[tauri::command]
+pub async fn skills_clean_dangling_links(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<Vec<String>, String> {
+    ensure_writable("skills_clean_dangling_links")?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let adapter = runtime_adapter_by_key(&tool, &custom_tools)
+        .ok_or_else(|| format!("Unknown tool: {}", tool))?;
+    let skills_dir = resolve_runtime_skills_path_with_state_async(state.db(), &adapter)
+        .await
+        .map_err(format_error)?;
+
+    let mut removed = Vec::new();
+    for path in artifact_sweeper::find_dangling_links(&skills_dir) {
+        let path = path.to_string_lossy().to_string();
+        remove_path(&path)?;
+        removed.push(path);
+    }
+    Ok(removed)
+}
+
+/// Dry run of what a tool loads from its skills dir under its loading rules,
+/// with the reason for every entry it passes over
+#[tauri::command]
+pub async fn skills_preview_tool_load(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<ToolLoadPreviewDto, String> {
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let adapter = runtime_adapter_by_key(&tool, &custom_tools)
+        .ok_or_else(|| format!("Unknown tool: {}", tool))?;
+    let skills_dir = resolve_runtime_skills_path_with_state_async(state.db(), &adapter)
+        .await
+        .map_err(format_error)?;
+    tokio::task::spawn_blocking(move || {
+        load_preview::preview_tool_load(&adapter.key, &adapter.display_name, &skills_dir)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))
+}
@@ -0,0 +1,184 @@
+//! Commands for per-skill metadata: compatibility, localized names, write
+//! protection and tags
+
+use std::collections::{BTreeMap, HashSet};
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::archive::ARCHIVED_STATUS;
+use super::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
+use super::commands::{format_error, resolve_skill_source_path};
+use super::compatibility::{self, SkillCompatibilityDto};
+use super::localization;
+use super::skill_store;
+use super::tags::{self, SkillTagCountDto, SkillTagSuggestionsDto};
+use super::types::SkillLocalization;
+use super::write_protect;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Check a skill's tool version constraints against the installed tools
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_check_compatibility<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<Vec<SkillCompatibilityDto>, String> {
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    Ok(compatibility::check_all(&skill, &source_path).await)
+}
+
+/// Set the user's tool version constraints of a skill; empty constraints are dropped
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_compatibility(
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    compatibility: BTreeMap<String, String>,
+) -> Result<(), String> {
+    ensure_writable("skills_set_compatibility")?;
+    let mut constraints = BTreeMap::new();
+    for (tool, constraint) in compatibility {
+        let constraint = constraint.trim();
+        if constraint.is_empty() {
+            continue;
+        }
+        if !compatibility::is_valid_constraint(constraint) {
+            return Err(format!(
+                "Invalid version constraint for {}: {}",
+                tool, constraint
+            ));
+        }
+        constraints.insert(
+            crate::coding::tools::canonical_tool_key(tool.trim()).to_string(),
+            constraint.to_string(),
+        );
+    }
+    skill_store::update_skill_compatibility(&state, &skillId, constraints).await
+}
+
+/// Set the localized display names and descriptions of a skill, by UI language
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_localizations(
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    localizations: BTreeMap<String, SkillLocalization>,
+) -> Result<(), String> {
+    ensure_writable("skills_set_localizations")?;
+    let localizations = localization::normalize_localizations(localizations)?;
+    skill_store::update_skill_localizations(&state, &skillId, localizations).await
+}
+
+/// Make the skill's central directory read-only, or writable again. Updates
+/// through the app lift the protection while they write.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_write_protected<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    enabled: bool,
+) -> Result<(), String> {
+    ensure_writable("skills_set_write_protected")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    // A linked folder belongs to the user, not to the central repo
+    if skill.source_type == "linked_folder" {
+        return Err(format!("LINKED_FOLDER|{}", skill.name));
+    }
+    if skill.status != ARCHIVED_STATUS {
+        let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+        if source_path.is_dir() {
+            write_protect::set_read_only(&source_path, enabled).map_err(format_error)?;
+        }
+    }
+    skill_store::update_skill_write_protected(&state, &skillId, enabled).await?;
+    let _ = app.emit("skills-changed", "window");
+    Ok(())
+}
+
+/// Built-in dictionary localizations for a skill name, empty when unknown
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_get_dictionary_localizations(
+    skillName: String,
+) -> Result<BTreeMap<String, SkillLocalization>, String> {
+    Ok(localization::dictionary_localizations(&skillName))
+}
+
+/// Fill empty localizations from the built-in dictionary for the given
+/// skills, or every skill when `skillIds` is empty; returns the number changed
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_fill_localizations(
+    state: State<'_, SqliteDbState>,
+    skillIds: Vec<String>,
+) -> Result<usize, String> {
+    ensure_writable("skills_fill_localizations")?;
+    skill_store::fill_skills_localizations(&state, &skillIds).await
+}
+
+/// Add and/or remove tags on the selected skills; returns how many changed
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_batch_update_tags(
+    state: State<'_, SqliteDbState>,
+    skillIds: Vec<String>,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<usize, String> {
+    ensure_writable("skills_batch_update_tags")?;
+    skill_store::update_skills_tags(&state, &skillIds, &add, &remove).await
+}
+
+/// All tags in the library with their skill counts
+#[tauri::command]
+pub async fn skills_get_all_tags(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<SkillTagCountDto>, String> {
+    let skills = skill_store::get_managed_skills(&state).await?;
+    Ok(tags::library_tag_counts(&skills))
+}
+
+/// Tag suggestions from each skill's SKILL.md (library tags, tool mentions, keywords)
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_suggest_tags<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillIds: Vec<String>,
+) -> Result<Vec<SkillTagSuggestionsDto>, String> {
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let library_tags: Vec<String> = tags::library_tag_counts(&skills)
+        .into_iter()
+        .map(|entry| entry.tag)
+        .collect();
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(format_error)?;
+
+    let wanted: HashSet<&str> = skillIds.iter().map(String::as_str).collect();
+    let mut result = Vec::new();
+    for skill in skills
+        .iter()
+        .filter(|skill| wanted.contains(skill.id.as_str()))
+    {
+        let skill_md =
+            resolve_skill_central_path(&skill.central_path, &central_dir).join("SKILL.md");
+        // A missing SKILL.md still gets library-tag / name based suggestions
+        let content = std::fs::read_to_string(&skill_md)
+            .unwrap_or_else(|_| format!("---\nname: {}\n---\n", skill.name));
+        result.push(SkillTagSuggestionsDto {
+            skill_id: skill.id.clone(),
+            skill_name: skill.name.clone(),
+            suggestions: tags::suggest_tags(&content, &library_tags, &skill.tags),
+        });
+    }
+    Ok(result)
+}
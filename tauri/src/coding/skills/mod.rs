@@ -2,19 +2,102 @@
 // Unified management for AI coding tool skills
 
 pub mod adapter;
+pub mod adopt_commands;
+pub mod adopt_policy;
+pub mod adopt_simulation;
+pub mod archive;
+pub mod archive_commands;
+pub mod archive_source;
+pub mod artifact_sweeper;
+pub mod audit;
+pub mod audit_commands;
+pub mod auto_adopt;
+pub mod auto_sync;
+pub mod auto_sync_commands;
 pub mod cache_cleanup;
 pub mod central_repo;
+pub mod central_watcher;
+pub mod collection_commands;
+pub mod collections;
+pub mod collision_policy;
 pub mod commands;
-pub mod content_hash;
+pub mod compatibility;
+pub mod confirmation;
+pub mod conflict_overview;
+pub mod consolidate;
+pub mod consolidate_commands;
+pub mod context_budget;
+pub mod db_rebuild;
+pub mod default_set_commands;
+pub mod default_sets;
+pub mod duplicate_content;
+pub mod external_change_commands;
+pub mod external_changes;
+pub mod file_skill_commands;
+pub mod file_skills;
 pub mod git_fetcher;
 pub mod installer;
+pub mod link_repair;
+pub mod linked_folder;
+pub mod load_preview;
+pub mod local_changes;
+pub mod localization;
+pub mod maintenance_commands;
+pub mod metadata_commands;
 pub mod onboarding;
 pub mod path_executor;
+pub mod path_rule_commands;
+pub mod plan_export;
+pub mod project_vars;
+pub mod provenance;
+pub mod quality;
+pub mod quality_commands;
+pub mod query_commands;
+pub mod rotation;
+pub mod rotation_commands;
+pub mod skill_ignore;
+pub mod skill_query;
 pub mod skill_store;
-pub mod sync_engine;
+pub mod source_mirror;
+pub mod startup_check;
+pub mod storage_report;
+pub mod sync_context;
+pub mod tags;
+pub mod target_guard;
+pub mod target_marker;
+pub mod target_pull;
+pub mod team_mirror;
+pub mod team_mirror_commands;
 pub mod tool_adapters;
+pub mod tool_watcher;
+pub mod trash_commands;
 pub mod tray_support;
 pub mod types;
+pub mod update_preview;
+pub mod variant_commands;
+pub mod variants;
 
+pub use adopt_commands::*;
+pub use ai_toolbox_core::skills::{
+    content_hash, name_collision, path_rules, sync_engine, sync_journal, target_conflict,
+    unicode_names, write_protect,
+};
+pub use archive_commands::*;
+pub use audit_commands::*;
+pub use auto_sync_commands::*;
+pub use collection_commands::*;
 pub use commands::*;
+pub use consolidate_commands::*;
+pub use default_set_commands::*;
+pub use external_change_commands::*;
+pub use file_skill_commands::*;
+pub use maintenance_commands::*;
+pub use metadata_commands::*;
+pub use path_rule_commands::*;
+pub use quality_commands::*;
+pub use query_commands::*;
+pub use rotation_commands::*;
+pub use team_mirror_commands::*;
+pub use trash_commands::*;
 pub use types::*;
+pub use variant_commands::*;
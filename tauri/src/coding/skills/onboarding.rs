@@ -1,14 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 
-use super::central_repo::resolve_central_repo_path;
-use super::content_hash::hash_dir;
+use super::central_repo::{
+    merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite, resolve_central_repo_path,
+};
+use super::content_hash::{dir_signature, hash_dir};
+use super::file_skills;
+use super::linked_folder::external_link_source;
+use super::provenance::frontmatter_value;
 use super::skill_store;
-use super::tool_adapters::{get_all_tool_adapters, RuntimeToolAdapter};
-use super::types::{OnboardingGroup, OnboardingPlan, OnboardingVariant};
+use super::sync_engine::resolve_link_target;
+use super::tool_adapters::{
+    get_all_tool_adapters, is_system_skill_dir, system_skill_dirs, RuntimeToolAdapter,
+};
+use super::types::{now_ms, OnboardingGroup, OnboardingPlan, OnboardingVariant, SystemSkill};
+use super::unicode_names::nfc_name;
 use crate::coding::tools::claude_plugins::PluginInfo;
+use crate::coding::tools::gemini_extensions::GeminiExtensionInfo;
+use crate::coding::tools::path_utils;
 use crate::SqliteDbState;
 
 /// Extra skill source directories to scan during onboarding discovery.
@@ -17,7 +29,7 @@ use crate::SqliteDbState;
 struct ExtraSkillSource {
     key: &'static str,
     display_name: &'static str,
-    /// Path to the skills directory (supports ~/ and %APPDATA%/ prefixes)
+    /// Path to the skills directory (supports ~/, %APPDATA%/ and env vars)
     skills_dir: &'static str,
 }
 
@@ -27,13 +39,321 @@ const EXTRA_SKILL_SOURCES: &[ExtraSkillSource] = &[ExtraSkillSource {
     skills_dir: "~/.cc-switch/skills",
 }];
 
-/// Build an onboarding plan by scanning installed tools for existing skills
+/// `skills/` directory bundled with an installed Claude Code plugin or
+/// Gemini CLI extension; its skills are copied, never linked
+struct BundledSkillSource {
+    key: String,
+    display_name: String,
+    /// `plugin` or `extension`, as recorded in the scan diagnostics
+    kind: &'static str,
+    skills_dir: std::path::PathBuf,
+}
+
+impl From<&PluginInfo> for BundledSkillSource {
+    fn from(plugin: &PluginInfo) -> Self {
+        Self {
+            key: format!("plugin::{}", plugin.plugin_id),
+            display_name: format!("Plugin: {}", plugin.display_name),
+            kind: "plugin",
+            skills_dir: plugin.install_path.join("skills"),
+        }
+    }
+}
+
+impl From<&GeminiExtensionInfo> for BundledSkillSource {
+    fn from(extension: &GeminiExtensionInfo) -> Self {
+        Self {
+            key: extension.source_key(),
+            display_name: extension.display_name(),
+            kind: "extension",
+            skills_dir: extension.skills_dir(),
+        }
+    }
+}
+
+/// Bundled skill sources of the current user's Claude Code plugins and
+/// Gemini CLI extensions
+async fn load_bundled_sources(state: &SqliteDbState) -> Vec<BundledSkillSource> {
+    let db = state.db();
+    let plugins = crate::coding::tools::claude_plugins::get_installed_plugins(&db).await;
+    let extensions = crate::coding::tools::gemini_extensions::get_installed_extensions(&db).await;
+    plugins
+        .iter()
+        .map(BundledSkillSource::from)
+        .chain(extensions.iter().map(BundledSkillSource::from))
+        .collect()
+}
+
+/// Variants seen by the last scan, stored in `skill_settings:skills.onboarding_scan_index`
+const SCAN_INDEX_SETTINGS_KEY: &str = "onboarding_scan_index";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OnboardingScanIndex {
+    pub scanned_at: i64,
+    /// Variant path -> what the scan found there
+    pub entries: BTreeMap<String, OnboardingScanEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OnboardingScanEntry {
+    /// `dir_signature` of the variant directory
+    pub signature: String,
+    pub fingerprint: Option<String>,
+}
+
+/// Fingerprints of the unfiltered scan behind `conflict_overview`, kept apart
+/// from the onboarding index so managed targets don't show up as new there
+const CONFLICT_INDEX_SETTINGS_KEY: &str = "conflict_scan_index";
+
+pub fn load_scan_index(state: &SqliteDbState) -> Option<OnboardingScanIndex> {
+    load_index(state, SCAN_INDEX_SETTINGS_KEY)
+}
+
+pub fn save_scan_index(state: &SqliteDbState, index: &OnboardingScanIndex) -> Result<(), String> {
+    save_index(state, SCAN_INDEX_SETTINGS_KEY, index)
+}
+
+fn load_index(state: &SqliteDbState, key: &str) -> Option<OnboardingScanIndex> {
+    read_skill_settings_value_from_sqlite(state, key)
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+fn save_index(state: &SqliteDbState, key: &str, index: &OnboardingScanIndex) -> Result<(), String> {
+    let value = serde_json::to_value(index)
+        .map_err(|e| format!("Failed to serialize onboarding scan index: {}", e))?;
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            key: value,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Source order for choosing a group's canonical variant, stored in
+/// `skill_settings:skills.onboarding_source_priority`
+const SOURCE_PRIORITY_SETTINGS_KEY: &str = "onboarding_source_priority";
+
+/// Third-party central stores first, then Claude Code; other sources keep scan order
+pub const DEFAULT_SOURCE_PRIORITY: &[&str] = &["cc_switch", "claude_code"];
+
+/// Saved source priority, or the default when none is saved
+pub fn load_source_priority(state: &SqliteDbState) -> Vec<String> {
+    read_skill_settings_value_from_sqlite(state, SOURCE_PRIORITY_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_else(|| {
+            DEFAULT_SOURCE_PRIORITY
+                .iter()
+                .map(|key| key.to_string())
+                .collect()
+        })
+}
+
+/// Save the source priority; an empty list restores the default
+pub fn save_source_priority(state: &SqliteDbState, priority: &[String]) -> Result<(), String> {
+    let mut seen = BTreeSet::new();
+    let priority: Vec<&str> = priority
+        .iter()
+        .map(|key| key.trim())
+        .filter(|key| !key.is_empty() && seen.insert(*key))
+        .collect();
+    let value = if priority.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::json!(priority)
+    };
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            SOURCE_PRIORITY_SETTINGS_KEY: value,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Position of `tool` in `priority`; `plugin::*` style entries match by prefix
+fn source_rank(priority: &[String], tool: &str) -> usize {
+    priority
+        .iter()
+        .position(|entry| match entry.strip_suffix('*') {
+            Some(prefix) => tool.starts_with(prefix),
+            None => entry == tool,
+        })
+        .unwrap_or(priority.len())
+}
+
+/// Order each group's variants by source priority, real directories before
+/// links within one source. The first variant is the group's canonical
+/// import source, used when its fingerprints are identical.
+pub fn sort_variants_by_priority(plan: &mut OnboardingPlan, priority: &[String]) {
+    for group in &mut plan.groups {
+        group
+            .variants
+            .sort_by_key(|variant| (source_rank(priority, &variant.tool), variant.is_link));
+    }
+}
+
+/// Fingerprints for one scan. With `differential`, a variant whose directory
+/// signature matches the previous scan reuses its fingerprint instead of
+/// being hashed again.
+struct ScanFingerprints<'a> {
+    previous: Option<&'a OnboardingScanIndex>,
+    differential: bool,
+    next: OnboardingScanIndex,
+    new_names: BTreeSet<String>,
+    rehashed: usize,
+}
+
+impl<'a> ScanFingerprints<'a> {
+    fn new(previous: Option<&'a OnboardingScanIndex>, differential: bool) -> Self {
+        Self {
+            previous,
+            differential,
+            next: OnboardingScanIndex {
+                scanned_at: now_ms(),
+                entries: BTreeMap::new(),
+            },
+            new_names: BTreeSet::new(),
+            rehashed: 0,
+        }
+    }
+
+    fn fingerprint(&mut self, name: &str, path: &Path) -> Option<String> {
+        let key = path.to_string_lossy().to_string();
+        let signature = dir_signature(path).ok();
+        let previous = self.previous.and_then(|index| index.entries.get(&key));
+        if self.previous.is_some() && previous.is_none() {
+            self.new_names.insert(name.to_string());
+        }
+
+        let reused = previous.filter(|entry| {
+            self.differential && signature.as_deref() == Some(entry.signature.as_str())
+        });
+        let fingerprint = match reused {
+            Some(entry) => entry.fingerprint.clone(),
+            None => {
+                self.rehashed += 1;
+                hash_dir(path).ok()
+            }
+        };
+        self.next.entries.insert(
+            key,
+            OnboardingScanEntry {
+                signature: signature.unwrap_or_default(),
+                fingerprint: fingerprint.clone(),
+            },
+        );
+        fingerprint
+    }
+}
+
+/// Where the scan looked and what it dropped, for exported bug reports
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ScanDiagnostics {
+    pub sources: Vec<ScannedSourceDto>,
+    pub filtered: Vec<FilteredSkillDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScannedSourceDto {
+    pub key: String,
+    /// `tool`, `extra_store`, `file`, `plugin` or `extension`
+    pub kind: String,
+    /// Declared location (storage path such as `~/.claude/skills`)
+    pub declared_dir: String,
+    /// Resolved location; `None` when it could not be resolved
+    pub resolved_dir: Option<String>,
+    /// Whether the tool was detected as installed and its dir scanned
+    pub scanned: bool,
+    /// Skills found before filtering
+    pub detected: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterReason {
+    /// Inside the central repo or linking into it
+    CentralRepo,
+    /// Already a target of a managed skill
+    ManagedTarget,
+    /// A managed skill has the same name
+    ManagedName,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FilteredSkillDto {
+    pub tool: String,
+    pub name: String,
+    pub path: String,
+    pub link_target: Option<String>,
+    pub reason: FilterReason,
+}
+
+impl ScanDiagnostics {
+    fn record_source(
+        &mut self,
+        key: &str,
+        kind: &str,
+        declared_dir: &str,
+        resolved_dir: Option<&Path>,
+        scanned: bool,
+        detected: usize,
+    ) {
+        self.sources.push(ScannedSourceDto {
+            key: key.to_string(),
+            kind: kind.to_string(),
+            declared_dir: declared_dir.to_string(),
+            resolved_dir: resolved_dir.map(|dir| dir.to_string_lossy().to_string()),
+            scanned,
+            detected,
+        });
+    }
+}
+
+/// Build an onboarding plan by scanning installed tools for existing skills.
+///
+/// Also returns the index of the variants found, for the caller to record
+/// with `save_scan_index`. A `differential` scan only re-hashes directories
+/// whose metadata changed since the recorded index; either way the plan lists
+/// groups that are new since the recorded scan.
 pub async fn build_onboarding_plan(
     app: &tauri::AppHandle,
     state: &SqliteDbState,
+    differential: bool,
+) -> Result<(OnboardingPlan, OnboardingScanIndex)> {
+    build_onboarding_plan_with_diagnostics(app, state, differential)
+        .await
+        .map(|(plan, index, _)| (plan, index))
+}
+
+/// `build_onboarding_plan`, plus the sources looked at and the skills filtered out
+pub async fn build_onboarding_plan_with_diagnostics(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    differential: bool,
+) -> Result<(OnboardingPlan, OnboardingScanIndex, ScanDiagnostics)> {
+    build_plan(app, state, differential, None).await
+}
+
+/// Scan a single tool, e.g. one that was just installed. Every directory is
+/// hashed; the index is not returned since it only covers this tool and
+/// must not replace the one recorded by a full scan.
+pub async fn build_tool_onboarding_plan(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    tool: &str,
 ) -> Result<OnboardingPlan> {
-    let home =
-        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("failed to resolve home directory"))?;
+    build_plan(app, state, false, Some(tool.to_string()))
+        .await
+        .map(|(plan, _, _)| plan)
+}
+
+async fn build_plan(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    differential: bool,
+    only_tool: Option<String>,
+) -> Result<(OnboardingPlan, OnboardingScanIndex, ScanDiagnostics)> {
     let central = resolve_central_repo_path(app, state).await?;
 
     // Get custom tools
@@ -55,10 +375,11 @@ pub async fn build_onboarding_plan(
         .await
         .unwrap_or_default()
         .into_iter()
-        .map(|s| s.name)
+        .map(|s| nfc_name(&s.name))
         .collect::<std::collections::HashSet<_>>();
-    let claude_plugins =
-        crate::coding::tools::claude_plugins::get_installed_plugins(&state.db()).await;
+    let bundled_sources = load_bundled_sources(state).await;
+    let previous = load_scan_index(state);
+    let source_priority = load_source_priority(state);
 
     // Run the blocking file system operations in a dedicated thread pool
     // to avoid blocking the tokio async runtime
@@ -68,43 +389,194 @@ pub async fn build_onboarding_plan(
             managed_targets: Some(&managed_targets),
             managed_names: Some(&managed_names),
         };
-        build_onboarding_plan_in_home(&home, &filter_ctx, &custom_tools, &claude_plugins)
+        let mut fingerprints = ScanFingerprints::new(previous.as_ref(), differential);
+        let mut diagnostics = ScanDiagnostics::default();
+        let mut plan = build_onboarding_plan_in_home(
+            None,
+            only_tool.as_deref(),
+            &filter_ctx,
+            &custom_tools,
+            &bundled_sources,
+            &mut fingerprints,
+            &mut diagnostics,
+        )?;
+        sort_variants_by_priority(&mut plan, &source_priority);
+        anyhow::Ok((plan, fingerprints.next, diagnostics))
     })
     .await
     .map_err(|e| anyhow::anyhow!("spawn_blocking failed: {}", e))?
 }
 
+/// Every skill the installed tools hold right now, managed targets and links
+/// into the central repo included. Directories unchanged since the previous
+/// call reuse their fingerprints from a separate cached index, so the scan
+/// stays cheap enough to run whenever the overview is opened.
+pub async fn build_unfiltered_plan(state: &SqliteDbState) -> Result<OnboardingPlan> {
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    let bundled_sources = load_bundled_sources(state).await;
+    let previous = load_index(state, CONFLICT_INDEX_SETTINGS_KEY);
+
+    let (plan, index) = tokio::task::spawn_blocking(move || {
+        let mut fingerprints = ScanFingerprints::new(previous.as_ref(), true);
+        let plan = build_onboarding_plan_in_home(
+            None,
+            None,
+            &FilterContext::default(),
+            &custom_tools,
+            &bundled_sources,
+            &mut fingerprints,
+            &mut ScanDiagnostics::default(),
+        )?;
+        anyhow::Ok((plan, fingerprints.next))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("spawn_blocking failed: {}", e))??;
+
+    if let Err(err) = save_index(state, CONFLICT_INDEX_SETTINGS_KEY, &index) {
+        log::warn!("Failed to save conflict scan index: {}", err);
+    }
+    Ok(plan)
+}
+
+/// Findings in one extra home directory, scanned read-only
+#[derive(Clone, Debug, Serialize)]
+pub struct ExtraRootScanDto {
+    pub root: String,
+    /// `None` when the root could not be scanned, see `error`
+    pub plan: Option<OnboardingPlan>,
+    pub error: Option<String>,
+}
+
+/// Scan other users' homes or mounted backups for skills.
+///
+/// Nothing is imported and no scan index is recorded: each root gets its own
+/// plan, so findings stay tagged by the home they came from.
+pub async fn scan_extra_roots(
+    state: &SqliteDbState,
+    roots: Vec<String>,
+) -> Result<Vec<ExtraRootScanDto>> {
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    let source_priority = load_source_priority(state);
+    tokio::task::spawn_blocking(move || {
+        roots
+            .into_iter()
+            .map(|root| {
+                let home = Path::new(&root);
+                let scanned = if home.is_dir() {
+                    let mut fingerprints = ScanFingerprints::new(None, false);
+                    build_onboarding_plan_in_home(
+                        Some(home),
+                        None,
+                        &FilterContext::default(),
+                        &custom_tools,
+                        &[],
+                        &mut fingerprints,
+                        &mut ScanDiagnostics::default(),
+                    )
+                    .map_err(|e| format!("{:#}", e))
+                } else {
+                    Err(format!("Not a directory: {}", root))
+                };
+                match scanned {
+                    Ok(mut plan) => {
+                        sort_variants_by_priority(&mut plan, &source_priority);
+                        ExtraRootScanDto {
+                            root,
+                            plan: Some(plan),
+                            error: None,
+                        }
+                    }
+                    Err(error) => ExtraRootScanDto {
+                        root,
+                        plan: None,
+                        error: Some(error),
+                    },
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("spawn_blocking failed: {}", e))
+}
+
+/// Scan one home directory. `None` is the current user; another home (a
+/// second account or a mounted backup) only gets its tool and extra store
+/// dirs scanned, since plugins, extensions and single-file sources resolve
+/// through the current user's config. `only_tool` limits the scan to that
+/// tool's skills dir or single config file.
 fn build_onboarding_plan_in_home(
-    _home: &Path,
+    home: Option<&Path>,
+    only_tool: Option<&str>,
     filter_ctx: &FilterContext<'_>,
     custom_tools: &[super::types::CustomTool],
-    claude_plugins: &[PluginInfo],
+    bundled_sources: &[BundledSkillSource],
+    fingerprints: &mut ScanFingerprints<'_>,
+    diagnostics: &mut ScanDiagnostics,
 ) -> Result<OnboardingPlan> {
+    let resolve = |storage_path: &str| match home {
+        Some(home) => path_utils::resolve_storage_path_in_home(storage_path, home),
+        None => path_utils::resolve_storage_path(storage_path),
+    };
     // Get all adapters (built-in + custom)
     let adapters = get_all_tool_adapters(custom_tools);
     let mut all_detected: Vec<super::types::DetectedSkill> = Vec::new();
     let mut scanned = 0usize;
+    // Skill dirs a link may point into without being an external folder
+    let mut known_roots: Vec<std::path::PathBuf> = filter_ctx
+        .exclude_root
+        .map(Path::to_path_buf)
+        .into_iter()
+        .collect();
 
     for adapter in &adapters {
+        if only_tool.is_some_and(|tool| tool != adapter.key) {
+            continue;
+        }
+        // Resolve skills directory using path_utils to handle ~/  and %APPDATA%/ paths correctly
+        let dir = resolve(&adapter.relative_skills_dir);
         // Check if tool is installed using path_utils
-        let detect_path =
-            crate::coding::tools::path_utils::resolve_storage_path(&adapter.relative_detect_dir);
+        let detect_path = resolve(&adapter.relative_detect_dir);
         if detect_path.is_none() || !detect_path.as_ref().unwrap().exists() {
+            diagnostics.record_source(
+                &adapter.key,
+                "tool",
+                &adapter.relative_skills_dir,
+                dir.as_deref(),
+                false,
+                0,
+            );
             continue;
         }
         scanned += 1;
-        // Resolve skills directory using path_utils to handle ~/  and %APPDATA%/ paths correctly
-        let dir =
-            crate::coding::tools::path_utils::resolve_storage_path(&adapter.relative_skills_dir);
-        if let Some(skills_dir) = dir {
-            let detected = scan_runtime_tool_dir(adapter, &skills_dir)?;
-            all_detected.extend(filter_detected(detected, filter_ctx));
+        let mut found = 0;
+        if let Some(skills_dir) = &dir {
+            let detected = scan_runtime_tool_dir(adapter, skills_dir)?;
+            found = detected.len();
+            all_detected.extend(filter_detected(detected, filter_ctx, diagnostics));
+            known_roots.push(skills_dir.clone());
         }
+        diagnostics.record_source(
+            &adapter.key,
+            "tool",
+            &adapter.relative_skills_dir,
+            dir.as_deref(),
+            true,
+            found,
+        );
     }
 
     // Scan extra skill directories (third-party skill stores)
-    for source in EXTRA_SKILL_SOURCES {
-        let skills_dir = crate::coding::tools::path_utils::resolve_storage_path(source.skills_dir);
+    let extra_sources = if only_tool.is_none() {
+        EXTRA_SKILL_SOURCES
+    } else {
+        &[]
+    };
+    for source in extra_sources {
+        let skills_dir = resolve(source.skills_dir);
         if let Some(dir) = skills_dir {
             if dir.exists() {
                 let adapter = RuntimeToolAdapter {
@@ -114,41 +586,105 @@ fn build_onboarding_plan_in_home(
                     relative_detect_dir: source.skills_dir.to_string(),
                     is_custom: false,
                     force_copy: false,
+                    preferred_sync_mode: None,
                 };
                 scanned += 1;
                 let detected = scan_runtime_tool_dir(&adapter, &dir)?;
-                all_detected.extend(filter_detected(detected, filter_ctx));
+                diagnostics.record_source(
+                    source.key,
+                    "extra_store",
+                    source.skills_dir,
+                    Some(&dir),
+                    true,
+                    detected.len(),
+                );
+                all_detected.extend(filter_detected(detected, filter_ctx, diagnostics));
+                known_roots.push(dir);
             }
         }
     }
 
-    // Scan Claude Code plugins for skills
-    for plugin in claude_plugins {
-        let skills_dir = plugin.install_path.join("skills");
+    // Extract skills embedded in single config files (see file_skills.rs)
+    let staging = file_skills::staging_root();
+    let file_sources = if home.is_none() {
+        file_skills::FILE_SKILL_SOURCES
+    } else {
+        &[]
+    };
+    for source in file_sources {
+        if only_tool.is_some_and(|tool| tool != source.key) {
+            continue;
+        }
+        let Some(config_path) = file_skills::resolve_config_path(source) else {
+            continue;
+        };
+        if !config_path.is_file() {
+            continue;
+        }
+        scanned += 1;
+        // A malformed config file must not break onboarding for other tools
+        let detected = match file_skills::scan_file_source(source, &config_path, &staging) {
+            Ok(detected) => detected,
+            Err(err) => {
+                log::warn!("[onboarding] skip {:?}: {:#}", config_path, err);
+                Vec::new()
+            }
+        };
+        diagnostics.record_source(
+            source.key,
+            "file",
+            source.config_path,
+            Some(&config_path),
+            true,
+            detected.len(),
+        );
+        all_detected.extend(filter_detected(detected, filter_ctx, diagnostics));
+    }
+
+    // Scan skills bundled with Claude Code plugins and Gemini CLI extensions
+    let bundled_sources = if only_tool.is_none() {
+        bundled_sources
+    } else {
+        &[]
+    };
+    for source in bundled_sources {
+        let skills_dir = &source.skills_dir;
         if !skills_dir.exists() {
             continue;
         }
         let adapter = RuntimeToolAdapter {
-            key: format!("plugin::{}", plugin.plugin_id),
-            display_name: format!("Plugin: {}", plugin.display_name),
+            key: source.key.clone(),
+            display_name: source.display_name.clone(),
             relative_skills_dir: skills_dir.to_string_lossy().to_string(),
             relative_detect_dir: skills_dir.to_string_lossy().to_string(),
             is_custom: false,
             force_copy: true,
+            preferred_sync_mode: None,
         };
         scanned += 1;
-        let detected = scan_runtime_tool_dir(&adapter, &skills_dir)?;
-        all_detected.extend(filter_detected(detected, filter_ctx));
+        let detected = scan_runtime_tool_dir(&adapter, skills_dir)?;
+        diagnostics.record_source(
+            &adapter.key,
+            source.kind,
+            &adapter.relative_skills_dir,
+            Some(skills_dir),
+            true,
+            detected.len(),
+        );
+        all_detected.extend(filter_detected(detected, filter_ctx, diagnostics));
+        known_roots.push(skills_dir.clone());
     }
 
     let mut grouped: HashMap<String, Vec<OnboardingVariant>> = HashMap::new();
     for skill in all_detected.iter() {
-        let fingerprint = hash_dir(&skill.path).ok();
-        let entry = grouped.entry(skill.name.clone()).or_default();
+        // Group NFD (macOS) and NFC spellings of one name together
+        let name = nfc_name(&skill.name);
+        let fingerprint = fingerprints.fingerprint(&name, &skill.path);
+        let entry = grouped.entry(name.clone()).or_default();
         entry.push(OnboardingVariant {
             tool: skill.tool.clone(),
             tool_display: skill.tool_display.clone(),
-            name: skill.name.clone(),
+            name,
             path: skill.path.to_string_lossy().to_string(),
             fingerprint,
             is_link: skill.is_link,
@@ -156,6 +692,11 @@ fn build_onboarding_plan_in_home(
                 .link_target
                 .as_ref()
                 .map(|p| p.to_string_lossy().to_string()),
+            linked_folder: skill
+                .is_link
+                .then(|| external_link_source(&skill.path, &known_roots))
+                .flatten()
+                .map(|p| p.to_string_lossy().to_string()),
             conflicting_tools: Vec::new(), // Will be calculated later
         });
     }
@@ -203,9 +744,66 @@ fn build_onboarding_plan_in_home(
         total_tools_scanned: scanned,
         total_skills_found: all_detected.len(),
         groups,
+        previous_scan_at: fingerprints.previous.map(|index| index.scanned_at),
+        new_since_last_scan: fingerprints.new_names.iter().cloned().collect(),
+        rehashed_dirs: fingerprints.rehashed,
+        system_skills: Vec::new(),
     })
 }
 
+/// Skills in the system dirs of the installed tools, for a read-only list of
+/// everything the tools load
+pub async fn list_system_skills(state: &SqliteDbState) -> Vec<SystemSkill> {
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    tokio::task::spawn_blocking(move || scan_system_skills(None, &custom_tools))
+        .await
+        .unwrap_or_default()
+}
+
+/// `home` as in `build_onboarding_plan_in_home`
+fn scan_system_skills(
+    home: Option<&Path>,
+    custom_tools: &[super::types::CustomTool],
+) -> Vec<SystemSkill> {
+    let resolve = |storage_path: &str| match home {
+        Some(home) => path_utils::resolve_storage_path_in_home(storage_path, home),
+        None => path_utils::resolve_storage_path(storage_path),
+    };
+    let mut skills = Vec::new();
+    for adapter in get_all_tool_adapters(custom_tools) {
+        let installed = resolve(&adapter.relative_detect_dir).is_some_and(|dir| dir.exists());
+        let Some(skills_dir) = resolve(&adapter.relative_skills_dir).filter(|_| installed) else {
+            continue;
+        };
+        for system_dir in system_skill_dirs(&adapter.key) {
+            let Ok(entries) = std::fs::read_dir(skills_dir.join(system_dir)) else {
+                continue;
+            };
+            let mut found: Vec<SystemSkill> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.join("SKILL.md").is_file())
+                .map(|path| SystemSkill {
+                    tool: adapter.key.clone(),
+                    tool_display: adapter.display_name.clone(),
+                    name: path
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                    description: frontmatter_value(&path, "description"),
+                    path: path.to_string_lossy().to_string(),
+                })
+                .collect();
+            found.sort_by(|a, b| a.name.cmp(&b.name));
+            skills.extend(found);
+        }
+    }
+    skills
+}
+
 /// Exclusion context for filtering detected skills during onboarding scan.
 #[derive(Default)]
 struct FilterContext<'a> {
@@ -214,9 +812,11 @@ struct FilterContext<'a> {
     managed_names: Option<&'a std::collections::HashSet<String>>,
 }
 
+/// Drop already managed skills, recording each decision in `diagnostics`
 fn filter_detected(
     detected: Vec<super::types::DetectedSkill>,
     ctx: &FilterContext<'_>,
+    diagnostics: &mut ScanDiagnostics,
 ) -> Vec<super::types::DetectedSkill> {
     if ctx.exclude_root.is_none() && ctx.managed_targets.is_none() && ctx.managed_names.is_none() {
         return detected;
@@ -224,40 +824,60 @@ fn filter_detected(
     detected
         .into_iter()
         .filter(|skill| {
-            if let Some(exclude_root) = ctx.exclude_root {
-                if is_under(&skill.path, exclude_root) {
-                    return false;
-                }
-                if let Some(target) = &skill.link_target {
-                    if is_under(target, exclude_root) {
-                        return false;
-                    }
-                }
-            }
-            if let Some(exclude) = ctx.managed_targets {
-                if exclude.contains(&managed_target_key(&skill.tool, &skill.path)) {
-                    return false;
-                }
-            }
-            // Exclude skills whose name is already managed in the database
-            // (covers plugin skills whose source path doesn't match any managed target)
-            if let Some(names) = ctx.managed_names {
-                if names.contains(&skill.name) {
-                    return false;
-                }
-            }
-            true
+            let Some(reason) = filter_reason(skill, ctx) else {
+                return true;
+            };
+            diagnostics.filtered.push(FilteredSkillDto {
+                tool: skill.tool.clone(),
+                name: skill.name.clone(),
+                path: skill.path.to_string_lossy().to_string(),
+                link_target: skill
+                    .link_target
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string()),
+                reason,
+            });
+            false
         })
         .collect()
 }
 
+fn filter_reason(
+    skill: &super::types::DetectedSkill,
+    ctx: &FilterContext<'_>,
+) -> Option<FilterReason> {
+    if let Some(exclude_root) = ctx.exclude_root {
+        if is_under(&skill.path, exclude_root) {
+            return Some(FilterReason::CentralRepo);
+        }
+        if let Some(target) = &skill.link_target {
+            if is_under(target, exclude_root) {
+                return Some(FilterReason::CentralRepo);
+            }
+        }
+    }
+    if let Some(exclude) = ctx.managed_targets {
+        if exclude.contains(&managed_target_key(&skill.tool, &skill.path)) {
+            return Some(FilterReason::ManagedTarget);
+        }
+    }
+    // Exclude skills whose name is already managed in the database
+    // (covers plugin skills whose source path doesn't match any managed target)
+    if let Some(names) = ctx.managed_names {
+        if names.contains(&nfc_name(&skill.name)) {
+            return Some(FilterReason::ManagedName);
+        }
+    }
+    None
+}
+
 fn is_under(path: &Path, base: &Path) -> bool {
     path.starts_with(base)
 }
 
 fn managed_target_key(tool: &str, path: &Path) -> String {
     let tool = tool.to_ascii_lowercase();
-    let normalized = normalize_path_for_key(path);
+    let normalized = nfc_name(&normalize_path_for_key(path));
     format!("{tool}\n{normalized}")
 }
 
@@ -297,8 +917,8 @@ fn scan_runtime_tool_dir(
         }
 
         let name = entry.file_name().to_string_lossy().to_string();
-        // Skip system directories for codex
-        if adapter.key == "codex" && name == ".system" {
+        // System skills are listed separately, see `scan_system_skills`
+        if is_system_skill_dir(&adapter.key, &name) {
             continue;
         }
 
@@ -328,11 +948,15 @@ fn scan_runtime_tool_dir(
 fn detect_link(path: &Path) -> (bool, Option<std::path::PathBuf>) {
     match std::fs::symlink_metadata(path) {
         Ok(metadata) if metadata.file_type().is_symlink() => {
-            let target = std::fs::read_link(path).ok();
+            let target = std::fs::read_link(path)
+                .ok()
+                .map(|stored| resolve_link_target(path, &stored));
             (true, target)
         }
         _ => {
-            let target = std::fs::read_link(path).ok();
+            let target = std::fs::read_link(path)
+                .ok()
+                .map(|stored| resolve_link_target(path, &stored));
             if target.is_some() {
                 (true, target)
             } else {
@@ -341,3 +965,208 @@ fn detect_link(path: &Path) -> (bool, Option<std::path::PathBuf>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn managed_target_key_ignores_unicode_normalization_form() {
+        assert_eq!(
+            managed_target_key("Claude", Path::new("/skills/cafe\u{301}")),
+            managed_target_key("claude", Path::new("/skills/caf\u{e9}"))
+        );
+    }
+
+    #[test]
+    fn variants_follow_source_priority_with_links_last() {
+        let variant = |tool: &str, is_link: bool| OnboardingVariant {
+            tool: tool.to_string(),
+            tool_display: tool.to_string(),
+            name: "demo".to_string(),
+            path: format!("/{tool}/demo/{is_link}"),
+            fingerprint: Some("same".to_string()),
+            is_link,
+            link_target: None,
+            linked_folder: None,
+            conflicting_tools: Vec::new(),
+        };
+        let mut plan = OnboardingPlan {
+            total_tools_scanned: 4,
+            total_skills_found: 5,
+            groups: vec![OnboardingGroup {
+                name: "demo".to_string(),
+                variants: vec![
+                    variant("codex", false),
+                    variant("plugin::docs@market", false),
+                    variant("claude_code", true),
+                    variant("cursor", false),
+                    variant("claude_code", false),
+                ],
+                has_conflict: false,
+            }],
+            previous_scan_at: None,
+            new_since_last_scan: Vec::new(),
+            rehashed_dirs: 0,
+            system_skills: Vec::new(),
+        };
+        let priority = vec!["plugin::*".to_string(), "claude_code".to_string()];
+        sort_variants_by_priority(&mut plan, &priority);
+        let order: Vec<(&str, bool)> = plan.groups[0]
+            .variants
+            .iter()
+            .map(|v| (v.tool.as_str(), v.is_link))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("plugin::docs@market", false),
+                ("claude_code", false),
+                ("claude_code", true),
+                ("codex", false),
+                ("cursor", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_home_scan_resolves_tool_dirs_under_that_home() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path().join("alice");
+        for tool in [".claude", ".codex"] {
+            let skill = home.join(tool).join("skills").join("review");
+            std::fs::create_dir_all(&skill).unwrap();
+            std::fs::write(skill.join("SKILL.md"), "same").unwrap();
+        }
+
+        let mut fingerprints = ScanFingerprints::new(None, false);
+        let plan = build_onboarding_plan_in_home(
+            Some(&home),
+            None,
+            &FilterContext::default(),
+            &[],
+            &[],
+            &mut fingerprints,
+            &mut ScanDiagnostics::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.groups.len(), 1);
+        let group = &plan.groups[0];
+        assert_eq!(group.name, "review");
+        assert!(!group.has_conflict);
+        let mut tools: Vec<&str> = group.variants.iter().map(|v| v.tool.as_str()).collect();
+        tools.sort();
+        assert_eq!(tools, vec!["claude_code", "codex"]);
+        assert!(group
+            .variants
+            .iter()
+            .all(|v| Path::new(&v.path).starts_with(&home)));
+    }
+
+    #[test]
+    fn scoped_scan_only_reads_that_tool() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path();
+        for (dir, name) in [
+            (".claude/skills", "review"),
+            (".factory/skills", "deploy"),
+            (".cc-switch/skills", "notes"),
+        ] {
+            let skill = home.join(dir).join(name);
+            std::fs::create_dir_all(&skill).unwrap();
+            std::fs::write(skill.join("SKILL.md"), name).unwrap();
+        }
+
+        let plan = build_onboarding_plan_in_home(
+            Some(home),
+            Some("droid"),
+            &FilterContext::default(),
+            &[],
+            &[],
+            &mut ScanFingerprints::new(None, false),
+            &mut ScanDiagnostics::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.total_tools_scanned, 1);
+        let found: Vec<(&str, &str)> = plan
+            .groups
+            .iter()
+            .flat_map(|group| &group.variants)
+            .map(|v| (v.tool.as_str(), v.name.as_str()))
+            .collect();
+        assert_eq!(found, vec![("droid", "deploy")]);
+    }
+
+    #[test]
+    fn system_skills_are_listed_but_not_offered_for_adoption() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path();
+        let skills_dir = home.join(".codex/skills");
+        for dir in ["review", ".system/skill-creator", ".system/notes"] {
+            std::fs::create_dir_all(skills_dir.join(dir)).unwrap();
+        }
+        std::fs::write(skills_dir.join("review/SKILL.md"), "review").unwrap();
+        std::fs::write(
+            skills_dir.join(".system/skill-creator/SKILL.md"),
+            "---\nname: skill-creator\ndescription: Create skills\n---\n",
+        )
+        .unwrap();
+
+        let plan = build_onboarding_plan_in_home(
+            Some(home),
+            Some("codex"),
+            &FilterContext::default(),
+            &[],
+            &[],
+            &mut ScanFingerprints::new(None, false),
+            &mut ScanDiagnostics::default(),
+        )
+        .unwrap();
+        let names: Vec<&str> = plan.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["review"]);
+
+        let system = scan_system_skills(Some(home), &[]);
+        assert_eq!(system.len(), 1);
+        assert_eq!(system[0].tool, "codex");
+        assert_eq!(system[0].name, "skill-creator");
+        assert_eq!(system[0].description.as_deref(), Some("Create skills"));
+    }
+
+    #[test]
+    fn differential_scan_rehashes_only_changed_dirs() {
+        let temp = tempfile::tempdir().unwrap();
+        let alpha = temp.path().join("alpha");
+        let beta = temp.path().join("beta");
+        for dir in [&alpha, &beta] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("SKILL.md"), "v1").unwrap();
+        }
+
+        let mut first = ScanFingerprints::new(None, true);
+        let alpha_fp = first.fingerprint("alpha", &alpha);
+        first.fingerprint("beta", &beta);
+        assert_eq!(first.rehashed, 2);
+        assert!(first.new_names.is_empty());
+        let index = first.next;
+
+        // Grow beta so its signature changes even within the same mtime tick
+        std::fs::write(beta.join("SKILL.md"), "v2 longer").unwrap();
+        let gamma = temp.path().join("gamma");
+        std::fs::create_dir_all(&gamma).unwrap();
+
+        let mut second = ScanFingerprints::new(Some(&index), true);
+        assert_eq!(second.fingerprint("alpha", &alpha), alpha_fp);
+        assert_eq!(second.fingerprint("beta", &beta), hash_dir(&beta).ok());
+        second.fingerprint("gamma", &gamma);
+        assert_eq!(second.rehashed, 2);
+        assert_eq!(
+            second.new_names.into_iter().collect::<Vec<_>>(),
+            vec!["gamma"]
+        );
+
+        // A full scan hashes everything but still reports new variants
+        let mut full = ScanFingerprints::new(Some(&index), false);
+        full.fingerprint("alpha", &alpha);
+        assert_eq!(full.rehashed, 1);
+    }
+}
@@ -1,29 +1,59 @@
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use anyhow::{Context, Result};
 
+use super::content_hash::hash_dir_with_rules;
+use super::path_rules::{self, copy_dir_filtered};
+use super::sync_context;
 use super::sync_engine::{
-    ensure_source_dir, ensure_source_target_not_overlapping, sync_dir_for_tool_with_overwrite,
+    ensure_source_dir, ensure_source_target_not_overlapping, sync_dir_dry_run_filtered,
+    sync_dir_for_tool_with_link_style, sync_dir_for_tool_with_overwrite,
     validate_sync_target_preflight,
 };
-use super::types::{SyncMode, SyncOutcome};
+use super::sync_journal::{keep_replaced, JournalOp};
+use super::types::{SkillPathRules, SyncDryRun, SyncMode, SyncOutcome, SyncTargetSpec};
 use crate::coding::runtime_location;
 use crate::coding::wsl;
 
+/// At most this many targets of one batch are synced at the same time
+const MAX_PARALLEL_SYNCS: usize = 8;
+
 fn parse_wsl_target_path(target: &Path) -> Option<runtime_location::WslLocationInfo> {
     target
         .to_str()
         .and_then(runtime_location::parse_wsl_unc_path)
 }
 
+/// Sync `source` to `target` with a tool's sync mode (`RuntimeToolAdapter::sync_mode`)
 pub fn sync_skill_to_target(
-    tool_key: &str,
     source: &Path,
     target: &Path,
     overwrite: bool,
-    force_copy: bool,
+    mode: SyncMode,
+    rules: &SkillPathRules,
 ) -> Result<SyncOutcome> {
     ensure_source_dir(source)?;
+    let ctx = sync_context::current();
+
+    // A target with path rules is a plain copy of a filtered staging dir. A
+    // target linked to `source` resolves to it, but is replaced, not written
+    // through.
+    if !rules.is_empty() {
+        if parse_wsl_target_path(target).is_none() {
+            validate_sync_target_preflight(source, target, false)?;
+        }
+        let staging = tempfile::tempdir().context("create path rules staging dir")?;
+        let filtered = staging.path().join("skill");
+        copy_dir_filtered(&ctx, source, &filtered, rules)?;
+        return sync_skill_to_target(
+            &filtered,
+            target,
+            overwrite,
+            SyncMode::Copy,
+            &SkillPathRules::default(),
+        );
+    }
 
     if let Some(wsl_target) = parse_wsl_target_path(target) {
         let source_path = source.to_string_lossy().to_string();
@@ -48,10 +78,72 @@ pub fn sync_skill_to_target(
             mode_used: SyncMode::Copy,
             target_path: unc_target_path,
             replaced: overwrite,
+            verified: None,
+            mismatches: Vec::new(),
+            file_changes: None,
         });
     }
 
-    sync_dir_for_tool_with_overwrite(tool_key, source, target, overwrite, force_copy)
+    sync_dir_for_tool_with_overwrite(&ctx, source, target, overwrite, mode)
+}
+
+/// Sync `source` to several targets at once on a few scoped worker threads.
+///
+/// Each spec is synced like `sync_skill_to_target`, from its own `source`
+/// when it has one. A failing target does not stop the others; the results
+/// come back in the order of `specs`. Targets must be distinct paths.
+pub fn sync_to_targets(source: &Path, specs: Vec<SyncTargetSpec>) -> Vec<Result<SyncOutcome>> {
+    let sync = |spec: &SyncTargetSpec| {
+        sync_skill_to_target(
+            spec.source.as_deref().unwrap_or(source),
+            &spec.target,
+            spec.overwrite,
+            spec.mode,
+            &spec.rules,
+        )
+    };
+    let workers = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(MAX_PARALLEL_SYNCS)
+        .min(specs.len());
+    if workers <= 1 {
+        return specs.iter().map(sync).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let finished: Vec<(usize, Result<SyncOutcome>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(spec) = specs.get(index) else {
+                            break done;
+                        };
+                        done.push((index, sync(spec)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    });
+
+    let mut results: Vec<Option<Result<SyncOutcome>>> = specs.iter().map(|_| None).collect();
+    for (index, result) in finished {
+        results[index] = Some(result);
+    }
+    results
+        .into_iter()
+        .zip(&specs)
+        .map(|(result, spec)| {
+            result
+                .unwrap_or_else(|| Err(anyhow::anyhow!("sync to {:?} did not finish", spec.target)))
+        })
+        .collect()
 }
 
 pub fn remove_skill_target(target_path: &str) -> Result<()> {
@@ -80,6 +172,17 @@ pub fn remove_skill_target_checked(source: &Path, target_path: &str) -> Result<(
                 }
             }
         }
+        // A removed target goes to the sync trash and can be rolled back
+        if std::fs::symlink_metadata(&target).is_ok()
+            && keep_replaced(
+                sync_context::current().trash(),
+                &target,
+                &target,
+                JournalOp::Remove,
+            )?
+        {
+            return Ok(());
+        }
     }
 
     remove_skill_target(target_path)
@@ -104,9 +207,135 @@ fn is_direct_link_target(target: &Path) -> bool {
     }
 }
 
-pub fn sync_copy_target_path(source: &Path, target_path: &str) -> Result<SyncOutcome> {
+/// Plan `sync_skill_to_target` without writing anything. A target with path
+/// rules is planned as a copy of the files the rules keep. WSL targets are
+/// written through `wsl.exe` and cannot be previewed.
+pub fn plan_skill_sync(
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: SyncMode,
+    rules: &SkillPathRules,
+) -> Result<SyncDryRun> {
+    if parse_wsl_target_path(target).is_some() {
+        anyhow::bail!(
+            "sync preview is not supported for WSL targets: {:?}",
+            target
+        );
+    }
+    let mode = if rules.is_empty() {
+        mode
+    } else {
+        SyncMode::Copy
+    };
+    sync_dir_dry_run_filtered(
+        &sync_context::current(),
+        source,
+        target,
+        overwrite,
+        mode,
+        |relative| path_rules::keeps(rules, relative),
+    )
+}
+
+/// Re-create an existing target as a copy (`mode` is `Copy`) or as a link of
+/// the given mode (`Auto`, `Symlink`, `Junction`).
+///
+/// The old target is moved aside first and restored when the new sync fails
+/// or the tool cannot use directory links (`LINK_UNSUPPORTED|{path}`; hard
+/// links do not count). A copy whose
+/// content differs from `source` is never replaced by a link
+/// (`TARGET_MODIFIED|{path}`), since that would drop the edits. `rules` are
+/// the target's path rules: the copy is compared with the files they keep,
+/// and a new copy honors them while a link always has the full directory.
+/// A new symlink is `relative` to the target's directory or absolute.
+pub fn convert_target_mode(
+    source: &Path,
+    target: &Path,
+    mode: SyncMode,
+    rules: &SkillPathRules,
+    relative: bool,
+) -> Result<SyncOutcome> {
+    ensure_source_dir(source)?;
+    let force_copy = mode == SyncMode::Copy;
+    if parse_wsl_target_path(target).is_some() {
+        anyhow::bail!("WSL targets can only be copies: {:?}", target);
+    }
+    let metadata =
+        std::fs::symlink_metadata(target).with_context(|| format!("target {:?}", target))?;
+    // The copy never received ignored files, compare it without them
+    let ctx = sync_context::current();
+    let ignore = ctx.ignore_for(source);
+    if !force_copy
+        && !is_direct_link_target(target)
+        && metadata.is_dir()
+        && hash_dir_with_rules(target, rules, &ignore)?
+            != hash_dir_with_rules(source, rules, &ignore)?
+    {
+        anyhow::bail!("TARGET_MODIFIED|{}", target.display());
+    }
+
+    let file_name = target
+        .file_name()
+        .with_context(|| format!("target has no file name: {:?}", target))?;
+    let backup = target.with_file_name(format!(".{}.mode-backup", file_name.to_string_lossy()));
+    if std::fs::symlink_metadata(&backup).is_ok() {
+        remove_skill_target(&backup.to_string_lossy())?;
+    }
+    std::fs::rename(target, &backup)
+        .with_context(|| format!("move {:?} -> {:?}", target, backup))?;
+
+    let synced = if force_copy {
+        sync_skill_to_target(source, target, false, mode, rules)
+    } else {
+        sync_dir_for_tool_with_link_style(&ctx, source, target, false, mode, relative)
+    };
+    let result = synced.and_then(|outcome| {
+        if !force_copy && outcome.mode_used.holds_own_files() {
+            anyhow::bail!("LINK_UNSUPPORTED|{}", target.display());
+        }
+        Ok(outcome)
+    });
+    match result {
+        Ok(outcome) => {
+            // The converted target can be rolled back to the previous one
+            let kept = keep_replaced(ctx.trash(), &backup, target, JournalOp::Overwrite)
+                .unwrap_or_else(|err| {
+                    log::warn!(
+                        "Failed to move target backup {:?} to the trash: {:#}",
+                        backup,
+                        err
+                    );
+                    false
+                });
+            if !kept {
+                if let Err(err) = remove_skill_target(&backup.to_string_lossy()) {
+                    log::warn!("Failed to remove target backup {:?}: {}", backup, err);
+                }
+            }
+            Ok(outcome)
+        }
+        Err(err) => {
+            if std::fs::symlink_metadata(target).is_ok() {
+                remove_skill_target(&target.to_string_lossy())?;
+            }
+            std::fs::rename(&backup, target)
+                .with_context(|| format!("restore {:?} -> {:?}", backup, target))?;
+            Err(err)
+        }
+    }
+}
+
+/// Refresh a target that holds its own files (a copy or hard links), which
+/// does not follow the central repo when its files are replaced
+pub fn sync_copy_target_path(
+    source: &Path,
+    target_path: &str,
+    mode: SyncMode,
+    rules: &SkillPathRules,
+) -> Result<SyncOutcome> {
     let target = PathBuf::from(target_path);
-    sync_skill_to_target("copy", source, &target, true, true)
+    sync_skill_to_target(source, &target, true, mode, rules)
 }
 
 pub fn validate_skill_sync_target(source: &Path, target: &Path, force_copy: bool) -> Result<()> {
@@ -172,6 +401,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sync_to_targets_reports_each_target_without_stopping_on_errors() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let variant = temp.path().join("variant");
+        for (dir, content) in [(&source, "shared"), (&variant, "variant")] {
+            std::fs::create_dir_all(dir).expect("create source");
+            std::fs::write(dir.join("SKILL.md"), content).expect("write source file");
+        }
+        let taken = temp.path().join("taken/demo");
+        std::fs::create_dir_all(&taken).expect("create existing target");
+        let spec = |tool: &str, target: PathBuf, source: Option<&PathBuf>| SyncTargetSpec {
+            tool_key: tool.to_string(),
+            target,
+            source: source.cloned(),
+            overwrite: false,
+            mode: SyncMode::Copy,
+            rules: SkillPathRules::default(),
+        };
+
+        let results = sync_to_targets(
+            &source,
+            vec![
+                spec("claude", temp.path().join("claude/demo"), None),
+                spec("codex", taken.clone(), None),
+                spec("cursor", temp.path().join("cursor/demo"), Some(&variant)),
+            ],
+        );
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("target already exists"));
+        assert!(results[2].is_ok());
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("claude/demo/SKILL.md")).expect("read"),
+            "shared"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("cursor/demo/SKILL.md")).expect("read"),
+            "variant"
+        );
+        assert!(!taken.join("SKILL.md").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn convert_target_mode_switches_in_place_and_restores_on_failure() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool").join("demo");
+        std::fs::create_dir_all(&source).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: demo\n---\n")
+            .expect("write source file");
+        let no_rules = SkillPathRules::default();
+        sync_skill_to_target(&source, &target, false, SyncMode::Copy, &no_rules)
+            .expect("initial copy");
+
+        let outcome = convert_target_mode(&source, &target, SyncMode::Auto, &no_rules, false)
+            .expect("to link");
+        assert!(matches!(outcome.mode_used, SyncMode::Symlink));
+        assert!(is_direct_link_target(&target));
+
+        let outcome = convert_target_mode(&source, &target, SyncMode::Copy, &no_rules, false)
+            .expect("to copy");
+        assert!(matches!(outcome.mode_used, SyncMode::Copy));
+        assert!(!is_direct_link_target(&target));
+        assert!(!temp.path().join("tool").join(".demo.mode-backup").exists());
+
+        // Hard links (Cursor's mode) are no directory link: the copy is restored untouched
+        let err = convert_target_mode(&source, &target, SyncMode::Hardlink, &no_rules, false)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("LINK_UNSUPPORTED|"));
+        assert!(target.join("SKILL.md").is_file());
+        assert!(!is_direct_link_target(&target));
+
+        std::fs::write(target.join("SKILL.md"), "edited").expect("edit copy");
+        let err =
+            convert_target_mode(&source, &target, SyncMode::Auto, &no_rules, false).unwrap_err();
+        assert!(err.to_string().starts_with("TARGET_MODIFIED|"));
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).expect("copy survives"),
+            "edited"
+        );
+    }
+
     #[test]
     fn checked_remove_allows_cleanup_when_source_is_missing() {
         let temp = tempfile::tempdir().expect("temp dir");
@@ -186,4 +504,59 @@ mod tests {
 
         assert!(!target.exists());
     }
+
+    #[test]
+    fn path_rules_sync_a_slim_copy_that_matches_its_source() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool").join("demo");
+        std::fs::create_dir_all(source.join("assets")).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: demo\n---\n")
+            .expect("write source file");
+        std::fs::write(source.join("assets").join("big.bin"), "0000").expect("write asset");
+        let rules = SkillPathRules {
+            include: Vec::new(),
+            exclude: vec!["assets".to_string()],
+        };
+
+        let outcome =
+            sync_skill_to_target(&source, &target, false, SyncMode::Auto, &rules).expect("sync");
+        assert!(matches!(outcome.mode_used, SyncMode::Copy));
+        assert!(target.join("SKILL.md").is_file());
+        assert!(!target.join("assets").exists());
+
+        // The slim copy is not reported as modified when linking the full dir
+        let outcome =
+            convert_target_mode(&source, &target, SyncMode::Auto, &rules, false).expect("to link");
+        assert!(matches!(outcome.mode_used, SyncMode::Symlink));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_rules_replace_a_linked_target_with_a_filtered_copy() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let source = temp.path().join("source");
+        let target = temp.path().join("tool").join("demo");
+        std::fs::create_dir_all(source.join("assets")).expect("create source");
+        std::fs::write(source.join("SKILL.md"), "---\nname: demo\n---\n")
+            .expect("write source file");
+        std::fs::write(source.join("assets").join("big.bin"), "0000").expect("write asset");
+        std::fs::create_dir_all(target.parent().unwrap()).expect("create tool dir");
+        std::os::unix::fs::symlink(&source, &target).expect("link target");
+        let rules = SkillPathRules {
+            include: Vec::new(),
+            exclude: vec!["assets".to_string()],
+        };
+
+        let outcome =
+            sync_skill_to_target(&source, &target, true, SyncMode::Auto, &rules).expect("sync");
+        assert!(matches!(outcome.mode_used, SyncMode::Copy));
+        assert!(!std::fs::symlink_metadata(&target)
+            .expect("target")
+            .file_type()
+            .is_symlink());
+        assert!(target.join("SKILL.md").is_file());
+        assert!(!target.join("assets").exists());
+        assert!(source.join("assets").join("big.bin").is_file());
+    }
 }
@@ -0,0 +1,94 @@
+//! Commands for per-target sub-path rules (`path_rules.rs`)
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::commands::{copy_verification_error, format_error, resolve_skill_source_path};
+use super::path_executor::sync_skill_to_target;
+use super::path_rules;
+use super::skill_store;
+use super::target_marker;
+use super::tool_adapters::runtime_adapter_by_key;
+use super::types::{now_ms, SkillTarget, SyncResultDto};
+use super::variants;
+use crate::coding::tools::post_sync::{schedule_post_sync, PostSyncScope};
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Set which subpaths of a skill one tool target receives and re-sync it.
+/// A target with rules is a filtered copy; clearing them re-syncs the full
+/// skill with the tool's default mode.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_target_path_rules<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> Result<SyncResultDto, String> {
+    ensure_writable("skills_set_target_path_rules")?;
+    let path_rules = path_rules::normalize_rules(&include, &exclude)?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    if !skill.management_enabled {
+        return Err(format!("SKILL_DISABLED|{}", skillId));
+    }
+    let target = skill_store::get_skill_target(&state, &skillId, &tool)
+        .await?
+        .ok_or_else(|| format!("Skill {} is not synced to {}", skill.name, tool))?;
+    if target.path_rules == path_rules {
+        return Ok(SyncResultDto {
+            mode_used: target.mode,
+            target_path: target.target_path,
+            verified: None,
+            mismatches: Vec::new(),
+            file_changes: None,
+        });
+    }
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let runtime_adapter =
+        runtime_adapter_by_key(&tool, &custom_tools).ok_or_else(|| "unknown tool".to_string())?;
+
+    let source_path = resolve_skill_source_path(&app, &state, &skill).await?;
+    let variant_source = variants::resolve_variant_source(&state, &skill.id, &tool)?;
+    let source_path = variant_source.as_deref().unwrap_or(&source_path);
+    let target_path = PathBuf::from(&target.target_path);
+    let result = sync_skill_to_target(
+        source_path,
+        &target_path,
+        true,
+        runtime_adapter.sync_mode(),
+        &path_rules,
+    )
+    .map_err(format_error)?;
+
+    let record = SkillTarget {
+        target_path: result.target_path.to_string_lossy().to_string(),
+        mode: result.mode_used.as_str().to_string(),
+        status: "ok".to_string(),
+        error_message: copy_verification_error(&result),
+        synced_at: Some(now_ms()),
+        path_rules,
+        ..target
+    };
+    target_marker::mark_target(&skill, &record);
+    skill_store::upsert_skill_target(&state, &skill.id, &record).await?;
+    if let Some(tool_root) = target_path.parent() {
+        schedule_post_sync(&state, &tool, PostSyncScope::Skills, tool_root);
+    }
+    let _ = app.emit("skills-changed", "window");
+
+    Ok(SyncResultDto {
+        mode_used: record.mode,
+        target_path: record.target_path,
+        verified: result.verified,
+        mismatches: result.mismatches,
+        file_changes: result.file_changes,
+    })
+}
@@ -0,0 +1,138 @@
+//! Onboarding plan export for bug reports
+//!
+//! The export carries what a maintainer needs to reproduce grouping and
+//! conflict bugs without the user's machine: the plan with fingerprints and
+//! link targets, the sources that were (or were not) scanned, and the skills
+//! filtered out with the reason. Paths are anonymized: the home directory
+//! becomes `~` and any path component equal to the user name `<user>`.
+//! The `plan` part can be passed to `skills_simulate_adoption` unchanged.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::onboarding::ScanDiagnostics;
+use super::types::OnboardingPlan;
+
+pub const PLAN_EXPORT_VERSION: u32 = 1;
+
+const USER_PLACEHOLDER: &str = "<user>";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OnboardingPlanExport {
+    pub version: u32,
+    pub exported_at: i64,
+    pub app_version: String,
+    pub os: String,
+    pub plan: OnboardingPlan,
+    pub diagnostics: ScanDiagnostics,
+}
+
+/// Rewrites user-specific parts of paths
+pub struct PathAnonymizer {
+    home: Option<String>,
+    user: Option<String>,
+}
+
+impl PathAnonymizer {
+    pub fn new(home: Option<&Path>) -> Self {
+        let home = home.map(|home| {
+            home.to_string_lossy()
+                .trim_end_matches(['/', '\\'])
+                .to_string()
+        });
+        // Split by hand so Windows homes anonymize the same on every platform
+        let user = home
+            .as_deref()
+            .and_then(|home| home.rsplit(['/', '\\']).next())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        Self { home, user }
+    }
+
+    pub fn anonymize(&self, path: &str) -> String {
+        let path = match &self.home {
+            Some(home) => match path.strip_prefix(home.as_str()) {
+                Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+                    format!("~{rest}")
+                }
+                _ => path.to_string(),
+            },
+            None => path.to_string(),
+        };
+        let Some(user) = &self.user else {
+            return path;
+        };
+        path.split_inclusive(['/', '\\'])
+            .map(|segment| {
+                let name = segment.trim_end_matches(['/', '\\']);
+                if name == user {
+                    format!("{USER_PLACEHOLDER}{}", &segment[name.len()..])
+                } else {
+                    segment.to_string()
+                }
+            })
+            .collect()
+    }
+
+    fn anonymize_opt(&self, path: &mut Option<String>) {
+        if let Some(value) = path {
+            *value = self.anonymize(value);
+        }
+    }
+
+    /// Anonymize every path in `export` in place
+    pub fn anonymize_export(&self, export: &mut OnboardingPlanExport) {
+        for variant in export
+            .plan
+            .groups
+            .iter_mut()
+            .flat_map(|group| group.variants.iter_mut())
+        {
+            variant.path = self.anonymize(&variant.path);
+            self.anonymize_opt(&mut variant.link_target);
+            self.anonymize_opt(&mut variant.linked_folder);
+        }
+        for source in &mut export.diagnostics.sources {
+            source.declared_dir = self.anonymize(&source.declared_dir);
+            self.anonymize_opt(&mut source.resolved_dir);
+        }
+        for filtered in &mut export.diagnostics.filtered {
+            filtered.path = self.anonymize(&filtered.path);
+            self.anonymize_opt(&mut filtered.link_target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anonymizes_home_prefix_and_user_components() {
+        let anonymizer = PathAnonymizer::new(Some(Path::new("/Users/alice/")));
+        assert_eq!(
+            anonymizer.anonymize("/Users/alice/.claude/skills/review"),
+            "~/.claude/skills/review"
+        );
+        assert_eq!(
+            anonymizer.anonymize("/Volumes/backup/alice/dev/skills"),
+            "/Volumes/backup/<user>/dev/skills"
+        );
+        assert_eq!(
+            anonymizer.anonymize("/Users/alicex/notes"),
+            "/Users/alicex/notes"
+        );
+        assert_eq!(
+            anonymizer.anonymize("~/.codex/skills/alice-tools"),
+            "~/.codex/skills/alice-tools"
+        );
+
+        let windows = PathAnonymizer::new(Some(Path::new(r"C:\Users\bob")));
+        assert_eq!(
+            windows.anonymize(r"C:\Users\bob\.cursor\skills"),
+            r"~\.cursor\skills"
+        );
+        assert_eq!(windows.anonymize(r"D:\share\bob\x"), r"D:\share\<user>\x");
+    }
+}
@@ -0,0 +1,272 @@
+//! Project-aware skill copies
+//!
+//! A project root may carry an `.ai-toolbox.toml`:
+//!
+//! ```toml
+//! [project]
+//! name = "billing-service"
+//! language = "rust"
+//! conventions = "docs/CONVENTIONS.md"
+//!
+//! [variables]
+//! team = "payments"
+//! ```
+//!
+//! Rendering a skill for such a project copies it into the project-scoped
+//! tool directory (`<project>/<tool skills dir>/<skill>`) and replaces
+//! `{{project.name}}`, `{{project.language}}`, `{{project.conventions}}`,
+//! `{{project.root}}` and `{{<variable>}}` in its Markdown / text files.
+//! Unknown placeholders are left untouched so generic skills that show
+//! template syntax in examples survive unchanged.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+use super::sync_engine::copy_skill_dir;
+
+pub const PROJECT_METADATA_FILE: &str = ".ai-toolbox.toml";
+
+/// File extensions whose content is rendered; everything else is copied as-is
+const RENDERED_EXTENSIONS: [&str; 3] = ["md", "mdc", "txt"];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectInfo {
+    pub name: Option<String>,
+    pub language: Option<String>,
+    /// Conventions doc path, relative to the project root
+    pub conventions: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectMetadata {
+    #[serde(default)]
+    pub project: ProjectInfo,
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProjectRenderResultDto {
+    pub target_path: String,
+    /// Variables available to the templates, after defaults were applied
+    pub variables: BTreeMap<String, String>,
+    /// Files in which at least one placeholder was replaced
+    pub rendered_files: Vec<String>,
+    pub metadata_found: bool,
+}
+
+/// Read `.ai-toolbox.toml` from the project root; `None` when there is none
+pub fn load_project_metadata(project_root: &Path) -> Result<Option<ProjectMetadata>, String> {
+    let path = project_root.join(PROJECT_METADATA_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|error| format!("Failed to parse {}: {error}", path.display()))
+}
+
+/// Template variables for a project; `project.name` falls back to the directory name
+pub fn project_template_vars(
+    project_root: &Path,
+    metadata: Option<&ProjectMetadata>,
+) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    if let Some(metadata) = metadata {
+        vars.extend(
+            metadata
+                .variables
+                .iter()
+                .map(|(key, value)| (key.trim().to_string(), value.clone())),
+        );
+    }
+    let project = metadata.map(|metadata| &metadata.project);
+    let name = project
+        .and_then(|project| project.name.clone())
+        .or_else(|| {
+            project_root
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        });
+    let entries = [
+        ("project.name", name),
+        (
+            "project.language",
+            project.and_then(|project| project.language.clone()),
+        ),
+        (
+            "project.conventions",
+            project.and_then(|project| project.conventions.clone()),
+        ),
+        (
+            "project.root",
+            Some(project_root.to_string_lossy().to_string()),
+        ),
+    ];
+    vars.extend(
+        entries
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.to_string(), value?))),
+    );
+    vars
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").unwrap())
+}
+
+/// Replace known `{{key}}` placeholders; returns `None` when nothing changed
+pub fn render_template(text: &str, vars: &BTreeMap<String, String>) -> Option<String> {
+    let mut replaced = false;
+    let rendered =
+        placeholder_regex().replace_all(text, |captures: &Captures| match vars.get(&captures[1]) {
+            Some(value) => {
+                replaced = true;
+                value.clone()
+            }
+            None => captures[0].to_string(),
+        });
+    replaced.then(|| rendered.into_owned())
+}
+
+fn is_rendered_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| {
+            RENDERED_EXTENSIONS
+                .iter()
+                .any(|rendered| extension.eq_ignore_ascii_case(rendered))
+        })
+        .unwrap_or(false)
+}
+
+/// Copy `source` to `target` and render placeholders in the copy.
+///
+/// Returns the rendered files relative to `target`.
+pub fn render_skill_dir(
+    source: &Path,
+    target: &Path,
+    vars: &BTreeMap<String, String>,
+) -> Result<Vec<String>, String> {
+    copy_skill_dir(source, target)
+        .map_err(|error| format!("Failed to copy skill to {}: {error:#}", target.display()))?;
+    let mut rendered = Vec::new();
+    for entry in walkdir::WalkDir::new(target)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && is_rendered_file(entry.path()))
+    {
+        let path = entry.path();
+        // Non UTF-8 content is left as copied
+        let Ok(text) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        if let Some(output) = render_template(&text, vars) {
+            std::fs::write(path, output)
+                .map_err(|error| format!("Failed to write {}: {error}", path.display()))?;
+            rendered.push(
+                path.strip_prefix(target)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+            );
+        }
+    }
+    rendered.sort();
+    Ok(rendered)
+}
+
+/// `<project>/<tool skills dir>/<skill name>`
+pub fn project_target_path(
+    project_root: &Path,
+    relative_skills_dir: &str,
+    skill_name: &str,
+) -> PathBuf {
+    project_root.join(relative_skills_dir).join(skill_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_replaces_known_placeholders_only() {
+        let vars = BTreeMap::from([
+            ("project.name".to_string(), "billing".to_string()),
+            ("team".to_string(), "payments".to_string()),
+        ]);
+        assert_eq!(
+            render_template("# {{ project.name }} by {{team}}: {{unknown}}", &vars).as_deref(),
+            Some("# billing by payments: {{unknown}}")
+        );
+        assert_eq!(render_template("plain {{unknown}}", &vars), None);
+    }
+
+    #[test]
+    fn metadata_fills_project_vars_with_directory_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("my-app");
+        std::fs::create_dir_all(&root).unwrap();
+        assert_eq!(load_project_metadata(&root).unwrap(), None);
+        let vars = project_template_vars(&root, None);
+        assert_eq!(vars.get("project.name").map(String::as_str), Some("my-app"));
+        assert!(!vars.contains_key("project.language"));
+
+        std::fs::write(
+            root.join(PROJECT_METADATA_FILE),
+            "[project]\nname = \"Billing\"\nlanguage = \"rust\"\nconventions = \"docs/CONVENTIONS.md\"\n\n[variables]\nteam = \"payments\"\n",
+        )
+        .unwrap();
+        let metadata = load_project_metadata(&root).unwrap();
+        let vars = project_template_vars(&root, metadata.as_ref());
+        assert_eq!(
+            vars.get("project.name").map(String::as_str),
+            Some("Billing")
+        );
+        assert_eq!(
+            vars.get("project.language").map(String::as_str),
+            Some("rust")
+        );
+        assert_eq!(
+            vars.get("project.conventions").map(String::as_str),
+            Some("docs/CONVENTIONS.md")
+        );
+        assert_eq!(vars.get("team").map(String::as_str), Some("payments"));
+    }
+
+    #[test]
+    fn render_skill_dir_rewrites_text_files_in_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("SKILL.md"), "Follow {{project.conventions}}").unwrap();
+        std::fs::write(source.join("script.sh"), "echo {{project.conventions}}").unwrap();
+        let vars = BTreeMap::from([(
+            "project.conventions".to_string(),
+            "docs/STYLE.md".to_string(),
+        )]);
+
+        let target = dir.path().join("target");
+        let rendered = render_skill_dir(&source, &target, &vars).unwrap();
+        assert_eq!(rendered, vec!["SKILL.md".to_string()]);
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "Follow docs/STYLE.md"
+        );
+        assert_eq!(
+            std::fs::read_to_string(target.join("script.sh")).unwrap(),
+            "echo {{project.conventions}}"
+        );
+        assert_eq!(
+            std::fs::read_to_string(source.join("SKILL.md")).unwrap(),
+            "Follow {{project.conventions}}"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! License detection and provenance for skills
+//!
+//! The license comes from the `license:` key of the SKILL.md frontmatter, or
+//! from a LICENSE / COPYING file in the skill directory (falling back to the
+//! source repository root). License texts are mapped to SPDX ids by a few
+//! well-known phrases; unrecognized texts are recorded as `NOASSERTION`.
+//! Licenses the user marks as disallowed (`skill_settings:skills.disallowed_licenses`)
+//! only produce a warning at install time — nothing is blocked.
+
+use std::path::Path;
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::types::{now_ms, SkillProvenance};
+use crate::SqliteDbState;
+
+const DISALLOWED_LICENSES_KEY: &str = "disallowed_licenses";
+pub const UNKNOWN_LICENSE: &str = "NOASSERTION";
+const LICENSE_FILE_NAMES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENCE",
+    "LICENCE.md",
+    "COPYING",
+];
+
+/// Value of a top-level `key:` in the SKILL.md frontmatter
+pub fn frontmatter_value(skill_dir: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(skill_dir.join("SKILL.md")).ok()?;
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    let prefix = format!("{}:", key);
+    lines
+        .take_while(|line| line.trim() != "---")
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| {
+            value
+                .trim()
+                .trim_matches('"')
+                .trim_matches('\'')
+                .to_string()
+        })
+        .filter(|value| !value.is_empty())
+}
+
+/// Map a license text to an SPDX id by well-known phrases
+pub fn identify_license_text(text: &str) -> String {
+    let text = text.to_lowercase();
+    let has = |needle: &str| text.contains(needle);
+    let id = if has("gnu affero general public license") {
+        "AGPL-3.0"
+    } else if has("gnu lesser general public license") {
+        if has("version 2.1") {
+            "LGPL-2.1"
+        } else {
+            "LGPL-3.0"
+        }
+    } else if has("gnu general public license") {
+        if has("version 2") && !has("version 3") {
+            "GPL-2.0"
+        } else {
+            "GPL-3.0"
+        }
+    } else if has("apache license") && has("version 2.0") {
+        "Apache-2.0"
+    } else if has("mozilla public license") && has("2.0") {
+        "MPL-2.0"
+    } else if has("permission is hereby granted, free of charge") {
+        "MIT"
+    } else if has("permission to use, copy, modify, and/or distribute this software") {
+        "ISC"
+    } else if has("redistribution and use in source and binary forms") {
+        if has("neither the name") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        }
+    } else if has("free and unencumbered software released into the public domain") {
+        "Unlicense"
+    } else if has("creative commons attribution 4.0") {
+        "CC-BY-4.0"
+    } else {
+        UNKNOWN_LICENSE
+    };
+    id.to_string()
+}
+
+fn license_from_files(dir: &Path) -> Option<String> {
+    LICENSE_FILE_NAMES.iter().find_map(|name| {
+        let text = std::fs::read_to_string(dir.join(name)).ok()?;
+        Some(identify_license_text(&text))
+    })
+}
+
+/// Detect a skill's license: frontmatter first, then LICENSE files in the
+/// skill dir, then in `source_root` (e.g. the repo a subpath skill came from).
+pub fn detect_license(skill_dir: &Path, source_root: Option<&Path>) -> Option<String> {
+    frontmatter_value(skill_dir, "license")
+        .or_else(|| license_from_files(skill_dir))
+        .or_else(|| source_root.and_then(license_from_files))
+}
+
+pub fn build_provenance(
+    skill_dir: &Path,
+    origin: Option<String>,
+    fetched_from: Option<String>,
+    revision: Option<String>,
+) -> SkillProvenance {
+    SkillProvenance {
+        // Only keep the fetch URL when it differs (e.g. routed via a mirror)
+        fetched_from: fetched_from.filter(|url| origin.as_deref() != Some(url.as_str())),
+        origin,
+        revision,
+        author: frontmatter_value(skill_dir, "author"),
+        recorded_at: now_ms(),
+    }
+}
+
+pub fn load_disallowed_licenses(state: &SqliteDbState) -> Vec<String> {
+    read_skill_settings_value_from_sqlite(state, DISALLOWED_LICENSES_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_disallowed_licenses(state: &SqliteDbState, licenses: &[String]) -> Result<(), String> {
+    let licenses: Vec<String> = licenses
+        .iter()
+        .map(|license| license.trim().to_string())
+        .filter(|license| !license.is_empty())
+        .collect();
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            DISALLOWED_LICENSES_KEY: licenses,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Warning when any id of the (possibly compound) license is disallowed.
+/// A missing license matches `NOASSERTION` in the list.
+pub fn license_warning(license: Option<&str>, disallowed: &[String]) -> Option<String> {
+    let license = license.unwrap_or(UNKNOWN_LICENSE);
+    let hit = license
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|part| !matches!(part.to_uppercase().as_str(), "" | "OR" | "AND" | "WITH"))
+        .find(|part| {
+            disallowed
+                .iter()
+                .any(|blocked| blocked.eq_ignore_ascii_case(part))
+        })?;
+    Some(format!("License {} is marked as disallowed", hit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn license_detected_from_frontmatter_then_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path();
+        let skill = repo.join("skills/pdf");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "---\nname: pdf\n---\nbody").unwrap();
+        std::fs::write(
+            repo.join("LICENSE"),
+            "Apache License\nVersion 2.0, January 2004",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_license(&skill, Some(repo)).as_deref(),
+            Some("Apache-2.0")
+        );
+        assert_eq!(detect_license(&skill, None), None);
+
+        std::fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: pdf\nlicense: \"Proprietary\"\nauthor: acme\n---\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_license(&skill, Some(repo)).as_deref(),
+            Some("Proprietary")
+        );
+        let provenance = build_provenance(
+            &skill,
+            Some("https://github.com/acme/skills".to_string()),
+            Some("https://github.com/acme/skills".to_string()),
+            None,
+        );
+        assert_eq!(provenance.author.as_deref(), Some("acme"));
+        assert!(provenance.fetched_from.is_none());
+    }
+
+    #[test]
+    fn warning_matches_any_part_case_insensitively() {
+        let disallowed = vec!["gpl-3.0".to_string(), UNKNOWN_LICENSE.to_string()];
+        assert!(license_warning(Some("MIT"), &disallowed).is_none());
+        assert!(license_warning(Some("MIT OR GPL-3.0"), &disallowed).is_some());
+        assert!(license_warning(None, &disallowed).is_some());
+        assert_eq!(
+            identify_license_text("MIT License\n\nPermission is hereby granted, free of charge"),
+            "MIT"
+        );
+    }
+}
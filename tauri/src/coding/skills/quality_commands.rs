@@ -0,0 +1,92 @@
+//! Commands for the context budget, quality report and license checks
+
+use tauri::{AppHandle, Runtime, State};
+
+use super::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
+use super::commands::{format_error, resolve_skill_source_path};
+use super::context_budget::{self, ContextBudgetDto};
+use super::provenance;
+use super::quality::{self, QualityFlag, SkillQualityReportDto};
+use super::skill_store;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Estimate how many tokens the instructions file and enabled skills of a
+/// tool add to the agent context, with disable suggestions when over budget.
+#[tauri::command]
+pub async fn skills_get_context_budget<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<ContextBudgetDto, String> {
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(format_error)?;
+    context_budget::build_context_budget(&state, &tool, &skills, &central_dir).await
+}
+
+/// Set a tool's context budget; `None` restores the default.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_set_context_budget(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+    budgetTokens: Option<u64>,
+) -> Result<(), String> {
+    ensure_writable("skills_set_context_budget")?;
+    context_budget::save_budget(&state, &tool, budgetTokens)
+}
+
+/// Quality report of one skill: lint, tokens, age, usage and risk scan
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_analyze_skill<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<SkillQualityReportDto, String> {
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let source = resolve_skill_source_path(&app, &state, &skill).await?;
+    Ok(quality::analyze_skill(&skill, &source))
+}
+
+/// Quality reports of the whole library, lowest score first
+#[tauri::command]
+pub async fn skills_rank_quality<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    flag: Option<QualityFlag>,
+) -> Result<Vec<SkillQualityReportDto>, String> {
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(format_error)?;
+    let reports = skills
+        .iter()
+        .map(|skill| {
+            let source = resolve_skill_central_path(&skill.central_path, &central_dir);
+            quality::analyze_skill(skill, &source)
+        })
+        .collect();
+    Ok(quality::rank_reports(reports, flag))
+}
+
+#[tauri::command]
+pub async fn skills_get_disallowed_licenses(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<String>, String> {
+    Ok(provenance::load_disallowed_licenses(&state))
+}
+
+/// Licenses (SPDX ids, or `NOASSERTION` for unknown) that trigger an install warning.
+#[tauri::command]
+pub async fn skills_save_disallowed_licenses(
+    state: State<'_, SqliteDbState>,
+    licenses: Vec<String>,
+) -> Result<(), String> {
+    ensure_writable("skills_save_disallowed_licenses")?;
+    provenance::save_disallowed_licenses(&state, &licenses)
+}
@@ -0,0 +1,25 @@
+//! Commands for structured skill queries (`skill_query.rs`)
+
+use tauri::State;
+
+use super::commands::{managed_skill_dtos, normalize_optional_text};
+use super::skill_query::{self, SkillQuery};
+use super::types::ManagedSkillDto;
+use crate::SqliteDbState;
+
+/// Skills matching a structured query and/or a filter string
+/// (e.g. `tool:codex drift:drifted`); see `skill_query.rs`.
+#[tauri::command]
+pub async fn skills_query(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    query: Option<SkillQuery>,
+    filter: Option<String>,
+) -> Result<Vec<ManagedSkillDto>, String> {
+    let mut query = query.unwrap_or_default();
+    if let Some(filter) = normalize_optional_text(filter) {
+        query = query.merge(SkillQuery::parse_filter(&filter)?);
+    }
+    let skills = skill_query::query_skills(&state, &query)?;
+    managed_skill_dtos(&app, &state, skills).await
+}
@@ -0,0 +1,193 @@
+//! Scheduled skill rotation
+//!
+//! A rotation policy keeps only `active_count` skills of a group enabled at a
+//! time and advances the window every `interval_days`. Policies are stored in
+//! `skill_settings:skills.group_rotations` keyed by group id; the actual
+//! enable/disable + target sync is done by
+//! `rotation_commands::apply_skill_rotation_internal`.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::types::{now_ms, Skill};
+use crate::db::SqliteDbState;
+
+const ROTATIONS_SETTINGS_KEY: &str = "group_rotations";
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+fn default_active_count() -> u32 {
+    5
+}
+
+fn default_interval_days() -> u32 {
+    7
+}
+
+/// Rotation policy attached to a skill group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillRotationPolicy {
+    pub group_id: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_active_count")]
+    pub active_count: u32,
+    #[serde(default = "default_interval_days")]
+    pub interval_days: u32,
+    /// Index (in group sort order) of the first active skill
+    #[serde(default)]
+    pub cursor: usize,
+    #[serde(default)]
+    pub last_rotated_at: Option<i64>,
+}
+
+/// Result of enforcing or advancing a rotation window
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SkillRotationResultDto {
+    pub group_id: String,
+    pub active_skill_ids: Vec<String>,
+    pub activated: Vec<String>,
+    pub deactivated: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+pub fn load_rotation_policies(state: &SqliteDbState) -> BTreeMap<String, SkillRotationPolicy> {
+    read_skill_settings_value_from_sqlite(state, ROTATIONS_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_rotation_policies(
+    state: &SqliteDbState,
+    policies: &BTreeMap<String, SkillRotationPolicy>,
+) -> Result<(), String> {
+    let value = serde_json::to_value(policies)
+        .map_err(|e| format!("Failed to serialize rotation policies: {}", e))?;
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            ROTATIONS_SETTINGS_KEY: value,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Members of a rotation group in the group's display order
+pub fn rotation_members<'a>(skills: &'a [Skill], group_id: &str) -> Vec<&'a Skill> {
+    let mut members: Vec<&Skill> = skills
+        .iter()
+        .filter(|skill| skill.group_id.as_deref() == Some(group_id))
+        .collect();
+    members.sort_by(|a, b| a.sort_index.cmp(&b.sort_index).then(a.name.cmp(&b.name)));
+    members
+}
+
+/// Indexes of the active window, wrapping around the end of the group
+pub fn active_window(member_count: usize, cursor: usize, active_count: u32) -> Vec<usize> {
+    if member_count == 0 {
+        return Vec::new();
+    }
+    let count = (active_count as usize).min(member_count);
+    (0..count)
+        .map(|offset| (cursor + offset) % member_count)
+        .collect()
+}
+
+pub fn next_cursor(member_count: usize, cursor: usize, active_count: u32) -> usize {
+    if member_count == 0 {
+        return 0;
+    }
+    (cursor + active_count as usize) % member_count
+}
+
+pub fn is_rotation_due(policy: &SkillRotationPolicy, now: i64) -> bool {
+    match policy.last_rotated_at {
+        None => true,
+        Some(last) => now - last >= i64::from(policy.interval_days.max(1)) * DAY_MS,
+    }
+}
+
+/// Start the skill rotation scheduler as a background task
+pub fn start_skill_rotation_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Initial delay: let tool detection and the DB settle first
+        tokio::time::sleep(Duration::from_secs(90)).await;
+
+        info!("Skill rotation scheduler started");
+
+        loop {
+            // Check every 10 minutes
+            if let Err(e) = check_and_rotate(&app_handle).await {
+                warn!("Skill rotation check failed: {}", e);
+            }
+
+            tokio::time::sleep(Duration::from_secs(600)).await;
+        }
+    });
+}
+
+async fn check_and_rotate(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    if crate::settings::read_only::is_read_only() {
+        return Ok(());
+    }
+    let state = app_handle.state::<SqliteDbState>();
+    let now = now_ms();
+    let due: Vec<String> = load_rotation_policies(&state)
+        .into_values()
+        .filter(|policy| policy.enabled && is_rotation_due(policy, now))
+        .map(|policy| policy.group_id)
+        .collect();
+
+    for group_id in due {
+        let result = super::rotation_commands::apply_skill_rotation_internal(
+            app_handle, &state, &group_id, true,
+        )
+        .await?;
+        info!(
+            "Skill group {} rotated: {} activated, {} deactivated",
+            group_id,
+            result.activated.len(),
+            result.deactivated.len()
+        );
+        for error in &result.errors {
+            warn!("Skill rotation for group {}: {}", group_id, error);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_window_wraps_and_clamps() {
+        assert_eq!(active_window(20, 0, 5), vec![0, 1, 2, 3, 4]);
+        assert_eq!(active_window(7, 5, 4), vec![5, 6, 0, 1]);
+        assert_eq!(active_window(3, 1, 5), vec![1, 2, 0]);
+        assert!(active_window(0, 0, 5).is_empty());
+    }
+
+    #[test]
+    fn cursor_advances_by_window_size() {
+        assert_eq!(next_cursor(20, 0, 5), 5);
+        assert_eq!(next_cursor(20, 15, 5), 0);
+        assert_eq!(next_cursor(7, 5, 4), 2);
+    }
+
+    #[test]
+    fn rotation_due_uses_interval_days() {
+        let mut policy: SkillRotationPolicy =
+            serde_json::from_value(serde_json::json!({ "group_id": "g" })).unwrap();
+        assert_eq!(policy.active_count, 5);
+        assert!(is_rotation_due(&policy, 0));
+
+        policy.last_rotated_at = Some(0);
+        assert!(!is_rotation_due(&policy, 6 * DAY_MS));
+        assert!(is_rotation_due(&policy, 7 * DAY_MS));
+    }
+}
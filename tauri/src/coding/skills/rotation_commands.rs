@@ -0,0 +1,182 @@
+//! Commands for group rotation policies (`rotation.rs`)
+
+use std::collections::HashSet;
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::commands::{
+    disabled_previous_tools_for_skill, remove_skill_targets_best_effort, resolve_skill_source_path,
+    resolve_skill_source_path_for_cleanup, sync_skill_to_tool_record,
+};
+use super::rotation::{self, SkillRotationPolicy, SkillRotationResultDto};
+use super::skill_store;
+use super::types::now_ms;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Enforce a group's rotation window, optionally advancing it first.
+///
+/// Skills leaving the window are disabled the same way as a manual disable
+/// (targets removed, previous tools remembered); skills entering it are
+/// re-enabled and synced back to their remembered tools.
+pub async fn apply_skill_rotation_internal<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    group_id: &str,
+    advance: bool,
+) -> Result<SkillRotationResultDto, String> {
+    let mut policies = rotation::load_rotation_policies(state);
+    let mut policy = policies
+        .get(group_id)
+        .cloned()
+        .ok_or_else(|| format!("Rotation policy not found: {}", group_id))?;
+    let skills = skill_store::get_managed_skills(state).await?;
+    let members = rotation::rotation_members(&skills, group_id);
+
+    let now = now_ms();
+    if advance && policy.last_rotated_at.is_some() {
+        policy.cursor = rotation::next_cursor(members.len(), policy.cursor, policy.active_count);
+    } else if !members.is_empty() {
+        policy.cursor %= members.len();
+    }
+    let active_ids: HashSet<&str> =
+        rotation::active_window(members.len(), policy.cursor, policy.active_count)
+            .into_iter()
+            .map(|index| members[index].id.as_str())
+            .collect();
+
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    let mut result = SkillRotationResultDto {
+        group_id: group_id.to_string(),
+        ..Default::default()
+    };
+    for member in &members {
+        let should_be_active = active_ids.contains(member.id.as_str());
+        if should_be_active && !member.management_enabled {
+            let previous_tools =
+                skill_store::set_skill_management_enabled(state, &member.id, true).await?;
+            result.activated.push(member.id.clone());
+            let Some(skill) = skill_store::get_skill_by_id(state, &member.id).await? else {
+                continue;
+            };
+            let source_path = match resolve_skill_source_path(app, state, &skill).await {
+                Ok(path) => path,
+                Err(err) => {
+                    result.errors.push(format!("{}: {}", skill.name, err));
+                    continue;
+                }
+            };
+            for tool in previous_tools {
+                if let Err(err) = sync_skill_to_tool_record(
+                    state,
+                    &skill,
+                    &tool,
+                    &source_path,
+                    false,
+                    &custom_tools,
+                )
+                .await
+                {
+                    result
+                        .errors
+                        .push(format!("{} -> {}: {}", skill.name, tool, err));
+                }
+            }
+        } else if !should_be_active && member.management_enabled {
+            let previous_tools = disabled_previous_tools_for_skill(member);
+            let source_path = resolve_skill_source_path_for_cleanup(app, state, member).await;
+            remove_skill_targets_best_effort(state, member, source_path.as_deref()).await?;
+            skill_store::disable_skill_with_previous_tools(state, &member.id, previous_tools)
+                .await?;
+            result.deactivated.push(member.id.clone());
+        }
+    }
+    result.active_skill_ids = members
+        .iter()
+        .filter(|member| active_ids.contains(member.id.as_str()))
+        .map(|member| member.id.clone())
+        .collect();
+
+    if advance || policy.last_rotated_at.is_none() {
+        policy.last_rotated_at = Some(now);
+    }
+    policies.insert(group_id.to_string(), policy);
+    rotation::save_rotation_policies(state, &policies)?;
+
+    let _ = app.emit("skills-changed", "window");
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn skills_get_rotation_policies(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<SkillRotationPolicy>, String> {
+    Ok(rotation::load_rotation_policies(&state)
+        .into_values()
+        .collect())
+}
+
+/// Save a group's rotation policy. An enabled policy is enforced right away
+/// (without advancing), so the group immediately matches the window.
+#[tauri::command]
+pub async fn skills_save_rotation_policy<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    policy: SkillRotationPolicy,
+) -> Result<SkillRotationResultDto, String> {
+    ensure_writable("skills_save_rotation_policy")?;
+    if policy.active_count == 0 {
+        return Err("Rotation must keep at least one skill active".to_string());
+    }
+    let groups = skill_store::get_skill_groups(&state).await?;
+    if !groups.iter().any(|group| group.id == policy.group_id) {
+        return Err(format!("Skill group not found: {}", policy.group_id));
+    }
+
+    let mut policies = rotation::load_rotation_policies(&state);
+    let mut policy = policy;
+    if let Some(existing) = policies.get(&policy.group_id) {
+        policy.cursor = existing.cursor;
+        policy.last_rotated_at = existing.last_rotated_at;
+    }
+    let group_id = policy.group_id.clone();
+    let enabled = policy.enabled;
+    policies.insert(group_id.clone(), policy);
+    rotation::save_rotation_policies(&state, &policies)?;
+
+    if !enabled {
+        return Ok(SkillRotationResultDto {
+            group_id,
+            ..Default::default()
+        });
+    }
+    apply_skill_rotation_internal(&app, &state, &group_id, false).await
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_delete_rotation_policy(
+    state: State<'_, SqliteDbState>,
+    groupId: String,
+) -> Result<(), String> {
+    ensure_writable("skills_delete_rotation_policy")?;
+    let mut policies = rotation::load_rotation_policies(&state);
+    if policies.remove(&groupId).is_some() {
+        rotation::save_rotation_policies(&state, &policies)?;
+    }
+    Ok(())
+}
+
+/// Advance a group's rotation window immediately
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_rotate_group_now<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    groupId: String,
+) -> Result<SkillRotationResultDto, String> {
+    ensure_writable("skills_rotate_group_now")?;
+    apply_skill_rotation_internal(&app, &state, &groupId, true).await
+}
@@ -0,0 +1,45 @@
+//! Saved global ignore patterns for copy sync
+//!
+//! The matching itself lives in `ai_toolbox_core::skills::skill_ignore`;
+//! this module keeps the global list in
+//! `skill_settings:skills.copy_ignore_patterns` and loads it into the app's
+//! `SyncContext`.
+
+pub use ai_toolbox_core::skills::skill_ignore::*;
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::sync_context::set_ignore_patterns;
+use super::types::now_ms;
+use crate::SqliteDbState;
+
+const COPY_IGNORE_PATTERNS_KEY: &str = "copy_ignore_patterns";
+
+fn saved_patterns(state: &SqliteDbState) -> Option<Vec<String>> {
+    read_skill_settings_value_from_sqlite(state, COPY_IGNORE_PATTERNS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+pub fn load_copy_ignore_patterns(state: &SqliteDbState) -> Vec<String> {
+    saved_patterns(state).unwrap_or_else(default_patterns)
+}
+
+/// Load the saved global patterns into the app's `SyncContext`
+pub fn init_from_settings(state: &SqliteDbState) {
+    set_ignore_patterns(load_copy_ignore_patterns(state));
+}
+
+pub fn save_copy_ignore_patterns(
+    state: &SqliteDbState,
+    patterns: &[String],
+) -> Result<Vec<String>, String> {
+    let patterns = normalize_patterns(patterns)?;
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            COPY_IGNORE_PATTERNS_KEY: patterns,
+            "updated_at": now_ms(),
+        }),
+    )?;
+    set_ignore_patterns(patterns.clone());
+    Ok(patterns)
+}
@@ -0,0 +1,401 @@
+//! Structured skill queries for advanced filters
+//!
+//! The frontend (and the `--filter` flag of a companion CLI) never sends SQL:
+//! it sends a `SkillQuery` or a filter string such as
+//! `tool:claude_code,codex drift:drifted source:git synced-before:2026-01-01 group:"My Group"`,
+//! which is compiled here into a parameterized WHERE clause over the `skill`
+//! table. Only fixed SQL fragments are concatenated; every user value is bound.
+//!
+//! Filters are AND-ed; values inside one filter are OR-ed.
+
+use chrono::{Local, NaiveDate, TimeZone};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::ToSql;
+use serde::{Deserialize, Serialize};
+
+use super::adapter::from_db_skill;
+use super::types::Skill;
+use crate::db::helpers::db_query_where;
+use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
+use crate::SqliteDbState;
+
+/// Target state derived from `enabled_tools` + `sync_details`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillDriftState {
+    /// Has targets, all `ok`, and every enabled tool has a target
+    InSync,
+    /// Some target is not `ok`, or an enabled tool has no target
+    Drifted,
+    /// No targets recorded at all
+    Unsynced,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillQuery {
+    /// Any of these tools is enabled
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub drift: Option<SkillDriftState>,
+    /// `local` | `git` | `import` | `central` | `archive` ...
+    #[serde(default)]
+    pub source_types: Vec<String>,
+    /// Last sync earlier than this (ms); never-synced skills match too
+    #[serde(default)]
+    pub last_sync_before: Option<i64>,
+    /// Group ids or names
+    #[serde(default)]
+    pub groups: Vec<String>,
+    /// Any of these tags is set
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub struct CompiledSkillQuery {
+    pub where_sql: String,
+    pub params: Vec<SqlValue>,
+}
+
+const DRIFTED_SQL: &str = "(EXISTS (SELECT 1 FROM json_each(data, '$.sync_details') AS target \
+     WHERE json_extract(target.value, '$.status') IS NOT 'ok') \
+     OR EXISTS (SELECT 1 FROM json_each(data, '$.enabled_tools') AS tool \
+     WHERE tool.value NOT IN (SELECT key FROM json_each(data, '$.sync_details'))))";
+const HAS_TARGETS_SQL: &str = "EXISTS (SELECT 1 FROM json_each(data, '$.sync_details'))";
+
+struct Builder {
+    clauses: Vec<String>,
+    params: Vec<SqlValue>,
+}
+
+impl Builder {
+    /// `?N, ?N+1, ...` placeholders for `values`
+    fn bind_list(&mut self, values: &[String]) -> String {
+        values
+            .iter()
+            .map(|value| {
+                self.params.push(SqlValue::Text(value.clone()));
+                format!("?{}", self.params.len())
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn bind(&mut self, value: SqlValue) -> String {
+        self.params.push(value);
+        format!("?{}", self.params.len())
+    }
+}
+
+fn non_empty(values: &[String]) -> Vec<String> {
+    values
+        .iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+pub fn compile_skill_query(query: &SkillQuery) -> CompiledSkillQuery {
+    let mut builder = Builder {
+        clauses: Vec::new(),
+        params: Vec::new(),
+    };
+
+    let tools = non_empty(&query.tools);
+    if !tools.is_empty() {
+        let list = builder.bind_list(&tools);
+        builder.clauses.push(format!(
+            "EXISTS (SELECT 1 FROM json_each(data, '$.enabled_tools') WHERE value IN ({list}))"
+        ));
+    }
+
+    match query.drift {
+        Some(SkillDriftState::InSync) => builder
+            .clauses
+            .push(format!("({HAS_TARGETS_SQL} AND NOT {DRIFTED_SQL})")),
+        Some(SkillDriftState::Drifted) => builder.clauses.push(DRIFTED_SQL.to_string()),
+        Some(SkillDriftState::Unsynced) => builder.clauses.push(format!("NOT {HAS_TARGETS_SQL}")),
+        None => {}
+    }
+
+    let source_types = non_empty(&query.source_types);
+    if !source_types.is_empty() {
+        let list = builder.bind_list(&source_types);
+        builder
+            .clauses
+            .push(format!("json_extract(data, '$.source_type') IN ({list})"));
+    }
+
+    if let Some(before) = query.last_sync_before {
+        let param = builder.bind(SqlValue::Integer(before));
+        builder.clauses.push(format!(
+            "(json_extract(data, '$.last_sync_at') IS NULL OR json_extract(data, '$.last_sync_at') < {param})"
+        ));
+    }
+
+    let groups = non_empty(&query.groups);
+    if !groups.is_empty() {
+        let list = builder.bind_list(&groups);
+        builder.clauses.push(format!(
+            "(json_extract(data, '$.group_id') IN ({list}) \
+             OR json_extract(data, '$.user_group') IN ({list}) \
+             OR json_extract(data, '$.group_id') IN \
+             (SELECT id FROM skill_group WHERE json_extract(data, '$.name') IN ({list})))"
+        ));
+    }
+
+    let tags = non_empty(&query.tags);
+    if !tags.is_empty() {
+        let list = builder.bind_list(&tags);
+        builder.clauses.push(format!(
+            "EXISTS (SELECT 1 FROM json_each(data, '$.tags') WHERE value IN ({list}))"
+        ));
+    }
+
+    CompiledSkillQuery {
+        where_sql: if builder.clauses.is_empty() {
+            "1 = 1".to_string()
+        } else {
+            builder.clauses.join(" AND ")
+        },
+        params: builder.params,
+    }
+}
+
+pub fn query_skills(state: &SqliteDbState, query: &SkillQuery) -> Result<Vec<Skill>, String> {
+    let compiled = compile_skill_query(query);
+    let params: Vec<&dyn ToSql> = compiled
+        .params
+        .iter()
+        .map(|value| value as &dyn ToSql)
+        .collect();
+    let order = OrderSpec::single(OrderField::json_integer("sort_index", OrderDirection::Asc)?);
+    state.with_conn(|conn| {
+        Ok(db_query_where(
+            conn,
+            DbTable::Skill,
+            &compiled.where_sql,
+            &params,
+            Some(&order),
+            query.limit,
+        )?
+        .into_iter()
+        .map(from_db_skill)
+        .collect())
+    })
+}
+
+/// Split filter values on commas outside double quotes, dropping the quotes
+fn split_values(input: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && c == ',' {
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Keep the quotes so values can be split on commas afterwards
+fn split_terms(input: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        if !quoted && c.is_whitespace() {
+            if !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    terms
+}
+
+/// `2026-01-01` (local midnight) or a millisecond timestamp
+fn parse_timestamp(value: &str) -> Result<i64, String> {
+    if let Ok(ms) = value.parse::<i64>() {
+        return Ok(ms);
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date (expected YYYY-MM-DD): {}", value))?;
+    let midnight = date
+        .and_hms_opt(0, 0, 0)
+        .and_then(|time| Local.from_local_datetime(&time).earliest())
+        .ok_or_else(|| format!("Invalid date: {}", value))?;
+    Ok(midnight.timestamp_millis())
+}
+
+impl SkillQuery {
+    /// Parse a filter string: space-separated `key:value[,value]` terms.
+    /// Keys: `tool`, `drift` (`in_sync` | `drifted` | `unsynced`), `source`,
+    /// `synced-before`, `group`, `tag`. Quote values containing spaces or commas.
+    pub fn parse_filter(filter: &str) -> Result<Self, String> {
+        let mut query = SkillQuery::default();
+        for term in split_terms(filter) {
+            let (key, raw) = term
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid filter term (expected key:value): {}", term))?;
+            let values = split_values(raw);
+            let single = || {
+                values
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| format!("Missing value for filter: {}", key))
+            };
+            match key {
+                "tool" => query.tools.extend(values.iter().cloned()),
+                "source" => query.source_types.extend(values.iter().cloned()),
+                "group" => query.groups.extend(values.iter().cloned()),
+                "tag" => query.tags.extend(values.iter().cloned()),
+                "drift" => {
+                    query.drift = Some(
+                        serde_json::from_value(serde_json::Value::String(single()?))
+                            .map_err(|_| format!("Unknown drift state: {}", raw))?,
+                    )
+                }
+                "synced-before" => query.last_sync_before = Some(parse_timestamp(&single()?)?),
+                "limit" => {
+                    query.limit = Some(
+                        single()?
+                            .parse()
+                            .map_err(|_| format!("Invalid limit: {}", raw))?,
+                    )
+                }
+                _ => return Err(format!("Unknown filter key: {}", key)),
+            }
+        }
+        Ok(query)
+    }
+
+    /// Combine with another query; list filters are concatenated, the other side wins otherwise
+    pub fn merge(mut self, other: SkillQuery) -> Self {
+        self.tools.extend(other.tools);
+        self.source_types.extend(other.source_types);
+        self.groups.extend(other.groups);
+        self.tags.extend(other.tags);
+        self.drift = other.drift.or(self.drift);
+        self.last_sync_before = other.last_sync_before.or(self.last_sync_before);
+        self.limit = other.limit.or(self.limit);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::{from_db_skill, to_clean_skill_payload};
+    use crate::db::helpers::db_put;
+    use serde_json::json;
+
+    fn skill(
+        id: &str,
+        source_type: &str,
+        tools: &[&str],
+        sync_details: serde_json::Value,
+    ) -> Skill {
+        from_db_skill(json!({
+            "id": id,
+            "name": id,
+            "source_type": source_type,
+            "central_path": id,
+            "last_sync_at": 1_000,
+            "status": "ok",
+            "group_id": "g1",
+            "enabled_tools": tools,
+            "sync_details": sync_details,
+        }))
+    }
+
+    fn ids(state: &SqliteDbState, query: &SkillQuery) -> Vec<String> {
+        let mut ids: Vec<String> = query_skills(state, query)
+            .unwrap()
+            .into_iter()
+            .map(|skill| skill.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn queries_filter_by_tool_drift_source_and_group() {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        let ok = json!({"codex": {"tool": "codex", "target_path": "/t", "mode": "symlink", "status": "ok", "synced_at": 1}});
+        let broken = json!({"codex": {"tool": "codex", "target_path": "/t", "mode": "copy", "status": "error", "synced_at": 1}});
+        let mut skills = [
+            skill("a", "git", &["codex"], ok.clone()),
+            skill("b", "local", &["codex"], broken),
+            skill("c", "git", &["codex", "claude_code"], ok),
+            skill("d", "local", &[], json!({})),
+        ];
+        skills[2].tags = vec!["review".to_string()];
+        state
+            .with_conn(|conn| {
+                for skill in &skills {
+                    db_put(
+                        conn,
+                        DbTable::Skill,
+                        &skill.id,
+                        &to_clean_skill_payload(skill),
+                    )?;
+                }
+                db_put(
+                    conn,
+                    DbTable::SkillGroup,
+                    "g1",
+                    &json!({"name": "Work Stuff", "sort_index": 0}),
+                )
+            })
+            .unwrap();
+
+        let drift = |state| SkillQuery {
+            drift: Some(state),
+            ..Default::default()
+        };
+        assert_eq!(ids(&state, &drift(SkillDriftState::InSync)), ["a"]);
+        assert_eq!(ids(&state, &drift(SkillDriftState::Drifted)), ["b", "c"]);
+        assert_eq!(ids(&state, &drift(SkillDriftState::Unsynced)), ["d"]);
+
+        let query = SkillQuery::parse_filter(
+            "tool:claude_code,codex source:git group:\"Work Stuff\" synced-before:2000",
+        )
+        .unwrap();
+        assert_eq!(ids(&state, &query), ["a", "c"]);
+        let tagged = SkillQuery::parse_filter("tag:review,docs").unwrap();
+        assert_eq!(ids(&state, &tagged), ["c"]);
+        // Values are bound, never spliced into SQL
+        let hostile = SkillQuery {
+            tools: vec!["codex') OR 1=1 --".to_string()],
+            ..Default::default()
+        };
+        assert!(ids(&state, &hostile).is_empty());
+    }
+
+    #[test]
+    fn filter_string_errors_are_reported() {
+        assert!(SkillQuery::parse_filter("color:red").is_err());
+        assert!(SkillQuery::parse_filter("drift:sideways").is_err());
+        assert!(SkillQuery::parse_filter("synced-before:yesterday").is_err());
+        assert_eq!(SkillQuery::parse_filter("").unwrap(), SkillQuery::default());
+    }
+}
@@ -1,5 +1,5 @@
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use crate::coding::db_id::db_new_id;
 use crate::db::helpers::{
@@ -14,8 +14,14 @@ use super::adapter::{
     to_clean_skill_payload, to_skill_group_payload, to_skill_preferences_payload,
     to_skill_repo_payload,
 };
+use super::archive::ARCHIVED_STATUS;
+use super::localization;
+use super::tags::apply_tag_edit;
 use super::tool_adapters::CustomTool;
-use super::types::{now_ms, Skill, SkillGroupRecord, SkillPreferences, SkillRepo, SkillTarget};
+use super::types::{
+    now_ms, Skill, SkillGroupRecord, SkillLocalization, SkillPreferences, SkillRepo, SkillTarget,
+};
+use super::unicode_names::nfc_name;
 
 const SKILL_PREFERENCES_ID: &str = "default";
 
@@ -290,7 +296,7 @@ pub async fn upsert_skill(state: &SqliteDbState, skill: &Skill) -> Result<String
 
 /// Get a skill by name
 pub async fn get_skill_by_name(state: &SqliteDbState, name: &str) -> Result<Option<Skill>, String> {
-    let name_value = Value::String(name.to_string());
+    let name_value = Value::String(nfc_name(name));
     state.with_conn(|conn| {
         Ok(db_query_by_field(
             conn,
@@ -373,6 +379,87 @@ pub async fn update_skills_group(
     Ok(())
 }
 
+/// Add / remove tags on several skills; returns the number of skills changed
+pub async fn update_skills_tags(
+    state: &SqliteDbState,
+    skill_ids: &[String],
+    add: &[String],
+    remove: &[String],
+) -> Result<usize, String> {
+    let mut changed = 0;
+    for skill_id in skill_ids {
+        let Some(skill) = sqlite_get_skill_by_id(state, skill_id)? else {
+            continue;
+        };
+        if let Some(tags) = apply_tag_edit(&skill.tags, add, remove) {
+            sqlite_patch_skill(state, skill_id, |skill| skill.tags = tags)?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Replace the user-set tool version constraints of a skill
+pub async fn update_skill_compatibility(
+    state: &SqliteDbState,
+    skill_id: &str,
+    compatibility: BTreeMap<String, String>,
+) -> Result<(), String> {
+    sqlite_patch_skill(state, skill_id, |skill| {
+        skill.compatibility = compatibility.clone();
+    })?
+    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    Ok(())
+}
+
+/// Replace the localized display names and descriptions of a skill
+pub async fn update_skill_localizations(
+    state: &SqliteDbState,
+    skill_id: &str,
+    localizations: BTreeMap<String, SkillLocalization>,
+) -> Result<(), String> {
+    sqlite_patch_skill(state, skill_id, |skill| {
+        skill.localizations = localizations.clone();
+    })?
+    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    Ok(())
+}
+
+/// Record whether the central directory of a skill is kept read-only
+pub async fn update_skill_write_protected(
+    state: &SqliteDbState,
+    skill_id: &str,
+    write_protected: bool,
+) -> Result<(), String> {
+    sqlite_patch_skill(state, skill_id, |skill| {
+        skill.write_protected = write_protected;
+    })?
+    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    Ok(())
+}
+
+/// Fill empty localizations of several skills (all when `skill_ids` is
+/// empty) from the built-in dictionary; returns the number of skills changed
+pub async fn fill_skills_localizations(
+    state: &SqliteDbState,
+    skill_ids: &[String],
+) -> Result<usize, String> {
+    let mut changed = 0;
+    for skill in get_managed_skills(state).await? {
+        if !skill_ids.is_empty() && !skill_ids.contains(&skill.id) {
+            continue;
+        }
+        let mut localizations = skill.localizations.clone();
+        if localization::fill_from_dictionary(&skill.name, &mut localizations) {
+            sqlite_patch_skill(state, &skill.id, |skill| {
+                skill.localizations = localizations
+            })?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
 async fn group_name_for_id(
     state: &SqliteDbState,
     group_id: Option<String>,
@@ -396,6 +483,9 @@ pub async fn set_skill_management_enabled(
         return Err(format!("Skill not found: {}", skill_id));
     };
     if enabled {
+        if skill.status == ARCHIVED_STATUS {
+            return Err(format!("SKILL_ARCHIVED|{}", skill_id));
+        }
         sqlite_patch_skill(state, skill_id, |skill| {
             skill.management_enabled = true;
         })?
@@ -443,6 +533,38 @@ pub async fn disable_skill_with_previous_tools(
     Ok(())
 }
 
+/// Mark a skill archived: disabled, no targets, previous tools remembered
+pub async fn archive_skill_record(
+    state: &SqliteDbState,
+    skill_id: &str,
+    previous_tools: Vec<String>,
+) -> Result<(), String> {
+    sqlite_patch_skill(state, skill_id, |skill| {
+        skill.status = ARCHIVED_STATUS.to_string();
+        skill.management_enabled = false;
+        skill.disabled_previous_tools = previous_tools.clone();
+        skill.enabled_tools = Vec::new();
+        skill.sync_details = Some(Value::Object(serde_json::Map::new()));
+        skill.updated_at = now_ms();
+    })?
+    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    Ok(())
+}
+
+/// Clear the archived mark and re-enable management; returns the tools to re-sync
+pub async fn unarchive_skill_record(
+    state: &SqliteDbState,
+    skill_id: &str,
+) -> Result<Vec<String>, String> {
+    let skill = sqlite_patch_skill(state, skill_id, |skill| {
+        skill.status = "ok".to_string();
+        skill.management_enabled = true;
+        skill.updated_at = now_ms();
+    })?
+    .ok_or_else(|| format!("Skill not found: {}", skill_id))?;
+    Ok(skill.disabled_previous_tools)
+}
+
 // ==================== Skill sync_details operations ====================
 
 /// Get all targets for a specific skill (parsed from sync_details)
@@ -669,6 +791,7 @@ pub async fn save_custom_tool(state: &SqliteDbState, tool: &CustomTool) -> Resul
         Some(tool.relative_skills_dir.clone()),
         Some(tool.relative_detect_dir.clone()),
         tool.force_copy,
+        tool.preferred_sync_mode.clone(),
         tool.created_at,
     )
     .await
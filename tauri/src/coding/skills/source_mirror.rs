@@ -0,0 +1,187 @@
+//! Mirror + checksum policy for remote skill sources
+//!
+//! When enabled, git clones and archive downloads are rewritten to
+//! `{base_url}/{host}/{path}` of the upstream URL, so installs go through an
+//! internal mirror. Expected checksums are keyed by the skill's upstream
+//! `source_ref` (the URL recorded on the skill) and verified before the
+//! content is adopted into the central repo:
+//! - git: the skill directory content hash (same value as `content_hash`)
+//! - archive: SHA-256 of the downloaded archive file
+//!
+//! Stored in `skill_settings:skills.source_mirror`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::types::now_ms;
+use crate::SqliteDbState;
+
+const MIRROR_SETTINGS_KEY: &str = "source_mirror";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SkillSourceMirror {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Mirror root, e.g. `https://mirror.internal/skills`
+    #[serde(default)]
+    pub base_url: String,
+    /// Upstream source URL -> expected checksum (`sha256:<hex>` or hex)
+    #[serde(default)]
+    pub checksums: BTreeMap<String, String>,
+    /// Refuse remote installs/updates without a configured checksum
+    #[serde(default)]
+    pub require_checksum: bool,
+}
+
+pub fn load_source_mirror(state: &SqliteDbState) -> SkillSourceMirror {
+    read_skill_settings_value_from_sqlite(state, MIRROR_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_source_mirror(state: &SqliteDbState, mirror: &SkillSourceMirror) -> Result<(), String> {
+    let value = serde_json::to_value(mirror)
+        .map_err(|e| format!("Failed to serialize source mirror: {}", e))?;
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            MIRROR_SETTINGS_KEY: value,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Key used for checksum lookups: trimmed, no trailing `/` or `.git`
+pub fn source_key(url: &str) -> String {
+    let trimmed = url.trim().trim_end_matches('/');
+    trimmed.strip_suffix(".git").unwrap_or(trimmed).to_string()
+}
+
+/// `host/path` part of an http(s) or scp-style git URL
+fn host_and_path(url: &str) -> Option<String> {
+    let url = url.trim();
+    if let Some((_, rest)) = url.split_once("://") {
+        // Drop credentials (`user@host`)
+        let rest = rest.rsplit_once('@').map_or(rest, |(_, host)| host);
+        return Some(rest.trim_start_matches('/').to_string());
+    }
+    // git@github.com:owner/repo.git
+    let (user_host, path) = url.split_once(':')?;
+    let host = user_host
+        .rsplit_once('@')
+        .map_or(user_host, |(_, host)| host);
+    Some(format!("{}/{}", host, path.trim_start_matches('/')))
+}
+
+impl SkillSourceMirror {
+    fn active(&self) -> bool {
+        self.enabled && !self.base_url.trim().is_empty()
+    }
+
+    /// Rewrite an upstream URL to the mirror (unchanged when disabled)
+    pub fn mirror_url(&self, upstream: &str) -> String {
+        if !self.active() {
+            return upstream.to_string();
+        }
+        match host_and_path(upstream) {
+            Some(host_path) => format!(
+                "{}/{}",
+                self.base_url.trim().trim_end_matches('/'),
+                host_path
+            ),
+            None => upstream.to_string(),
+        }
+    }
+
+    /// Expected checksum for an upstream source. An explicit checksum wins;
+    /// with `require_checksum` a missing one is an error.
+    pub fn expected_checksum(
+        &self,
+        source_ref: &str,
+        explicit: Option<&str>,
+    ) -> Result<Option<String>, String> {
+        if let Some(explicit) = explicit.filter(|value| !value.trim().is_empty()) {
+            return Ok(Some(explicit.to_string()));
+        }
+        if !self.enabled {
+            return Ok(None);
+        }
+        let key = source_key(source_ref);
+        let configured = self
+            .checksums
+            .iter()
+            .find(|(url, _)| source_key(url) == key)
+            .map(|(_, checksum)| checksum.clone());
+        if configured.is_none() && self.require_checksum {
+            return Err(format!("CHECKSUM_REQUIRED|{}", source_ref));
+        }
+        Ok(configured)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirror() -> SkillSourceMirror {
+        SkillSourceMirror {
+            enabled: true,
+            base_url: "https://mirror.internal/skills/".to_string(),
+            checksums: BTreeMap::from([(
+                "https://github.com/acme/skills/tree/main/pdf".to_string(),
+                "sha256:abc".to_string(),
+            )]),
+            require_checksum: false,
+        }
+    }
+
+    #[test]
+    fn urls_are_rewritten_by_host_and_path() {
+        let mirror = mirror();
+        assert_eq!(
+            mirror.mirror_url("https://github.com/acme/skills.git"),
+            "https://mirror.internal/skills/github.com/acme/skills.git"
+        );
+        assert_eq!(
+            mirror.mirror_url("git@github.com:acme/skills.git"),
+            "https://mirror.internal/skills/github.com/acme/skills.git"
+        );
+        assert_eq!(
+            SkillSourceMirror::default().mirror_url("https://github.com/acme/skills"),
+            "https://github.com/acme/skills"
+        );
+    }
+
+    #[test]
+    fn checksum_lookup_and_requirement() {
+        let mut mirror = mirror();
+        assert_eq!(
+            mirror
+                .expected_checksum("https://github.com/acme/skills/tree/main/pdf/", None)
+                .unwrap()
+                .as_deref(),
+            Some("sha256:abc")
+        );
+        assert_eq!(
+            mirror
+                .expected_checksum("https://github.com/acme/other", Some("sha256:def"))
+                .unwrap()
+                .as_deref(),
+            Some("sha256:def")
+        );
+        assert!(mirror
+            .expected_checksum("https://github.com/acme/other", None)
+            .unwrap()
+            .is_none());
+
+        mirror.require_checksum = true;
+        assert_eq!(
+            mirror
+                .expected_checksum("https://github.com/acme/other", None)
+                .unwrap_err(),
+            "CHECKSUM_REQUIRED|https://github.com/acme/other"
+        );
+    }
+}
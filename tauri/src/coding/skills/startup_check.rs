@@ -0,0 +1,248 @@
+//! Startup consistency check
+//!
+//! Once per launch, after any post-restore resync, the library audit runs
+//! together with checks that the settings records are readable and the
+//! central repo exists, so silent corruption shows up at startup instead of
+//! failing a later sync. The `startup_check_level` setting decides which
+//! audit fixes are applied without asking:
+//! - `report`: none, the summary only lists the issues
+//! - `safe`: fixes that only make records match the disk or add records:
+//!   `record_mode`, `adopt_dir` and `drop_target` of a target that is gone
+//! - `full`: also `delete_record` of ghost skills and `drop_target` of
+//!   duplicate claims, which pick a winner heuristically
+//!
+//! Nothing is fixed when the central repo is missing (an unmounted drive
+//! would otherwise turn every skill into a ghost), when settings are
+//! unreadable, or in read-only mode. The summary is emitted as
+//! `skills-startup-check` and kept for `skills_get_startup_check_summary`,
+//! since the window may not be listening yet.
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use super::audit::{AuditFixAction, AuditIssueKind, LibraryAuditIssue};
+use super::audit_commands::{apply_library_audit_fix, build_library_audit};
+use super::central_repo::load_authoritative_central_repo_path_sync;
+use super::types::now_ms;
+use crate::db::SqliteDbState;
+use crate::settings::store;
+
+static LAST_SUMMARY: Mutex<Option<StartupCheckSummary>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupFixLevel {
+    Report,
+    Safe,
+    Full,
+}
+
+impl StartupFixLevel {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "safe" => StartupFixLevel::Safe,
+            "full" => StartupFixLevel::Full,
+            _ => StartupFixLevel::Report,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StartupCheckSummary {
+    pub level: StartupFixLevel,
+    pub checked_at: i64,
+    pub duration_ms: u64,
+    pub checked_skills: usize,
+    pub checked_targets: usize,
+    /// Settings records that could not be read; defaults are in use
+    pub settings_errors: Vec<String>,
+    pub central_repo_missing: bool,
+    /// Issues left for the user to review in the library audit
+    pub issues: Vec<LibraryAuditIssue>,
+    pub fixed: Vec<LibraryAuditIssue>,
+    pub errors: Vec<String>,
+    /// Fixes were due but skipped because read-only mode is on
+    pub read_only: bool,
+}
+
+impl StartupCheckSummary {
+    fn is_clean(&self) -> bool {
+        self.settings_errors.is_empty()
+            && !self.central_repo_missing
+            && self.issues.is_empty()
+            && self.fixed.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+/// Whether `level` applies the fix of `issue` without asking
+pub fn is_auto_fixable(issue: &LibraryAuditIssue, level: StartupFixLevel) -> bool {
+    match level {
+        StartupFixLevel::Report => false,
+        StartupFixLevel::Safe => match issue.fix {
+            AuditFixAction::RecordMode | AuditFixAction::AdoptDir => true,
+            AuditFixAction::DropTarget => issue.kind == AuditIssueKind::MissingTarget,
+            AuditFixAction::DeleteRecord => false,
+        },
+        StartupFixLevel::Full => true,
+    }
+}
+
+/// Summary of this launch's check, `None` until it has run
+pub fn last_summary() -> Option<StartupCheckSummary> {
+    LAST_SUMMARY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+fn settings_errors(state: &SqliteDbState) -> Vec<String> {
+    let mut errors = Vec::new();
+    if let Err(err) = store::check_settings_record(state) {
+        errors.push(format!("App settings are unreadable: {}", err));
+    }
+    if let Err(err) = load_authoritative_central_repo_path_sync(state) {
+        errors.push(format!(
+            "Skill settings are unreadable, the default central repo is used: {}",
+            err
+        ));
+    }
+    errors
+}
+
+async fn check(app_handle: &tauri::AppHandle) -> Result<StartupCheckSummary, String> {
+    let started = Instant::now();
+    let state = app_handle.state::<SqliteDbState>();
+    let settings_errors = settings_errors(&state);
+    let level = store::load_settings_from_sqlite_state(&state)
+        .map(|settings| StartupFixLevel::parse(&settings.startup_check_level))
+        .unwrap_or(StartupFixLevel::Report);
+
+    let (central_dir, report) = build_library_audit(app_handle, &state).await?;
+    let central_repo_missing = !central_dir.is_dir();
+    let read_only = crate::settings::read_only::is_read_only();
+    let can_fix = !central_repo_missing && settings_errors.is_empty() && !read_only;
+
+    let mut summary = StartupCheckSummary {
+        level,
+        checked_at: report.checked_at,
+        duration_ms: 0,
+        checked_skills: report.checked_skills,
+        checked_targets: report.checked_targets,
+        settings_errors,
+        central_repo_missing,
+        issues: Vec::new(),
+        fixed: Vec::new(),
+        errors: Vec::new(),
+        read_only: false,
+    };
+    for issue in report.issues {
+        if !is_auto_fixable(&issue, level) {
+            summary.issues.push(issue);
+            continue;
+        }
+        if !can_fix {
+            summary.read_only |= read_only;
+            summary.issues.push(issue);
+            continue;
+        }
+        match apply_library_audit_fix(&state, &central_dir, &issue).await {
+            Ok(()) => summary.fixed.push(issue),
+            Err(err) => {
+                summary.errors.push(format!("{}: {}", issue.path, err));
+                summary.issues.push(issue);
+            }
+        }
+    }
+    summary.duration_ms = started.elapsed().as_millis() as u64;
+    Ok(summary)
+}
+
+/// Run the check once and publish its summary
+pub async fn run_startup_check(app_handle: &tauri::AppHandle) {
+    let summary = match check(app_handle).await {
+        Ok(summary) => summary,
+        Err(err) => {
+            warn!("Startup consistency check failed: {}", err);
+            StartupCheckSummary {
+                level: StartupFixLevel::Report,
+                checked_at: now_ms(),
+                duration_ms: 0,
+                checked_skills: 0,
+                checked_targets: 0,
+                settings_errors: Vec::new(),
+                central_repo_missing: false,
+                issues: Vec::new(),
+                fixed: Vec::new(),
+                errors: vec![err],
+                read_only: false,
+            }
+        }
+    };
+
+    if summary.is_clean() {
+        info!(
+            "Startup consistency check passed: {} skills, {} targets in {} ms",
+            summary.checked_skills, summary.checked_targets, summary.duration_ms
+        );
+    } else {
+        warn!(
+            "Startup consistency check: {} issue(s) left, {} fixed, {} error(s), {} settings error(s)",
+            summary.issues.len(),
+            summary.fixed.len(),
+            summary.errors.len(),
+            summary.settings_errors.len()
+        );
+    }
+    if !summary.fixed.is_empty() {
+        let _ = app_handle.emit("skills-changed", "window");
+    }
+    let _ = app_handle.emit("skills-startup-check", &summary);
+    *LAST_SUMMARY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(summary);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(kind: AuditIssueKind, fix: AuditFixAction) -> LibraryAuditIssue {
+        LibraryAuditIssue {
+            kind,
+            fix,
+            skill_id: Some("skill".to_string()),
+            skill_name: Some("skill".to_string()),
+            tool: Some("codex".to_string()),
+            path: "/tools/codex/skill".to_string(),
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn fix_levels_gate_destructive_fixes() {
+        let missing = issue(AuditIssueKind::MissingTarget, AuditFixAction::DropTarget);
+        let duplicate = issue(AuditIssueKind::DuplicateTarget, AuditFixAction::DropTarget);
+        let ghost = issue(AuditIssueKind::GhostSkill, AuditFixAction::DeleteRecord);
+        let mode = issue(AuditIssueKind::MismatchedMode, AuditFixAction::RecordMode);
+        let orphan = issue(AuditIssueKind::OrphanDir, AuditFixAction::AdoptDir);
+
+        let report = StartupFixLevel::parse("unknown");
+        assert_eq!(report, StartupFixLevel::Report);
+        for item in [&missing, &duplicate, &ghost, &mode, &orphan] {
+            assert!(!is_auto_fixable(item, report));
+            assert!(is_auto_fixable(item, StartupFixLevel::parse("full")));
+        }
+
+        let safe = StartupFixLevel::parse("safe");
+        assert!(is_auto_fixable(&missing, safe));
+        assert!(is_auto_fixable(&mode, safe));
+        assert!(is_auto_fixable(&orphan, safe));
+        assert!(!is_auto_fixable(&duplicate, safe));
+        assert!(!is_auto_fixable(&ghost, safe));
+    }
+}
@@ -0,0 +1,169 @@
+//! Disk usage report for the skills library
+//!
+//! Sizes are measured by walking the directories (links are not followed), so
+//! a linked target costs nothing while a copy-mode target is counted in full.
+//! There is no trash for skills: deletes remove directories directly, so the
+//! report only covers the central repo, copy-mode targets, the git cache and
+//! the snapshot store (config snapshots and skill variants).
+
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::adapter::parse_sync_details;
+use super::central_repo::resolve_skill_central_path;
+use super::consolidate::dir_size;
+use super::types::{now_ms, Skill};
+
+/// Number of entries listed in `link_savings`
+const LINK_SAVINGS_LIMIT: usize = 10;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillStorageDto {
+    pub skill_id: String,
+    pub name: String,
+    /// Size of the skill directory in the central repo
+    pub central_bytes: u64,
+    /// Tools whose target is a full copy instead of a link
+    pub copy_tools: Vec<String>,
+    /// Bytes held by copy-mode targets
+    pub duplicated_bytes: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StorageReportDto {
+    /// Sorted by `central_bytes`, largest first
+    pub skills: Vec<SkillStorageDto>,
+    pub central_bytes: u64,
+    pub duplicated_bytes: u64,
+    pub git_cache_bytes: u64,
+    pub variant_snapshot_bytes: u64,
+    pub config_snapshot_bytes: u64,
+    /// Skills that would free the most space if their copy-mode tools used links
+    pub link_savings: Vec<SkillStorageDto>,
+    pub generated_at: i64,
+}
+
+/// Locations outside the central repo that are included in the report
+#[derive(Default)]
+pub struct StorageRoots {
+    pub git_cache: Option<PathBuf>,
+    pub snapshots: Option<PathBuf>,
+    pub variants: Option<PathBuf>,
+}
+
+pub fn build_storage_report(
+    skills: &[Skill],
+    central_dir: &Path,
+    roots: &StorageRoots,
+) -> StorageReportDto {
+    let size_of = |path: &Option<PathBuf>| path.as_deref().map(dir_size).unwrap_or(0);
+
+    let mut entries = skills
+        .iter()
+        .map(|skill| {
+            let mut entry = SkillStorageDto {
+                skill_id: skill.id.clone(),
+                name: skill.name.clone(),
+                central_bytes: dir_size(&resolve_skill_central_path(
+                    &skill.central_path,
+                    central_dir,
+                )),
+                copy_tools: Vec::new(),
+                duplicated_bytes: 0,
+            };
+            for target in parse_sync_details(skill) {
+                if target.mode == "copy" {
+                    entry.duplicated_bytes += dir_size(Path::new(&target.target_path));
+                    entry.copy_tools.push(target.tool);
+                }
+            }
+            entry
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| Reverse(entry.central_bytes));
+
+    let mut link_savings = entries
+        .iter()
+        .filter(|entry| entry.duplicated_bytes > 0)
+        .cloned()
+        .collect::<Vec<_>>();
+    link_savings.sort_by_key(|entry| Reverse(entry.duplicated_bytes));
+    link_savings.truncate(LINK_SAVINGS_LIMIT);
+
+    // Variants live inside the snapshot root; report them separately
+    let variant_snapshot_bytes = size_of(&roots.variants);
+    StorageReportDto {
+        central_bytes: entries.iter().map(|entry| entry.central_bytes).sum(),
+        duplicated_bytes: entries.iter().map(|entry| entry.duplicated_bytes).sum(),
+        git_cache_bytes: size_of(&roots.git_cache),
+        config_snapshot_bytes: size_of(&roots.snapshots).saturating_sub(variant_snapshot_bytes),
+        variant_snapshot_bytes,
+        skills: entries,
+        link_savings,
+        generated_at: now_ms(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use serde_json::json;
+
+    fn skill(id: &str, central: &Path, sync_details: serde_json::Value) -> Skill {
+        from_db_skill(json!({
+            "id": id,
+            "name": id,
+            "central_path": central,
+            "sync_details": sync_details,
+        }))
+    }
+
+    fn write_file(path: &Path, len: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; len]).unwrap();
+    }
+
+    #[test]
+    fn counts_copy_targets_and_ranks_link_savings() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        write_file(&central.join("big/SKILL.md"), 30);
+        write_file(&central.join("small/SKILL.md"), 5);
+        write_file(&temp.path().join("tool/small/SKILL.md"), 5);
+        write_file(&temp.path().join("snapshots/a.json"), 7);
+        write_file(&temp.path().join("snapshots/skill-variants/v/SKILL.md"), 3);
+
+        let skills = vec![
+            skill(
+                "small",
+                &central.join("small"),
+                json!({
+                    "codex": { "target_path": temp.path().join("tool/small"), "mode": "copy", "status": "ok" },
+                    "claude_code": { "target_path": temp.path().join("link/small"), "mode": "symlink", "status": "ok" },
+                }),
+            ),
+            skill("big", &central.join("big"), json!({})),
+        ];
+        let report = build_storage_report(
+            &skills,
+            &central,
+            &StorageRoots {
+                git_cache: None,
+                snapshots: Some(temp.path().join("snapshots")),
+                variants: Some(temp.path().join("snapshots/skill-variants")),
+            },
+        );
+
+        assert_eq!(report.skills[0].name, "big");
+        assert_eq!(report.central_bytes, 35);
+        assert_eq!(report.duplicated_bytes, 5);
+        assert_eq!(report.link_savings.len(), 1);
+        assert_eq!(report.link_savings[0].copy_tools, vec!["codex"]);
+        assert_eq!(report.variant_snapshot_bytes, 3);
+        assert_eq!(report.config_snapshot_bytes, 7);
+        assert_eq!(report.git_cache_bytes, 0);
+    }
+}
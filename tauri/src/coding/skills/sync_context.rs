@@ -0,0 +1,54 @@
+//! Sync settings of the running app
+//!
+//! The core sync engine keeps no process-wide state and takes its settings
+//! as a `SyncContext` on every call; the app keeps the current ones here.
+//! lib.rs sets the trash dir at startup, `central_repo` the symlink style and
+//! `skill_ignore` the global copy ignore patterns, each loaded from the skill
+//! settings at startup and updated when the user saves them.
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+pub use ai_toolbox_core::skills::sync_engine::SyncContext;
+
+/// `None` until the first setting is applied
+static CURRENT: RwLock<Option<SyncContext>> = RwLock::new(None);
+
+/// The settings syncs run with now
+pub fn current() -> SyncContext {
+    CURRENT
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+fn update(apply: impl FnOnce(&mut SyncContext)) {
+    let mut guard = CURRENT
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    apply(guard.get_or_insert_with(SyncContext::default));
+}
+
+pub fn set_trash_dir(dir: PathBuf) {
+    update(|ctx| ctx.trash_dir = Some(dir));
+}
+
+pub fn set_relative_symlinks(relative: bool) {
+    update(|ctx| ctx.relative_symlinks = relative);
+}
+
+pub fn set_ignore_patterns(patterns: Vec<String>) {
+    update(|ctx| ctx.ignore_patterns = patterns);
+}
+
+pub fn relative_symlinks() -> bool {
+    current().relative_symlinks
+}
+
+/// The sync trash, for listing, rolling back and purging its entries
+pub fn trash_dir() -> Result<PathBuf, String> {
+    current()
+        .trash_dir
+        .ok_or_else(|| "the sync trash is not available".to_string())
+}
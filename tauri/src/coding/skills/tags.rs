@@ -0,0 +1,378 @@
+//! Skill tags: batch editing and suggestions from SKILL.md content
+//!
+//! Tags are free-form labels stored on the skill record (`skill.data.tags`),
+//! independent of the single skill group. They are compared case-insensitively
+//! and keep the spelling they were first added with.
+//!
+//! Suggestions come from three signals, strongest first: tags already used in
+//! the library that the SKILL.md mentions, well-known tools / technologies it
+//! mentions, and its most frequent keywords (description and headings weigh
+//! more than the body).
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+
+use super::file_skills::skill_md_to_entry;
+use super::types::Skill;
+
+const MAX_SUGGESTIONS: usize = 8;
+const MAX_KEYWORD_SUGGESTIONS: usize = 4;
+/// Weighted occurrences a keyword needs before it is suggested
+const MIN_KEYWORD_SCORE: u32 = 4;
+
+const LIBRARY_TAG_SCORE: u32 = 100;
+const TOOL_MENTION_SCORE: u32 = 50;
+
+/// Mentioned words -> suggested tag
+const TOOL_TAGS: &[(&[&str], &str)] = &[
+    (&["git", "commit", "rebase"], "git"),
+    (&["github", "pull-request"], "github"),
+    (&["gitlab"], "gitlab"),
+    (&["docker", "dockerfile", "container"], "docker"),
+    (&["kubernetes", "kubectl", "helm"], "kubernetes"),
+    (&["terraform"], "terraform"),
+    (&["aws", "s3", "lambda"], "aws"),
+    (&["gcp", "bigquery"], "gcp"),
+    (&["azure"], "azure"),
+    (&["python", "pip", "pytest"], "python"),
+    (&["rust", "cargo", "clippy"], "rust"),
+    (&["golang"], "go"),
+    (&["java", "maven", "gradle"], "java"),
+    (&["typescript", "tsx"], "typescript"),
+    (&["javascript", "npm", "node", "nodejs"], "javascript"),
+    (&["react", "nextjs"], "react"),
+    (&["vue", "nuxt"], "vue"),
+    (&["css", "tailwind"], "css"),
+    (
+        &["sql", "postgres", "postgresql", "mysql", "sqlite"],
+        "database",
+    ),
+    (&["test", "tests", "testing", "jest", "vitest"], "testing"),
+    (&["pdf"], "pdf"),
+    (&["excel", "xlsx", "spreadsheet", "csv"], "spreadsheet"),
+    (&["docx"], "docx"),
+    (&["pptx", "slides", "powerpoint"], "slides"),
+    (&["figma"], "figma"),
+    (&["slack"], "slack"),
+    (&["jira"], "jira"),
+    (&["mcp"], "mcp"),
+    (&["security", "vulnerability", "cve"], "security"),
+];
+
+const STOPWORDS: &[&str] = &[
+    "about",
+    "above",
+    "after",
+    "again",
+    "all",
+    "also",
+    "always",
+    "and",
+    "any",
+    "are",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "can",
+    "could",
+    "does",
+    "doing",
+    "each",
+    "example",
+    "file",
+    "files",
+    "following",
+    "from",
+    "further",
+    "have",
+    "here",
+    "into",
+    "just",
+    "like",
+    "make",
+    "more",
+    "most",
+    "must",
+    "need",
+    "never",
+    "only",
+    "other",
+    "over",
+    "should",
+    "skill",
+    "some",
+    "such",
+    "than",
+    "that",
+    "their",
+    "them",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "under",
+    "until",
+    "use",
+    "used",
+    "user",
+    "using",
+    "very",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "will",
+    "with",
+    "within",
+    "without",
+    "would",
+    "your",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagSuggestionReason {
+    /// Tag already used elsewhere in the library and mentioned in the content
+    LibraryTag,
+    /// A well-known tool or technology is mentioned
+    ToolMention,
+    /// Frequent keyword of the SKILL.md
+    Keyword,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TagSuggestion {
+    pub tag: String,
+    pub reason: TagSuggestionReason,
+    pub score: u32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillTagSuggestionsDto {
+    pub skill_id: String,
+    pub skill_name: String,
+    pub suggestions: Vec<TagSuggestion>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillTagCountDto {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Trim, drop empty and case-insensitive duplicates (first spelling wins)
+pub fn normalize_tags<S: AsRef<str>>(tags: &[S]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tags.iter()
+        .map(|tag| tag.as_ref().trim())
+        .filter(|tag| !tag.is_empty() && seen.insert(tag.to_lowercase()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `current` without `remove`, plus `add`; returns `None` when nothing changes
+pub fn apply_tag_edit(
+    current: &[String],
+    add: &[String],
+    remove: &[String],
+) -> Option<Vec<String>> {
+    let removed: HashSet<String> = remove.iter().map(|tag| tag.trim().to_lowercase()).collect();
+    let kept: Vec<String> = current
+        .iter()
+        .filter(|tag| !removed.contains(&tag.to_lowercase()))
+        .cloned()
+        .collect();
+    let next = normalize_tags(&[kept, add.to_vec()].concat());
+    (next != current).then_some(next)
+}
+
+/// Tags used across `skills` with their skill counts, most used first
+pub fn library_tag_counts(skills: &[Skill]) -> Vec<SkillTagCountDto> {
+    let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for skill in skills {
+        for tag in normalize_tags(&skill.tags) {
+            counts
+                .entry(tag.to_lowercase())
+                .or_insert_with(|| (tag.clone(), 0))
+                .1 += 1;
+        }
+    }
+    let mut result: Vec<SkillTagCountDto> = counts
+        .into_values()
+        .map(|(tag, count)| SkillTagCountDto { tag, count })
+        .collect();
+    result.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    result
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .map(|word| word.trim_matches(['-', '_']).to_lowercase())
+        .filter(|word| !word.is_empty())
+}
+
+/// Suggest tags for a SKILL.md, skipping tags the skill already has
+pub fn suggest_tags(
+    skill_md: &str,
+    library_tags: &[String],
+    current_tags: &[String],
+) -> Vec<TagSuggestion> {
+    let entry = skill_md_to_entry("", skill_md);
+    let description = entry.description.unwrap_or_default();
+    let mut weighted: HashMap<String, u32> = HashMap::new();
+    let mut add_words = |text: &str, weight: u32| {
+        for word in words(text) {
+            *weighted.entry(word).or_insert(0) += weight;
+        }
+    };
+    add_words(&entry.name, 3);
+    add_words(&description, 3);
+    for line in entry.body.lines() {
+        let weight = if line.trim_start().starts_with('#') {
+            2
+        } else {
+            1
+        };
+        add_words(line, weight);
+    }
+    let full_text = format!("{}\n{}\n{}", entry.name, description, entry.body).to_lowercase();
+
+    let mut suggestions: Vec<TagSuggestion> = Vec::new();
+    let mut push = |tag: &str, reason: TagSuggestionReason, score: u32| {
+        if let Some(existing) = suggestions
+            .iter_mut()
+            .find(|suggestion| suggestion.tag.eq_ignore_ascii_case(tag))
+        {
+            existing.score = existing.score.max(score);
+            return;
+        }
+        suggestions.push(TagSuggestion {
+            tag: tag.to_string(),
+            reason,
+            score,
+        });
+    };
+
+    for tag in normalize_tags(library_tags) {
+        let key = tag.to_lowercase();
+        let hits = match weighted.get(&key) {
+            Some(hits) => *hits,
+            // Multi-word tags only match as a phrase
+            None if key.contains(' ') && full_text.contains(&key) => 1,
+            None => continue,
+        };
+        push(
+            &tag,
+            TagSuggestionReason::LibraryTag,
+            LIBRARY_TAG_SCORE + hits,
+        );
+    }
+
+    for (mentions, tag) in TOOL_TAGS {
+        let hits: u32 = mentions
+            .iter()
+            .filter_map(|mention| weighted.get(*mention))
+            .sum();
+        if hits > 0 {
+            push(
+                tag,
+                TagSuggestionReason::ToolMention,
+                TOOL_MENTION_SCORE + hits,
+            );
+        }
+    }
+
+    let mut keywords: Vec<(&String, &u32)> = weighted
+        .iter()
+        .filter(|(word, score)| {
+            **score >= MIN_KEYWORD_SCORE
+                && word.chars().count() >= 4
+                && !word.chars().all(|c| c.is_ascii_digit())
+                && !STOPWORDS.contains(&word.as_str())
+        })
+        .collect();
+    keywords.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (word, score) in keywords.into_iter().take(MAX_KEYWORD_SUGGESTIONS) {
+        push(word, TagSuggestionReason::Keyword, *score);
+    }
+
+    let current: HashSet<String> = current_tags.iter().map(|tag| tag.to_lowercase()).collect();
+    suggestions.retain(|suggestion| !current.contains(&suggestion.tag.to_lowercase()));
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.tag.cmp(&b.tag)));
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn tag_edit_adds_removes_and_dedupes_case_insensitively() {
+        let current = strings(&["Review", "docs"]);
+        assert_eq!(
+            apply_tag_edit(
+                &current,
+                &strings(&["review", " git ", ""]),
+                &strings(&["DOCS"])
+            ),
+            Some(strings(&["Review", "git"]))
+        );
+        assert_eq!(apply_tag_edit(&current, &strings(&["docs"]), &[]), None);
+    }
+
+    #[test]
+    fn suggestions_rank_library_tags_then_tools_then_keywords() {
+        let skill_md = "---\nname: pr-review\ndescription: Review GitHub pull requests for the payments team\n---\n\n# Review checklist\n\nRun `cargo clippy` before reviewing. Check the review comments.\nReview tests too.\n";
+        let suggestions = suggest_tags(skill_md, &strings(&["Payments", "frontend"]), &[]);
+        let tags: Vec<&str> = suggestions.iter().map(|s| s.tag.as_str()).collect();
+        assert_eq!(tags[0], "Payments");
+        assert_eq!(suggestions[0].reason, TagSuggestionReason::LibraryTag);
+        assert!(tags.contains(&"github"));
+        assert!(tags.contains(&"rust"));
+        assert!(tags.contains(&"review"));
+        assert!(!tags.contains(&"frontend"));
+
+        let without_current = suggest_tags(skill_md, &[], &strings(&["GitHub"]));
+        assert!(without_current.iter().all(|s| s.tag != "github"));
+    }
+
+    #[test]
+    fn library_counts_merge_spellings() {
+        let skill = |tags: &[&str]| {
+            crate::coding::skills::adapter::from_db_skill(serde_json::json!({
+                "id": "x",
+                "name": "x",
+                "tags": tags,
+            }))
+        };
+        let counts = library_tag_counts(&[skill(&["Git", "docs"]), skill(&["git"])]);
+        assert_eq!(
+            counts,
+            vec![
+                SkillTagCountDto {
+                    tag: "Git".to_string(),
+                    count: 2
+                },
+                SkillTagCountDto {
+                    tag: "docs".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Sanity checks for tool skills dirs before syncing into them
+//!
+//! A wrongly resolved skills dir (typo in a custom tool, an empty path, a
+//! runtime location pointing at the home dir) would otherwise get skill links
+//! created among unrelated files. Before syncing, the resolved dir must be
+//! absolute, under the user's home but not the home itself, and, when it
+//! already holds files, end in the same directory name as the adapter's
+//! declared path. Dirs below an entry of
+//! `skill_settings:skills.sync_target_whitelist` skip the checks.
+//!
+//! Refusals are reported as `SYNC_TARGET_UNEXPECTED|{tool}|{dir}|{reason}`.
+
+use std::path::{Component, Path, PathBuf};
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite};
+use super::tool_adapters::{resolve_runtime_skills_path_with_state_async, RuntimeToolAdapter};
+use super::types::now_ms;
+use crate::coding::runtime_location;
+use crate::SqliteDbState;
+
+const WHITELIST_SETTINGS_KEY: &str = "sync_target_whitelist";
+
+/// Why a skills dir was refused
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnexpectedTarget {
+    Relative,
+    Home,
+    OutsideHome,
+    /// Non-empty dir whose name differs from the declared one
+    Pattern,
+    /// Skill name escapes the skills dir
+    OutsideRoot,
+}
+
+impl UnexpectedTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UnexpectedTarget::Relative => "relative",
+            UnexpectedTarget::Home => "home",
+            UnexpectedTarget::OutsideHome => "outside_home",
+            UnexpectedTarget::Pattern => "pattern",
+            UnexpectedTarget::OutsideRoot => "outside_root",
+        }
+    }
+}
+
+pub fn load_whitelist(state: &SqliteDbState) -> Vec<String> {
+    read_skill_settings_value_from_sqlite(state, WHITELIST_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_whitelist(state: &SqliteDbState, dirs: &[String]) -> Result<(), String> {
+    let mut saved: Vec<&str> = Vec::new();
+    for dir in dirs.iter().map(|dir| dir.trim()) {
+        if dir.is_empty() || saved.contains(&dir) {
+            continue;
+        }
+        if !Path::new(dir).is_absolute() {
+            return Err(format!("Whitelisted directory must be absolute: {}", dir));
+        }
+        saved.push(dir);
+    }
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            WHITELIST_SETTINGS_KEY: saved,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Check the resolved skills dir `root` of `adapter`
+pub fn check_tool_root(
+    adapter: &RuntimeToolAdapter,
+    root: &Path,
+    home: Option<&Path>,
+    whitelist: &[PathBuf],
+) -> Result<(), UnexpectedTarget> {
+    // WSL dirs come from the distro's own config, not from a user-typed path
+    if root
+        .to_str()
+        .and_then(runtime_location::parse_wsl_unc_path)
+        .is_some()
+    {
+        return Ok(());
+    }
+    if !root.is_absolute() {
+        return Err(UnexpectedTarget::Relative);
+    }
+    if whitelist.iter().any(|dir| path_starts_with(root, dir)) {
+        return Ok(());
+    }
+    let Some(home) = home else {
+        return Err(UnexpectedTarget::OutsideHome);
+    };
+    if path_starts_with(home, root) {
+        return Err(UnexpectedTarget::Home);
+    }
+    if !path_starts_with(root, home) {
+        return Err(UnexpectedTarget::OutsideHome);
+    }
+    let declared_name = adapter
+        .relative_skills_dir
+        .rsplit(['/', '\\'])
+        .find(|part| !part.is_empty());
+    let name_matches = match (declared_name, root.file_name()) {
+        (Some(declared), Some(name)) => component_eq(declared, &name.to_string_lossy()),
+        _ => false,
+    };
+    if !name_matches && !is_missing_or_empty(root) {
+        return Err(UnexpectedTarget::Pattern);
+    }
+    Ok(())
+}
+
+/// The target of a skill must be a direct child of its skills dir
+pub fn check_target_in_root(root: &Path, target: &Path) -> Result<(), UnexpectedTarget> {
+    let direct_child = target.parent() == Some(root)
+        && matches!(target.components().next_back(), Some(Component::Normal(_)));
+    if direct_child {
+        Ok(())
+    } else {
+        Err(UnexpectedTarget::OutsideRoot)
+    }
+}
+
+/// Resolve the skills dir of `adapter` and the target of `skill_name` in it,
+/// refusing unexpected locations
+pub async fn resolve_checked_target(
+    state: &SqliteDbState,
+    adapter: &RuntimeToolAdapter,
+    skill_name: &str,
+) -> Result<(PathBuf, PathBuf), String> {
+    let root = resolve_runtime_skills_path_with_state_async(state.db(), adapter)
+        .await
+        .map_err(|e| format!("{:#}", e))?;
+    let target = root.join(skill_name);
+    let whitelist: Vec<PathBuf> = load_whitelist(state)
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    check_tool_root(
+        adapter,
+        &root,
+        crate::coding::tools::home_dir().as_deref(),
+        &whitelist,
+    )
+    .and_then(|_| check_target_in_root(&root, &target))
+    .map_err(|reason| {
+        format!(
+            "SYNC_TARGET_UNEXPECTED|{}|{}|{}",
+            adapter.key,
+            target.parent().unwrap_or(&root).display(),
+            reason.as_str()
+        )
+    })?;
+    Ok((root, target))
+}
+
+fn component_eq(left: &str, right: &str) -> bool {
+    if cfg!(windows) {
+        left.eq_ignore_ascii_case(right)
+    } else {
+        left == right
+    }
+}
+
+fn path_starts_with(path: &Path, base: &Path) -> bool {
+    let mut components = path.components();
+    base.components().all(|base_part| {
+        components.next().is_some_and(|part| {
+            component_eq(
+                &part.as_os_str().to_string_lossy(),
+                &base_part.as_os_str().to_string_lossy(),
+            )
+        })
+    })
+}
+
+fn is_missing_or_empty(dir: &Path) -> bool {
+    match std::fs::read_dir(dir) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(_) => !dir.exists(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(skills_dir: &str) -> RuntimeToolAdapter {
+        RuntimeToolAdapter {
+            key: "custom".to_string(),
+            display_name: "Custom".to_string(),
+            relative_skills_dir: skills_dir.to_string(),
+            relative_detect_dir: skills_dir.to_string(),
+            is_custom: true,
+            force_copy: false,
+            preferred_sync_mode: None,
+        }
+    }
+
+    #[test]
+    fn refuses_roots_outside_home_or_with_foreign_content() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path().join("home");
+        let claude = adapter("~/.claude/skills");
+        let skills = home.join(".claude/skills");
+        std::fs::create_dir_all(&skills).unwrap();
+        std::fs::write(home.join(".bashrc"), "").unwrap();
+        let check = |adapter: &RuntimeToolAdapter, root: &Path, whitelist: &[PathBuf]| {
+            check_tool_root(adapter, root, Some(&home), whitelist)
+        };
+
+        assert_eq!(check(&claude, &skills, &[]), Ok(()));
+        assert_eq!(
+            check(&claude, Path::new("skills"), &[]),
+            Err(UnexpectedTarget::Relative)
+        );
+        assert_eq!(check(&claude, &home, &[]), Err(UnexpectedTarget::Home));
+        let shared = temp.path().join("shared/skills");
+        assert_eq!(
+            check(&claude, &shared, &[]),
+            Err(UnexpectedTarget::OutsideHome)
+        );
+        assert_eq!(
+            check(&claude, &shared, &[temp.path().join("shared")]),
+            Ok(())
+        );
+
+        // A populated dir with another name is refused, a fresh one is not
+        let config = home.join(".config");
+        std::fs::create_dir_all(config.join("app")).unwrap();
+        assert_eq!(check(&claude, &config, &[]), Err(UnexpectedTarget::Pattern));
+        assert_eq!(check(&claude, &home.join(".agents/prompts"), &[]), Ok(()));
+        assert_eq!(check(&adapter("~/.config"), &config, &[]), Ok(()));
+    }
+
+    #[test]
+    fn target_must_be_a_direct_child() {
+        let root = Path::new("/home/alice/.claude/skills");
+        assert_eq!(check_target_in_root(root, &root.join("review")), Ok(()));
+        assert_eq!(
+            check_target_in_root(root, &root.join("../review")),
+            Err(UnexpectedTarget::OutsideRoot)
+        );
+        assert_eq!(
+            check_target_in_root(root, &root.join("nested/review")),
+            Err(UnexpectedTarget::OutsideRoot)
+        );
+    }
+}
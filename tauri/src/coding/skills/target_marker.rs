@@ -0,0 +1,213 @@
+//! "Managed by AI Toolbox" marker files in copy targets
+//!
+//! A copied skill dir looks like any hand-made skill to other skill managers
+//! and to the tools themselves. Copy-mode syncs therefore write
+//! `.ai-toolbox.meta` (`{ skill_id, skill_name, tool, mode, synced_at }`) into
+//! the target, so managed copies can be recognized again even when the
+//! database record is gone. Link targets need no marker: they resolve into
+//! the central repo.
+//!
+//! The marker is excluded from content hashes and from copies, so it never
+//! counts as a local change and never travels back into the central repo.
+//! Reading and writing it is `ai_toolbox_core::skills::target_marker`; this
+//! module marks synced targets and rebuilds records from markers.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::adapter::parse_sync_details;
+use super::tool_adapters::{
+    get_all_tool_adapters, resolve_runtime_skills_path_async,
+    resolve_runtime_skills_path_with_state_async, CustomTool,
+};
+use super::types::{Skill, SkillTarget, SyncMode};
+use crate::SqliteDbState;
+
+pub use ai_toolbox_core::skills::target_marker::{
+    read_marker, write_marker, TargetMarker, MARKER_FILE,
+};
+
+/// Target rebuilt from a marker
+#[derive(Clone, Debug, Serialize)]
+pub struct RecoveredTargetDto {
+    pub skill_id: String,
+    pub skill_name: String,
+    pub tool: String,
+    pub target_path: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MarkerRecoveryDto {
+    pub recovered: Vec<RecoveredTargetDto>,
+    /// Marked dirs whose skill is not in the library
+    pub unmatched: Vec<String>,
+}
+
+/// Write the marker of a freshly synced target; links are left alone.
+/// Failures are only logged, the sync itself already succeeded.
+pub fn mark_target(skill: &Skill, target: &SkillTarget) {
+    if target.mode != SyncMode::Copy.as_str() && target.mode != SyncMode::Hardlink.as_str() {
+        return;
+    }
+    let marker = TargetMarker {
+        skill_id: skill.id.clone(),
+        skill_name: skill.name.clone(),
+        tool: target.tool.clone(),
+        mode: target.mode.clone(),
+        synced_at: target.synced_at,
+    };
+    if let Err(err) = write_marker(Path::new(&target.target_path), &marker) {
+        log::warn!(
+            "Failed to write marker for '{}' ({}): {:#}",
+            skill.name,
+            target.tool,
+            err
+        );
+    }
+}
+
+/// Marked copies directly inside `skills_dir`
+pub fn scan_markers(skills_dir: &Path) -> Vec<(PathBuf, TargetMarker)> {
+    let Ok(entries) = std::fs::read_dir(skills_dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(PathBuf, TargetMarker)> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| {
+            let path = entry.path();
+            read_marker(&path).map(|marker| (path, marker))
+        })
+        .collect();
+    found.sort_by(|left, right| left.0.cmp(&right.0));
+    found
+}
+
+/// Skill a marker belongs to: by id, else by name (ids change when the
+/// library was rebuilt)
+fn owner<'a>(skills: &'a [Skill], marker: &TargetMarker) -> Option<&'a Skill> {
+    skills
+        .iter()
+        .find(|skill| skill.id == marker.skill_id)
+        .or_else(|| skills.iter().find(|skill| skill.name == marker.skill_name))
+}
+
+/// Target records to restore from the markers found in `dirs` (skills dir ->
+/// tool key). Tools a skill already has a target for are left alone.
+pub fn plan_recovery(
+    skills: &[Skill],
+    dirs: &[(PathBuf, String)],
+) -> (Vec<(String, SkillTarget)>, MarkerRecoveryDto) {
+    let mut records: Vec<(String, SkillTarget)> = Vec::new();
+    let mut report = MarkerRecoveryDto::default();
+    for (dir, tool) in dirs {
+        for (path, marker) in scan_markers(dir) {
+            let target_path = path.to_string_lossy().to_string();
+            let Some(skill) = owner(skills, &marker) else {
+                report.unmatched.push(target_path);
+                continue;
+            };
+            let recorded = parse_sync_details(skill)
+                .iter()
+                .any(|target| &target.tool == tool)
+                || records
+                    .iter()
+                    .any(|(skill_id, target)| skill_id == &skill.id && &target.tool == tool);
+            if recorded {
+                continue;
+            }
+            records.push((
+                skill.id.clone(),
+                SkillTarget {
+                    tool: tool.clone(),
+                    target_path: target_path.clone(),
+                    mode: SyncMode::Copy.as_str().to_string(),
+                    status: "ok".to_string(),
+                    synced_at: marker.synced_at,
+                    error_message: None,
+                    path_rules: Default::default(),
+                },
+            ));
+            report.recovered.push(RecoveredTargetDto {
+                skill_id: skill.id.clone(),
+                skill_name: skill.name.clone(),
+                tool: tool.clone(),
+                target_path,
+            });
+        }
+    }
+    (records, report)
+}
+
+/// Skills dirs of every tool (default and configured path): dir -> tool key
+pub async fn marker_dirs(
+    state: &SqliteDbState,
+    custom_tools: &[CustomTool],
+) -> Vec<(PathBuf, String)> {
+    let mut dirs: Vec<(PathBuf, String)> = Vec::new();
+    for adapter in get_all_tool_adapters(custom_tools) {
+        let configured = resolve_runtime_skills_path_with_state_async(state, &adapter).await;
+        let default = resolve_runtime_skills_path_async(&adapter).await;
+        for path in [configured, default].into_iter().flatten() {
+            if !dirs.iter().any(|(dir, _)| dir == &path) {
+                dirs.push((path, adapter.key.clone()));
+            }
+        }
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+
+    fn skill(id: &str, name: &str) -> Skill {
+        from_db_skill(serde_json::json!({
+            "id": id,
+            "name": name,
+            "central_path": name,
+            "status": "ok",
+        }))
+    }
+
+    fn marker(skill_id: &str, skill_name: &str) -> TargetMarker {
+        TargetMarker {
+            skill_id: skill_id.to_string(),
+            skill_name: skill_name.to_string(),
+            tool: "cursor".to_string(),
+            mode: "copy".to_string(),
+            synced_at: Some(42),
+        }
+    }
+
+    #[test]
+    fn recovers_targets_by_id_then_name() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("skills");
+        for (name, marker) in [
+            ("review", marker("old-id", "review")),
+            ("deploy", marker("deploy-id", "renamed")),
+            ("stray", marker("gone", "stray")),
+        ] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+            write_marker(&dir.join(name), &marker).unwrap();
+        }
+        std::fs::create_dir_all(dir.join("unmarked")).unwrap();
+
+        let skills = vec![skill("new-id", "review"), skill("deploy-id", "deploy")];
+        let (records, report) = plan_recovery(&skills, &[(dir.clone(), "cursor".to_string())]);
+
+        let restored: Vec<(&str, &str)> = records
+            .iter()
+            .map(|(id, target)| (id.as_str(), target.mode.as_str()))
+            .collect();
+        assert_eq!(restored, vec![("deploy-id", "copy"), ("new-id", "copy")]);
+        assert_eq!(records[1].1.synced_at, Some(42));
+        assert_eq!(
+            report.unmatched,
+            vec![dir.join("stray").to_string_lossy().to_string()]
+        );
+    }
+}
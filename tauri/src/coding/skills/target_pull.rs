@@ -0,0 +1,228 @@
+//! Pull of edits made in a deployed copy back into the central repo
+//!
+//! Copy-mode targets are often edited in place (Cursor rules, a quick fix
+//! in `~/.codex/skills/...`), and the next sync would overwrite the edit.
+//! `diff_target` lists the files a copy target differs from the skill's
+//! central directory in; `pull_into_central` applies the target's version
+//! of the chosen files to a staged copy of the central directory and swaps
+//! it in, the way a revert does (`external_changes`): the old directory is
+//! moved aside and restored if the swap fails, then kept in the sync trash
+//! so the pull can be rolled back like a sync. Files the copy never
+//! receives, left out by the ignore patterns or the target's path rules,
+//! are not compared, so a pull never deletes them from the central repo.
+//! `external_change_commands::skills_pull_from_target` then records the new
+//! `content_hash` and re-syncs the skill's other targets.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::path_rules;
+use super::sync_context;
+use super::sync_engine::copy_dir_recursive;
+use super::sync_journal::{keep_replaced, JournalOp};
+use super::types::{Skill, SkillPathRules};
+use super::update_preview::{diff_against_upstream, SkillUpdateFileDto};
+use super::write_protect;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TargetPullPreviewDto {
+    pub skill_id: String,
+    pub name: String,
+    pub tool: String,
+    pub target_path: String,
+    /// Files that differ, from the central copy to the target: `added` files
+    /// exist only in the target, `removed` ones were deleted there
+    pub files: Vec<SkillUpdateFileDto>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TargetPullResultDto {
+    /// Files written to or removed from the central copy
+    pub pulled: Vec<String>,
+    pub content_hash: Option<String>,
+    /// Other tools re-synced with the new content
+    pub resynced: Vec<String>,
+    /// `tool: error` for re-syncs that failed
+    pub errors: Vec<String>,
+}
+
+/// Files of `target_path` that differ from `central_path`, limited to what
+/// a copy with `rules` receives
+pub fn diff_target(
+    central_path: &Path,
+    target_path: &Path,
+    rules: &SkillPathRules,
+) -> Result<Vec<SkillUpdateFileDto>> {
+    let ignore = sync_context::current().ignore_for(central_path);
+    let mut files = diff_against_upstream(central_path, target_path, None)?;
+    files.retain(|file| {
+        !ignore.is_ignored(&file.path, false) && path_rules::keeps(rules, &file.path)
+    });
+    Ok(files)
+}
+
+/// Apply the target's version of `paths` (every differing file when `None`)
+/// to the central directory of `skill`. Paths that no longer differ are
+/// skipped. Returns the files pulled.
+pub fn pull_into_central(
+    skill: &Skill,
+    central_path: &Path,
+    target_path: &Path,
+    rules: &SkillPathRules,
+    paths: Option<&[String]>,
+) -> Result<Vec<String>> {
+    let files = diff_target(central_path, target_path, rules)?
+        .into_iter()
+        .filter(|file| paths.is_none_or(|paths| paths.contains(&file.path)))
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let parent = central_path
+        .parent()
+        .with_context(|| format!("invalid central path {:?}", central_path))?;
+    let staging = parent.join(format!(".skills-pull-{}", skill.id));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).with_context(|| format!("remove {:?}", staging))?;
+    }
+    let staged = copy_dir_recursive(central_path, &staging).and_then(|_| {
+        for file in &files {
+            let staged_file = staging.join(&file.path);
+            if file.status == "removed" {
+                std::fs::remove_file(&staged_file)
+                    .with_context(|| format!("remove {:?}", staged_file))?;
+                continue;
+            }
+            if let Some(dir) = staged_file.parent() {
+                std::fs::create_dir_all(dir).with_context(|| format!("create {:?}", dir))?;
+            }
+            std::fs::copy(target_path.join(&file.path), &staged_file)
+                .with_context(|| format!("copy {:?} into {:?}", file.path, staging))?;
+        }
+        Ok(())
+    });
+    if let Err(err) = staged {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(err);
+    }
+
+    let backup = parent.join(format!(".skills-pull-old-{}", skill.id));
+    let aside = write_protect::unlock(central_path, skill.write_protected).and_then(|_| {
+        if backup.exists() {
+            std::fs::remove_dir_all(&backup).with_context(|| format!("remove {:?}", backup))?;
+        }
+        std::fs::rename(central_path, &backup)
+            .with_context(|| format!("move {:?} -> {:?}", central_path, backup))
+    });
+    if let Err(err) = aside {
+        let _ = std::fs::remove_dir_all(&staging);
+        write_protect::protect_or_warn(central_path, skill.write_protected);
+        return Err(err);
+    }
+    if let Err(err) = std::fs::rename(&staging, central_path) {
+        let _ = std::fs::remove_dir_all(&staging);
+        let err =
+            anyhow::Error::new(err).context(format!("move {:?} -> {:?}", staging, central_path));
+        let restored = std::fs::rename(&backup, central_path);
+        write_protect::protect_or_warn(central_path, skill.write_protected);
+        return Err(match restored {
+            Ok(()) => err,
+            Err(restore_err) => err.context(format!(
+                "previous central directory could not be restored and is kept at {:?}: {}",
+                backup, restore_err
+            )),
+        });
+    }
+    write_protect::protect_or_warn(central_path, skill.write_protected);
+
+    // The pulled-over directory can be rolled back like a synced target
+    let trash = sync_context::current().trash_dir;
+    let kept = keep_replaced(
+        trash.as_deref(),
+        &backup,
+        central_path,
+        JournalOp::Overwrite,
+    )
+    .unwrap_or_else(|err| {
+        log::warn!(
+            "Failed to move central backup {:?} to the trash: {:#}",
+            backup,
+            err
+        );
+        false
+    });
+    if !kept {
+        if let Err(err) = std::fs::remove_dir_all(&backup) {
+            log::warn!("Failed to remove central backup {:?}: {}", backup, err);
+        }
+    }
+    Ok(files.into_iter().map(|file| file.path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::skills::adapter::from_db_skill;
+    use serde_json::json;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn pulls_chosen_edits_and_keeps_files_the_copy_never_received() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central/review");
+        let target = temp.path().join("cursor/review");
+        write(&central, "SKILL.md", "# Review\n");
+        write(&central, "notes.md", "old notes\n");
+        write(&central, "scripts/run.sh", "echo run\n");
+        write(&central, "docs/guide.md", "guide\n");
+        write(&target, "SKILL.md", "# Review\n");
+        write(&target, "notes.md", "new notes\n");
+        write(&target, "extra.md", "added in cursor\n");
+        // `docs/` is outside the target's path rules, `scripts/run.sh` was
+        // deleted in the target
+        let rules = SkillPathRules {
+            include: Vec::new(),
+            exclude: vec!["docs".to_string()],
+        };
+
+        let files = diff_target(&central, &target, &rules).unwrap();
+        let listed = files
+            .iter()
+            .map(|file| (file.path.as_str(), file.status.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listed,
+            vec![
+                ("extra.md", "added"),
+                ("notes.md", "modified"),
+                ("scripts/run.sh", "removed"),
+            ]
+        );
+
+        let skill = from_db_skill(json!({ "id": "skill-1", "name": "review" }));
+        let chosen = vec!["notes.md".to_string(), "scripts/run.sh".to_string()];
+        let pulled = pull_into_central(&skill, &central, &target, &rules, Some(&chosen)).unwrap();
+        assert_eq!(pulled, chosen);
+        assert_eq!(
+            std::fs::read_to_string(central.join("notes.md")).unwrap(),
+            "new notes\n"
+        );
+        assert!(!central.join("scripts/run.sh").exists());
+        assert!(!central.join("extra.md").exists());
+        assert!(central.join("docs/guide.md").exists());
+        // Neither the staging copy nor the old directory is left behind
+        let leftovers = std::fs::read_dir(temp.path().join("central"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(leftovers, vec!["review"]);
+    }
+}
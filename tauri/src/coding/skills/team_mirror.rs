@@ -0,0 +1,842 @@
+//! Read-only team mirrors of the skill library
+//!
+//! A mirror target publishes the central repo, or one collection of it, to a
+//! directory other machines only read from, typically a network share or a
+//! synced folder:
+//!
+//! ```text
+//! {path}/manifest.json
+//! {path}/skills/{name}/...
+//! ```
+//!
+//! Publishing is incremental: only skills whose `content_hash` (a hash of
+//! the files as served, `published_hash`) differs from the previous manifest
+//! are copied, skills that left the selection are
+//! removed, and the manifest is replaced last so consumers never see it
+//! listing half-copied content. Enabled targets republish after every
+//! `skills-changed` event. Targets are stored in
+//! `skill_settings:skills.team_mirror_targets`.
+//!
+//! On the consuming side a mirror location (directory path or `http(s)` URL
+//! serving the same layout) is a skill source: its manifest lists what can
+//! be installed, installs copy the skill directory (over HTTP file by file,
+//! using the manifest's `files`), and updates compare the manifest's
+//! `content_hash` with the one recorded at install. A fetched skill is
+//! hashed and rejected when it does not match its `content_hash`; version 1
+//! manifests hashed the publisher's central dir instead, so they can only be
+//! consumed from a directory, unchecked. Such skills use
+//! `source_type = "team_mirror"` and `source_ref = "{location}#{name}"`.
+//! Subscribed locations are stored in `skill_settings:skills.team_mirror_sources`.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::archive::ARCHIVED_STATUS;
+use super::archive_source::safe_entry_path;
+use super::central_repo::{
+    expand_home_path, merge_skill_settings_sqlite, read_skill_settings_value_from_sqlite,
+    resolve_central_repo_path, resolve_skill_central_path,
+};
+use super::collections;
+use super::content_hash::hash_dir_with_rules;
+use super::provenance::frontmatter_value;
+use super::skill_ignore::SkillIgnore;
+use super::skill_store;
+use super::sync_engine::copy_skill_dir;
+use super::types::{now_ms, Skill, SkillPathRules};
+use crate::http_client;
+use crate::SqliteDbState;
+
+pub const MIRROR_MANIFEST_FILE: &str = "manifest.json";
+pub const MIRROR_MANIFEST_FORMAT: &str = "ai-toolbox-skill-mirror";
+/// 2: `content_hash` is `published_hash` of the served files
+pub const MIRROR_MANIFEST_VERSION: u32 = 2;
+pub const MIRROR_SKILLS_DIR: &str = "skills";
+
+pub const TEAM_MIRROR_SOURCE_TYPE: &str = "team_mirror";
+
+const TARGETS_SETTINGS_KEY: &str = "team_mirror_targets";
+const SOURCES_SETTINGS_KEY: &str = "team_mirror_sources";
+const FETCH_TIMEOUT_SECS: u64 = 60;
+/// Upper bound for a single file fetched from an HTTP mirror
+const MAX_FILE_BYTES: usize = 50 * 1024 * 1024;
+
+/// Publishes to the same share must not interleave
+static PUBLISH_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TeamMirrorTarget {
+    pub id: String,
+    pub path: String,
+    /// Publish only this collection; `None` publishes the whole library
+    #[serde(default)]
+    pub collection_id: Option<String>,
+    /// Republish automatically when skills change
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub last_published_at: Option<i64>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MirrorManifest {
+    pub format: String,
+    pub version: u32,
+    pub published_at: i64,
+    /// Name of the published collection, if any
+    #[serde(default)]
+    pub collection: Option<String>,
+    pub skills: Vec<MirrorManifestSkill>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MirrorManifestSkill {
+    pub name: String,
+    /// Skill directory relative to the mirror root, `/`-separated
+    pub path: String,
+    pub content_hash: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub updated_at: i64,
+    /// Files of the skill relative to `path`, `/`-separated
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MirrorPublishResultDto {
+    pub target_id: String,
+    pub path: String,
+    pub copied: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+    pub error: Option<String>,
+}
+
+pub fn load_targets(state: &SqliteDbState) -> Vec<TeamMirrorTarget> {
+    read_skill_settings_value_from_sqlite(state, TARGETS_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_targets(state: &SqliteDbState, targets: &[TeamMirrorTarget]) -> Result<(), String> {
+    let value = serde_json::to_value(targets)
+        .map_err(|e| format!("Failed to serialize mirror targets: {}", e))?;
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            TARGETS_SETTINGS_KEY: value,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+/// Publish the target `only`, or every enabled target, recording the
+/// outcome on each target
+pub async fn publish_targets<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    state: &SqliteDbState,
+    only: Option<&str>,
+) -> Result<Vec<MirrorPublishResultDto>, String> {
+    let mut targets = load_targets(state);
+    let selected: Vec<usize> = targets
+        .iter()
+        .enumerate()
+        .filter(|(_, target)| match only {
+            Some(id) => target.id == id,
+            None => target.enabled,
+        })
+        .map(|(index, _)| index)
+        .collect();
+    if selected.is_empty() {
+        return match only {
+            Some(id) => Err(format!("Mirror target not found: {}", id)),
+            None => Ok(Vec::new()),
+        };
+    }
+
+    let central_dir = resolve_central_repo_path(app, state)
+        .await
+        .map_err(|e| format!("{:#}", e))?;
+    let skills: Vec<(Skill, PathBuf)> = skill_store::get_managed_skills(state)
+        .await?
+        .into_iter()
+        .filter(|skill| skill.management_enabled && skill.status != ARCHIVED_STATUS)
+        .map(|skill| {
+            let dir = resolve_skill_central_path(&skill.central_path, &central_dir);
+            (skill, dir)
+        })
+        .collect();
+    let all_collections = collections::list_collections(state)?;
+
+    let mut results = Vec::with_capacity(selected.len());
+    for index in selected {
+        let target = &mut targets[index];
+        let published = match &target.collection_id {
+            Some(collection_id) => all_collections
+                .iter()
+                .find(|collection| &collection.id == collection_id)
+                .map(|collection| {
+                    let members: Vec<(Skill, PathBuf)> = skills
+                        .iter()
+                        .filter(|(skill, _)| collection.skill_ids.contains(&skill.id))
+                        .cloned()
+                        .collect();
+                    (Some(collection.name.clone()), members)
+                })
+                .ok_or_else(|| format!("Collection not found: {}", collection_id)),
+            None => Ok((None, skills.clone())),
+        };
+        let root = PathBuf::from(&target.path);
+        let outcome = match published {
+            Ok((collection, members)) => tokio::task::spawn_blocking(move || {
+                publish_mirror(&root, collection, &members).map_err(|e| format!("{:#}", e))
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking failed: {}", e))
+            .and_then(|result| result),
+            Err(err) => Err(err),
+        };
+        let result = match outcome {
+            Ok(result) => {
+                target.last_published_at = Some(now_ms());
+                target.last_error = None;
+                result
+            }
+            Err(err) => {
+                log::warn!("[team_mirror] publish to {} failed: {}", target.path, err);
+                target.last_error = Some(err.clone());
+                MirrorPublishResultDto {
+                    path: target.path.clone(),
+                    error: Some(err),
+                    ..Default::default()
+                }
+            }
+        };
+        results.push(MirrorPublishResultDto {
+            target_id: target.id.clone(),
+            ..result
+        });
+    }
+    save_targets(state, &targets)?;
+    Ok(results)
+}
+
+/// Read the manifest of the mirror at `root`
+pub fn read_manifest(root: &Path) -> Result<MirrorManifest> {
+    let path = root.join(MIRROR_MANIFEST_FILE);
+    let raw = std::fs::read(&path).with_context(|| format!("read {:?}", path))?;
+    parse_manifest(&raw).with_context(|| format!("parse {:?}", path))
+}
+
+/// Directory of a manifest entry, refusing paths that leave the mirror
+pub fn manifest_skill_dir(root: &Path, skill: &MirrorManifestSkill) -> Result<PathBuf> {
+    let relative = Path::new(&skill.path);
+    if relative.as_os_str().is_empty()
+        || !relative
+            .components()
+            .all(|part| matches!(part, Component::Normal(_)))
+    {
+        anyhow::bail!("invalid skill path in mirror manifest: {}", skill.path);
+    }
+    Ok(root.join(relative))
+}
+
+/// Publish `skills` (skill + its central repo dir) to the mirror at `root`
+pub fn publish_mirror(
+    root: &Path,
+    collection: Option<String>,
+    skills: &[(Skill, PathBuf)],
+) -> Result<MirrorPublishResultDto> {
+    let _guard = PUBLISH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let skills_root = root.join(MIRROR_SKILLS_DIR);
+    std::fs::create_dir_all(&skills_root)
+        .with_context(|| format!("create mirror dir {:?}", skills_root))?;
+    let previous: BTreeMap<String, String> = read_manifest(root)
+        .map(|manifest| {
+            manifest
+                .skills
+                .into_iter()
+                .map(|skill| (skill.name, skill.content_hash))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut result = MirrorPublishResultDto::default();
+    let mut entries = Vec::with_capacity(skills.len());
+    for (skill, source) in skills {
+        if !is_plain_name(&skill.name) {
+            anyhow::bail!("invalid skill name for mirror: {:?}", skill.name);
+        }
+        let source_hash = published_hash(source)?;
+        let target = skills_root.join(&skill.name);
+        let content_hash = if previous.get(&skill.name) == Some(&source_hash) && target.is_dir() {
+            result.unchanged += 1;
+            source_hash
+        } else {
+            replace_dir(source, &target)?;
+            result.copied.push(skill.name.clone());
+            // Top-level links are resolved by the copy; hash what is served
+            published_hash(&target)?
+        };
+        entries.push(MirrorManifestSkill {
+            name: skill.name.clone(),
+            path: format!("{}/{}", MIRROR_SKILLS_DIR, skill.name),
+            content_hash,
+            description: frontmatter_value(source, "description"),
+            license: skill.license.clone(),
+            tags: skill.tags.clone(),
+            updated_at: skill.updated_at,
+            files: list_files(&target),
+        });
+    }
+
+    // Only skills this mirror published before are removed
+    for name in previous.keys() {
+        if is_plain_name(name) && !entries.iter().any(|entry| &entry.name == name) {
+            let stale = skills_root.join(name);
+            if stale.exists() {
+                std::fs::remove_dir_all(&stale)
+                    .with_context(|| format!("remove stale mirror skill {:?}", stale))?;
+            }
+            result.removed.push(name.clone());
+        }
+    }
+
+    let manifest = MirrorManifest {
+        format: MIRROR_MANIFEST_FORMAT.to_string(),
+        version: MIRROR_MANIFEST_VERSION,
+        published_at: now_ms(),
+        collection,
+        skills: entries,
+    };
+    let json = serde_json::to_string_pretty(&manifest).context("serialize mirror manifest")?;
+    let manifest_path = root.join(MIRROR_MANIFEST_FILE);
+    let staging = root.join(format!(".{}.tmp", MIRROR_MANIFEST_FILE));
+    std::fs::write(&staging, json).with_context(|| format!("write {:?}", staging))?;
+    std::fs::rename(&staging, &manifest_path)
+        .with_context(|| format!("move {:?} -> {:?}", staging, manifest_path))?;
+    result.path = root.to_string_lossy().to_string();
+    Ok(result)
+}
+
+/// Where a consumed mirror lives
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MirrorLocation {
+    Dir(PathBuf),
+    Url(String),
+}
+
+impl MirrorLocation {
+    pub fn parse(location: &str) -> Result<Self> {
+        let location = location.trim();
+        if location.is_empty() {
+            anyhow::bail!("mirror location is empty");
+        }
+        let lower = location.to_ascii_lowercase();
+        if lower.starts_with("http://") || lower.starts_with("https://") {
+            return Ok(MirrorLocation::Url(
+                location.trim_end_matches('/').to_string(),
+            ));
+        }
+        let path = location
+            .strip_prefix("file://")
+            .unwrap_or(location)
+            .to_string();
+        let path = expand_home_path(&path)?;
+        if !path.is_absolute() {
+            anyhow::bail!("mirror path must be absolute: {}", location);
+        }
+        Ok(MirrorLocation::Dir(path))
+    }
+
+    fn url_of(&self, relative: &str) -> Option<String> {
+        match self {
+            MirrorLocation::Url(base) => Some(format!("{}/{}", base, relative)),
+            MirrorLocation::Dir(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MirrorSkillDto {
+    pub name: String,
+    pub description: Option<String>,
+    pub license: Option<String>,
+    pub tags: Vec<String>,
+    pub content_hash: String,
+    pub updated_at: i64,
+    /// Managed skill installed from this entry
+    pub installed_skill_id: Option<String>,
+    pub update_available: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MirrorListingDto {
+    pub location: String,
+    pub collection: Option<String>,
+    pub published_at: i64,
+    pub skills: Vec<MirrorSkillDto>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MirrorUpdateDto {
+    pub skill_id: String,
+    pub name: String,
+    pub location: String,
+    pub current_hash: Option<String>,
+    /// `None` when the mirror no longer publishes the skill
+    pub available_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn load_sources(state: &SqliteDbState) -> Vec<String> {
+    read_skill_settings_value_from_sqlite(state, SOURCES_SETTINGS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_sources(state: &SqliteDbState, sources: &[String]) -> Result<(), String> {
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            SOURCES_SETTINGS_KEY: sources,
+            "updated_at": now_ms(),
+        }),
+    )
+}
+
+pub fn mirror_source_ref(location: &str, name: &str) -> String {
+    format!("{}#{}", location.trim(), name)
+}
+
+/// `(location, name)` of a `team_mirror` source_ref
+pub fn parse_mirror_source_ref(source_ref: &str) -> Option<(&str, &str)> {
+    source_ref
+        .rsplit_once('#')
+        .filter(|(location, name)| !location.is_empty() && !name.is_empty())
+}
+
+async fn fetch_bytes(state: &SqliteDbState, url: &str) -> Result<Vec<u8>> {
+    let client = http_client::client_with_timeout(state, FETCH_TIMEOUT_SECS)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        anyhow::bail!("failed to fetch {}: HTTP {}", url, response.status());
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .with_context(|| format!("failed to read {}", url))?;
+    if bytes.len() > MAX_FILE_BYTES {
+        anyhow::bail!("{} is larger than {} bytes", url, MAX_FILE_BYTES);
+    }
+    Ok(bytes.to_vec())
+}
+
+pub async fn fetch_manifest(
+    state: &SqliteDbState,
+    location: &MirrorLocation,
+) -> Result<MirrorManifest> {
+    match location {
+        MirrorLocation::Dir(root) => read_manifest(root),
+        MirrorLocation::Url(_) => {
+            let url = location.url_of(MIRROR_MANIFEST_FILE).unwrap_or_default();
+            let bytes = fetch_bytes(state, &url).await?;
+            parse_manifest(&bytes).with_context(|| format!("parse {}", url))
+        }
+    }
+}
+
+/// Hash of the files a mirror serves for a skill (see `list_files`), which
+/// a consumer can recompute from what it fetched
+fn published_hash(dir: &Path) -> Result<String> {
+    hash_dir_with_rules(dir, &SkillPathRules::default(), &SkillIgnore::default())
+}
+
+/// Reject a fetched skill whose files do not hash to the manifest's
+/// `content_hash`
+fn verify_fetched(entry: &MirrorManifestSkill, dest: &Path) -> Result<()> {
+    let fetched = published_hash(dest)?;
+    if fetched != entry.content_hash {
+        anyhow::bail!(
+            "mirror skill {} does not match its manifest: expected content hash {}, got {}",
+            entry.name,
+            entry.content_hash,
+            fetched
+        );
+    }
+    Ok(())
+}
+
+/// Copy the skill of `entry` (listed by `manifest`) into `dest` and check it
+/// against the entry's `content_hash`
+pub async fn fetch_skill(
+    state: &SqliteDbState,
+    location: &MirrorLocation,
+    manifest: &MirrorManifest,
+    entry: &MirrorManifestSkill,
+    dest: &Path,
+) -> Result<()> {
+    let verifiable = manifest.version >= 2;
+    match location {
+        MirrorLocation::Dir(root) => {
+            let source = manifest_skill_dir(root, entry)?;
+            if !source.is_dir() {
+                anyhow::bail!("mirror skill not found: {:?}", source);
+            }
+            copy_skill_dir(&source, dest)
+                .with_context(|| format!("copy {:?} -> {:?}", source, dest))?;
+        }
+        MirrorLocation::Url(_) => {
+            if !verifiable {
+                anyhow::bail!(
+                    "mirror manifest version {} cannot be verified over HTTP; republish the mirror",
+                    manifest.version
+                );
+            }
+            manifest_skill_dir(Path::new(""), entry)?;
+            if entry.files.is_empty() {
+                anyhow::bail!("mirror manifest lists no files for {}", entry.name);
+            }
+            for file in &entry.files {
+                let relative = safe_entry_path(Path::new(file))
+                    .with_context(|| format!("invalid file path in mirror manifest: {}", file))?;
+                let url = location
+                    .url_of(&format!("{}/{}", entry.path, file))
+                    .unwrap_or_default();
+                let bytes = fetch_bytes(state, &url).await?;
+                let target = dest.join(relative);
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| format!("create dir {:?}", parent))?;
+                }
+                std::fs::write(&target, bytes).with_context(|| format!("write {:?}", target))?;
+            }
+        }
+    }
+    if verifiable {
+        verify_fetched(entry, dest)?;
+    }
+    Ok(())
+}
+
+/// Manifest entries annotated with the managed skills installed from them
+pub fn list_mirror_skills(
+    location: &str,
+    manifest: MirrorManifest,
+    managed: &[Skill],
+) -> MirrorListingDto {
+    let skills = manifest
+        .skills
+        .into_iter()
+        .map(|entry| {
+            let installed = managed.iter().find(|skill| {
+                skill.source_type == TEAM_MIRROR_SOURCE_TYPE
+                    && skill
+                        .source_ref
+                        .as_deref()
+                        .and_then(parse_mirror_source_ref)
+                        == Some((location, entry.name.as_str()))
+            });
+            MirrorSkillDto {
+                update_available: installed.is_some_and(|skill| {
+                    skill.source_revision.as_ref() != Some(&entry.content_hash)
+                }),
+                installed_skill_id: installed.map(|skill| skill.id.clone()),
+                name: entry.name,
+                description: entry.description,
+                license: entry.license,
+                tags: entry.tags,
+                content_hash: entry.content_hash,
+                updated_at: entry.updated_at,
+            }
+        })
+        .collect();
+    MirrorListingDto {
+        location: location.to_string(),
+        collection: manifest.collection,
+        published_at: manifest.published_at,
+        skills,
+    }
+}
+
+/// Compare `team_mirror` skills with their mirror manifests, reading each
+/// manifest once
+pub async fn check_mirror_updates(state: &SqliteDbState, skills: &[Skill]) -> Vec<MirrorUpdateDto> {
+    let mut manifests: BTreeMap<String, Result<MirrorManifest, String>> = BTreeMap::new();
+    let mut updates = Vec::new();
+    for skill in skills
+        .iter()
+        .filter(|skill| skill.source_type == TEAM_MIRROR_SOURCE_TYPE)
+    {
+        let Some((location, name)) = skill
+            .source_ref
+            .as_deref()
+            .and_then(parse_mirror_source_ref)
+        else {
+            continue;
+        };
+        if !manifests.contains_key(location) {
+            let manifest = match MirrorLocation::parse(location) {
+                Ok(parsed) => fetch_manifest(state, &parsed).await,
+                Err(err) => Err(err),
+            };
+            manifests.insert(
+                location.to_string(),
+                manifest.map_err(|e| format!("{:#}", e)),
+            );
+        }
+        let (available_hash, error) = match &manifests[location] {
+            Ok(manifest) => (
+                manifest
+                    .skills
+                    .iter()
+                    .find(|entry| entry.name == name)
+                    .map(|entry| entry.content_hash.clone()),
+                None,
+            ),
+            Err(err) => (None, Some(err.clone())),
+        };
+        if error.is_none() && available_hash.is_some() && available_hash == skill.source_revision {
+            continue;
+        }
+        updates.push(MirrorUpdateDto {
+            skill_id: skill.id.clone(),
+            name: skill.name.clone(),
+            location: location.to_string(),
+            current_hash: skill.source_revision.clone(),
+            available_hash,
+            error,
+        });
+    }
+    updates
+}
+
+fn parse_manifest(raw: &[u8]) -> Result<MirrorManifest> {
+    let manifest: MirrorManifest = serde_json::from_slice(raw)?;
+    if manifest.format != MIRROR_MANIFEST_FORMAT {
+        anyhow::bail!("not a skill mirror manifest");
+    }
+    if manifest.version > MIRROR_MANIFEST_VERSION {
+        anyhow::bail!(
+            "mirror manifest version {} is newer than supported ({})",
+            manifest.version,
+            MIRROR_MANIFEST_VERSION
+        );
+    }
+    Ok(manifest)
+}
+
+/// Copy next to `target` first so a failed copy keeps the old content
+fn replace_dir(source: &Path, target: &Path) -> Result<()> {
+    let file_name = target
+        .file_name()
+        .with_context(|| format!("target has no file name: {:?}", target))?;
+    let staging = target.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).with_context(|| format!("remove {:?}", staging))?;
+    }
+    if let Err(err) = copy_skill_dir(source, &staging) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(err).with_context(|| format!("copy {:?} -> {:?}", source, staging));
+    }
+    if target.exists() {
+        std::fs::remove_dir_all(target).with_context(|| format!("remove {:?}", target))?;
+    }
+    std::fs::rename(&staging, target).with_context(|| format!("move {:?} -> {:?}", staging, target))
+}
+
+fn list_files(dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            entry
+                .path()
+                .strip_prefix(dir)
+                .ok()
+                .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn is_plain_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &str, dir: &Path) -> (Skill, PathBuf) {
+        let skill = crate::coding::skills::adapter::from_db_skill(serde_json::json!({
+            "id": name,
+            "name": name,
+            "source_type": "local",
+        }));
+        (skill, dir.to_path_buf())
+    }
+
+    #[test]
+    fn publish_copies_changed_skills_and_removes_dropped_ones() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let mirror = temp.path().join("share");
+        for name in ["review", "lint"] {
+            std::fs::create_dir_all(central.join(name)).unwrap();
+            std::fs::write(
+                central.join(name).join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: {name} skill\n---\n"),
+            )
+            .unwrap();
+        }
+
+        let first = publish_mirror(
+            &mirror,
+            None,
+            &[
+                skill("review", &central.join("review")),
+                skill("lint", &central.join("lint")),
+            ],
+        )
+        .unwrap();
+        assert_eq!(first.copied, vec!["review", "lint"]);
+        let manifest = read_manifest(&mirror).unwrap();
+        assert_eq!(manifest.skills.len(), 2);
+        assert_eq!(manifest.skills[0].path, "skills/review");
+        assert_eq!(
+            manifest.skills[0].description.as_deref(),
+            Some("review skill")
+        );
+        assert!(mirror.join("skills/lint/SKILL.md").is_file());
+
+        std::fs::write(central.join("review/extra.md"), "more").unwrap();
+        let second =
+            publish_mirror(&mirror, None, &[skill("review", &central.join("review"))]).unwrap();
+        assert_eq!(second.copied, vec!["review"]);
+        assert_eq!(second.removed, vec!["lint"]);
+        assert!(mirror.join("skills/review/extra.md").is_file());
+        assert!(!mirror.join("skills/lint").exists());
+
+        let third =
+            publish_mirror(&mirror, None, &[skill("review", &central.join("review"))]).unwrap();
+        assert!(third.copied.is_empty());
+        assert_eq!(third.unchanged, 1);
+    }
+
+    #[test]
+    fn fetched_skills_must_match_the_manifest_hash() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let mirror = temp.path().join("share");
+        std::fs::create_dir_all(central.join("review/docs")).unwrap();
+        std::fs::write(central.join("review/SKILL.md"), "review").unwrap();
+        std::fs::write(central.join("review/docs/usage.md"), "usage").unwrap();
+        publish_mirror(&mirror, None, &[skill("review", &central.join("review"))]).unwrap();
+        let manifest = read_manifest(&mirror).unwrap();
+        let entry = &manifest.skills[0];
+
+        // A download rebuilt from the listed files verifies
+        let fetched = temp.path().join("fetched");
+        for file in &entry.files {
+            let target = fetched.join(file);
+            std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+            std::fs::copy(mirror.join(&entry.path).join(file), &target).unwrap();
+        }
+        verify_fetched(entry, &fetched).unwrap();
+
+        std::fs::write(fetched.join("docs/usage.md"), "tampered").unwrap();
+        let err = verify_fetched(entry, &fetched).unwrap_err();
+        assert!(err.to_string().contains("does not match its manifest"));
+    }
+
+    #[test]
+    fn manifest_paths_must_stay_inside_the_mirror() {
+        let entry = |path: &str| MirrorManifestSkill {
+            name: "x".to_string(),
+            path: path.to_string(),
+            content_hash: String::new(),
+            description: None,
+            license: None,
+            tags: Vec::new(),
+            updated_at: 0,
+            files: Vec::new(),
+        };
+        let root = Path::new("/mnt/share");
+        assert!(manifest_skill_dir(root, &entry("skills/x")).is_ok());
+        assert!(manifest_skill_dir(root, &entry("../x")).is_err());
+        assert!(manifest_skill_dir(root, &entry("/etc")).is_err());
+        assert!(manifest_skill_dir(root, &entry("")).is_err());
+    }
+
+    #[test]
+    fn listing_marks_installed_and_outdated_skills() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let mirror = temp.path().join("share");
+        for name in ["review", "lint"] {
+            std::fs::create_dir_all(central.join(name).join("docs")).unwrap();
+            std::fs::write(central.join(name).join("SKILL.md"), name).unwrap();
+            std::fs::write(central.join(name).join("docs/usage.md"), name).unwrap();
+        }
+        publish_mirror(
+            &mirror,
+            Some("team".to_string()),
+            &[
+                skill("review", &central.join("review")),
+                skill("lint", &central.join("lint")),
+            ],
+        )
+        .unwrap();
+        let manifest = read_manifest(&mirror).unwrap();
+        assert_eq!(manifest.skills[0].files, vec!["SKILL.md", "docs/usage.md"]);
+
+        let location = mirror.to_string_lossy().to_string();
+        assert_eq!(
+            MirrorLocation::parse(&location).unwrap(),
+            MirrorLocation::Dir(mirror.clone())
+        );
+        let source_ref = mirror_source_ref(&location, "review");
+        assert_eq!(
+            parse_mirror_source_ref(&source_ref),
+            Some((location.as_str(), "review"))
+        );
+        let mut installed = crate::coding::skills::adapter::from_db_skill(serde_json::json!({
+            "id": "s1",
+            "name": "review",
+            "source_type": TEAM_MIRROR_SOURCE_TYPE,
+            "source_ref": source_ref,
+            "source_revision": "outdated",
+        }));
+        let listing = list_mirror_skills(&location, manifest.clone(), &[installed.clone()]);
+        assert_eq!(listing.collection.as_deref(), Some("team"));
+        assert_eq!(listing.skills[0].installed_skill_id.as_deref(), Some("s1"));
+        assert!(listing.skills[0].update_available);
+        assert_eq!(listing.skills[1].installed_skill_id, None);
+
+        installed.source_revision = Some(manifest.skills[0].content_hash.clone());
+        let listing = list_mirror_skills(&location, manifest, &[installed]);
+        assert!(!listing.skills[0].update_available);
+    }
+}
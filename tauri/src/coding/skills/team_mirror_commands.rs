@@ -0,0 +1,207 @@
+//! Commands for publishing to and installing from read-only team mirrors
+//! (`team_mirror.rs`)
+
+use tauri::State;
+
+use super::central_repo::resolve_central_repo_path;
+use super::collections;
+use super::commands::{
+    format_error, install_result_dto, normalize_optional_id, resolve_local_source_path,
+};
+use super::installer::install_team_mirror_skill;
+use super::skill_store;
+use super::team_mirror::{
+    self, MirrorListingDto, MirrorPublishResultDto, MirrorUpdateDto, TeamMirrorTarget,
+};
+use super::types::InstallResultDto;
+use crate::settings::policy;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+#[tauri::command]
+pub async fn skills_get_mirror_targets(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<TeamMirrorTarget>, String> {
+    Ok(team_mirror::load_targets(&state))
+}
+
+/// Create (`id` empty) or update a read-only mirror target. `collectionId`
+/// limits the mirror to one collection.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_save_mirror_target(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    id: Option<String>,
+    path: String,
+    collectionId: Option<String>,
+    enabled: bool,
+) -> Result<String, String> {
+    ensure_writable("skills_save_mirror_target")?;
+    let path = resolve_local_source_path(path.trim())?;
+    if !path.is_absolute() {
+        return Err(format!("Mirror path must be absolute: {}", path.display()));
+    }
+    let central_dir = resolve_central_repo_path(&app, &state)
+        .await
+        .map_err(|e| format_error(e))?;
+    if path.starts_with(&central_dir) || central_dir.starts_with(&path) {
+        return Err(format!(
+            "Mirror path overlaps the central repo: {}",
+            path.display()
+        ));
+    }
+    let collection_id = normalize_optional_id(collectionId);
+    if let Some(collection_id) = &collection_id {
+        if collections::get_collection(&state, collection_id)?.is_none() {
+            return Err(format!("Collection not found: {}", collection_id));
+        }
+    }
+
+    let mut targets = team_mirror::load_targets(&state);
+    let path = path.to_string_lossy().to_string();
+    let id = match normalize_optional_id(id) {
+        Some(id) => {
+            let target = targets
+                .iter_mut()
+                .find(|target| target.id == id)
+                .ok_or_else(|| format!("Mirror target not found: {}", id))?;
+            target.path = path;
+            target.collection_id = collection_id;
+            target.enabled = enabled;
+            id
+        }
+        None => {
+            let id = crate::coding::db_id::db_new_id();
+            targets.push(TeamMirrorTarget {
+                id: id.clone(),
+                path,
+                collection_id,
+                enabled,
+                last_published_at: None,
+                last_error: None,
+            });
+            id
+        }
+    };
+    team_mirror::save_targets(&state, &targets)?;
+    Ok(id)
+}
+
+/// Forget a mirror target; the published files are left in place
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_delete_mirror_target(
+    state: State<'_, SqliteDbState>,
+    targetId: String,
+) -> Result<(), String> {
+    ensure_writable("skills_delete_mirror_target")?;
+    let mut targets = team_mirror::load_targets(&state);
+    targets.retain(|target| target.id != targetId);
+    team_mirror::save_targets(&state, &targets)
+}
+
+/// Publish one mirror target now, or every enabled one when `targetId` is empty
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_publish_mirror(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    targetId: Option<String>,
+) -> Result<Vec<MirrorPublishResultDto>, String> {
+    ensure_writable("skills_publish_mirror")?;
+    let target_id = normalize_optional_id(targetId);
+    team_mirror::publish_targets(&app, &state, target_id.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn skills_get_mirror_sources(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<String>, String> {
+    Ok(team_mirror::load_sources(&state))
+}
+
+/// Remember a team mirror (dir or URL) to install from; its manifest must be
+/// readable
+#[tauri::command]
+pub async fn skills_add_mirror_source(
+    state: State<'_, SqliteDbState>,
+    location: String,
+) -> Result<MirrorListingDto, String> {
+    ensure_writable("skills_add_mirror_source")?;
+    let location = location.trim().to_string();
+    let listing = list_mirror_source(&state, &location).await?;
+    let mut sources = team_mirror::load_sources(&state);
+    if !sources.contains(&location) {
+        sources.push(location);
+        team_mirror::save_sources(&state, &sources)?;
+    }
+    Ok(listing)
+}
+
+/// Forget a mirror source; skills installed from it stay managed
+#[tauri::command]
+pub async fn skills_remove_mirror_source(
+    state: State<'_, SqliteDbState>,
+    location: String,
+) -> Result<(), String> {
+    ensure_writable("skills_remove_mirror_source")?;
+    let mut sources = team_mirror::load_sources(&state);
+    sources.retain(|source| source != location.trim());
+    team_mirror::save_sources(&state, &sources)
+}
+
+#[tauri::command]
+pub async fn skills_list_mirror_skills(
+    state: State<'_, SqliteDbState>,
+    location: String,
+) -> Result<MirrorListingDto, String> {
+    list_mirror_source(&state, location.trim()).await
+}
+
+async fn list_mirror_source(
+    state: &SqliteDbState,
+    location: &str,
+) -> Result<MirrorListingDto, String> {
+    policy::ensure_source_allowed(location)?;
+    let mirror = team_mirror::MirrorLocation::parse(location).map_err(format_error)?;
+    let manifest = team_mirror::fetch_manifest(state, &mirror)
+        .await
+        .map_err(format_error)?;
+    let managed = skill_store::get_managed_skills(state).await?;
+    Ok(team_mirror::list_mirror_skills(
+        location, manifest, &managed,
+    ))
+}
+
+#[tauri::command]
+pub async fn skills_install_from_mirror(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    location: String,
+    name: String,
+    overwrite: Option<bool>,
+) -> Result<InstallResultDto, String> {
+    ensure_writable("skills_install_from_mirror")?;
+    let result = install_team_mirror_skill(
+        &app,
+        &state,
+        location.trim(),
+        &name,
+        overwrite.unwrap_or(false),
+    )
+    .await
+    .map_err(format_error)?;
+
+    Ok(install_result_dto(&state, result))
+}
+
+/// Skills installed from team mirrors whose manifest publishes different
+/// content
+#[tauri::command]
+pub async fn skills_check_mirror_updates(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<MirrorUpdateDto>, String> {
+    let skills = skill_store::get_managed_skills(&state).await?;
+    Ok(team_mirror::check_mirror_updates(&state, &skills).await)
+}
@@ -7,7 +7,9 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
-use crate::coding::tools::{self, BUILTIN_TOOLS};
+use super::sync_engine::resolve_link_target;
+use super::types::SyncMode;
+use crate::coding::tools::{self, BoxedToolAdapter, ToolLoadRules, BUILTIN_TOOLS, STANDARD_RULES};
 
 /// Legacy CustomTool type for backward compatibility with Skills
 /// This type has required fields while the new tools::CustomTool has optional fields
@@ -21,6 +23,9 @@ pub struct CustomTool {
     /// Force copy mode for skills sync (instead of symlink)
     #[serde(default)]
     pub force_copy: bool,
+    /// Sync mode for skills sync; `None` links with a copy fallback
+    #[serde(default)]
+    pub preferred_sync_mode: Option<String>,
 }
 
 /// Convert from shared CustomTool to skills CustomTool
@@ -33,6 +38,7 @@ impl From<tools::CustomTool> for CustomTool {
             relative_detect_dir: tool.relative_detect_dir.unwrap_or_default(),
             created_at: tool.created_at,
             force_copy: tool.force_copy,
+            preferred_sync_mode: tool.preferred_sync_mode,
         }
     }
 }
@@ -46,6 +52,7 @@ impl From<&CustomTool> for tools::CustomTool {
             relative_skills_dir: Some(tool.relative_skills_dir.clone()),
             relative_detect_dir: Some(tool.relative_detect_dir.clone()),
             force_copy: tool.force_copy,
+            preferred_sync_mode: tool.preferred_sync_mode.clone(),
             mcp_config_path: None,
             mcp_config_format: None,
             mcp_field: None,
@@ -61,6 +68,7 @@ pub struct ToolAdapter {
     pub display_name: &'static str,
     pub relative_skills_dir: &'static str,
     pub relative_detect_dir: &'static str,
+    pub preferred_sync_mode: Option<&'static str>,
 }
 
 /// Get all default tool adapters (built-in tools that support Skills)
@@ -74,6 +82,7 @@ pub fn default_tool_adapters() -> Vec<ToolAdapter> {
                 display_name: tool.display_name,
                 relative_skills_dir: tool.relative_skills_dir?,
                 relative_detect_dir: tool.relative_detect_dir?,
+                preferred_sync_mode: tool.preferred_sync_mode,
             })
         })
         .collect()
@@ -81,6 +90,7 @@ pub fn default_tool_adapters() -> Vec<ToolAdapter> {
 
 /// Find adapter by key
 pub fn adapter_by_key(key: &str) -> Option<ToolAdapter> {
+    let key = tools::canonical_tool_key(key);
     default_tool_adapters()
         .into_iter()
         .find(|adapter| adapter.key == key)
@@ -110,6 +120,110 @@ pub struct RuntimeToolAdapter {
     pub is_custom: bool,
     /// Force copy mode for skills sync (instead of symlink)
     pub force_copy: bool,
+    /// Sync mode for skills sync; `None` links with a copy fallback
+    pub preferred_sync_mode: Option<String>,
+}
+
+impl RuntimeToolAdapter {
+    /// Mode skills are synced to this tool with: `Copy` when copies are
+    /// forced, otherwise the tool's preferred mode or `Auto`
+    pub fn sync_mode(&self) -> SyncMode {
+        if self.force_copy {
+            return SyncMode::Copy;
+        }
+        self.preferred_sync_mode
+            .as_deref()
+            .and_then(SyncMode::parse)
+            .unwrap_or(SyncMode::Auto)
+    }
+}
+
+impl RuntimeToolAdapter {
+    /// Adapter of the built-in tool this is, for its quirks; `None` for
+    /// custom tools
+    fn builtin(&self) -> Option<BoxedToolAdapter> {
+        if self.is_custom {
+            return None;
+        }
+        tools::builtin_adapter(&self.key)
+    }
+}
+
+/// Lets built-in and custom tools be handed to `ai_toolbox_core` as is;
+/// built-in tools keep the quirks of their own adapter, custom tools have
+/// none
+impl ai_toolbox_core::ToolAdapter for RuntimeToolAdapter {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn skills_dir(&self) -> Option<&str> {
+        Some(&self.relative_skills_dir)
+    }
+
+    fn detect_dir(&self) -> Option<&str> {
+        Some(&self.relative_detect_dir)
+    }
+
+    fn sync_mode(&self) -> SyncMode {
+        RuntimeToolAdapter::sync_mode(self)
+    }
+
+    fn detects_by_mcp_config(&self) -> bool {
+        self.builtin()
+            .is_some_and(|adapter| adapter.detects_by_mcp_config())
+    }
+
+    fn has_runtime_location(&self) -> bool {
+        self.builtin()
+            .is_some_and(|adapter| adapter.has_runtime_location())
+    }
+
+    fn system_skill_dirs(&self) -> &'static [&'static str] {
+        system_skill_dirs(&self.key)
+    }
+
+    fn load_rules(&self) -> ToolLoadRules {
+        self.builtin()
+            .map(|adapter| adapter.load_rules())
+            .unwrap_or(STANDARD_RULES)
+    }
+
+    fn after_sync(&self, target: &Path) -> Result<()> {
+        match self.builtin() {
+            Some(adapter) => adapter.after_sync(target),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Dirs in the skills dir of `tool` that hold the tool's own skills
+/// (`ToolAdapter::system_skill_dirs`); none for custom tools
+pub fn system_skill_dirs(tool: &str) -> &'static [&'static str] {
+    tools::builtin_adapter(tool)
+        .map(|adapter| adapter.system_skill_dirs())
+        .unwrap_or(&[])
+}
+
+pub(crate) fn is_system_skill_dir(tool: &str, name: &str) -> bool {
+    system_skill_dirs(tool).contains(&name)
+}
+
+/// Normalize a preferred sync mode entered for a tool; `auto` and empty
+/// values clear the preference
+pub fn normalize_preferred_sync_mode(value: Option<&str>) -> Result<Option<String>, String> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    match SyncMode::parse(value) {
+        Some(SyncMode::Auto) => Ok(None),
+        Some(mode) => Ok(Some(mode.as_str().to_string())),
+        None => Err(format!("Unsupported sync mode: {}", value)),
+    }
 }
 
 impl From<&ToolAdapter> for RuntimeToolAdapter {
@@ -120,7 +234,8 @@ impl From<&ToolAdapter> for RuntimeToolAdapter {
             relative_skills_dir: adapter.relative_skills_dir.to_string(),
             relative_detect_dir: adapter.relative_detect_dir.to_string(),
             is_custom: false,
-            force_copy: false, // Built-in tools use default (cursor handled specially in sync logic)
+            force_copy: false,
+            preferred_sync_mode: adapter.preferred_sync_mode.map(|s| s.to_string()),
         }
     }
 }
@@ -134,6 +249,7 @@ impl From<&CustomTool> for RuntimeToolAdapter {
             relative_detect_dir: tool.relative_detect_dir.clone(),
             is_custom: true,
             force_copy: tool.force_copy,
+            preferred_sync_mode: tool.preferred_sync_mode.clone(),
         }
     }
 }
@@ -298,7 +414,7 @@ pub fn scan_tool_dir(
 
         let name = entry.file_name().to_string_lossy().to_string();
         // Skip system directories
-        if adapter.key == "codex" && name == ".system" {
+        if is_system_skill_dir(adapter.key, &name) {
             continue;
         }
 
@@ -328,11 +444,15 @@ pub fn scan_tool_dir(
 fn detect_link(path: &Path) -> (bool, Option<PathBuf>) {
     match std::fs::symlink_metadata(path) {
         Ok(metadata) if metadata.file_type().is_symlink() => {
-            let target = std::fs::read_link(path).ok();
+            let target = std::fs::read_link(path)
+                .ok()
+                .map(|stored| resolve_link_target(path, &stored));
             (true, target)
         }
         _ => {
-            let target = std::fs::read_link(path).ok();
+            let target = std::fs::read_link(path)
+                .ok()
+                .map(|stored| resolve_link_target(path, &stored));
             if target.is_some() {
                 (true, target)
             } else {
@@ -0,0 +1,117 @@
+//! Background tool detection and onboarding for tools installed while the
+//! app runs
+//!
+//! Every `POLL_INTERVAL` the watcher re-detects the built-in tools
+//! (`tools::detection_cache::refresh_detection`), which keeps the detection
+//! cache fresh for `skills_get_tool_status` and `mcp_get_tools` and emits
+//! `TOOLS_DETECTION_CHANGED_EVENT` when a cached answer was wrong. The first
+//! pass is the baseline; a skills tool that shows up later (e.g. `~/.factory`
+//! created by a Droid install) gets a scan of just its skills dir
+//! (`onboarding::build_tool_onboarding_plan`) and `TOOL_DETECTED_EVENT`
+//! carries the result to the frontend. Nothing is imported here.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use log::{info, warn};
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use super::onboarding;
+use super::tool_adapters::adapter_by_key;
+use super::types::OnboardingPlan;
+use crate::coding::tools::builtin_tool_by_key;
+use crate::coding::tools::detection_cache::{refresh_detection, TOOLS_DETECTION_CHANGED_EVENT};
+use crate::db::SqliteDbState;
+
+pub const TOOL_DETECTED_EVENT: &str = "skills-tool-detected";
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ToolDetectedEventDto {
+    pub tool: String,
+    pub tool_display: String,
+    /// Unmanaged skills found in the new tool
+    pub plan: OnboardingPlan,
+}
+
+pub fn start_tool_detection_watcher(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut installed: Option<HashSet<String>> = None;
+        loop {
+            let current = installed_tools(&app_handle).await;
+            if let Some(previous) = &installed {
+                for tool in appeared(previous, &current) {
+                    if adapter_by_key(&tool).is_some() {
+                        on_tool_detected(&app_handle, &tool).await;
+                    }
+                }
+            }
+            installed = Some(current);
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Built-in tools installed now; custom tools always count as installed, so
+/// only built-ins can appear
+async fn installed_tools(app_handle: &tauri::AppHandle) -> HashSet<String> {
+    let state = app_handle.state::<SqliteDbState>();
+    let (installed, change) = refresh_detection(state.db()).await;
+    if let Some(change) = change {
+        info!(
+            "Installed tools changed: added {:?}, removed {:?}",
+            change.added, change.removed
+        );
+        let _ = app_handle.emit(TOOLS_DETECTION_CHANGED_EVENT, change);
+    }
+    installed.into_iter().collect()
+}
+
+/// Tools in `current` that were not installed at the previous check, sorted
+fn appeared(previous: &HashSet<String>, current: &HashSet<String>) -> Vec<String> {
+    let mut tools: Vec<String> = current.difference(previous).cloned().collect();
+    tools.sort();
+    tools
+}
+
+async fn on_tool_detected(app_handle: &tauri::AppHandle, tool: &str) {
+    let state = app_handle.state::<SqliteDbState>();
+    let plan = match onboarding::build_tool_onboarding_plan(app_handle, &state, tool).await {
+        Ok(plan) => plan,
+        Err(e) => {
+            warn!("Onboarding scan of new tool '{}' failed: {:#}", tool, e);
+            return;
+        }
+    };
+    let tool_display = builtin_tool_by_key(tool)
+        .map(|builtin| builtin.display_name.to_string())
+        .unwrap_or_else(|| tool.to_string());
+    info!(
+        "New tool '{}' detected with {} unmanaged skill(s)",
+        tool, plan.total_skills_found
+    );
+    let _ = app_handle.emit(
+        TOOL_DETECTED_EVENT,
+        ToolDetectedEventDto {
+            tool: tool.to_string(),
+            tool_display,
+            plan,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_only_tools_missing_from_the_previous_check() {
+        let set = |keys: &[&str]| keys.iter().map(|key| key.to_string()).collect();
+        assert_eq!(
+            appeared(&set(&["codex", "cursor"]), &set(&["droid", "codex", "amp"])),
+            vec!["amp", "droid"]
+        );
+        assert!(appeared(&set(&["codex"]), &set(&[])).is_empty());
+    }
+}
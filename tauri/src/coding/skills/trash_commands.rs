@@ -0,0 +1,62 @@
+//! Commands for the sync journal and its trash (`sync_journal`)
+
+use std::path::Path;
+
+use tauri::{AppHandle, Emitter};
+
+use super::commands::format_error;
+use super::confirmation::{self, DangerousOp};
+use super::sync_context;
+use super::sync_journal::{self, SyncJournalEntry, SyncTrashPurgeSummary};
+use crate::settings::read_only::ensure_writable;
+
+/// What syncs replaced or removed and kept in the trash, newest first;
+/// `target` limits the list to one target path
+#[tauri::command]
+pub async fn skills_list_sync_journal(
+    target: Option<String>,
+) -> Result<Vec<SyncJournalEntry>, String> {
+    let Ok(trash) = sync_context::trash_dir() else {
+        return Ok(Vec::new());
+    };
+    Ok(sync_journal::list_entries(
+        &trash,
+        target.as_deref().map(Path::new),
+    ))
+}
+
+/// Put back what the most recent sync replaced or removed at `target`. The
+/// skill's target record is not changed; status checks report the restored
+/// content as it is.
+#[tauri::command]
+pub async fn skills_rollback_last_sync(
+    app: AppHandle,
+    target: String,
+) -> Result<SyncJournalEntry, String> {
+    ensure_writable("skills_rollback_last_sync")?;
+    let trash = sync_context::trash_dir()?;
+    let entry = tokio::task::spawn_blocking(move || {
+        sync_journal::rollback_last_sync(&trash, Path::new(&target))
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?
+    .map_err(format_error)?;
+    let _ = app.emit("skills-changed", "window");
+    Ok(entry)
+}
+
+/// Empty the sync trash; no sync can be rolled back afterwards. Needs a
+/// `purge_trash` confirmation token.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_purge_sync_trash(
+    confirmationToken: Option<String>,
+) -> Result<SyncTrashPurgeSummary, String> {
+    ensure_writable("skills_purge_sync_trash")?;
+    confirmation::consume(DangerousOp::PurgeTrash, None, confirmationToken.as_deref())?;
+    let trash = sync_context::trash_dir()?;
+    tokio::task::spawn_blocking(move || sync_journal::purge_all(&trash))
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e))?
+        .map_err(format_error)
+}
@@ -7,13 +7,15 @@ use tauri::{AppHandle, Emitter, Manager, Runtime};
 
 use super::adapter::parse_sync_details;
 use super::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
+use super::commands::resolve_tool_target;
 use super::path_executor::{remove_skill_target_checked, sync_skill_to_target};
 use super::skill_store;
+use super::target_marker;
 use super::tool_adapters::{
-    get_all_tool_adapters, is_tool_installed_with_state_async,
-    resolve_runtime_skills_path_with_state_async, runtime_adapter_by_key,
+    get_all_tool_adapters, is_tool_installed_with_state_async, runtime_adapter_by_key,
 };
 use super::types::{now_ms, SkillTarget};
+use super::variants;
 use crate::SqliteDbState;
 
 /// Item for tool selection in skill submenu
@@ -156,6 +158,7 @@ pub async fn apply_skills_tool_toggle<R: Runtime>(
     skill_id: &str,
     tool_key: &str,
 ) -> Result<(), String> {
+    crate::settings::read_only::ensure_writable("skills_tray_toggle")?;
     let state = app.state::<SqliteDbState>();
 
     let custom_tools = skill_store::get_custom_tools(&state)
@@ -191,17 +194,17 @@ pub async fn apply_skills_tool_toggle<R: Runtime>(
             .map_err(|e| format!("{:#}", e))?;
         skill_store::delete_skill_target(&state, skill_id, tool_key).await?;
     } else {
-        let tool_root = resolve_runtime_skills_path_with_state_async(state.db(), &runtime_adapter)
-            .await
-            .map_err(|e| format!("{:#}", e))?;
-        let target = tool_root.join(&skill.name);
+        let sync_source_path = variants::resolve_variant_source(&state, skill_id, tool_key)?
+            .unwrap_or_else(|| skill_source_path.clone());
+        let (_, target) =
+            resolve_tool_target(&state, &skill, &runtime_adapter, &sync_source_path, None).await?;
 
         let result = sync_skill_to_target(
-            tool_key,
-            &skill_source_path,
+            &sync_source_path,
             &target,
             true,
-            runtime_adapter.force_copy,
+            runtime_adapter.sync_mode(),
+            &Default::default(),
         )
         .map_err(|e| format!("{:#}", e))?;
 
@@ -212,7 +215,9 @@ pub async fn apply_skills_tool_toggle<R: Runtime>(
             status: "ok".to_string(),
             error_message: None,
             synced_at: Some(now_ms()),
+            path_rules: Default::default(),
         };
+        target_marker::mark_target(&skill, &record);
         skill_store::upsert_skill_target(&state, skill_id, &record).await?;
     }
 
@@ -1,62 +1,75 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // Re-export CustomTool from tool_adapters for backward compatibility
 pub use super::tool_adapters::CustomTool;
+// Skill records and sync types live in the Tauri-free core crate
+pub use ai_toolbox_core::skills::types::{
+    now_ms, Skill, SkillLocalization, SkillPathRules, SkillProvenance, SkillTarget, SyncDryRun,
+    SyncFileChanges, SyncFileMismatch, SyncMismatchKind, SyncMode, SyncOutcome, SyncTargetSpec,
+};
 
-/// Skill record stored in SQLite JSONB
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Skill {
+pub struct SkillGroupRecord {
     pub id: String,
     pub name: String,
-    pub source_type: String, // "local" | "git" | "import" | "central"
-    pub source_ref: Option<String>,
-    pub source_revision: Option<String>,
-    pub central_path: String,
-    pub content_hash: Option<String>,
+    pub note: Option<String>,
+    pub sort_index: i32,
     pub created_at: i64,
     pub updated_at: i64,
-    pub last_sync_at: Option<i64>,
-    pub status: String,
-
-    // Sort order for drag-and-drop reordering
-    pub sort_index: i32,
-
-    // User-managed local metadata for organization inside AI Toolbox.
-    pub user_group: Option<String>,
-    pub group_id: Option<String>,
-    pub user_note: Option<String>,
-    pub management_enabled: bool,
-    pub disabled_previous_tools: Vec<String>,
-
-    // Enabled tool keys list
-    pub enabled_tools: Vec<String>, // ["claude_code", "codex", "opencode"]
-
-    // Sync details JSON (per-tool target_path/mode/status etc.)
-    // Structure: { "claude_code": { "target_path": "...", "mode": "...", ... }, ... }
-    pub sync_details: Option<Value>,
 }
 
+/// Alternative content version of a skill (SQLite `skill_variant` table).
+/// The content is a snapshot stored as blobs (see `variants.rs`); tools
+/// listed in `assigned_tools` sync from its checkout instead of the central
+/// repo copy.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct SkillGroupRecord {
+pub struct SkillVariant {
     pub id: String,
+    pub skill_id: String,
     pub name: String,
-    pub note: Option<String>,
-    pub sort_index: i32,
+    /// Absolute path of the variant checkout directory
+    pub stored_path: String,
+    pub content_hash: Option<String>,
+    /// Files of the snapshot; empty for variants stored as a plain copy
+    /// before the blob store
+    #[serde(default)]
+    pub files: Vec<SkillVariantFile>,
+    #[serde(default)]
+    pub assigned_tools: Vec<String>,
     pub created_at: i64,
     pub updated_at: i64,
 }
 
-/// Skill target info - used within sync_details (no longer a separate table)
+/// One file of a skill variant, stored as a blob keyed by `hash`
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct SkillTarget {
-    pub tool: String,
-    pub target_path: String,
-    pub mode: String, // "symlink" | "copy" | "junction"
-    pub status: String,
-    pub synced_at: Option<i64>,
-    pub error_message: Option<String>,
+pub struct SkillVariantFile {
+    /// Path relative to the skill directory, `/` separated
+    pub path: String,
+    pub hash: String,
+    pub size: u64,
+    /// Size of the compressed blob
+    pub stored_size: u64,
+    #[serde(default)]
+    pub executable: bool,
+}
+
+/// Named bundle of skills (SQLite `skill_collection` table). Unlike groups,
+/// a skill can belong to any number of collections; enabling a collection for
+/// a tool syncs every member to that tool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillCollection {
+    pub id: String,
+    pub name: String,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub skill_ids: Vec<String>,
+    /// Repo the collection was defined by (e.g. "owner/name"), if any
+    #[serde(default)]
+    pub source: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
 }
 
 /// Skill repository source - user configured skill source repos
@@ -124,6 +137,8 @@ pub struct ToolInfoDto {
     pub label: String,
     pub installed: bool,
     pub skills_dir: String,
+    /// Broken symlinks / junctions in `skills_dir`, whoever created them
+    pub dangling_links: Vec<String>,
 }
 
 /// DTO for managed skills (frontend display)
@@ -150,6 +165,12 @@ pub struct ManagedSkillDto {
     pub source_error: Option<String>,
     pub enabled_tools: Vec<String>,
     pub targets: Vec<SkillTargetDto>, // Derived from sync_details
+    pub license: Option<String>,
+    pub provenance: Option<SkillProvenance>,
+    pub tags: Vec<String>,
+    pub compatibility: BTreeMap<String, String>,
+    pub localizations: BTreeMap<String, SkillLocalization>,
+    pub write_protected: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -363,6 +384,8 @@ pub struct SkillTargetDto {
     pub status: String,
     pub target_path: String,
     pub synced_at: Option<i64>,
+    #[serde(skip_serializing_if = "SkillPathRules::is_empty")]
+    pub path_rules: SkillPathRules,
 }
 
 /// DTO for install result
@@ -372,6 +395,9 @@ pub struct InstallResultDto {
     pub name: String,
     pub central_path: String,
     pub content_hash: Option<String>,
+    pub license: Option<String>,
+    /// Set when the license is in the user's disallowed list
+    pub license_warning: Option<String>,
 }
 
 /// DTO for sync result
@@ -379,6 +405,23 @@ pub struct InstallResultDto {
 pub struct SyncResultDto {
     pub mode_used: String,
     pub target_path: String,
+    /// See `SyncOutcome::verified`
+    pub verified: Option<bool>,
+    pub mismatches: Vec<SyncFileMismatch>,
+    /// See `SyncOutcome::file_changes`
+    pub file_changes: Option<SyncFileChanges>,
+}
+
+/// DTO for a sync preview (`skills_preview_sync`)
+#[derive(Debug, Serialize)]
+pub struct SyncPreviewDto {
+    pub mode_used: String,
+    pub target_path: String,
+    /// The existing target would be replaced
+    pub replaced: bool,
+    pub created: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub removed: Vec<String>,
 }
 
 /// DTO for update result
@@ -400,21 +443,43 @@ pub struct GitSkillCandidate {
 }
 
 /// Onboarding plan for discovered skills
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OnboardingPlan {
     pub total_tools_scanned: usize,
     pub total_skills_found: usize,
     pub groups: Vec<OnboardingGroup>,
+    /// When the previous scan ran; `None` on the first scan
+    pub previous_scan_at: Option<i64>,
+    /// Group names with a variant path the previous scan did not see
+    #[serde(default)]
+    pub new_since_last_scan: Vec<String>,
+    /// Variant directories hashed by this scan (the rest reused the index)
+    #[serde(default)]
+    pub rehashed_dirs: usize,
+    /// Skills a tool ships in its own system dir; listed only when asked for
+    /// and never offered for adoption
+    #[serde(default)]
+    pub system_skills: Vec<SystemSkill>,
 }
 
-#[derive(Clone, Debug, Serialize)]
+/// A skill a tool manages itself (e.g. Codex's `.system` skills)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SystemSkill {
+    pub tool: String,
+    pub tool_display: String,
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OnboardingGroup {
     pub name: String,
     pub variants: Vec<OnboardingVariant>,
     pub has_conflict: bool,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OnboardingVariant {
     pub tool: String,
     /// Human-readable tool label for display (e.g. "Plugin: demo-plugin" instead of "plugin::demo-plugin@xxx")
@@ -424,6 +489,8 @@ pub struct OnboardingVariant {
     pub fingerprint: Option<String>,
     pub is_link: bool,
     pub link_target: Option<String>,
+    /// Resolved folder when the link points outside every tool dir
+    pub linked_folder: Option<String>,
     /// Tools that have the same skill name but different content (conflicting versions)
     pub conflicting_tools: Vec<String>,
 }
@@ -434,6 +501,7 @@ pub struct InstallResult {
     pub name: String,
     pub central_path: std::path::PathBuf,
     pub content_hash: Option<String>,
+    pub license: Option<String>,
 }
 
 /// Internal struct for update operations
@@ -446,34 +514,6 @@ pub struct UpdateResult {
     pub updated_targets: Vec<String>,
 }
 
-/// Sync mode used for skill syncing
-#[derive(Clone, Debug)]
-pub enum SyncMode {
-    Auto,
-    Symlink,
-    Junction,
-    Copy,
-}
-
-impl SyncMode {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            SyncMode::Auto => "auto",
-            SyncMode::Symlink => "symlink",
-            SyncMode::Junction => "junction",
-            SyncMode::Copy => "copy",
-        }
-    }
-}
-
-/// Sync outcome from sync operations
-#[derive(Clone, Debug)]
-pub struct SyncOutcome {
-    pub mode_used: SyncMode,
-    pub target_path: std::path::PathBuf,
-    pub replaced: bool,
-}
-
 /// Detected skill in a tool directory
 #[derive(Clone, Debug)]
 pub struct DetectedSkill {
@@ -495,6 +535,7 @@ pub struct CustomToolDto {
     pub relative_detect_dir: String,
     pub created_at: i64,
     pub force_copy: bool,
+    pub preferred_sync_mode: Option<String>,
 }
 
 /// DTO for skill repo
@@ -507,11 +548,3 @@ pub struct SkillRepoDto {
     pub enabled: bool,
     pub created_at: i64,
 }
-
-/// Helper function to get current timestamp in milliseconds
-pub fn now_ms() -> i64 {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::SystemTime::UNIX_EPOCH)
-        .unwrap_or_default();
-    now.as_millis() as i64
-}
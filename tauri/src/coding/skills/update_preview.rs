@@ -0,0 +1,336 @@
+//! Preview of what `skills_update_managed` would change
+//!
+//! The upstream content is staged exactly like a real update, then compared
+//! file by file with the central copy. For git sources the installed revision
+//! (`source_revision`) is the common base: the changelog lists the commits
+//! after it, and local files whose git blob id differs from the base are
+//! reported as local modifications that the update would overwrite. Without a
+//! usable base (non-git sources, or a cache re-cloned after install) only the
+//! skill-level `content_hash` tells whether the central copy was edited.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::content_hash::{hash_dir, is_ignored_path, list_files};
+use super::git_fetcher::{self, GitCommitInfo};
+use super::installer::GitCheckout;
+use crate::coding::config_snapshot::diff::unified_file_diff;
+
+/// Max commits listed in the changelog
+const CHANGELOG_LIMIT: usize = 200;
+/// Files larger than this (on either side) get no inline patch
+const PATCH_MAX_BYTES: u64 = 64 * 1024;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillUpdateCommitDto {
+    pub revision: String,
+    pub author: String,
+    pub committed_at: i64,
+    pub summary: String,
+}
+
+impl From<GitCommitInfo> for SkillUpdateCommitDto {
+    fn from(commit: GitCommitInfo) -> Self {
+        Self {
+            revision: commit.revision,
+            author: commit.author,
+            committed_at: commit.committed_at,
+            summary: commit.summary,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillUpdateFileDto {
+    pub path: String,
+    /// "added" | "removed" | "modified", from the central copy to upstream
+    pub status: String,
+    /// Whether the central copy of this file differs from the installed
+    /// revision; `None` when there is no base to compare with
+    pub locally_modified: Option<bool>,
+    /// Unified diff from the central copy to upstream; `None` for large files
+    pub patch: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillUpdatePreviewDto {
+    pub skill_id: String,
+    pub name: String,
+    pub source_type: String,
+    pub current_revision: Option<String>,
+    pub new_revision: Option<String>,
+    /// Commits after the installed revision, newest first; `None` when the
+    /// source has no history or the installed revision is not in the cache
+    pub changelog: Option<Vec<SkillUpdateCommitDto>>,
+    /// Files that differ between the central copy and upstream
+    pub files: Vec<SkillUpdateFileDto>,
+    /// The central copy no longer matches what was installed
+    pub has_local_modifications: bool,
+    /// Locally modified files the update would overwrite or delete
+    pub overwritten_local_files: Vec<String>,
+}
+
+/// Changelog and per-file local changes derived from the git base revision
+#[derive(Default)]
+pub struct GitBaseline {
+    pub changelog: Option<Vec<SkillUpdateCommitDto>>,
+    /// Files of the central copy that differ from the installed revision
+    pub local_changes: Option<BTreeSet<String>>,
+}
+
+/// Compare the central copy against the installed revision in the git cache
+pub fn git_baseline(
+    checkout: &GitCheckout,
+    installed_revision: Option<&str>,
+    new_revision: Option<&str>,
+    central_path: &Path,
+) -> GitBaseline {
+    let Some(installed) = installed_revision.filter(|rev| !rev.is_empty()) else {
+        return GitBaseline::default();
+    };
+    let repo_dir = &checkout.repo_dir;
+    let subpath = checkout.subpath.as_deref();
+    let changelog = new_revision.and_then(|new| {
+        git_fetcher::commits_between(repo_dir, installed, new, subpath, CHANGELOG_LIMIT)
+            .map_err(|err| log::warn!("[update_preview] changelog unavailable: {:#}", err))
+            .ok()
+            .flatten()
+            .map(|commits| commits.into_iter().map(Into::into).collect())
+    });
+    let local_changes = if git_fetcher::has_commit(repo_dir, installed) {
+        local_changes_against(repo_dir, installed, subpath, central_path)
+            .map_err(|err| log::warn!("[update_preview] base comparison failed: {:#}", err))
+            .ok()
+    } else {
+        None
+    };
+    GitBaseline {
+        changelog,
+        local_changes,
+    }
+}
+
+fn local_changes_against(
+    repo_dir: &Path,
+    revision: &str,
+    subpath: Option<&str>,
+    central_path: &Path,
+) -> Result<BTreeSet<String>> {
+    let base = git_fetcher::tree_blob_ids(repo_dir, revision, subpath)?
+        .into_iter()
+        .filter(|(path, _)| !is_ignored_path(path))
+        .collect::<BTreeMap<_, _>>();
+    let files = list_files(central_path)?;
+    let paths = files
+        .iter()
+        .map(|file| central_path.join(file))
+        .collect::<Vec<PathBuf>>();
+    let local = files
+        .into_iter()
+        .zip(git_fetcher::hash_blob_ids(repo_dir, &paths)?)
+        .collect::<BTreeMap<_, _>>();
+    Ok(base
+        .keys()
+        .chain(local.keys())
+        .filter(|path| base.get(*path) != local.get(*path))
+        .cloned()
+        .collect())
+}
+
+/// File-level diff from `central_path` to the staged upstream content
+pub fn diff_against_upstream(
+    central_path: &Path,
+    upstream_path: &Path,
+    local_changes: Option<&BTreeSet<String>>,
+) -> Result<Vec<SkillUpdateFileDto>> {
+    let local = list_files(central_path)?;
+    let upstream = list_files(upstream_path)?;
+    let paths = local.iter().chain(upstream.iter()).collect::<BTreeSet<_>>();
+
+    let mut files = Vec::new();
+    for path in paths {
+        let local_file = central_path.join(path);
+        let upstream_file = upstream_path.join(path);
+        let local_bytes = std::fs::read(&local_file).ok();
+        let upstream_bytes = std::fs::read(&upstream_file).ok();
+        let status = match (&local_bytes, &upstream_bytes) {
+            (None, None) => continue,
+            (None, Some(_)) => "added",
+            (Some(_), None) => "removed",
+            (Some(a), Some(b)) if a == b => continue,
+            (Some(_), Some(_)) => "modified",
+        };
+        let too_large = [&local_bytes, &upstream_bytes]
+            .iter()
+            .any(|bytes| bytes.as_ref().map(|b| b.len() as u64).unwrap_or(0) > PATCH_MAX_BYTES);
+        files.push(SkillUpdateFileDto {
+            path: path.clone(),
+            status: status.to_string(),
+            locally_modified: local_changes.map(|changes| changes.contains(path)),
+            patch: (!too_large).then(|| {
+                unified_file_diff(path, local_bytes.as_deref(), upstream_bytes.as_deref())
+            }),
+        });
+    }
+    Ok(files)
+}
+
+/// Whether the central copy still matches the hash recorded at install/update
+pub fn central_copy_modified(central_path: &Path, recorded_hash: Option<&str>) -> bool {
+    match recorded_hash {
+        Some(recorded) => hash_dir(central_path)
+            .map(|current| current != recorded)
+            .unwrap_or(false),
+        None => false,
+    }
+}
+
+/// Locally modified files among `files`. Without a per-file base, every
+/// differing file is at risk once the skill as a whole was edited.
+pub fn overwritten_local_files(
+    files: &[SkillUpdateFileDto],
+    has_local_modifications: bool,
+) -> Vec<String> {
+    files
+        .iter()
+        .filter(|file| {
+            file.locally_modified
+                .unwrap_or(has_local_modifications && file.status != "added")
+        })
+        .map(|file| file.path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn diff_reports_changes_and_local_modifications() {
+        let temp = tempfile::tempdir().unwrap();
+        let central = temp.path().join("central");
+        let upstream = temp.path().join("upstream");
+        write(&central, "SKILL.md", "---\nname: demo\n---\nlocal edit\n");
+        write(&central, "same.txt", "same");
+        write(&central, "old.txt", "gone upstream");
+        write(&central, ".DS_Store", "ignored");
+        write(&upstream, "SKILL.md", "---\nname: demo\n---\nupstream\n");
+        write(&upstream, "same.txt", "same");
+        write(&upstream, "scripts/new.sh", "echo hi");
+
+        let local_changes = BTreeSet::from(["SKILL.md".to_string()]);
+        let files = diff_against_upstream(&central, &upstream, Some(&local_changes)).unwrap();
+        let summary = files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.as_str(),
+                    file.status.as_str(),
+                    file.locally_modified,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("SKILL.md", "modified", Some(true)),
+                ("old.txt", "removed", Some(false)),
+                ("scripts/new.sh", "added", Some(false)),
+            ]
+        );
+        assert!(files[0].patch.as_deref().unwrap().contains("+upstream"));
+        assert_eq!(overwritten_local_files(&files, true), vec!["SKILL.md"]);
+
+        // Without a per-file base, every existing differing file is at risk
+        let files = diff_against_upstream(&central, &upstream, None).unwrap();
+        assert_eq!(
+            overwritten_local_files(&files, true),
+            vec!["SKILL.md", "old.txt"]
+        );
+        assert!(overwritten_local_files(&files, false).is_empty());
+
+        let recorded = hash_dir(&central).unwrap();
+        assert!(!central_copy_modified(&central, Some(&recorded)));
+        write(&central, "same.txt", "edited");
+        assert!(central_copy_modified(&central, Some(&recorded)));
+    }
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let out = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args([
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn git_baseline_lists_commits_and_local_edits() {
+        if std::process::Command::new("git")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path().join("repo");
+        write(&repo, "skills/demo/SKILL.md", "v1");
+        write(&repo, "skills/demo/keep.txt", "keep");
+        write(&repo, "README.md", "outside the skill");
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["add", "-A"]);
+        git(&repo, &["commit", "-qm", "initial"]);
+        let installed = git(&repo, &["rev-parse", "HEAD"]);
+        write(&repo, "skills/demo/SKILL.md", "v2");
+        git(&repo, &["commit", "-qam", "Update demo"]);
+        write(&repo, "README.md", "unrelated");
+        git(&repo, &["commit", "-qam", "Touch readme"]);
+        let head = git(&repo, &["rev-parse", "HEAD"]);
+
+        // Central copy installed at `installed`, then keep.txt was edited
+        let central = temp.path().join("central");
+        write(&central, "SKILL.md", "v1");
+        write(&central, "keep.txt", "edited");
+
+        let checkout = GitCheckout {
+            repo_dir: repo,
+            subpath: Some("skills/demo".to_string()),
+        };
+        let baseline = git_baseline(&checkout, Some(&installed), Some(&head), &central);
+        let changelog = baseline.changelog.unwrap();
+        assert_eq!(changelog.len(), 1);
+        assert_eq!(changelog[0].summary, "Update demo");
+        assert_eq!(
+            baseline.local_changes.unwrap(),
+            BTreeSet::from(["keep.txt".to_string()])
+        );
+
+        let missing = git_baseline(&checkout, Some("0123456789abcdef"), Some(&head), &central);
+        assert!(missing.changelog.is_none());
+        assert!(missing.local_changes.is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//! Commands for skill A/B variants (`variants.rs`)
+
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use super::commands::{
+    normalize_optional_id, normalize_optional_text, resolve_local_source_path,
+    resolve_skill_source_path, sync_skill_to_tool_records,
+};
+use super::skill_store;
+use super::types::{now_ms, Skill, SkillVariant};
+use super::variants;
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Re-sync the given tools of a skill so they pick up their current variant
+/// assignment. Tools the skill is not synced to are left alone.
+async fn resync_skill_variant_tools<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    skill: &Skill,
+    tools: &[String],
+) -> Result<Vec<String>, String> {
+    if !skill.management_enabled {
+        return Ok(Vec::new());
+    }
+    let source_path = resolve_skill_source_path(app, state, skill).await?;
+    let custom_tools = skill_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+
+    let mut synced_tools = Vec::new();
+    for tool in tools {
+        if skill_store::get_skill_target(state, &skill.id, tool)
+            .await?
+            .is_some()
+        {
+            synced_tools.push(tool.clone());
+        }
+    }
+    let results = sync_skill_to_tool_records(
+        state,
+        skill,
+        &synced_tools,
+        &source_path,
+        true,
+        &custom_tools,
+    )
+    .await;
+    Ok(results
+        .into_iter()
+        .filter_map(|(tool, result)| result.err().map(|err| format!("{}: {}", tool, err)))
+        .collect())
+}
+
+fn variant_resync_result(errors: Vec<String>) -> Result<(), String> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Variant assignment saved, but some tools could not be re-synced:\n- {}",
+            errors.join("\n- ")
+        ))
+    }
+}
+
+/// Unassigned variants kept per skill, newest first; 0 keeps everything
+#[tauri::command]
+pub async fn skills_get_variants_per_skill(state: State<'_, SqliteDbState>) -> Result<u32, String> {
+    Ok(variants::get_variants_per_skill(&state))
+}
+
+#[tauri::command]
+pub async fn skills_set_variants_per_skill<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    keep: i64,
+) -> Result<u32, String> {
+    ensure_writable("skills_set_variants_per_skill")?;
+    let keep = variants::set_variants_per_skill(&state, keep)?;
+    variants::apply_variant_retention(&app, &state)?;
+    Ok(keep)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_list_variants(
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+) -> Result<Vec<SkillVariant>, String> {
+    variants::list_skill_variants(&state, &skillId)
+}
+
+/// Snapshot a skill's content as a new variant. Without `sourcePath` the
+/// current central repo copy is captured.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_create_variant<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    name: String,
+    sourcePath: Option<String>,
+) -> Result<SkillVariant, String> {
+    ensure_writable("skills_create_variant")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Variant name is required".to_string());
+    }
+    let source = match normalize_optional_text(sourcePath) {
+        Some(path) => resolve_local_source_path(&path)?,
+        None => resolve_skill_source_path(&app, &state, &skill).await?,
+    };
+
+    let id = crate::coding::db_id::db_new_id();
+    let root = variants::variants_root(&app)?;
+    // Blobs of a failed capture are unreferenced and collected by retention
+    let (files, content_hash) = variants::capture_variant_content(&source, &root, &id)?;
+    let now = now_ms();
+    let variant = SkillVariant {
+        stored_path: root.join(&id).to_string_lossy().to_string(),
+        id,
+        skill_id: skill.id,
+        name,
+        content_hash: Some(content_hash),
+        files,
+        assigned_tools: Vec::new(),
+        created_at: now,
+        updated_at: now,
+    };
+    variants::put_skill_variant(&state, &variant)?;
+    variants::apply_variant_retention(&app, &state)?;
+    Ok(variant)
+}
+
+/// Assign a tool to a variant (`variantId` = None switches it back to the
+/// central copy) and re-sync that tool.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_assign_variant<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    tool: String,
+    variantId: Option<String>,
+) -> Result<(), String> {
+    ensure_writable("skills_assign_variant")?;
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let mut skill_variants = variants::list_skill_variants(&state, &skill.id)?;
+    let variant_id = normalize_optional_id(variantId);
+    if let Some(variant_id) = variant_id.as_deref() {
+        if !skill_variants
+            .iter()
+            .any(|variant| variant.id == variant_id)
+        {
+            return Err(format!("Skill variant not found: {}", variant_id));
+        }
+    }
+
+    let changed =
+        variants::assign_tool_to_variant(&mut skill_variants, &tool, variant_id.as_deref());
+    save_changed_variants(&state, &skill_variants, &changed)?;
+
+    let errors = resync_skill_variant_tools(&app, &state, &skill, &[tool]).await?;
+    variants::apply_variant_retention(&app, &state)?;
+    let _ = app.emit("skills-changed", "window");
+    variant_resync_result(errors)
+}
+
+/// Exchange the variant assignments of two tools and re-sync both, e.g. to
+/// flip A/B between Claude Code and Codex.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_swap_variants<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    skillId: String,
+    toolA: String,
+    toolB: String,
+) -> Result<(), String> {
+    ensure_writable("skills_swap_variants")?;
+    if toolA == toolB {
+        return Ok(());
+    }
+    let skill = skill_store::get_skill_by_id(&state, &skillId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", skillId))?;
+    let mut skill_variants = variants::list_skill_variants(&state, &skill.id)?;
+    let changed = variants::swap_tool_variants(&mut skill_variants, &toolA, &toolB);
+    save_changed_variants(&state, &skill_variants, &changed)?;
+
+    let errors = resync_skill_variant_tools(&app, &state, &skill, &[toolA, toolB]).await?;
+    variants::apply_variant_retention(&app, &state)?;
+    let _ = app.emit("skills-changed", "window");
+    variant_resync_result(errors)
+}
+
+/// Delete a variant; tools assigned to it switch back to the central copy.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_delete_variant<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    variantId: String,
+) -> Result<(), String> {
+    ensure_writable("skills_delete_variant")?;
+    let Some(variant) = variants::get_skill_variant(&state, &variantId)? else {
+        return Ok(());
+    };
+    variants::delete_skill_variant(&state, &variant.id)?;
+
+    let mut errors = Vec::new();
+    if let Some(skill) = skill_store::get_skill_by_id(&state, &variant.skill_id).await? {
+        errors = resync_skill_variant_tools(&app, &state, &skill, &variant.assigned_tools).await?;
+    }
+    variants::remove_variant_content(&variant)?;
+    variants::apply_variant_retention(&app, &state)?;
+
+    let _ = app.emit("skills-changed", "window");
+    variant_resync_result(errors)
+}
+
+fn save_changed_variants(
+    state: &SqliteDbState,
+    skill_variants: &[SkillVariant],
+    changed: &[String],
+) -> Result<(), String> {
+    let now = now_ms();
+    for variant in skill_variants
+        .iter()
+        .filter(|variant| changed.contains(&variant.id))
+    {
+        let mut variant = variant.clone();
+        variant.updated_at = now;
+        variants::put_skill_variant(state, &variant)?;
+    }
+    Ok(())
+}
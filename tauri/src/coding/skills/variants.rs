@@ -0,0 +1,513 @@
+//! Skill A/B variants
+//!
+//! A variant is a snapshot of a skill directory. Its files are stored once in
+//! a content-addressed blob store of their own
+//! (`{app_data_dir}/config-snapshots/skill-variants/blobs/`, see
+//! `config_snapshot::blob_store`), listed in the variant record. Tools
+//! assigned to a variant sync from a checkout of those blobs in
+//! `skill-variants/{variant_id}/`, written on first use and removed once no
+//! tool is assigned any more; every other tool keeps syncing from the central
+//! repo copy. Variants created before the blob store have no file list and
+//! keep their plain copy as the checkout.
+//!
+//! Retention is separate from config snapshots: `variants_per_skill` in the
+//! skill settings caps the unassigned variants kept per skill, and blobs no
+//! variant references are collected after every prune.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use tauri::{AppHandle, Runtime};
+
+use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_i64_from_sqlite};
+use super::content_hash::{hash_dir, list_files};
+use super::sync_engine::{copy_skill_dir, ensure_source_dir};
+use super::types::{now_ms, SkillVariant, SkillVariantFile};
+use crate::coding::config_snapshot::blob_store;
+use crate::coding::config_snapshot::snapshot::snapshots_root;
+use crate::coding::db_id::db_clean_id;
+use crate::db::helpers::{db_delete, db_get, db_list, db_put, db_query_by_field};
+use crate::db::schema::{DbTable, JsonFieldPath, OrderDirection, OrderField, OrderSpec};
+use crate::SqliteDbState;
+
+pub const VARIANTS_DIR_NAME: &str = "skill-variants";
+const VARIANTS_PER_SKILL_KEY: &str = "variants_per_skill";
+const MAX_VARIANTS_PER_SKILL: i64 = 1000;
+/// Blobs written this recently are never collected, so a variant being
+/// captured while retention runs keeps its content
+const BLOB_GC_MIN_AGE: Duration = Duration::from_secs(600);
+
+pub fn variants_root<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    Ok(snapshots_root(app)?.join(VARIANTS_DIR_NAME))
+}
+
+/// Unassigned variants kept per skill, newest first; 0 keeps everything
+pub fn get_variants_per_skill(state: &SqliteDbState) -> u32 {
+    read_skill_settings_i64_from_sqlite(state, VARIANTS_PER_SKILL_KEY)
+        .and_then(|keep| u32::try_from(keep).ok())
+        .unwrap_or(0)
+}
+
+pub fn set_variants_per_skill(state: &SqliteDbState, keep: i64) -> Result<u32, String> {
+    if !(0..=MAX_VARIANTS_PER_SKILL).contains(&keep) {
+        return Err(format!(
+            "variants per skill must be between 0 and {}",
+            MAX_VARIANTS_PER_SKILL
+        ));
+    }
+    merge_skill_settings_sqlite(
+        state,
+        serde_json::json!({
+            VARIANTS_PER_SKILL_KEY: keep,
+            "updated_at": now_ms(),
+        }),
+    )?;
+    Ok(keep as u32)
+}
+
+fn variant_order() -> Result<OrderSpec, String> {
+    Ok(OrderSpec::single(OrderField::json_integer(
+        "created_at",
+        OrderDirection::Asc,
+    )?))
+}
+
+fn sqlite_value_to_variant(value: serde_json::Value) -> Result<SkillVariant, String> {
+    serde_json::from_value(value)
+        .map(|mut variant: SkillVariant| {
+            variant.id = db_clean_id(&variant.id);
+            variant
+        })
+        .map_err(|error| format!("Failed to parse SQLite skill variant: {error}"))
+}
+
+/// Variants of one skill, oldest first
+pub fn list_skill_variants(
+    state: &SqliteDbState,
+    skill_id: &str,
+) -> Result<Vec<SkillVariant>, String> {
+    let order = variant_order()?;
+    let field = JsonFieldPath::new("skill_id")?;
+    state.with_conn(|conn| {
+        db_query_by_field(
+            conn,
+            DbTable::SkillVariant,
+            &field,
+            &serde_json::Value::String(skill_id.to_string()),
+            Some(&order),
+            None,
+        )?
+        .into_iter()
+        .map(sqlite_value_to_variant)
+        .collect()
+    })
+}
+
+/// Variants of every skill, oldest first
+pub fn list_all_skill_variants(state: &SqliteDbState) -> Result<Vec<SkillVariant>, String> {
+    let order = variant_order()?;
+    state.with_conn(|conn| {
+        db_list(conn, DbTable::SkillVariant, Some(&order))?
+            .into_iter()
+            .map(sqlite_value_to_variant)
+            .collect()
+    })
+}
+
+pub fn get_skill_variant(
+    state: &SqliteDbState,
+    variant_id: &str,
+) -> Result<Option<SkillVariant>, String> {
+    state.with_conn(|conn| {
+        db_get(conn, DbTable::SkillVariant, &db_clean_id(variant_id))?
+            .map(sqlite_value_to_variant)
+            .transpose()
+    })
+}
+
+pub fn put_skill_variant(state: &SqliteDbState, variant: &SkillVariant) -> Result<(), String> {
+    let data = serde_json::to_value(variant)
+        .map_err(|error| format!("Failed to serialize skill variant: {error}"))?;
+    state.with_conn(|conn| db_put(conn, DbTable::SkillVariant, &variant.id, &data))
+}
+
+pub fn delete_skill_variant(state: &SqliteDbState, variant_id: &str) -> Result<(), String> {
+    state.with_conn(|conn| {
+        db_delete(conn, DbTable::SkillVariant, &db_clean_id(variant_id)).map(|_| ())
+    })
+}
+
+/// Store the content of `source` in the blob store under `root`.
+/// Returns the stored files and the content hash of the snapshot.
+///
+/// The directory is first copied to a staging dir with the sync engine's
+/// copy rules (symlinks resolved, `.git` and the target marker skipped), so
+/// a checkout matches what a copy-mode sync of `source` would produce.
+pub fn capture_variant_content(
+    source: &Path,
+    root: &Path,
+    variant_id: &str,
+) -> Result<(Vec<SkillVariantFile>, String), String> {
+    ensure_source_dir(source).map_err(|e| format!("{:#}", e))?;
+    let staging = root.join(format!("{}.capture", variant_id));
+    remove_dir_if_exists(&staging)?;
+    let captured = copy_skill_dir(source, &staging)
+        .map_err(|e| format!("Failed to snapshot skill variant: {:#}", e))
+        .and_then(|_| store_variant_files(&staging, root));
+    let _ = remove_dir_if_exists(&staging);
+    captured
+}
+
+fn store_variant_files(
+    staging: &Path,
+    root: &Path,
+) -> Result<(Vec<SkillVariantFile>, String), String> {
+    let content_hash =
+        hash_dir(staging).map_err(|e| format!("Failed to hash skill variant: {:#}", e))?;
+    let mut files = Vec::new();
+    for relative in list_files(staging).map_err(|e| format!("{:#}", e))? {
+        let path = staging.join(&relative);
+        let content = std::fs::read(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let (hash, stored_size) = blob_store::put_blob(root, &content)?;
+        files.push(SkillVariantFile {
+            executable: is_executable(&path),
+            path: relative,
+            hash,
+            size: content.len() as u64,
+            stored_size,
+        });
+    }
+    Ok((files, content_hash))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|meta| meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .map_err(|e| format!("Failed to set permissions on {}: {}", path.display(), e))
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// Write the checkout of a blob-backed variant unless it exists; legacy
+/// variants are their own checkout. Returns the checkout directory.
+pub fn ensure_variant_checkout(variant: &SkillVariant) -> Result<PathBuf, String> {
+    let checkout = PathBuf::from(&variant.stored_path);
+    if variant.files.is_empty() || checkout.is_dir() {
+        return Ok(checkout);
+    }
+    let root = checkout
+        .parent()
+        .ok_or_else(|| format!("Invalid skill variant path: {}", variant.stored_path))?;
+    let staging = root.join(format!("{}.checkout", variant.id));
+    remove_dir_if_exists(&staging)?;
+    let written = write_variant_files(&variant.files, root, &staging).and_then(|_| {
+        std::fs::rename(&staging, &checkout)
+            .map_err(|e| format!("Failed to write {}: {}", checkout.display(), e))
+    });
+    if written.is_err() {
+        let _ = remove_dir_if_exists(&staging);
+    }
+    written.map(|_| checkout)
+}
+
+fn write_variant_files(files: &[SkillVariantFile], root: &Path, dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for file in files {
+        // Records can come from an imported backup; never write outside `dir`
+        let relative = Path::new(&file.path);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(format!("Invalid skill variant file path: {}", file.path));
+        }
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = blob_store::read_blob(root, &file.hash)?;
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        if file.executable {
+            set_executable(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove the checkout of a variant. For a legacy variant this removes its
+/// only copy; blob-backed content stays until retention collects it.
+pub fn remove_variant_content(variant: &SkillVariant) -> Result<(), String> {
+    remove_dir_if_exists(Path::new(&variant.stored_path))
+}
+
+fn remove_dir_if_exists(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        std::fs::remove_dir_all(path)
+            .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Variant a tool is currently assigned to, if any
+pub fn assigned_variant<'a>(variants: &'a [SkillVariant], tool: &str) -> Option<&'a SkillVariant> {
+    variants
+        .iter()
+        .find(|variant| variant.assigned_tools.iter().any(|key| key == tool))
+}
+
+/// Assign `tool` to `variant_id` (`None` = back to the central copy).
+/// A tool is assigned to at most one variant. Returns the ids of variants
+/// whose assignment list changed.
+pub fn assign_tool_to_variant(
+    variants: &mut [SkillVariant],
+    tool: &str,
+    variant_id: Option<&str>,
+) -> Vec<String> {
+    let mut changed = Vec::new();
+    for variant in variants.iter_mut() {
+        let wanted = variant_id == Some(variant.id.as_str());
+        let assigned = variant.assigned_tools.iter().any(|key| key == tool);
+        if wanted && !assigned {
+            variant.assigned_tools.push(tool.to_string());
+            changed.push(variant.id.clone());
+        } else if !wanted && assigned {
+            variant.assigned_tools.retain(|key| key != tool);
+            changed.push(variant.id.clone());
+        }
+    }
+    changed
+}
+
+/// Exchange the variant assignments of two tools. Returns changed variant ids.
+pub fn swap_tool_variants(
+    variants: &mut [SkillVariant],
+    tool_a: &str,
+    tool_b: &str,
+) -> Vec<String> {
+    let variant_a = assigned_variant(variants, tool_a).map(|variant| variant.id.clone());
+    let variant_b = assigned_variant(variants, tool_b).map(|variant| variant.id.clone());
+    let mut changed = assign_tool_to_variant(variants, tool_a, variant_b.as_deref());
+    for id in assign_tool_to_variant(variants, tool_b, variant_a.as_deref()) {
+        if !changed.contains(&id) {
+            changed.push(id);
+        }
+    }
+    changed
+}
+
+/// Ids of variants to prune so that each skill keeps at most `keep` variants
+/// no tool is assigned to, newest first (`keep = 0` keeps everything).
+/// Assigned variants are sync sources and never pruned. `variants` must be
+/// sorted oldest first.
+pub fn select_pruned_variants(variants: &[SkillVariant], keep: u32) -> Vec<String> {
+    if keep == 0 {
+        return Vec::new();
+    }
+    let mut unassigned: BTreeMap<&str, Vec<&SkillVariant>> = BTreeMap::new();
+    for variant in variants
+        .iter()
+        .filter(|variant| variant.assigned_tools.is_empty())
+    {
+        unassigned
+            .entry(variant.skill_id.as_str())
+            .or_default()
+            .push(variant);
+    }
+    unassigned
+        .into_values()
+        .flat_map(|group| {
+            let excess = group.len().saturating_sub(keep as usize);
+            group.into_iter().take(excess)
+        })
+        .map(|variant| variant.id.clone())
+        .collect()
+}
+
+/// Apply the variant retention: prune unassigned variants beyond
+/// `variants_per_skill`, drop the checkouts of blob-backed variants no tool
+/// is assigned to and collect blobs no variant references. Returns the
+/// number of pruned variants.
+pub fn apply_variant_retention<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+) -> Result<usize, String> {
+    prune_variants(state, &variants_root(app)?, get_variants_per_skill(state))
+}
+
+pub fn prune_variants(state: &SqliteDbState, root: &Path, keep: u32) -> Result<usize, String> {
+    let mut variants = list_all_skill_variants(state)?;
+    let pruned = select_pruned_variants(&variants, keep);
+    for variant in variants
+        .iter()
+        .filter(|variant| pruned.contains(&variant.id))
+    {
+        remove_variant_content(variant)?;
+        delete_skill_variant(state, &variant.id)?;
+    }
+    variants.retain(|variant| !pruned.contains(&variant.id));
+
+    for variant in variants
+        .iter()
+        .filter(|variant| variant.assigned_tools.is_empty() && !variant.files.is_empty())
+    {
+        remove_variant_content(variant)?;
+    }
+    collect_variant_blob_garbage(root, &variants);
+    Ok(pruned.len())
+}
+
+/// Remove blobs no variant in `variants` references; returns the freed bytes
+pub fn collect_variant_blob_garbage(root: &Path, variants: &[SkillVariant]) -> u64 {
+    let referenced: HashSet<&str> = variants
+        .iter()
+        .flat_map(|variant| &variant.files)
+        .map(|file| file.hash.as_str())
+        .collect();
+    blob_store::remove_unreferenced_blobs(root, &referenced, BLOB_GC_MIN_AGE)
+}
+
+/// Source directory a tool should sync from when it is assigned to a
+/// variant; writes the variant checkout if needed
+pub fn resolve_variant_source(
+    state: &SqliteDbState,
+    skill_id: &str,
+    tool: &str,
+) -> Result<Option<PathBuf>, String> {
+    let variants = list_skill_variants(state, skill_id)?;
+    assigned_variant(&variants, tool)
+        .map(ensure_variant_checkout)
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(id: &str, tools: &[&str]) -> SkillVariant {
+        SkillVariant {
+            id: id.to_string(),
+            skill_id: "skill".to_string(),
+            name: id.to_string(),
+            stored_path: format!("/variants/{}", id),
+            content_hash: None,
+            files: Vec::new(),
+            assigned_tools: tools.iter().map(|tool| tool.to_string()).collect(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn assign_moves_tool_between_variants() {
+        let mut variants = vec![variant("a", &["claude_code"]), variant("b", &[])];
+
+        let changed = assign_tool_to_variant(&mut variants, "claude_code", Some("b"));
+        assert_eq!(changed, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(assigned_variant(&variants, "claude_code").unwrap().id, "b");
+
+        assign_tool_to_variant(&mut variants, "claude_code", None);
+        assert!(assigned_variant(&variants, "claude_code").is_none());
+    }
+
+    #[test]
+    fn swap_exchanges_assignments_including_central_copy() {
+        let mut variants = vec![variant("a", &["claude_code"]), variant("b", &["codex"])];
+        swap_tool_variants(&mut variants, "claude_code", "codex");
+        assert_eq!(assigned_variant(&variants, "claude_code").unwrap().id, "b");
+        assert_eq!(assigned_variant(&variants, "codex").unwrap().id, "a");
+
+        let changed = swap_tool_variants(&mut variants, "codex", "opencode");
+        assert_eq!(changed, vec!["a".to_string()]);
+        assert!(assigned_variant(&variants, "codex").is_none());
+        assert_eq!(assigned_variant(&variants, "opencode").unwrap().id, "a");
+    }
+
+    #[test]
+    fn prune_keeps_newest_unassigned_variants_per_skill() {
+        let mut other = variant("x", &[]);
+        other.skill_id = "other".to_string();
+        let variants = vec![
+            variant("a", &[]),
+            variant("b", &["codex"]),
+            variant("c", &[]),
+            variant("d", &[]),
+            other,
+        ];
+        assert_eq!(
+            select_pruned_variants(&variants, 1),
+            vec!["a".to_string(), "c".to_string()]
+        );
+        assert!(select_pruned_variants(&variants, 0).is_empty());
+    }
+
+    #[test]
+    fn checkout_restores_captured_content_from_blobs() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let source = temp.path().join("skill");
+        let root = temp.path().join("skill-variants");
+        std::fs::create_dir_all(source.join("scripts")).unwrap();
+        std::fs::write(source.join("SKILL.md"), "variant A").unwrap();
+        std::fs::write(source.join("scripts/run.sh"), "echo a").unwrap();
+
+        let (files, content_hash) = capture_variant_content(&source, &root, "v1").expect("capture");
+        assert_eq!(files.len(), 2);
+        assert!(!root.join("v1.capture").exists());
+        assert!(!root.join("v1").exists());
+
+        let mut stored = variant("v1", &["codex"]);
+        stored.stored_path = root.join("v1").to_string_lossy().to_string();
+        stored.files = files;
+        let checkout = ensure_variant_checkout(&stored).expect("checkout");
+        assert_eq!(
+            std::fs::read_to_string(checkout.join("scripts/run.sh")).unwrap(),
+            "echo a"
+        );
+        assert_eq!(hash_dir(&checkout).unwrap(), content_hash);
+
+        // Unreferenced blobs only go once they are old enough
+        assert_eq!(collect_variant_blob_garbage(&root, &[]), 0);
+        assert_eq!(blob_store::blob_stats(&root).0, 2);
+    }
+
+    #[test]
+    fn checkout_rejects_paths_outside_the_variant() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().join("skill-variants");
+        let (hash, stored_size) = blob_store::put_blob(&root, b"x").unwrap();
+        let mut stored = variant("v1", &["codex"]);
+        stored.stored_path = root.join("v1").to_string_lossy().to_string();
+        stored.files = vec![SkillVariantFile {
+            path: "../escape.md".to_string(),
+            hash,
+            size: 1,
+            stored_size,
+            executable: false,
+        }];
+
+        assert!(ensure_variant_checkout(&stored).is_err());
+        assert!(!root.join("escape.md").exists());
+        assert!(!root.join("v1").exists());
+    }
+}
@@ -14,6 +14,7 @@ use crate::coding::runtime_location;
 use crate::db::helpers::{db_delete, db_delete_all, db_get, db_list, db_put};
 use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
+use crate::settings::read_only::ensure_writable;
 use chrono::Local;
 use std::path::Path;
 use tauri::Emitter;
@@ -167,6 +168,7 @@ pub async fn ssh_save_config(
     app: tauri::AppHandle,
     config: SSHSyncConfig,
 ) -> Result<(), String> {
+    ensure_writable("ssh_save_config")?;
     // Check if being enabled
     let was_enabled = {
         let db = state.db();
@@ -262,6 +264,7 @@ pub async fn ssh_create_connection(
     app: tauri::AppHandle,
     mut connection: SSHConnection,
 ) -> Result<(), String> {
+    ensure_writable("ssh_create_connection")?;
     normalise_key_fields(&mut connection);
 
     let conn_data = adapter::connection_to_db_value(&connection);
@@ -278,6 +281,7 @@ pub async fn ssh_update_connection(
     app: tauri::AppHandle,
     mut connection: SSHConnection,
 ) -> Result<(), String> {
+    ensure_writable("ssh_update_connection")?;
     normalise_key_fields(&mut connection);
 
     let conn_data = adapter::connection_to_db_value(&connection);
@@ -294,6 +298,7 @@ pub async fn ssh_delete_connection(
     app: tauri::AppHandle,
     id: String,
 ) -> Result<(), String> {
+    ensure_writable("ssh_delete_connection")?;
     state.with_conn(|conn| {
         db_delete(conn, DbTable::SshConnection, &id)?;
         if let Some(mut config_data) = db_get(conn, DbTable::SshSyncConfig, "config")? {
@@ -326,6 +331,7 @@ pub async fn ssh_set_active_connection(
     app: tauri::AppHandle,
     connection_id: String,
 ) -> Result<(), String> {
+    ensure_writable("ssh_set_active_connection")?;
     {
         state.with_conn(|conn| {
             let mut config_data = db_get(conn, DbTable::SshSyncConfig, "config")?
@@ -388,6 +394,7 @@ pub async fn ssh_add_file_mapping(
     app: tauri::AppHandle,
     mapping: SSHFileMapping,
 ) -> Result<(), String> {
+    ensure_writable("ssh_add_file_mapping")?;
     validate_file_mapping_cleanup_paths(&mapping)?;
     let mapping_data = adapter::mapping_to_db_value(&mapping);
     state.with_conn(|conn| db_put(conn, DbTable::SshFileMapping, &mapping.id, &mapping_data))?;
@@ -403,6 +410,7 @@ pub async fn ssh_update_file_mapping(
     app: tauri::AppHandle,
     mapping: SSHFileMapping,
 ) -> Result<(), String> {
+    ensure_writable("ssh_update_file_mapping")?;
     validate_file_mapping_cleanup_paths(&mapping)?;
     let mapping_data = adapter::mapping_to_db_value(&mapping);
     state.with_conn(|conn| db_put(conn, DbTable::SshFileMapping, &mapping.id, &mapping_data))?;
@@ -418,6 +426,7 @@ pub async fn ssh_delete_file_mapping(
     app: tauri::AppHandle,
     id: String,
 ) -> Result<(), String> {
+    ensure_writable("ssh_delete_file_mapping")?;
     state.with_conn(|conn| db_delete(conn, DbTable::SshFileMapping, &id).map(|_| ()))?;
 
     let _ = app.emit("ssh-config-changed", ());
@@ -430,6 +439,7 @@ pub async fn ssh_reset_file_mappings(
     state: tauri::State<'_, SqliteDbState>,
     app: tauri::AppHandle,
 ) -> Result<(), String> {
+    ensure_writable("ssh_reset_file_mappings")?;
     state.with_conn(|conn| db_delete_all(conn, DbTable::SshFileMapping).map(|_| ()))?;
 
     let _ = app.emit("ssh-config-changed", ());
@@ -939,6 +949,7 @@ pub async fn ssh_sync(
     module: Option<String>,
     skip_modules: Option<Vec<String>>,
 ) -> Result<SyncResult, String> {
+    ensure_writable("ssh_sync")?;
     let config = ssh_get_config(state.clone()).await?;
     let active_connection = config
         .connections
@@ -0,0 +1,183 @@
+//! Skill / MCP status summary for external status bars
+//!
+//! Raycast, xbar, Stream Deck and similar integrations poll this every few
+//! seconds, so it is a handful of aggregate SQL queries on one connection:
+//! no file system probes, no network. It is served by the
+//! `get_status_summary` command and by `GET /status` on the local gateway.
+
+use std::collections::BTreeMap;
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::db::helpers::db_query_by_bool;
+use crate::db::schema::{DbTable, JsonFieldPath};
+use crate::db::SqliteDbState;
+use crate::update::{PendingUpdatePhase, PendingUpdateState};
+
+/// Provider tables whose applied record is the tool's active profile
+const PROFILE_TABLES: [(&str, DbTable); 4] = [
+    ("claude_code", DbTable::ClaudeProvider),
+    ("codex", DbTable::CodexProvider),
+    ("gemini_cli", DbTable::GeminiCliProvider),
+    ("grok", DbTable::GrokProvider),
+];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StatusSummary {
+    /// Managed, non-archived skills
+    pub managed_skills: i64,
+    /// Skill targets whose last sync did not end `ok`
+    pub broken_skill_targets: i64,
+    pub mcp_servers: i64,
+    /// MCP tool syncs that ended in `error`
+    pub broken_mcp_targets: i64,
+    /// App update downloaded and waiting to install on quit
+    pub pending_updates: i64,
+    /// Tool -> name of its applied provider profile
+    pub active_profiles: BTreeMap<String, String>,
+}
+
+fn count(conn: &Connection, sql: &str) -> Result<i64, String> {
+    conn.query_row(sql, [], |row| row.get(0))
+        .map_err(|error| format!("Failed to query status summary: {error}"))
+}
+
+pub fn load_status_summary(db: &SqliteDbState) -> Result<StatusSummary, String> {
+    db.with_conn(|conn| {
+        let skill = DbTable::Skill.name();
+        let mcp = DbTable::McpServer.name();
+        let mut summary = StatusSummary {
+            managed_skills: count(
+                conn,
+                &format!(
+                    "SELECT COUNT(*) FROM {skill} \
+                     WHERE COALESCE(json_extract(data, '$.management_enabled'), 1) \
+                     AND COALESCE(json_extract(data, '$.status'), 'ok') IS NOT 'archived'"
+                ),
+            )?,
+            broken_skill_targets: count(
+                conn,
+                &format!(
+                    "SELECT COUNT(*) FROM {skill}, json_each({skill}.data, '$.sync_details') AS target \
+                     WHERE json_extract(target.value, '$.status') IS NOT 'ok'"
+                ),
+            )?,
+            mcp_servers: count(conn, &format!("SELECT COUNT(*) FROM {mcp}"))?,
+            broken_mcp_targets: count(
+                conn,
+                &format!(
+                    "SELECT COUNT(*) FROM {mcp}, json_each({mcp}.data, '$.sync_details') AS target \
+                     WHERE json_extract(target.value, '$.status') = 'error'"
+                ),
+            )?,
+            ..Default::default()
+        };
+
+        let is_applied = JsonFieldPath::new("is_applied")?;
+        for (tool, table) in PROFILE_TABLES {
+            let applied = db_query_by_bool(conn, table, &is_applied, true, None, Some(1))?;
+            if let Some(name) = applied
+                .first()
+                .and_then(|provider| provider.get("name"))
+                .and_then(|name| name.as_str())
+            {
+                summary
+                    .active_profiles
+                    .insert(tool.to_string(), name.to_string());
+            }
+        }
+        Ok(summary)
+    })
+}
+
+/// Summary including in-memory app state (the install-on-quit update)
+pub fn status_summary_for_app(
+    app: &tauri::AppHandle,
+    db: &SqliteDbState,
+) -> Result<StatusSummary, String> {
+    let mut summary = load_status_summary(db)?;
+    summary.pending_updates = app
+        .try_state::<PendingUpdateState>()
+        .filter(|pending| pending.status().phase == PendingUpdatePhase::Ready)
+        .map_or(0, |_| 1);
+    Ok(summary)
+}
+
+/// Cheap counts for external status bar integrations
+#[tauri::command]
+pub fn get_status_summary(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<StatusSummary, String> {
+    status_summary_for_app(&app, &state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::helpers::db_put;
+    use serde_json::json;
+
+    #[test]
+    fn summary_counts_skills_targets_mcp_and_active_profiles() {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        state
+            .with_conn(|conn| {
+                db_put(
+                    conn,
+                    DbTable::Skill,
+                    "a",
+                    &json!({"name": "a", "status": "ok", "sync_details": {
+                        "codex": {"status": "ok"},
+                        "claude_code": {"status": "error"},
+                    }}),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::Skill,
+                    "b",
+                    &json!({"name": "b", "status": "archived"}),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::Skill,
+                    "c",
+                    &json!({"name": "c", "status": "ok", "management_enabled": false}),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::McpServer,
+                    "m",
+                    &json!({"name": "m", "sync_details": {
+                        "codex": {"status": "error"},
+                        "claude_code": {"status": "pending"},
+                    }}),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::ClaudeProvider,
+                    "p1",
+                    &json!({"name": "Work", "is_applied": true}),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::ClaudeProvider,
+                    "p2",
+                    &json!({"name": "Personal", "is_applied": false}),
+                )
+            })
+            .unwrap();
+
+        let summary = load_status_summary(&state).unwrap();
+        assert_eq!(summary.managed_skills, 1);
+        assert_eq!(summary.broken_skill_targets, 1);
+        assert_eq!(summary.mcp_servers, 1);
+        assert_eq!(summary.broken_mcp_targets, 1);
+        assert_eq!(
+            summary.active_profiles,
+            BTreeMap::from([("claude_code".to_string(), "Work".to_string())])
+        );
+    }
+}
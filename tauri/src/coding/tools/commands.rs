@@ -0,0 +1,118 @@
+//! Tauri commands for settings shared by Skills and MCP
+
+use tauri::State;
+
+use super::custom_store;
+use super::detection::{
+    resolve_mcp_config_path_with_db_async, resolve_skills_path_with_db_async, runtime_tool_by_key,
+};
+use super::key_alias::{migrate_tool_keys, ToolKeyMigrationDto};
+use super::post_sync::{
+    load_post_sync_hooks, run_post_sync_action, save_post_sync_hooks, PostSyncHook, PostSyncHooks,
+    PostSyncScope,
+};
+use super::wasm_plugins::{self, ToolPluginsDto};
+use crate::settings::read_only::ensure_writable;
+use crate::SqliteDbState;
+
+/// Get the post-sync hooks, keyed by tool key
+#[tauri::command]
+pub async fn tools_get_post_sync_hooks(
+    state: State<'_, SqliteDbState>,
+) -> Result<PostSyncHooks, String> {
+    Ok(load_post_sync_hooks(&state))
+}
+
+/// Replace all post-sync hooks
+#[tauri::command]
+pub async fn tools_save_post_sync_hooks(
+    state: State<'_, SqliteDbState>,
+    hooks: PostSyncHooks,
+) -> Result<(), String> {
+    ensure_writable("tools_save_post_sync_hooks")?;
+    save_post_sync_hooks(&state, &hooks)
+}
+
+/// Run a hook right away (no debounce) against the tool's current skills dir,
+/// or its MCP config file when `scope` is "mcp"
+#[tauri::command]
+pub async fn tools_test_post_sync_hook(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+    scope: String,
+    hook: PostSyncHook,
+) -> Result<(), String> {
+    let custom_tools = custom_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let runtime_tool = runtime_tool_by_key(&tool, &custom_tools)
+        .ok_or_else(|| format!("Unknown tool: {}", tool))?;
+    let (scope, synced_path) = match scope.as_str() {
+        "mcp" => (
+            PostSyncScope::Mcp,
+            resolve_mcp_config_path_with_db_async(&state, &runtime_tool).await,
+        ),
+        _ => (
+            PostSyncScope::Skills,
+            resolve_skills_path_with_db_async(&state, &runtime_tool).await,
+        ),
+    };
+    let synced_path =
+        synced_path.ok_or_else(|| format!("Tool {} does not support {}", tool, scope.as_str()))?;
+    run_post_sync_action(&tool, scope, &hook.action, &synced_path).await
+}
+
+/// Count the skills, MCP servers and preferences still naming a tool by a
+/// retired key (see `key_alias::TOOL_KEY_ALIASES`)
+#[tauri::command]
+pub async fn tools_preview_key_migration(
+    state: State<'_, SqliteDbState>,
+) -> Result<ToolKeyMigrationDto, String> {
+    state.with_conn_mut(|conn| migrate_tool_keys(conn, false))
+}
+
+/// Rewrite retired tool keys to the current ones, after the user confirmed
+/// the preview
+#[tauri::command]
+pub async fn tools_migrate_keys(
+    state: State<'_, SqliteDbState>,
+) -> Result<ToolKeyMigrationDto, String> {
+    ensure_writable("tools_migrate_keys")?;
+    let result = state.with_conn_mut(|conn| migrate_tool_keys(conn, true))?;
+    if result.total > 0 {
+        log::info!("Migrated retired tool keys: {:?}", result.rows);
+    }
+    Ok(result)
+}
+
+/// Tool plugins found in the plugins dir at the last load
+#[tauri::command]
+pub async fn tools_list_plugins() -> Result<ToolPluginsDto, String> {
+    Ok(wasm_plugins::plugin_statuses())
+}
+
+/// Load the plugins dir again, e.g. after a plugin was added or removed
+#[tauri::command]
+pub async fn tools_reload_plugins(
+    state: State<'_, SqliteDbState>,
+) -> Result<ToolPluginsDto, String> {
+    let state = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || wasm_plugins::reload_plugins(&state))
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e))?;
+    Ok(wasm_plugins::plugin_statuses())
+}
+
+/// Approve the plugin file with the listed SHA-256 (or withdraw the
+/// approval) and load the plugins again
+#[tauri::command]
+pub async fn tools_approve_plugin(
+    state: State<'_, SqliteDbState>,
+    file: String,
+    sha256: String,
+    approved: bool,
+) -> Result<ToolPluginsDto, String> {
+    ensure_writable("tools_approve_plugin")?;
+    wasm_plugins::set_plugin_approval(&state, &file, &sha256, approved)?;
+    tools_reload_plugins(state).await
+}
@@ -34,6 +34,11 @@ pub fn from_db_custom_tool(value: Value) -> CustomTool {
             .get("force_copy")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        preferred_sync_mode: value
+            .get("preferred_sync_mode")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
         mcp_config_path: value
             .get("mcp_config_path")
             .and_then(|v| v.as_str())
@@ -56,9 +61,16 @@ pub fn from_db_custom_tool(value: Value) -> CustomTool {
     }
 }
 
-/// Get all custom tools
+/// Get all custom tools, followed by the installed plugin tools whose key no
+/// stored tool uses
 pub async fn get_custom_tools(state: &SqliteDbState) -> Result<Vec<CustomTool>, String> {
-    get_custom_tools_from_sqlite(state)
+    let mut tools = get_custom_tools_from_sqlite(state)?;
+    for plugin_tool in super::wasm_plugins::plugin_tools() {
+        if !tools.iter().any(|tool| tool.key == plugin_tool.key) {
+            tools.push(plugin_tool);
+        }
+    }
+    Ok(tools)
 }
 
 fn get_custom_tools_from_sqlite(sqlite_state: &SqliteDbState) -> Result<Vec<CustomTool>, String> {
@@ -140,6 +152,7 @@ fn save_custom_tool_to_sqlite(
 }
 
 /// Save only skills-related fields, preserving MCP fields if they exist
+#[allow(clippy::too_many_arguments)]
 pub async fn save_custom_tool_skills_fields(
     state: &SqliteDbState,
     key: &str,
@@ -147,6 +160,7 @@ pub async fn save_custom_tool_skills_fields(
     relative_skills_dir: Option<String>,
     relative_detect_dir: Option<String>,
     force_copy: bool,
+    preferred_sync_mode: Option<String>,
     created_at: i64,
 ) -> Result<(), String> {
     // First check if the tool already exists
@@ -166,6 +180,7 @@ pub async fn save_custom_tool_skills_fields(
             relative_skills_dir,
             relative_detect_dir,
             force_copy,
+            preferred_sync_mode,
             mcp_config_path: mcp_path,
             mcp_config_format: mcp_format,
             mcp_field,
@@ -192,6 +207,9 @@ pub async fn save_custom_tool_mcp_fields(
         .as_ref()
         .map(|tool| tool.force_copy)
         .unwrap_or(false);
+    let existing_sync_mode = existing
+        .as_ref()
+        .and_then(|tool| tool.preferred_sync_mode.clone());
 
     // Preserve existing skills fields
     let (skills_dir, detect_dir) = match existing {
@@ -210,6 +228,7 @@ pub async fn save_custom_tool_mcp_fields(
             relative_skills_dir: skills_dir,
             relative_detect_dir: detect_dir,
             force_copy: existing_force_copy,
+            preferred_sync_mode: existing_sync_mode,
             mcp_config_path,
             mcp_config_format,
             mcp_field,
@@ -230,6 +249,7 @@ fn custom_tool_to_value(tool: &CustomTool) -> Value {
         "relative_skills_dir": tool.relative_skills_dir,
         "relative_detect_dir": tool.relative_detect_dir,
         "force_copy": tool.force_copy,
+        "preferred_sync_mode": tool.preferred_sync_mode,
         "mcp_config_path": tool.mcp_config_path,
         "mcp_config_format": tool.mcp_config_format,
         "mcp_field": tool.mcp_field,
@@ -257,6 +277,7 @@ mod tests {
                 relative_skills_dir: Some("skills".to_string()),
                 relative_detect_dir: Some("detect".to_string()),
                 force_copy: true,
+                preferred_sync_mode: None,
                 mcp_config_path: None,
                 mcp_config_format: None,
                 mcp_field: None,
@@ -272,6 +293,7 @@ mod tests {
                 relative_skills_dir: None,
                 relative_detect_dir: None,
                 force_copy: false,
+                preferred_sync_mode: Some("junction".to_string()),
                 mcp_config_path: Some("mcp.json".to_string()),
                 mcp_config_format: Some("json".to_string()),
                 mcp_field: Some("mcpServers".to_string()),
@@ -285,6 +307,7 @@ mod tests {
         assert_eq!(tools[0].key, "tool_a");
         assert_eq!(tools[0].display_name, "Alpha");
         assert_eq!(tools[0].mcp_config_path.as_deref(), Some("mcp.json"));
+        assert_eq!(tools[0].preferred_sync_mode.as_deref(), Some("junction"));
         assert_eq!(tools[1].key, "tool_b");
         assert!(tools[1].force_copy);
         assert_eq!(tools[1].relative_skills_dir.as_deref(), Some("skills"));
@@ -4,14 +4,17 @@
 
 use std::path::PathBuf;
 
+use super::adapters::builtin_adapter;
 use super::builtin::BUILTIN_TOOLS;
+use super::detection_cache::{cached_detection, store_detection};
+use super::key_alias::canonical_tool_key;
 use super::path_utils::{resolve_storage_path, to_platform_path};
 use super::types::{CustomTool, RuntimeTool, RuntimeToolDto, ToolDetectionDto};
 
 fn resolve_github_copilot_intellij_mcp_path() -> Option<PathBuf> {
     #[cfg(target_os = "linux")]
     {
-        return dirs::config_dir().map(|config_dir| {
+        return super::path_utils::config_dir().map(|config_dir| {
             config_dir
                 .join("github-copilot")
                 .join("intellij")
@@ -31,7 +34,7 @@ fn resolve_github_copilot_intellij_mcp_path() -> Option<PathBuf> {
 
     #[cfg(target_os = "macos")]
     {
-        return dirs::config_dir().map(|config_dir| {
+        return super::path_utils::config_dir().map(|config_dir| {
             config_dir
                 .join("GitHub Copilot")
                 .join("intellij")
@@ -53,6 +56,13 @@ fn resolve_special_mcp_config_path(tool: &RuntimeTool) -> Option<PathBuf> {
     }
 }
 
+/// Whether `tool`'s paths come from its runtime location (see
+/// `ToolAdapter::has_runtime_location`)
+fn has_runtime_location(tool: &RuntimeTool) -> bool {
+    !tool.is_custom
+        && builtin_adapter(&tool.key).is_some_and(|adapter| adapter.has_runtime_location())
+}
+
 /// Check if a runtime tool is installed by checking its detect directory
 pub fn is_tool_installed(tool: &RuntimeTool) -> bool {
     // Custom tools are always considered installed
@@ -62,7 +72,7 @@ pub fn is_tool_installed(tool: &RuntimeTool) -> bool {
 
     // Some MCP targets have OS-specific paths that cannot be represented by a
     // single static storage string.
-    if matches!(tool.key.as_str(), "opencode" | "github_copilot_intellij") {
+    if builtin_adapter(&tool.key).is_some_and(|adapter| adapter.detects_by_mcp_config()) {
         if let Some(config_path) = resolve_mcp_config_path(tool) {
             if config_path.exists() {
                 return true;
@@ -109,12 +119,11 @@ pub fn resolve_mcp_config_path_with_db(
     db: &crate::db::SqliteDbState,
     tool: &RuntimeTool,
 ) -> Option<PathBuf> {
-    match tool.key.as_str() {
-        "opencode" | "claude_code" | "codex" | "grok" | "openclaw" | "pi" => {
-            crate::coding::runtime_location::get_tool_mcp_config_path_sync(db, &tool.key)
-                .or_else(|| resolve_mcp_config_path(tool))
-        }
-        _ => resolve_mcp_config_path(tool),
+    if has_runtime_location(tool) {
+        crate::coding::runtime_location::get_tool_mcp_config_path_sync(db, &tool.key)
+            .or_else(|| resolve_mcp_config_path(tool))
+    } else {
+        resolve_mcp_config_path(tool)
     }
 }
 
@@ -122,13 +131,12 @@ pub async fn resolve_mcp_config_path_with_db_async(
     db: &crate::db::SqliteDbState,
     tool: &RuntimeTool,
 ) -> Option<PathBuf> {
-    match tool.key.as_str() {
-        "opencode" | "claude_code" | "codex" | "grok" | "openclaw" | "pi" => {
-            crate::coding::runtime_location::get_tool_mcp_config_path_async(db, &tool.key)
-                .await
-                .or_else(|| resolve_mcp_config_path(tool))
-        }
-        _ => resolve_mcp_config_path(tool),
+    if has_runtime_location(tool) {
+        crate::coding::runtime_location::get_tool_mcp_config_path_async(db, &tool.key)
+            .await
+            .or_else(|| resolve_mcp_config_path(tool))
+    } else {
+        resolve_mcp_config_path(tool)
     }
 }
 
@@ -136,12 +144,11 @@ pub fn resolve_skills_path_with_db(
     db: &crate::db::SqliteDbState,
     tool: &RuntimeTool,
 ) -> Option<PathBuf> {
-    match tool.key.as_str() {
-        "opencode" | "claude_code" | "codex" | "grok" | "openclaw" | "pi" => {
-            crate::coding::runtime_location::get_tool_skills_path_sync(db, &tool.key)
-                .or_else(|| resolve_skills_path(tool))
-        }
-        _ => resolve_skills_path(tool),
+    if has_runtime_location(tool) {
+        crate::coding::runtime_location::get_tool_skills_path_sync(db, &tool.key)
+            .or_else(|| resolve_skills_path(tool))
+    } else {
+        resolve_skills_path(tool)
     }
 }
 
@@ -149,13 +156,12 @@ pub async fn resolve_skills_path_with_db_async(
     db: &crate::db::SqliteDbState,
     tool: &RuntimeTool,
 ) -> Option<PathBuf> {
-    match tool.key.as_str() {
-        "opencode" | "claude_code" | "codex" | "grok" | "openclaw" | "pi" => {
-            crate::coding::runtime_location::get_tool_skills_path_async(db, &tool.key)
-                .await
-                .or_else(|| resolve_skills_path(tool))
-        }
-        _ => resolve_skills_path(tool),
+    if has_runtime_location(tool) {
+        crate::coding::runtime_location::get_tool_skills_path_async(db, &tool.key)
+            .await
+            .or_else(|| resolve_skills_path(tool))
+    } else {
+        resolve_skills_path(tool)
     }
 }
 
@@ -189,6 +195,8 @@ pub fn is_tool_installed_with_db(db: &crate::db::SqliteDbState, tool: &RuntimeTo
     is_tool_installed(tool)
 }
 
+/// Like `is_tool_installed_with_db`, answered from `detection_cache` while
+/// the cached result is fresh
 pub async fn is_tool_installed_with_db_async(
     db: &crate::db::SqliteDbState,
     tool: &RuntimeTool,
@@ -196,6 +204,23 @@ pub async fn is_tool_installed_with_db_async(
     if tool.is_custom {
         return true;
     }
+    if let Some(installed) = cached_detection(&tool.key) {
+        return installed;
+    }
+
+    let installed = detect_tool_installed_with_db_async(db, tool).await;
+    store_detection(&tool.key, installed);
+    installed
+}
+
+/// Detect `tool` on disk, bypassing the cache
+pub async fn detect_tool_installed_with_db_async(
+    db: &crate::db::SqliteDbState,
+    tool: &RuntimeTool,
+) -> bool {
+    if tool.is_custom {
+        return true;
+    }
 
     if let Some(path) = resolve_mcp_config_path_with_db_async(db, tool).await {
         if path.exists() {
@@ -267,9 +292,14 @@ pub fn get_installed_mcp_tools(custom_tools: &[CustomTool]) -> Vec<RuntimeTool>
 
 /// Find a runtime tool by key
 pub fn runtime_tool_by_key(key: &str, custom_tools: &[CustomTool]) -> Option<RuntimeTool> {
-    get_all_runtime_tools(custom_tools)
-        .into_iter()
-        .find(|t| t.key == key)
+    let tools = get_all_runtime_tools(custom_tools);
+    // A retired builtin key resolves to the renamed tool
+    let key = if tools.iter().any(|t| t.key == key) {
+        key
+    } else {
+        canonical_tool_key(key)
+    };
+    tools.into_iter().find(|t| t.key == key)
 }
 
 /// Convert RuntimeTool to RuntimeToolDto with installation status
@@ -0,0 +1,188 @@
+//! Cached detection of the built-in tools
+//!
+//! Detecting a tool stats its MCP config, skills dir and detect dir, which is
+//! slow on network homes, and Skills and MCP detect every tool each time a
+//! page opens. `is_tool_installed_with_db_async` answers from this cache
+//! while an entry is younger than `CACHE_TTL` and detects (and caches) only
+//! on a miss. The Skills tool watcher re-verifies every built-in tool in the
+//! background (`refresh_detection`) and emits `TOOLS_DETECTION_CHANGED_EVENT`
+//! only when a cached answer turned out wrong, so the pages reload their
+//! tool lists only then.
+//!
+//! Custom tools are always installed and never cached. Moving a tool's
+//! runtime location drops the cache, as its paths change.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::builtin::BUILTIN_TOOLS;
+use super::detection::detect_tool_installed_with_db_async;
+use super::types::RuntimeTool;
+
+pub const TOOLS_DETECTION_CHANGED_EVENT: &str = "tools-detection-changed";
+/// Longer than the tool watcher's 30 s poll, so pages hit the cache while
+/// the app runs
+const CACHE_TTL: Duration = Duration::from_secs(120);
+
+#[derive(Clone, Copy)]
+struct CachedDetection {
+    installed: bool,
+    checked_at: Instant,
+}
+
+static DETECTION_CACHE: OnceLock<Mutex<HashMap<String, CachedDetection>>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<HashMap<String, CachedDetection>> {
+    DETECTION_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Payload of `tools-detection-changed`: built-in tools whose installed state
+/// differs from what the cache reported, all keys sorted
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ToolDetectionChangeDto {
+    pub installed: Vec<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// The cached installed state of `key`, while fresh
+pub fn cached_detection(key: &str) -> Option<bool> {
+    let guard = cache().lock().ok()?;
+    guard
+        .get(key)
+        .filter(|entry| entry.checked_at.elapsed() < CACHE_TTL)
+        .map(|entry| entry.installed)
+}
+
+pub fn store_detection(key: &str, installed: bool) {
+    if let Ok(mut guard) = cache().lock() {
+        guard.insert(
+            key.to_string(),
+            CachedDetection {
+                installed,
+                checked_at: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Forget every cached answer; the next lookup detects again
+pub fn invalidate_detection_cache() {
+    if let Ok(mut guard) = cache().lock() {
+        guard.clear();
+    }
+}
+
+/// Detect every built-in tool again and cache the results. Returns the
+/// installed keys, sorted, and what changed against the cached answers;
+/// tools never looked up before are not a change.
+pub async fn refresh_detection(
+    db: &crate::db::SqliteDbState,
+) -> (Vec<String>, Option<ToolDetectionChangeDto>) {
+    let mut fresh = Vec::with_capacity(BUILTIN_TOOLS.len());
+    for builtin in BUILTIN_TOOLS.iter() {
+        let tool = RuntimeTool::from(builtin);
+        let installed = detect_tool_installed_with_db_async(db, &tool).await;
+        fresh.push((tool.key, installed));
+    }
+
+    let previous: HashMap<String, bool> = cache()
+        .lock()
+        .map(|guard| {
+            guard
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.installed))
+                .collect()
+        })
+        .unwrap_or_default();
+    let change = detection_change(&previous, &fresh);
+    for (key, installed) in &fresh {
+        store_detection(key, *installed);
+    }
+
+    let mut installed: Vec<String> = fresh
+        .into_iter()
+        .filter(|(_, installed)| *installed)
+        .map(|(key, _)| key)
+        .collect();
+    installed.sort();
+    (installed, change)
+}
+
+fn detection_change(
+    previous: &HashMap<String, bool>,
+    fresh: &[(String, bool)],
+) -> Option<ToolDetectionChangeDto> {
+    let mut change = ToolDetectionChangeDto {
+        installed: Vec::new(),
+        added: Vec::new(),
+        removed: Vec::new(),
+    };
+    for (key, installed) in fresh {
+        if *installed {
+            change.installed.push(key.clone());
+        }
+        match previous.get(key) {
+            Some(false) if *installed => change.added.push(key.clone()),
+            Some(true) if !*installed => change.removed.push(key.clone()),
+            _ => {}
+        }
+    }
+    if change.added.is_empty() && change.removed.is_empty() {
+        return None;
+    }
+    change.installed.sort();
+    change.added.sort();
+    change.removed.sort();
+    Some(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_only_answers_the_cache_got_wrong() {
+        let previous: HashMap<String, bool> = [("codex", true), ("cursor", false), ("amp", true)]
+            .into_iter()
+            .map(|(key, installed)| (key.to_string(), installed))
+            .collect();
+        let fresh = |entries: &[(&str, bool)]| -> Vec<(String, bool)> {
+            entries
+                .iter()
+                .map(|(key, installed)| (key.to_string(), *installed))
+                .collect()
+        };
+
+        // Same answers, plus a tool nobody looked up yet
+        assert_eq!(
+            detection_change(
+                &previous,
+                &fresh(&[
+                    ("codex", true),
+                    ("cursor", false),
+                    ("amp", true),
+                    ("droid", true)
+                ])
+            ),
+            None
+        );
+
+        let change = detection_change(
+            &previous,
+            &fresh(&[
+                ("codex", true),
+                ("cursor", true),
+                ("amp", false),
+                ("droid", true),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(change.installed, vec!["codex", "cursor", "droid"]);
+        assert_eq!(change.added, vec!["cursor"]);
+        assert_eq!(change.removed, vec!["amp"]);
+    }
+}
@@ -0,0 +1,280 @@
+//! Gemini CLI extension discovery helpers shared by MCP and Skills modules.
+//!
+//! Extensions live in `<gemini root>/extensions/<dir>/`, each described by a
+//! `gemini-extension.json` manifest that can bundle MCP servers
+//! (`mcpServers`, same shape as `settings.json`) and context files
+//! (`contextFileName`, `GEMINI.md` by default); skills sit in a `skills/`
+//! directory next to it. A linked extension (`gemini extensions link`) only
+//! keeps `.gemini-extension-install.json` there and points at its source.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::coding::runtime_location::{self, RuntimeLocationInfo};
+
+/// Prefix of the source keys MCP and Skills use for extension contents
+pub const GEMINI_EXTENSION_KEY_PREFIX: &str = "gemini-extension::";
+const MANIFEST_FILE: &str = "gemini-extension.json";
+const INSTALL_METADATA_FILE: &str = ".gemini-extension-install.json";
+const DEFAULT_CONTEXT_FILE: &str = "GEMINI.md";
+
+/// Resolved extension info returned to callers.
+#[derive(Debug, Clone)]
+pub struct GeminiExtensionInfo {
+    pub name: String,
+    pub version: Option<String>,
+    /// Directory holding the manifest (the link source for linked extensions)
+    pub install_path: PathBuf,
+    pub linked: bool,
+    /// `mcpServers` with `${extensionPath}` and `${/}` already substituted
+    pub mcp_servers: Map<String, Value>,
+    /// Context files that exist in the extension directory
+    pub context_files: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExtensionManifest {
+    name: Option<String>,
+    version: Option<String>,
+    #[serde(default)]
+    mcp_servers: Map<String, Value>,
+    context_file_name: Option<Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct InstallMetadata {
+    source: Option<String>,
+    #[serde(rename = "type")]
+    install_type: Option<String>,
+}
+
+impl GeminiExtensionInfo {
+    /// Source key used by MCP import and skill onboarding
+    pub fn source_key(&self) -> String {
+        format!("{}{}", GEMINI_EXTENSION_KEY_PREFIX, self.name)
+    }
+
+    pub fn display_name(&self) -> String {
+        format!("Gemini extension: {}", self.name)
+    }
+
+    pub fn skills_dir(&self) -> PathBuf {
+        self.install_path.join("skills")
+    }
+}
+
+/// Read installed Gemini CLI extensions using the current runtime location.
+///
+/// Returns an empty list when there are no installed extensions.
+pub async fn get_installed_extensions(db: &crate::db::SqliteDbState) -> Vec<GeminiExtensionInfo> {
+    match runtime_location::get_gemini_cli_runtime_location_async(db).await {
+        Ok(location) => list_extensions_in(&location.host_path.join("extensions"), &location),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Extensions in `extensions_dir`, sorted by name; unreadable ones are
+/// skipped with a warning
+pub fn list_extensions_in(
+    extensions_dir: &Path,
+    location: &RuntimeLocationInfo,
+) -> Vec<GeminiExtensionInfo> {
+    let Ok(entries) = std::fs::read_dir(extensions_dir) else {
+        return Vec::new();
+    };
+    let mut extensions: Vec<GeminiExtensionInfo> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| match read_extension(&entry.path(), location) {
+            Ok(extension) => extension,
+            Err(err) => {
+                log::warn!("Skipping Gemini extension {:?}: {}", entry.path(), err);
+                None
+            }
+        })
+        .collect();
+    extensions.sort_by(|a, b| a.name.cmp(&b.name));
+    extensions
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(content.trim_start_matches('\u{feff}'))
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// `None` for directories that are not extensions
+fn read_extension(
+    dir: &Path,
+    location: &RuntimeLocationInfo,
+) -> Result<Option<GeminiExtensionInfo>, String> {
+    let metadata_path = dir.join(INSTALL_METADATA_FILE);
+    let metadata: InstallMetadata = if metadata_path.is_file() {
+        read_json(&metadata_path)?
+    } else {
+        InstallMetadata::default()
+    };
+    let linked = metadata.install_type.as_deref() == Some("link");
+    let install_path = match metadata.source.as_deref().filter(|_| linked) {
+        Some(source) => resolve_source_path(location, source),
+        None => dir.to_path_buf(),
+    };
+    let manifest_path = install_path.join(MANIFEST_FILE);
+    if !manifest_path.is_file() {
+        return Ok(None);
+    }
+    let manifest: ExtensionManifest = read_json(&manifest_path)?;
+
+    let name = manifest
+        .name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| {
+            dir.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        });
+    let extension_path = install_path.to_string_lossy().to_string();
+    let mcp_servers = manifest
+        .mcp_servers
+        .into_iter()
+        .map(|(server, config)| (server, substitute_variables(config, &extension_path)))
+        .collect();
+    let context_files = context_file_names(manifest.context_file_name.as_ref())
+        .into_iter()
+        .filter(|file| install_path.join(file).is_file())
+        .collect();
+
+    Ok(Some(GeminiExtensionInfo {
+        name,
+        version: manifest.version,
+        install_path,
+        linked,
+        mcp_servers,
+        context_files,
+    }))
+}
+
+/// Link sources are stored as seen by Gemini CLI, i.e. Linux paths in WSL
+fn resolve_source_path(location: &RuntimeLocationInfo, source: &str) -> PathBuf {
+    let source = source.trim();
+    if let Some(wsl) = location.wsl.as_ref() {
+        let expanded =
+            runtime_location::expand_home_from_user_root(wsl.linux_user_root.as_deref(), source);
+        if expanded.starts_with('/') {
+            return runtime_location::build_windows_unc_path(&wsl.distro, &expanded);
+        }
+    }
+    PathBuf::from(source)
+}
+
+/// `contextFileName` is a string or a list; Gemini CLI loads `GEMINI.md`
+/// when it is missing
+fn context_file_names(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(file)) => vec![file.clone()],
+        Some(Value::Array(files)) => files
+            .iter()
+            .filter_map(|file| file.as_str().map(str::to_string))
+            .collect(),
+        _ => vec![DEFAULT_CONTEXT_FILE.to_string()],
+    }
+}
+
+/// Replace the variables Gemini CLI expands in extension MCP configs, so an
+/// adopted server keeps pointing into the extension directory
+fn substitute_variables(value: Value, extension_path: &str) -> Value {
+    match value {
+        Value::String(text) => Value::String(
+            text.replace("${extensionPath}", extension_path)
+                .replace("${/}", std::path::MAIN_SEPARATOR_STR)
+                .replace("${pathSeparator}", std::path::MAIN_SEPARATOR_STR),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| substitute_variables(item, extension_path))
+                .collect(),
+        ),
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, item)| (key, substitute_variables(item, extension_path)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::runtime_location::RuntimeLocationMode;
+
+    fn local_location(root: &Path) -> RuntimeLocationInfo {
+        RuntimeLocationInfo {
+            mode: RuntimeLocationMode::LocalWindows,
+            source: "default".to_string(),
+            host_path: root.to_path_buf(),
+            wsl: None,
+        }
+    }
+
+    #[test]
+    fn lists_installed_and_linked_extensions() {
+        let temp = tempfile::tempdir().unwrap();
+        let extensions = temp.path().join("extensions");
+        let installed = extensions.join("db-tools");
+        std::fs::create_dir_all(installed.join("skills/query")).unwrap();
+        std::fs::write(
+            installed.join(MANIFEST_FILE),
+            r#"{
+                "name": "db-tools",
+                "version": "1.2.0",
+                "mcpServers": {
+                    "db": { "command": "node", "args": ["${extensionPath}${/}server.js"] }
+                },
+                "contextFileName": ["GEMINI.md", "MISSING.md"]
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(installed.join("GEMINI.md"), "context").unwrap();
+
+        let source = temp.path().join("src/docs-ext");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join(MANIFEST_FILE), r#"{ "name": "docs" }"#).unwrap();
+        std::fs::create_dir_all(extensions.join("docs")).unwrap();
+        std::fs::write(
+            extensions.join("docs").join(INSTALL_METADATA_FILE),
+            serde_json::json!({ "source": source, "type": "link" }).to_string(),
+        )
+        .unwrap();
+        std::fs::create_dir_all(extensions.join("not-an-extension")).unwrap();
+
+        let found = list_extensions_in(&extensions, &local_location(temp.path()));
+        let names: Vec<&str> = found.iter().map(|ext| ext.name.as_str()).collect();
+        assert_eq!(names, vec!["db-tools", "docs"]);
+
+        let db_tools = &found[0];
+        assert_eq!(db_tools.version.as_deref(), Some("1.2.0"));
+        assert_eq!(db_tools.context_files, vec!["GEMINI.md".to_string()]);
+        assert_eq!(
+            db_tools.mcp_servers["db"]["args"][0],
+            format!(
+                "{}{}server.js",
+                installed.to_string_lossy(),
+                std::path::MAIN_SEPARATOR
+            )
+        );
+        assert_eq!(db_tools.source_key(), "gemini-extension::db-tools");
+
+        let docs = &found[1];
+        assert!(docs.linked);
+        assert_eq!(docs.install_path, source);
+        assert!(docs.mcp_servers.is_empty());
+    }
+}
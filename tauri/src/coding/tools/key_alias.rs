@@ -0,0 +1,214 @@
+//! Historical tool keys
+//!
+//! A tool that rebrands gets a new builtin key, but skills, MCP servers and
+//! preferences keep the old one in `enabled_tools`, `sync_details` and
+//! `preferred_tools`. `TOOL_KEY_ALIASES` maps retired keys to current ones:
+//! key lookups (`builtin_tool_by_key`, `runtime_tool_by_key`,
+//! `adapter_by_key`) resolve an old key to the renamed tool right away, and
+//! `migrate_tool_keys` rewrites the stored rows once the user confirms.
+
+use std::collections::BTreeMap;
+
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::db::helpers::db_transaction;
+use crate::db::schema::DbTable;
+
+pub use ai_toolbox_core::tools::key_alias::{canonical_in, canonical_tool_key, TOOL_KEY_ALIASES};
+
+/// Tables whose rows name tools by key
+const KEYED_TABLES: &[DbTable] = &[
+    DbTable::Skill,
+    DbTable::SkillPreferences,
+    DbTable::McpServer,
+    DbTable::McpPreferences,
+];
+
+/// Fields holding a list of tool keys
+const KEY_LIST_FIELDS: &[&str] = &[
+    "enabled_tools",
+    "disabled_previous_tools",
+    "preferred_tools",
+    "remote_only_tools",
+];
+
+/// Fields holding an object keyed by tool key
+const KEY_MAP_FIELDS: &[&str] = &["sync_details"];
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ToolKeyMigrationDto {
+    /// Table name -> rows that use a retired key (preview) or were rewritten
+    pub rows: BTreeMap<String, usize>,
+    pub total: usize,
+}
+
+/// Rewrite retired keys in one row; returns whether anything changed
+fn rewrite_row(data: &mut Value, aliases: &[(&str, &str)]) -> bool {
+    let Some(object) = data.as_object_mut() else {
+        return false;
+    };
+    let mut changed = false;
+
+    for field in KEY_LIST_FIELDS {
+        let Some(Value::Array(keys)) = object.get_mut(*field) else {
+            continue;
+        };
+        let mut rewritten: Vec<Value> = Vec::with_capacity(keys.len());
+        for key in keys.iter() {
+            let key = match key.as_str() {
+                Some(key) => Value::String(canonical_in(aliases, key).to_string()),
+                None => key.clone(),
+            };
+            // An old and a new key in one list collapse into one entry
+            if !rewritten.contains(&key) {
+                rewritten.push(key);
+            }
+        }
+        if rewritten != *keys {
+            *keys = rewritten;
+            changed = true;
+        }
+    }
+
+    for field in KEY_MAP_FIELDS {
+        let Some(Value::Object(entries)) = object.get_mut(*field) else {
+            continue;
+        };
+        let retired = entries
+            .keys()
+            .filter(|key| canonical_in(aliases, key) != key.as_str())
+            .cloned()
+            .collect::<Vec<_>>();
+        for old in retired {
+            let new = canonical_in(aliases, &old).to_string();
+            if let Some(entry) = entries.remove(&old) {
+                // An entry under the current key is newer than the retired one
+                entries.entry(new).or_insert(entry);
+                changed = true;
+            }
+        }
+    }
+
+    changed
+}
+
+fn read_rows(conn: &Connection, name: &str) -> Result<Vec<(String, String)>, String> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT id, json(data) FROM {name}"))
+        .map_err(|e| format!("Failed to read {name}: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to read {name}: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read {name}: {e}"))?;
+    Ok(rows)
+}
+
+fn migrate_in(
+    conn: &mut Connection,
+    aliases: &[(&str, &str)],
+    apply: bool,
+) -> Result<ToolKeyMigrationDto, String> {
+    db_transaction(conn, |tx| {
+        let mut result = ToolKeyMigrationDto::default();
+        for table in KEYED_TABLES {
+            let name = table.name();
+            let mut count = 0;
+            for (id, data) in read_rows(tx, name)? {
+                let Ok(mut value) = serde_json::from_str::<Value>(&data) else {
+                    continue;
+                };
+                if !rewrite_row(&mut value, aliases) {
+                    continue;
+                }
+                count += 1;
+                if apply {
+                    tx.execute(
+                        &format!("UPDATE {name} SET data = jsonb(?2) WHERE id = ?1"),
+                        (id.as_str(), value.to_string()),
+                    )
+                    .map_err(|e| format!("Failed to rewrite {name} {id}: {e}"))?;
+                }
+            }
+            if count > 0 {
+                result.rows.insert(name.to_string(), count);
+                result.total += count;
+            }
+        }
+        Ok(result)
+    })
+}
+
+/// Count (`apply == false`) or rewrite the rows still naming tools by a
+/// retired key. Rewriting runs in one transaction.
+pub fn migrate_tool_keys(
+    conn: &mut Connection,
+    apply: bool,
+) -> Result<ToolKeyMigrationDto, String> {
+    migrate_in(conn, TOOL_KEY_ALIASES, apply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::helpers::{db_get, db_put};
+    use crate::db::SqliteDbState;
+    use serde_json::json;
+
+    const ALIASES: &[(&str, &str)] = &[
+        ("windsurf_ide", "windsurf_next"),
+        ("windsurf_next", "windsurf"),
+    ];
+
+    #[test]
+    fn rewrites_keyed_rows_only_when_applied() {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        state
+            .with_conn(|conn| {
+                db_put(
+                    conn,
+                    DbTable::Skill,
+                    "review",
+                    &json!({
+                        "name": "review",
+                        "enabled_tools": ["windsurf_ide", "codex", "windsurf"],
+                        "sync_details": {
+                            "windsurf_ide": { "target_path": "/old", "mode": "copy" },
+                            "codex": { "target_path": "/codex", "mode": "symlink" },
+                        },
+                    }),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::McpServer,
+                    "fetch",
+                    &json!({ "name": "fetch", "enabled_tools": ["codex"] }),
+                )
+            })
+            .unwrap();
+
+        let preview = state
+            .with_conn_mut(|conn| migrate_in(conn, ALIASES, false))
+            .unwrap();
+        assert_eq!(preview.total, 1);
+        assert_eq!(preview.rows.get("skill"), Some(&1));
+
+        state
+            .with_conn_mut(|conn| migrate_in(conn, ALIASES, true))
+            .unwrap();
+        let skill = state
+            .with_conn(|conn| db_get(conn, DbTable::Skill, "review"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(skill["enabled_tools"], json!(["windsurf", "codex"]));
+        assert_eq!(skill["sync_details"]["windsurf"]["target_path"], "/old");
+        assert!(skill["sync_details"].get("windsurf_ide").is_none());
+
+        let again = state
+            .with_conn_mut(|conn| migrate_in(conn, ALIASES, false))
+            .unwrap();
+        assert_eq!(again.total, 0);
+    }
+}
@@ -3,14 +3,24 @@
 //! This module provides unified tool adapter functionality for both Skills and MCP features.
 //! It contains built-in tool configurations, custom tool management, and detection logic.
 
-pub mod builtin;
 pub mod claude_plugins;
+pub mod commands;
 pub mod custom_store;
 pub mod detection;
+pub mod detection_cache;
+pub mod gemini_extensions;
+pub mod key_alias;
 pub mod path_utils;
+pub mod post_sync;
 pub mod types;
+pub mod wasm_plugins;
 
+pub use adapters::{builtin_adapter, builtin_adapters, BoxedToolAdapter};
+pub use ai_toolbox_core::tools::{adapters, builtin, load_rules};
 pub use builtin::*;
+pub use commands::*;
 pub use detection::*;
+pub use key_alias::canonical_tool_key;
+pub use load_rules::{ToolLoadRules, STANDARD_RULES};
 pub use path_utils::*;
 pub use types::*;
@@ -1,8 +1,89 @@
 //! Path normalization utilities for custom tools
 //!
-//! Provides functions to normalize user-input paths to relative paths.
+//! Provides functions to normalize user-input paths to relative paths, and to
+//! resolve stored paths that may reference environment variables (`$VAR`,
+//! `${VAR}`, `%VAR%`). Unset XDG base dirs and home/app-data variables fall
+//! back to their per-OS defaults.
+//!
+//! The user's home can be overridden (`AI_TOOLBOX_HOME`) for portable installs,
+//! fixture homes in tests, or managing another user's profile. Every home and
+//! config dir lookup for tool paths goes through `home_dir` / `config_dir`,
+//! so the override relocates `~`, `%APPDATA%` and the home-derived variables
+//! together.
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Environment variable that replaces the user's home for all tool paths
+pub const HOME_OVERRIDE_ENV: &str = "AI_TOOLBOX_HOME";
+
+/// Variables derived from the home dir; with an override set, their values
+/// from the environment describe the real home and are ignored
+const HOME_DERIVED_VARS: &[&str] = &[
+    "HOME",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "XDG_CONFIG_HOME",
+    "XDG_DATA_HOME",
+    "XDG_STATE_HOME",
+    "XDG_CACHE_HOME",
+];
+
+static HOME_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Read `AI_TOOLBOX_HOME`. Called once at startup.
+pub fn init_home_override_from_env() {
+    let home = std::env::var_os(HOME_OVERRIDE_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    set_home_override(home);
+}
+
+pub fn set_home_override(home: Option<PathBuf>) {
+    if let Ok(mut current) = HOME_OVERRIDE.write() {
+        *current = home;
+    }
+}
 
-use std::path::PathBuf;
+pub fn home_override() -> Option<PathBuf> {
+    HOME_OVERRIDE.read().ok().and_then(|home| home.clone())
+}
+
+/// Home dir for tool paths: the override, else the OS home
+pub fn home_dir() -> Option<PathBuf> {
+    home_override().or_else(dirs::home_dir)
+}
+
+/// Home dir the way the tools' own CLIs find it (`USERPROFILE`, then
+/// `HOME`); the override still wins
+pub fn env_home_dir() -> Option<PathBuf> {
+    home_override().or_else(|| {
+        std::env::var("USERPROFILE")
+            .or_else(|_| std::env::var("HOME"))
+            .ok()
+            .map(PathBuf::from)
+    })
+}
+
+/// `dir` at the same place under `home` as it is under `real_home`
+fn relocate(dir: &Path, real_home: &Path, home: &Path) -> Option<PathBuf> {
+    let relative = dir.strip_prefix(real_home).ok()?;
+    Some(home.join(relative))
+}
+
+/// Move a per-user OS dir under the home override
+fn in_home(dir: Option<PathBuf>) -> Option<PathBuf> {
+    match home_override() {
+        Some(home) => relocate(&dir?, &dirs::home_dir()?, &home),
+        None => dir,
+    }
+}
+
+/// Config dir (`%APPDATA%`) for tool paths, under the home override if set
+pub fn config_dir() -> Option<PathBuf> {
+    in_home(dirs::config_dir())
+}
 
 /// Path type indicator for normalized paths
 #[derive(Debug, Clone, PartialEq)]
@@ -76,7 +157,7 @@ pub fn normalize_path(input: &str) -> NormalizedPath {
     }
 
     // Try to detect if it's an absolute path matching home_dir
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = home_dir() {
         let home_str = home.to_string_lossy().replace('\\', "/");
         if normalized_input.starts_with(&home_str) {
             let relative = if normalized_input.len() > home_str.len() {
@@ -94,7 +175,7 @@ pub fn normalize_path(input: &str) -> NormalizedPath {
     }
 
     // Try to detect if it's an absolute path matching config_dir (APPDATA on Windows)
-    if let Some(config) = dirs::config_dir() {
+    if let Some(config) = config_dir() {
         let config_str = config.to_string_lossy().replace('\\', "/");
         if normalized_input.starts_with(&config_str) {
             let relative = if normalized_input.len() > config_str.len() {
@@ -144,29 +225,32 @@ pub fn to_storage_path(normalized: &NormalizedPath) -> String {
 /// Resolve a storage path to an absolute path.
 /// - If path starts with ~/, resolve using home_dir
 /// - If path starts with %APPDATA%/, resolve using config_dir
+/// - Other environment variables are expanded (see `expand_env_vars`);
+///   returns None when one is unset and has no default
 /// - If path is absolute (starts with / or contains :), use as-is
 /// - Otherwise, treat as home-relative for backward compatibility
 pub fn resolve_storage_path(storage_path: &str) -> Option<PathBuf> {
-    let path = storage_path.trim();
+    let expanded = expand_env_vars(storage_path.trim())?;
+    let path = expanded.as_str();
     let normalized = path.replace('\\', "/");
 
     // Check for ~/ prefix (home directory)
     if normalized.starts_with("~/") {
         let relative = to_platform_path(&normalized[2..]);
-        return dirs::home_dir().map(|h| h.join(relative));
+        return home_dir().map(|h| h.join(relative));
     }
     if normalized == "~" {
-        return dirs::home_dir();
+        return home_dir();
     }
 
     // Check for %APPDATA% prefix (config directory)
     let upper_path = normalized.to_uppercase();
     if upper_path.starts_with("%APPDATA%/") {
         let relative = to_platform_path(&normalized[10..]);
-        return dirs::config_dir().map(|c| c.join(relative));
+        return config_dir().map(|c| c.join(relative));
     }
     if upper_path == "%APPDATA%" {
-        return dirs::config_dir();
+        return config_dir();
     }
 
     // Check if it's an absolute path
@@ -176,7 +260,39 @@ pub fn resolve_storage_path(storage_path: &str) -> Option<PathBuf> {
 
     // Backward compatibility: treat plain relative paths as home-relative
     let platform_path = to_platform_path(path);
-    dirs::home_dir().map(|h| h.join(platform_path))
+    home_dir().map(|h| h.join(platform_path))
+}
+
+/// Resolve a storage path against another user's home, e.g. a second account
+/// or a mounted backup.
+/// - `~/` and plain relative paths resolve under `home`
+/// - `%APPDATA%/` resolves to the config dir at the same place under `home`
+///   as the current user's config dir under theirs
+/// - Absolute paths and other environment variables describe this machine's
+///   current user, so they return None
+pub fn resolve_storage_path_in_home(storage_path: &str, home: &Path) -> Option<PathBuf> {
+    let normalized = storage_path.trim().replace('\\', "/");
+    let upper = normalized.to_uppercase();
+    if let Some(rest) = upper.strip_prefix("%APPDATA%") {
+        if !(rest.is_empty() || rest.starts_with('/')) {
+            return None;
+        }
+        let config = config_dir()?;
+        let config_in_home = config.strip_prefix(home_dir()?).ok()?.to_path_buf();
+        let relative = to_platform_path(normalized[9..].trim_start_matches('/'));
+        return Some(home.join(config_in_home).join(relative));
+    }
+    if normalized.contains(['$', '%']) {
+        return None;
+    }
+    if normalized == "~" {
+        return Some(home.to_path_buf());
+    }
+    let relative = normalized.strip_prefix("~/").unwrap_or(&normalized);
+    if relative.starts_with('/') || relative.contains(':') {
+        return None;
+    }
+    Some(home.join(to_platform_path(relative)))
 }
 
 /// Check if a storage path is a root directory (home or appdata) that would be dangerous to scan.
@@ -197,12 +313,12 @@ pub fn is_root_directory(storage_path: &str) -> bool {
 
     // Check if resolved path equals home_dir or config_dir
     if let Some(resolved) = resolve_storage_path(storage_path) {
-        if let Some(home) = dirs::home_dir() {
+        if let Some(home) = home_dir() {
             if resolved == home {
                 return true;
             }
         }
-        if let Some(config) = dirs::config_dir() {
+        if let Some(config) = config_dir() {
             if resolved == config {
                 return true;
             }
@@ -212,6 +328,97 @@ pub fn is_root_directory(storage_path: &str) -> bool {
     false
 }
 
+/// Expand `$VAR`, `${VAR}` and `%VAR%` references.
+///
+/// `%APPDATA%` at the start of a path is left alone: `resolve_storage_path`
+/// maps it to the config dir on every OS. Returns None when a variable is
+/// unset and has no per-OS default.
+pub fn expand_env_vars(input: &str) -> Option<String> {
+    let overridden = home_override().is_some();
+    expand_env_vars_with(input, |name| {
+        if overridden && HOME_DERIVED_VARS.contains(&name.to_ascii_uppercase().as_str()) {
+            return None;
+        }
+        std::env::var(name).ok().filter(|value| !value.is_empty())
+    })
+}
+
+fn expand_env_vars_with(input: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let upper = input.to_uppercase();
+    let (mut out, mut rest) = if upper == "%APPDATA%"
+        || upper.starts_with("%APPDATA%/")
+        || upper.starts_with("%APPDATA%\\")
+    {
+        (input[..9].to_string(), &input[9..])
+    } else {
+        (String::new(), input)
+    };
+
+    while let Some(pos) = rest.find(['$', '%']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        let (name, len) = if let Some(inner) = tail.strip_prefix("${") {
+            match inner.find('}') {
+                Some(end) => (&inner[..end], end + 3),
+                None => ("", tail.len()),
+            }
+        } else if let Some(inner) = tail.strip_prefix('$') {
+            let end = inner
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(inner.len());
+            (&inner[..end], end + 1)
+        } else {
+            match tail[1..].find('%') {
+                Some(end) => (&tail[1..end + 1], end + 2),
+                None => ("", tail.len()),
+            }
+        };
+
+        if is_env_var_name(name) {
+            out.push_str(&lookup(name).or_else(|| default_env_value(name))?);
+            rest = &tail[len..];
+        } else {
+            // Not a variable reference: keep the sigil and continue after it
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+fn is_env_var_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Per-OS default of a well-known variable that is not set
+fn default_env_value(name: &str) -> Option<String> {
+    let path = match name.to_ascii_uppercase().as_str() {
+        "HOME" | "USERPROFILE" => home_dir(),
+        "APPDATA" => config_dir(),
+        "LOCALAPPDATA" => in_home(dirs::data_local_dir()),
+        "XDG_CONFIG_HOME" => xdg_default(config_dir(), ".config"),
+        "XDG_DATA_HOME" => xdg_default(in_home(dirs::data_dir()), ".local/share"),
+        "XDG_STATE_HOME" => xdg_default(in_home(dirs::data_local_dir()), ".local/state"),
+        "XDG_CACHE_HOME" => xdg_default(in_home(dirs::cache_dir()), ".cache"),
+        _ => None,
+    }?;
+    Some(path.to_string_lossy().to_string())
+}
+
+/// XDG base dir default: `~/<home_relative>` as the spec says (CLI tools use
+/// it on macOS too), or the matching known folder on Windows
+fn xdg_default(windows_dir: Option<PathBuf>, home_relative: &str) -> Option<PathBuf> {
+    if cfg!(windows) {
+        windows_dir
+    } else {
+        home_dir().map(|home| home.join(home_relative))
+    }
+}
+
 /// Convert forward slashes to the platform-native separator.
 /// On Windows this replaces `/` with `\`; on Unix it's a no-op.
 pub fn to_platform_path(p: &str) -> String {
@@ -221,3 +428,99 @@ pub fn to_platform_path(p: &str) -> String {
         p.replace('/', &std::path::MAIN_SEPARATOR.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "XDG_CONFIG_HOME" => Some("/xdg/config".to_string()),
+            "TOOL_ROOT" => Some("/opt/tool".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn relocates_user_dirs_under_the_home_override() {
+        let real_home = Path::new("/home/alice");
+        let home = Path::new("/portable/home");
+        assert_eq!(
+            relocate(Path::new("/home/alice/.config"), real_home, home),
+            Some(home.join(".config"))
+        );
+        assert_eq!(relocate(Path::new("/etc/xdg"), real_home, home), None);
+    }
+
+    #[test]
+    fn resolves_storage_paths_in_other_home() {
+        let home = Path::new("/mnt/backup/home/alice");
+        assert_eq!(
+            resolve_storage_path_in_home("~/.claude/skills", home),
+            Some(home.join(".claude").join("skills"))
+        );
+        assert_eq!(
+            resolve_storage_path_in_home(".codex/skills", home),
+            Some(home.join(".codex").join("skills"))
+        );
+        assert_eq!(
+            resolve_storage_path_in_home("~", home),
+            Some(home.to_path_buf())
+        );
+        assert_eq!(resolve_storage_path_in_home("/opt/skills", home), None);
+        assert_eq!(
+            resolve_storage_path_in_home("$XDG_CONFIG_HOME/opencode", home),
+            None
+        );
+        if let (Some(config), Some(real_home)) = (dirs::config_dir(), dirs::home_dir()) {
+            if let Ok(config_in_home) = config.strip_prefix(real_home) {
+                assert_eq!(
+                    resolve_storage_path_in_home("%APPDATA%/Code/User", home),
+                    Some(home.join(config_in_home).join("Code").join("User"))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn expands_all_reference_styles() {
+        assert_eq!(
+            expand_env_vars_with("$XDG_CONFIG_HOME/opencode/skills", lookup).as_deref(),
+            Some("/xdg/config/opencode/skills")
+        );
+        assert_eq!(
+            expand_env_vars_with("${TOOL_ROOT}/skills", lookup).as_deref(),
+            Some("/opt/tool/skills")
+        );
+        assert_eq!(
+            expand_env_vars_with("%TOOL_ROOT%\\skills", lookup).as_deref(),
+            Some("/opt/tool\\skills")
+        );
+        // Left for resolve_storage_path, which maps it to the config dir
+        assert_eq!(
+            expand_env_vars_with("%APPDATA%/tool", lookup).as_deref(),
+            Some("%APPDATA%/tool")
+        );
+        // Not variable references
+        assert_eq!(
+            expand_env_vars_with("~/50% off/$", lookup).as_deref(),
+            Some("~/50% off/$")
+        );
+        assert_eq!(expand_env_vars_with("$UNSET_TOOL_VAR/skills", lookup), None);
+    }
+
+    #[test]
+    fn unset_xdg_and_home_vars_fall_back_to_defaults() {
+        let home = dirs::home_dir().unwrap();
+        let expanded = expand_env_vars_with("${HOME}/x", |_| None).unwrap();
+        assert_eq!(PathBuf::from(expanded), home.join("x"));
+        if !cfg!(windows) {
+            let expanded = expand_env_vars_with("$XDG_CACHE_HOME/tool", |_| None).unwrap();
+            assert_eq!(PathBuf::from(expanded), home.join(".cache").join("tool"));
+        }
+        assert_eq!(
+            resolve_storage_path("${TOOL_ROOT_NOT_SET_ANYWHERE}/skills"),
+            None
+        );
+    }
+}
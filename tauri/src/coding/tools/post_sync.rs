@@ -0,0 +1,413 @@
+//! Post-sync reload hooks
+//!
+//! Some tools cache their skills / MCP config and only pick up changes after a
+//! reload. A hook is configured per tool key and runs after a successful sync
+//! of the selected scopes. Calls are debounced per tool, so a batch sync that
+//! touches one tool many times runs its hook once.
+//!
+//! Built-in tools that watch their skills dir get a default hook that touches
+//! it after a skills sync: they notice new and removed skills, but not files
+//! rewritten inside an existing skill. Configured hooks replace the default
+//! of their tool; removing a default stores it with both scopes off.
+//!
+//! Stored in the `settings` table under the `tool_post_sync_hooks` record.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use super::path_utils::resolve_storage_path;
+use crate::db::helpers::{db_get, db_put};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+const HOOKS_RECORD_ID: &str = "tool_post_sync_hooks";
+const DEBOUNCE: Duration = Duration::from_millis(1500);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+/// Created and removed inside a directory to bump its mtime
+const RELOAD_MARKER: &str = ".ai-toolbox-reload";
+/// Built-in tools whose skills dir is watched by the editor
+const TOUCH_SKILLS_DIR_TOOLS: &[&str] = &[
+    "antigravity",
+    "cursor",
+    "github_copilot",
+    "kilo_code",
+    "qoder",
+    "roo_code",
+    "windsurf",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostSyncScope {
+    Skills,
+    Mcp,
+}
+
+impl PostSyncScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostSyncScope::Skills => "skills",
+            PostSyncScope::Mcp => "mcp",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PostSyncAction {
+    /// Bump the mtime of `path` (created if missing), or of the synced
+    /// location (skills dir / MCP config file) when `path` is empty
+    Touch {
+        #[serde(default)]
+        path: Option<String>,
+    },
+    /// Send a signal to every process named `process` (Unix only)
+    Signal {
+        process: String,
+        #[serde(default = "default_signal")]
+        signal: String,
+    },
+    /// Run a program directly, without a shell
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+fn default_signal() -> String {
+    "HUP".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PostSyncHook {
+    pub action: PostSyncAction,
+    #[serde(default = "default_true")]
+    pub on_skills: bool,
+    #[serde(default = "default_true")]
+    pub on_mcp: bool,
+}
+
+impl PostSyncHook {
+    fn applies_to(&self, scope: PostSyncScope) -> bool {
+        match scope {
+            PostSyncScope::Skills => self.on_skills,
+            PostSyncScope::Mcp => self.on_mcp,
+        }
+    }
+}
+
+/// Tool key -> hook
+pub type PostSyncHooks = BTreeMap<String, PostSyncHook>;
+
+/// The hooks built-in tools get without any configuration
+pub fn default_post_sync_hooks() -> PostSyncHooks {
+    TOUCH_SKILLS_DIR_TOOLS
+        .iter()
+        .map(|tool| {
+            (
+                tool.to_string(),
+                PostSyncHook {
+                    action: PostSyncAction::Touch { path: None },
+                    on_skills: true,
+                    on_mcp: false,
+                },
+            )
+        })
+        .collect()
+}
+
+/// The configured hooks over the built-in defaults
+pub fn load_post_sync_hooks(state: &SqliteDbState) -> PostSyncHooks {
+    let mut hooks = default_post_sync_hooks();
+    hooks.extend(load_configured_hooks(state));
+    hooks
+}
+
+fn load_configured_hooks(state: &SqliteDbState) -> PostSyncHooks {
+    state
+        .with_conn(|conn| db_get(conn, DbTable::Settings, HOOKS_RECORD_ID))
+        .ok()
+        .flatten()
+        .and_then(|record| record.get("hooks").cloned())
+        .and_then(|hooks| serde_json::from_value(hooks).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_post_sync_hooks(state: &SqliteDbState, hooks: &PostSyncHooks) -> Result<(), String> {
+    for (tool, hook) in hooks {
+        validate_hook(hook).map_err(|e| format!("{}: {}", tool, e))?;
+    }
+    // Store only what differs from the defaults, so later defaults still
+    // apply; a removed default stays off
+    let defaults = default_post_sync_hooks();
+    let mut configured: PostSyncHooks = hooks
+        .iter()
+        .filter(|(tool, hook)| defaults.get(*tool) != Some(*hook))
+        .map(|(tool, hook)| (tool.clone(), hook.clone()))
+        .collect();
+    for (tool, default) in defaults {
+        if !hooks.contains_key(&tool) {
+            configured.insert(
+                tool,
+                PostSyncHook {
+                    on_skills: false,
+                    on_mcp: false,
+                    ..default
+                },
+            );
+        }
+    }
+    let hooks = serde_json::to_value(&configured)
+        .map_err(|e| format!("Failed to serialize post-sync hooks: {}", e))?;
+    let record = serde_json::json!({ "hooks": hooks });
+    state.with_conn(|conn| db_put(conn, DbTable::Settings, HOOKS_RECORD_ID, &record))
+}
+
+fn validate_hook(hook: &PostSyncHook) -> Result<(), String> {
+    match &hook.action {
+        PostSyncAction::Touch { .. } => Ok(()),
+        PostSyncAction::Signal { process, signal } => {
+            if process.trim().is_empty() {
+                return Err("process name is required".to_string());
+            }
+            if signal.is_empty() || !signal.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(format!("invalid signal: {}", signal));
+            }
+            Ok(())
+        }
+        PostSyncAction::Command { program, .. } if program.trim().is_empty() => {
+            Err("program is required".to_string())
+        }
+        PostSyncAction::Command { .. } => Ok(()),
+    }
+}
+
+fn pending() -> &'static Mutex<HashMap<String, u64>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Schedule the hook of `tool_key` after a successful sync. Does nothing when
+/// the tool has no hook for `scope`; repeated calls within the debounce window
+/// collapse into one run.
+pub fn schedule_post_sync(
+    state: &SqliteDbState,
+    tool_key: &str,
+    scope: PostSyncScope,
+    synced_path: &Path,
+) {
+    let Some(hook) = load_post_sync_hooks(state)
+        .remove(tool_key)
+        .filter(|hook| hook.applies_to(scope))
+    else {
+        return;
+    };
+    let generation = {
+        let Ok(mut pending) = pending().lock() else {
+            return;
+        };
+        let entry = pending.entry(tool_key.to_string()).or_insert(0);
+        *entry += 1;
+        *entry
+    };
+
+    let tool_key = tool_key.to_string();
+    let synced_path = synced_path.to_path_buf();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(DEBOUNCE).await;
+        let latest = pending()
+            .lock()
+            .ok()
+            .and_then(|pending| pending.get(&tool_key).copied());
+        if latest != Some(generation) {
+            return;
+        }
+        match run_post_sync_action(&tool_key, scope, &hook.action, &synced_path).await {
+            Ok(()) => log::info!("[post-sync] ran {} hook for {}", scope.as_str(), tool_key),
+            Err(err) => log::warn!(
+                "[post-sync] {} hook for {} failed: {}",
+                scope.as_str(),
+                tool_key,
+                err
+            ),
+        }
+    });
+}
+
+/// Run an action now; used by the debounced scheduler and the "test hook" command
+pub async fn run_post_sync_action(
+    tool_key: &str,
+    scope: PostSyncScope,
+    action: &PostSyncAction,
+    synced_path: &Path,
+) -> Result<(), String> {
+    match action {
+        PostSyncAction::Touch { path } => {
+            let explicit = path
+                .as_deref()
+                .filter(|path| !path.trim().is_empty())
+                .map(|path| {
+                    resolve_storage_path(path).ok_or_else(|| format!("Invalid path: {}", path))
+                })
+                .transpose()?;
+            touch(explicit.as_deref().unwrap_or(synced_path))
+        }
+        PostSyncAction::Signal { process, signal } => send_signal(process, signal).await,
+        PostSyncAction::Command { program, args } => {
+            let mut command = tokio::process::Command::new(program);
+            command
+                .args(args)
+                .env("AI_TOOLBOX_TOOL", tool_key)
+                .env("AI_TOOLBOX_SCOPE", scope.as_str())
+                .env("AI_TOOLBOX_SYNCED_PATH", synced_path)
+                .kill_on_drop(true);
+            #[cfg(windows)]
+            command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+            run_with_timeout(command, program).await
+        }
+    }
+}
+
+fn touch(path: &Path) -> Result<(), String> {
+    if path.is_dir() {
+        // Directory mtimes cannot be set portably; adding and removing an
+        // entry updates it and wakes up directory watchers
+        let marker = path.join(RELOAD_MARKER);
+        std::fs::write(&marker, b"").map_err(|e| format!("Failed to touch {:?}: {}", path, e))?;
+        return std::fs::remove_file(&marker)
+            .map_err(|e| format!("Failed to touch {:?}: {}", path, e));
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+        .map_err(|e| format!("Failed to touch {:?}: {}", path, e))
+}
+
+#[cfg(unix)]
+async fn send_signal(process: &str, signal: &str) -> Result<(), String> {
+    let mut command = tokio::process::Command::new("pkill");
+    command
+        .arg(format!("-{}", signal))
+        .args(["-x", process])
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(COMMAND_TIMEOUT, command.output())
+        .await
+        .map_err(|_| "pkill timed out".to_string())?
+        .map_err(|e| format!("Failed to run pkill: {}", e))?;
+    // Exit code 1: no process matched, i.e. the tool is not running
+    match output.status.code() {
+        Some(0) | Some(1) => Ok(()),
+        _ => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+    }
+}
+
+#[cfg(not(unix))]
+async fn send_signal(_process: &str, _signal: &str) -> Result<(), String> {
+    Err("Signal hooks are not supported on this platform".to_string())
+}
+
+async fn run_with_timeout(
+    mut command: tokio::process::Command,
+    program: &str,
+) -> Result<(), String> {
+    let output = tokio::time::timeout(COMMAND_TIMEOUT, command.output())
+        .await
+        .map_err(|_| format!("{} timed out after {}s", program, COMMAND_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(format!(
+        "{} exited with {}: {}",
+        program, output.status, stderr
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hooks_round_trip_and_validate() {
+        let state = SqliteDbState::in_memory_for_test().unwrap();
+        assert_eq!(load_post_sync_hooks(&state), default_post_sync_hooks());
+
+        let mut hooks = default_post_sync_hooks();
+        hooks.remove("cursor");
+        hooks.insert(
+            "codex".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "action": { "kind": "signal", "process": "codex" },
+                "on_mcp": false,
+            }))
+            .unwrap(),
+        );
+        save_post_sync_hooks(&state, &hooks).unwrap();
+        let stored = state
+            .with_conn(|conn| db_get(conn, DbTable::Settings, HOOKS_RECORD_ID))
+            .unwrap()
+            .unwrap();
+        assert!(stored["hooks"].get("windsurf").is_none());
+        let loaded = load_post_sync_hooks(&state);
+        assert_eq!(loaded["windsurf"], default_post_sync_hooks()["windsurf"]);
+        assert!(!loaded["cursor"].applies_to(PostSyncScope::Skills));
+        assert!(!loaded["cursor"].applies_to(PostSyncScope::Mcp));
+        let hook = &loaded["codex"];
+        assert!(hook.applies_to(PostSyncScope::Skills));
+        assert!(!hook.applies_to(PostSyncScope::Mcp));
+        assert_eq!(
+            hook.action,
+            PostSyncAction::Signal {
+                process: "codex".to_string(),
+                signal: "HUP".to_string(),
+            }
+        );
+
+        hooks.insert(
+            "bad".to_string(),
+            PostSyncHook {
+                action: PostSyncAction::Signal {
+                    process: "x".to_string(),
+                    signal: "HUP; rm".to_string(),
+                },
+                on_skills: true,
+                on_mcp: true,
+            },
+        );
+        assert!(save_post_sync_hooks(&state, &hooks).is_err());
+    }
+
+    #[tokio::test]
+    async fn touch_action_updates_synced_dir_and_explicit_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let action = PostSyncAction::Touch { path: None };
+        run_post_sync_action("demo", PostSyncScope::Skills, &action, temp.path())
+            .await
+            .unwrap();
+        assert!(!temp.path().join(RELOAD_MARKER).exists());
+
+        let file = temp.path().join("nested/reload.flag");
+        let action = PostSyncAction::Touch {
+            path: Some(file.to_string_lossy().to_string()),
+        };
+        run_post_sync_action("demo", PostSyncScope::Mcp, &action, temp.path())
+            .await
+            .unwrap();
+        assert!(file.is_file());
+    }
+}
@@ -4,20 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 
-/// Built-in tool configuration (compile-time constants)
-/// Contains both Skills and MCP related configuration
-#[derive(Clone, Debug)]
-pub struct BuiltinTool {
-    pub key: &'static str,
-    pub display_name: &'static str,
-    // Skills related (optional)
-    pub relative_skills_dir: Option<&'static str>,
-    pub relative_detect_dir: Option<&'static str>,
-    // MCP related (optional)
-    pub mcp_config_path: Option<&'static str>,
-    pub mcp_config_format: Option<&'static str>, // "json" | "toml"
-    pub mcp_field: Option<&'static str>,         // field name in config file
-}
+pub use ai_toolbox_core::mcp::types::McpFormatConfig;
+pub use ai_toolbox_core::tools::types::BuiltinTool;
 
 /// Custom tool defined by user (database storage)
 /// Supports both Skills and MCP configurations
@@ -31,6 +19,10 @@ pub struct CustomTool {
     /// Force copy mode for skills sync (instead of symlink)
     #[serde(default)]
     pub force_copy: bool,
+    /// Sync mode for skills sync ("copy" | "symlink" | "junction" | "hardlink");
+    /// `None` links with a copy fallback
+    #[serde(default)]
+    pub preferred_sync_mode: Option<String>,
     // MCP related (optional)
     pub mcp_config_path: Option<String>,
     pub mcp_config_format: Option<String>,
@@ -49,6 +41,8 @@ pub struct RuntimeTool {
     pub relative_detect_dir: Option<String>,
     /// Force copy mode for skills sync (instead of symlink)
     pub force_copy: bool,
+    /// Sync mode for skills sync; `None` links with a copy fallback
+    pub preferred_sync_mode: Option<String>,
     // MCP related
     pub mcp_config_path: Option<String>,
     pub mcp_config_format: Option<String>,
@@ -63,7 +57,8 @@ impl From<&BuiltinTool> for RuntimeTool {
             is_custom: false,
             relative_skills_dir: tool.relative_skills_dir.map(|s| s.to_string()),
             relative_detect_dir: tool.relative_detect_dir.map(|s| s.to_string()),
-            force_copy: false, // Built-in tools use default (cursor handled specially in sync logic)
+            force_copy: false,
+            preferred_sync_mode: tool.preferred_sync_mode.map(|s| s.to_string()),
             mcp_config_path: tool.mcp_config_path.map(|s| s.to_string()),
             mcp_config_format: tool.mcp_config_format.map(|s| s.to_string()),
             mcp_field: tool.mcp_field.map(|s| s.to_string()),
@@ -80,6 +75,7 @@ impl From<&CustomTool> for RuntimeTool {
             relative_skills_dir: tool.relative_skills_dir.clone(),
             relative_detect_dir: tool.relative_detect_dir.clone(),
             force_copy: tool.force_copy,
+            preferred_sync_mode: tool.preferred_sync_mode.clone(),
             mcp_config_path: tool.mcp_config_path.clone(),
             mcp_config_format: tool.mcp_config_format.clone(),
             mcp_field: tool.mcp_field.clone(),
@@ -95,6 +91,7 @@ pub struct CustomToolDto {
     pub relative_skills_dir: Option<String>,
     pub relative_detect_dir: Option<String>,
     pub force_copy: bool,
+    pub preferred_sync_mode: Option<String>,
     pub mcp_config_path: Option<String>,
     pub mcp_config_format: Option<String>,
     pub mcp_field: Option<String>,
@@ -109,6 +106,7 @@ impl From<CustomTool> for CustomToolDto {
             relative_skills_dir: tool.relative_skills_dir,
             relative_detect_dir: tool.relative_detect_dir,
             force_copy: tool.force_copy,
+            preferred_sync_mode: tool.preferred_sync_mode,
             mcp_config_path: tool.mcp_config_path,
             mcp_config_format: tool.mcp_config_format,
             mcp_field: tool.mcp_field,
@@ -145,73 +143,6 @@ pub struct ToolDetectionDto {
     pub supports_mcp: bool,
 }
 
-/// MCP format configuration for different tools
-/// Defines how to convert between ai-toolbox's unified format and tool-specific formats
-#[derive(Clone, Debug)]
-pub struct McpFormatConfig {
-    /// Type mappings (e.g., "stdio" -> "local", "sse" -> "remote")
-    pub type_mappings: &'static [(&'static str, &'static str)],
-    /// Whether to merge command and args into a single array
-    pub merge_command_args: bool,
-    /// Field name for environment variables ("env" or "environment")
-    pub env_field: &'static str,
-    /// Whether the format requires an "enabled" field
-    pub requires_enabled: bool,
-    /// Default tool type when type field is missing (e.g., "local" for OpenCode)
-    pub default_tool_type: &'static str,
-    /// Whether the format supports a "timeout" field
-    pub supports_timeout: bool,
-    /// Field mappings for remote server URLs (e.g. "http" -> "httpUrl")
-    pub remote_url_field_mappings: &'static [(&'static str, &'static str)],
-    /// Whether missing type should be inferred from tool-specific remote URL fields
-    pub infer_remote_type_from_url_fields_when_type_missing: bool,
-}
-
-impl McpFormatConfig {
-    /// Map a server type from unified format to tool format
-    pub fn map_type_to_tool(&self, server_type: &str) -> String {
-        for (from, to) in self.type_mappings {
-            if *from == server_type {
-                return to.to_string();
-            }
-        }
-        server_type.to_string()
-    }
-
-    /// Map a server type from tool format to unified format
-    pub fn map_type_from_tool(&self, tool_type: &str) -> String {
-        for (from, to) in self.type_mappings {
-            if *to == tool_type {
-                return from.to_string();
-            }
-        }
-        tool_type.to_string()
-    }
-
-    /// Resolve the tool-side URL field for a unified remote server type.
-    pub fn remote_url_field_for_type(&self, server_type: &str) -> &'static str {
-        for (from, field) in self.remote_url_field_mappings {
-            if *from == server_type {
-                return field;
-            }
-        }
-        "url"
-    }
-
-    /// Infer unified remote server type from tool-specific URL field names.
-    pub fn infer_remote_type_from_url_fields(
-        &self,
-        server_config: &serde_json::Value,
-    ) -> Option<String> {
-        for (server_type, field) in self.remote_url_field_mappings {
-            if server_config.get(*field).is_some() {
-                return Some((*server_type).to_string());
-            }
-        }
-        None
-    }
-}
-
 /// Helper function to get current timestamp in milliseconds
 pub fn now_ms() -> i64 {
     let now = std::time::SystemTime::now()
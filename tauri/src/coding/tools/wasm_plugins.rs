@@ -0,0 +1,346 @@
+//! Tools supplied by WASM plugins in the app data dir
+//!
+//! Each `*.wasm` file in `<app data>/tool-plugins` is a sandboxed tool
+//! adapter (`ai_toolbox_core::tools::wasm_plugin`). Only files the user
+//! approved are loaded: an approval (`tools_approve_plugin`) records the
+//! file's SHA-256 in the `settings` table under `tool_plugin_approvals`, so a
+//! file dropped into or replaced in the dir stays unloaded until approved.
+//! Plugins are loaded at startup and on `tools_reload_plugins`; their
+//! `detect` runs once per load and every path it returns stays in the
+//! plugin's tool dir.
+//! An installed plugin tool joins the custom tools (`custom_store::
+//! get_custom_tools`), so Skills and MCP list, sync and resolve it like a
+//! tool the user added, with the paths the plugin detected. A plugin that
+//! renders MCP configs gets the `plugin` config format, which
+//! `mcp::config_sync` writes through `render_config`.
+//!
+//! A stored custom tool with the same key wins over the plugin.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use log::{info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use ai_toolbox_core::tools::wasm_plugin::{
+    PluginDetection, RenderConfigRequest, WasmToolPlugin, PLUGIN_EXTENSION,
+};
+
+use super::path_utils::resolve_storage_path;
+use super::types::CustomTool;
+use crate::db::helpers::{db_get, db_put};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+/// Dir under the app data dir plugins are loaded from
+pub const PLUGINS_DIR_NAME: &str = "tool-plugins";
+/// MCP config format of plugin tools that render their own config
+pub const PLUGIN_CONFIG_FORMAT: &str = "plugin";
+const APPROVALS_RECORD_ID: &str = "tool_plugin_approvals";
+
+static PLUGINS_DIR: OnceLock<PathBuf> = OnceLock::new();
+static PLUGINS: RwLock<Vec<LoadedPlugin>> = RwLock::new(Vec::new());
+static STATUSES: RwLock<Vec<ToolPluginDto>> = RwLock::new(Vec::new());
+
+struct LoadedPlugin {
+    plugin: Arc<WasmToolPlugin>,
+    detection: PluginDetection,
+    installed: bool,
+}
+
+/// The plugins dir and its plugin files
+#[derive(Clone, Debug, Serialize)]
+pub struct ToolPluginsDto {
+    pub dir: Option<String>,
+    pub plugins: Vec<ToolPluginDto>,
+}
+
+/// A plugin file and what came of loading it
+#[derive(Clone, Debug, Serialize)]
+pub struct ToolPluginDto {
+    pub file: String,
+    /// SHA-256 of the file, what an approval is for
+    pub sha256: String,
+    /// Approved by the user; unapproved files are not loaded
+    pub approved: bool,
+    pub key: Option<String>,
+    pub display_name: Option<String>,
+    pub installed: bool,
+    /// Dir all of the plugin's paths stay in
+    pub tool_dir: Option<String>,
+    pub skills_dir: Option<String>,
+    pub mcp_config_path: Option<String>,
+    pub renders_config: bool,
+    /// Why the plugin was not loaded
+    pub error: Option<String>,
+}
+
+pub fn set_plugins_dir(dir: PathBuf) {
+    let _ = PLUGINS_DIR.set(dir);
+}
+
+pub fn plugins_dir() -> Option<&'static Path> {
+    PLUGINS_DIR.get().map(PathBuf::as_path)
+}
+
+/// File name -> SHA-256 of the approved content
+type PluginApprovals = BTreeMap<String, String>;
+
+fn load_approvals(state: &SqliteDbState) -> PluginApprovals {
+    state
+        .with_conn(|conn| db_get(conn, DbTable::Settings, APPROVALS_RECORD_ID))
+        .ok()
+        .flatten()
+        .and_then(|record| record.get("plugins").cloned())
+        .and_then(|plugins| serde_json::from_value(plugins).ok())
+        .unwrap_or_default()
+}
+
+fn save_approvals(state: &SqliteDbState, approvals: &PluginApprovals) -> Result<(), String> {
+    let record = serde_json::json!({ "plugins": approvals });
+    state.with_conn(|conn| db_put(conn, DbTable::Settings, APPROVALS_RECORD_ID, &record))
+}
+
+/// Approve the plugin `file` with content `sha256` (what the user was
+/// shown), or withdraw its approval
+pub fn set_plugin_approval(
+    state: &SqliteDbState,
+    file: &str,
+    sha256: &str,
+    approved: bool,
+) -> Result<(), String> {
+    let mut approvals = load_approvals(state);
+    if approved {
+        let dir = plugins_dir().ok_or("The tool plugins dir is not set")?;
+        let path = plugin_files(dir)
+            .into_iter()
+            .find(|path| file_name(path) == file)
+            .ok_or_else(|| format!("Tool plugin {} not found", file))?;
+        if file_sha256(&path)? != sha256 {
+            return Err(format!(
+                "Tool plugin {} changed since it was listed; reload and review it again",
+                file
+            ));
+        }
+        approvals.insert(file.to_string(), sha256.to_string());
+    } else {
+        approvals.remove(file);
+    }
+    save_approvals(state, &approvals)
+}
+
+/// Load every approved plugin in the plugins dir again, replacing the loaded
+/// ones; creates the dir when missing so the user can open it
+pub fn reload_plugins(state: &SqliteDbState) -> Vec<ToolPluginDto> {
+    let approvals = load_approvals(state);
+    let (loaded, statuses) = match plugins_dir() {
+        Some(dir) => {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                warn!("Failed to create tool plugins dir {:?}: {}", dir, e);
+            }
+            load_dir(dir, &approvals)
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+    info!(
+        "Loaded {} of {} tool plugin file(s)",
+        loaded.len(),
+        statuses.len()
+    );
+    *PLUGINS.write().unwrap_or_else(|e| e.into_inner()) = loaded;
+    *STATUSES.write().unwrap_or_else(|e| e.into_inner()) = statuses.clone();
+    statuses
+}
+
+/// Plugin files found by the last load
+pub fn plugin_statuses() -> ToolPluginsDto {
+    ToolPluginsDto {
+        dir: plugins_dir().map(|dir| dir.to_string_lossy().to_string()),
+        plugins: STATUSES.read().unwrap_or_else(|e| e.into_inner()).clone(),
+    }
+}
+
+fn plugin_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case(PLUGIN_EXTENSION))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn read_plugin_file(path: &Path) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn file_sha256(path: &Path) -> Result<String, String> {
+    read_plugin_file(path).map(|wasm| hex::encode(Sha256::digest(&wasm)))
+}
+
+fn load_dir(dir: &Path, approvals: &PluginApprovals) -> (Vec<LoadedPlugin>, Vec<ToolPluginDto>) {
+    let mut loaded: Vec<LoadedPlugin> = Vec::new();
+    let mut statuses = Vec::new();
+    for path in plugin_files(dir) {
+        let file = file_name(&path);
+        let unloaded = |sha256: String, approved: bool, error: Option<String>| ToolPluginDto {
+            file: file.clone(),
+            sha256,
+            approved,
+            key: None,
+            display_name: None,
+            installed: false,
+            tool_dir: None,
+            skills_dir: None,
+            mcp_config_path: None,
+            renders_config: false,
+            error,
+        };
+        // Hash and load the same bytes, so the file cannot change in between
+        let wasm = match read_plugin_file(&path) {
+            Ok(wasm) => wasm,
+            Err(error) => {
+                statuses.push(unloaded(String::new(), false, Some(error)));
+                continue;
+            }
+        };
+        let sha256 = hex::encode(Sha256::digest(&wasm));
+        // Approved content only; a replaced file needs a new approval
+        if approvals.get(&file) != Some(&sha256) {
+            statuses.push(unloaded(sha256, false, None));
+            continue;
+        }
+        let result = load_plugin(&wasm).and_then(|plugin| {
+            let key = &plugin.plugin.manifest().key;
+            if loaded
+                .iter()
+                .any(|other| &other.plugin.manifest().key == key)
+            {
+                return Err(format!("Another plugin already provides tool {}", key));
+            }
+            Ok(plugin)
+        });
+        match result {
+            Ok(plugin) => {
+                statuses.push(status_of(file, sha256, &plugin));
+                loaded.push(plugin);
+            }
+            Err(error) => {
+                warn!("Skipping tool plugin {}: {}", file, error);
+                statuses.push(unloaded(sha256, true, Some(error)));
+            }
+        }
+    }
+    (loaded, statuses)
+}
+
+fn load_plugin(wasm: &[u8]) -> Result<LoadedPlugin, String> {
+    let plugin = WasmToolPlugin::from_bytes(wasm).map_err(|e| format!("{:#}", e))?;
+    let detection = plugin
+        .detect(&plugin.detect_context())
+        .map_err(|e| format!("{:#}", e))?;
+    let installed = detection
+        .detect_paths
+        .iter()
+        .filter_map(|path| resolve_storage_path(path))
+        .any(|path| path.exists());
+    Ok(LoadedPlugin {
+        plugin: Arc::new(plugin),
+        detection,
+        installed,
+    })
+}
+
+fn status_of(file: String, sha256: String, loaded: &LoadedPlugin) -> ToolPluginDto {
+    let manifest = loaded.plugin.manifest();
+    ToolPluginDto {
+        file,
+        sha256,
+        approved: true,
+        key: Some(manifest.key.clone()),
+        display_name: Some(manifest.display_name.clone()),
+        installed: loaded.installed,
+        tool_dir: Some(loaded.plugin.tool_dir()),
+        skills_dir: loaded.detection.skills_dir.clone(),
+        mcp_config_path: loaded.detection.mcp_config_path.clone(),
+        renders_config: loaded.plugin.renders_config(),
+        error: None,
+    }
+}
+
+/// Installed plugin tools, as the custom tools Skills and MCP work with
+pub fn plugin_tools() -> Vec<CustomTool> {
+    PLUGINS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter(|loaded| loaded.installed)
+        .map(|loaded| {
+            let manifest = loaded.plugin.manifest();
+            let renders_config = loaded.plugin.renders_config();
+            CustomTool {
+                key: manifest.key.clone(),
+                display_name: manifest.display_name.clone(),
+                relative_skills_dir: loaded.detection.skills_dir.clone(),
+                relative_detect_dir: loaded.detection.detect_paths.first().cloned(),
+                force_copy: false,
+                preferred_sync_mode: manifest.sync_mode.clone(),
+                mcp_config_path: loaded
+                    .detection
+                    .mcp_config_path
+                    .clone()
+                    .filter(|_| renders_config),
+                mcp_config_format: Some(PLUGIN_CONFIG_FORMAT.to_string()),
+                // Only read by the apply history, which lists servers when
+                // the plugin writes JSON with the usual field
+                mcp_field: Some("mcpServers".to_string()),
+                created_at: 0,
+            }
+        })
+        .collect()
+}
+
+/// Whether `key` is a tool provided by a loaded plugin
+pub fn is_plugin_tool(key: &str) -> bool {
+    find_plugin(key).is_some()
+}
+
+fn find_plugin(key: &str) -> Option<Arc<WasmToolPlugin>> {
+    PLUGINS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|loaded| loaded.plugin.manifest().key == key)
+        .map(|loaded| loaded.plugin.clone())
+}
+
+/// Have the plugin of `tool_key` apply `request` to its MCP config
+pub fn render_plugin_config(
+    tool_key: &str,
+    request: &RenderConfigRequest,
+) -> Result<Option<String>, String> {
+    let plugin =
+        find_plugin(tool_key).ok_or_else(|| format!("Tool plugin {} is not loaded", tool_key))?;
+    plugin.render_config(request).map_err(|e| {
+        format!(
+            "Tool plugin {} failed to render its config: {:#}",
+            tool_key, e
+        )
+    })
+}
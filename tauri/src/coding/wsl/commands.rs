@@ -17,6 +17,7 @@ use crate::coding::runtime_location;
 use crate::db::helpers::{db_delete, db_delete_all, db_get, db_list, db_put};
 use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
+use crate::settings::read_only::ensure_writable;
 use chrono::Local;
 use std::path::Path;
 use tauri::{Emitter, Manager};
@@ -112,6 +113,7 @@ pub async fn wsl_save_config(
     app: tauri::AppHandle,
     config: WSLSyncConfig,
 ) -> Result<(), String> {
+    ensure_writable("wsl_save_config")?;
     // Check if WSL sync is being enabled (was disabled, now enabled)
     let was_enabled = {
         let db = state.db();
@@ -212,6 +214,7 @@ pub async fn wsl_add_file_mapping(
     app: tauri::AppHandle,
     mapping: FileMapping,
 ) -> Result<(), String> {
+    ensure_writable("wsl_add_file_mapping")?;
     validate_file_mapping_cleanup_paths(&mapping)?;
     let mapping_data = adapter::mapping_to_db_value(&mapping);
     state.with_conn(|conn| db_put(conn, DbTable::WslFileMapping, &mapping.id, &mapping_data))?;
@@ -228,6 +231,7 @@ pub async fn wsl_update_file_mapping(
     app: tauri::AppHandle,
     mapping: FileMapping,
 ) -> Result<(), String> {
+    ensure_writable("wsl_update_file_mapping")?;
     validate_file_mapping_cleanup_paths(&mapping)?;
     let mapping_data = adapter::mapping_to_db_value(&mapping);
     state.with_conn(|conn| db_put(conn, DbTable::WslFileMapping, &mapping.id, &mapping_data))?;
@@ -244,6 +248,7 @@ pub async fn wsl_delete_file_mapping(
     app: tauri::AppHandle,
     id: String,
 ) -> Result<(), String> {
+    ensure_writable("wsl_delete_file_mapping")?;
     state.with_conn(|conn| db_delete(conn, DbTable::WslFileMapping, &id).map(|_| ()))?;
 
     let _ = app.emit("wsl-config-changed", ());
@@ -257,6 +262,7 @@ pub async fn wsl_reset_file_mappings(
     state: tauri::State<'_, SqliteDbState>,
     app: tauri::AppHandle,
 ) -> Result<(), String> {
+    ensure_writable("wsl_reset_file_mappings")?;
     state.with_conn(|conn| db_delete_all(conn, DbTable::WslFileMapping).map(|_| ()))?;
 
     let _ = app.emit("wsl-config-changed", ());
@@ -730,6 +736,7 @@ pub async fn wsl_sync(
     module: Option<String>,
     skip_modules: Option<Vec<String>>,
 ) -> Result<SyncResult, String> {
+    ensure_writable("wsl_sync")?;
     let config = wsl_get_config(state.clone()).await?;
 
     let result = do_full_sync(
@@ -755,6 +762,9 @@ pub async fn wsl_sync(
 /// Automatic triggers include startup sync and event-driven sync from
 /// model/MCP/skills changes. Manual sync is intentionally not gated by this.
 pub async fn is_wsl_auto_sync_enabled(state: &SqliteDbState) -> bool {
+    if crate::settings::policy::is_feature_disabled("wsl") {
+        return false;
+    }
     state
         .with_conn(|conn| db_get(conn, DbTable::WslSyncConfig, "config"))
         .ok()
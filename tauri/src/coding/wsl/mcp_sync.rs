@@ -47,6 +47,7 @@ async fn get_file_mappings(state: &SqliteDbState) -> Result<Vec<FileMapping>, St
 
 /// Sync MCP configuration to WSL (called on mcp-changed event)
 pub async fn sync_mcp_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result<(), String> {
+    crate::settings::read_only::ensure_writable("wsl_sync_mcp")?;
     let config = get_wsl_config(state).await?;
 
     if !config.enabled || !config.sync_mcp {
@@ -81,6 +81,7 @@ fn get_all_skill_tool_keys() -> Vec<&'static str> {
 
 /// Sync all skills to WSL (called on skills-changed event)
 pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result<(), String> {
+    crate::settings::read_only::ensure_writable("wsl_sync_skills")?;
     let _sync_guard = SKILLS_WSL_SYNC_LOCK
         .get_or_init(|| Mutex::new(()))
         .lock()
@@ -0,0 +1,887 @@
+//! Per-window command capabilities
+//!
+//! Every command invoked from a webview passes `authorize` before it reaches
+//! its handler (see the `invoke_handler` wrapper in `lib.rs`). Every command
+//! is listed explicitly: read-only commands in `OPEN_COMMANDS`, the rest in
+//! the table of the capability they need, and only the windows listed in
+//! `WINDOW_CAPABILITIES` hold any. A command missing from the tables is
+//! denied for every window, so a new command stays unusable until it is
+//! classified. A webview that is not the main window (a future popup, or a
+//! compromised extra window) can still call the open commands, but it cannot
+//! write anything, launch anything or read tokens. Denied calls fail with a
+//! `COMMAND_DENIED|<command>` error and are logged. Commands of a feature the
+//! group policy disables (see `settings::policy`) are refused for every
+//! window with `POLICY_LOCKED|disabled_feature|<feature>`.
+
+use serde::Serialize;
+use tauri::ipc::Invoke;
+use tauri::Runtime;
+
+pub const COMMAND_DENIED_ERROR_PREFIX: &str = "COMMAND_DENIED|";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Changes skills, their targets or the skills settings
+    SkillsWrite,
+    /// Changes MCP servers, their sync or the MCP settings, or starts a
+    /// server process
+    McpWrite,
+    /// Changes tool configs, app settings or app data, or opens programs,
+    /// folders and connections
+    ConfigWrite,
+    /// Returns tokens, passwords or configs that carry API keys
+    SecretsRead,
+}
+
+const ALL_CAPABILITIES: &[Capability] = &[
+    Capability::SkillsWrite,
+    Capability::McpWrite,
+    Capability::ConfigWrite,
+    Capability::SecretsRead,
+];
+
+/// Window label -> capabilities; windows not listed hold none
+const WINDOW_CAPABILITIES: &[(&str, &[Capability])] = &[("main", ALL_CAPABILITIES)];
+
+/// Commands every window may invoke: they only read, and return no secrets
+const OPEN_COMMANDS: &[&str] = &[
+    "check_for_updates",
+    "check_oh_my_openagent_config_exists",
+    "check_oh_my_opencode_slim_config_exists",
+    "config_snapshot_get_settings",
+    "config_snapshot_list",
+    "devcontainer_get_selection",
+    "get_auto_launch_status",
+    "get_claude_config_path",
+    "get_claude_onboarding_status",
+    "get_claude_plugin_runtime_status",
+    "get_claude_plugin_status",
+    "get_claude_root_path_info",
+    "get_codex_config_dir_path",
+    "get_codex_config_file_path",
+    "get_codex_history_sync_status",
+    "get_codex_plugin_runtime_status",
+    "get_codex_root_path_info",
+    "get_database_encryption_status",
+    "get_database_path",
+    "get_gemini_cli_config_path",
+    "get_gemini_cli_root_path_info",
+    "get_grok_config_dir_path",
+    "get_grok_config_file_path",
+    "get_grok_official_account_auth_status",
+    "get_grok_plugin_details",
+    "get_grok_plugin_runtime_status",
+    "get_grok_root_path_info",
+    "get_model_pricing_list",
+    "get_oh_my_openagent_config_path_info",
+    "get_oh_my_openagent_global_config",
+    "get_oh_my_openagent_upgrade_status",
+    "get_oh_my_opencode_slim_config_path_info",
+    "get_oh_my_opencode_slim_global_config",
+    "get_openclaw_agents_defaults",
+    "get_openclaw_config_path",
+    "get_openclaw_config_path_info",
+    "get_openclaw_tools",
+    "get_opencode_auth_config_path",
+    "get_opencode_config_path",
+    "get_opencode_config_path_info",
+    "get_opencode_free_models",
+    "get_opencode_unified_models",
+    "get_pending_update_status",
+    "get_pi_root_path_info",
+    "get_pi_settings_config",
+    "get_policy_status",
+    "get_portable_export_status",
+    "get_provider_models",
+    "get_read_only_status",
+    "get_snapshot_storage_report",
+    "get_status_summary",
+    "get_tool_session_detail",
+    "get_tool_subagent_session_detail",
+    "has_all_api_hub_extension",
+    "has_codex_unified_history_backup",
+    "image_get_workspace",
+    "image_list_jobs",
+    "list_backup_file_filter_path_options",
+    "list_claude_installed_plugins",
+    "list_claude_known_marketplaces",
+    "list_claude_marketplace_plugins",
+    "list_claude_prompt_configs",
+    "list_codex_installed_plugins",
+    "list_codex_marketplace_plugins",
+    "list_codex_marketplaces",
+    "list_codex_official_accounts",
+    "list_codex_plugin_workspace_roots",
+    "list_codex_prompt_configs",
+    "list_gemini_cli_extensions",
+    "list_gemini_cli_official_accounts",
+    "list_gemini_cli_prompt_configs",
+    "list_grok_installed_plugins",
+    "list_grok_marketplace_plugins",
+    "list_grok_marketplaces",
+    "list_grok_official_accounts",
+    "list_grok_plugin_workspace_roots",
+    "list_grok_prompt_configs",
+    "list_oh_my_openagent_configs",
+    "list_oh_my_opencode_slim_configs",
+    "list_opencode_favorite_plugins",
+    "list_opencode_markdown_agents",
+    "list_opencode_prompt_configs",
+    "list_pi_extensions",
+    "list_pi_prompt_configs",
+    "list_tool_session_paths",
+    "list_tool_session_subagents",
+    "list_tool_sessions",
+    "list_webdav_backups",
+    "load_cached_gateway_provider_profiles",
+    "load_cached_preset_models",
+    "mcp_check_prerequisites",
+    "mcp_check_tool_collisions",
+    "mcp_get_apply_history",
+    "mcp_get_gateway_status",
+    "mcp_get_limit_add_more_to_preferred_tools",
+    "mcp_get_preferred_tools",
+    "mcp_get_show_in_tray",
+    "mcp_get_sync_disabled_to_opencode",
+    "mcp_get_tools",
+    "mcp_list_favorites",
+    "mcp_list_servers",
+    "mcp_list_templates",
+    "mcp_resolve_package_versions",
+    "mcp_scan_servers",
+    "normalize_backup_custom_entry_path",
+    "proxy_gateway_check_port_available",
+    "proxy_gateway_cli_status",
+    "proxy_gateway_cli_statuses",
+    "proxy_gateway_data_source_breakdown",
+    "proxy_gateway_get_settings",
+    "proxy_gateway_health_check",
+    "proxy_gateway_model_health_entries",
+    "proxy_gateway_model_stats",
+    "proxy_gateway_provider_stats",
+    "proxy_gateway_request_logs",
+    "proxy_gateway_status",
+    "proxy_gateway_stop_preflight",
+    "proxy_gateway_usage_summary",
+    "proxy_gateway_usage_summary_by_cli",
+    "proxy_gateway_usage_trends",
+    "read_magic_context_config",
+    "skills_analyze_skill",
+    "skills_audit_library",
+    "skills_check_compatibility",
+    "skills_check_custom_tool_path",
+    "skills_check_mirror_updates",
+    "skills_check_new_tools",
+    "skills_get_all_tags",
+    "skills_get_auto_adopt_onboarding",
+    "skills_get_auto_resync_copies",
+    "skills_get_auto_sync_interval",
+    "skills_get_central_repo_path",
+    "skills_get_central_repo_path_status",
+    "skills_get_collections",
+    "skills_get_conflict_overview",
+    "skills_get_consolidation_candidates",
+    "skills_get_context_budget",
+    "skills_get_copy_ignore_patterns",
+    "skills_get_custom_tools",
+    "skills_get_default_central_repo_path",
+    "skills_get_default_skill_sets",
+    "skills_get_default_view_mode",
+    "skills_get_dictionary_localizations",
+    "skills_get_disallowed_licenses",
+    "skills_get_duplicate_content",
+    "skills_get_git_cache_cleanup_days",
+    "skills_get_git_cache_path",
+    "skills_get_git_cache_ttl_secs",
+    "skills_get_groups",
+    "skills_get_linked_folder_changes",
+    "skills_get_locally_modified",
+    "skills_get_managed_skills",
+    "skills_get_mirror_sources",
+    "skills_get_mirror_targets",
+    "skills_get_name_collision_policies",
+    "skills_get_onboarding_plan",
+    "skills_get_preferred_tools",
+    "skills_get_relative_symlinks",
+    "skills_get_repos",
+    "skills_get_rotation_policies",
+    "skills_get_show_in_tray",
+    "skills_get_source_mirror",
+    "skills_get_source_priority",
+    "skills_get_startup_check_summary",
+    "skills_get_storage_report",
+    "skills_get_sync_target_whitelist",
+    "skills_get_tool_status",
+    "skills_get_variants_per_skill",
+    "skills_list_file_skill_tools",
+    "skills_list_git_collections",
+    "skills_list_git_skills",
+    "skills_list_local_skills",
+    "skills_list_mirror_skills",
+    "skills_list_sync_journal",
+    "skills_list_sync_runs",
+    "skills_list_variants",
+    "skills_preview_central_repo_path",
+    "skills_preview_inventory_import",
+    "skills_preview_inventory_import_file",
+    "skills_preview_pull_from_target",
+    "skills_preview_sync",
+    "skills_preview_target_conflict",
+    "skills_preview_tool_load",
+    "skills_preview_update",
+    "skills_query",
+    "skills_rank_quality",
+    "skills_scan_central_repo",
+    "skills_scan_external_changes",
+    "skills_scan_extra_roots",
+    "skills_scan_stale_tool_artifacts",
+    "skills_simulate_adoption",
+    "skills_suggest_tags",
+    "ssh_get_default_mappings",
+    "ssh_get_status",
+    "ssh_test_local_path",
+    "tools_get_post_sync_hooks",
+    "tools_list_plugins",
+    "tools_preview_key_migration",
+    "validate_grok_plugin",
+    "wsl_check_distro",
+    "wsl_detect",
+    "wsl_get_config",
+    "wsl_get_default_mappings",
+    "wsl_get_distro_state",
+    "wsl_get_status",
+    "wsl_test_path",
+];
+
+const SKILLS_WRITE_COMMANDS: &[&str] = &[
+    "skills_accept_external_change",
+    "skills_add_custom_tool",
+    "skills_add_mirror_source",
+    "skills_add_repo",
+    "skills_adopt_central_repo_skills",
+    "skills_adopt_onboarding_plan",
+    "skills_apply_central_repo_path_change",
+    "skills_apply_default_skill_set",
+    "skills_apply_inventory_import",
+    "skills_apply_inventory_import_file",
+    "skills_archive_skill",
+    "skills_assign_variant",
+    "skills_batch_update_group",
+    "skills_batch_update_tags",
+    "skills_clean_dangling_links",
+    "skills_clear_git_cache",
+    "skills_consolidate_copies",
+    "skills_convert_target_mode",
+    "skills_create_custom_tool_path",
+    "skills_create_variant",
+    "skills_delete_collection",
+    "skills_delete_group",
+    "skills_delete_managed",
+    "skills_delete_mirror_target",
+    "skills_delete_rotation_policy",
+    "skills_delete_variant",
+    "skills_export_collections_file",
+    "skills_export_inventory",
+    "skills_export_inventory_file",
+    "skills_export_onboarding_plan",
+    "skills_fill_localizations",
+    "skills_fix_library_audit_issues",
+    "skills_import_collections_file",
+    "skills_import_existing",
+    "skills_init_default_repos",
+    "skills_install_archive_url",
+    "skills_install_from_mirror",
+    "skills_install_git",
+    "skills_install_git_selection",
+    "skills_install_local",
+    "skills_install_local_selection",
+    "skills_publish_mirror",
+    "skills_pull_from_target",
+    "skills_purge_sync_trash",
+    "skills_rebuild_database",
+    "skills_recover_targets_from_markers",
+    "skills_remove_custom_tool",
+    "skills_remove_file_skill",
+    "skills_remove_mirror_source",
+    "skills_remove_repo",
+    "skills_render_to_project",
+    "skills_reorder",
+    "skills_repair_central_repo_skill",
+    "skills_repair_links",
+    "skills_request_confirmation",
+    "skills_resync_all",
+    "skills_revert_external_change",
+    "skills_rollback_last_sync",
+    "skills_rotate_group_now",
+    "skills_run_skill_sync_now",
+    "skills_save_collection",
+    "skills_save_copy_ignore_patterns",
+    "skills_save_default_skill_sets",
+    "skills_save_disallowed_licenses",
+    "skills_save_group",
+    "skills_save_mirror_target",
+    "skills_save_name_collision_policies",
+    "skills_save_rotation_policy",
+    "skills_save_source_mirror",
+    "skills_set_auto_adopt_onboarding",
+    "skills_set_auto_resync_copies",
+    "skills_set_auto_sync_interval",
+    "skills_set_central_repo_path",
+    "skills_set_collection_tool_enabled",
+    "skills_set_compatibility",
+    "skills_set_context_budget",
+    "skills_set_default_view_mode",
+    "skills_set_git_cache_cleanup_days",
+    "skills_set_linked_folder_tracking",
+    "skills_set_localizations",
+    "skills_set_management_enabled",
+    "skills_set_preferred_tools",
+    "skills_set_relative_symlinks",
+    "skills_set_show_in_tray",
+    "skills_set_source_priority",
+    "skills_set_sync_target_whitelist",
+    "skills_set_target_path_rules",
+    "skills_set_variants_per_skill",
+    "skills_set_write_protected",
+    "skills_swap_variants",
+    "skills_sweep_stale_tool_artifacts",
+    "skills_sync_to_tool",
+    "skills_unarchive_skill",
+    "skills_unsync_from_tool",
+    "skills_update_managed",
+    "skills_update_metadata",
+    "skills_write_file_skill",
+];
+
+const MCP_WRITE_COMMANDS: &[&str] = &[
+    "mcp_add_custom_tool",
+    "mcp_add_server_from_template",
+    "mcp_clear_traffic",
+    "mcp_create_server",
+    "mcp_delete_favorite",
+    "mcp_delete_server",
+    "mcp_health_check",
+    "mcp_import_from_tool",
+    "mcp_init_default_favorites",
+    "mcp_remove_custom_tool",
+    "mcp_reorder_servers",
+    "mcp_set_limit_add_more_to_preferred_tools",
+    "mcp_set_preferred_tools",
+    "mcp_set_remote_only_tools",
+    "mcp_set_show_in_tray",
+    "mcp_set_sync_disabled_to_opencode",
+    "mcp_set_traffic_logging",
+    "mcp_sync_all",
+    "mcp_sync_to_tool",
+    "mcp_toggle_tool",
+    "mcp_update_metadata",
+    "mcp_update_server",
+    "mcp_upsert_favorite",
+];
+
+const CONFIG_WRITE_COMMANDS: &[&str] = &[
+    "add_claude_marketplace",
+    "add_codex_plugin_workspace_root",
+    "add_grok_plugin_workspace_root",
+    "add_opencode_favorite_plugin",
+    "apply_claude_config",
+    "apply_claude_onboarding_skip",
+    "apply_claude_plugin_config",
+    "apply_claude_prompt_config",
+    "apply_codex_config",
+    "apply_codex_official_account",
+    "apply_codex_prompt_config",
+    "apply_gemini_cli_official_account",
+    "apply_gemini_cli_prompt_config",
+    "apply_grok_official_account",
+    "apply_grok_prompt_config",
+    "apply_oh_my_openagent_config",
+    "apply_oh_my_opencode_slim_config",
+    "apply_opencode_prompt_config",
+    "apply_pi_prompt_config",
+    "backup_codex_history",
+    "backup_database",
+    "backup_openclaw_config",
+    "backup_opencode_config",
+    "backup_to_webdav",
+    "cancel_codex_official_account_device_auth",
+    "cancel_grok_official_account_device_auth",
+    "clear_claude_onboarding_skip",
+    "clear_oh_my_openagent_applied_config",
+    "clear_oh_my_opencode_slim_applied_config",
+    "config_snapshot_create",
+    "config_snapshot_delete",
+    "config_snapshot_restore",
+    "config_snapshot_save_settings",
+    "create_claude_prompt_config",
+    "create_claude_provider",
+    "create_codex_prompt_config",
+    "create_codex_provider",
+    "create_gemini_cli_prompt_config",
+    "create_gemini_cli_provider",
+    "create_grok_prompt_config",
+    "create_grok_provider",
+    "create_magic_context_config",
+    "create_oh_my_openagent_config",
+    "create_oh_my_opencode_slim_config",
+    "create_opencode_prompt_config",
+    "create_pi_prompt_config",
+    "delete_claude_prompt_config",
+    "delete_claude_provider",
+    "delete_codex_official_account",
+    "delete_codex_prompt_config",
+    "delete_codex_provider",
+    "delete_gemini_cli_official_account",
+    "delete_gemini_cli_prompt_config",
+    "delete_gemini_cli_provider",
+    "delete_grok_official_account",
+    "delete_grok_prompt_config",
+    "delete_grok_provider",
+    "delete_model_pricing",
+    "delete_oh_my_openagent_config",
+    "delete_oh_my_opencode_slim_config",
+    "delete_opencode_favorite_plugin",
+    "delete_opencode_favorite_provider",
+    "delete_opencode_markdown_agent",
+    "delete_opencode_prompt_config",
+    "delete_pi_prompt_config",
+    "delete_pi_runtime_provider",
+    "delete_tool_session",
+    "delete_tool_sessions",
+    "delete_webdav_backup",
+    "devcontainer_generate",
+    "devcontainer_save_selection",
+    "disable_claude_plugin_user_scope",
+    "disable_codex_plugin",
+    "disable_grok_plugin",
+    "download_update_on_quit",
+    "enable_claude_plugin_user_scope",
+    "enable_codex_plugin",
+    "enable_codex_plugins_feature",
+    "enable_grok_plugin",
+    "export_portable_data",
+    "export_tool_session",
+    "export_tool_sessions",
+    "extract_claude_common_config_from_current_file",
+    "extract_codex_common_config_from_current_file",
+    "extract_gemini_cli_common_config_from_current_file",
+    "extract_grok_common_config_from_current_file",
+    "fetch_codex_official_models",
+    "fetch_gemini_cli_official_models",
+    "fetch_grok_official_models",
+    "fetch_provider_models",
+    "fetch_remote_gateway_provider_profiles",
+    "fetch_remote_model_pricing",
+    "fetch_remote_preset_models",
+    "image_create_job",
+    "image_delete_channel",
+    "image_delete_job",
+    "image_export_asset",
+    "image_reorder_channels",
+    "image_reveal_assets_dir",
+    "image_update_channel",
+    "import_portable_data",
+    "import_tool_session",
+    "install_claude_plugin_user_scope",
+    "install_codex_plugin",
+    "install_grok_plugin",
+    "install_pi_extension",
+    "install_update",
+    "logout_grok_official_runtime",
+    "open_app_data_dir",
+    "open_existing_folder",
+    "open_folder",
+    "proxy_gateway_disengage_failover",
+    "proxy_gateway_engage_failover",
+    "proxy_gateway_engage_single",
+    "proxy_gateway_export_request_log_detail",
+    "proxy_gateway_import_session_usage",
+    "proxy_gateway_restart",
+    "proxy_gateway_restore_cli_direct",
+    "proxy_gateway_start",
+    "proxy_gateway_stop",
+    "proxy_gateway_switch_primary_provider",
+    "proxy_gateway_test_provider_model_connectivity",
+    "proxy_gateway_update_settings",
+    "refresh_codex_official_account_limits",
+    "refresh_gemini_cli_official_account_limits",
+    "refresh_grok_official_account",
+    "refresh_tray_menu",
+    "remove_claude_marketplace",
+    "remove_codex_plugin_workspace_root",
+    "remove_grok_plugin_workspace_root",
+    "rename_tool_session",
+    "reorder_claude_prompt_configs",
+    "reorder_claude_providers",
+    "reorder_codex_prompt_configs",
+    "reorder_codex_providers",
+    "reorder_gemini_cli_prompt_configs",
+    "reorder_gemini_cli_providers",
+    "reorder_grok_prompt_configs",
+    "reorder_grok_providers",
+    "reorder_oh_my_openagent_configs",
+    "reorder_oh_my_opencode_slim_configs",
+    "reorder_opencode_prompt_configs",
+    "reorder_pi_prompt_configs",
+    "repair_codex_providers",
+    "restart_app",
+    "restore_codex_unified_session_history",
+    "restore_database",
+    "restore_from_webdav",
+    "restore_latest_codex_history_backup",
+    "reveal_claude_config_folder",
+    "reveal_codex_config_folder",
+    "reveal_gemini_cli_config_folder",
+    "reveal_grok_config_folder",
+    "run_magic_context_doctor",
+    "run_portable_export_now",
+    "save_claude_common_config",
+    "save_claude_local_config",
+    "save_claude_local_prompt_config",
+    "save_codex_common_config",
+    "save_codex_local_config",
+    "save_codex_local_prompt_config",
+    "save_codex_official_local_account",
+    "save_gemini_cli_common_config",
+    "save_gemini_cli_local_config",
+    "save_gemini_cli_local_prompt_config",
+    "save_gemini_cli_official_local_account",
+    "save_grok_common_config",
+    "save_grok_local_config",
+    "save_grok_local_prompt_config",
+    "save_grok_official_local_account",
+    "save_magic_context_config",
+    "save_oh_my_openagent_global_config",
+    "save_oh_my_openagent_local_config",
+    "save_oh_my_opencode_slim_global_config",
+    "save_oh_my_opencode_slim_local_config",
+    "save_openclaw_common_config",
+    "save_openclaw_config",
+    "save_opencode_common_config",
+    "save_opencode_config",
+    "save_opencode_local_prompt_config",
+    "save_opencode_markdown_agent",
+    "save_pi_auth_provider",
+    "save_pi_local_prompt_config",
+    "save_pi_model_settings",
+    "save_pi_models_provider",
+    "save_pi_other_settings",
+    "save_pi_settings_config",
+    "save_settings",
+    "select_claude_provider",
+    "select_codex_provider",
+    "select_gemini_cli_provider",
+    "select_grok_provider",
+    "set_auto_launch",
+    "set_claude_marketplace_auto_update",
+    "set_claude_plugins_user_scope_enabled",
+    "set_codex_installed_plugins_enabled",
+    "set_codex_unified_session_history",
+    "set_database_encryption",
+    "set_grok_installed_plugins_enabled",
+    "set_openclaw_agents_defaults",
+    "set_openclaw_env",
+    "set_openclaw_tools",
+    "set_window_background_color",
+    "ssh_add_file_mapping",
+    "ssh_create_connection",
+    "ssh_delete_connection",
+    "ssh_delete_file_mapping",
+    "ssh_reset_file_mappings",
+    "ssh_save_config",
+    "ssh_set_active_connection",
+    "ssh_sync",
+    "ssh_test_connection",
+    "ssh_update_connection",
+    "ssh_update_file_mapping",
+    "start_codex_official_account_device_auth",
+    "start_codex_official_account_oauth",
+    "start_gemini_cli_official_account_oauth",
+    "start_grok_official_account_device_auth",
+    "sync_codex_history",
+    "test_provider_model_connectivity",
+    "test_proxy_connection",
+    "test_webdav_connection",
+    "toggle_claude_code_provider_disabled",
+    "toggle_codex_provider_disabled",
+    "toggle_gemini_cli_provider_disabled",
+    "toggle_grok_provider_disabled",
+    "toggle_oh_my_openagent_config_disabled",
+    "toggle_oh_my_opencode_slim_config_disabled",
+    "tools_approve_plugin",
+    "tools_migrate_keys",
+    "tools_reload_plugins",
+    "tools_save_post_sync_hooks",
+    "tools_test_post_sync_hook",
+    "uninstall_claude_plugin_user_scope",
+    "uninstall_codex_plugin",
+    "uninstall_grok_plugin",
+    "uninstall_pi_extension",
+    "update_claude_marketplace",
+    "update_claude_plugin_user_scope",
+    "update_claude_prompt_config",
+    "update_claude_provider",
+    "update_codex_prompt_config",
+    "update_codex_provider",
+    "update_gemini_cli_prompt_config",
+    "update_gemini_cli_provider",
+    "update_grok_plugin",
+    "update_grok_plugin_marketplace",
+    "update_grok_prompt_config",
+    "update_grok_provider",
+    "update_oh_my_openagent_config",
+    "update_oh_my_opencode_slim_config",
+    "update_opencode_prompt_config",
+    "update_pi_extensions",
+    "update_pi_prompt_config",
+    "upgrade_oh_my_openagent_legacy_setup",
+    "upsert_model_pricing",
+    "upsert_opencode_favorite_provider",
+    "wsl_add_file_mapping",
+    "wsl_delete_file_mapping",
+    "wsl_open_folder",
+    "wsl_open_terminal",
+    "wsl_reset_file_mappings",
+    "wsl_save_config",
+    "wsl_sync",
+    "wsl_update_file_mapping",
+];
+
+const SECRET_COMMANDS: &[&str] = &[
+    "config_snapshot_diff",
+    "copy_codex_official_account_token",
+    "copy_gemini_cli_official_account_token",
+    "get_all_api_hub_provider_models",
+    "get_claude_common_config",
+    "get_codex_common_config",
+    "get_gemini_cli_common_config",
+    "get_grok_common_config",
+    "get_openclaw_common_config",
+    "get_openclaw_env",
+    "get_opencode_auth_providers",
+    "get_opencode_common_config",
+    "get_settings",
+    "image_list_channels",
+    "list_claude_all_api_hub_providers",
+    "list_claude_providers",
+    "list_codex_all_api_hub_providers",
+    "list_codex_providers",
+    "list_gemini_cli_providers",
+    "list_grok_all_api_hub_providers",
+    "list_grok_providers",
+    "list_openclaw_all_api_hub_providers",
+    "list_opencode_all_api_hub_providers",
+    "list_opencode_favorite_providers",
+    "mcp_get_apply_backup",
+    "mcp_get_traffic",
+    "proxy_gateway_request_log_detail",
+    "read_claude_settings",
+    "read_codex_settings",
+    "read_gemini_cli_settings",
+    "read_grok_settings",
+    "read_openclaw_config",
+    "read_opencode_config",
+    "read_pi_runtime_config",
+    "resolve_claude_all_api_hub_providers",
+    "resolve_codex_all_api_hub_providers",
+    "resolve_grok_all_api_hub_providers",
+    "resolve_openclaw_all_api_hub_providers",
+    "resolve_opencode_all_api_hub_providers",
+    "ssh_get_config",
+    "ssh_list_connections",
+];
+
+/// Access a command needs; every command of the invoke handler must be listed
+/// in exactly one of the tables above
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommandAccess {
+    Open,
+    Requires(Capability),
+    /// Not in any table: denied for every window
+    Unlisted,
+}
+
+pub fn command_access(command: &str) -> CommandAccess {
+    let tables: [(&[&str], Option<Capability>); 5] = [
+        (OPEN_COMMANDS, None),
+        (SKILLS_WRITE_COMMANDS, Some(Capability::SkillsWrite)),
+        (MCP_WRITE_COMMANDS, Some(Capability::McpWrite)),
+        (CONFIG_WRITE_COMMANDS, Some(Capability::ConfigWrite)),
+        (SECRET_COMMANDS, Some(Capability::SecretsRead)),
+    ];
+    for (commands, capability) in tables {
+        if commands.contains(&command) {
+            return capability.map_or(CommandAccess::Open, CommandAccess::Requires);
+        }
+    }
+    CommandAccess::Unlisted
+}
+
+pub fn window_capabilities(window: &str) -> &'static [Capability] {
+    WINDOW_CAPABILITIES
+        .iter()
+        .find(|(label, _)| *label == window)
+        .map(|(_, capabilities)| *capabilities)
+        .unwrap_or(&[])
+}
+
+/// Check that the webview `window` may invoke `command`
+pub fn authorize(window: &str, command: &str) -> Result<(), String> {
+    crate::settings::policy::ensure_command_allowed(command)?;
+    let capability = match command_access(command) {
+        CommandAccess::Open => return Ok(()),
+        CommandAccess::Requires(capability) => capability,
+        CommandAccess::Unlisted => {
+            log::warn!(
+                "Denied unlisted command '{}' from window '{}'",
+                command,
+                window
+            );
+            return Err(format!("{COMMAND_DENIED_ERROR_PREFIX}{command}"));
+        }
+    };
+    if window_capabilities(window).contains(&capability) {
+        return Ok(());
+    }
+    log::warn!(
+        "Denied command '{}' from window '{}' (needs {:?})",
+        command,
+        window,
+        capability
+    );
+    Err(format!("{COMMAND_DENIED_ERROR_PREFIX}{command}"))
+}
+
+/// Wrap the app's invoke handler so every command passes `authorize` first
+pub fn guard<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let window = invoke.message.webview_ref().label().to_string();
+        if let Err(error) = authorize(&window, invoke.message.command()) {
+            invoke.resolver.reject(error);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Command names of the `generate_handler!` list in `lib.rs`
+    fn registered_commands() -> Vec<&'static str> {
+        let source = include_str!("lib.rs");
+        let start = source.find("generate_handler![").unwrap() + "generate_handler![".len();
+        let end = start + source[start..].find(']').unwrap();
+        source[start..end]
+            .lines()
+            .map(|line| line.split("//").next().unwrap_or_default())
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(|path| path.rsplit("::").next().unwrap_or(path))
+            .collect()
+    }
+
+    #[test]
+    fn every_registered_command_is_listed_exactly_once() {
+        let registered = registered_commands();
+        assert!(registered.len() > 100);
+        let tables = [
+            OPEN_COMMANDS,
+            SKILLS_WRITE_COMMANDS,
+            MCP_WRITE_COMMANDS,
+            CONFIG_WRITE_COMMANDS,
+            SECRET_COMMANDS,
+        ];
+        for command in &registered {
+            let listed = tables
+                .iter()
+                .filter(|commands| commands.contains(command))
+                .count();
+            assert_eq!(listed, 1, "{command} must be listed exactly once");
+        }
+        for command in tables.iter().flat_map(|commands| commands.iter()) {
+            assert!(
+                registered.contains(command),
+                "{command} is listed but not registered"
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_commands_explicitly() {
+        assert_eq!(
+            command_access("skills_get_managed_skills"),
+            CommandAccess::Open
+        );
+        assert_eq!(
+            command_access("skills_sync_to_tool"),
+            CommandAccess::Requires(Capability::SkillsWrite)
+        );
+        assert_eq!(
+            command_access("mcp_delete_server"),
+            CommandAccess::Requires(Capability::McpWrite)
+        );
+        assert_eq!(
+            command_access("read_codex_settings"),
+            CommandAccess::Requires(Capability::SecretsRead)
+        );
+        for command in [
+            "config_snapshot_diff",
+            "get_claude_common_config",
+            "get_openclaw_env",
+            "mcp_get_apply_backup",
+            "mcp_get_traffic",
+            "proxy_gateway_request_log_detail",
+        ] {
+            assert_eq!(
+                command_access(command),
+                CommandAccess::Requires(Capability::SecretsRead),
+                "{command}"
+            );
+        }
+        // Starts the server's process
+        assert_eq!(
+            command_access("mcp_health_check"),
+            CommandAccess::Requires(Capability::McpWrite)
+        );
+        for command in [
+            "tools_save_post_sync_hooks",
+            "tools_reload_plugins",
+            "tools_approve_plugin",
+            "config_snapshot_restore",
+            "config_snapshot_delete",
+            "import_portable_data",
+            "devcontainer_generate",
+            "open_folder",
+        ] {
+            assert_eq!(
+                command_access(command),
+                CommandAccess::Requires(Capability::ConfigWrite),
+                "{command}"
+            );
+        }
+        assert_eq!(
+            command_access("skills_new_command"),
+            CommandAccess::Unlisted
+        );
+    }
+
+    #[test]
+    fn only_the_main_window_holds_capabilities() {
+        assert!(authorize("main", "skills_delete_managed").is_ok());
+        assert!(authorize("main", "copy_codex_official_account_token").is_ok());
+        assert!(authorize("preview", "skills_get_managed_skills").is_ok());
+        assert_eq!(
+            authorize("preview", "mcp_sync_all").unwrap_err(),
+            "COMMAND_DENIED|mcp_sync_all"
+        );
+        assert!(authorize("preview", "read_claude_settings").is_err());
+        assert!(authorize("preview", "config_snapshot_restore").is_err());
+        assert!(authorize("main", "unknown_command").is_err());
+    }
+}
@@ -1,9 +1,11 @@
 pub mod backup;
 pub mod change_hook;
+pub mod encryption;
 pub mod health;
 pub mod helpers;
 pub mod migrations;
 pub mod model_pricing_seed;
+pub mod portable;
 pub mod schema;
 pub mod sqlite_state;
 pub mod surreal_import;
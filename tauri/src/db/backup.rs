@@ -2,10 +2,20 @@ use std::path::Path;
 
 use rusqlite::Connection;
 
-use super::health;
+use super::{encryption, health};
 
+/// Copy the database to `backup_path`; an encrypted database stays encrypted
+/// with the same key
 pub fn backup_to_path(conn: &Connection, backup_path: &Path) -> Result<(), String> {
     health::checkpoint_truncate(conn)?;
+    if encryption::is_encrypted_connection(conn) {
+        return encryption::export_database(conn, backup_path, None).map_err(|error| {
+            format!(
+                "Failed to create SQLite backup {}: {error}",
+                backup_path.display()
+            )
+        });
+    }
     conn.backup(rusqlite::MAIN_DB, backup_path, None)
         .map_err(|error| {
             format!(
@@ -15,6 +25,57 @@ pub fn backup_to_path(conn: &Connection, backup_path: &Path) -> Result<(), Strin
         })
 }
 
+/// Replace the database content with the snapshot at `snapshot_path`. The
+/// snapshot may be plain or encrypted with the keychain key (`backup_to_path`
+/// of an encrypted database); it is converted to the database's own state
+/// first, as SQLCipher cannot copy pages between differently keyed files.
+pub fn restore_from_path(conn: &mut Connection, snapshot_path: &Path) -> Result<(), String> {
+    let snapshot_encrypted = encryption::is_encrypted_file(snapshot_path);
+    let database_encrypted = encryption::is_encrypted_connection(conn);
+    if !snapshot_encrypted && !database_encrypted {
+        return conn
+            .restore(
+                rusqlite::MAIN_DB,
+                snapshot_path,
+                None::<fn(rusqlite::backup::Progress)>,
+            )
+            .map_err(|error| format!("Failed to restore SQLite backup snapshot: {error}"));
+    }
+
+    let key = encryption::load_key()?.ok_or_else(|| {
+        if snapshot_encrypted {
+            "The backup is encrypted, but no database key was found in the system keychain"
+                .to_string()
+        } else {
+            "No database key was found in the system keychain".to_string()
+        }
+    })?;
+    let mut converted_path = snapshot_path.as_os_str().to_os_string();
+    converted_path.push(".converted");
+    let converted_path = std::path::PathBuf::from(converted_path);
+    let result = (|| {
+        let snapshot = Connection::open(snapshot_path)
+            .map_err(|error| format!("Failed to open SQLite backup snapshot: {error}"))?;
+        if snapshot_encrypted {
+            encryption::apply_key(&snapshot, &key).map_err(|_| {
+                "The backup is encrypted with a key this installation does not have".to_string()
+            })?;
+        }
+        let target_key = if database_encrypted { key.as_str() } else { "" };
+        encryption::export_database(&snapshot, &converted_path, Some(target_key))?;
+        let source = Connection::open(&converted_path)
+            .map_err(|error| format!("Failed to open SQLite backup snapshot: {error}"))?;
+        if database_encrypted {
+            encryption::apply_key(&source, &key)?;
+        }
+        rusqlite::backup::Backup::new(&source, conn)
+            .and_then(|backup| backup.run_to_completion(256, std::time::Duration::ZERO, None))
+            .map_err(|error| format!("Failed to restore SQLite backup snapshot: {error}"))
+    })();
+    let _ = std::fs::remove_file(&converted_path);
+    result
+}
+
 pub fn vacuum(conn: &Connection) -> Result<(), String> {
     conn.execute_batch("VACUUM;")
         .map_err(|error| format!("Failed to vacuum SQLite database: {error}"))
@@ -0,0 +1,372 @@
+//! Optional at-rest encryption of the SQLite database (SQLCipher).
+//!
+//! The key is a random passphrase kept in the OS keychain (Keychain,
+//! Credential Manager, Secret Service); it never touches the settings or the
+//! database itself. Whether a file is encrypted is read from its header, so a
+//! plain database keeps opening without any keychain access.
+//!
+//! Encrypting or decrypting is requested with a flag file next to the
+//! database and carried out by the next `SqliteDbState::open`, before the
+//! connection is handed to the app: the database is exported into a converted
+//! copy which then replaces it. Nothing written between the request and the
+//! restart is lost that way.
+//!
+//! SQLCipher and the keychain are behind the `sqlcipher` feature. Without it
+//! the app cannot encrypt, and refuses to open an encrypted database instead
+//! of treating it as corrupt.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::{health, migrations};
+
+#[cfg(feature = "sqlcipher")]
+const KEYRING_SERVICE: &str = "ai-toolbox";
+#[cfg(feature = "sqlcipher")]
+const KEYRING_USER: &str = "sqlite-database-key";
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+const REQUEST_SUFFIX: &str = "encryption-request";
+const CONVERTING_SUFFIX: &str = "converting";
+/// Built with SQLCipher and keychain support
+pub const AVAILABLE: bool = cfg!(feature = "sqlcipher");
+const UNAVAILABLE_ERROR: &str = "This build has no database encryption support (SQLCipher)";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EncryptionRequest {
+    Encrypt,
+    Decrypt,
+}
+
+impl EncryptionRequest {
+    fn as_str(self) -> &'static str {
+        match self {
+            EncryptionRequest::Encrypt => "encrypt",
+            EncryptionRequest::Decrypt => "decrypt",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "encrypt" => Some(EncryptionRequest::Encrypt),
+            "decrypt" => Some(EncryptionRequest::Decrypt),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DatabaseEncryptionStatus {
+    pub available: bool,
+    pub encrypted: bool,
+    /// A key for the database is stored in the OS keychain
+    pub key_stored: bool,
+    /// `encrypt` / `decrypt` waiting for the next start
+    pub pending: Option<String>,
+}
+
+fn sibling_path(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.as_os_str().to_os_string();
+    name.push(format!(".{suffix}"));
+    PathBuf::from(name)
+}
+
+#[cfg(feature = "sqlcipher")]
+fn keyring_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|error| format!("Failed to access the system keychain: {error}"))
+}
+
+#[cfg(feature = "sqlcipher")]
+pub fn load_key() -> Result<Option<String>, String> {
+    match keyring_entry()?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(format!(
+            "Failed to read the database key from the system keychain: {error}"
+        )),
+    }
+}
+
+#[cfg(feature = "sqlcipher")]
+fn store_key(key: &str) -> Result<(), String> {
+    keyring_entry()?.set_password(key).map_err(|error| {
+        format!("Failed to store the database key in the system keychain: {error}")
+    })
+}
+
+#[cfg(feature = "sqlcipher")]
+fn delete_key() -> Result<(), String> {
+    match keyring_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) => Err(format!(
+            "Failed to remove the database key from the system keychain: {error}"
+        )),
+    }
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+pub fn load_key() -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn store_key(_key: &str) -> Result<(), String> {
+    Err(UNAVAILABLE_ERROR.to_string())
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn delete_key() -> Result<(), String> {
+    Ok(())
+}
+
+fn generate_key() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// Whether `path` is an existing database file without the plain SQLite header
+pub fn is_encrypted_file(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    match fs::File::open(path) {
+        Ok(mut file) => {
+            use std::io::Read;
+            file.read_exact(&mut header).is_ok() && &header != SQLITE_HEADER
+        }
+        Err(_) => false,
+    }
+}
+
+pub fn is_encrypted_connection(conn: &Connection) -> bool {
+    conn.path()
+        .filter(|path| !path.is_empty())
+        .is_some_and(|path| is_encrypted_file(Path::new(path)))
+}
+
+pub fn apply_key(conn: &Connection, key: &str) -> Result<(), String> {
+    conn.pragma_update(None, "key", key)
+        .map_err(|error| format!("Failed to apply the database key: {error}"))?;
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .map(|_| ())
+    .map_err(|_| "The database key in the system keychain does not open the database".to_string())
+}
+
+/// Key a freshly opened connection when its file is encrypted
+pub fn unlock(conn: &Connection, db_path: &Path) -> Result<(), String> {
+    if !is_encrypted_file(db_path) {
+        return Ok(());
+    }
+    if !AVAILABLE {
+        return Err(format!(
+            "Database {} is encrypted. {UNAVAILABLE_ERROR}",
+            db_path.display()
+        ));
+    }
+    let key = load_key()?.ok_or_else(|| {
+        format!(
+            "Database {} is encrypted but no key was found in the system keychain",
+            db_path.display()
+        )
+    })?;
+    apply_key(conn, &key)
+}
+
+/// Export the main database of `conn` into the new file `dest`. `key` is the
+/// key of the copy (`Some("")` for a plain copy); `None` keeps the key of the
+/// main database.
+pub fn export_database(conn: &Connection, dest: &Path, key: Option<&str>) -> Result<(), String> {
+    let dest_str = dest.to_string_lossy().to_string();
+    let attached = match key {
+        Some(key) => conn.execute(
+            "ATTACH DATABASE ?1 AS export KEY ?2",
+            rusqlite::params![dest_str, key],
+        ),
+        None => conn.execute("ATTACH DATABASE ?1 AS export", [&dest_str]),
+    };
+    attached.map_err(|error| format!("Failed to attach {}: {error}", dest.display()))?;
+
+    let exported = migrations::get_user_version(conn).and_then(|version| {
+        conn.query_row("SELECT sqlcipher_export('export')", [], |_| Ok(()))
+            .map_err(|error| format!("Failed to export the database: {error}"))?;
+        conn.execute_batch(&format!("PRAGMA export.user_version = {version}"))
+            .map_err(|error| format!("Failed to copy the schema version: {error}"))
+    });
+    let detached = conn
+        .execute_batch("DETACH DATABASE export")
+        .map_err(|error| format!("Failed to detach {}: {error}", dest.display()));
+    exported.and(detached)
+}
+
+pub fn encryption_status(db_path: &Path) -> Result<DatabaseEncryptionStatus, String> {
+    let pending = fs::read_to_string(sibling_path(db_path, REQUEST_SUFFIX))
+        .ok()
+        .and_then(|value| EncryptionRequest::parse(&value))
+        .map(|request| request.as_str().to_string());
+    Ok(DatabaseEncryptionStatus {
+        available: AVAILABLE,
+        encrypted: is_encrypted_file(db_path),
+        key_stored: load_key()?.is_some(),
+        pending,
+    })
+}
+
+/// Ask the next start to encrypt (`true`) or decrypt the database. Asking for
+/// the current state clears a pending request.
+pub fn request_conversion(db_path: &Path, encrypt: bool) -> Result<(), String> {
+    let request_path = sibling_path(db_path, REQUEST_SUFFIX);
+    if encrypt == is_encrypted_file(db_path) {
+        return match fs::remove_file(&request_path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(format!(
+                "Failed to clear the encryption request {}: {error}",
+                request_path.display()
+            )),
+            _ => Ok(()),
+        };
+    }
+    if encrypt && !AVAILABLE {
+        return Err(UNAVAILABLE_ERROR.to_string());
+    }
+    let request = if encrypt {
+        EncryptionRequest::Encrypt
+    } else {
+        EncryptionRequest::Decrypt
+    };
+    fs::write(&request_path, request.as_str()).map_err(|error| {
+        format!(
+            "Failed to write the encryption request {}: {error}",
+            request_path.display()
+        )
+    })
+}
+
+/// Carry out a pending encrypt / decrypt request. A failed conversion keeps
+/// the database as it was and drops the request, so the app still starts.
+pub fn apply_requested_conversion(db_path: &Path) {
+    let request_path = sibling_path(db_path, REQUEST_SUFFIX);
+    let Some(request) = fs::read_to_string(&request_path)
+        .ok()
+        .and_then(|value| EncryptionRequest::parse(&value))
+    else {
+        return;
+    };
+    let converting = sibling_path(db_path, CONVERTING_SUFFIX);
+    match convert(db_path, &converting, request) {
+        Ok(()) => log::info!(
+            "Database {} finished: {}",
+            request.as_str(),
+            db_path.display()
+        ),
+        Err(error) => {
+            log::error!("Database {} failed: {}", request.as_str(), error);
+            let _ = fs::remove_file(&converting);
+        }
+    }
+    let _ = fs::remove_file(&request_path);
+}
+
+fn convert(db_path: &Path, converting: &Path, request: EncryptionRequest) -> Result<(), String> {
+    let encrypted = is_encrypted_file(db_path);
+    if !db_path.exists() || encrypted == (request == EncryptionRequest::Encrypt) {
+        return Ok(());
+    }
+    let _ = fs::remove_file(converting);
+
+    {
+        let conn = Connection::open(db_path)
+            .map_err(|error| format!("Failed to open {}: {error}", db_path.display()))?;
+        unlock(&conn, db_path)?;
+        health::checkpoint_truncate(&conn)?;
+        match request {
+            EncryptionRequest::Encrypt => {
+                // Stored first: once the file is swapped, it can't be opened without it
+                let key = generate_key();
+                store_key(&key)?;
+                export_database(&conn, converting, Some(&key))?;
+            }
+            EncryptionRequest::Decrypt => export_database(&conn, converting, Some(""))?,
+        }
+    }
+
+    // Checkpointed and closed above; leftovers must not be replayed on the copy
+    for suffix in ["-wal", "-shm"] {
+        let mut side_file = db_path.as_os_str().to_os_string();
+        side_file.push(suffix);
+        let _ = fs::remove_file(side_file);
+    }
+    fs::rename(converting, db_path).map_err(|error| {
+        format!(
+            "Failed to replace {} with {}: {error}",
+            db_path.display(),
+            converting.display()
+        )
+    })?;
+    if request == EncryptionRequest::Decrypt {
+        delete_key()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn plain_files_are_not_reported_as_encrypted() {
+        let temp = tempfile::tempdir().unwrap();
+        let db_path = temp.path().join("ai-toolbox.db");
+        assert!(!is_encrypted_file(&db_path));
+
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch("CREATE TABLE item (id INTEGER PRIMARY KEY);")
+            .unwrap();
+        assert!(!is_encrypted_file(&db_path));
+        assert!(!is_encrypted_connection(&conn));
+
+        let copy = temp.path().join("copy.db");
+        export_database(&conn, &copy, Some("secret")).unwrap();
+        assert!(is_encrypted_file(&copy));
+        let reopened = Connection::open(&copy).unwrap();
+        assert!(apply_key(&reopened, "wrong").is_err());
+        let reopened = Connection::open(&copy).unwrap();
+        apply_key(&reopened, "secret").unwrap();
+        assert!(is_encrypted_connection(&reopened));
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn requesting_the_current_state_clears_the_request() {
+        let temp = tempfile::tempdir().unwrap();
+        let db_path = temp.path().join("ai-toolbox.db");
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch("CREATE TABLE item (id INTEGER PRIMARY KEY);")
+            .unwrap();
+        let request_path = sibling_path(&db_path, REQUEST_SUFFIX);
+
+        request_conversion(&db_path, true).unwrap();
+        assert_eq!(fs::read_to_string(&request_path).unwrap(), "encrypt");
+        request_conversion(&db_path, false).unwrap();
+        assert!(!request_path.exists());
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[test]
+    fn encryption_is_refused_without_sqlcipher() {
+        let temp = tempfile::tempdir().unwrap();
+        let db_path = temp.path().join("ai-toolbox.db");
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch("CREATE TABLE item (id INTEGER PRIMARY KEY);")
+            .unwrap();
+
+        assert!(request_conversion(&db_path, true).is_err());
+        assert!(request_conversion(&db_path, false).is_ok());
+        assert!(!sibling_path(&db_path, REQUEST_SUFFIX).exists());
+        assert!(!encryption_status(&db_path).unwrap().available);
+    }
+}
@@ -122,6 +122,25 @@ pub fn db_query_by_field(
     }
 }
 
+/// Query rows with a caller-compiled WHERE clause. `where_sql` must be built
+/// from trusted fragments only; every user value goes through `params`
+/// (`?1`, `?2`, ...).
+pub fn db_query_where(
+    conn: &Connection,
+    table: DbTable,
+    where_sql: &str,
+    params: &[&dyn ToSql],
+    order: Option<&OrderSpec>,
+    limit: Option<usize>,
+) -> Result<Vec<Value>, String> {
+    let table_name = table.name();
+    let mut sql = format!(
+        "SELECT id, json(data) AS data_json, created_at, updated_at FROM {table_name} WHERE {where_sql}"
+    );
+    append_order_and_limit(&mut sql, order, limit);
+    query_rows(conn, &sql, params)
+}
+
 pub fn db_query_by_bool(
     conn: &Connection,
     table: DbTable,
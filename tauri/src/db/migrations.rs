@@ -1,8 +1,9 @@
 use rusqlite::Connection;
+use unicode_normalization::UnicodeNormalization;
 
 use super::schema::{sql_string_literal, DbTable, JsonFieldPath, ALL_TABLES};
 
-pub const TARGET_SCHEMA_VERSION: i32 = 8;
+pub const TARGET_SCHEMA_VERSION: i32 = 13;
 const FUTURE_SCHEMA_ERROR_PREFIX: &str = "AI_TOOLBOX_SQLITE_SCHEMA_TOO_NEW";
 
 pub fn run_all(conn: &mut Connection) -> Result<(), String> {
@@ -32,6 +33,21 @@ pub fn run_all(conn: &mut Connection) -> Result<(), String> {
     if current_version < 8 {
         run_migration_step(conn, 8, migrate_v8)?;
     }
+    if current_version < 9 {
+        run_migration_step(conn, 9, migrate_v9)?;
+    }
+    if current_version < 10 {
+        run_migration_step(conn, 10, migrate_v10)?;
+    }
+    if current_version < 11 {
+        run_migration_step(conn, 11, migrate_v11)?;
+    }
+    if current_version < 12 {
+        run_migration_step(conn, 12, migrate_v12)?;
+    }
+    if current_version < 13 {
+        run_migration_step(conn, 13, migrate_v13)?;
+    }
 
     Ok(())
 }
@@ -193,6 +209,77 @@ fn migrate_v8(conn: &Connection) -> Result<(), String> {
     )
 }
 
+fn migrate_v9(conn: &Connection) -> Result<(), String> {
+    for table in [DbTable::ConfigSnapshot, DbTable::ConfigSnapshotSettings] {
+        create_jsonb_table(conn, table)?;
+    }
+
+    create_json_index(
+        conn,
+        DbTable::ConfigSnapshot,
+        &JsonFieldPath::new("created_at")?,
+    )
+}
+
+fn migrate_v10(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::SkillVariant)?;
+    create_json_index(
+        conn,
+        DbTable::SkillVariant,
+        &JsonFieldPath::new("skill_id")?,
+    )
+}
+
+fn migrate_v11(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::SkillCollection)
+}
+
+fn migrate_v12(conn: &Connection) -> Result<(), String> {
+    normalize_skill_names_nfc(conn)
+}
+
+fn migrate_v13(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::SkillSyncRun)?;
+    create_json_index(
+        conn,
+        DbTable::SkillSyncRun,
+        &JsonFieldPath::new("started_at")?,
+    )
+}
+
+/// Skill names imported from macOS file names can be NFD; rewrite them to the
+/// NFC form new rows are stored in. Paths are left alone, they name real
+/// directories on disk.
+fn normalize_skill_names_nfc(conn: &Connection) -> Result<(), String> {
+    let table_name = DbTable::Skill.name();
+    let mut statement = conn
+        .prepare(&format!(
+            "SELECT id, json_extract(data, '$.name') FROM {table_name}
+             WHERE json_type(data, '$.name') = 'text'"
+        ))
+        .map_err(|error| format!("Failed to read skill names: {error}"))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|error| format!("Failed to read skill names: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to read skill names: {error}"))?;
+
+    for (id, name) in rows {
+        let normalized = name.nfc().collect::<String>();
+        if normalized == name {
+            continue;
+        }
+        conn.execute(
+            &format!("UPDATE {table_name} SET data = jsonb_set(data, '$.name', ?1) WHERE id = ?2"),
+            (normalized, id),
+        )
+        .map_err(|error| format!("Failed to normalize skill name: {error}"))?;
+    }
+    Ok(())
+}
+
 fn create_jsonb_table(conn: &Connection, table: DbTable) -> Result<(), String> {
     let table_name = table.name();
     conn.execute_batch(&format!(
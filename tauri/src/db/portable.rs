@@ -0,0 +1,274 @@
+//! Portable subset of the database, for moving the library to another machine.
+//!
+//! A full backup restores this machine's state as-is. Copying it to another
+//! machine drags along local paths (central repo location, sync configs,
+//! custom tool dirs) and resolved tool targets that don't exist there.
+//! `DbTable::scope` draws the line: a portable export holds the portable tables
+//! only, with `DbTable::machine_fields` removed from their rows. Importing it
+//! replaces those tables and leaves the machine tables alone; tool targets are
+//! re-derived from each row's `enabled_tools` by the post-restore resync.
+
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use super::schema::{DataScope, DbTable, ALL_TABLES};
+use super::{encryption, health, migrations};
+
+const PORTABLE_SCHEMA: &str = "portable";
+
+fn portable_tables() -> impl Iterator<Item = DbTable> {
+    ALL_TABLES
+        .iter()
+        .copied()
+        .filter(|table| table.scope() == DataScope::Portable)
+}
+
+fn table_exists(conn: &Connection, schema: &str, table: DbTable) -> Result<bool, String> {
+    conn.query_row(
+        &format!("SELECT count(*) FROM {schema}.sqlite_master WHERE type = 'table' AND name = ?1"),
+        [table.name()],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count > 0)
+    .map_err(|error| format!("Failed to inspect table {}: {error}", table.name()))
+}
+
+/// Attach `path` as plain SQLite, whatever the main database's encryption
+fn attach_plain(conn: &Connection, path: &Path) -> Result<(), String> {
+    let path_str = path.to_string_lossy().to_string();
+    let attached = if encryption::is_encrypted_connection(conn) {
+        conn.execute(
+            &format!("ATTACH DATABASE ?1 AS {PORTABLE_SCHEMA} KEY ''"),
+            [&path_str],
+        )
+    } else {
+        conn.execute(
+            &format!("ATTACH DATABASE ?1 AS {PORTABLE_SCHEMA}"),
+            [&path_str],
+        )
+    };
+    attached
+        .map(|_| ())
+        .map_err(|error| format!("Failed to attach {}: {error}", path.display()))
+}
+
+fn detach(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(&format!("DETACH DATABASE {PORTABLE_SCHEMA}"))
+        .map_err(|error| format!("Failed to detach portable database: {error}"))
+}
+
+/// Write the portable tables to the new file `dest`; returns the row count
+pub fn export_portable(conn: &Connection, dest: &Path) -> Result<usize, String> {
+    if dest.exists() {
+        return Err(format!("Export target already exists: {}", dest.display()));
+    }
+    health::checkpoint_truncate(conn)?;
+    attach_plain(conn, dest)?;
+    let exported = (|| -> Result<usize, String> {
+        let mut rows = 0;
+        for table in portable_tables() {
+            if !table_exists(conn, "main", table)? {
+                continue;
+            }
+            let name = table.name();
+            conn.execute_batch(&format!(
+                "CREATE TABLE {PORTABLE_SCHEMA}.{name} (
+                    id TEXT PRIMARY KEY NOT NULL,
+                    data BLOB NOT NULL CHECK (json_valid(data, 4)),
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );
+                INSERT INTO {PORTABLE_SCHEMA}.{name} (id, data, created_at, updated_at)
+                    SELECT id, data, created_at, updated_at FROM main.{name};"
+            ))
+            .map_err(|error| format!("Failed to export table {name}: {error}"))?;
+            for field in table.machine_fields() {
+                conn.execute(
+                    &format!("UPDATE {PORTABLE_SCHEMA}.{name} SET data = jsonb_remove(data, ?1)"),
+                    [field],
+                )
+                .map_err(|error| format!("Failed to strip {field} from {name}: {error}"))?;
+            }
+            rows += conn
+                .query_row(
+                    &format!("SELECT count(*) FROM {PORTABLE_SCHEMA}.{name}"),
+                    [],
+                    |row| row.get::<_, i64>(0),
+                )
+                .map_err(|error| format!("Failed to count table {name}: {error}"))?
+                as usize;
+        }
+        let version = migrations::get_user_version(conn)?;
+        conn.execute_batch(&format!(
+            "PRAGMA {PORTABLE_SCHEMA}.user_version = {version}"
+        ))
+        .map_err(|error| format!("Failed to write the schema version: {error}"))?;
+        Ok(rows)
+    })();
+    let detached = detach(conn);
+    let rows = exported?;
+    detached?;
+    Ok(rows)
+}
+
+/// Replace the portable tables with the ones in the export at `src`; machine
+/// tables are kept. Returns the imported row count.
+pub fn import_portable(conn: &mut Connection, src: &Path) -> Result<usize, String> {
+    if !src.is_file() {
+        return Err(format!("Portable export not found: {}", src.display()));
+    }
+    attach_plain(conn, src)?;
+    let imported = (|| -> Result<usize, String> {
+        let version: i32 = conn
+            .query_row(
+                &format!("PRAGMA {PORTABLE_SCHEMA}.user_version"),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|error| format!("Failed to read the export's schema version: {error}"))?;
+        let current = migrations::get_user_version(conn)?;
+        if version != current {
+            return Err(format!(
+                "Portable export has schema version {version}, this app uses {current}; export it again with the same app version"
+            ));
+        }
+
+        let tx = conn
+            .transaction()
+            .map_err(|error| format!("Failed to start import: {error}"))?;
+        let mut rows = 0;
+        for table in portable_tables() {
+            if !table_exists(&tx, PORTABLE_SCHEMA, table)? || !table_exists(&tx, "main", table)? {
+                continue;
+            }
+            let name = table.name();
+            rows += tx
+                .execute(&format!("DELETE FROM main.{name}"), [])
+                .and_then(|_| {
+                    tx.execute(
+                        &format!(
+                            "INSERT INTO main.{name} (id, data, created_at, updated_at)
+                                SELECT id, data, created_at, updated_at FROM {PORTABLE_SCHEMA}.{name}"
+                        ),
+                        [],
+                    )
+                })
+                .map_err(|error| format!("Failed to import table {name}: {error}"))?;
+        }
+        tx.commit()
+            .map_err(|error| format!("Failed to finish import: {error}"))?;
+        Ok(rows)
+    })();
+    let detached = detach(conn);
+    let rows = imported?;
+    detached?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SqliteDbState;
+
+    fn insert(state: &SqliteDbState, table: &str, id: &str, data: serde_json::Value) {
+        state
+            .with_conn(|conn| {
+                conn.execute(
+                    &format!(
+                        "INSERT OR REPLACE INTO {table} (id, data, created_at, updated_at)
+                            VALUES (?1, jsonb(?2), '0', '0')"
+                    ),
+                    [id, &data.to_string()],
+                )
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+            })
+            .unwrap();
+    }
+
+    fn read(state: &SqliteDbState, table: &str) -> Vec<serde_json::Value> {
+        state
+            .with_conn(|conn| {
+                let mut stmt = conn
+                    .prepare(&format!("SELECT json(data) FROM {table} ORDER BY id"))
+                    .map_err(|error| error.to_string())?;
+                let rows = stmt
+                    .query_map([], |row| row.get::<_, String>(0))
+                    .map_err(|error| error.to_string())?
+                    .map(|row| serde_json::from_str(&row.unwrap()).unwrap())
+                    .collect();
+                Ok(rows)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn import_replaces_portable_tables_and_keeps_machine_state() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = SqliteDbState::open(temp.path().join("source.db")).unwrap();
+        insert(
+            &source,
+            "skill",
+            "review",
+            serde_json::json!({
+                "name": "review",
+                "enabled_tools": ["claude_code"],
+                "sync_details": { "claude_code": { "target_path": "/home/alice/.claude/skills/review" } },
+                "last_sync_at": 1,
+            }),
+        );
+        insert(
+            &source,
+            "skill_settings",
+            "skills",
+            serde_json::json!({ "central_repo_path": "/home/alice/skills" }),
+        );
+        insert(
+            &source,
+            "mcp_preferences",
+            "mcp",
+            serde_json::json!({
+                "preferred_tools": ["claude_code"],
+                "remote_only_tools": ["cursor"],
+                "gateway_port": 41234,
+                "gateway_token": "secret",
+                "traffic_log_servers": ["filesystem"],
+            }),
+        );
+        let export_path = temp.path().join("library.portable.db");
+        source
+            .with_conn(|conn| export_portable(conn, &export_path))
+            .unwrap();
+
+        let target = SqliteDbState::open(temp.path().join("target.db")).unwrap();
+        insert(
+            &target,
+            "skill",
+            "stale",
+            serde_json::json!({ "name": "stale" }),
+        );
+        insert(
+            &target,
+            "skill_settings",
+            "skills",
+            serde_json::json!({ "central_repo_path": "D:\\skills" }),
+        );
+        target
+            .with_conn_mut(|conn| import_portable(conn, &export_path))
+            .unwrap();
+
+        assert_eq!(
+            read(&target, "skill"),
+            vec![serde_json::json!({ "name": "review", "enabled_tools": ["claude_code"] })]
+        );
+        assert_eq!(
+            read(&target, "mcp_preferences"),
+            vec![serde_json::json!({ "preferred_tools": ["claude_code"] })]
+        );
+        assert_eq!(
+            read(&target, "skill_settings"),
+            vec![serde_json::json!({ "central_repo_path": "D:\\skills" })]
+        );
+    }
+}
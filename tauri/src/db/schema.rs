@@ -36,6 +36,9 @@ pub enum DbTable {
     SkillRepo,
     SkillPreferences,
     SkillSettings,
+    SkillVariant,
+    SkillCollection,
+    SkillSyncRun,
     CustomTool,
     McpServer,
     McpPreferences,
@@ -49,6 +52,8 @@ pub enum DbTable {
     ImageChannel,
     ImageJob,
     ImageAsset,
+    ConfigSnapshot,
+    ConfigSnapshotSettings,
 }
 
 pub const ALL_TABLES: &[DbTable] = &[
@@ -86,6 +91,9 @@ pub const ALL_TABLES: &[DbTable] = &[
     DbTable::SkillRepo,
     DbTable::SkillPreferences,
     DbTable::SkillSettings,
+    DbTable::SkillVariant,
+    DbTable::SkillCollection,
+    DbTable::SkillSyncRun,
     DbTable::CustomTool,
     DbTable::McpServer,
     DbTable::McpPreferences,
@@ -99,8 +107,19 @@ pub const ALL_TABLES: &[DbTable] = &[
     DbTable::ImageChannel,
     DbTable::ImageJob,
     DbTable::ImageAsset,
+    DbTable::ConfigSnapshot,
+    DbTable::ConfigSnapshotSettings,
 ];
 
+/// Whether rows stay meaningful on another machine
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DataScope {
+    /// Library content: providers, prompts, skills, collections, MCP servers
+    Portable,
+    /// Local paths and state: settings, sync configs, custom tool dirs
+    Machine,
+}
+
 impl DbTable {
     pub fn name(self) -> &'static str {
         match self {
@@ -138,6 +157,9 @@ impl DbTable {
             DbTable::SkillRepo => "skill_repo",
             DbTable::SkillPreferences => "skill_preferences",
             DbTable::SkillSettings => "skill_settings",
+            DbTable::SkillVariant => "skill_variant",
+            DbTable::SkillCollection => "skill_collection",
+            DbTable::SkillSyncRun => "skill_sync_run",
             DbTable::CustomTool => "custom_tool",
             DbTable::McpServer => "mcp_server",
             DbTable::McpPreferences => "mcp_preferences",
@@ -151,6 +173,48 @@ impl DbTable {
             DbTable::ImageChannel => "image_channel",
             DbTable::ImageJob => "image_job",
             DbTable::ImageAsset => "image_asset",
+            DbTable::ConfigSnapshot => "config_snapshot",
+            DbTable::ConfigSnapshotSettings => "config_snapshot_settings",
+        }
+    }
+
+    pub fn scope(self) -> DataScope {
+        match self {
+            DbTable::Settings
+            | DbTable::AppMigration
+            | DbTable::CodexPluginWorkspaceRoots
+            | DbTable::SkillSettings
+            | DbTable::SkillVariant
+            | DbTable::SkillSyncRun
+            | DbTable::CustomTool
+            | DbTable::WslSyncConfig
+            | DbTable::WslFileMapping
+            | DbTable::SshSyncConfig
+            | DbTable::SshConnection
+            | DbTable::SshFileMapping
+            | DbTable::ProxyGatewaySettings
+            | DbTable::ImageJob
+            | DbTable::ImageAsset
+            | DbTable::ConfigSnapshot
+            | DbTable::ConfigSnapshotSettings => DataScope::Machine,
+            _ => DataScope::Portable,
+        }
+    }
+
+    /// Machine-specific fields of a portable table's rows (resolved tool
+    /// targets, the local MCP gateway and traffic logging), dropped when the
+    /// rows leave the machine
+    pub fn machine_fields(self) -> &'static [&'static str] {
+        match self {
+            DbTable::Skill => &["$.sync_details", "$.last_sync_at"],
+            DbTable::McpServer => &["$.sync_details"],
+            DbTable::McpPreferences => &[
+                "$.remote_only_tools",
+                "$.gateway_port",
+                "$.gateway_token",
+                "$.traffic_log_servers",
+            ],
+            _ => &[],
         }
     }
 }
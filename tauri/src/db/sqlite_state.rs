@@ -6,7 +6,7 @@ use chrono::Utc;
 use rusqlite::Connection;
 use uuid::Uuid;
 
-use super::{backup, health, migrations, model_pricing_seed};
+use super::{backup, encryption, health, migrations, model_pricing_seed};
 
 pub const SQLITE_MIGRATION_BACKUP_DIR: &str = "sqlite-migration-backups";
 
@@ -18,12 +18,14 @@ pub struct SqliteDbState {
 
 impl SqliteDbState {
     pub fn open(db_path: PathBuf) -> Result<Self, String> {
+        encryption::apply_requested_conversion(&db_path);
         let mut conn = Connection::open(&db_path).map_err(|error| {
             format!(
                 "Failed to open SQLite database {}: {error}",
                 db_path.display()
             )
         })?;
+        encryption::unlock(&conn, &db_path)?;
         initialize_file_connection(&mut conn, &db_path)?;
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
@@ -26,12 +26,16 @@ use std::sync::Mutex as StdMutex;
 
 // Module declarations
 pub mod auto_launch;
+pub mod cli;
 pub mod coding;
+pub mod command_access;
 pub mod db;
 pub mod db_migration;
 pub mod http_client;
 pub mod settings;
 pub mod single_instance;
+#[cfg(feature = "test-harness")]
+pub mod test_harness;
 pub mod tray;
 pub mod update;
 
@@ -277,7 +281,7 @@ fn init_logging() -> Option<std::path::PathBuf> {
     // 正式版本：日志写入文件
     let log_dir = dirs::data_dir()
         .map(|p| p.join("com.ai-toolbox").join("logs"))
-        .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox").join("logs")));
+        .or_else(|| crate::coding::tools::home_dir().map(|p| p.join(".ai-toolbox").join("logs")));
 
     let log_dir = match log_dir {
         Some(dir) => dir,
@@ -359,7 +363,9 @@ fn setup_panic_hook() {
         // 尝试将错误写入单独的崩溃日志文件
         if let Some(log_dir) = dirs::data_dir()
             .map(|p| p.join("com.ai-toolbox").join("logs"))
-            .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox").join("logs")))
+            .or_else(|| {
+                crate::coding::tools::home_dir().map(|p| p.join(".ai-toolbox").join("logs"))
+            })
         {
             let crash_file = log_dir.join("CRASH.log");
             let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
@@ -546,7 +552,7 @@ const WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL: u8 = 4;
 fn wayland_webview_workaround_level_path() -> Option<std::path::PathBuf> {
     let base_dir = dirs::data_dir()
         .map(|p| p.join("com.ai-toolbox"))
-        .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox")))?;
+        .or_else(|| crate::coding::tools::home_dir().map(|p| p.join(".ai-toolbox")))?;
     Some(
         base_dir
             .join("runtime")
@@ -875,6 +881,9 @@ fn setup_linux_wayland_webview_workaround() -> u8 {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // 主目录覆盖会影响日志目录，必须最先读取
+    coding::tools::init_home_override_from_env();
+
     // 初始化日志系统
     let log_file = init_logging();
     if let Some(ref path) = log_file {
@@ -891,6 +900,27 @@ pub fn run() {
     info!("架构: {}", std::env::consts::ARCH);
     info!("========================================");
 
+    settings::read_only::init_from_launch();
+    if settings::read_only::is_read_only() {
+        info!("只读合规模式已通过启动参数启用");
+    }
+    let policy_status = settings::policy::init_from_system();
+    if policy_status.active {
+        info!(
+            "已加载组策略文件: {}",
+            policy_status.path.as_deref().unwrap_or("-")
+        );
+    } else if let Some(error) = &policy_status.error {
+        warn!("组策略文件无效，已忽略: {}", error);
+    }
+    if let Some(home) = coding::tools::home_override() {
+        info!(
+            "用户主目录已通过 {} 覆盖: {}",
+            coding::tools::HOME_OVERRIDE_ENV,
+            home.display()
+        );
+    }
+
     #[cfg(target_os = "linux")]
     maybe_reexec_appimage_with_system_wayland_client();
 
@@ -1050,6 +1080,22 @@ pub fn run() {
             db::model_pricing_seed::set_cache_dir(app_data_dir.clone());
             info!("模型定价缓存目录已初始化");
 
+            // Initialize the sync trash (rollback journal) and apply its limits
+            let trash_dir = app_data_dir.join("trash");
+            coding::skills::sync_journal::purge(&trash_dir);
+            coding::skills::sync_context::set_trash_dir(trash_dir);
+
+            // Point the MCP traffic logging shim at this executable
+            if let Err(e) = coding::mcp::traffic_log::install_proxy_shim() {
+                warn!("MCP 流量日志代理脚本写入失败: {}", e);
+            }
+
+            // Tool adapters supplied as WASM plugins load once the database
+            // holds the user's approvals
+            coding::tools::wasm_plugins::set_plugins_dir(
+                app_data_dir.join(coding::tools::wasm_plugins::PLUGINS_DIR_NAME),
+            );
+
             let migration_paths = db::surreal_import::MigrationPaths::new(&app_data_dir);
             let startup_migration_state = match prepare_startup_migration_state(&migration_paths) {
                 Ok(state) => state,
@@ -1146,14 +1192,29 @@ pub fn run() {
                     warn!("Gemini CLI 默认配置初始化失败: {}", e);
                 }
 
+                match settings::store::load_settings_from_sqlite_state(&db_state) {
+                    Ok(app_settings) => settings::read_only::apply_settings(&app_settings),
+                    Err(e) => warn!("读取只读模式设置失败: {}", e),
+                }
+                coding::skills::skill_ignore::init_from_settings(&db_state);
+                coding::skills::central_repo::init_link_style_from_settings(&db_state);
+                coding::tools::wasm_plugins::reload_plugins(&db_state);
+
                 app.manage(db_state);
                 info!("SQLite 主数据库状态已注册到应用");
 
                 app.manage(coding::proxy_gateway::ProxyGatewayState::default());
                 info!("网关状态已注册到应用");
 
+                app.manage(update::PendingUpdateState::default());
+                tauri::async_runtime::spawn(update::resume_pending_update(app_handle.clone()));
+
                 let gateway_start_app = app_handle.clone();
                 tauri::async_runtime::spawn(async move {
+                    if settings::policy::is_feature_disabled("gateway") {
+                        info!("代理网关已被组策略禁用，跳过自动启动");
+                        return;
+                    }
                     let db_state = gateway_start_app.state::<SqliteDbState>();
                     let gateway_state =
                         gateway_start_app.state::<coding::proxy_gateway::ProxyGatewayState>();
@@ -1217,6 +1278,29 @@ pub fn run() {
                 std::future::pending::<()>().await;
             });
 
+            // Republish enabled skill team mirrors when skills change
+            let app_mirror = app_handle.clone();
+            let app_mirror_clone = app_mirror.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = app_mirror.listen("skills-changed", move |_event| {
+                    if settings::read_only::is_read_only() {
+                        return;
+                    }
+                    let app = app_mirror_clone.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let db_state = app.state::<crate::SqliteDbState>();
+                        if let Err(error) =
+                            coding::skills::team_mirror::publish_targets(&app, &db_state, None)
+                                .await
+                        {
+                            warn!("Skills team mirror publish failed: {}", error);
+                        }
+                    });
+                });
+
+                std::future::pending::<()>().await;
+            });
+
             // Enable auto-launch if setting is true, and handle start_minimized
             let app_handle_clone = app_handle.clone();
             tauri::async_runtime::spawn(async move {
@@ -1511,6 +1595,9 @@ pub fn run() {
                 let app_ssh_restore = app_handle.clone();
                 tauri::async_runtime::spawn(async move {
                     tokio::time::sleep(Duration::from_secs(3)).await;
+                    if settings::policy::is_feature_disabled("ssh") {
+                        return;
+                    }
 
                     let db_state = app_ssh_restore.state::<SqliteDbState>();
                     let session_state = app_ssh_restore.state::<coding::ssh::SshSessionState>();
@@ -1632,13 +1719,17 @@ pub fn run() {
                             warn!("Post-restore runtime location cache refresh failed: {}", e);
                         }
 
-                        // Resync skills
-                        match coding::skills::commands::skills_resync_all(
-                            app_clone.clone(),
-                            db_state.clone(),
-                        )
-                        .await
-                        {
+                        // Resync skills. The restore itself was confirmed by the user, so
+                        // the bulk-overwrite confirmation token is not required here.
+                        let skills_resync = async {
+                            settings::read_only::ensure_writable("skills_resync_all")?;
+                            coding::skills::commands::resync_all_skills_internal(
+                                app_clone.clone(),
+                                &db_state,
+                            )
+                            .await
+                        };
+                        match skills_resync.await {
                             Ok(synced) => {
                                 info!("Skills resync completed: {} items synced", synced.len());
                             }
@@ -1665,12 +1756,42 @@ pub fn run() {
 
                         info!("Post-restore resync completed");
                     }
+
+                    // Consistency check runs after any resync so it sees the final state
+                    coding::skills::startup_check::run_startup_check(&app_clone).await;
                 });
             }
 
             // Start auto-backup scheduler
             settings::backup::auto_backup::start_auto_backup_scheduler(app_handle.clone());
 
+            // Start scheduled portable export
+            settings::backup::portable_export::start_portable_export_scheduler(app_handle.clone());
+
+            // Start tool config snapshot scheduler
+            coding::config_snapshot::scheduler::start_config_snapshot_scheduler(app_handle.clone());
+
+            // Start skill group rotation scheduler
+            coding::skills::rotation::start_skill_rotation_scheduler(app_handle.clone());
+
+            // Scan tools installed while the app runs
+            coding::skills::tool_watcher::start_tool_detection_watcher(app_handle.clone());
+
+            // Adopt low-risk onboarding groups when the user opted in
+            coding::skills::auto_adopt::start_auto_adopt_scheduler(app_handle.clone());
+
+            // Alert on central repo edits made outside the app
+            coding::skills::external_changes::start_external_change_watcher(app_handle.clone());
+
+            // Re-sync copy targets when the central repo is edited
+            coding::skills::central_watcher::start_central_repo_watcher(app_handle.clone());
+
+            // Re-sync drifted skill targets on the user's schedule
+            coding::skills::auto_sync::start_auto_sync_scheduler(app_handle.clone());
+
+            // Serve stdio MCP servers to remote-only tools
+            coding::mcp::gateway::start_mcp_gateway(app_handle.clone());
+
             info!("setup() 完成，应用即将启动");
             Ok(())
         })
@@ -1709,7 +1830,7 @@ pub fn run() {
                 // If minimize_to_tray is false, do nothing - window will close normally
             }
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(command_access::guard(tauri::generate_handler![
             // Common
             open_folder,
             open_existing_folder,
@@ -1717,9 +1838,14 @@ pub fn run() {
             // Update
             update::check_for_updates,
             update::install_update,
+            update::download_update_on_quit,
+            update::get_pending_update_status,
+            coding::status_summary::get_status_summary,
             // Settings
             settings::get_settings,
             settings::save_settings,
+            settings::get_read_only_status,
+            settings::get_policy_status,
             settings::normalize_backup_custom_entry_path,
             settings::list_backup_file_filter_path_options,
             settings::set_auto_launch,
@@ -1763,6 +1889,12 @@ pub fn run() {
             settings::backup::backup_database,
             settings::backup::restore_database,
             settings::backup::get_database_path,
+            settings::backup::get_database_encryption_status,
+            settings::backup::set_database_encryption,
+            settings::backup::export_portable_data,
+            settings::backup::import_portable_data,
+            settings::backup::get_portable_export_status,
+            settings::backup::run_portable_export_now,
             settings::backup::open_app_data_dir,
             // Backup - WebDAV
             settings::backup::backup_to_webdav,
@@ -1986,6 +2118,7 @@ pub fn run() {
             coding::gemini_cli::get_gemini_cli_config_path,
             coding::gemini_cli::get_gemini_cli_root_path_info,
             coding::gemini_cli::reveal_gemini_cli_config_folder,
+            coding::gemini_cli::list_gemini_cli_extensions,
             coding::gemini_cli::read_gemini_cli_settings,
             coding::gemini_cli::list_gemini_cli_providers,
             coding::gemini_cli::create_gemini_cli_provider,
@@ -2127,23 +2260,61 @@ pub fn run() {
             coding::skills::skills_adopt_central_repo_skills,
             coding::skills::skills_repair_central_repo_skill,
             coding::skills::skills_get_managed_skills,
+            coding::skills::skills_query,
             coding::skills::skills_install_local,
             coding::skills::skills_list_local_skills,
             coding::skills::skills_install_local_selection,
             coding::skills::skills_install_git,
+            coding::skills::skills_install_archive_url,
             coding::skills::skills_list_git_skills,
             coding::skills::skills_install_git_selection,
             coding::skills::skills_sync_to_tool,
+            coding::skills::skills_preview_sync,
+            coding::skills::skills_preview_target_conflict,
             coding::skills::skills_unsync_from_tool,
+            coding::skills::skills_convert_target_mode,
+            coding::skills::skills_set_target_path_rules,
+            coding::skills::skills_get_variants_per_skill,
+            coding::skills::skills_set_variants_per_skill,
+            coding::skills::skills_list_variants,
+            coding::skills::skills_create_variant,
+            coding::skills::skills_assign_variant,
+            coding::skills::skills_swap_variants,
+            coding::skills::skills_render_to_project,
+            coding::skills::skills_delete_variant,
             coding::skills::skills_update_managed,
+            coding::skills::skills_preview_update,
+            coding::skills::skills_get_locally_modified,
             coding::skills::skills_delete_managed,
             coding::skills::skills_get_onboarding_plan,
+            coding::skills::skills_export_onboarding_plan,
+            coding::skills::skills_scan_extra_roots,
             coding::skills::skills_import_existing,
+            coding::skills::skills_get_linked_folder_changes,
+            coding::skills::skills_set_linked_folder_tracking,
+            coding::skills::skills_adopt_onboarding_plan,
+            coding::skills::skills_get_auto_adopt_onboarding,
+            coding::skills::skills_set_auto_adopt_onboarding,
+            coding::skills::skills_get_auto_resync_copies,
+            coding::skills::skills_set_auto_resync_copies,
+            coding::skills::skills_simulate_adoption,
+            coding::skills::skills_get_source_priority,
+            coding::skills::skills_set_source_priority,
+            coding::skills::skills_get_consolidation_candidates,
+            coding::skills::skills_consolidate_copies,
+            coding::skills::skills_get_duplicate_content,
+            coding::skills::skills_get_conflict_overview,
+            coding::skills::skills_scan_external_changes,
+            coding::skills::skills_accept_external_change,
+            coding::skills::skills_revert_external_change,
+            coding::skills::skills_preview_pull_from_target,
+            coding::skills::skills_pull_from_target,
             coding::skills::skills_get_git_cache_cleanup_days,
             coding::skills::skills_set_git_cache_cleanup_days,
             coding::skills::skills_get_git_cache_ttl_secs,
             coding::skills::skills_clear_git_cache,
             coding::skills::skills_get_git_cache_path,
+            coding::skills::skills_get_storage_report,
             coding::skills::skills_get_preferred_tools,
             coding::skills::skills_set_preferred_tools,
             coding::skills::skills_get_show_in_tray,
@@ -2156,6 +2327,8 @@ pub fn run() {
             coding::skills::skills_remove_custom_tool,
             coding::skills::skills_check_custom_tool_path,
             coding::skills::skills_create_custom_tool_path,
+            coding::skills::skills_get_sync_target_whitelist,
+            coding::skills::skills_set_sync_target_whitelist,
             // Skills Hub - Skill Repos
             coding::skills::skills_get_repos,
             coding::skills::skills_add_repo,
@@ -2166,9 +2339,79 @@ pub fn run() {
             coding::skills::skills_get_groups,
             coding::skills::skills_save_group,
             coding::skills::skills_delete_group,
+            coding::skills::skills_get_collections,
+            coding::skills::skills_save_collection,
+            coding::skills::skills_delete_collection,
+            coding::skills::skills_set_collection_tool_enabled,
+            coding::skills::skills_export_collections_file,
+            coding::skills::skills_import_collections_file,
+            coding::skills::skills_list_git_collections,
+            coding::skills::skills_get_mirror_targets,
+            coding::skills::skills_save_mirror_target,
+            coding::skills::skills_delete_mirror_target,
+            coding::skills::skills_publish_mirror,
+            coding::skills::skills_get_mirror_sources,
+            coding::skills::skills_add_mirror_source,
+            coding::skills::skills_remove_mirror_source,
+            coding::skills::skills_list_mirror_skills,
+            coding::skills::skills_install_from_mirror,
+            coding::skills::skills_check_mirror_updates,
+            coding::skills::skills_get_context_budget,
+            coding::skills::skills_set_context_budget,
+            coding::skills::skills_analyze_skill,
+            coding::skills::skills_rank_quality,
+            coding::skills::skills_get_disallowed_licenses,
+            coding::skills::skills_save_disallowed_licenses,
+            coding::skills::skills_get_copy_ignore_patterns,
+            coding::skills::skills_save_copy_ignore_patterns,
+            coding::skills::skills_get_relative_symlinks,
+            coding::skills::skills_set_relative_symlinks,
+            coding::skills::skills_list_sync_journal,
+            coding::skills::skills_rollback_last_sync,
+            coding::skills::skills_purge_sync_trash,
+            coding::skills::skills_scan_stale_tool_artifacts,
+            coding::skills::skills_sweep_stale_tool_artifacts,
+            coding::skills::skills_recover_targets_from_markers,
+            coding::skills::skills_rebuild_database,
+            coding::skills::skills_audit_library,
+            coding::skills::skills_fix_library_audit_issues,
+            coding::skills::skills_get_startup_check_summary,
+            coding::skills::skills_clean_dangling_links,
+            coding::skills::skills_preview_tool_load,
+            coding::skills::skills_repair_links,
+            coding::skills::skills_get_auto_sync_interval,
+            coding::skills::skills_set_auto_sync_interval,
+            coding::skills::skills_run_skill_sync_now,
+            coding::skills::skills_list_sync_runs,
+            coding::skills::skills_list_file_skill_tools,
+            coding::skills::skills_write_file_skill,
+            coding::skills::skills_remove_file_skill,
+            coding::skills::skills_get_source_mirror,
+            coding::skills::skills_save_source_mirror,
+            coding::skills::skills_get_rotation_policies,
+            coding::skills::skills_save_rotation_policy,
+            coding::skills::skills_delete_rotation_policy,
+            coding::skills::skills_rotate_group_now,
+            coding::skills::skills_get_name_collision_policies,
+            coding::skills::skills_save_name_collision_policies,
+            coding::skills::skills_get_default_skill_sets,
+            coding::skills::skills_save_default_skill_sets,
+            coding::skills::skills_check_new_tools,
+            coding::skills::skills_apply_default_skill_set,
             coding::skills::skills_update_metadata,
             coding::skills::skills_batch_update_group,
+            coding::skills::skills_batch_update_tags,
+            coding::skills::skills_check_compatibility,
+            coding::skills::skills_set_compatibility,
+            coding::skills::skills_set_localizations,
+            coding::skills::skills_get_dictionary_localizations,
+            coding::skills::skills_fill_localizations,
+            coding::skills::skills_set_write_protected,
+            coding::skills::skills_get_all_tags,
+            coding::skills::skills_suggest_tags,
             coding::skills::skills_set_management_enabled,
+            coding::skills::skills_archive_skill,
+            coding::skills::skills_unarchive_skill,
             coding::skills::skills_export_inventory,
             coding::skills::skills_export_inventory_file,
             coding::skills::skills_preview_inventory_import,
@@ -2177,10 +2420,13 @@ pub fn run() {
             coding::skills::skills_apply_inventory_import_file,
             // Skills Hub - Resync
             coding::skills::skills_resync_all,
+            coding::skills::skills_request_confirmation,
             // MCP Servers
             coding::mcp::mcp_list_servers,
             coding::mcp::mcp_resolve_package_versions,
             coding::mcp::mcp_create_server,
+            coding::mcp::mcp_list_templates,
+            coding::mcp::mcp_add_server_from_template,
             coding::mcp::mcp_update_server,
             coding::mcp::mcp_delete_server,
             coding::mcp::mcp_toggle_tool,
@@ -2191,6 +2437,16 @@ pub fn run() {
             coding::mcp::mcp_import_from_tool,
             coding::mcp::mcp_get_tools,
             coding::mcp::mcp_scan_servers,
+            coding::mcp::mcp_health_check,
+            coding::mcp::mcp_check_tool_collisions,
+            coding::mcp::mcp_check_prerequisites,
+            coding::mcp::mcp_get_gateway_status,
+            coding::mcp::mcp_set_remote_only_tools,
+            coding::mcp::mcp_set_traffic_logging,
+            coding::mcp::mcp_get_traffic,
+            coding::mcp::mcp_clear_traffic,
+            coding::mcp::mcp_get_apply_history,
+            coding::mcp::mcp_get_apply_backup,
             coding::mcp::mcp_get_show_in_tray,
             coding::mcp::mcp_set_show_in_tray,
             coding::mcp::mcp_get_preferred_tools,
@@ -2201,11 +2457,32 @@ pub fn run() {
             coding::mcp::mcp_set_sync_disabled_to_opencode,
             coding::mcp::mcp_add_custom_tool,
             coding::mcp::mcp_remove_custom_tool,
+            coding::tools::tools_get_post_sync_hooks,
+            coding::tools::tools_save_post_sync_hooks,
+            coding::tools::tools_test_post_sync_hook,
+            coding::tools::tools_preview_key_migration,
+            coding::tools::tools_migrate_keys,
+            coding::tools::tools_list_plugins,
+            coding::tools::tools_reload_plugins,
+            coding::tools::tools_approve_plugin,
             // MCP Favorites
             coding::mcp::mcp_list_favorites,
             coding::mcp::mcp_upsert_favorite,
             coding::mcp::mcp_delete_favorite,
             coding::mcp::mcp_init_default_favorites,
+            // Dev Container
+            coding::devcontainer::devcontainer_get_selection,
+            coding::devcontainer::devcontainer_save_selection,
+            coding::devcontainer::devcontainer_generate,
+            // Config Snapshots
+            coding::config_snapshot::config_snapshot_get_settings,
+            coding::config_snapshot::config_snapshot_save_settings,
+            coding::config_snapshot::config_snapshot_list,
+            coding::config_snapshot::config_snapshot_create,
+            coding::config_snapshot::config_snapshot_restore,
+            coding::config_snapshot::config_snapshot_delete,
+            coding::config_snapshot::config_snapshot_diff,
+            coding::config_snapshot::get_snapshot_storage_report,
             // Image
             coding::image::image_get_workspace,
             coding::image::image_list_channels,
@@ -2217,7 +2494,7 @@ pub fn run() {
             coding::image::image_list_jobs,
             coding::image::image_create_job,
             coding::image::image_reveal_assets_dir,
-        ])
+        ]))
         .build(tauri::generate_context!())
         .map_err(|e| {
             error!("构建 Tauri 应用失败: {}", e);
@@ -2238,6 +2515,8 @@ pub fn run() {
                     }
                 }
 
+                tauri::RunEvent::Exit => update::install_pending_update_on_exit(app_handle),
+
                 _ => {}
             }
 
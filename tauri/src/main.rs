@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `list ...` / `status` run headless and exit; anything else starts the app
+    if let Some(code) = ai_toolbox_lib::cli::run_from_env() {
+        std::process::exit(code);
+    }
     ai_toolbox_lib::run()
 }
@@ -36,7 +36,13 @@ pub fn from_db_value(value: Value) -> AppSettings {
         auto_backup_interval_days: get_u32(&value, "auto_backup_interval_days", 7),
         auto_backup_max_keep: get_u32(&value, "auto_backup_max_keep", 10),
         last_auto_backup_time: get_opt_str(&value, "last_auto_backup_time"),
+        portable_export_enabled: get_bool(&value, "portable_export_enabled", false),
+        portable_export_path: get_str(&value, "portable_export_path", ""),
+        portable_export_interval_days: get_u32(&value, "portable_export_interval_days", 1).max(1),
+        portable_export_max_keep: get_u32(&value, "portable_export_max_keep", 7),
         auto_check_update: get_bool(&value, "auto_check_update", true),
+        update_manifest_url: get_str(&value, "update_manifest_url", ""),
+        update_release_repo: get_str(&value, "update_release_repo", ""),
         visible_tabs: normalize_visible_tabs_order(get_string_array(
             &value,
             "visible_tabs",
@@ -71,6 +77,8 @@ pub fn from_db_value(value: Value) -> AppSettings {
             false,
         ),
         backup_file_filter_rules: get_backup_file_filter_rules(&value),
+        read_only_mode: get_bool(&value, "read_only_mode", false),
+        startup_check_level: get_startup_check_level(&value),
     }
 }
 
@@ -118,6 +126,15 @@ fn get_proxy_mode(value: &Value) -> String {
         .to_string()
 }
 
+fn get_startup_check_level(value: &Value) -> String {
+    value
+        .get("startup_check_level")
+        .and_then(|v| v.as_str())
+        .filter(|level| matches!(*level, "report" | "safe" | "full"))
+        .unwrap_or("report")
+        .to_string()
+}
+
 fn get_u32(value: &Value, key: &str, default: u32) -> u32 {
     value
         .get(key)
@@ -351,7 +368,7 @@ fn get_backup_file_filter_rules(value: &Value) -> Vec<BackupFileFilterRule> {
 
 #[cfg(test)]
 mod tests {
-    use super::from_db_value;
+    use super::{from_db_value, to_db_value};
     use serde_json::json;
 
     #[test]
@@ -565,6 +582,33 @@ mod tests {
         assert!(settings.opencode_allow_clear_applied_oh_my_config);
     }
 
+    #[test]
+    fn read_only_mode_defaults_to_false_and_round_trips() {
+        assert!(!from_db_value(json!({})).read_only_mode);
+
+        let settings = from_db_value(json!({ "read_only_mode": true }));
+        assert!(settings.read_only_mode);
+        assert_eq!(to_db_value(&settings)["read_only_mode"], json!(true));
+    }
+
+    #[test]
+    fn update_source_defaults_to_empty_and_round_trips() {
+        let settings = from_db_value(json!({}));
+        assert_eq!(settings.update_manifest_url, "");
+        assert_eq!(settings.update_release_repo, "");
+
+        let settings = from_db_value(json!({
+            "update_manifest_url": "https://updates.example.com/latest.json",
+            "update_release_repo": "acme/ai-toolbox",
+        }));
+        let value = to_db_value(&settings);
+        assert_eq!(
+            value["update_manifest_url"],
+            json!("https://updates.example.com/latest.json")
+        );
+        assert_eq!(value["update_release_repo"], json!("acme/ai-toolbox"));
+    }
+
     #[test]
     fn codex_preserve_official_auth_on_switch_defaults_to_false() {
         let settings = from_db_value(json!({}));
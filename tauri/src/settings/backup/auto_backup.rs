@@ -130,7 +130,7 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
 }
 
 /// Check if a backup is due based on last backup time and interval
-fn is_backup_due(last_time: &Option<String>, interval_days: u32) -> bool {
+pub(super) fn is_backup_due(last_time: &Option<String>, interval_days: u32) -> bool {
     let Some(last_time_str) = last_time else {
         return true;
     };
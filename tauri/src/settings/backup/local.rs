@@ -16,15 +16,12 @@ use super::utils::{
     sanitize_restored_claude_database_for_current_os, should_filter_external_config_entry,
     write_backup_zip_contents, RestoreResult,
 };
-use crate::db::SqliteDbState;
+use crate::db::{encryption, portable, SqliteDbState};
 use crate::settings::store;
 use crate::settings::types::default_backup_file_filter_rules;
 
 fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 #[cfg(unix)]
@@ -647,6 +644,57 @@ pub fn get_database_path(app_handle: tauri::AppHandle) -> Result<String, String>
     Ok(db_path.to_string_lossy().to_string())
 }
 
+/// Whether the SQLite database is encrypted, and any change waiting for restart
+#[tauri::command]
+pub fn get_database_encryption_status(
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<encryption::DatabaseEncryptionStatus, String> {
+    encryption::encryption_status(state.db_path())
+}
+
+/// Encrypt or decrypt the SQLite database on the next start; the app has to
+/// be restarted to apply it
+#[tauri::command]
+pub fn set_database_encryption(
+    state: tauri::State<'_, SqliteDbState>,
+    enabled: bool,
+) -> Result<encryption::DatabaseEncryptionStatus, String> {
+    crate::settings::read_only::ensure_writable("set_database_encryption")?;
+    encryption::request_conversion(state.db_path(), enabled)?;
+    encryption::encryption_status(state.db_path())
+}
+
+/// Export the portable part of the database (library content without local
+/// paths or sync state) to a new file, for use on another machine
+#[tauri::command]
+pub fn export_portable_data(
+    state: tauri::State<'_, SqliteDbState>,
+    path: String,
+) -> Result<usize, String> {
+    state.with_conn(|conn| portable::export_portable(conn, Path::new(&path)))
+}
+
+/// Replace the portable part of the database with an export; settings and
+/// other machine data stay. Tool targets are re-derived on the next start.
+#[tauri::command]
+pub fn import_portable_data(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, SqliteDbState>,
+    path: String,
+) -> Result<usize, String> {
+    crate::settings::read_only::ensure_writable("import_portable_data")?;
+    let imported = state.with_conn_mut(|conn| portable::import_portable(conn, Path::new(&path)))?;
+
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let resync_flag = app_data_dir.join(".resync_required");
+    let _ = fs::write(&resync_flag, "1");
+
+    Ok(imported)
+}
+
 /// Open the app data directory in the file explorer
 #[tauri::command]
 pub fn open_app_data_dir(app_handle: tauri::AppHandle) -> Result<(), String> {
@@ -1,7 +1,9 @@
 pub mod auto_backup;
 pub mod local;
+pub mod portable_export;
 pub mod utils;
 pub mod webdav;
 
 pub use local::*;
+pub use portable_export::*;
 pub use webdav::*;
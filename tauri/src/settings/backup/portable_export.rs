@@ -0,0 +1,413 @@
+//! Scheduled export of the portable state bundle to a user-chosen directory
+//!
+//! A bundle is a zip with the portable database export (`db::portable`), the
+//! central skills library and a `manifest.json`. It is written on the
+//! `portable_export_interval_days` schedule to `portable_export_path` (e.g. a
+//! NAS mount) and rotated down to `portable_export_max_keep` files, so users
+//! who never export by hand still have a copy to move to another machine.
+//! The outcome of the last run is kept in `portable-export-status.json` in the
+//! app data dir rather than in settings, so saving settings from the UI never
+//! rolls it back.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::auto_backup::is_backup_due;
+use super::utils::{
+    add_directory_contents_to_zip, add_path_to_zip, add_text_to_zip, get_skills_dir,
+};
+use crate::db::{portable, SqliteDbState};
+use crate::settings::store;
+use crate::settings::types::AppSettings;
+
+const PORTABLE_BUNDLE_PREFIX: &str = "ai-toolbox-portable-";
+const PORTABLE_BUNDLE_EXTENSION: &str = ".zip";
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+const BUNDLE_MANIFEST_PATH: &str = "manifest.json";
+const BUNDLE_DB_PATH: &str = "portable/ai-toolbox-portable.db";
+const BUNDLE_SKILLS_DIR: &str = "skills";
+const STATUS_FILE_NAME: &str = "portable-export-status.json";
+
+/// Set while a bundle is being written, so a manual run never races the scheduler
+static EXPORT_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Outcome of the most recent export run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PortableExportStatus {
+    /// Last run, successful or not (RFC 3339); the schedule counts from here
+    pub last_attempt_at: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_file: Option<String>,
+    pub last_size_bytes: Option<u64>,
+    /// Error of the last run; cleared by the next successful one
+    pub last_error: Option<String>,
+}
+
+/// Scheduled export settings plus the last run, for the diagnostics view
+#[derive(Debug, Clone, Serialize)]
+pub struct PortableExportStatusDto {
+    pub enabled: bool,
+    pub export_dir: String,
+    pub interval_days: u32,
+    pub max_keep: u32,
+    pub next_due_at: Option<String>,
+    /// Bundles currently in `export_dir`
+    pub bundle_count: usize,
+    pub running: bool,
+    #[serde(flatten)]
+    pub last_run: PortableExportStatus,
+}
+
+fn status_file_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    Ok(app_data_dir.join(STATUS_FILE_NAME))
+}
+
+fn load_status(app_handle: &tauri::AppHandle) -> PortableExportStatus {
+    status_file_path(app_handle)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_status(app_handle: &tauri::AppHandle, status: &PortableExportStatus) -> Result<(), String> {
+    let path = status_file_path(app_handle)?;
+    let content = serde_json::to_string_pretty(status)
+        .map_err(|e| format!("Failed to serialize portable export status: {}", e))?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn is_bundle_file_name(name: &str) -> bool {
+    name.starts_with(PORTABLE_BUNDLE_PREFIX) && name.ends_with(PORTABLE_BUNDLE_EXTENSION)
+}
+
+/// Bundles in `dir`, newest first (the file names sort by timestamp)
+fn list_bundles(dir: &Path) -> Vec<PathBuf> {
+    let mut bundles: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| is_bundle_file_name(&entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect();
+    bundles.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    bundles
+}
+
+/// Delete all but the newest `max_keep` bundles in `dir` (0 keeps all);
+/// returns the deleted files
+fn rotate_bundles(dir: &Path, max_keep: u32) -> Vec<PathBuf> {
+    if max_keep == 0 {
+        return Vec::new();
+    }
+    list_bundles(dir)
+        .into_iter()
+        .skip(max_keep as usize)
+        .filter(|path| match std::fs::remove_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to delete old portable bundle {:?}: {}", path, e);
+                false
+            }
+        })
+        .collect()
+}
+
+fn count_skill_dirs(skills_dir: &Path) -> usize {
+    std::fs::read_dir(skills_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            entry.path().is_dir() && !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .count()
+}
+
+/// Write a new bundle to `export_dir`; returns its path and size
+fn write_bundle(
+    app_handle: &tauri::AppHandle,
+    export_dir: &Path,
+) -> Result<(PathBuf, u64), String> {
+    std::fs::create_dir_all(export_dir)
+        .map_err(|e| format!("Failed to create {}: {}", export_dir.display(), e))?;
+
+    let skills_dir = get_skills_dir(app_handle)?;
+    let sqlite_state = app_handle.state::<SqliteDbState>();
+    let temp_db = std::env::temp_dir().join(format!(
+        "ai-toolbox-portable-export-{}.db",
+        uuid::Uuid::new_v4().simple()
+    ));
+    let exported = sqlite_state.with_conn(|conn| {
+        let rows = portable::export_portable(conn, &temp_db)?;
+        let schema_version = crate::db::migrations::get_user_version(conn)?;
+        Ok((rows, schema_version))
+    });
+    let (rows, schema_version) = match exported {
+        Ok(exported) => exported,
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp_db);
+            return Err(e);
+        }
+    };
+
+    let file_name = format!(
+        "{}{}{}",
+        PORTABLE_BUNDLE_PREFIX,
+        Local::now().format("%Y%m%d-%H%M%S"),
+        PORTABLE_BUNDLE_EXTENSION
+    );
+    let bundle_path = export_dir.join(&file_name);
+    // Written under a temporary name and renamed, so rotation and the user
+    // never see a half-written bundle on a slow network mount
+    let partial_path = export_dir.join(format!("{}.part", file_name));
+
+    let written = (|| -> Result<(), String> {
+        let file = File::create(&partial_path)
+            .map_err(|e| format!("Failed to create {}: {}", partial_path.display(), e))?;
+        let mut zip = ZipWriter::new(file);
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        let manifest = serde_json::json!({
+            "format_version": BUNDLE_FORMAT_VERSION,
+            "app_version": app_handle.package_info().version.to_string(),
+            "schema_version": schema_version,
+            "created_at": Utc::now().to_rfc3339(),
+            "portable_rows": rows,
+            "skill_count": count_skill_dirs(&skills_dir),
+            "database_path": BUNDLE_DB_PATH,
+            "skills_path": BUNDLE_SKILLS_DIR,
+        });
+        add_text_to_zip(
+            &mut zip,
+            BUNDLE_MANIFEST_PATH,
+            &serde_json::to_string_pretty(&manifest)
+                .map_err(|e| format!("Failed to serialize bundle manifest: {}", e))?,
+            options,
+        )?;
+        add_path_to_zip(&mut zip, &temp_db, BUNDLE_DB_PATH, options)?;
+        add_directory_contents_to_zip(&mut zip, &skills_dir, BUNDLE_SKILLS_DIR, options)?;
+        zip.finish()
+            .map_err(|e| format!("Failed to finish portable bundle: {}", e))?;
+        std::fs::rename(&partial_path, &bundle_path)
+            .map_err(|e| format!("Failed to move bundle to {}: {}", bundle_path.display(), e))
+    })();
+    let _ = std::fs::remove_file(&temp_db);
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&partial_path);
+        return Err(e);
+    }
+
+    let size = std::fs::metadata(&bundle_path)
+        .map(|meta| meta.len())
+        .unwrap_or(0);
+    Ok((bundle_path, size))
+}
+
+/// Export a bundle with the current settings, rotate old ones and record the outcome
+fn run_export(app_handle: &tauri::AppHandle, settings: &AppSettings) -> PortableExportStatus {
+    let mut status = load_status(app_handle);
+    status.last_attempt_at = Some(Utc::now().to_rfc3339());
+
+    let export_dir = settings.portable_export_path.trim();
+    let result = if export_dir.is_empty() {
+        Err("No portable export directory is configured".to_string())
+    } else {
+        write_bundle(app_handle, Path::new(export_dir))
+    };
+    match result {
+        Ok((path, size)) => {
+            info!("Portable export: saved to {:?}", path);
+            let removed = rotate_bundles(Path::new(export_dir), settings.portable_export_max_keep);
+            if !removed.is_empty() {
+                info!("Portable export: deleted {} old bundle(s)", removed.len());
+            }
+            status.last_success_at = status.last_attempt_at.clone();
+            status.last_file = Some(path.to_string_lossy().to_string());
+            status.last_size_bytes = Some(size);
+            status.last_error = None;
+        }
+        Err(e) => {
+            warn!("Portable export failed: {}", e);
+            status.last_error = Some(e);
+        }
+    }
+
+    if let Err(e) = save_status(app_handle, &status) {
+        warn!("Failed to save portable export status: {}", e);
+    }
+    status
+}
+
+async fn run_export_exclusive(
+    app_handle: &tauri::AppHandle,
+    settings: AppSettings,
+) -> Result<PortableExportStatus, String> {
+    if EXPORT_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A portable export is already running".to_string());
+    }
+    let app = app_handle.clone();
+    let status = tokio::task::spawn_blocking(move || run_export(&app, &settings))
+        .await
+        .map_err(|e| format!("spawn_blocking failed: {}", e));
+    EXPORT_RUNNING.store(false, Ordering::SeqCst);
+
+    let status = status?;
+    match &status.last_error {
+        None => {
+            let _ = app_handle.emit("portable-export-completed", &status.last_success_at);
+        }
+        Some(e) => {
+            let _ = app_handle.emit("portable-export-failed", e);
+        }
+    }
+    Ok(status)
+}
+
+fn next_due_at(last_attempt_at: &Option<String>, interval_days: u32) -> Option<String> {
+    let last = DateTime::parse_from_rfc3339(last_attempt_at.as_deref()?).ok()?;
+    Some((last + chrono::Duration::days(interval_days as i64)).to_rfc3339())
+}
+
+fn build_status_dto(
+    app_handle: &tauri::AppHandle,
+    settings: &AppSettings,
+) -> PortableExportStatusDto {
+    let last_run = load_status(app_handle);
+    let export_dir = settings.portable_export_path.trim().to_string();
+    PortableExportStatusDto {
+        enabled: settings.portable_export_enabled,
+        bundle_count: if export_dir.is_empty() {
+            0
+        } else {
+            list_bundles(Path::new(&export_dir)).len()
+        },
+        export_dir,
+        interval_days: settings.portable_export_interval_days,
+        max_keep: settings.portable_export_max_keep,
+        next_due_at: settings
+            .portable_export_enabled
+            .then(|| {
+                next_due_at(
+                    &last_run.last_attempt_at,
+                    settings.portable_export_interval_days,
+                )
+            })
+            .flatten(),
+        running: EXPORT_RUNNING.load(Ordering::SeqCst),
+        last_run,
+    }
+}
+
+/// Start the portable export scheduler as a background task
+pub fn start_portable_export_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Stay clear of the auto-backup scheduler's first run
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        info!("Portable export scheduler started");
+
+        loop {
+            if let Err(e) = check_and_perform_export(&app_handle).await {
+                warn!("Portable export check failed: {}", e);
+            }
+
+            tokio::time::sleep(Duration::from_secs(600)).await;
+        }
+    });
+}
+
+async fn check_and_perform_export(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let sqlite_state = app_handle.state::<SqliteDbState>();
+    let settings = store::load_settings_from_sqlite_state(&sqlite_state)?;
+    if !settings.portable_export_enabled || settings.portable_export_path.trim().is_empty() {
+        return Ok(());
+    }
+    // Failed runs count as attempts too, so an unreachable NAS is retried on
+    // the next interval instead of every 10 minutes
+    if !is_backup_due(
+        &load_status(app_handle).last_attempt_at,
+        settings.portable_export_interval_days,
+    ) {
+        return Ok(());
+    }
+
+    info!("Portable export is due");
+    run_export_exclusive(app_handle, settings).await.map(|_| ())
+}
+
+/// Scheduled portable export settings and the outcome of the last run
+#[tauri::command]
+pub async fn get_portable_export_status(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<PortableExportStatusDto, String> {
+    let settings = store::load_settings_from_sqlite_state(&state)?;
+    Ok(build_status_dto(&app_handle, &settings))
+}
+
+/// Export a portable bundle to the configured directory now, whether or not
+/// the schedule is enabled; a failed run is reported in the returned status
+#[tauri::command]
+pub async fn run_portable_export_now(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<PortableExportStatusDto, String> {
+    let settings = store::load_settings_from_sqlite_state(&state)?;
+    run_export_exclusive(&app_handle, settings.clone()).await?;
+    Ok(build_status_dto(&app_handle, &settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_keeps_newest_bundles_and_ignores_other_files() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        for name in [
+            "ai-toolbox-portable-20260101-010000.zip",
+            "ai-toolbox-portable-20260102-010000.zip",
+            "ai-toolbox-portable-20260103-010000.zip",
+            "ai-toolbox-portable-20260104-010000.zip.part",
+            "ai-toolbox-backup-20260101-010000.zip",
+        ] {
+            std::fs::write(temp.path().join(name), b"zip").unwrap();
+        }
+
+        assert!(rotate_bundles(temp.path(), 0).is_empty());
+        let removed = rotate_bundles(temp.path(), 2);
+        assert_eq!(
+            removed,
+            vec![temp.path().join("ai-toolbox-portable-20260101-010000.zip")]
+        );
+        assert_eq!(
+            list_bundles(temp.path()),
+            vec![
+                temp.path().join("ai-toolbox-portable-20260103-010000.zip"),
+                temp.path().join("ai-toolbox-portable-20260102-010000.zip"),
+            ]
+        );
+        assert!(temp
+            .path()
+            .join("ai-toolbox-backup-20260101-010000.zip")
+            .exists());
+        assert_eq!(
+            next_due_at(&Some("2026-01-01T00:00:00+00:00".to_string()), 2).as_deref(),
+            Some("2026-01-03T00:00:00+00:00")
+        );
+    }
+}
@@ -108,14 +108,8 @@ pub fn restore_sqlite_database_snapshot_from_zip<R: Read + Seek>(
     }
 
     let sqlite_state = app_handle.state::<crate::db::SqliteDbState>();
-    let restore_result = sqlite_state.with_conn_mut(|conn| {
-        conn.restore(
-            rusqlite::MAIN_DB,
-            &temp_path,
-            None::<fn(rusqlite::backup::Progress)>,
-        )
-        .map_err(|error| format!("Failed to restore SQLite backup snapshot: {error}"))
-    });
+    let restore_result =
+        sqlite_state.with_conn_mut(|conn| crate::db::backup::restore_from_path(conn, &temp_path));
     let _ = std::fs::remove_file(&temp_path);
     restore_result?;
 
@@ -245,7 +239,7 @@ fn read_backup_schema_version<R: Read + Seek>(
         .and_then(|value| value.as_i64()))
 }
 
-fn add_path_to_zip<W: Write + Seek>(
+pub(crate) fn add_path_to_zip<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
     source_path: &Path,
     zip_path: &str,
@@ -276,10 +270,7 @@ fn build_db_manifest(engine: &str, schema_version: i64) -> serde_json::Value {
 
 /// Get home directory
 fn get_home_dir() -> Result<PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 pub fn get_claude_restore_dir() -> Result<PathBuf, String> {
@@ -1290,13 +1281,13 @@ pub fn normalize_backup_storage_path(input: &str) -> String {
         normalized_input.as_str()
     };
 
-    if let Some(config_dir) = dirs::config_dir() {
+    if let Some(config_dir) = crate::coding::tools::config_dir() {
         if let Some(relative_path) = strip_base_path(candidate, &config_dir) {
             return with_storage_prefix("%APPDATA%", &relative_path);
         }
     }
 
-    if let Some(home_dir) = dirs::home_dir() {
+    if let Some(home_dir) = crate::coding::tools::home_dir() {
         if let Some(relative_path) = strip_base_path(candidate, &home_dir) {
             return with_storage_prefix("~", &relative_path);
         }
@@ -23,10 +23,7 @@ use crate::settings::store;
 use crate::settings::types::default_backup_file_filter_rules;
 
 fn get_home_dir() -> Result<std::path::PathBuf, String> {
-    std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map(std::path::PathBuf::from)
-        .map_err(|_| "Failed to get home directory".to_string())
+    crate::coding::tools::env_home_dir().ok_or_else(|| "Failed to get home directory".to_string())
 }
 
 #[cfg(unix)]
@@ -1,3 +1,5 @@
+use super::policy::{self, PolicyStatus};
+use super::read_only::{self, ReadOnlyStatus};
 use super::store;
 use super::types::{AppSettings, BackupFileFilterPathOption};
 use crate::auto_launch;
@@ -20,6 +22,7 @@ pub async fn save_settings(
     settings: AppSettings,
 ) -> Result<(), String> {
     store::save_settings_to_sqlite_state(&sqlite_state, &settings)?;
+    read_only::apply_settings(&settings);
 
     if let Err(err) = tray::refresh_tray_menus(&app).await {
         log::warn!("Failed to refresh tray after saving settings: {err}");
@@ -28,6 +31,18 @@ pub async fn save_settings(
     Ok(())
 }
 
+/// Current read-only compliance mode state
+#[tauri::command]
+pub fn get_read_only_status() -> ReadOnlyStatus {
+    read_only::status()
+}
+
+/// Admin-provisioned group policy loaded at startup
+#[tauri::command]
+pub fn get_policy_status() -> PolicyStatus {
+    policy::status()
+}
+
 /// Normalize a backup custom entry path for portable storage and display.
 #[tauri::command]
 pub fn normalize_backup_custom_entry_path(path: String) -> String {
@@ -1,6 +1,8 @@
 pub(crate) mod adapter;
 pub mod backup;
 pub mod commands;
+pub mod policy;
+pub mod read_only;
 pub mod store;
 pub mod types;
 
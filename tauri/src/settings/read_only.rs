@@ -0,0 +1,99 @@
+//! Read-only compliance mode
+//!
+//! When enabled, mutating commands of the skills / MCP / WSL / SSH / config
+//! snapshot modules fail with a `READ_ONLY_MODE|<action>` error while listing,
+//! verification and diagnostic commands keep working. The mode is on when
+//! either the `read_only_mode` setting is set or the app was launched with
+//! `--read-only` (or `AI_TOOLBOX_READ_ONLY=1`); the launch flag cannot be
+//! turned off from the UI.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+use super::types::AppSettings;
+
+pub const READ_ONLY_CLI_FLAG: &str = "--read-only";
+pub const READ_ONLY_ENV: &str = "AI_TOOLBOX_READ_ONLY";
+pub const READ_ONLY_ERROR_PREFIX: &str = "READ_ONLY_MODE|";
+
+static LAUNCH_READ_ONLY: AtomicBool = AtomicBool::new(false);
+static SETTING_READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ReadOnlyStatus {
+    pub enabled: bool,
+    /// Enabled by `--read-only` / `AI_TOOLBOX_READ_ONLY`; cannot be disabled from settings
+    pub locked_by_launch_flag: bool,
+    pub setting_enabled: bool,
+}
+
+fn env_flag_enabled(value: Option<&str>) -> bool {
+    matches!(
+        value
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref(),
+        Some("1" | "true" | "yes" | "on")
+    )
+}
+
+/// Read the launch flag from process args and environment. Called once at startup.
+pub fn init_from_launch() {
+    let from_args = std::env::args_os()
+        .skip(1)
+        .any(|arg| arg == READ_ONLY_CLI_FLAG);
+    let from_env = env_flag_enabled(std::env::var(READ_ONLY_ENV).ok().as_deref());
+    LAUNCH_READ_ONLY.store(from_args || from_env, Ordering::SeqCst);
+}
+
+/// Mirror the persisted setting into the in-memory flag
+pub fn apply_settings(settings: &AppSettings) {
+    SETTING_READ_ONLY.store(settings.read_only_mode, Ordering::SeqCst);
+}
+
+pub fn is_read_only() -> bool {
+    LAUNCH_READ_ONLY.load(Ordering::SeqCst) || SETTING_READ_ONLY.load(Ordering::SeqCst)
+}
+
+pub fn status() -> ReadOnlyStatus {
+    ReadOnlyStatus {
+        enabled: is_read_only(),
+        locked_by_launch_flag: LAUNCH_READ_ONLY.load(Ordering::SeqCst),
+        setting_enabled: SETTING_READ_ONLY.load(Ordering::SeqCst),
+    }
+}
+
+/// Error string returned by guarded commands
+pub fn read_only_error(action: &str) -> String {
+    format!("{}{}", READ_ONLY_ERROR_PREFIX, action)
+}
+
+/// Guard for mutating commands: `ensure_writable("skills_install_git")?;`
+pub fn ensure_writable(action: &str) -> Result<(), String> {
+    if is_read_only() {
+        Err(read_only_error(action))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_flag_accepts_common_truthy_values() {
+        assert!(env_flag_enabled(Some("1")));
+        assert!(env_flag_enabled(Some(" TRUE ")));
+        assert!(!env_flag_enabled(Some("0")));
+        assert!(!env_flag_enabled(None));
+    }
+
+    #[test]
+    fn read_only_error_is_prefixed() {
+        assert_eq!(
+            read_only_error("mcp_delete_server"),
+            "READ_ONLY_MODE|mcp_delete_server"
+        );
+    }
+}
@@ -13,6 +13,18 @@ pub fn load_settings_from_sqlite_state(
     sqlite_state.with_conn(load_settings_from_sqlite_conn)
 }
 
+/// Error when the stored settings record cannot be read as settings; a
+/// missing record is fine, defaults are used until the first save
+pub fn check_settings_record(sqlite_state: &SqliteDbState) -> Result<(), String> {
+    let record = sqlite_state.with_conn(|conn| db_get(conn, DbTable::Settings, SETTINGS_ID))?;
+    match record {
+        Some(value) if !value.is_object() => {
+            Err("settings record is not a JSON object".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn save_settings_to_sqlite_state(
     sqlite_state: &SqliteDbState,
     settings: &AppSettings,
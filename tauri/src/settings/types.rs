@@ -117,8 +117,26 @@ pub struct AppSettings {
     pub auto_backup_max_keep: u32,
     /// Last auto backup time in ISO 8601 format
     pub last_auto_backup_time: Option<String>,
+    /// Periodically export the portable bundle (library + manifest) (default: false)
+    #[serde(default)]
+    pub portable_export_enabled: bool,
+    /// Directory that receives the scheduled portable bundles, e.g. a NAS mount
+    #[serde(default)]
+    pub portable_export_path: String,
+    /// Portable export interval in days (default: 1)
+    #[serde(default = "default_portable_export_interval_days")]
+    pub portable_export_interval_days: u32,
+    /// Max number of portable bundles to keep, 0 = unlimited (default: 7)
+    #[serde(default = "default_portable_export_max_keep")]
+    pub portable_export_max_keep: u32,
     /// Auto check for updates on startup (default: true)
     pub auto_check_update: bool,
+    /// Update manifest (latest.json) URL; empty uses the release repo's latest release
+    #[serde(default)]
+    pub update_manifest_url: String,
+    /// GitHub `owner/repo` for release links; empty uses the upstream repo
+    #[serde(default)]
+    pub update_release_repo: String,
     /// Visible tabs in the tab bar (default: all tabs shown)
     pub visible_tabs: Vec<String>,
     /// Sidebar hidden state by page
@@ -131,6 +149,12 @@ pub struct AppSettings {
     pub codex_unified_session_history_enabled: bool,
     /// File filter rules for backup/restore
     pub backup_file_filter_rules: Vec<BackupFileFilterRule>,
+    /// Read-only compliance mode: block mutating skills/MCP/sync commands (default: false)
+    #[serde(default)]
+    pub read_only_mode: bool,
+    /// Auto-fix level of the startup consistency check: "report", "safe" or "full" (default: "report")
+    #[serde(default = "default_startup_check_level")]
+    pub startup_check_level: String,
 }
 
 impl Default for AppSettings {
@@ -156,7 +180,13 @@ impl Default for AppSettings {
             auto_backup_interval_days: 7,
             auto_backup_max_keep: 10,
             last_auto_backup_time: None,
+            portable_export_enabled: false,
+            portable_export_path: String::new(),
+            portable_export_interval_days: default_portable_export_interval_days(),
+            portable_export_max_keep: default_portable_export_max_keep(),
             auto_check_update: true,
+            update_manifest_url: String::new(),
+            update_release_repo: String::new(),
             visible_tabs: vec![
                 "opencode".to_string(),
                 "claudecode".to_string(),
@@ -175,10 +205,24 @@ impl Default for AppSettings {
             codex_preserve_official_auth_on_switch: false,
             codex_unified_session_history_enabled: false,
             backup_file_filter_rules: default_backup_file_filter_rules(),
+            read_only_mode: false,
+            startup_check_level: default_startup_check_level(),
         }
     }
 }
 
+fn default_startup_check_level() -> String {
+    "report".to_string()
+}
+
+fn default_portable_export_interval_days() -> u32 {
+    1
+}
+
+fn default_portable_export_max_keep() -> u32 {
+    7
+}
+
 pub fn default_sidebar_hidden_by_page() -> HashMap<String, bool> {
     HashMap::from([
         ("opencode".to_string(), false),
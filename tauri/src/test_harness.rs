@@ -0,0 +1,159 @@
+//! Simulated user home for integration tests (`test-harness` feature)
+//!
+//! `SimulatedHome` creates a temporary directory and points the home override
+//! (`path_utils::set_home_override`) at it, so every path the app resolves
+//! from `~`, `%APPDATA%` or the home-derived variables lands inside it. The
+//! helpers lay out what a real machine has: installed tool dirs, skills,
+//! linked skills and MCP configs in each tool's own format. Onboarding,
+//! adoption, sync and verification can then run end to end against it on
+//! every OS without touching the real home.
+//!
+//! The override is process-wide, so homes are exclusive: `new` waits until
+//! the previous home was dropped.
+//!
+//! ```ignore
+//! let home = SimulatedHome::new();
+//! let skills_dir = home.install_tool("claude_code");
+//! home.write_skill(&skills_dir, "review", "Reviews a diff");
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use serde_json::Value;
+
+use crate::coding::tools::{self, builtin_tool_by_key, BuiltinTool};
+use crate::SqliteDbState;
+
+static HOME_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub struct SimulatedHome {
+    dir: tempfile::TempDir,
+    // Released last, after the override was reset and the dir removed
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl SimulatedHome {
+    pub fn new() -> Self {
+        // A failed test poisons the lock; the next home starts clean anyway
+        let lock = HOME_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+        let dir = tempfile::tempdir().expect("create simulated home");
+        tools::set_home_override(Some(dir.path().to_path_buf()));
+        Self { dir, _lock: lock }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Resolve a stored tool path (`~/...`, `%APPDATA%/...`) inside this home
+    pub fn resolve(&self, storage_path: &str) -> PathBuf {
+        tools::resolve_storage_path(storage_path)
+            .unwrap_or_else(|| panic!("resolve {storage_path} in simulated home"))
+    }
+
+    fn tool(key: &str) -> &'static BuiltinTool {
+        builtin_tool_by_key(key).unwrap_or_else(|| panic!("unknown tool {key}"))
+    }
+
+    /// Create the dirs that make `key` count as installed and return its
+    /// skills dir (the detect dir for tools without skills)
+    pub fn install_tool(&self, key: &str) -> PathBuf {
+        let tool = Self::tool(key);
+        if let Some(detect_dir) = tool.relative_detect_dir {
+            create_dir(&self.resolve(detect_dir));
+        }
+        match tool.relative_skills_dir.or(tool.relative_detect_dir) {
+            Some(dir) => {
+                let dir = self.resolve(dir);
+                create_dir(&dir);
+                dir
+            }
+            None => self.path().to_path_buf(),
+        }
+    }
+
+    pub fn skills_dir(&self, key: &str) -> PathBuf {
+        let dir = Self::tool(key)
+            .relative_skills_dir
+            .unwrap_or_else(|| panic!("{key} has no skills dir"));
+        self.resolve(dir)
+    }
+
+    /// Write `dir/<name>/SKILL.md` with frontmatter; returns the skill dir
+    pub fn write_skill(&self, dir: &Path, name: &str, description: &str) -> PathBuf {
+        let skill_dir = dir.join(name);
+        self.write_file(
+            &skill_dir.join("SKILL.md"),
+            &format!("---\nname: {name}\ndescription: {description}\n---\n\n# {name}\n"),
+        );
+        skill_dir
+    }
+
+    pub fn write_file(&self, path: &Path, content: &str) {
+        if let Some(parent) = path.parent() {
+            create_dir(parent);
+        }
+        std::fs::write(path, content).unwrap_or_else(|error| panic!("write {path:?}: {error}"));
+    }
+
+    /// Link `link` to the dir `target` the way skill syncs do: a symlink, or a
+    /// junction on Windows
+    pub fn link_dir(&self, target: &Path, link: &Path) {
+        if let Some(parent) = link.parent() {
+            create_dir(parent);
+        }
+        #[cfg(unix)]
+        let linked = std::os::unix::fs::symlink(target, link);
+        #[cfg(windows)]
+        let linked = junction::create(target, link);
+        linked.unwrap_or_else(|error| panic!("link {link:?} -> {target:?}: {error}"));
+    }
+
+    /// Write `servers` (name -> server config) into the MCP config of `key`,
+    /// in the tool's format and under its field; returns the config path
+    pub fn write_mcp_servers(&self, key: &str, servers: Value) -> PathBuf {
+        let tool = Self::tool(key);
+        let path = self.resolve(
+            tool.mcp_config_path
+                .unwrap_or_else(|| panic!("{key} has no MCP config")),
+        );
+        let field = tool.mcp_field.unwrap_or("mcpServers");
+        let mut config = serde_json::Map::new();
+        config.insert(field.to_string(), servers);
+        let content = match tool.mcp_config_format.unwrap_or("json") {
+            "toml" => toml::to_string(&config).expect("render TOML MCP config"),
+            _ => serde_json::to_string_pretty(&config).expect("render JSON MCP config"),
+        };
+        self.write_file(&path, &content);
+        path
+    }
+
+    /// Central skills repo inside this home, created on first use
+    pub fn central_repo(&self) -> PathBuf {
+        let dir = self.path().join(".ai-toolbox").join("skills");
+        create_dir(&dir);
+        dir
+    }
+
+    /// Fresh database with the current schema
+    pub fn database(&self) -> SqliteDbState {
+        SqliteDbState::in_memory_for_test().expect("open test database")
+    }
+}
+
+impl Default for SimulatedHome {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SimulatedHome {
+    fn drop(&mut self) {
+        tools::set_home_override(None);
+    }
+}
+
+fn create_dir(path: &Path) {
+    std::fs::create_dir_all(path).unwrap_or_else(|error| panic!("create {path:?}: {error}"));
+}
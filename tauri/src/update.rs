@@ -1,3 +1,5 @@
+use base64::Engine;
+use ed25519_dalek::{Signature, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -7,6 +9,11 @@ use tauri_plugin_updater::UpdaterExt;
 
 use crate::db::SqliteDbState;
 use crate::http_client;
+use crate::settings::store::load_settings_from_sqlite_state;
+use crate::settings::AppSettings;
+
+/// Upstream repo; forks and internal builds point elsewhere via settings
+const DEFAULT_RELEASE_REPO: &str = "coulsontl/ai-toolbox";
 
 /// Response from GitHub latest.json
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,6 +40,58 @@ pub struct UpdateCheckResult {
     pub release_notes: String,
     pub signature: Option<String>,
     pub url: Option<String>,
+    /// `owner/repo` the release links point at
+    pub release_repo: String,
+    /// Result of checking the platform signature against the embedded key;
+    /// `signature` is only passed on when this is `Verified`
+    pub signature_status: SignatureStatus,
+}
+
+/// Trust of a manifest's per-platform signature
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Signed by the key this build trusts
+    Verified,
+    /// No signature for this platform, or no key to check against
+    Unsigned,
+    /// Well-formed signature from a different key
+    Foreign,
+    /// Malformed signature, or its trusted comment fails verification
+    Invalid,
+}
+
+/// Where releases come from: settings, falling back to the upstream repo
+struct UpdateSource {
+    manifest_url: String,
+    release_repo: String,
+}
+
+impl UpdateSource {
+    fn from_settings(settings: &AppSettings) -> Self {
+        let release_repo = Some(settings.update_release_repo.trim().trim_matches('/'))
+            .filter(|repo| !repo.is_empty())
+            .unwrap_or(DEFAULT_RELEASE_REPO)
+            .to_string();
+        let manifest_url = Some(settings.update_manifest_url.trim())
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                format!(
+                    "https://github.com/{}/releases/latest/download/latest.json",
+                    release_repo
+                )
+            });
+        Self {
+            manifest_url,
+            release_repo,
+        }
+    }
+
+    fn load(state: &SqliteDbState) -> Result<Self, String> {
+        let settings = load_settings_from_sqlite_state(state)?;
+        Ok(Self::from_settings(&settings))
+    }
 }
 
 /// Check for updates from GitHub releases
@@ -41,22 +100,18 @@ pub async fn check_for_updates(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, SqliteDbState>,
 ) -> Result<UpdateCheckResult, String> {
-    const GITHUB_REPO: &str = "coulsontl/ai-toolbox";
-    let latest_json_url = format!(
-        "https://github.com/{}/releases/latest/download/latest.json",
-        GITHUB_REPO
-    );
+    let source = UpdateSource::load(&state)?;
 
     // Get current version from package info
     let current_version = app_handle.package_info().version.to_string();
 
     // Detect current platform
-    let current_platform = detect_current_platform();
+    let current_platforms = detect_current_platforms();
 
     // Fetch latest.json using http_client with proxy support
     let client = http_client::client(&state).await?;
     let response = client
-        .get(&latest_json_url)
+        .get(&source.manifest_url)
         .send()
         .await
         .map_err(|e| format!("Failed to fetch latest.json: {}", e))?;
@@ -77,11 +132,31 @@ pub async fn check_for_updates(
 
     let has_update = compare_versions(&latest_version, &current_version) > 0;
 
-    // Get signature and url for current platform
-    let platform_info = release.platforms.get(&current_platform);
+    // Get signature and url for current platform; without a matching entry the
+    // frontend falls back to the release page instead of the in-app updater
+    let platform_info = find_platform(&release.platforms, &current_platforms);
+    if platform_info.is_none() {
+        log::info!(
+            "latest.json has no artifact for {}, skipping in-app update",
+            current_platforms.join(" / ")
+        );
+    }
     let signature = platform_info
         .and_then(|p| p.signature.clone())
         .filter(|s| !s.is_empty());
+    let signature_status = match (signature.as_deref(), updater_pubkey(&app_handle)) {
+        (Some(signature), Some(pubkey)) => verify_manifest_signature(signature, &pubkey),
+        _ => SignatureStatus::Unsigned,
+    };
+    if signature_status != SignatureStatus::Verified {
+        log::warn!(
+            "latest.json from {} is not signed by the embedded key: {:?}",
+            source.manifest_url,
+            signature_status
+        );
+    }
+    // Unverified artifacts never reach the in-app updater
+    let signature = signature.filter(|_| signature_status == SignatureStatus::Verified);
     let url = platform_info
         .and_then(|p| p.url.clone())
         .filter(|s| !s.is_empty());
@@ -92,40 +167,196 @@ pub async fn check_for_updates(
         latest_version: latest_version.clone(),
         release_url: format!(
             "https://github.com/{}/releases/tag/v{}",
-            GITHUB_REPO, latest_version
+            source.release_repo, latest_version
         ),
         release_notes: release.notes.unwrap_or_default(),
         signature,
         url,
+        release_repo: source.release_repo,
+        signature_status,
     })
 }
 
-/// Detect current platform string for matching latest.json
-#[allow(unreachable_code)]
-fn detect_current_platform() -> String {
-    #[cfg(target_os = "windows")]
-    {
-        return "windows-x86_64".to_string();
+/// minisign public key from `plugins.updater.pubkey`
+fn updater_pubkey(app_handle: &tauri::AppHandle) -> Option<String> {
+    app_handle
+        .config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|pubkey| pubkey.as_str())
+        .map(str::to_string)
+}
+
+/// Non-comment line `index` of a base64-wrapped minisign file, decoded
+fn minisign_line(text: &str, index: usize) -> Option<Vec<u8>> {
+    let line = text
+        .lines()
+        .filter(|line| {
+            !line.starts_with("untrusted comment:") && !line.starts_with("trusted comment:")
+        })
+        .nth(index)?;
+    base64::engine::general_purpose::STANDARD
+        .decode(line.trim())
+        .ok()
+}
+
+fn decode_minisign_file(encoded: &str) -> Option<String> {
+    let raw = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()?;
+    String::from_utf8(raw).ok()
+}
+
+/// Check a manifest signature (base64 of a minisign `.sig` file) against the
+/// updater public key (base64 of a minisign `.pub` file). The artifact itself
+/// is only downloaded at install time, so this verifies the key id and the
+/// global signature over the signature and its trusted comment; the updater
+/// plugin still verifies the artifact bytes before installing.
+fn verify_manifest_signature(signature: &str, pubkey: &str) -> SignatureStatus {
+    let Some(key) = decode_minisign_file(pubkey)
+        .and_then(|text| minisign_line(&text, 0))
+        .filter(|key| key.len() == 42)
+    else {
+        return SignatureStatus::Unsigned;
+    };
+    let Some(text) = decode_minisign_file(signature) else {
+        return SignatureStatus::Invalid;
+    };
+    let (Some(sig), Some(global), Some(comment)) = (
+        minisign_line(&text, 0).filter(|sig| sig.len() == 74),
+        minisign_line(&text, 1).filter(|global| global.len() == 64),
+        text.lines()
+            .find_map(|line| line.strip_prefix("trusted comment: ")),
+    ) else {
+        return SignatureStatus::Invalid;
+    };
+
+    // Layout: 2-byte algorithm, 8-byte key id, then key or signature bytes
+    if sig[2..10] != key[2..10] {
+        return SignatureStatus::Foreign;
     }
+    let Ok(verifying_key) = VerifyingKey::from_bytes(key[10..].try_into().unwrap_or(&[0; 32]))
+    else {
+        return SignatureStatus::Invalid;
+    };
+    let global = Signature::from_bytes(global[..].try_into().unwrap_or(&[0; 64]));
+    let mut signed = sig[10..].to_vec();
+    signed.extend_from_slice(comment.as_bytes());
+    match verifying_key.verify_strict(&signed, &global) {
+        Ok(()) => SignatureStatus::Verified,
+        Err(_) => SignatureStatus::Invalid,
+    }
+}
+
+/// Platform keys for matching latest.json, most specific first
+fn detect_current_platforms() -> Vec<String> {
+    platform_candidates(
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        cfg!(target_env = "musl"),
+    )
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        return "linux-x86_64".to_string();
+/// Build `{os}-{arch}` keys the way the release manifest names them; musl
+/// builds prefer a `-musl` entry and fall back to the plain one
+fn platform_candidates(os: &str, arch: &str, musl: bool) -> Vec<String> {
+    let os = match os {
+        "macos" => "darwin",
+        other => other,
+    };
+    let arch = match arch {
+        "arm" => "armv7",
+        "x86" => "i686",
+        other => other,
+    };
+    let base = format!("{}-{}", os, arch);
+    if musl {
+        vec![format!("{}-musl", base), base]
+    } else {
+        vec![base]
     }
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        #[cfg(target_arch = "aarch64")]
-        {
-            return "darwin-aarch64".to_string();
+/// Manifest entry for the first candidate present. Bundle-specific keys such
+/// as `linux-aarch64-appimage` also match; `-musl` entries only match a musl
+/// candidate exactly.
+fn find_platform<'a>(
+    platforms: &'a HashMap<String, PlatformInfo>,
+    candidates: &[String],
+) -> Option<&'a PlatformInfo> {
+    candidates.iter().find_map(|candidate| {
+        platforms.get(candidate).or_else(|| {
+            let prefix = format!("{}-", candidate);
+            platforms
+                .iter()
+                .filter(|(key, _)| key.starts_with(&prefix) && !key.ends_with("-musl"))
+                .min_by_key(|(key, _)| key.as_str())
+                .map(|(_, info)| info)
+        })
+    })
+}
+
+/// Proxy env vars set for the updater plugin (it reads them for its own
+/// requests), restored when dropped
+struct UpdaterProxyEnv {
+    saved: Vec<(&'static str, Option<String>)>,
+}
+
+const PROXY_ENV_VARS: [&str; 4] = ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"];
+
+impl UpdaterProxyEnv {
+    async fn apply(state: &SqliteDbState) -> Result<Self, String> {
+        let (proxy_mode, proxy_url) = http_client::get_proxy_from_settings(state).await?;
+        let saved = PROXY_ENV_VARS
+            .iter()
+            .map(|key| (*key, std::env::var(key).ok()))
+            .collect();
+
+        match proxy_mode {
+            http_client::ProxyMode::Direct => {
+                for key in PROXY_ENV_VARS {
+                    std::env::remove_var(key);
+                }
+            }
+            http_client::ProxyMode::Custom => {
+                if !proxy_url.is_empty() {
+                    for key in PROXY_ENV_VARS {
+                        std::env::set_var(key, &proxy_url);
+                    }
+                }
+            }
+            http_client::ProxyMode::System => {}
         }
-        #[cfg(target_arch = "x86_64")]
-        {
-            return "darwin-x86_64".to_string();
+        Ok(Self { saved })
+    }
+}
+
+impl Drop for UpdaterProxyEnv {
+    fn drop(&mut self) {
+        for (key, value) in &self.saved {
+            match value {
+                Some(value) => std::env::set_var(key, value),
+                None => std::env::remove_var(key),
+            }
         }
     }
+}
 
-    "unknown".to_string()
+/// Updater plugin pointed at the same manifest `check_for_updates` read
+/// instead of the endpoint baked into the config
+fn build_updater(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+) -> Result<tauri_plugin_updater::Updater, String> {
+    let source = UpdateSource::load(state)?;
+    let endpoint = tauri::Url::parse(&source.manifest_url)
+        .map_err(|e| format!("Invalid update manifest URL: {}", e))?;
+    app.updater_builder()
+        .endpoints(vec![endpoint])
+        .and_then(|builder| builder.build())
+        .map_err(|e| e.to_string())
 }
 
 /// Install the update
@@ -134,37 +365,10 @@ pub async fn install_update(
     app: tauri::AppHandle,
     state: tauri::State<'_, SqliteDbState>,
 ) -> Result<bool, String> {
-    // Get proxy settings from database
-    let (proxy_mode, proxy_url) = http_client::get_proxy_from_settings(&state).await?;
-
-    // Set proxy environment variables for the updater plugin
-    // (tauri-plugin-updater reads these env vars for proxy configuration)
-    let old_http_proxy = std::env::var("HTTP_PROXY").ok();
-    let old_https_proxy = std::env::var("HTTPS_PROXY").ok();
-    let old_http_proxy_lower = std::env::var("http_proxy").ok();
-    let old_https_proxy_lower = std::env::var("https_proxy").ok();
-
-    match proxy_mode {
-        http_client::ProxyMode::Direct => {
-            std::env::remove_var("HTTP_PROXY");
-            std::env::remove_var("HTTPS_PROXY");
-            std::env::remove_var("http_proxy");
-            std::env::remove_var("https_proxy");
-        }
-        http_client::ProxyMode::Custom => {
-            if !proxy_url.is_empty() {
-                std::env::set_var("HTTP_PROXY", &proxy_url);
-                std::env::set_var("HTTPS_PROXY", &proxy_url);
-                std::env::set_var("http_proxy", &proxy_url);
-                std::env::set_var("https_proxy", &proxy_url);
-            }
-        }
-        http_client::ProxyMode::System => {}
-    }
+    let _proxy_env = UpdaterProxyEnv::apply(&state).await?;
 
-    // Check for updates using the updater plugin
-    let updater = app.updater().map_err(|e| e.to_string())?;
-    let result = match updater.check().await {
+    let updater = build_updater(&app, &state)?;
+    match updater.check().await {
         Ok(Some(update)) => {
             // Emit download started event
             let _ = app.emit(
@@ -258,31 +462,330 @@ pub async fn install_update(
         }
         Ok(None) => Err("No update available".to_string()),
         Err(e) => Err(format!("Failed to check for updates: {}", e)),
-    };
+    }
+}
 
-    // Restore original environment variables
-    if let old @ Some(_) = old_http_proxy {
-        std::env::set_var("HTTP_PROXY", old.unwrap());
-    } else {
-        std::env::remove_var("HTTP_PROXY");
+const PENDING_UPDATE_DIR: &str = "pending-update";
+const PENDING_RECORD_FILE: &str = "pending.json";
+const PENDING_ARTIFACT_FILE: &str = "update.bin";
+
+/// Artifact downloaded for install-on-quit, persisted next to it so a quit
+/// that could not install (crash, kill) installs on the next launch instead
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingUpdateRecord {
+    version: String,
+    /// Manifest signature the artifact was verified against
+    signature: String,
+    /// sha256 of the artifact, checked again before installing
+    sha256: String,
+    downloaded_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingUpdatePhase {
+    #[default]
+    None,
+    Downloading,
+    /// Downloaded and verified; installs when the app exits
+    Ready,
+    Failed,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PendingUpdateStatus {
+    pub phase: PendingUpdatePhase,
+    pub version: Option<String>,
+    /// Download progress in percent while `Downloading`
+    pub progress: u32,
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+struct PendingUpdateInner {
+    status: PendingUpdateStatus,
+    /// Updater handle for the ready artifact; installing needs the plugin's
+    /// `Update`, which only a manifest check yields
+    update: Option<tauri_plugin_updater::Update>,
+}
+
+/// Install-on-quit state, managed by the app
+#[derive(Default)]
+pub struct PendingUpdateState(std::sync::Mutex<PendingUpdateInner>);
+
+impl PendingUpdateState {
+    pub(crate) fn status(&self) -> PendingUpdateStatus {
+        self.0
+            .lock()
+            .map(|inner| inner.status.clone())
+            .unwrap_or_default()
     }
-    if let old @ Some(_) = old_https_proxy {
-        std::env::set_var("HTTPS_PROXY", old.unwrap());
-    } else {
-        std::env::remove_var("HTTPS_PROXY");
+
+    fn set_status(&self, status: PendingUpdateStatus) {
+        if let Ok(mut inner) = self.0.lock() {
+            inner.status = status;
+        }
     }
-    if let old @ Some(_) = old_http_proxy_lower {
-        std::env::set_var("http_proxy", old.unwrap());
-    } else {
-        std::env::remove_var("http_proxy");
+}
+
+fn pending_update_dir(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    use tauri::Manager;
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(PENDING_UPDATE_DIR))
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn write_pending_update(
+    dir: &std::path::Path,
+    record: &PendingUpdateRecord,
+    bytes: &[u8],
+) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    std::fs::write(dir.join(PENDING_ARTIFACT_FILE), bytes)
+        .map_err(|e| format!("Failed to save update artifact: {}", e))?;
+    let raw = serde_json::to_vec_pretty(record).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(PENDING_RECORD_FILE), raw)
+        .map_err(|e| format!("Failed to save pending update: {}", e))
+}
+
+fn read_pending_record(dir: &std::path::Path) -> Option<PendingUpdateRecord> {
+    let raw = std::fs::read(dir.join(PENDING_RECORD_FILE)).ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+/// Artifact bytes for `record`, refused when the file changed on disk
+fn read_pending_artifact(
+    dir: &std::path::Path,
+    record: &PendingUpdateRecord,
+) -> Result<Vec<u8>, String> {
+    let bytes = std::fs::read(dir.join(PENDING_ARTIFACT_FILE))
+        .map_err(|e| format!("Failed to read update artifact: {}", e))?;
+    if sha256_hex(&bytes) != record.sha256 {
+        return Err("Update artifact does not match its recorded checksum".to_string());
     }
-    if let old @ Some(_) = old_https_proxy_lower {
-        std::env::set_var("https_proxy", old.unwrap());
-    } else {
-        std::env::remove_var("https_proxy");
+    Ok(bytes)
+}
+
+fn discard_pending_update(dir: &std::path::Path) {
+    if dir.exists() {
+        if let Err(e) = std::fs::remove_dir_all(dir) {
+            log::warn!("Failed to remove pending update {}: {}", dir.display(), e);
+        }
+    }
+}
+
+/// Download and verify the update in the background; it installs when the
+/// app exits instead of interrupting work now
+#[tauri::command]
+pub async fn download_update_on_quit(
+    app: tauri::AppHandle,
+    pending: tauri::State<'_, PendingUpdateState>,
+) -> Result<PendingUpdateStatus, String> {
+    let status = pending.status();
+    if matches!(
+        status.phase,
+        PendingUpdatePhase::Downloading | PendingUpdatePhase::Ready
+    ) {
+        return Ok(status);
+    }
+    pending.set_status(PendingUpdateStatus {
+        phase: PendingUpdatePhase::Downloading,
+        ..Default::default()
+    });
+
+    let task_app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        use tauri::Manager;
+        let pending = task_app.state::<PendingUpdateState>();
+        if let Err(error) = download_pending_update(&task_app, &pending).await {
+            log::warn!("Background update download failed: {}", error);
+            pending.set_status(PendingUpdateStatus {
+                phase: PendingUpdatePhase::Failed,
+                error: Some(error),
+                ..Default::default()
+            });
+        }
+    });
+
+    Ok(pending.status())
+}
+
+async fn download_pending_update(
+    app: &tauri::AppHandle,
+    pending: &PendingUpdateState,
+) -> Result<(), String> {
+    use tauri::Manager;
+    let state = app.state::<SqliteDbState>();
+    let _proxy_env = UpdaterProxyEnv::apply(&state).await?;
+    let update = build_updater(app, &state)?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?
+        .ok_or_else(|| "No update available".to_string())?;
+    let version = update.version.trim_start_matches('v').to_string();
+
+    let mut downloaded = 0u64;
+    // `download` verifies the artifact against the embedded key
+    let bytes = update
+        .download(
+            |chunk_length, content_length| {
+                downloaded += chunk_length as u64;
+                if let Some(total) = content_length.filter(|total| *total > 0) {
+                    pending.set_status(PendingUpdateStatus {
+                        phase: PendingUpdatePhase::Downloading,
+                        version: Some(version.clone()),
+                        progress: (downloaded * 100 / total) as u32,
+                        error: None,
+                    });
+                }
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| format!("Failed to download update: {}", e))?;
+
+    let record = PendingUpdateRecord {
+        version: version.clone(),
+        signature: update.signature.clone(),
+        sha256: sha256_hex(&bytes),
+        downloaded_at: chrono::Utc::now().timestamp_millis(),
+    };
+    write_pending_update(&pending_update_dir(app)?, &record, &bytes)?;
+    log::info!("Update {} downloaded, installs on quit", version);
+
+    let mut inner = pending.0.lock().map_err(|e| e.to_string())?;
+    inner.update = Some(update);
+    inner.status = PendingUpdateStatus {
+        phase: PendingUpdatePhase::Ready,
+        version: Some(version),
+        progress: 100,
+        error: None,
+    };
+    Ok(())
+}
+
+/// Install-on-quit progress for the UI
+#[tauri::command]
+pub fn get_pending_update_status(
+    pending: tauri::State<'_, PendingUpdateState>,
+) -> PendingUpdateStatus {
+    pending.status()
+}
+
+/// Install the ready update while the app exits
+pub fn install_pending_update_on_exit(app: &tauri::AppHandle) {
+    use tauri::Manager;
+    let Some(pending) = app.try_state::<PendingUpdateState>() else {
+        return;
+    };
+    let Ok(mut inner) = pending.0.lock() else {
+        return;
+    };
+    let Some(update) = inner.update.take() else {
+        return;
+    };
+    let Ok(dir) = pending_update_dir(app) else {
+        return;
+    };
+    let Some(record) = read_pending_record(&dir) else {
+        return;
+    };
+
+    match read_pending_artifact(&dir, &record).and_then(|bytes| {
+        update
+            .install(bytes)
+            .map_err(|e| format!("Failed to install update: {}", e))
+    }) {
+        Ok(()) => {
+            log::info!("Update {} installed on quit", record.version);
+            discard_pending_update(&dir);
+        }
+        // Left on disk; the next launch retries
+        Err(error) => log::warn!("Install on quit failed: {}", error),
+    }
+}
+
+/// Whether a persisted pending update should be installed now: it must still
+/// be newer than this build and be the artifact the manifest announces
+fn pending_update_applies(
+    record: &PendingUpdateRecord,
+    current_version: &str,
+    announced_version: &str,
+    announced_signature: &str,
+) -> bool {
+    compare_versions(&record.version, current_version) > 0
+        && announced_version.trim_start_matches('v') == record.version
+        && announced_signature == record.signature
+}
+
+/// Install an update left pending by a quit that did not install it (crash,
+/// forced kill), then restart. Anything stale or mismatched is discarded.
+pub async fn resume_pending_update(app: tauri::AppHandle) {
+    use tauri::Manager;
+    let Ok(dir) = pending_update_dir(&app) else {
+        return;
+    };
+    let Some(record) = read_pending_record(&dir) else {
+        discard_pending_update(&dir);
+        return;
+    };
+    let current_version = app.package_info().version.to_string();
+    if compare_versions(&record.version, &current_version) <= 0 {
+        // Already running it (or newer)
+        discard_pending_update(&dir);
+        return;
     }
 
-    result
+    let state = app.state::<SqliteDbState>();
+    let Ok(_proxy_env) = UpdaterProxyEnv::apply(&state).await else {
+        return;
+    };
+    let Ok(updater) = build_updater(&app, &state) else {
+        return;
+    };
+    let update = match updater.check().await {
+        Ok(Some(update))
+            if pending_update_applies(
+                &record,
+                &current_version,
+                &update.version,
+                &update.signature,
+            ) =>
+        {
+            update
+        }
+        Ok(_) => {
+            log::info!(
+                "Discarding pending update {}: no longer announced",
+                record.version
+            );
+            discard_pending_update(&dir);
+            return;
+        }
+        // Offline: keep the artifact for the next launch
+        Err(_) => return,
+    };
+
+    let installed = read_pending_artifact(&dir, &record).and_then(|bytes| {
+        update
+            .install(bytes)
+            .map_err(|e| format!("Failed to install update: {}", e))
+    });
+    discard_pending_update(&dir);
+    match installed {
+        Ok(()) => {
+            log::info!("Installed pending update {}, restarting", record.version);
+            app.restart();
+        }
+        Err(error) => log::warn!("Pending update install failed: {}", error),
+    }
 }
 
 /// Compare two version strings (e.g., "1.2.3" vs "1.2.4")
@@ -307,3 +810,186 @@ fn compare_versions(v1: &str, v2: &str) -> i32 {
 
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(url: &str) -> PlatformInfo {
+        PlatformInfo {
+            signature: Some("sig".to_string()),
+            url: Some(url.to_string()),
+        }
+    }
+
+    /// minisign `.pub` and `.sig` files, base64-wrapped like the updater uses
+    fn minisign_pair(seed: u8, key_id: [u8; 8], comment: &str) -> (String, String) {
+        use ed25519_dalek::{Signer, SigningKey};
+        let b64 = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+        let signing = SigningKey::from_bytes(&[seed; 32]);
+
+        let mut key = b"Ed".to_vec();
+        key.extend_from_slice(&key_id);
+        key.extend_from_slice(signing.verifying_key().as_bytes());
+        let pubkey = format!("untrusted comment: minisign public key\n{}\n", b64(&key));
+
+        let mut sig = b"ED".to_vec();
+        sig.extend_from_slice(&key_id);
+        sig.extend_from_slice(&signing.sign(b"artifact").to_bytes());
+        let mut signed = sig[10..].to_vec();
+        signed.extend_from_slice(comment.as_bytes());
+        let global = signing.sign(&signed).to_bytes();
+        let signature = format!(
+            "untrusted comment: signature\n{}\ntrusted comment: {}\n{}\n",
+            b64(&sig),
+            comment,
+            b64(&global)
+        );
+        (b64(pubkey.as_bytes()), b64(signature.as_bytes()))
+    }
+
+    #[test]
+    fn manifest_signature_is_checked_against_the_embedded_key() {
+        let (pubkey, signature) = minisign_pair(1, [1; 8], "timestamp:1\tfile:app.tar.gz");
+        assert_eq!(
+            verify_manifest_signature(&signature, &pubkey),
+            SignatureStatus::Verified
+        );
+
+        // Signed by someone else's key
+        let (_, foreign) = minisign_pair(2, [2; 8], "timestamp:1\tfile:app.tar.gz");
+        assert_eq!(
+            verify_manifest_signature(&foreign, &pubkey),
+            SignatureStatus::Foreign
+        );
+
+        // Same key id, but the trusted comment was not signed by our key
+        let (_, forged) = minisign_pair(3, [1; 8], "timestamp:1\tfile:app.tar.gz");
+        assert_eq!(
+            verify_manifest_signature(&forged, &pubkey),
+            SignatureStatus::Invalid
+        );
+        assert_eq!(
+            verify_manifest_signature("not base64!", &pubkey),
+            SignatureStatus::Invalid
+        );
+        assert_eq!(
+            verify_manifest_signature(&signature, ""),
+            SignatureStatus::Unsigned
+        );
+    }
+
+    #[test]
+    fn pending_update_installs_only_the_announced_newer_artifact() {
+        let record = PendingUpdateRecord {
+            version: "1.2.0".to_string(),
+            signature: "sig".to_string(),
+            sha256: String::new(),
+            downloaded_at: 0,
+        };
+        assert!(pending_update_applies(&record, "1.1.0", "v1.2.0", "sig"));
+        // Already installed, or the manifest moved on
+        assert!(!pending_update_applies(&record, "1.2.0", "1.2.0", "sig"));
+        assert!(!pending_update_applies(&record, "1.1.0", "1.3.0", "sig"));
+        assert!(!pending_update_applies(&record, "1.1.0", "1.2.0", "other"));
+    }
+
+    #[test]
+    fn pending_artifact_is_refused_when_changed_on_disk() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join(PENDING_UPDATE_DIR);
+        let record = PendingUpdateRecord {
+            version: "1.2.0".to_string(),
+            signature: "sig".to_string(),
+            sha256: sha256_hex(b"artifact"),
+            downloaded_at: 1,
+        };
+        write_pending_update(&dir, &record, b"artifact").unwrap();
+
+        let loaded = read_pending_record(&dir).unwrap();
+        assert_eq!(read_pending_artifact(&dir, &loaded).unwrap(), b"artifact");
+
+        std::fs::write(dir.join(PENDING_ARTIFACT_FILE), b"tampered").unwrap();
+        assert!(read_pending_artifact(&dir, &loaded).is_err());
+
+        discard_pending_update(&dir);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn update_source_defaults_to_upstream_and_derives_manifest_from_repo() {
+        let mut settings = AppSettings::default();
+        let source = UpdateSource::from_settings(&settings);
+        assert_eq!(source.release_repo, DEFAULT_RELEASE_REPO);
+        assert_eq!(
+            source.manifest_url,
+            "https://github.com/coulsontl/ai-toolbox/releases/latest/download/latest.json"
+        );
+
+        settings.update_release_repo = " acme/ai-toolbox/ ".to_string();
+        let source = UpdateSource::from_settings(&settings);
+        assert_eq!(source.release_repo, "acme/ai-toolbox");
+        assert_eq!(
+            source.manifest_url,
+            "https://github.com/acme/ai-toolbox/releases/latest/download/latest.json"
+        );
+
+        settings.update_manifest_url = "https://updates.example.com/latest.json".to_string();
+        assert_eq!(
+            UpdateSource::from_settings(&settings).manifest_url,
+            "https://updates.example.com/latest.json"
+        );
+    }
+
+    #[test]
+    fn platform_candidates_follow_target_arch_and_libc() {
+        assert_eq!(
+            platform_candidates("linux", "aarch64", false),
+            vec!["linux-aarch64"]
+        );
+        assert_eq!(
+            platform_candidates("linux", "arm", false),
+            vec!["linux-armv7"]
+        );
+        assert_eq!(
+            platform_candidates("linux", "x86_64", true),
+            vec!["linux-x86_64-musl", "linux-x86_64"]
+        );
+        assert_eq!(
+            platform_candidates("macos", "aarch64", false),
+            vec!["darwin-aarch64"]
+        );
+        assert_eq!(
+            platform_candidates("windows", "aarch64", false),
+            vec!["windows-aarch64"]
+        );
+    }
+
+    #[test]
+    fn find_platform_falls_back_through_candidates_and_bundle_keys() {
+        let platforms = HashMap::from([
+            ("linux-x86_64".to_string(), info("glibc")),
+            ("linux-x86_64-musl".to_string(), info("musl")),
+            ("linux-aarch64-appimage".to_string(), info("arm-appimage")),
+        ]);
+        let url = |candidates: Vec<String>| {
+            find_platform(&platforms, &candidates).and_then(|info| info.url.clone())
+        };
+
+        assert_eq!(
+            url(platform_candidates("linux", "x86_64", true)).as_deref(),
+            Some("musl")
+        );
+        assert_eq!(
+            url(platform_candidates("linux", "aarch64", false)).as_deref(),
+            Some("arm-appimage")
+        );
+        // musl artifacts are never offered to glibc builds
+        let musl_only = HashMap::from([("linux-x86_64-musl".to_string(), info("musl"))]);
+        assert!(
+            find_platform(&musl_only, &platform_candidates("linux", "x86_64", false)).is_none()
+        );
+        // Missing platform: no in-app update, caller falls back to the release page
+        assert!(url(platform_candidates("linux", "riscv64", false)).is_none());
+    }
+}
@@ -0,0 +1,64 @@
+use ai_toolbox_lib::coding::mcp::config_sync::import_servers_from_tool;
+use ai_toolbox_lib::coding::tools::{get_mcp_runtime_tools, is_tool_installed};
+use ai_toolbox_lib::test_harness::SimulatedHome;
+use serde_json::json;
+
+#[test]
+fn imports_mcp_servers_from_json_and_toml_configs() {
+    let home = SimulatedHome::new();
+    let db = home.database();
+    let server = json!({
+        "context7": {
+            "type": "stdio",
+            "command": "npx",
+            "args": ["-y", "@upstash/context7-mcp"]
+        }
+    });
+    for key in ["claude_code", "codex"] {
+        home.install_tool(key);
+        let config = home.write_mcp_servers(key, server.clone());
+        assert!(config.starts_with(home.path()));
+    }
+
+    let tools = get_mcp_runtime_tools(&[]);
+    for key in ["claude_code", "codex"] {
+        let tool = tools
+            .iter()
+            .find(|tool| tool.key == key)
+            .expect("builtin MCP tool");
+        assert!(is_tool_installed(tool), "{key} is installed");
+        let imported = import_servers_from_tool(&db, tool).expect("import servers");
+        let names: Vec<&str> = imported
+            .servers
+            .iter()
+            .map(|server| server.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["context7"], "servers of {key}");
+        assert!(imported.warnings.is_empty(), "warnings of {key}");
+    }
+}
+
+#[test]
+fn imports_mcp_servers_from_a_json_config_the_tool_accepts() {
+    let home = SimulatedHome::new();
+    let db = home.database();
+    home.install_tool("claude_code");
+    let config = home.write_mcp_servers("claude_code", json!({}));
+    home.write_file(
+        &config,
+        "\u{feff}{\n  // pinned\n  \"mcpServers\": {\n    \"fetch\": { \"command\": \"npx\" },\n    \"fetch\": { \"command\": \"uvx\", \"args\": [\"mcp-server-fetch\"], },\n  },\n}\n",
+    );
+
+    let tool = get_mcp_runtime_tools(&[])
+        .into_iter()
+        .find(|tool| tool.key == "claude_code")
+        .expect("builtin MCP tool");
+    let imported = import_servers_from_tool(&db, &tool).expect("import servers");
+
+    assert_eq!(imported.servers.len(), 1);
+    assert_eq!(imported.servers[0].server_config["command"], "uvx");
+    assert_eq!(imported.warnings.len(), 3, "{:?}", imported.warnings);
+    assert!(
+        imported.warnings[1].ends_with("Duplicate key mcpServers.fetch; the last value is used")
+    );
+}
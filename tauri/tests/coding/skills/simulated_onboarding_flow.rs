@@ -0,0 +1,112 @@
+use ai_toolbox_lib::coding::skills::audit::target_disk_mode;
+use ai_toolbox_lib::coding::skills::content_hash::hash_dir;
+use ai_toolbox_lib::coding::skills::onboarding::scan_extra_roots;
+use ai_toolbox_lib::coding::skills::skill_store;
+use ai_toolbox_lib::coding::skills::sync_engine::{
+    copy_skill_dir, sync_dir_for_tool_with_overwrite,
+};
+use ai_toolbox_lib::coding::skills::types::{now_ms, Skill, SkillTarget};
+use ai_toolbox_lib::test_harness::SimulatedHome;
+
+fn central_skill(name: &str, content_hash: String) -> Skill {
+    Skill {
+        id: String::new(),
+        name: name.to_string(),
+        source_type: "import".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: name.to_string(),
+        content_hash: Some(content_hash),
+        created_at: now_ms(),
+        updated_at: now_ms(),
+        last_sync_at: None,
+        status: "ok".to_string(),
+        sort_index: 0,
+        user_group: None,
+        group_id: None,
+        user_note: None,
+        management_enabled: true,
+        disabled_previous_tools: Vec::new(),
+        enabled_tools: Vec::new(),
+        sync_details: None,
+        license: None,
+        provenance: None,
+        tags: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn onboarding_adoption_sync_and_verify_in_simulated_home() {
+    let home = SimulatedHome::new();
+    let db = home.database();
+    let claude_skills = home.install_tool("claude_code");
+    home.install_tool("codex");
+    home.write_skill(&claude_skills, "review", "Reviews a diff");
+
+    // Onboarding finds the skill
+    let scans = scan_extra_roots(&db, vec![home.path().to_string_lossy().to_string()])
+        .await
+        .expect("scan simulated home");
+    let plan = scans[0].plan.as_ref().expect("simulated home is scanned");
+    let names: Vec<&str> = plan
+        .groups
+        .iter()
+        .map(|group| group.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["review"]);
+    let variant = &plan.groups[0].variants[0];
+    assert_eq!(variant.tool, "claude_code");
+
+    // Adoption copies the variant into the central repo and records it
+    let central_dir = home.central_repo().join("review");
+    copy_skill_dir(std::path::Path::new(&variant.path), &central_dir).expect("adopt skill");
+    let hash = hash_dir(&central_dir).expect("hash central skill");
+    let skill_id = skill_store::upsert_skill(&db, &central_skill("review", hash.clone()))
+        .await
+        .expect("record skill");
+
+    // Sync to another tool
+    let target = home.skills_dir("codex").join("review");
+    let outcome = sync_dir_for_tool_with_overwrite("codex", &central_dir, &target, false, false)
+        .expect("sync to codex");
+    skill_store::upsert_skill_target(
+        &db,
+        &skill_id,
+        &SkillTarget {
+            tool: "codex".to_string(),
+            target_path: target.to_string_lossy().to_string(),
+            mode: outcome.mode_used.as_str().to_string(),
+            status: "ok".to_string(),
+            synced_at: Some(now_ms()),
+            error_message: None,
+        },
+    )
+    .await
+    .expect("record target");
+
+    // Verify: the recorded target exists on disk in the recorded mode with the central content
+    let targets = skill_store::get_skill_targets(&db, &skill_id)
+        .await
+        .expect("read targets");
+    assert_eq!(targets.len(), 1);
+    assert!(target.starts_with(home.path()));
+    assert_eq!(target_disk_mode(&target), Some(targets[0].mode.as_str()));
+    assert_eq!(hash_dir(&target).expect("hash target"), hash);
+}
+
+#[tokio::test]
+async fn linked_skills_are_reported_with_their_target() {
+    let home = SimulatedHome::new();
+    let db = home.database();
+    let claude_skills = home.install_tool("claude_code");
+    let shared = home.write_skill(&home.path().join("shared-skills"), "deploy", "Deploys");
+    home.link_dir(&shared, &claude_skills.join("deploy"));
+
+    let scans = scan_extra_roots(&db, vec![home.path().to_string_lossy().to_string()])
+        .await
+        .expect("scan simulated home");
+    let plan = scans[0].plan.as_ref().expect("simulated home is scanned");
+    let variant = &plan.groups[0].variants[0];
+    assert_eq!(variant.name, "deploy");
+    assert!(variant.is_link);
+}
@@ -0,0 +1,6 @@
+// End-to-end tests against `test_harness::SimulatedHome`; run with
+// `cargo test --features test-harness --test simulated_home`
+#[path = "coding/mcp/simulated_import.rs"]
+mod mcp_simulated_import;
+#[path = "coding/skills/simulated_onboarding_flow.rs"]
+mod skills_simulated_onboarding_flow;
@@ -108,6 +108,32 @@ fn schema_migration_rejects_future_user_version() {
     assert!(message.contains(&error));
 }
 
+#[test]
+fn schema_migration_normalizes_decomposed_skill_names() {
+    let mut conn = test_conn();
+    db_put(
+        &conn,
+        DbTable::Skill,
+        "nfd",
+        &json!({"name": "cafe\u{301}", "central_path": "cafe\u{301}", "sort_index": 0}),
+    )
+    .expect("put skill");
+    migrations::set_user_version(&conn, 11).expect("set user version");
+
+    migrations::run_all(&mut conn).expect("rerun migrations");
+
+    let record = db_get(&conn, DbTable::Skill, "nfd")
+        .expect("get skill")
+        .expect("skill record");
+    assert_eq!(record["name"], "caf\u{e9}");
+    // Paths name real directories and stay as they were
+    assert_eq!(record["central_path"], "cafe\u{301}");
+    assert_eq!(
+        migrations::get_user_version(&conn).expect("user_version"),
+        TARGET_SCHEMA_VERSION
+    );
+}
+
 #[test]
 fn sqlite_state_open_rejects_future_user_version_before_file_initialization() {
     let temp_dir = tempfile::tempdir().expect("tempdir");
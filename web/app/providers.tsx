@@ -20,6 +20,8 @@ import {
   type UpdateInfo,
 } from '@/services';
 import { restartApp } from '@/services/settingsApi';
+import { getStartupCheckSummary } from '@/features/coding/skills/services/skillsApi';
+import type { StartupCheckSummary } from '@/features/coding/skills/types';
 import i18n from '@/i18n';
 
 interface ProvidersProps {
@@ -57,6 +59,7 @@ const formatSpeed = (bytesPerSecond: number) => {
 const AppInitializer: React.FC<{ children: React.ReactNode }> = ({ children }) => {
   const { notification, message } = App.useApp();
   const hasCheckedUpdate = React.useRef(false);
+  const shownStartupCheckAt = React.useRef<number | null>(null);
 
   // Update progress states
   const [updateModalOpen, setUpdateModalOpen] = React.useState(false);
@@ -119,7 +122,7 @@ const AppInitializer: React.FC<{ children: React.ReactNode }> = ({ children }) =
         console.error('Failed to install update:', error);
         setUpdateModalOpen(false);
 
-        const githubActionsUrl = `https://github.com/${GITHUB_REPO}/actions`;
+        const githubActionsUrl = `https://github.com/${info.releaseRepo || GITHUB_REPO}/actions`;
         Modal.error({
           title: i18n.t('settings.about.updateFailed'),
           content: (
@@ -162,7 +165,10 @@ const AppInitializer: React.FC<{ children: React.ReactNode }> = ({ children }) =
         if (info.hasUpdate) {
           notification.info({
             message: i18n.t('settings.about.newVersion'),
-            description: i18n.t('settings.about.updateAvailable', { version: info.latestVersion }),
+            description: [
+              i18n.t('settings.about.updateAvailable', { version: info.latestVersion }),
+              info.signatureStatus !== 'verified' && i18n.t(`settings.about.signature.${info.signatureStatus}`),
+            ].filter(Boolean).join(' '),
             btn: (
               <Space>
                 <Button
@@ -195,6 +201,44 @@ const AppInitializer: React.FC<{ children: React.ReactNode }> = ({ children }) =
   // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [notification]);
 
+  // Surface the startup consistency check. The check may finish before this
+  // listener is attached, so the stored summary is fetched as well.
+  React.useEffect(() => {
+    const showSummary = (summary: StartupCheckSummary | null) => {
+      if (!summary || shownStartupCheckAt.current === summary.checked_at) return;
+      shownStartupCheckAt.current = summary.checked_at;
+
+      const lines = [
+        ...summary.settings_errors,
+        summary.central_repo_missing && i18n.t('skills.startupCheck.centralRepoMissing'),
+        summary.fixed.length > 0 && i18n.t('skills.startupCheck.fixed', { count: summary.fixed.length }),
+        summary.issues.length > 0 && i18n.t('skills.startupCheck.issues', { count: summary.issues.length }),
+        summary.errors.length > 0 && i18n.t('skills.startupCheck.errors', { count: summary.errors.length }),
+        summary.read_only && i18n.t('skills.startupCheck.readOnly'),
+      ].filter(Boolean);
+      if (lines.length === 0) return;
+
+      const hasProblems = summary.settings_errors.length > 0
+        || summary.central_repo_missing
+        || summary.issues.length > 0
+        || summary.errors.length > 0;
+      notification[hasProblems ? 'warning' : 'info']({
+        message: i18n.t('skills.startupCheck.title'),
+        description: lines.join(' '),
+        duration: hasProblems ? 0 : 6,
+      });
+    };
+
+    const unlisten = listen<StartupCheckSummary>('skills-startup-check', (event) => {
+      showSummary(event.payload);
+    });
+    getStartupCheckSummary().then(showSummary).catch(console.error);
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(console.error);
+    };
+  }, [notification]);
+
   // Keep a global fallback for tray-driven config changes so inactive pages and
   // subpanels that do not maintain their own listeners still resync to disk state.
   React.useEffect(() => {
@@ -13,7 +13,12 @@ import { useWSLSync } from '@/features/settings/hooks/useWSLSync';
 import { SSHStatusIndicator } from '@/features/settings/components/SSHStatusIndicator';
 import { SSHSyncModal } from '@/features/settings/components/SSHSyncModal';
 import { useSSHSync } from '@/features/settings/hooks/useSSHSync';
-import { SkillsButton } from '@/features/coding/skills';
+import {
+  SkillsButton,
+  useDefaultSkillSetOffers,
+  useExternalChangeAlerts,
+  useToolDetectedOnboarding,
+} from '@/features/coding/skills';
 import { McpButton } from '@/features/coding/mcp';
 import { ImageButton } from '@/features/coding/image';
 import { GatewayButton } from '@/features/coding/gateway';
@@ -45,10 +50,13 @@ const MainLayout: React.FC = () => {
   const navigate = useNavigate();
   const location = useLocation();
   const { setCurrentModule, setCurrentSubTab } = useAppStore();
-  const { visibleTabs } = useSettingsStore();
+  const { visibleTabs, policy } = useSettingsStore();
   const { resolvedTheme } = useThemeStore();
   const { config, status, loadError } = useWSLSync();
   const { config: sshConfig, status: sshStatus } = useSSHSync();
+  useDefaultSkillSetOffers();
+  useToolDetectedOnboarding();
+  useExternalChangeAlerts();
   const mainRef = React.useRef<HTMLElement | null>(null);
 
   // Check if current platform is Windows (only show WSL on Windows)
@@ -85,6 +93,9 @@ const MainLayout: React.FC = () => {
   const contentTopOffset = showAppHeader ? CONTENT_TOP_OFFSET : DRAG_BAR_HEIGHT;
   const isGatewayVisible = visibleTabs.includes('gateway');
   const isImageVisible = visibleTabs.includes('image');
+  const policyDisabledFeatures = policy?.active ? policy.policy.disabled_features : [];
+  const isSkillsVisible = !policyDisabledFeatures.includes('skills');
+  const isMcpVisible = !policyDisabledFeatures.includes('mcp');
   const isStandalonePage = isSettingsPage || isSkillsPage || isMcpPage || isGatewayPage || isImagePage;
   const isNonTabPage = isStandalonePage || routeChrome.mode !== 'default';
   const showCodingTabs = showAppHeader && routeChrome.mode === 'default';
@@ -144,6 +155,14 @@ const MainLayout: React.FC = () => {
     navigate(subTabs[0]?.path ?? '/settings', { replace: true });
   }, [isGatewayPage, isGatewayVisible, navigate, subTabs]);
 
+  React.useEffect(() => {
+    if ((!isSkillsPage || isSkillsVisible) && (!isMcpPage || isMcpVisible)) {
+      return;
+    }
+
+    navigate(subTabs[0]?.path ?? '/settings', { replace: true });
+  }, [isMcpPage, isMcpVisible, isSkillsPage, isSkillsVisible, navigate, subTabs]);
+
   const handleTabChange = (key: string) => {
     const tab = subTabs.find((t) => t.key === key);
     if (tab) {
@@ -273,12 +292,20 @@ const MainLayout: React.FC = () => {
               )}
 
               {/* Skills button */}
-              <SkillsButton />
-              <div className={styles.actionsDivider} />
+              {isSkillsVisible && (
+                <>
+                  <SkillsButton />
+                  <div className={styles.actionsDivider} />
+                </>
+              )}
 
               {/* MCP button */}
-              <McpButton />
-              <div className={styles.actionsDivider} />
+              {isMcpVisible && (
+                <>
+                  <McpButton />
+                  <div className={styles.actionsDivider} />
+                </>
+              )}
 
               {isGatewayVisible && (
                 <>
@@ -0,0 +1,121 @@
+import React from 'react';
+import { Button, Empty, List, Space, Spin, Tag, Typography, message } from 'antd';
+import { CloudDownloadOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import { listGeminiCliExtensions } from '@/services/geminiCliApi';
+import { importMcpFromTool } from '@/features/coding/mcp/services/mcpApi';
+import type { GeminiCliExtension } from '@/types/geminicli';
+
+const { Text } = Typography;
+
+interface GeminiExtensionsPanelProps {
+  refreshToken?: number;
+}
+
+const GeminiExtensionsPanel: React.FC<GeminiExtensionsPanelProps> = ({ refreshToken }) => {
+  const { t } = useTranslation();
+  const [extensions, setExtensions] = React.useState<GeminiCliExtension[]>([]);
+  const [loading, setLoading] = React.useState(false);
+  const [importingKey, setImportingKey] = React.useState<string | null>(null);
+
+  const loadExtensions = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      setExtensions(await listGeminiCliExtensions());
+    } catch (error) {
+      console.error('Failed to list Gemini CLI extensions:', error);
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  React.useEffect(() => {
+    void loadExtensions();
+  }, [loadExtensions, refreshToken]);
+
+  const handleImportMcp = async (extension: GeminiCliExtension) => {
+    setImportingKey(extension.sourceKey);
+    try {
+      const result = await importMcpFromTool(extension.sourceKey);
+      if (result.servers_imported > 0) {
+        message.success(t('mcp.importSuccess', { count: result.servers_imported }));
+      } else if (result.servers_skipped > 0) {
+        message.info(t('mcp.importSkipped', { count: result.servers_skipped }));
+      } else {
+        message.info(t('mcp.importNoServers'));
+      }
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setImportingKey(null);
+    }
+  };
+
+  const renderTags = (values: string[]) => (values.length > 0
+    ? values.map((value) => <Tag key={value}>{value}</Tag>)
+    : <Text type="secondary">{t('geminicli.extensions.none')}</Text>);
+
+  if (!loading && extensions.length === 0) {
+    return <Empty description={t('geminicli.extensions.empty')} image={Empty.PRESENTED_IMAGE_SIMPLE} />;
+  }
+
+  return (
+    <Spin spinning={loading}>
+      <List
+        dataSource={extensions}
+        rowKey={(extension) => extension.sourceKey}
+        renderItem={(extension) => (
+          <List.Item
+            actions={[
+              <Button
+                key="import-mcp"
+                size="small"
+                icon={<CloudDownloadOutlined />}
+                disabled={extension.mcpServers.length === 0}
+                loading={importingKey === extension.sourceKey}
+                onClick={() => void handleImportMcp(extension)}
+              >
+                {t('geminicli.extensions.importMcp')}
+              </Button>,
+            ]}
+          >
+            <List.Item.Meta
+              title={(
+                <Space size="small">
+                  <Text strong>{extension.name}</Text>
+                  {extension.version && <Tag>{extension.version}</Tag>}
+                  {extension.linked && <Tag color="blue">{t('geminicli.extensions.linked')}</Tag>}
+                </Space>
+              )}
+              description={(
+                <Space direction="vertical" size={4} style={{ fontSize: 12 }}>
+                  <Text type="secondary" code style={{ fontSize: 12 }}>{extension.installPath}</Text>
+                  <div>
+                    <Text type="secondary">{t('geminicli.extensions.mcpServers')}: </Text>
+                    {renderTags(extension.mcpServers)}
+                  </div>
+                  <div>
+                    <Text type="secondary">{t('geminicli.extensions.skills')}: </Text>
+                    {renderTags(extension.skills)}
+                  </div>
+                  <div>
+                    <Text type="secondary">{t('geminicli.extensions.contextFiles')}: </Text>
+                    {renderTags(extension.contextFiles)}
+                  </div>
+                  {extension.skills.length > 0 && (
+                    <Text type="secondary" style={{ fontSize: 12 }}>
+                      {t('geminicli.extensions.skillsHint', { source: `Gemini extension: ${extension.name}` })}
+                    </Text>
+                  )}
+                </Space>
+              )}
+            />
+          </List.Item>
+        )}
+      />
+    </Spin>
+  );
+};
+
+export default GeminiExtensionsPanel;
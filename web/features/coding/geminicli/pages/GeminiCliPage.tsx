@@ -95,6 +95,7 @@ import {
 import GeminiCliProviderCard from '../components/GeminiCliProviderCard';
 import GeminiCliProviderFormModal from '../components/GeminiCliProviderFormModal';
 import GeminiCliCommonConfigModal from '../components/GeminiCliCommonConfigModal';
+import GeminiExtensionsPanel from '../components/GeminiExtensionsPanel';
 import {
   GEMINI_CLI_LOCAL_PROVIDER_ID,
   shouldLoadGeminiCliOfficialAccounts,
@@ -190,6 +191,8 @@ const GeminiCliPage: React.FC = () => {
   const [isCopyMode, setIsCopyMode] = React.useState(false);
   const [commonConfigModalOpen, setCommonConfigModalOpen] = React.useState(false);
   const [providerListCollapsed, setProviderListCollapsed] = React.useState(false);
+  const [extensionListCollapsed, setExtensionListCollapsed] = React.useState(true);
+  const [extensionPanelRefreshToken, setExtensionPanelRefreshToken] = React.useState(0);
   const [promptExpandNonce, setPromptExpandNonce] = React.useState(0);
   const [sessionManagerExpandNonce, setSessionManagerExpandNonce] = React.useState(0);
   const [settingsModalOpen, setSettingsModalOpen] = React.useState(false);
@@ -202,8 +205,9 @@ const GeminiCliPage: React.FC = () => {
 
   const sidebarSections = React.useMemo<SidebarSectionMarker[]>(() => [
     { id: 'geminicli-providers', title: t('geminicli.provider.title'), order: 1 },
-    { id: 'geminicli-global-prompt', title: t('geminicli.prompt.title'), order: 2 },
-    { id: 'geminicli-session-manager', title: t('sessionManager.title'), order: 3 },
+    { id: 'geminicli-extensions', title: t('geminicli.extensions.title'), order: 2 },
+    { id: 'geminicli-global-prompt', title: t('geminicli.prompt.title'), order: 3 },
+    { id: 'geminicli-session-manager', title: t('sessionManager.title'), order: 4 },
   ], [t]);
 
   const loadConfig = React.useCallback(async (silent = false) => {
@@ -610,6 +614,8 @@ const GeminiCliPage: React.FC = () => {
         switch (id) {
           case 'geminicli-providers':
             return <DatabaseOutlined />;
+          case 'geminicli-extensions':
+            return <AppstoreOutlined />;
           case 'geminicli-global-prompt':
             return <FileTextOutlined />;
           case 'geminicli-session-manager':
@@ -623,6 +629,9 @@ const GeminiCliPage: React.FC = () => {
           case 'geminicli-providers':
             setProviderListCollapsed(false);
             break;
+          case 'geminicli-extensions':
+            setExtensionListCollapsed(false);
+            break;
           case 'geminicli-global-prompt':
             setPromptExpandNonce((value) => value + 1);
             break;
@@ -831,6 +840,44 @@ const GeminiCliPage: React.FC = () => {
           />
         </div>
 
+        <div
+          id="geminicli-extensions"
+          data-sidebar-section="true"
+          data-sidebar-title={t('geminicli.extensions.title')}
+        >
+          <Collapse
+            style={{ marginBottom: 16 }}
+            activeKey={extensionListCollapsed ? [] : ['extensions']}
+            onChange={(keys) => setExtensionListCollapsed(!keys.includes('extensions'))}
+            items={[
+              {
+                key: 'extensions',
+                label: (
+                  <Text strong>
+                    <AppstoreOutlined style={{ marginRight: 8 }} />
+                    {t('geminicli.extensions.title')}
+                  </Text>
+                ),
+                extra: (
+                  <Button
+                    type="link"
+                    size="small"
+                    style={{ fontSize: 12 }}
+                    icon={<SyncOutlined />}
+                    onClick={(event) => {
+                      event.stopPropagation();
+                      setExtensionPanelRefreshToken((value) => value + 1);
+                    }}
+                  >
+                    {t('common.refresh')}
+                  </Button>
+                ),
+                children: <GeminiExtensionsPanel refreshToken={extensionPanelRefreshToken} />,
+              },
+            ]}
+          />
+        </div>
+
         <div
           id="geminicli-global-prompt"
           data-sidebar-section="true"
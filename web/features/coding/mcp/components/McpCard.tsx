@@ -3,9 +3,11 @@ import { message } from 'antd';
 import {
   Code2,
   Globe2,
+  History,
   MoreHorizontal,
   Pencil,
   Plus,
+  ScrollText,
   Tags,
   Trash2,
 } from 'lucide-react';
@@ -50,6 +52,8 @@ interface McpCardProps {
   onSelectChange?: (serverId: string, checked: boolean) => void;
   onEdit: (server: McpServer) => void;
   onEditMetadata: (server: McpServer) => void;
+  onShowTraffic?: (server: McpServer) => void;
+  onShowHistory?: (server: McpServer) => void;
   onDelete: (serverId: string) => void;
   onToggleTool: (serverId: string, toolKey: string) => void;
 }
@@ -73,6 +77,8 @@ const McpCardContent = React.memo(function McpCardContent({
   onSelectChange,
   onEdit,
   onEditMetadata,
+  onShowTraffic,
+  onShowHistory,
   onDelete,
   onToggleTool,
   dragHandle,
@@ -170,6 +176,23 @@ const McpCardContent = React.memo(function McpCardContent({
         label: t('mcp.metadata.edit'),
         onSelect: () => onEditMetadata(server),
       },
+      // Traffic logging wraps the server command, so only stdio servers qualify
+      ...(onShowTraffic && server.server_type === 'stdio'
+        ? [{
+          key: 'traffic',
+          icon: <ScrollText size={14} />,
+          label: t('mcp.traffic.open'),
+          onSelect: () => onShowTraffic(server),
+        }]
+        : []),
+      ...(onShowHistory
+        ? [{
+          key: 'history',
+          icon: <History size={14} />,
+          label: t('mcp.applyHistory.open'),
+          onSelect: () => onShowHistory(server),
+        }]
+        : []),
       {
         key: 'delete',
         danger: true,
@@ -178,7 +201,7 @@ const McpCardContent = React.memo(function McpCardContent({
         onSelect: () => onDelete(server.id),
       },
     ],
-    [onDelete, onEditMetadata, server, t],
+    [onDelete, onEditMetadata, onShowHistory, onShowTraffic, server, t],
   );
 
   return (
@@ -29,6 +29,8 @@ interface McpGroupedListProps {
   onSelectAllGroup: (group: McpGroup, checked: boolean) => void;
   onEdit: (server: McpServer) => void;
   onEditMetadata: (server: McpServer) => void;
+  onShowTraffic?: (server: McpServer) => void;
+  onShowHistory?: (server: McpServer) => void;
   onDelete: (serverId: string) => void;
   onToggleTool: (serverId: string, toolKey: string) => void;
   groupToolMode?: boolean;
@@ -52,6 +54,8 @@ export const McpGroupedList: React.FC<McpGroupedListProps> = ({
   onSelectAllGroup,
   onEdit,
   onEditMetadata,
+  onShowTraffic,
+  onShowHistory,
   onDelete,
   onToggleTool,
   groupToolMode = false,
@@ -184,6 +188,8 @@ export const McpGroupedList: React.FC<McpGroupedListProps> = ({
                       onSelectChange={onSelectChange}
                       onEdit={onEdit}
                       onEditMetadata={onEditMetadata}
+                      onShowTraffic={onShowTraffic}
+                      onShowHistory={onShowHistory}
                       onDelete={onDelete}
                       onToggleTool={onToggleTool}
                     />
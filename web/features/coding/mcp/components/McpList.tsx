@@ -31,6 +31,8 @@ interface McpListProps {
   limitAddMoreToPreferredTools?: boolean;
   onEdit: (server: McpServer) => void;
   onEditMetadata: (server: McpServer) => void;
+  onShowTraffic?: (server: McpServer) => void;
+  onShowHistory?: (server: McpServer) => void;
   onDelete: (serverId: string) => void;
   onToggleTool: (serverId: string, toolKey: string) => void;
   onDragEnd: (event: DragEndEvent) => void;
@@ -47,6 +49,8 @@ export const McpList: React.FC<McpListProps> = ({
   limitAddMoreToPreferredTools,
   onEdit,
   onEditMetadata,
+  onShowTraffic,
+  onShowHistory,
   onDelete,
   onToggleTool,
   onDragEnd,
@@ -102,6 +106,8 @@ export const McpList: React.FC<McpListProps> = ({
           limitAddMoreToPreferredTools={limitAddMoreToPreferredTools}
           onEdit={onEdit}
           onEditMetadata={onEditMetadata}
+          onShowTraffic={onShowTraffic}
+          onShowHistory={onShowHistory}
           onDelete={onDelete}
           onToggleTool={onToggleTool}
         />
@@ -129,6 +135,8 @@ export const McpList: React.FC<McpListProps> = ({
             limitAddMoreToPreferredTools={limitAddMoreToPreferredTools}
             onEdit={onEdit}
             onEditMetadata={onEditMetadata}
+            onShowTraffic={onShowTraffic}
+            onShowHistory={onShowHistory}
             onDelete={onDelete}
             onToggleTool={onToggleTool}
           />
@@ -234,6 +234,7 @@ export const ImportMcpModal: React.FC<ImportMcpModalProps> = ({
     let totalSkipped = 0;
     const allDuplicated: string[] = [];
     const errors: string[] = [];
+    const warnings: string[] = [];
 
     // Snapshot existing server IDs before import, so we can scope dedup to new servers only
     const preImportIds = new Set(existingServers.map((s) => s.id));
@@ -250,6 +251,9 @@ export const ImportMcpModal: React.FC<ImportMcpModalProps> = ({
           if (result.errors.length > 0) {
             errors.push(...result.errors);
           }
+          if (result.warnings?.length > 0) {
+            warnings.push(...result.warnings);
+          }
         } catch (error) {
           errors.push(`${toolKey}: ${String(error)}`);
         }
@@ -258,6 +262,10 @@ export const ImportMcpModal: React.FC<ImportMcpModalProps> = ({
       if (errors.length > 0) {
         console.error('Import errors:', errors);
       }
+      if (warnings.length > 0) {
+        console.warn('Import warnings:', warnings);
+        message.warning(t('mcp.importWarnings', { count: warnings.length }));
+      }
 
       await fetchServers();
 
@@ -0,0 +1,161 @@
+import React from 'react';
+import { Button, Empty, Input, message, Modal, Select, Space, Tag } from 'antd';
+import { ReloadOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import * as mcpApi from '../../services/mcpApi';
+import type { McpApplyRecord, McpTool } from '../../types';
+import styles from './McpApplyHistoryModal.module.less';
+
+interface McpApplyHistoryModalProps {
+  open: boolean;
+  tools: McpTool[];
+  /** Initial server filter, e.g. when opened from a server card */
+  serverName?: string;
+  onClose: () => void;
+}
+
+const shortHash = (hash: string | null) => (hash ? hash.slice(0, 8) : '—');
+
+export const McpApplyHistoryModal: React.FC<McpApplyHistoryModalProps> = ({
+  open,
+  tools,
+  serverName,
+  onClose,
+}) => {
+  const { t } = useTranslation();
+  const [toolKey, setToolKey] = React.useState<string | undefined>();
+  const [serverFilter, setServerFilter] = React.useState('');
+  const [records, setRecords] = React.useState<McpApplyRecord[]>([]);
+  const [loading, setLoading] = React.useState(false);
+  const [backup, setBackup] = React.useState<{ record: McpApplyRecord; content: string } | null>(null);
+
+  React.useEffect(() => {
+    if (open) {
+      setToolKey(undefined);
+      setServerFilter(serverName ?? '');
+    }
+  }, [open, serverName]);
+
+  const trimmedServer = serverFilter.trim();
+
+  const loadHistory = React.useCallback(async () => {
+    setLoading(true);
+    try {
+      setRecords(await mcpApi.getMcpApplyHistory(toolKey, trimmedServer || undefined));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, [toolKey, trimmedServer]);
+
+  React.useEffect(() => {
+    if (open) {
+      loadHistory();
+    }
+  }, [loadHistory, open]);
+
+  const toolNames = React.useMemo(
+    () => new Map(tools.map((tool) => [tool.key, tool.display_name])),
+    [tools],
+  );
+
+  const toolOptions = React.useMemo(
+    () => tools
+      .filter((tool) => tool.mcp_config_path)
+      .map((tool) => ({ value: tool.key, label: tool.display_name })),
+    [tools],
+  );
+
+  const handleViewBackup = async (record: McpApplyRecord) => {
+    if (!record.backup_ref) {
+      return;
+    }
+    try {
+      setBackup({ record, content: await mcpApi.getMcpApplyBackup(record.backup_ref) });
+    } catch (error) {
+      message.error(String(error));
+    }
+  };
+
+  const renderServers = (names: string[], color: string, labelKey: string) => (
+    names.length > 0 && (
+      <div className={styles.serverRow}>
+        <span className={styles.serverLabel}>{t(labelKey)}</span>
+        {names.map((name) => <Tag key={name} color={color}>{name}</Tag>)}
+      </div>
+    )
+  );
+
+  return (
+    <Modal
+      open={open}
+      title={t('mcp.applyHistory.title')}
+      onCancel={onClose}
+      footer={null}
+      width={760}
+      destroyOnHidden
+    >
+      <div className={styles.filters}>
+        <Space wrap>
+          <Select
+            allowClear
+            value={toolKey}
+            onChange={setToolKey}
+            options={toolOptions}
+            placeholder={t('mcp.applyHistory.allTools')}
+            className={styles.toolSelect}
+          />
+          <Input
+            allowClear
+            value={serverFilter}
+            onChange={(event) => setServerFilter(event.target.value)}
+            placeholder={t('mcp.applyHistory.serverPlaceholder')}
+            className={styles.serverInput}
+          />
+        </Space>
+        <Button icon={<ReloadOutlined />} loading={loading} onClick={loadHistory} />
+      </div>
+      <p className={styles.hint}>{t('mcp.applyHistory.hint')}</p>
+
+      {records.length === 0 ? (
+        <Empty description={t('mcp.applyHistory.empty')} image={Empty.PRESENTED_IMAGE_SIMPLE} />
+      ) : (
+        <div className={styles.records}>
+          {records.map((record) => (
+            <div key={`${record.ts}-${record.tool}-${record.file_hash}`} className={styles.record}>
+              <div className={styles.recordHeader}>
+                <span className={styles.recordTool}>{toolNames.get(record.tool) ?? record.tool}</span>
+                <span>{new Date(record.ts).toLocaleString()}</span>
+                <span className={styles.hashes} title={`${record.previous_hash ?? ''} → ${record.file_hash}`}>
+                  {shortHash(record.previous_hash)} → {shortHash(record.file_hash)}
+                </span>
+                {record.backup_ref && (
+                  <Button size="small" type="link" onClick={() => handleViewBackup(record)}>
+                    {t('mcp.applyHistory.viewBackup')}
+                  </Button>
+                )}
+              </div>
+              <div className={styles.configPath} title={record.config_path}>{record.config_path}</div>
+              {renderServers(record.servers_added, 'green', 'mcp.applyHistory.added')}
+              {renderServers(record.servers_removed, 'red', 'mcp.applyHistory.removed')}
+              {renderServers(record.servers_modified, 'blue', 'mcp.applyHistory.modified')}
+            </div>
+          ))}
+        </div>
+      )}
+
+      <Modal
+        open={!!backup}
+        title={t('mcp.applyHistory.backupTitle', { path: backup?.record.config_path ?? '' })}
+        onCancel={() => setBackup(null)}
+        footer={null}
+        width={720}
+      >
+        <pre className={styles.backupContent}>{backup?.content}</pre>
+      </Modal>
+    </Modal>
+  );
+};
+
+export default McpApplyHistoryModal;
@@ -1,8 +1,8 @@
 import React from 'react';
-import { Modal, Button, Checkbox, message, Form, Input, Space, Tooltip, Switch, Radio } from 'antd';
+import { Modal, Button, Checkbox, message, Form, Input, Space, Tooltip, Switch, Radio, Select } from 'antd';
 import { ClearOutlined, DeleteOutlined, PlusOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
-import type { McpServer, McpTool, StdioConfig, HttpConfig } from '../../types';
+import type { McpServer, McpTool, StdioConfig, HttpConfig, McpGatewayStatus } from '../../types';
 import * as mcpApi from '../../services/mcpApi';
 import { useMcpStore } from '../../stores/mcpStore';
 import { refreshTrayMenu } from '@/services/appApi';
@@ -52,6 +52,8 @@ export const McpSettingsModal: React.FC<McpSettingsModalProps> = ({
   const [showInTray, setShowInTray] = React.useState(false);
   const [syncDisabledToOpencode, setSyncDisabledToOpencode] = React.useState(false);
   const [limitAddMoreToPreferredTools, setLimitAddMoreToPreferredTools] = React.useState(false);
+  const [gatewayStatus, setGatewayStatus] = React.useState<McpGatewayStatus | null>(null);
+  const [savingRemoteOnly, setSavingRemoteOnly] = React.useState(false);
   const [showClearAllModal, setShowClearAllModal] = React.useState(false);
   const [clearAllConfirmText, setClearAllConfirmText] = React.useState('');
   const [clearingAll, setClearingAll] = React.useState(false);
@@ -69,12 +71,14 @@ export const McpSettingsModal: React.FC<McpSettingsModalProps> = ({
         savedPreferredTools,
         syncDisabled,
         savedLimitAddMoreToPreferredTools,
+        savedGatewayStatus,
       ] = await Promise.all([
         mcpApi.getMcpTools(),
         mcpApi.getMcpShowInTray(),
         mcpApi.getMcpPreferredTools(),
         mcpApi.getMcpSyncDisabledToOpencode(),
         mcpApi.getMcpLimitAddMoreToPreferredTools(),
+        mcpApi.getMcpGatewayStatus(),
       ]);
 
       // Sort: installed tools first
@@ -86,6 +90,7 @@ export const McpSettingsModal: React.FC<McpSettingsModalProps> = ({
       setShowInTray(trayEnabled);
       setSyncDisabledToOpencode(syncDisabled);
       setLimitAddMoreToPreferredTools(savedLimitAddMoreToPreferredTools);
+      setGatewayStatus(savedGatewayStatus);
 
       // Extract custom tools
       const custom = tools.filter((t) => t.is_custom && t.supports_mcp);
@@ -137,6 +142,19 @@ export const McpSettingsModal: React.FC<McpSettingsModalProps> = ({
     }
   };
 
+  // Resyncs the affected tools right away, like the switches above
+  const handleRemoteOnlyToolsChange = async (tools: string[]) => {
+    setSavingRemoteOnly(true);
+    try {
+      setGatewayStatus(await mcpApi.setMcpRemoteOnlyTools(tools));
+      await fetchServers();
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setSavingRemoteOnly(false);
+    }
+  };
+
   const isOpencodeInstalled = React.useMemo(
     () => allTools.some((t) => t.key === 'opencode' && t.installed),
     [allTools]
@@ -339,6 +357,30 @@ export const McpSettingsModal: React.FC<McpSettingsModalProps> = ({
         </div>
       )}
 
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('mcp.gateway.remoteOnlyTools')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Select
+            mode="multiple"
+            style={{ width: '100%' }}
+            value={gatewayStatus?.remote_only_tools ?? []}
+            onChange={handleRemoteOnlyToolsChange}
+            loading={savingRemoteOnly}
+            disabled={savingRemoteOnly}
+            placeholder={t('mcp.gateway.remoteOnlyToolsPlaceholder')}
+            options={sortedTools.map((tool) => ({ label: tool.display_name, value: tool.key }))}
+          />
+          <p className={styles.hint}>
+            {t('mcp.gateway.remoteOnlyToolsHint')}
+            {gatewayStatus?.running && gatewayStatus.port !== null && (
+              <> {t('mcp.gateway.running', { port: gatewayStatus.port, count: gatewayStatus.sessions })}</>
+            )}
+          </p>
+        </div>
+      </div>
+
       <div className={styles.section}>
         <div className={styles.labelArea}>
           <label className={styles.label}>{t('mcp.preferredTools')}</label>
@@ -0,0 +1,149 @@
+import React from 'react';
+import { Button, Empty, message, Modal, Space, Switch, Tooltip } from 'antd';
+import { ClearOutlined, ReloadOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import * as mcpApi from '../../services/mcpApi';
+import type { McpServer, McpTraffic, McpTrafficEntry } from '../../types';
+import styles from './McpTrafficModal.module.less';
+
+interface McpTrafficModalProps {
+  open: boolean;
+  server: McpServer | null;
+  onClose: () => void;
+}
+
+const DIRECTION_ARROWS: Record<McpTrafficEntry['direction'], string> = {
+  client: '→',
+  server: '←',
+  stderr: '!',
+  exit: '■',
+};
+
+const formatTime = (ts: number) => new Date(ts).toLocaleTimeString();
+
+const formatMessage = (value: unknown) => (
+  typeof value === 'string' ? value : JSON.stringify(value, null, 2)
+);
+
+export const McpTrafficModal: React.FC<McpTrafficModalProps> = ({
+  open,
+  server,
+  onClose,
+}) => {
+  const { t } = useTranslation();
+  const [traffic, setTraffic] = React.useState<McpTraffic | null>(null);
+  const [loading, setLoading] = React.useState(false);
+  const [toggling, setToggling] = React.useState(false);
+
+  const serverId = server?.id;
+
+  const loadTraffic = React.useCallback(async () => {
+    if (!serverId) {
+      return;
+    }
+    setLoading(true);
+    try {
+      setTraffic(await mcpApi.getMcpTraffic(serverId));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setLoading(false);
+    }
+  }, [serverId]);
+
+  React.useEffect(() => {
+    if (open) {
+      loadTraffic();
+    } else {
+      setTraffic(null);
+    }
+  }, [loadTraffic, open]);
+
+  const handleToggle = async (enabled: boolean) => {
+    if (!serverId) {
+      return;
+    }
+    setToggling(true);
+    try {
+      await mcpApi.setMcpTrafficLogging(serverId, enabled);
+      message.success(t(enabled ? 'mcp.traffic.enabled' : 'mcp.traffic.disabled'));
+      await loadTraffic();
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setToggling(false);
+    }
+  };
+
+  const handleClear = async () => {
+    if (!serverId) {
+      return;
+    }
+    try {
+      await mcpApi.clearMcpTraffic(serverId);
+      await loadTraffic();
+    } catch (error) {
+      message.error(String(error));
+    }
+  };
+
+  if (!server) {
+    return null;
+  }
+
+  const entries = traffic?.entries ?? [];
+
+  return (
+    <Modal
+      open={open}
+      title={t('mcp.traffic.title', { name: server.name })}
+      onCancel={onClose}
+      footer={null}
+      width={760}
+      destroyOnHidden
+    >
+      <div className={styles.toolbar}>
+        <Space>
+          <Switch
+            checked={!!traffic?.enabled}
+            loading={toggling}
+            onChange={handleToggle}
+          />
+          <span>{t('mcp.traffic.enable')}</span>
+        </Space>
+        <Space>
+          <Tooltip title={t('mcp.traffic.refresh')}>
+            <Button icon={<ReloadOutlined />} loading={loading} onClick={loadTraffic} />
+          </Tooltip>
+          <Tooltip title={t('mcp.traffic.clear')}>
+            <Button icon={<ClearOutlined />} disabled={entries.length === 0} onClick={handleClear} />
+          </Tooltip>
+        </Space>
+      </div>
+      <p className={styles.hint}>{t('mcp.traffic.hint')}</p>
+      {traffic?.log_path && (
+        <p className={styles.logPath} title={traffic.log_path}>{traffic.log_path}</p>
+      )}
+
+      {entries.length === 0 ? (
+        <Empty description={t('mcp.traffic.empty')} image={Empty.PRESENTED_IMAGE_SIMPLE} />
+      ) : (
+        <div className={styles.entries}>
+          {entries.map((entry, index) => (
+            <div key={`${entry.ts}-${entry.pid}-${index}`} className={`${styles.entry} ${styles[entry.direction]}`}>
+              <div className={styles.entryMeta}>
+                <span className={styles.direction}>{DIRECTION_ARROWS[entry.direction]}</span>
+                <span>{t(`mcp.traffic.direction.${entry.direction}`)}</span>
+                <span>{formatTime(entry.ts)}</span>
+                <span>pid {entry.pid}</span>
+              </div>
+              <pre className={styles.message}>{formatMessage(entry.message)}</pre>
+            </div>
+          ))}
+        </div>
+      )}
+    </Modal>
+  );
+};
+
+export default McpTrafficModal;
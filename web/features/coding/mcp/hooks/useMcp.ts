@@ -31,6 +31,18 @@ export const useMcp = () => {
     return () => { unlisten?.(); };
   }, [fetchServers, fetchTools]);
 
+  // 后台重新检测到工具安装/卸载时刷新工具列表
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    const setup = async () => {
+      unlisten = await listen('tools-detection-changed', () => {
+        fetchTools();
+      });
+    };
+    setup();
+    return () => { unlisten?.(); };
+  }, [fetchTools]);
+
   return {
     servers,
     tools,
@@ -2,17 +2,44 @@ import { message } from 'antd';
 import { useTranslation } from 'react-i18next';
 import { useMcpStore } from '../stores/mcpStore';
 import * as mcpApi from '../services/mcpApi';
-import type { CreateMcpServerInput, UpdateMcpServerInput } from '../types';
+import type { CreateMcpServerInput, McpServer, UpdateMcpServerInput } from '../types';
 
 export const useMcpActions = () => {
   const { t } = useTranslation();
   const { addServer, updateServer, removeServer, fetchServers } = useMcpStore();
 
+  // Warn (without blocking) when the runtime a server launches through is
+  // missing or too old on this machine
+  const warnMissingPrerequisites = async (server: McpServer) => {
+    try {
+      const report = await mcpApi.checkMcpPrerequisites(server.server_type, server.server_config);
+      if (report.satisfied) return;
+      const details = report.checks
+        .filter((check) => !check.satisfied)
+        .map((check) => {
+          const required = check.min_version ? `${check.runtime} >= ${check.min_version}` : check.runtime;
+          const status = check.found
+            ? t('mcp.prerequisites.foundVersion', { version: check.version })
+            : t('mcp.prerequisites.notFound');
+          return check.install_hint
+            ? `${required} (${status}): ${check.install_hint}`
+            : `${required} (${status})`;
+        });
+      message.warning(
+        t('mcp.prerequisites.missing', { name: server.name, details: details.join('; ') }),
+        10,
+      );
+    } catch (error) {
+      console.warn('Failed to check MCP prerequisites:', error);
+    }
+  };
+
   const createServer = async (input: CreateMcpServerInput) => {
     try {
       const server = await mcpApi.createMcpServer(input);
       addServer(server);
       message.success(t('mcp.serverCreated'));
+      void warnMissingPrerequisites(server);
       return server;
     } catch (error) {
       message.error(t('mcp.serverCreateFailed') + ': ' + String(error));
@@ -48,6 +75,10 @@ export const useMcpActions = () => {
       const isEnabled = await mcpApi.toggleMcpTool(serverId, toolKey);
       // Refresh servers to get updated state
       await fetchServers();
+      const server = useMcpStore.getState().servers.find((item) => item.id === serverId);
+      if (isEnabled && server) {
+        void warnMissingPrerequisites(server);
+      }
       return isEnabled;
     } catch (error) {
       message.error(t('mcp.toggleToolFailed') + ': ' + String(error));
@@ -110,6 +141,10 @@ export const useMcpActions = () => {
       } else {
         message.info(t('mcp.importNoServers'));
       }
+      if (result.warnings?.length > 0) {
+        console.warn('Import warnings:', result.warnings);
+        message.warning(t('mcp.importWarnings', { count: result.warnings.length }));
+      }
       await fetchServers();
       return result;
     } catch (error) {
@@ -6,6 +6,7 @@ import {
   ExternalLink,
   FileText,
   GripVertical,
+  History,
   Import,
   LayoutGrid,
   ListTree,
@@ -42,6 +43,8 @@ import { McpSettingsModal } from '../components/modals/McpSettingsModal';
 import { ImportMcpModal } from '../components/modals/ImportMcpModal';
 import { ImportJsonModal } from '../components/modals/ImportJsonModal';
 import { McpMetadataModal } from '../components/modals/McpMetadataModal';
+import { McpTrafficModal } from '../components/modals/McpTrafficModal';
+import { McpApplyHistoryModal } from '../components/modals/McpApplyHistoryModal';
 import * as mcpApi from '../services/mcpApi';
 import {
   buildMcpGroups,
@@ -97,6 +100,9 @@ const McpPage: React.FC = () => {
   const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set());
   const [selectionMode, setSelectionMode] = useState(false);
   const [metadataServer, setMetadataServer] = useState<McpServer | null>(null);
+  const [trafficServer, setTrafficServer] = useState<McpServer | null>(null);
+  // `serverName` narrows the history to changes that touched one server
+  const [applyHistory, setApplyHistory] = useState<{ serverName?: string } | null>(null);
   const [batchGroupModalOpen, setBatchGroupModalOpen] = useState(false);
   const [batchGroupValue, setBatchGroupValue] = useState('');
   const [groupToolMode, setGroupToolMode] = useState(false);
@@ -587,6 +593,10 @@ const McpPage: React.FC = () => {
     setAddModalOpen(true);
   };
 
+  const handleShowHistory = useCallback((server: McpServer) => {
+    setApplyHistory({ serverName: server.name });
+  }, []);
+
   const handleCloseModal = () => {
     setAddModalOpen(false);
     setEditingServer(null);
@@ -711,6 +721,12 @@ const McpPage: React.FC = () => {
           </ManagementButton>
         </div>
         <div className={styles.toolbarActions}>
+          <ManagementIconButton
+            icon={<History size={14} aria-hidden="true" />}
+            onClick={() => setApplyHistory({})}
+            title={t('mcp.applyHistory.title')}
+            controlSize="compact"
+          />
           {viewMode === 'flat' && (
             <ManagementButton
               variant={reorderMode ? 'primary' : 'ghost'}
@@ -817,6 +833,8 @@ const McpPage: React.FC = () => {
             limitAddMoreToPreferredTools={limitAddMoreToPreferredTools}
             onEdit={handleEdit}
             onEditMetadata={setMetadataServer}
+            onShowTraffic={setTrafficServer}
+            onShowHistory={handleShowHistory}
             onDelete={handleDelete}
             onToggleTool={handleToggleTool}
             onDragEnd={handleDragEnd}
@@ -838,6 +856,8 @@ const McpPage: React.FC = () => {
             onSelectAllGroup={handleSelectAllGroup}
             onEdit={handleEdit}
             onEditMetadata={setMetadataServer}
+            onShowTraffic={setTrafficServer}
+            onShowHistory={handleShowHistory}
             onDelete={handleDelete}
             onToggleTool={handleToggleTool}
             groupToolMode={groupToolMode}
@@ -934,6 +954,19 @@ const McpPage: React.FC = () => {
           refresh();
         }}
       />
+
+      <McpTrafficModal
+        open={!!trafficServer}
+        server={trafficServer}
+        onClose={() => setTrafficServer(null)}
+      />
+
+      <McpApplyHistoryModal
+        open={!!applyHistory}
+        tools={tools}
+        serverName={applyHistory?.serverName}
+        onClose={() => setApplyHistory(null)}
+      />
     </div>
   );
 };
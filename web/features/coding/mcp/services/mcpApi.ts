@@ -9,6 +9,12 @@ import type {
   McpScanResult,
   McpPackageVersionResolveRequest,
   McpPackageVersionResolveResult,
+  McpTemplate,
+  McpTemplateInput,
+  McpPrerequisiteReport,
+  McpGatewayStatus,
+  McpTraffic,
+  McpApplyRecord,
 } from '../types';
 
 // Server CRUD
@@ -26,6 +32,21 @@ export const createMcpServer = async (input: CreateMcpServerInput): Promise<McpS
   return invoke<McpServer>('mcp_create_server', { input });
 };
 
+export const listMcpTemplates = async (): Promise<McpTemplate[]> => {
+  return invoke<McpTemplate[]>('mcp_list_templates');
+};
+
+export const addMcpServerFromTemplate = async (input: McpTemplateInput): Promise<McpServer> => {
+  return invoke<McpServer>('mcp_add_server_from_template', { input });
+};
+
+export const checkMcpPrerequisites = async (
+  serverType: McpServer['server_type'],
+  serverConfig: McpServer['server_config'],
+): Promise<McpPrerequisiteReport> => {
+  return invoke<McpPrerequisiteReport>('mcp_check_prerequisites', { serverType, serverConfig });
+};
+
 export const updateMcpServer = async (serverId: string, input: UpdateMcpServerInput): Promise<McpServer> => {
   return invoke<McpServer>('mcp_update_server', { serverId, input });
 };
@@ -74,6 +95,38 @@ export const scanMcpServers = async (): Promise<McpScanResult> => {
 };
 
 // Preferences
+export const getMcpGatewayStatus = async (): Promise<McpGatewayStatus> => {
+  return invoke<McpGatewayStatus>('mcp_get_gateway_status');
+};
+
+export const setMcpRemoteOnlyTools = async (tools: string[]): Promise<McpGatewayStatus> => {
+  return invoke<McpGatewayStatus>('mcp_set_remote_only_tools', { tools });
+};
+
+export const getMcpTraffic = async (serverId: string, limit?: number): Promise<McpTraffic> => {
+  return invoke<McpTraffic>('mcp_get_traffic', { serverId, limit });
+};
+
+export const setMcpTrafficLogging = async (serverId: string, enabled: boolean): Promise<void> => {
+  return invoke('mcp_set_traffic_logging', { serverId, enabled });
+};
+
+export const clearMcpTraffic = async (serverId: string): Promise<void> => {
+  return invoke('mcp_clear_traffic', { serverId });
+};
+
+export const getMcpApplyHistory = async (
+  toolKey?: string,
+  serverName?: string,
+  limit?: number,
+): Promise<McpApplyRecord[]> => {
+  return invoke<McpApplyRecord[]>('mcp_get_apply_history', { toolKey, serverName, limit });
+};
+
+export const getMcpApplyBackup = async (backupRef: string): Promise<string> => {
+  return invoke<string>('mcp_get_apply_backup', { backupRef });
+};
+
 export const getMcpShowInTray = async (): Promise<boolean> => {
   return invoke<boolean>('mcp_get_show_in_tray');
 };
@@ -55,6 +55,97 @@ export interface UpdateMcpServerInput {
   timeout?: number;
 }
 
+// Built-in server templates
+export interface McpTemplateParam {
+  key: string;
+  label: string;
+  /** `paths` takes one path per line */
+  kind: 'text' | 'secret' | 'path' | 'paths';
+  required: boolean;
+  placeholder: string;
+}
+
+export interface McpTemplateTransport {
+  server_type: 'stdio' | 'http' | 'sse';
+  /** Server config JSON with `{{param}}` placeholders */
+  config: string;
+}
+
+export interface McpTemplate {
+  id: string;
+  name: string;
+  description: string;
+  params: McpTemplateParam[];
+  /** The first transport is the default */
+  transports: McpTemplateTransport[];
+}
+
+export interface McpTemplateInput {
+  template_id: string;
+  server_type?: 'stdio' | 'http' | 'sse';
+  params: Record<string, string>;
+  name?: string;
+  enabled_tools?: string[];
+}
+
+export interface McpPrerequisite {
+  /** Program probed with `--version` (node, npx, uvx, docker, ...) */
+  runtime: string;
+  min_version: string | null;
+  found: boolean;
+  path: string | null;
+  version: string | null;
+  satisfied: boolean;
+  /** Install command for the current OS, set when not satisfied */
+  install_hint: string | null;
+}
+
+export interface McpPrerequisiteReport {
+  satisfied: boolean;
+  checks: McpPrerequisite[];
+}
+
+export interface McpGatewayStatus {
+  running: boolean;
+  port: number | null;
+  /** Tools whose stdio servers are synced as gateway SSE URLs */
+  remote_only_tools: string[];
+  /** Open SSE connections, one server process each */
+  sessions: number;
+}
+
+export interface McpTrafficEntry {
+  ts: number;
+  /** Proxy process id; differs per tool running the server */
+  pid: number;
+  direction: 'client' | 'server' | 'stderr' | 'exit';
+  /** JSON-RPC message, or the raw line when it is not JSON */
+  message: unknown;
+}
+
+export interface McpTraffic {
+  server_id: string;
+  enabled: boolean;
+  log_path: string;
+  /** Oldest first */
+  entries: McpTrafficEntry[];
+}
+
+/** One recorded write of a tool's MCP config */
+export interface McpApplyRecord {
+  ts: number;
+  tool: string;
+  config_path: string;
+  servers_added: string[];
+  servers_removed: string[];
+  servers_modified: string[];
+  /** Hash of the file before the write; null when it did not exist */
+  previous_hash: string | null;
+  file_hash: string;
+  /** Pre-write content, readable with getMcpApplyBackup */
+  backup_ref: string | null;
+}
+
 export interface McpSyncResult {
   tool: string;
   success: boolean;
@@ -66,6 +157,7 @@ export interface McpImportResult {
   servers_skipped: number;
   servers_duplicated: string[];  // Names of servers created with suffix due to config differences
   errors: string[];
+  warnings: string[];  // Recoveries made while reading the source config (BOM, comments, duplicate keys)
 }
 
 export interface McpDiscoveredServer {
@@ -80,6 +172,7 @@ export interface McpScanResult {
   total_tools_scanned: number;
   total_servers_found: number;
   servers: McpDiscoveredServer[];
+  warnings: string[];
 }
 
 export interface McpTool {
@@ -2,11 +2,18 @@ import React from 'react';
 import { message } from 'antd';
 import { invoke } from '@tauri-apps/api/core';
 import {
+  Archive,
+  ArchiveRestore,
+  ArrowDownToLine,
   Copy,
   Eye,
+  FileDiff,
+  Filter,
   Folder,
   Grid2X2,
+  Link2,
   MoreHorizontal,
+  PencilLine,
   Plus,
   Power,
   RefreshCw,
@@ -32,8 +39,14 @@ import {
   ManagementMenu,
   type ManagementMenuItem,
 } from '@/features/coding/shared/management';
-import type { ManagedSkill, ToolOption } from '../types';
-import { getSkillFolderOpenCandidates, getSkillManifestPath } from '../utils/skillPath';
+import { useSkillsStore } from '../stores/skillsStore';
+import type { ManagedSkill, TargetSyncMode, ToolOption } from '../types';
+import {
+  getSkillFolderOpenCandidates,
+  getSkillManifestPath,
+  isFolderSource,
+} from '../utils/skillPath';
+import { getSkillDisplayDescription, getSkillDisplayName } from '../utils/skillLocalization';
 import styles from './SkillCard.module.less';
 
 const GitHubSourceIcon: React.FC<{ className?: string }> = ({ className }) => (
@@ -74,6 +87,11 @@ interface SkillCardProps {
   onToggleTool: (skill: ManagedSkill, toolId: string) => void;
   onEditMetadata: (skill: ManagedSkill) => void;
   onSetManagementEnabled: (skill: ManagedSkill, enabled: boolean) => void;
+  onSetArchived?: (skill: ManagedSkill, archived: boolean) => void;
+  onPreviewUpdate?: (skill: ManagedSkill) => void;
+  onConvertTargetMode?: (skill: ManagedSkill, toolId: string, mode: TargetSyncMode) => void;
+  onEditPathRules?: (skill: ManagedSkill, toolId: string) => void;
+  onPullFromTarget?: (skill: ManagedSkill, toolId: string) => void;
 }
 
 interface SkillCardContentProps extends Omit<SkillCardProps, 'dragDisabled'> {
@@ -99,11 +117,22 @@ const SkillCardContent = React.memo(function SkillCardContent({
   onToggleTool,
   onEditMetadata,
   onSetManagementEnabled,
+  onSetArchived,
+  onPreviewUpdate,
+  onConvertTargetMode,
+  onEditPathRules,
+  onPullFromTarget,
   dragHandle,
   containerRef,
   containerStyle,
 }: SkillCardContentProps) {
-  const { t } = useTranslation();
+  const { t, i18n } = useTranslation();
+  const displayName = getSkillDisplayName(skill, i18n.language);
+  const displayDescription = getSkillDisplayDescription(skill, i18n.language);
+  // Keep the directory name visible when a localized name replaces it
+  const nameTitle = [displayName === skill.name ? skill.name : `${displayName} (${skill.name})`, displayDescription]
+    .filter(Boolean)
+    .join('\n');
 
   const typeKey = skill.source_type.toLowerCase();
   const sourceWarningMessage = skill.source_health === 'warning'
@@ -119,6 +148,8 @@ const SkillCardContent = React.memo(function SkillCardContent({
   const shouldShowGroupTag = showGroupTag && groupLabel.length > 0;
   const hasUserNote = userNoteText.length > 0;
   const managementToggleLabel = skill.management_enabled ? t('skills.disableSkill') : t('skills.enableSkill');
+  const isArchived = skill.status === 'archived';
+  const isLocallyModified = useSkillsStore((state) => state.locallyModifiedIds.includes(skill.id));
 
   // These values are derived from stable inputs and are recalculated for every card.
   // Memoizing them keeps scroll and hover interactions cheaper when many cards are on screen.
@@ -176,7 +207,7 @@ const SkillCardContent = React.memo(function SkillCardContent({
       return;
     }
 
-    if (skill.source_type === 'local') {
+    if (isFolderSource(skill.source_type)) {
       const sourcePath = getSkillFolderOpenCandidates(skill)[0];
       if (!sourcePath) {
         message.error(t('skills.sourceFolderMissing'));
@@ -223,7 +254,7 @@ const SkillCardContent = React.memo(function SkillCardContent({
     if (typeKey.includes('git') && (github?.href || skill.source_ref?.trim())) {
       return t('skills.openRepo');
     }
-    if (skill.source_type === 'local' && skill.source_ref?.trim()) {
+    if (isFolderSource(skill.source_type) && skill.source_ref?.trim()) {
       return t('skills.openFolder');
     }
     return undefined;
@@ -233,7 +264,7 @@ const SkillCardContent = React.memo(function SkillCardContent({
 
   const iconNode = typeKey.includes('git') ? (
     <GitHubSourceIcon className={`${styles.icon}${iconClickable ? ` ${styles.clickableIcon}` : ''}`} />
-  ) : typeKey.includes('local') ? (
+  ) : isFolderSource(typeKey) ? (
     <Folder size={18} className={`${styles.icon}${iconClickable ? ` ${styles.clickableIcon}` : ''}`} />
   ) : (
     <Grid2X2 size={18} className={styles.icon} />
@@ -251,6 +282,12 @@ const SkillCardContent = React.memo(function SkillCardContent({
     [allTools, syncedToolIds],
   );
 
+  // Copies can be edited in place and pulled back into the central repo
+  const copyTools = React.useMemo(
+    () => syncedTools.filter((tool) => skill.targets.some((target) => target.tool === tool.id && target.mode === 'copy')),
+    [skill.targets, syncedTools],
+  );
+
   const availableDropdownTools = React.useMemo(() => {
     return allTools.filter((tool) => tool.installed && !syncedToolIds.has(tool.id));
   }, [allTools, syncedToolIds]);
@@ -281,8 +318,60 @@ const SkillCardContent = React.memo(function SkillCardContent({
         icon: <Power size={14} />,
         label: managementToggleLabel,
         onSelect: handleToggleManagement,
-        disabled: loading || isUpdating,
+        disabled: loading || isUpdating || isArchived,
       },
+      ...(onPreviewUpdate && skill.source_type !== 'central' ? [{
+        key: 'preview-update',
+        icon: <FileDiff size={14} />,
+        label: t('skills.updatePreview.action'),
+        onSelect: () => onPreviewUpdate(skill),
+        disabled: loading || isUpdating || isArchived,
+      }] : []),
+      ...(onConvertTargetMode && syncedTools.length > 0 ? [
+        { key: 'convert-mode', type: 'section' as const, label: t('skills.convertMode.section') },
+        ...syncedTools.map((tool) => {
+          const mode = skill.targets.find((target) => target.tool === tool.id)?.mode;
+          const nextMode: TargetSyncMode = mode === 'copy' ? 'symlink' : 'copy';
+          return {
+            key: `convert-mode-${tool.id}`,
+            icon: nextMode === 'copy' ? <Copy size={14} /> : <Link2 size={14} />,
+            label: t('skills.convertMode.action', { tool: tool.label, mode: t(`skills.convertMode.${nextMode}`) }),
+            onSelect: () => onConvertTargetMode(skill, tool.id, nextMode),
+            disabled: loading || isUpdating || !skill.management_enabled,
+          };
+        }),
+      ] : []),
+      ...(onEditPathRules && syncedTools.length > 0 ? [
+        { key: 'path-rules', type: 'section' as const, label: t('skills.pathRules.section') },
+        ...syncedTools.map((tool) => {
+          const target = skill.targets.find((item) => item.tool === tool.id);
+          const hasRules = !!(target?.path_rules?.include.length || target?.path_rules?.exclude.length);
+          return {
+            key: `path-rules-${tool.id}`,
+            icon: <Filter size={14} />,
+            label: t(hasRules ? 'skills.pathRules.editAction' : 'skills.pathRules.action', { tool: tool.label }),
+            onSelect: () => onEditPathRules(skill, tool.id),
+            disabled: loading || isUpdating || !skill.management_enabled,
+          };
+        }),
+      ] : []),
+      ...(onPullFromTarget && copyTools.length > 0 ? [
+        { key: 'pull-from-target', type: 'section' as const, label: t('skills.pullFromTarget.section') },
+        ...copyTools.map((tool) => ({
+          key: `pull-from-target-${tool.id}`,
+          icon: <ArrowDownToLine size={14} />,
+          label: t('skills.pullFromTarget.action', { tool: tool.label }),
+          onSelect: () => onPullFromTarget(skill, tool.id),
+          disabled: loading || isUpdating || !skill.management_enabled,
+        })),
+      ] : []),
+      ...(onSetArchived ? [{
+        key: 'archive',
+        icon: isArchived ? <ArchiveRestore size={14} /> : <Archive size={14} />,
+        label: isArchived ? t('skills.archive.restore') : t('skills.archive.archive'),
+        onSelect: () => onSetArchived(skill, !isArchived),
+        disabled: loading || isUpdating,
+      }] : []),
       {
         key: 'delete',
         danger: true,
@@ -292,7 +381,7 @@ const SkillCardContent = React.memo(function SkillCardContent({
         disabled: loading || isUpdating,
       },
     ],
-    [handleToggleManagement, isUpdating, loading, managementToggleLabel, onDelete, onEditMetadata, skill, t],
+    [copyTools, handleToggleManagement, isArchived, isUpdating, loading, managementToggleLabel, onConvertTargetMode, onDelete, onEditMetadata, onEditPathRules, onPreviewUpdate, onPullFromTarget, onSetArchived, skill, syncedTools, t],
   );
 
   return (
@@ -322,7 +411,7 @@ const SkillCardContent = React.memo(function SkillCardContent({
       />
       <ManagementCardMain>
         <div className={styles.cardHeader}>
-          <span className={styles.skillNameText} title={skill.name}>{skill.name}</span>
+          <span className={styles.skillNameText} title={nameTitle}>{displayName}</span>
           <div className={styles.headerMetaCompact}>
             <button
               type="button"
@@ -353,6 +442,12 @@ const SkillCardContent = React.memo(function SkillCardContent({
                 <span>{t('skills.sourceWarning')}</span>
               </span>
             )}
+            {isLocallyModified && (
+              <span className={styles.localChangesMeta} title={t('skills.localChanges.badgeHint')}>
+                <PencilLine size={12} aria-hidden="true" />
+                <span>{t('skills.localChanges.badge')}</span>
+              </span>
+            )}
             <span className={styles.dot}>•</span>
             <span className={styles.time}>{formatRelative(skill.updated_at)}</span>
           </div>
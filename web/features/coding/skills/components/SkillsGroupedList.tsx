@@ -9,7 +9,7 @@ import {
   type ManagementMenuItem,
 } from '@/features/coding/shared/management';
 import { SkillCard } from './SkillCard';
-import type { SkillGroup, ToolOption, ManagedSkill } from '../types';
+import type { SkillGroup, ToolOption, ManagedSkill, TargetSyncMode } from '../types';
 import { getSkillGroupToolIds, isSkillUngroupedCustomGroup } from '../utils/skillGrouping';
 import styles from './SkillsGroupedList.module.less';
 
@@ -32,6 +32,11 @@ interface SkillsGroupedListProps {
   onToggleTool: (skill: ManagedSkill, toolId: string) => void;
   onEditMetadata: (skill: ManagedSkill) => void;
   onSetManagementEnabled: (skill: ManagedSkill, enabled: boolean) => void;
+  onSetArchived?: (skill: ManagedSkill, archived: boolean) => void;
+  onPreviewUpdate?: (skill: ManagedSkill) => void;
+  onConvertTargetMode?: (skill: ManagedSkill, toolId: string, mode: TargetSyncMode) => void;
+  onEditPathRules?: (skill: ManagedSkill, toolId: string) => void;
+  onPullFromTarget?: (skill: ManagedSkill, toolId: string) => void;
   groupToolMode?: boolean;
   onAddGroupTool?: (group: SkillGroup, toolId: string) => void;
   onRemoveGroupTool?: (group: SkillGroup, toolId: string) => void;
@@ -56,6 +61,11 @@ export const SkillsGroupedList: React.FC<SkillsGroupedListProps> = ({
   onToggleTool,
   onEditMetadata,
   onSetManagementEnabled,
+  onSetArchived,
+  onPreviewUpdate,
+  onConvertTargetMode,
+  onEditPathRules,
+  onPullFromTarget,
   groupToolMode = false,
   onAddGroupTool,
   onRemoveGroupTool,
@@ -194,6 +204,11 @@ export const SkillsGroupedList: React.FC<SkillsGroupedListProps> = ({
                       onToggleTool={onToggleTool}
                       onEditMetadata={onEditMetadata}
                       onSetManagementEnabled={onSetManagementEnabled}
+                      onSetArchived={onSetArchived}
+                      onPreviewUpdate={onPreviewUpdate}
+                      onConvertTargetMode={onConvertTargetMode}
+                      onEditPathRules={onEditPathRules}
+                      onPullFromTarget={onPullFromTarget}
                     />
                   )}
                 />
@@ -17,7 +17,7 @@ import {
 import { restrictToWindowEdges } from '@dnd-kit/modifiers';
 import { ManagementEmpty, VirtualGrid } from '@/features/coding/shared/management';
 import { SkillCard } from './SkillCard';
-import type { ManagedSkill, ToolOption } from '../types';
+import type { ManagedSkill, TargetSyncMode, ToolOption } from '../types';
 import styles from './SkillsList.module.less';
 
 interface SkillsListProps {
@@ -34,6 +34,11 @@ interface SkillsListProps {
   onToggleTool: (skill: ManagedSkill, toolId: string) => void;
   onEditMetadata: (skill: ManagedSkill) => void;
   onSetManagementEnabled: (skill: ManagedSkill, enabled: boolean) => void;
+  onSetArchived?: (skill: ManagedSkill, archived: boolean) => void;
+  onPreviewUpdate?: (skill: ManagedSkill) => void;
+  onConvertTargetMode?: (skill: ManagedSkill, toolId: string, mode: TargetSyncMode) => void;
+  onEditPathRules?: (skill: ManagedSkill, toolId: string) => void;
+  onPullFromTarget?: (skill: ManagedSkill, toolId: string) => void;
   onDragEnd: (event: DragEndEvent) => void;
 }
 
@@ -51,6 +56,11 @@ export const SkillsList: React.FC<SkillsListProps> = ({
   onToggleTool,
   onEditMetadata,
   onSetManagementEnabled,
+  onSetArchived,
+  onPreviewUpdate,
+  onConvertTargetMode,
+  onEditPathRules,
+  onPullFromTarget,
   onDragEnd,
 }) => {
   const { t } = useTranslation();
@@ -100,6 +110,11 @@ export const SkillsList: React.FC<SkillsListProps> = ({
           onToggleTool={onToggleTool}
           onEditMetadata={onEditMetadata}
           onSetManagementEnabled={onSetManagementEnabled}
+          onSetArchived={onSetArchived}
+          onPreviewUpdate={onPreviewUpdate}
+          onConvertTargetMode={onConvertTargetMode}
+          onEditPathRules={onEditPathRules}
+          onPullFromTarget={onPullFromTarget}
         />
       ))}
     </div>
@@ -129,6 +144,11 @@ export const SkillsList: React.FC<SkillsListProps> = ({
             onToggleTool={onToggleTool}
             onEditMetadata={onEditMetadata}
             onSetManagementEnabled={onSetManagementEnabled}
+            onSetArchived={onSetArchived}
+            onPreviewUpdate={onPreviewUpdate}
+            onConvertTargetMode={onConvertTargetMode}
+            onEditPathRules={onEditPathRules}
+            onPullFromTarget={onPullFromTarget}
           />
         )}
       />
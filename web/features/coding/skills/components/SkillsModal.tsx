@@ -83,6 +83,7 @@ export const SkillsModal: React.FC<SkillsModalProps> = ({ open, onClose }) => {
     confirmDelete,
     handleDragEnd,
     handleSetManagementEnabled,
+    handleSetArchived,
   } = useSkillActions({ allTools });
 
   const moreMenuItems: MenuProps['items'] = [
@@ -142,6 +143,7 @@ export const SkillsModal: React.FC<SkillsModalProps> = ({ open, onClose }) => {
             onToggleTool={handleToggleTool}
             onEditMetadata={setMetadataSkill}
             onSetManagementEnabled={handleSetManagementEnabled}
+            onSetArchived={handleSetArchived}
             onDragEnd={handleDragEnd}
           />
         </div>
@@ -4,7 +4,7 @@ import { FolderOutlined, GithubOutlined, PlusOutlined } from '@ant-design/icons'
 import { open } from '@tauri-apps/plugin-dialog';
 import { useTranslation } from 'react-i18next';
 import * as api from '../../services/skillsApi';
-import type { ToolOption, GitSkillCandidate, SkillRepo } from '../../types';
+import type { ToolOption, GitSkillCandidate, GitCollectionCandidate, SkillRepo, InstallResult } from '../../types';
 import { GitPickModal } from './GitPickModal';
 import {
   isSkillExistsError,
@@ -43,6 +43,12 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
   const [preferredTools, setPreferredTools] = React.useState<string[] | null>(null);
   const [repoExpanded, setRepoExpanded] = React.useState(false);
 
+  const warnDisallowedLicense = (result: InstallResult) => {
+    if (result.license_warning) {
+      message.warning(t('skills.licenseWarning', { name: result.name, license: result.license ?? 'NOASSERTION' }));
+    }
+  };
+
   // Branch options for AutoComplete
   const branchOptions = [
     { value: 'main' },
@@ -51,6 +57,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
 
   // Git pick modal state
   const [gitCandidates, setGitCandidates] = React.useState<GitSkillCandidate[]>([]);
+  const [gitCollections, setGitCollections] = React.useState<GitCollectionCandidate[]>([]);
   const [showGitPick, setShowGitPick] = React.useState(false);
 
   // Local pick modal state
@@ -187,6 +194,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
 
       // Single skill found - install via selection API
       const result = await api.installLocalSelection(localPath, candidates[0].subpath, overwrite);
+      warnDisallowedLicense(result);
       if (selectedTools.length > 0) {
         await syncSkillToTools({
           skillId: result.skill_id,
@@ -220,6 +228,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
     setLoading(true);
     try {
       const result = await api.installLocalSkill(localPath, overwrite);
+      warnDisallowedLicense(result);
       if (selectedTools.length > 0) {
         await syncSkillToTools({
           skillId: result.skill_id,
@@ -248,12 +257,55 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
     }
   };
 
+  // Collections from the repo's skill-collections.json; optional, so failures are ignored
+  const loadGitCollections = async () => {
+    try {
+      setGitCollections(await api.listGitCollections(gitUrl, gitBranch || undefined));
+    } catch (error) {
+      console.error('Failed to load repo collections:', error);
+      setGitCollections([]);
+    }
+  };
+
+  // Record the repo collections whose members were just installed
+  const saveRepoCollections = async (installedIds: Map<string, string>) => {
+    const installed = gitCollections
+      .map((collection) => ({
+        ...collection,
+        skillIds: collection.subpaths
+          .map((subpath) => installedIds.get(subpath))
+          .filter((id): id is string => !!id),
+      }))
+      .filter((collection) => collection.skillIds.length > 0);
+    if (installed.length === 0) return;
+
+    const parsed = parseGitUrl(gitUrl);
+    const source = parsed ? `${parsed.owner}/${parsed.name}` : gitUrl;
+    try {
+      const existing = await api.getSkillCollections();
+      for (const collection of installed) {
+        const match = existing.find((item) => item.name.toLowerCase() === collection.name.toLowerCase());
+        const skillIds = Array.from(new Set([...(match?.skill_ids ?? []), ...collection.skillIds]));
+        await api.saveSkillCollection(
+          match?.name ?? collection.name,
+          match ? match.note : collection.note,
+          skillIds,
+          match?.id,
+          match ? match.source : source,
+        );
+      }
+    } catch (error) {
+      message.warning(t('skills.collections.registrySaveFailed', { error: String(error) }));
+    }
+  };
+
   const doGitInstall = async (overwrite: boolean) => {
     setLoading(true);
     try {
       const candidates = await api.listGitSkills(gitUrl, gitBranch || undefined);
       if (candidates.length > 1) {
         setGitCandidates(candidates);
+        await loadGitCollections();
         setShowGitPick(true);
         setLoading(false);
         return;
@@ -274,6 +326,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
 
       // Single skill - install directly
       const result = await api.installGitSkill(gitUrl, gitBranch || undefined, overwrite);
+      warnDisallowedLicense(result);
       if (selectedTools.length > 0) {
         await syncSkillToTools({
           skillId: result.skill_id,
@@ -307,6 +360,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
           const fallbackCandidates = await api.listGitSkills(gitUrl, gitBranch || undefined);
           if (fallbackCandidates.length > 0) {
             setGitCandidates(fallbackCandidates);
+            await loadGitCollections();
             setShowGitPick(true);
           } else {
             message.error(t('skills.errors.noSkillsFoundInRepo'));
@@ -327,6 +381,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
     setLoading(true);
     try {
       const result = await api.installGitSkill(gitUrl, gitBranch || undefined, overwrite);
+      warnDisallowedLicense(result);
       if (selectedTools.length > 0) {
         await syncSkillToTools({
           skillId: result.skill_id,
@@ -383,12 +438,15 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
     setLoading(true);
 
     const skippedNames: string[] = [];
+    const installedIds = new Map<string, string>();
     let overwriteAll = false;
 
     try {
       for (const sel of selections) {
         try {
           const result = await api.installGitSelection(gitUrl, sel.subpath, gitBranch || undefined);
+          warnDisallowedLicense(result);
+          installedIds.set(sel.subpath, result.skill_id);
           if (selectedTools.length > 0) {
             await syncSkillToTools({
               skillId: result.skill_id,
@@ -406,6 +464,8 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
             const skillName = extractSkillName(errMsg);
             if (overwriteAll) {
               const result = await api.installGitSelection(gitUrl, sel.subpath, gitBranch || undefined, true);
+              warnDisallowedLicense(result);
+              installedIds.set(sel.subpath, result.skill_id);
               if (selectedTools.length > 0) {
                 await syncSkillToTools({
                   skillId: result.skill_id,
@@ -421,6 +481,8 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
               const action = await confirmBatchOverwrite(skillName, selections.length > 1, t);
               if (action === 'overwrite') {
                 const result = await api.installGitSelection(gitUrl, sel.subpath, gitBranch || undefined, true);
+                warnDisallowedLicense(result);
+                installedIds.set(sel.subpath, result.skill_id);
                 if (selectedTools.length > 0) {
                   await syncSkillToTools({
                     skillId: result.skill_id,
@@ -435,6 +497,8 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
               } else if (action === 'overwriteAll') {
                 overwriteAll = true;
                 const result = await api.installGitSelection(gitUrl, sel.subpath, gitBranch || undefined, true);
+                warnDisallowedLicense(result);
+                installedIds.set(sel.subpath, result.skill_id);
                 if (selectedTools.length > 0) {
                   await syncSkillToTools({
                     skillId: result.skill_id,
@@ -462,6 +526,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
         await api.addSkillRepo(parsed.owner, parsed.name, gitBranch || 'main');
         await loadRepos();
       }
+      await saveRepoCollections(installedIds);
 
       if (skippedNames.length > 0) {
         message.info(t('skills.status.installWithSkipped', { skipped: skippedNames.join(', ') }));
@@ -489,6 +554,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
       for (const sel of selections) {
         try {
           const result = await api.installLocalSelection(localPath, sel.subpath);
+          warnDisallowedLicense(result);
           if (selectedTools.length > 0) {
             await syncSkillToTools({
               skillId: result.skill_id,
@@ -506,6 +572,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
             const skillName = extractSkillName(errMsg);
             if (overwriteAll) {
               const result = await api.installLocalSelection(localPath, sel.subpath, true);
+              warnDisallowedLicense(result);
               if (selectedTools.length > 0) {
                 await syncSkillToTools({
                   skillId: result.skill_id,
@@ -521,6 +588,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
               const action = await confirmBatchOverwrite(skillName, selections.length > 1, t);
               if (action === 'overwrite') {
                 const result = await api.installLocalSelection(localPath, sel.subpath, true);
+                warnDisallowedLicense(result);
                 if (selectedTools.length > 0) {
                   await syncSkillToTools({
                     skillId: result.skill_id,
@@ -535,6 +603,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
               } else if (action === 'overwriteAll') {
                 overwriteAll = true;
                 const result = await api.installLocalSelection(localPath, sel.subpath, true);
+                warnDisallowedLicense(result);
                 if (selectedTools.length > 0) {
                   await syncSkillToTools({
                     skillId: result.skill_id,
@@ -769,6 +838,7 @@ export const AddSkillModal: React.FC<AddSkillModalProps> = ({
         <GitPickModal
           open={showGitPick}
           candidates={gitCandidates}
+          collections={gitCollections}
           onClose={() => setShowGitPick(false)}
           onConfirm={handleGitPickConfirm}
         />
@@ -0,0 +1,135 @@
+import React from 'react';
+import { Alert, App, Button, Collapse, Empty, Modal, Space, Spin, Tag } from 'antd';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { ExternalChange, SkillUpdateFileStatus } from '../../types';
+import styles from './ExternalChangesModal.module.less';
+
+interface ExternalChangesModalProps {
+  open: boolean;
+  onClose: () => void;
+  onSuccess: () => void;
+}
+
+const STATUS_COLORS: Record<SkillUpdateFileStatus, string> = {
+  added: 'green',
+  removed: 'red',
+  modified: 'blue',
+};
+
+export const ExternalChangesModal: React.FC<ExternalChangesModalProps> = ({
+  open,
+  onClose,
+  onSuccess,
+}) => {
+  const { t } = useTranslation();
+  const { message } = App.useApp();
+  const [changes, setChanges] = React.useState<ExternalChange[] | null>(null);
+  const [loading, setLoading] = React.useState(false);
+  const [busyId, setBusyId] = React.useState<string | null>(null);
+  const [error, setError] = React.useState<string | null>(null);
+
+  const load = React.useCallback(async () => {
+    setLoading(true);
+    setError(null);
+    try {
+      setChanges(await api.scanExternalChanges());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  React.useEffect(() => {
+    if (open) {
+      void load();
+    }
+  }, [load, open]);
+
+  const resolve = async (change: ExternalChange, action: 'accept' | 'revert') => {
+    setBusyId(change.skill_id);
+    try {
+      if (action === 'accept') {
+        await api.acceptExternalChange(change.skill_id);
+      } else {
+        await api.revertExternalChange(change.skill_id);
+      }
+      message.success(t(`skills.externalChanges.${action}Success`, { name: change.name }));
+      setChanges((current) => current?.filter((item) => item.skill_id !== change.skill_id) ?? null);
+      onSuccess();
+    } catch (err) {
+      message.error(String(err));
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  return (
+    <Modal
+      open={open}
+      title={t('skills.externalChanges.title')}
+      onCancel={onClose}
+      footer={null}
+      width={760}
+      destroyOnHidden
+    >
+      <Spin spinning={loading}>
+        <div className={styles.content}>
+          <div className={styles.hint}>{t('skills.externalChanges.hint')}</div>
+          {error && <Alert type="error" showIcon message={error} />}
+          {changes && changes.length === 0 && (
+            <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('skills.externalChanges.none')} />
+          )}
+          {changes?.map((change) => (
+            <section key={change.skill_id} className={styles.skill}>
+              <div className={styles.skillHeader}>
+                <span className={styles.skillName}>{change.name}</span>
+                <Space size={6}>
+                  <Button
+                    size="small"
+                    disabled={busyId !== null}
+                    onClick={() => resolve(change, 'accept')}
+                  >
+                    {t('skills.externalChanges.accept')}
+                  </Button>
+                  <Button
+                    size="small"
+                    danger
+                    disabled={busyId !== null || !change.has_baseline}
+                    loading={busyId === change.skill_id}
+                    onClick={() => resolve(change, 'revert')}
+                  >
+                    {t('skills.externalChanges.revert')}
+                  </Button>
+                </Space>
+              </div>
+              <div className={styles.path}>{change.central_path}</div>
+              {change.has_baseline ? (
+                <Collapse
+                  size="small"
+                  items={change.files.map((file) => ({
+                    key: file.path,
+                    label: (
+                      <span className={styles.fileLabel}>
+                        <Tag color={STATUS_COLORS[file.status]}>
+                          {t(`skills.updatePreview.status.${file.status}`)}
+                        </Tag>
+                        <span className={styles.filePath}>{file.path}</span>
+                      </span>
+                    ),
+                    children: file.patch
+                      ? <pre className={styles.patch}>{file.patch}</pre>
+                      : <div className={styles.hint}>{t('skills.updatePreview.patchTooLarge')}</div>,
+                  }))}
+                />
+              ) : (
+                <div className={styles.hint}>{t('skills.externalChanges.noBaseline')}</div>
+              )}
+            </section>
+          ))}
+        </div>
+      </Spin>
+    </Modal>
+  );
+};
@@ -1,12 +1,14 @@
 import React from 'react';
-import { Modal, Checkbox, Button, Empty } from 'antd';
+import { Modal, Checkbox, Button, Empty, Tag } from 'antd';
 import { useTranslation } from 'react-i18next';
-import type { GitSkillCandidate } from '../../types';
+import type { GitCollectionCandidate, GitSkillCandidate } from '../../types';
 import styles from './GitPickModal.module.less';
 
 interface GitPickModalProps {
   open: boolean;
   candidates: GitSkillCandidate[];
+  /** Collections the repo defines; picking one selects its members */
+  collections?: GitCollectionCandidate[];
   onClose: () => void;
   onConfirm: (selections: { subpath: string }[]) => void;
 }
@@ -14,6 +16,7 @@ interface GitPickModalProps {
 export const GitPickModal: React.FC<GitPickModalProps> = ({
   open,
   candidates,
+  collections = [],
   onClose,
   onConfirm,
 }) => {
@@ -40,6 +43,10 @@ export const GitPickModal: React.FC<GitPickModalProps> = ({
     }
   };
 
+  const handleSelectCollection = (collection: GitCollectionCandidate) => {
+    setSelected((prev) => new Set([...prev, ...collection.subpaths]));
+  };
+
   const handleConfirm = () => {
     const selections = candidates
       .filter((c) => selected.has(c.subpath))
@@ -57,6 +64,22 @@ export const GitPickModal: React.FC<GitPickModalProps> = ({
     >
       <p className={styles.hint}>{t('skills.gitPick.description')}</p>
 
+      {collections.length > 0 && (
+        <div className={styles.collections}>
+          <span className={styles.collectionsLabel}>{t('skills.gitPick.collections')}</span>
+          {collections.map((collection) => (
+            <Tag.CheckableTag
+              key={collection.name}
+              checked={collection.subpaths.length > 0 && collection.subpaths.every((subpath) => selected.has(subpath))}
+              onChange={() => handleSelectCollection(collection)}
+              title={collection.note ?? undefined}
+            >
+              {collection.name} · {collection.subpaths.length}
+            </Tag.CheckableTag>
+          ))}
+        </div>
+      )}
+
       {candidates.length === 0 ? (
         <Empty description={t('skills.errors.noSkillsFoundInRepo')} />
       ) : (
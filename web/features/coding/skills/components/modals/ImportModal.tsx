@@ -1,5 +1,5 @@
 import React from 'react';
-import { Modal, Checkbox, Button, Empty, message, Spin, Tooltip, Dropdown } from 'antd';
+import { Modal, Checkbox, Button, Empty, message, Spin, Tooltip, Dropdown, Select } from 'antd';
 import { WarningOutlined, FolderOpenOutlined, PlusOutlined } from '@ant-design/icons';
 import { revealItemInDir } from '@tauri-apps/plugin-opener';
 import { useTranslation } from 'react-i18next';
@@ -15,6 +15,19 @@ import { syncSkillToTools } from '../../utils/syncHelpers';
 import { refreshTrayMenu } from '@/services/appApi';
 import styles from './ImportModal.module.less';
 import addSkillStyles from './AddSkillModal.module.less';
+import type { ConsolidationCandidate, VariantPolicy } from '../../types';
+
+const POLICY_OPTIONS: { key: string; policy: VariantPolicy }[] = [
+  { key: 'preferClaudeCode', policy: { kind: 'prefer_tool', tool: 'claude_code' } },
+  { key: 'newestMtime', policy: { kind: 'newest_mtime' } },
+  { key: 'largest', policy: { kind: 'largest' } },
+];
+
+const formatBytes = (bytes: number): string => {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+};
 
 interface ImportModalProps {
   open: boolean;
@@ -33,16 +46,23 @@ export const ImportModal: React.FC<ImportModalProps> = ({
   const [selectedTools, setSelectedTools] = React.useState<string[]>([]);
   const [loading, setLoading] = React.useState(false);
   const [preferredTools, setPreferredTools] = React.useState<string[] | null>(null);
+  const [candidates, setCandidates] = React.useState<ConsolidationCandidate[]>([]);
+  const [policyKey, setPolicyKey] = React.useState(POLICY_OPTIONS[0].key);
+  // Linked-folder variants whose original folder stays tracked after import
+  const [trackedOrigins, setTrackedOrigins] = React.useState<Set<string>>(new Set());
+  // Also list the tools' own system skills (read-only, never imported)
+  const [includeSystem, setIncludeSystem] = React.useState(false);
 
   // Track if we've initialized tools for this open session
   const toolsInitializedRef = React.useRef(false);
 
   React.useEffect(() => {
-    loadOnboardingPlan();
+    loadOnboardingPlan(true, includeSystem);
     setSelected(new Set());
     // Load preferred tools
     api.getPreferredTools().then(setPreferredTools).catch(console.error);
-  }, [loadOnboardingPlan]);
+    api.getConsolidationCandidates().then(setCandidates).catch(console.error);
+  }, [loadOnboardingPlan, includeSystem]);
 
   // Reset initialized state when modal closes
   React.useEffect(() => {
@@ -104,7 +124,28 @@ export const ImportModal: React.FC<ImportModalProps> = ({
     );
   };
 
-  const groups = onboardingPlan?.groups || [];
+  const newGroupNames = React.useMemo(
+    () => new Set(onboardingPlan?.new_since_last_scan ?? []),
+    [onboardingPlan],
+  );
+  // Groups that appeared since the previous scan are listed first
+  const groups = React.useMemo(() => {
+    const all = onboardingPlan?.groups || [];
+    return [
+      ...all.filter((g) => newGroupNames.has(g.name)),
+      ...all.filter((g) => !newGroupNames.has(g.name)),
+    ];
+  }, [newGroupNames, onboardingPlan]);
+  const systemSkills = onboardingPlan?.system_skills ?? [];
+  const linkedFolders = React.useMemo(() => {
+    const folders = new Map<string, string>();
+    groups.forEach((g) => {
+      g.variants.forEach((v) => {
+        if (v.linked_folder) folders.set(v.path, v.linked_folder);
+      });
+    });
+    return folders;
+  }, [groups]);
   const allPaths = React.useMemo(() => {
     const paths: string[] = [];
     groups.forEach((g) => {
@@ -127,6 +168,18 @@ export const ImportModal: React.FC<ImportModalProps> = ({
     });
   };
 
+  const handleTrackOriginToggle = (path: string) => {
+    setTrackedOrigins((prev) => {
+      const next = new Set(prev);
+      if (next.has(path)) {
+        next.delete(path);
+      } else {
+        next.add(path);
+      }
+      return next;
+    });
+  };
+
   const handleSelectAll = () => {
     if (selected.size === allPaths.length) {
       setSelected(new Set());
@@ -146,23 +199,29 @@ export const ImportModal: React.FC<ImportModalProps> = ({
     try {
       for (let i = 0; i < selectedPaths.length; i++) {
         const path = selectedPaths[i];
+        const importVariant = (overwrite?: boolean) => api.importExistingSkill(
+          path,
+          overwrite,
+          linkedFolders.get(path),
+          trackedOrigins.has(path),
+        );
         let result;
         try {
-          result = await api.importExistingSkill(path);
+          result = await importVariant();
         } catch (error) {
           const errMsg = String(error);
           if (isSkillExistsError(errMsg)) {
             const skillName = extractSkillName(errMsg);
             if (overwriteAll) {
-              result = await api.importExistingSkill(path, true);
+              result = await importVariant(true);
             } else {
               const hasMore = i < selectedPaths.length - 1;
               const action = await confirmBatchOverwrite(skillName, hasMore, t);
               if (action === 'overwrite') {
-                result = await api.importExistingSkill(path, true);
+                result = await importVariant(true);
               } else if (action === 'overwriteAll') {
                 overwriteAll = true;
-                result = await api.importExistingSkill(path, true);
+                result = await importVariant(true);
               } else {
                 skippedNames.push(skillName);
                 continue;
@@ -201,6 +260,75 @@ export const ImportModal: React.FC<ImportModalProps> = ({
     }
   };
 
+  // Import every group (or the groups with a selected variant) using one variant policy
+  const handleAdoptWithPolicy = async () => {
+    const policy = POLICY_OPTIONS.find((option) => option.key === policyKey)?.policy;
+    if (!policy) return;
+    const names = selected.size > 0
+      ? groups.filter((g) => g.variants.some((v) => selected.has(v.path))).map((g) => g.name)
+      : undefined;
+
+    setLoading(true);
+    try {
+      const result = await api.adoptOnboardingPlan(policy, names);
+      if (selectedTools.length > 0) {
+        for (const adopted of result.adopted) {
+          await syncSkillToTools({
+            skillId: adopted.skill_id,
+            centralPath: adopted.central_path,
+            skillName: adopted.name,
+            selectedTools,
+            allTools,
+            t,
+            onTargetExists: 'skip',
+          });
+        }
+      }
+      if (result.skipped.length > 0) {
+        message.info(t('skills.status.installWithSkipped', {
+          skipped: result.skipped.map((s) => s.name).join(', '),
+        }));
+      } else {
+        message.success(t('skills.status.importCompleted'));
+      }
+      onSuccess();
+      refreshTrayMenu();
+    } catch (error) {
+      showGitError(String(error), t, allTools);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  // Adopt skills copied by hand into several tools and link every copy
+  const handleConsolidate = async () => {
+    setLoading(true);
+    try {
+      const result = await api.consolidateCopies();
+      if (result.failed.length > 0) {
+        message.warning(t('skills.consolidate.partial', {
+          count: result.consolidated.length,
+          failed: result.failed.map((f) => `${f.name}: ${f.error}`).join('; '),
+        }));
+      } else {
+        message.success(t('skills.consolidate.done', {
+          count: result.consolidated.length,
+          size: formatBytes(result.bytes_reclaimed),
+        }));
+      }
+      setCandidates(await api.getConsolidationCandidates());
+      loadOnboardingPlan(false, includeSystem);
+      onSuccess();
+      refreshTrayMenu();
+    } catch (error) {
+      showGitError(String(error), t, allTools);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const reclaimableBytes = candidates.reduce((sum, c) => sum + c.reclaimable_bytes, 0);
+
   const handleOpenFolder = async (path: string, e: React.MouseEvent) => {
     e.stopPropagation();
     try {
@@ -226,6 +354,21 @@ export const ImportModal: React.FC<ImportModalProps> = ({
             <span>{t('skills.toolsScanned', { count: onboardingPlan.total_tools_scanned })}</span>
             <span className={styles.dot}>•</span>
             <span>{t('skills.skillsFound', { count: onboardingPlan.total_skills_found })}</span>
+            {newGroupNames.size > 0 && (
+              <>
+                <span className={styles.dot}>•</span>
+                <span className={styles.newStat}>
+                  {t('skills.newSinceLastScan', { count: newGroupNames.size })}
+                </span>
+              </>
+            )}
+            <Checkbox
+              className={styles.systemToggle}
+              checked={includeSystem}
+              onChange={(e) => setIncludeSystem(e.target.checked)}
+            >
+              {t('skills.systemSkills.include')}
+            </Checkbox>
           </div>
         )}
 
@@ -254,6 +397,9 @@ export const ImportModal: React.FC<ImportModalProps> = ({
                 <div key={group.name} className={styles.group}>
                   <div className={styles.groupHeader}>
                     <span className={styles.groupName}>{group.name}</span>
+                    {newGroupNames.has(group.name) && (
+                      <span className={styles.newBadge}>{t('skills.newBadge')}</span>
+                    )}
                   </div>
                   {group.variants.map((v) => (
                     <div
@@ -284,6 +430,16 @@ export const ImportModal: React.FC<ImportModalProps> = ({
                             onClick={(e) => handleOpenFolder(v.path, e)}
                           />
                         </div>
+                        {v.linked_folder && (
+                          <div onClick={(e) => e.stopPropagation()}>
+                            <Checkbox
+                              checked={trackedOrigins.has(v.path)}
+                              onChange={() => handleTrackOriginToggle(v.path)}
+                            >
+                              {t('skills.trackLinkedFolder', { folder: v.linked_folder })}
+                            </Checkbox>
+                          </div>
+                        )}
                       </div>
                     </div>
                   ))}
@@ -334,8 +490,68 @@ export const ImportModal: React.FC<ImportModalProps> = ({
           </>
         )}
 
+        {includeSystem && systemSkills.length > 0 && (
+          <div className={styles.systemSection}>
+            <div className={styles.groupHeader}>
+              <span className={styles.groupName}>{t('skills.systemSkills.title')}</span>
+              <span className={styles.readOnlyBadge}>{t('skills.systemSkills.readOnly')}</span>
+            </div>
+            <div className={styles.hint}>{t('skills.systemSkills.hint')}</div>
+            {systemSkills.map((skill) => (
+              <div key={skill.path} className={`${styles.variant} ${styles.systemVariant}`}>
+                <div className={styles.variantInfo}>
+                  <div className={styles.variantTool}>
+                    {skill.name}
+                    <span className={styles.systemTool}>{skill.tool_display || skill.tool}</span>
+                  </div>
+                  {skill.description && (
+                    <div className={styles.systemDescription}>{skill.description}</div>
+                  )}
+                  <div className={styles.variantPath}>
+                    <span>{skill.path}</span>
+                    <FolderOpenOutlined
+                      className={styles.openFolder}
+                      onClick={(e) => handleOpenFolder(skill.path, e)}
+                    />
+                  </div>
+                </div>
+              </div>
+            ))}
+          </div>
+        )}
+
         <div className={styles.footer}>
           <Button onClick={onClose}>{t('common.close')}</Button>
+          {candidates.length > 0 && (
+            <Tooltip
+              title={t('skills.consolidate.hint', {
+                names: candidates.map((c) => c.name).join(', '),
+                size: formatBytes(reclaimableBytes),
+              })}
+            >
+              <Button onClick={handleConsolidate} loading={loading}>
+                {t('skills.consolidate.button', { count: candidates.length })}
+              </Button>
+            </Tooltip>
+          )}
+          {groups.length > 0 && (
+            <>
+              <Select
+                value={policyKey}
+                onChange={setPolicyKey}
+                className={styles.policySelect}
+                options={POLICY_OPTIONS.map((option) => ({
+                  value: option.key,
+                  label: t(`skills.adoptPolicy.${option.key}`),
+                }))}
+              />
+              <Tooltip title={t('skills.adoptPolicy.hint')}>
+                <Button onClick={handleAdoptWithPolicy} loading={loading}>
+                  {t('skills.adoptPolicy.button')}
+                </Button>
+              </Tooltip>
+            </>
+          )}
           <Button
             type="primary"
             onClick={handleImport}
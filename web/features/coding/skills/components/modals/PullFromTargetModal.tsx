@@ -0,0 +1,140 @@
+import React from 'react';
+import { Alert, App, Checkbox, Collapse, Empty, Modal, Spin, Tag } from 'antd';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { ManagedSkill, SkillUpdateFileStatus, TargetPullPreview } from '../../types';
+import styles from './PullFromTargetModal.module.less';
+
+interface PullFromTargetModalProps {
+  open: boolean;
+  skill: ManagedSkill | null;
+  toolId: string | null;
+  toolLabel: string;
+  onClose: () => void;
+  onSuccess: () => void;
+}
+
+const STATUS_COLORS: Record<SkillUpdateFileStatus, string> = {
+  added: 'green',
+  removed: 'red',
+  modified: 'blue',
+};
+
+export const PullFromTargetModal: React.FC<PullFromTargetModalProps> = ({
+  open,
+  skill,
+  toolId,
+  toolLabel,
+  onClose,
+  onSuccess,
+}) => {
+  const { t } = useTranslation();
+  const { message } = App.useApp();
+  const [preview, setPreview] = React.useState<TargetPullPreview | null>(null);
+  const [selected, setSelected] = React.useState<string[]>([]);
+  const [loading, setLoading] = React.useState(false);
+  const [pulling, setPulling] = React.useState(false);
+  const [error, setError] = React.useState<string | null>(null);
+
+  const describeError = React.useCallback((err: unknown) => {
+    const text = String(err);
+    if (text.includes('PULL_TARGET_NOT_COPY|')) {
+      return t('skills.pullFromTarget.notCopy', { tool: toolLabel });
+    }
+    if (text.includes('PULL_TARGET_VARIANT|')) {
+      return t('skills.pullFromTarget.variant', { tool: toolLabel });
+    }
+    return text;
+  }, [t, toolLabel]);
+
+  React.useEffect(() => {
+    if (!open || !skill || !toolId) {
+      return;
+    }
+    setLoading(true);
+    setError(null);
+    setPreview(null);
+    api.previewPullFromTarget(skill.id, toolId)
+      .then((result) => {
+        setPreview(result);
+        setSelected(result.files.map((file) => file.path));
+      })
+      .catch((err) => setError(describeError(err)))
+      .finally(() => setLoading(false));
+  }, [describeError, open, skill, toolId]);
+
+  const handlePull = async () => {
+    if (!skill || !toolId || selected.length === 0) {
+      return;
+    }
+    setPulling(true);
+    try {
+      const result = await api.pullFromTarget(skill.id, toolId, selected);
+      message.success(t('skills.pullFromTarget.success', {
+        name: skill.name,
+        count: result.pulled.length,
+        resynced: result.resynced.length,
+      }));
+      if (result.errors.length > 0) {
+        message.warning(t('skills.pullFromTarget.resyncFailed', { errors: result.errors.join('; ') }));
+      }
+      onSuccess();
+    } catch (err) {
+      message.error(describeError(err));
+    } finally {
+      setPulling(false);
+    }
+  };
+
+  const toggle = (path: string, checked: boolean) => {
+    setSelected((current) => (checked ? [...current, path] : current.filter((item) => item !== path)));
+  };
+
+  return (
+    <Modal
+      open={open}
+      title={t('skills.pullFromTarget.title', { name: skill?.name ?? '', tool: toolLabel })}
+      onCancel={onClose}
+      onOk={() => void handlePull()}
+      okText={t('skills.pullFromTarget.pull', { count: selected.length })}
+      okButtonProps={{ disabled: selected.length === 0, loading: pulling }}
+      width={760}
+      destroyOnHidden
+    >
+      <Spin spinning={loading}>
+        <div className={styles.content}>
+          <div className={styles.hint}>{t('skills.pullFromTarget.hint')}</div>
+          {error && <Alert type="error" showIcon message={error} />}
+          {preview && <div className={styles.path}>{preview.target_path}</div>}
+          {preview && preview.files.length === 0 && (
+            <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('skills.pullFromTarget.none')} />
+          )}
+          {preview && preview.files.length > 0 && (
+            <Collapse
+              size="small"
+              items={preview.files.map((file) => ({
+                key: file.path,
+                label: (
+                  <span className={styles.fileLabel}>
+                    <Checkbox
+                      checked={selected.includes(file.path)}
+                      onClick={(event) => event.stopPropagation()}
+                      onChange={(event) => toggle(file.path, event.target.checked)}
+                    />
+                    <Tag color={STATUS_COLORS[file.status]}>
+                      {t(`skills.pullFromTarget.status.${file.status}`)}
+                    </Tag>
+                    <span className={styles.filePath}>{file.path}</span>
+                  </span>
+                ),
+                children: file.patch
+                  ? <pre className={styles.patch}>{file.patch}</pre>
+                  : <div className={styles.hint}>{t('skills.updatePreview.patchTooLarge')}</div>,
+              }))}
+            />
+          )}
+        </div>
+      </Spin>
+    </Modal>
+  );
+};
@@ -0,0 +1,330 @@
+import React from 'react';
+import { Button, Empty, Form, Input, Modal, Select, Space, Tag, message } from 'antd';
+import {
+  DeleteOutlined,
+  DownloadOutlined,
+  EditOutlined,
+  FolderOpenOutlined,
+  PlusOutlined,
+} from '@ant-design/icons';
+import { open as openDialog } from '@tauri-apps/plugin-dialog';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { ManagedSkill, SkillCollection, SkillCollectionToolResult, ToolOption } from '../../types';
+import styles from './SkillCollectionsModal.module.less';
+
+interface SkillCollectionsModalProps {
+  open: boolean;
+  skills: ManagedSkill[];
+  tools: ToolOption[];
+  onClose: () => void;
+  onSuccess: () => void;
+}
+
+interface CollectionFormValues {
+  name: string;
+  note?: string;
+  skillIds: string[];
+}
+
+/** Members that can be synced: enabled and not archived */
+const eligibleMembers = (collection: SkillCollection, skillsById: Map<string, ManagedSkill>) =>
+  collection.skill_ids
+    .map((id) => skillsById.get(id))
+    .filter((skill): skill is ManagedSkill =>
+      !!skill && skill.management_enabled && skill.status !== 'archived');
+
+export const SkillCollectionsModal: React.FC<SkillCollectionsModalProps> = ({
+  open,
+  skills,
+  tools,
+  onClose,
+  onSuccess,
+}) => {
+  const { t } = useTranslation();
+  const [form] = Form.useForm<CollectionFormValues>();
+  const [collections, setCollections] = React.useState<SkillCollection[]>([]);
+  const [editing, setEditing] = React.useState<SkillCollection | null>(null);
+  const [saving, setSaving] = React.useState(false);
+  const [busyKey, setBusyKey] = React.useState<string | null>(null);
+
+  const skillsById = React.useMemo(() => new Map(skills.map((skill) => [skill.id, skill])), [skills]);
+  const skillOptions = React.useMemo(
+    () => skills.map((skill) => ({ value: skill.id, label: skill.name })),
+    [skills],
+  );
+
+  const loadCollections = React.useCallback(async () => {
+    try {
+      setCollections(await api.getSkillCollections());
+    } catch (error) {
+      message.error(String(error));
+    }
+  }, []);
+
+  const startEdit = (collection?: SkillCollection) => {
+    setEditing(collection ?? null);
+    form.setFieldsValue({
+      name: collection?.name ?? '',
+      note: collection?.note ?? '',
+      skillIds: collection?.skill_ids ?? [],
+    });
+  };
+
+  React.useEffect(() => {
+    if (open) {
+      void loadCollections();
+      startEdit();
+    }
+  }, [open]);
+
+  const handleSubmit = async (values: CollectionFormValues) => {
+    setSaving(true);
+    try {
+      await api.saveSkillCollection(
+        values.name,
+        values.note?.trim() || null,
+        values.skillIds ?? [],
+        editing?.id,
+      );
+      message.success(t('skills.collections.saveSuccess'));
+      startEdit();
+      await loadCollections();
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDelete = (collection: SkillCollection) => {
+    Modal.confirm({
+      title: t('skills.collections.deleteTitle'),
+      content: t('skills.collections.deleteContent', { name: collection.name }),
+      okText: t('common.delete'),
+      okButtonProps: { danger: true },
+      cancelText: t('common.cancel'),
+      onOk: async () => {
+        await api.deleteSkillCollection(collection.id);
+        if (editing?.id === collection.id) startEdit();
+        await loadCollections();
+      },
+    });
+  };
+
+  const reportToolResult = (result: SkillCollectionToolResult, enabled: boolean) => {
+    if (result.errors.length > 0) {
+      Modal.warning({
+        title: t('skills.collections.toolPartial'),
+        content: (
+          <ul className={styles.errorList}>
+            {result.errors.map((error) => <li key={error}>{error}</li>)}
+          </ul>
+        ),
+      });
+      return;
+    }
+    message.success(t(enabled ? 'skills.collections.toolEnabled' : 'skills.collections.toolDisabled', {
+      count: result.changed.length,
+    }));
+  };
+
+  const handleToggleTool = async (collection: SkillCollection, tool: ToolOption, enabled: boolean) => {
+    const key = `${collection.id}:${tool.id}`;
+    setBusyKey(key);
+    try {
+      const result = await api.setCollectionToolEnabled(collection.id, tool.id, enabled);
+      reportToolResult(result, enabled);
+      onSuccess();
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setBusyKey(null);
+    }
+  };
+
+  const handleExport = async () => {
+    try {
+      const path = await api.exportSkillCollectionsFile();
+      message.success(t('skills.collections.exportSuccess', { path }));
+    } catch (error) {
+      message.error(String(error));
+    }
+  };
+
+  const handleImport = async () => {
+    const selected = await openDialog({
+      title: t('skills.collections.importDialogTitle'),
+      multiple: false,
+      directory: false,
+      filters: [{ name: 'JSON', extensions: ['json'] }],
+    });
+    if (typeof selected !== 'string') {
+      return;
+    }
+    try {
+      const result = await api.importSkillCollectionsFile(selected);
+      await loadCollections();
+      if (result.unresolved.length > 0) {
+        Modal.info({
+          title: t('skills.collections.importUnresolved', { count: result.unresolved.length }),
+          content: (
+            <ul className={styles.errorList}>
+              {result.unresolved.map((item) => <li key={item}>{item}</li>)}
+            </ul>
+          ),
+        });
+      } else {
+        message.success(t('skills.collections.importSuccess', {
+          created: result.created,
+          updated: result.updated,
+        }));
+      }
+    } catch (error) {
+      message.error(String(error));
+    }
+  };
+
+  return (
+    <Modal
+      open={open}
+      title={t('skills.collections.title')}
+      onCancel={onClose}
+      footer={null}
+      width={1040}
+      destroyOnHidden
+    >
+      <div className={styles.content}>
+        <section className={styles.sectionCard}>
+          <div className={styles.panelHeader}>
+            <div>
+              <div className={styles.sectionTitle}>{t('skills.collections.listTitle')}</div>
+              <p className={styles.sectionDescription}>{t('skills.collections.listDescription')}</p>
+            </div>
+            <Space size={8}>
+              <Button size="small" icon={<FolderOpenOutlined />} onClick={handleImport}>
+                {t('skills.collections.import')}
+              </Button>
+              <Button
+                size="small"
+                icon={<DownloadOutlined />}
+                onClick={handleExport}
+                disabled={collections.length === 0}
+              >
+                {t('skills.collections.export')}
+              </Button>
+            </Space>
+          </div>
+
+          {collections.length === 0 ? (
+            <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('skills.collections.empty')} />
+          ) : (
+            <div className={styles.list}>
+              {collections.map((collection) => {
+                const members = eligibleMembers(collection, skillsById);
+                return (
+                  <div
+                    key={collection.id}
+                    role="button"
+                    tabIndex={0}
+                    className={editing?.id === collection.id ? `${styles.row} ${styles.rowActive}` : styles.row}
+                    onClick={() => startEdit(collection)}
+                    onKeyDown={(event) => {
+                      if (event.key === 'Enter' || event.key === ' ') {
+                        event.preventDefault();
+                        startEdit(collection);
+                      }
+                    }}
+                  >
+                    <div className={styles.rowHeader}>
+                      <strong>{collection.name}</strong>
+                      <span className={styles.meta}>
+                        {t('skills.collections.memberCount', { count: collection.skill_ids.length })}
+                      </span>
+                      {collection.source && <Tag className={styles.sourceTag}>{collection.source}</Tag>}
+                      <Button
+                        size="small"
+                        type="text"
+                        danger
+                        icon={<DeleteOutlined />}
+                        aria-label={t('common.delete')}
+                        onClick={(event) => {
+                          event.stopPropagation();
+                          handleDelete(collection);
+                        }}
+                      />
+                    </div>
+                    {collection.note && <div className={styles.note}>{collection.note}</div>}
+                    <div className={styles.tools} onClick={(event) => event.stopPropagation()}>
+                      {tools.map((tool) => {
+                        const enabled = members.length > 0
+                          && members.every((skill) => skill.targets.some((target) => target.tool === tool.id));
+                        return (
+                          <Button
+                            key={tool.id}
+                            size="small"
+                            type={enabled ? 'primary' : 'default'}
+                            ghost={enabled}
+                            loading={busyKey === `${collection.id}:${tool.id}`}
+                            disabled={members.length === 0 || (busyKey !== null && busyKey !== `${collection.id}:${tool.id}`)}
+                            title={t(enabled ? 'skills.collections.disableForTool' : 'skills.collections.enableForTool', {
+                              tool: tool.label,
+                            })}
+                            onClick={() => void handleToggleTool(collection, tool, !enabled)}
+                          >
+                            {tool.label}
+                          </Button>
+                        );
+                      })}
+                    </div>
+                  </div>
+                );
+              })}
+            </div>
+          )}
+        </section>
+
+        <section className={styles.sectionCard}>
+          <div className={styles.panelHeader}>
+            <div className={styles.sectionTitle}>
+              {editing ? t('skills.collections.editTitle', { name: editing.name }) : t('skills.collections.createTitle')}
+            </div>
+            {editing ? <Button onClick={() => startEdit()}>{t('skills.collections.newAction')}</Button> : null}
+          </div>
+          <Form form={form} layout="vertical" onFinish={handleSubmit}>
+            <Form.Item
+              label={t('skills.collections.name')}
+              name="name"
+              rules={[{ required: true, message: t('skills.collections.nameRequired') }]}
+            >
+              <Input placeholder={t('skills.collections.namePlaceholder')} />
+            </Form.Item>
+            <Form.Item label={t('skills.collections.note')} name="note">
+              <Input.TextArea rows={2} />
+            </Form.Item>
+            <Form.Item label={t('skills.collections.members')} name="skillIds">
+              <Select
+                mode="multiple"
+                options={skillOptions}
+                optionFilterProp="label"
+                placeholder={t('skills.collections.membersPlaceholder')}
+                maxTagCount="responsive"
+              />
+            </Form.Item>
+            <div className={styles.formActions}>
+              <Button onClick={() => startEdit()}>{editing ? t('common.cancel') : t('common.reset')}</Button>
+              <Button
+                type="primary"
+                htmlType="submit"
+                loading={saving}
+                icon={editing ? <EditOutlined /> : <PlusOutlined />}
+              >
+                {editing ? t('common.save') : t('skills.collections.create')}
+              </Button>
+            </div>
+          </Form>
+        </section>
+      </div>
+    </Modal>
+  );
+};
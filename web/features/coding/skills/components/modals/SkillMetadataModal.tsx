@@ -1,10 +1,11 @@
 import React from 'react';
-import { AutoComplete, Form, Input, message, Modal } from 'antd';
-import { FileTextOutlined, TagsOutlined } from '@ant-design/icons';
+import { AutoComplete, Button, Form, Input, message, Modal, Switch } from 'antd';
+import { ApiOutlined, FileTextOutlined, GlobalOutlined, LockOutlined, TagsOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import * as api from '../../services/skillsApi';
-import type { ManagedSkill } from '../../types';
+import type { ManagedSkill, SkillCompatibility, SkillLocalization } from '../../types';
 import { findSkillGroupOptionByName, normalizeSkillMetadataText } from '../../utils/skillGrouping';
+import { SKILL_LOCALIZATION_LANGUAGES } from '../../utils/skillLocalization';
 import styles from './SkillMetadataModal.module.less';
 
 interface SkillMetadataModalProps {
@@ -18,8 +19,46 @@ interface SkillMetadataModalProps {
 interface SkillMetadataFormValues {
   userGroup?: string;
   userNote?: string;
+  compatibility?: string;
+  localizations?: Record<string, SkillLocalization>;
+  writeProtected?: boolean;
 }
 
+// Trimmed entries without empty fields, in a stable order for comparison
+const normalizeLocalizations = (
+  localizations: Record<string, SkillLocalization> | undefined,
+): Record<string, SkillLocalization> => {
+  const result: Record<string, SkillLocalization> = {};
+  for (const language of SKILL_LOCALIZATION_LANGUAGES) {
+    const display_name = normalizeSkillMetadataText(localizations?.[language]?.display_name);
+    const description = normalizeSkillMetadataText(localizations?.[language]?.description);
+    if (display_name || description) {
+      result[language] = { display_name, description };
+    }
+  }
+  return result;
+};
+
+// One `tool: constraint` per line, e.g. `claude_code: >=2.0`
+const formatCompatibility = (compatibility: Record<string, string> | undefined) =>
+  Object.entries(compatibility ?? {})
+    .map(([tool, constraint]) => `${tool}: ${constraint}`)
+    .join('\n');
+
+const parseCompatibility = (text: string | undefined): Record<string, string> => {
+  const result: Record<string, string> = {};
+  for (const line of (text ?? '').split('\n')) {
+    const separator = line.indexOf(':');
+    if (separator <= 0) continue;
+    const tool = line.slice(0, separator).trim();
+    const constraint = line.slice(separator + 1).trim();
+    if (tool && constraint) {
+      result[tool] = constraint;
+    }
+  }
+  return result;
+};
+
 export const SkillMetadataModal: React.FC<SkillMetadataModalProps> = ({
   open,
   skill,
@@ -30,6 +69,7 @@ export const SkillMetadataModal: React.FC<SkillMetadataModalProps> = ({
   const { t } = useTranslation();
   const [form] = Form.useForm<SkillMetadataFormValues>();
   const [saving, setSaving] = React.useState(false);
+  const [compatibilityChecks, setCompatibilityChecks] = React.useState<SkillCompatibility[]>([]);
   const currentGroup = normalizeSkillMetadataText(skill?.user_group);
 
   React.useEffect(() => {
@@ -40,7 +80,27 @@ export const SkillMetadataModal: React.FC<SkillMetadataModalProps> = ({
     form.setFieldsValue({
       userGroup: skill.user_group ?? '',
       userNote: skill.user_note ?? '',
+      compatibility: formatCompatibility(skill.compatibility),
+      localizations: Object.fromEntries(SKILL_LOCALIZATION_LANGUAGES.map((language) => [
+        language,
+        {
+          display_name: skill.localizations?.[language]?.display_name ?? '',
+          description: skill.localizations?.[language]?.description ?? '',
+        },
+      ])),
+      writeProtected: skill.write_protected,
     });
+
+    let cancelled = false;
+    setCompatibilityChecks([]);
+    api.checkSkillCompatibility(skill.id)
+      .then((checks) => {
+        if (!cancelled) setCompatibilityChecks(checks);
+      })
+      .catch(console.error);
+    return () => {
+      cancelled = true;
+    };
   }, [form, open, skill]);
 
   const handleSubmit = async (values: SkillMetadataFormValues) => {
@@ -60,6 +120,17 @@ export const SkillMetadataModal: React.FC<SkillMetadataModalProps> = ({
         groupId,
         normalizeSkillMetadataText(values.userNote),
       );
+      const compatibility = parseCompatibility(values.compatibility);
+      if (formatCompatibility(compatibility) !== formatCompatibility(skill.compatibility)) {
+        await api.setSkillCompatibility(skill.id, compatibility);
+      }
+      const localizations = normalizeLocalizations(values.localizations);
+      if (JSON.stringify(localizations) !== JSON.stringify(normalizeLocalizations(skill.localizations))) {
+        await api.setSkillLocalizations(skill.id, localizations);
+      }
+      if (values.writeProtected !== undefined && values.writeProtected !== skill.write_protected) {
+        await api.setSkillWriteProtected(skill.id, values.writeProtected);
+      }
       message.success(t('skills.metadata.saveSuccess'));
       onSuccess();
     } catch (error) {
@@ -69,6 +140,30 @@ export const SkillMetadataModal: React.FC<SkillMetadataModalProps> = ({
     }
   };
 
+  // Fill only the empty fields, so text the user typed is kept
+  const handleFillFromDictionary = async () => {
+    if (!skill) {
+      return;
+    }
+    try {
+      const suggested = await api.getDictionaryLocalizations(skill.name);
+      if (Object.keys(suggested).length === 0) {
+        message.info(t('skills.localization.notInDictionary'));
+        return;
+      }
+      const current = form.getFieldValue('localizations') ?? {};
+      form.setFieldValue('localizations', Object.fromEntries(SKILL_LOCALIZATION_LANGUAGES.map((language) => [
+        language,
+        {
+          display_name: current[language]?.display_name || suggested[language]?.display_name || '',
+          description: current[language]?.description || suggested[language]?.description || '',
+        },
+      ])));
+    } catch (error) {
+      message.error(String(error));
+    }
+  };
+
   if (!skill) {
     return null;
   }
@@ -140,6 +235,83 @@ export const SkillMetadataModal: React.FC<SkillMetadataModalProps> = ({
                 autoSize={{ minRows: 4, maxRows: 8 }}
               />
             </Form.Item>
+            <Form.Item
+              label={(
+                <span className={styles.fieldLabel}>
+                  <ApiOutlined />
+                  {t('skills.compatibility.label')}
+                </span>
+              )}
+              name="compatibility"
+              extra={(
+                <>
+                  <div>{t('skills.compatibility.hint')}</div>
+                  {compatibilityChecks.map((check) => (
+                    <div key={check.tool}>
+                      {t(`skills.compatibility.status.${
+                        check.satisfied === null ? 'unknown' : check.satisfied ? 'ok' : 'incompatible'
+                      }`, {
+                        tool: check.tool,
+                        constraint: check.constraint,
+                        version: check.detected_version ?? '-',
+                        source: t(`skills.compatibility.source.${check.source}`),
+                      })}
+                    </div>
+                  ))}
+                </>
+              )}
+            >
+              <Input.TextArea
+                placeholder="claude_code: >=2.0"
+                autoSize={{ minRows: 2, maxRows: 6 }}
+              />
+            </Form.Item>
+            {skill.source_type !== 'linked_folder' && (
+              <Form.Item
+                label={(
+                  <span className={styles.fieldLabel}>
+                    <LockOutlined />
+                    {t('skills.metadata.writeProtected')}
+                  </span>
+                )}
+                name="writeProtected"
+                valuePropName="checked"
+                extra={t('skills.metadata.writeProtectedHint')}
+              >
+                <Switch />
+              </Form.Item>
+            )}
+          </section>
+          <section className={styles.sectionCard}>
+            {SKILL_LOCALIZATION_LANGUAGES.map((language) => (
+              <React.Fragment key={language}>
+                <Form.Item
+                  label={(
+                    <span className={styles.fieldLabel}>
+                      <GlobalOutlined />
+                      {t(`skills.localization.displayName.${language}`)}
+                    </span>
+                  )}
+                  name={['localizations', language, 'display_name']}
+                >
+                  <Input allowClear maxLength={80} placeholder={skill.name} />
+                </Form.Item>
+                <Form.Item
+                  label={t(`skills.localization.description.${language}`)}
+                  name={['localizations', language, 'description']}
+                >
+                  <Input.TextArea maxLength={1000} autoSize={{ minRows: 1, maxRows: 4 }} />
+                </Form.Item>
+              </React.Fragment>
+            ))}
+            <Form.Item
+              wrapperCol={{ offset: 5, span: 19 }}
+              extra={t('skills.localization.hint')}
+            >
+              <Button size="small" onClick={handleFillFromDictionary}>
+                {t('skills.localization.fillFromDictionary')}
+              </Button>
+            </Form.Item>
           </section>
         </Form>
       </div>
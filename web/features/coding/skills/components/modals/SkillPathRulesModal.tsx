@@ -0,0 +1,97 @@
+import React from 'react';
+import { Form, message, Modal, Select } from 'antd';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { ManagedSkill } from '../../types';
+import styles from './SkillPathRulesModal.module.less';
+
+interface SkillPathRulesModalProps {
+  open: boolean;
+  skill: ManagedSkill | null;
+  toolId: string | null;
+  toolLabel: string;
+  onClose: () => void;
+  onSuccess: () => void;
+}
+
+interface SkillPathRulesFormValues {
+  include: string[];
+  exclude: string[];
+}
+
+export const SkillPathRulesModal: React.FC<SkillPathRulesModalProps> = ({
+  open,
+  skill,
+  toolId,
+  toolLabel,
+  onClose,
+  onSuccess,
+}) => {
+  const { t } = useTranslation();
+  const [form] = Form.useForm<SkillPathRulesFormValues>();
+  const [saving, setSaving] = React.useState(false);
+
+  React.useEffect(() => {
+    if (!open || !skill || !toolId) {
+      return;
+    }
+    const rules = skill.targets.find((target) => target.tool === toolId)?.path_rules;
+    form.setFieldsValue({
+      include: rules?.include ?? [],
+      exclude: rules?.exclude ?? [],
+    });
+  }, [form, open, skill, toolId]);
+
+  const handleSubmit = async (values: SkillPathRulesFormValues) => {
+    if (!skill || !toolId) {
+      return;
+    }
+    setSaving(true);
+    try {
+      await api.setTargetPathRules(skill.id, toolId, values.include ?? [], values.exclude ?? []);
+      message.success(t('skills.pathRules.saved', { tool: toolLabel }));
+      onSuccess();
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!skill || !toolId) {
+    return null;
+  }
+
+  return (
+    <Modal
+      open={open}
+      title={t('skills.pathRules.title', { skill: skill.name, tool: toolLabel })}
+      okText={t('common.save')}
+      cancelText={t('common.cancel')}
+      confirmLoading={saving}
+      onCancel={onClose}
+      onOk={() => form.submit()}
+      destroyOnHidden
+    >
+      <p className={styles.hint}>{t('skills.pathRules.hint')}</p>
+      <Form form={form} layout="vertical" onFinish={handleSubmit}>
+        <Form.Item
+          label={t('skills.pathRules.include')}
+          name="include"
+          extra={t('skills.pathRules.includeHint')}
+        >
+          <Select mode="tags" open={false} tokenSeparators={[',', ' ']} placeholder="scripts" />
+        </Form.Item>
+        <Form.Item
+          label={t('skills.pathRules.exclude')}
+          name="exclude"
+          extra={t('skills.pathRules.excludeHint')}
+        >
+          <Select mode="tags" open={false} tokenSeparators={[',', ' ']} placeholder="assets" />
+        </Form.Item>
+      </Form>
+    </Modal>
+  );
+};
+
+export default SkillPathRulesModal;
@@ -0,0 +1,185 @@
+import React from 'react';
+import { Alert, Button, Collapse, Empty, Modal, Spin, Tag } from 'antd';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { ManagedSkill, SkillUpdateFileStatus, SkillUpdatePreview } from '../../types';
+import { parseChecksumRequiredError, promptArchiveChecksum } from '../../utils/errorHandlers';
+import styles from './SkillUpdatePreviewModal.module.less';
+
+interface SkillUpdatePreviewModalProps {
+  open: boolean;
+  skill: ManagedSkill | null;
+  onClose: () => void;
+  /** `checksum` is the archive checksum the user entered for the preview */
+  onUpdate: (skill: ManagedSkill, checksum?: string) => void;
+}
+
+const STATUS_COLORS: Record<SkillUpdateFileStatus, string> = {
+  added: 'green',
+  removed: 'red',
+  modified: 'blue',
+};
+
+const shortRevision = (revision: string | null) => (revision ? revision.slice(0, 8) : '-');
+
+export const SkillUpdatePreviewModal: React.FC<SkillUpdatePreviewModalProps> = ({
+  open,
+  skill,
+  onClose,
+  onUpdate,
+}) => {
+  const { t } = useTranslation();
+  const [preview, setPreview] = React.useState<SkillUpdatePreview | null>(null);
+  const [loading, setLoading] = React.useState(false);
+  const [error, setError] = React.useState<string | null>(null);
+  const [checksum, setChecksum] = React.useState<string | undefined>(undefined);
+
+  React.useEffect(() => {
+    setChecksum(undefined);
+  }, [skill]);
+
+  React.useEffect(() => {
+    if (!open || !skill) {
+      return;
+    }
+    let cancelled = false;
+    setPreview(null);
+    setError(null);
+    setLoading(true);
+    api.previewSkillUpdate(skill.id, checksum)
+      .then((result) => {
+        if (!cancelled) setPreview(result);
+      })
+      .catch((err) => {
+        if (!cancelled) setError(String(err));
+      })
+      .finally(() => {
+        if (!cancelled) setLoading(false);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open, skill, checksum]);
+
+  if (!skill) {
+    return null;
+  }
+
+  const checksumUrl = error && skill.source_type === 'archive' ? parseChecksumRequiredError(error) : null;
+  const enterChecksum = async () => {
+    if (!checksumUrl) return;
+    const entered = await promptArchiveChecksum(skill.name, checksumUrl, t);
+    if (entered) setChecksum(entered);
+  };
+
+  const hasChanges = (preview?.files.length ?? 0) > 0;
+
+  return (
+    <Modal
+      open={open}
+      title={t('skills.updatePreview.title', { name: skill.name })}
+      okText={t('skills.updatePreview.updateNow')}
+      cancelText={t('common.cancel')}
+      okButtonProps={{
+        disabled: loading || !preview || !hasChanges,
+        danger: (preview?.overwritten_local_files.length ?? 0) > 0,
+      }}
+      onCancel={onClose}
+      onOk={() => {
+        onUpdate(skill, checksum);
+        onClose();
+      }}
+      width={760}
+      destroyOnHidden
+    >
+      <Spin spinning={loading}>
+        <div className={styles.content}>
+          {error && (checksumUrl ? (
+            <Alert
+              type="warning"
+              showIcon
+              message={t('skills.checksum.promptTitle')}
+              description={t('skills.checksum.promptMessage', { name: skill.name })}
+              action={(
+                <Button size="small" onClick={() => void enterChecksum()}>
+                  {t('skills.checksum.enter')}
+                </Button>
+              )}
+            />
+          ) : (
+            <Alert type="error" showIcon message={error} />
+          ))}
+          {preview && (
+            <>
+              <div className={styles.revisions}>
+                {t('skills.updatePreview.revisions', {
+                  from: shortRevision(preview.current_revision),
+                  to: shortRevision(preview.new_revision),
+                })}
+              </div>
+              {preview.has_local_modifications && (
+                <Alert
+                  type="warning"
+                  showIcon
+                  message={t('skills.updatePreview.localModified')}
+                  description={preview.overwritten_local_files.length > 0
+                    ? preview.overwritten_local_files.join(', ')
+                    : undefined}
+                />
+              )}
+              <section>
+                <div className={styles.sectionTitle}>{t('skills.updatePreview.changelog')}</div>
+                {preview.changelog === null ? (
+                  <div className={styles.hint}>{t('skills.updatePreview.changelogUnavailable')}</div>
+                ) : preview.changelog.length === 0 ? (
+                  <div className={styles.hint}>{t('skills.updatePreview.noCommits')}</div>
+                ) : (
+                  <ul className={styles.commits}>
+                    {preview.changelog.map((commit) => (
+                      <li key={commit.revision}>
+                        <code>{shortRevision(commit.revision)}</code>
+                        <span className={styles.commitSummary}>{commit.summary}</span>
+                        <span className={styles.hint}>
+                          {commit.author} · {new Date(commit.committed_at).toLocaleDateString()}
+                        </span>
+                      </li>
+                    ))}
+                  </ul>
+                )}
+              </section>
+              <section>
+                <div className={styles.sectionTitle}>
+                  {t('skills.updatePreview.files', { count: preview.files.length })}
+                </div>
+                {hasChanges ? (
+                  <Collapse
+                    size="small"
+                    items={preview.files.map((file) => ({
+                      key: file.path,
+                      label: (
+                        <span className={styles.fileLabel}>
+                          <Tag color={STATUS_COLORS[file.status]}>
+                            {t(`skills.updatePreview.status.${file.status}`)}
+                          </Tag>
+                          <span className={styles.filePath}>{file.path}</span>
+                          {preview.overwritten_local_files.includes(file.path) && (
+                            <Tag color="orange">{t('skills.updatePreview.localEdit')}</Tag>
+                          )}
+                        </span>
+                      ),
+                      children: file.patch
+                        ? <pre className={styles.patch}>{file.patch}</pre>
+                        : <div className={styles.hint}>{t('skills.updatePreview.patchTooLarge')}</div>,
+                    }))}
+                  />
+                ) : (
+                  <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('skills.updatePreview.upToDate')} />
+                )}
+              </section>
+            </>
+          )}
+        </div>
+      </Spin>
+    </Modal>
+  );
+};
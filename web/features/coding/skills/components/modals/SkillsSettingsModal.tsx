@@ -1,19 +1,38 @@
 import React from 'react';
-import { Modal, InputNumber, Button, Checkbox, message, Form, Input, Space, Tooltip, Switch, Radio, Alert } from 'antd';
-import { FolderOpenOutlined, DeleteOutlined, PlusOutlined, ClearOutlined, ReloadOutlined, SwapOutlined } from '@ant-design/icons';
+import { Modal, InputNumber, Button, Checkbox, message, Form, Input, Space, Tooltip, Switch, Radio, Alert, Select } from 'antd';
+import { FolderOpenOutlined, DeleteOutlined, PlusOutlined, ClearOutlined, ReloadOutlined, SwapOutlined, LockOutlined, GlobalOutlined, EyeOutlined, LinkOutlined, SyncOutlined } from '@ant-design/icons';
 import { open } from '@tauri-apps/plugin-dialog';
 import { revealItemInDir } from '@tauri-apps/plugin-opener';
 import { useTranslation } from 'react-i18next';
-import type { ToolInfo, CustomTool, SkillViewMode, CentralRepoPathPreview, CentralRepoScan } from '../../types';
+import type { ToolInfo, CustomTool, PreferredSyncMode, SkillViewMode, CentralRepoPathPreview, CentralRepoScan, DefaultSkillSets, NameCollisionPolicies, NameCollisionPolicy, SkillSyncRun } from '../../types';
 import * as api from '../../services/skillsApi';
 import { useSkillsStore } from '../../stores/skillsStore';
 import { refreshTrayMenu } from '@/services/appApi';
+import {
+  approveToolPlugin,
+  listToolPlugins,
+  reloadToolPlugins,
+  type ToolPlugin,
+  type ToolPlugins,
+} from '@/services/toolPluginsApi';
+import { useSettingsStore } from '@/stores';
 import {
   parseManagementGridColumnSetting,
   type ManagementGridColumnSetting,
 } from '@/features/coding/shared/management';
+import { ToolLoadPreviewModal } from './ToolLoadPreviewModal';
 import styles from './SkillsSettingsModal.module.less';
 
+type CustomToolSyncMode = 'auto' | PreferredSyncMode;
+
+const CUSTOM_TOOL_SYNC_MODES: { value: CustomToolSyncMode; labelKey: string }[] = [
+  { value: 'auto', labelKey: 'syncModeAuto' },
+  { value: 'copy', labelKey: 'syncModeCopy' },
+  { value: 'symlink', labelKey: 'syncModeSymlink' },
+  { value: 'junction', labelKey: 'syncModeJunction' },
+  { value: 'hardlink', labelKey: 'syncModeHardlink' },
+];
+
 interface SkillsSettingsModalProps {
   open: boolean;
   cardColumnSetting?: ManagementGridColumnSetting;
@@ -32,6 +51,8 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
   onClose,
 }) => {
   const { t } = useTranslation();
+  const { policy } = useSettingsStore();
+  const pathLockedByPolicy = Boolean(policy?.active && policy.policy.central_repo_path);
   const { loadToolStatus, skills, loadSkills, loadCentralRepoPath } = useSkillsStore();
   const [form] = Form.useForm();
   const [path, setPath] = React.useState('');
@@ -47,14 +68,29 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
   const [selectedScanAdoptions, setSelectedScanAdoptions] = React.useState<string[]>([]);
   const [cleanupDays, setCleanupDays] = React.useState(30);
   const [ttlSecs, setTtlSecs] = React.useState(60);
+  const [variantsPerSkill, setVariantsPerSkill] = React.useState(0);
+  const [copyIgnorePatterns, setCopyIgnorePatterns] = React.useState('');
   const [loading, setLoading] = React.useState(false);
   const [clearingCache, setClearingCache] = React.useState(false);
+  const [fillingLocalizations, setFillingLocalizations] = React.useState(false);
+  const [repairingLinks, setRepairingLinks] = React.useState(false);
+  const [autoSyncInterval, setAutoSyncIntervalState] = React.useState(0);
+  const [lastSyncRun, setLastSyncRun] = React.useState<SkillSyncRun | null>(null);
+  const [runningSync, setRunningSync] = React.useState(false);
   const [allTools, setAllTools] = React.useState<ToolInfo[]>([]);
   const [preferredTools, setPreferredTools] = React.useState<string[]>([]);
+  const [defaultSkillSets, setDefaultSkillSets] = React.useState<DefaultSkillSets>({ global: [], per_tool: {} });
+  const [collisionPolicies, setCollisionPolicies] = React.useState<NameCollisionPolicies>({ default: 'block', per_tool: {} });
   const [customTools, setCustomTools] = React.useState<CustomTool[]>([]);
+  const [toolPlugins, setToolPlugins] = React.useState<ToolPlugins | null>(null);
+  const [reloadingPlugins, setReloadingPlugins] = React.useState(false);
   const [addingTool, setAddingTool] = React.useState(false);
   const [showAddCustomModal, setShowAddCustomModal] = React.useState(false);
   const [showInTray, setShowInTray] = React.useState(false);
+  const [autoAdopt, setAutoAdopt] = React.useState(false);
+  const [autoResync, setAutoResync] = React.useState(true);
+  const [loadPreviewTool, setLoadPreviewTool] = React.useState<ToolInfo | null>(null);
+  const [relativeSymlinks, setRelativeSymlinks] = React.useState(false);
   const [defaultViewMode, setDefaultViewMode] = React.useState<SkillViewMode>('flat');
   const [showClearAllModal, setShowClearAllModal] = React.useState(false);
   const [clearAllConfirmText, setClearAllConfirmText] = React.useState('');
@@ -65,9 +101,23 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     loadPathStatus();
     api.getGitCacheCleanupDays().then(setCleanupDays).catch(console.error);
     api.getGitCacheTtlSecs().then(setTtlSecs).catch(console.error);
+    api.getVariantsPerSkill().then(setVariantsPerSkill).catch(console.error);
+    api.getCopyIgnorePatterns()
+      .then((patterns) => setCopyIgnorePatterns(patterns.join('\n')))
+      .catch(console.error);
     api.getShowSkillsInTray().then(setShowInTray).catch(console.error);
+    api.getAutoAdoptOnboarding().then(setAutoAdopt).catch(console.error);
+    api.getAutoResyncCopies().then(setAutoResync).catch(console.error);
+    api.getAutoSyncInterval().then(setAutoSyncIntervalState).catch(console.error);
+    api.listSyncRuns(1)
+      .then((runs) => setLastSyncRun(runs[0] ?? null))
+      .catch(console.error);
+    api.getRelativeSymlinks().then(setRelativeSymlinks).catch(console.error);
     api.getDefaultViewMode().then(setDefaultViewMode).catch(console.error);
+    api.getDefaultSkillSets().then(setDefaultSkillSets).catch(console.error);
+    api.getNameCollisionPolicies().then(setCollisionPolicies).catch(console.error);
     loadCustomTools();
+    listToolPlugins().then(setToolPlugins).catch(console.error);
     loadSkills();
 
     // Load tools and preferred tools together
@@ -255,6 +305,36 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     }
   };
 
+  const handleAutoAdoptChange = async (checked: boolean) => {
+    setAutoAdopt(checked);
+    try {
+      await api.setAutoAdoptOnboarding(checked);
+    } catch (error) {
+      message.error(String(error));
+      setAutoAdopt(!checked);
+    }
+  };
+
+  const handleAutoResyncChange = async (checked: boolean) => {
+    setAutoResync(checked);
+    try {
+      await api.setAutoResyncCopies(checked);
+    } catch (error) {
+      message.error(String(error));
+      setAutoResync(!checked);
+    }
+  };
+
+  const handleRelativeSymlinksChange = async (checked: boolean) => {
+    setRelativeSymlinks(checked);
+    try {
+      await api.setRelativeSymlinks(checked);
+    } catch (error) {
+      message.error(String(error));
+      setRelativeSymlinks(!checked);
+    }
+  };
+
   // Sort tools: installed built-in > custom tools > not installed built-in
   const sortedTools = React.useMemo(() => {
     const customKeys = new Set(customTools.map(c => c.key));
@@ -264,12 +344,34 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     return [...installedBuiltin, ...customToolItems, ...notInstalledBuiltin];
   }, [allTools, customTools]);
 
+  const collisionPolicyOptions = (['block', 'auto_suffix', 'priority'] as NameCollisionPolicy[]).map((policy) => ({
+    value: policy,
+    label: t(`skills.collisionPolicy.${policy}`),
+  }));
+
+  const handleToolCollisionPolicy = (toolKey: string, value: NameCollisionPolicy | 'default') => {
+    setCollisionPolicies((prev) => {
+      const perTool = { ...prev.per_tool };
+      if (value === 'default') {
+        delete perTool[toolKey];
+      } else {
+        perTool[toolKey] = value;
+      }
+      return { ...prev, per_tool: perTool };
+    });
+  };
+
   const handleSave = async () => {
     setLoading(true);
     try {
       await api.setGitCacheCleanupDays(cleanupDays);
+      await api.setVariantsPerSkill(variantsPerSkill);
       await api.setPreferredTools(preferredTools);
+      await api.saveDefaultSkillSets(defaultSkillSets);
+      await api.saveNameCollisionPolicies(collisionPolicies);
       await api.setDefaultViewMode(defaultViewMode);
+      const savedPatterns = await api.saveCopyIgnorePatterns(copyIgnorePatterns.split('\n'));
+      setCopyIgnorePatterns(savedPatterns.join('\n'));
       await loadToolStatus(); // Refresh global store
       onDefaultViewModeApply?.(defaultViewMode);
       message.success(t('common.success'));
@@ -293,6 +395,76 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     }
   };
 
+  const handleFillLocalizations = async () => {
+    setFillingLocalizations(true);
+    try {
+      const count = await api.fillSkillLocalizations();
+      await loadSkills();
+      message.success(t('skills.localization.filled', { count }));
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setFillingLocalizations(false);
+    }
+  };
+
+  const handleRepairLinks = async () => {
+    setRepairingLinks(true);
+    try {
+      const result = await api.repairLinks();
+      await loadSkills();
+      const summary = t('skills.repairLinks.done', {
+        checked: result.checked,
+        repaired: result.repaired.length,
+        broken: result.broken.length,
+      });
+      if (result.broken.length > 0) {
+        message.warning(summary);
+      } else {
+        message.success(summary);
+      }
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setRepairingLinks(false);
+    }
+  };
+
+  const handleAutoSyncIntervalChange = async (minutes: number) => {
+    const previous = autoSyncInterval;
+    setAutoSyncIntervalState(minutes);
+    try {
+      await api.setAutoSyncInterval(minutes);
+    } catch (error) {
+      message.error(String(error));
+      setAutoSyncIntervalState(previous);
+    }
+  };
+
+  const handleRunSyncNow = async () => {
+    setRunningSync(true);
+    try {
+      const run = await api.runSkillSyncNow();
+      setLastSyncRun(run);
+      await loadSkills();
+      const summary = t('skills.autoSync.done', {
+        checked: run.checked,
+        resynced: run.resynced.length,
+        repaired: run.repaired.length,
+        failed: run.errors.length,
+      });
+      if (run.errors.length > 0) {
+        message.warning(summary);
+      } else {
+        message.success(summary);
+      }
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setRunningSync(false);
+    }
+  };
+
   const handleOpenCacheFolder = async () => {
     try {
       const cachePath = await api.getGitCachePath();
@@ -306,7 +478,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     key: string;
     displayName: string;
     relativeSkillsDir: string;
-    forceCopy?: boolean;
+    syncMode: CustomToolSyncMode;
   }) => {
     setAddingTool(true);
     try {
@@ -344,7 +516,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     key: string;
     displayName: string;
     relativeSkillsDir: string;
-    forceCopy?: boolean;
+    syncMode: CustomToolSyncMode;
   }) => {
     try {
       // Derive detectDir from skillsDir by taking the parent directory
@@ -356,7 +528,8 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
         values.displayName,
         values.relativeSkillsDir,
         relativeDetectDir,
-        values.forceCopy
+        values.syncMode === 'copy',
+        values.syncMode === 'auto' || values.syncMode === 'copy' ? undefined : values.syncMode,
       );
       message.success(t('common.success'));
       form.resetFields();
@@ -377,6 +550,50 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     }
   };
 
+  const applyToolPlugins = async (plugins: ToolPlugins) => {
+    setToolPlugins(plugins);
+    await loadToolStatus(); // Update global store
+    const status = await api.getToolStatus();
+    const sorted = [...status.tools].sort((a, b) => {
+      if (a.installed === b.installed) return 0;
+      return a.installed ? -1 : 1;
+    });
+    setAllTools(sorted);
+  };
+
+  const handleReloadToolPlugins = async () => {
+    setReloadingPlugins(true);
+    try {
+      const plugins = await reloadToolPlugins();
+      const loaded = plugins.plugins.filter((plugin) => plugin.approved && !plugin.error).length;
+      const failed = plugins.plugins.filter((plugin) => plugin.error).length;
+      message.success(t('skills.toolPlugins.reloaded', { count: loaded, failed }));
+      await applyToolPlugins(plugins);
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setReloadingPlugins(false);
+    }
+  };
+
+  const setToolPluginApproval = async (plugin: ToolPlugin, approved: boolean) => {
+    try {
+      await applyToolPlugins(await approveToolPlugin(plugin.file, plugin.sha256, approved));
+    } catch (error) {
+      message.error(String(error));
+    }
+  };
+
+  const handleApproveToolPlugin = (plugin: ToolPlugin) => {
+    Modal.confirm({
+      title: t('skills.toolPlugins.approveTitle', { file: plugin.file }),
+      content: t('skills.toolPlugins.approveMessage', { file: plugin.file, sha256: plugin.sha256 }),
+      okText: t('skills.toolPlugins.approve'),
+      cancelText: t('common.cancel'),
+      onOk: () => setToolPluginApproval(plugin, true),
+    });
+  };
+
   const handleRemoveCustomTool = async (key: string) => {
     try {
       await api.removeCustomTool(key);
@@ -395,6 +612,19 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
     }
   };
 
+  const handleCleanDanglingLinks = async (tool: ToolInfo) => {
+    try {
+      const removed = await api.cleanDanglingLinks(tool.key);
+      message.success(t('skills.danglingLinks.cleaned', { count: removed.length }));
+      await loadToolStatus();
+      setAllTools((prev) => prev.map((item) => (
+        item.key === tool.key ? { ...item, dangling_links: [] } : item
+      )));
+    } catch (error) {
+      message.error(String(error));
+    }
+  };
+
   const expectedConfirmText = t('skills.clearAll.confirmText');
 
   const handleClearAllSkills = async () => {
@@ -437,6 +667,11 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
             {pathUsesDefault && (
               <span className={styles.pathTag}>{t('skills.globalDir.defaultTag')}</span>
             )}
+            {pathLockedByPolicy && (
+              <Tooltip title={t('skills.globalDir.lockedByPolicy')}>
+                <span className={styles.pathTag}><LockOutlined /> {t('skills.globalDir.policyTag')}</span>
+              </Tooltip>
+            )}
             <Tooltip title={t('skills.globalDir.openFolder')}>
               <Button
                 type="text"
@@ -454,6 +689,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
               className={styles.pathActionButton}
               icon={<SwapOutlined />}
               loading={pathActionLoading && !pathPreview && !scanResult}
+              disabled={pathLockedByPolicy}
               onClick={handleChooseCentralPath}
             >
               {t('skills.globalDir.change')}
@@ -461,7 +697,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
             <Button
               size="small"
               className={styles.pathActionButton}
-              disabled={pathUsesDefault}
+              disabled={pathUsesDefault || pathLockedByPolicy}
               onClick={handleRestoreDefaultPath}
             >
               {t('skills.globalDir.restoreDefault')}
@@ -498,6 +734,36 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
         </div>
       </div>
 
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.autoAdoptOnboarding')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Switch checked={autoAdopt} onChange={handleAutoAdoptChange} />
+          <p className={styles.hint}>{t('skills.autoAdoptOnboardingHint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.autoResyncCopies')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Switch checked={autoResync} onChange={handleAutoResyncChange} />
+          <p className={styles.hint}>{t('skills.autoResyncCopiesHint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.relativeSymlinks')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Switch checked={relativeSymlinks} onChange={handleRelativeSymlinksChange} />
+          <p className={styles.hint}>{t('skills.relativeSymlinksHint')}</p>
+        </div>
+      </div>
+
       <div className={styles.section}>
         <div className={styles.labelArea}>
           <label className={styles.label}>{t('skills.defaultViewMode')}</label>
@@ -558,6 +824,28 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
                       {tool.label}
                     </Checkbox>
                   </Tooltip>
+                  {tool.installed && (
+                    <Tooltip title={t('skills.toolLoad.open')}>
+                      <Button
+                        type="text"
+                        size="small"
+                        icon={<EyeOutlined />}
+                        onClick={() => setLoadPreviewTool(tool)}
+                      />
+                    </Tooltip>
+                  )}
+                  {tool.dangling_links.length > 0 && (
+                    <Tooltip title={tool.dangling_links.join('\n')}>
+                      <Button
+                        type="link"
+                        size="small"
+                        danger
+                        onClick={() => handleCleanDanglingLinks(tool)}
+                      >
+                        {t('skills.danglingLinks.clean', { count: tool.dangling_links.length })}
+                      </Button>
+                    </Tooltip>
+                  )}
                   {isCustomTool && (
                     <Button
                       type="text"
@@ -583,6 +871,107 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
         </div>
       </div>
 
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.toolPlugins.title')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Space>
+            <Button icon={<ReloadOutlined />} onClick={handleReloadToolPlugins} loading={reloadingPlugins}>
+              {t('skills.toolPlugins.reload')}
+            </Button>
+            {toolPlugins?.dir && (
+              <Button
+                icon={<FolderOpenOutlined />}
+                onClick={() => revealItemInDir(toolPlugins.dir!).catch(console.error)}
+              >
+                {t('skills.toolPlugins.openDir')}
+              </Button>
+            )}
+          </Space>
+          {toolPlugins && toolPlugins.plugins.length > 0 && (
+            <ul className={styles.hint}>
+              {toolPlugins.plugins.map((plugin) => (
+                <li key={plugin.file}>
+                  {!plugin.approved && !plugin.error
+                    ? t('skills.toolPlugins.unapproved', { file: plugin.file })
+                    : plugin.error
+                      ? t('skills.toolPlugins.failed', { file: plugin.file, error: plugin.error })
+                      : t(plugin.installed ? 'skills.toolPlugins.installed' : 'skills.toolPlugins.notInstalled', {
+                        file: plugin.file,
+                        name: plugin.display_name,
+                        dir: plugin.tool_dir,
+                      })}
+                  {plugin.approved ? (
+                    <Button type="link" size="small" onClick={() => setToolPluginApproval(plugin, false)}>
+                      {t('skills.toolPlugins.revoke')}
+                    </Button>
+                  ) : plugin.sha256 && (
+                    <Button type="link" size="small" onClick={() => handleApproveToolPlugin(plugin)}>
+                      {t('skills.toolPlugins.approve')}
+                    </Button>
+                  )}
+                </li>
+              ))}
+            </ul>
+          )}
+          <p className={styles.hint}>{t('skills.toolPlugins.hint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.defaultSkillSet.label')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Select
+            mode="multiple"
+            allowClear
+            style={{ width: '100%' }}
+            value={defaultSkillSets.global}
+            onChange={(global: string[]) => setDefaultSkillSets((prev) => ({ ...prev, global }))}
+            options={skills.map((skill) => ({ label: skill.name, value: skill.id }))}
+            optionFilterProp="label"
+            placeholder={t('skills.defaultSkillSet.placeholder')}
+          />
+          <p className={styles.hint}>{t('skills.defaultSkillSet.hint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.collisionPolicy.label')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Select
+            style={{ width: 240 }}
+            value={collisionPolicies.default}
+            onChange={(value: NameCollisionPolicy) => setCollisionPolicies((prev) => ({ ...prev, default: value }))}
+            options={collisionPolicyOptions}
+          />
+          <div className={styles.toolList} style={{ marginTop: 8 }}>
+            {sortedTools
+              .filter((tool) => tool.installed || customTools.some((c) => c.key === tool.key))
+              .map((tool) => (
+                <div key={tool.key} className={styles.toolItem}>
+                  <span>{tool.label}</span>
+                  <Select
+                    size="small"
+                    style={{ width: 180 }}
+                    value={collisionPolicies.per_tool[tool.key] ?? 'default'}
+                    onChange={(value: NameCollisionPolicy | 'default') => handleToolCollisionPolicy(tool.key, value)}
+                    options={[
+                      { value: 'default', label: t('skills.collisionPolicy.useDefault') },
+                      ...collisionPolicyOptions,
+                    ]}
+                  />
+                </div>
+              ))}
+          </div>
+          <p className={styles.hint}>{t('skills.collisionPolicy.hint')}</p>
+        </div>
+      </div>
+
       <div className={styles.section}>
         <div className={styles.labelArea}>
           <label className={styles.label}>{t('skills.gitCacheCleanupDays')}</label>
@@ -632,6 +1021,110 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
         </div>
       </div>
 
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.variantsPerSkill')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <InputNumber
+            min={0}
+            max={1000}
+            value={variantsPerSkill}
+            onChange={(v) => setVariantsPerSkill(v || 0)}
+            style={{ width: 120 }}
+          />
+          <p className={styles.hint}>{t('skills.variantsPerSkillHint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.copyIgnorePatterns')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Input.TextArea
+            value={copyIgnorePatterns}
+            onChange={(e) => setCopyIgnorePatterns(e.target.value)}
+            autoSize={{ minRows: 3, maxRows: 8 }}
+            placeholder={'node_modules/\n__pycache__/\n.DS_Store'}
+            style={{ fontFamily: 'monospace' }}
+          />
+          <p className={styles.hint}>{t('skills.copyIgnorePatternsHint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.localization.title')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Button
+            icon={<GlobalOutlined />}
+            onClick={handleFillLocalizations}
+            loading={fillingLocalizations}
+            disabled={skills.length === 0}
+          >
+            {t('skills.localization.fillAll')}
+          </Button>
+          <p className={styles.hint}>{t('skills.localization.fillAllHint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.repairLinks.title')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Button
+            icon={<LinkOutlined />}
+            onClick={handleRepairLinks}
+            loading={repairingLinks}
+            disabled={skills.length === 0}
+          >
+            {t('skills.repairLinks.button')}
+          </Button>
+          <p className={styles.hint}>{t('skills.repairLinks.hint')}</p>
+        </div>
+      </div>
+
+      <div className={styles.section}>
+        <div className={styles.labelArea}>
+          <label className={styles.label}>{t('skills.autoSync.title')}</label>
+        </div>
+        <div className={styles.inputArea}>
+          <Space>
+            <Select
+              style={{ width: 180 }}
+              value={autoSyncInterval}
+              onChange={handleAutoSyncIntervalChange}
+              options={[0, 30, 60, 360, 1440].map((minutes) => ({
+                value: minutes,
+                label: t(`skills.autoSync.interval.${minutes}`),
+              }))}
+            />
+            <Button
+              icon={<SyncOutlined />}
+              onClick={handleRunSyncNow}
+              loading={runningSync}
+              disabled={skills.length === 0}
+            >
+              {t('skills.autoSync.runNow')}
+            </Button>
+          </Space>
+          <p className={styles.hint}>{t('skills.autoSync.hint')}</p>
+          {lastSyncRun && (
+            <p className={styles.hint}>
+              {t('skills.autoSync.lastRun', {
+                time: new Date(lastSyncRun.started_at).toLocaleString(),
+                resynced: lastSyncRun.resynced.length,
+                repaired: lastSyncRun.repaired.length,
+                failed: lastSyncRun.errors.length,
+              })}
+            </p>
+          )}
+        </div>
+      </div>
+
       <div className={styles.section}>
         <div className={styles.labelArea}>
           <label className={styles.label}>{t('skills.clearAll.title')}</label>
@@ -663,7 +1156,7 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
           onCancel={() => setShowAddCustomModal(false)}
           footer={null}
         >
-        <Form form={form} layout="vertical" onFinish={handleAddCustomTool} initialValues={{ forceCopy: false }}>
+        <Form form={form} layout="vertical" onFinish={handleAddCustomTool} initialValues={{ syncMode: 'auto' }}>
           <Form.Item
             name="key"
             label={t('skills.customToolSettings.key')}
@@ -692,20 +1185,22 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
           <div style={{ display: 'flex', alignItems: 'flex-start', marginBottom: 24 }}>
             <label style={{ width: 100, flexShrink: 0, paddingTop: 5 }}>{t('skills.customToolSettings.syncMode')}</label>
             <div style={{ flex: 1 }}>
-              <Form.Item name="forceCopy" noStyle>
+              <Form.Item name="syncMode" noStyle>
                 <Radio.Group>
-                  <Radio value={false}>{t('skills.customToolSettings.syncModeAuto')}</Radio>
-                  <Radio value={true}>{t('skills.customToolSettings.syncModeCopy')}</Radio>
+                  {CUSTOM_TOOL_SYNC_MODES.map((mode) => (
+                    <Radio key={mode.value} value={mode.value}>{t(`skills.customToolSettings.${mode.labelKey}`)}</Radio>
+                  ))}
                 </Radio.Group>
               </Form.Item>
-              <Form.Item noStyle shouldUpdate={(prev, cur) => prev.forceCopy !== cur.forceCopy}>
-                {({ getFieldValue }) => (
-                  <div style={{ fontSize: 12, color: '#888', marginTop: 4 }}>
-                    {getFieldValue('forceCopy')
-                      ? t('skills.customToolSettings.syncModeCopyHint')
-                      : t('skills.customToolSettings.syncModeAutoHint')}
-                  </div>
-                )}
+              <Form.Item noStyle shouldUpdate={(prev, cur) => prev.syncMode !== cur.syncMode}>
+                {({ getFieldValue }) => {
+                  const mode = CUSTOM_TOOL_SYNC_MODES.find((item) => item.value === getFieldValue('syncMode'));
+                  return (
+                    <div style={{ fontSize: 12, color: '#888', marginTop: 4 }}>
+                      {t(`skills.customToolSettings.${mode?.labelKey ?? 'syncModeAuto'}Hint`)}
+                    </div>
+                  );
+                }}
               </Form.Item>
             </div>
           </div>
@@ -1024,6 +1519,8 @@ export const SkillsSettingsModal: React.FC<SkillsSettingsModalProps> = ({
           </Space>
         </div>
       </Modal>
+
+      <ToolLoadPreviewModal tool={loadPreviewTool} onClose={() => setLoadPreviewTool(null)} />
     </Modal>
   );
 };
@@ -0,0 +1,112 @@
+import React from 'react';
+import { Alert, Collapse, Empty, Modal, Spin, Tag } from 'antd';
+import { useTranslation } from 'react-i18next';
+import * as api from '../../services/skillsApi';
+import type { ToolInfo, ToolLoadPreview } from '../../types';
+import styles from './ToolLoadPreviewModal.module.less';
+
+interface ToolLoadPreviewModalProps {
+  tool: ToolInfo | null;
+  onClose: () => void;
+}
+
+export const ToolLoadPreviewModal: React.FC<ToolLoadPreviewModalProps> = ({ tool, onClose }) => {
+  const { t } = useTranslation();
+  const [preview, setPreview] = React.useState<ToolLoadPreview | null>(null);
+  const [loading, setLoading] = React.useState(false);
+  const [error, setError] = React.useState<string | null>(null);
+
+  React.useEffect(() => {
+    if (!tool) {
+      return;
+    }
+    setLoading(true);
+    setError(null);
+    setPreview(null);
+    api.previewToolLoad(tool.key)
+      .then(setPreview)
+      .catch((err) => setError(String(err)))
+      .finally(() => setLoading(false));
+  }, [tool]);
+
+  const rulesSummary = preview && t('skills.toolLoad.rules', {
+    entry: preview.rules.entry_file,
+    depth: preview.rules.max_depth,
+    keys: preview.rules.required_keys.join(', '),
+    links: preview.rules.follows_links
+      ? t('skills.toolLoad.linksFollowed')
+      : t('skills.toolLoad.linksIgnored'),
+  });
+
+  return (
+    <Modal
+      open={!!tool}
+      title={t('skills.toolLoad.title', { tool: tool?.label ?? '' })}
+      onCancel={onClose}
+      footer={null}
+      width={720}
+      destroyOnHidden
+    >
+      <Spin spinning={loading}>
+        <div className={styles.content}>
+          {error && <Alert type="error" showIcon message={error} />}
+          {preview && (
+            <>
+              <div className={styles.path}>{preview.skills_dir}</div>
+              <div className={styles.hint}>{rulesSummary}</div>
+              {!preview.exists && <Alert type="warning" showIcon message={t('skills.toolLoad.missingDir')} />}
+
+              <div className={styles.sectionTitle}>
+                {t('skills.toolLoad.loaded', { count: preview.loaded.length })}
+              </div>
+              {preview.loaded.length === 0 ? (
+                <Empty image={Empty.PRESENTED_IMAGE_SIMPLE} description={t('skills.toolLoad.noneLoaded')} />
+              ) : (
+                <Collapse
+                  size="small"
+                  items={preview.loaded.map((skill) => ({
+                    key: skill.path,
+                    label: (
+                      <span className={styles.label}>
+                        <span className={styles.name}>{skill.name}</span>
+                        <span className={styles.path}>{skill.path}</span>
+                        {skill.is_link && <Tag>{t('skills.toolLoad.link')}</Tag>}
+                        {skill.system && <Tag color="purple">{t('skills.toolLoad.system')}</Tag>}
+                      </span>
+                    ),
+                    children: (
+                      <ul className={styles.files}>
+                        {skill.files.map((file) => <li key={file}>{file}</li>)}
+                      </ul>
+                    ),
+                  }))}
+                />
+              )}
+
+              {preview.skipped.length > 0 && (
+                <>
+                  <div className={styles.sectionTitle}>
+                    {t('skills.toolLoad.skipped', { count: preview.skipped.length })}
+                  </div>
+                  <ul className={styles.skipped}>
+                    {preview.skipped.map((entry) => (
+                      <li key={entry.path}>
+                        <span className={styles.path}>{entry.path}</span>
+                        <span className={styles.reason}>
+                          {t(`skills.toolLoad.reason.${entry.reason}`, {
+                            entry: preview.rules.entry_file,
+                            detail: entry.detail ?? '',
+                          })}
+                        </span>
+                      </li>
+                    ))}
+                  </ul>
+                </>
+              )}
+            </>
+          )}
+        </div>
+      </Spin>
+    </Modal>
+  );
+};
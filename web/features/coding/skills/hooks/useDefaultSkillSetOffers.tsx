@@ -0,0 +1,80 @@
+import React from 'react';
+import { App, Button, Space } from 'antd';
+import { useTranslation } from 'react-i18next';
+import * as api from '../services/skillsApi';
+import { useSkillsStore } from '../stores/skillsStore';
+import type { DefaultSetOffer } from '../types';
+
+/**
+ * On startup, offer the default skill set to each newly detected tool.
+ * Nothing is synced until the user confirms in the notification.
+ */
+export function useDefaultSkillSetOffers() {
+  const { t } = useTranslation();
+  const { notification, message } = App.useApp();
+  const hasCheckedRef = React.useRef(false);
+
+  const apply = React.useCallback(
+    async (offer: DefaultSetOffer) => {
+      notification.destroy(`default-skill-set-${offer.tool}`);
+      try {
+        const result = await api.applyDefaultSkillSet(offer.tool);
+        if (result.errors.length > 0) {
+          console.warn(`Default skill set for ${offer.tool}:`, result.errors);
+          message.warning(
+            t('skills.defaultSkillSet.appliedWithErrors', {
+              tool: offer.tool_display,
+              count: result.synced.length,
+              failed: result.errors.length,
+            }),
+          );
+        } else {
+          message.success(
+            t('skills.defaultSkillSet.applied', {
+              tool: offer.tool_display,
+              count: result.synced.length,
+            }),
+          );
+        }
+        await useSkillsStore.getState().loadSkills();
+      } catch (error) {
+        message.error(String(error));
+      }
+    },
+    [message, notification, t],
+  );
+
+  React.useEffect(() => {
+    // Prevent duplicate checks on StrictMode double-mount
+    if (hasCheckedRef.current) return;
+    hasCheckedRef.current = true;
+
+    api
+      .checkNewTools()
+      .then((offers) => {
+        for (const offer of offers) {
+          const key = `default-skill-set-${offer.tool}`;
+          notification.info({
+            key,
+            message: t('skills.defaultSkillSet.offerTitle', { tool: offer.tool_display }),
+            description: t('skills.defaultSkillSet.offerBody', {
+              tool: offer.tool_display,
+              count: offer.skill_ids.length,
+            }),
+            btn: (
+              <Space>
+                <Button size="small" onClick={() => notification.destroy(key)}>
+                  {t('skills.defaultSkillSet.dismiss')}
+                </Button>
+                <Button type="primary" size="small" onClick={() => apply(offer)}>
+                  {t('skills.defaultSkillSet.apply')}
+                </Button>
+              </Space>
+            ),
+            duration: 0,
+          });
+        }
+      })
+      .catch((error) => console.error('Failed to check for new tools:', error));
+  }, [apply, notification, t]);
+}
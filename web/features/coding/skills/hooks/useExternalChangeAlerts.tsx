@@ -0,0 +1,53 @@
+import React from 'react';
+import { App, Button } from 'antd';
+import { listen } from '@tauri-apps/api/event';
+import { useNavigate } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { useSkillsStore } from '../stores/skillsStore';
+import type { ExternalChange } from '../types';
+
+/**
+ * The backend watches the central repo and emits `skills-externally-modified`
+ * when a skill no longer matches what the app last wrote (an agent or script
+ * rewrote it). Point the user to the review dialog.
+ */
+export function useExternalChangeAlerts() {
+  const { t } = useTranslation();
+  const { notification } = App.useApp();
+  const navigate = useNavigate();
+
+  React.useEffect(() => {
+    const unlisten = listen<ExternalChange[]>('skills-externally-modified', (event) => {
+      const changes = event.payload;
+      if (changes.length === 0) return;
+
+      const key = 'skills-externally-modified';
+      notification.warning({
+        key,
+        message: t('skills.externalChanges.alertTitle'),
+        description: t('skills.externalChanges.alertBody', {
+          count: changes.length,
+          names: changes.map((change) => change.name).join(', '),
+        }),
+        btn: (
+          <Button
+            type="primary"
+            size="small"
+            onClick={() => {
+              notification.destroy(key);
+              navigate('/skills');
+              useSkillsStore.getState().setExternalChangesModalOpen(true);
+            }}
+          >
+            {t('skills.externalChanges.review')}
+          </Button>
+        ),
+        duration: 0,
+      });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(console.error);
+    };
+  }, [navigate, notification, t]);
+}
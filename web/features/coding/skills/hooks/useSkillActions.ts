@@ -1,12 +1,23 @@
 import React from 'react';
-import { message } from 'antd';
+import { message, Modal } from 'antd';
 import { arrayMove } from '@dnd-kit/sortable';
 import type { DragEndEvent } from '@dnd-kit/core';
 import { useTranslation } from 'react-i18next';
 import * as api from '../services/skillsApi';
 import { useSkills } from './useSkills';
-import type { ManagedSkill, ToolOption } from '../types';
-import { showGitError, confirmTargetOverwrite } from '../utils/errorHandlers';
+import type { ManagedSkill, TargetSyncMode, ToolOption } from '../types';
+import {
+  showGitError,
+  confirmTargetOverwrite,
+  confirmIncompatibleSync,
+  parseIncompatibleToolVersionError,
+  showCopyVerificationWarning,
+  chooseLocalChangesResolution,
+  isLocalModifiedError,
+  parseMergeConflictError,
+  parseChecksumRequiredError,
+  promptArchiveChecksum,
+} from '../utils/errorHandlers';
 import { shouldOverwriteExistingTarget, type BatchToolOptions } from '../utils/batchToolOptions';
 import { refreshTrayMenu } from '@/services/appApi';
 
@@ -23,7 +34,7 @@ export interface UseSkillActionsResult {
   batchDeleteIds: string[];
   setBatchDeleteIds: (ids: string[]) => void;
   handleToggleTool: (skill: ManagedSkill, toolId: string) => Promise<void>;
-  handleUpdate: (skill: ManagedSkill) => Promise<void>;
+  handleUpdate: (skill: ManagedSkill, checksum?: string) => Promise<void>;
   handleDelete: (skillId: string) => void;
   confirmDelete: () => Promise<void>;
   handleDragEnd: (event: DragEndEvent) => Promise<void>;
@@ -47,6 +58,8 @@ export interface UseSkillActionsResult {
     restoreToolsBySkillId?: Record<string, string[]>,
   ) => Promise<boolean>;
   handleSetManagementEnabled: (skill: ManagedSkill, enabled: boolean, restoreTools?: string[]) => Promise<boolean>;
+  handleSetArchived: (skill: ManagedSkill, archived: boolean) => Promise<boolean>;
+  handleConvertTargetMode: (skill: ManagedSkill, toolId: string, mode: TargetSyncMode) => Promise<void>;
 }
 
 export function useSkillActions({ allTools }: UseSkillActionsOptions): UseSkillActionsResult {
@@ -70,50 +83,106 @@ export function useSkillActions({ allTools }: UseSkillActionsOptions): UseSkillA
 
     const target = skill.targets.find((t) => t.tool === toolId);
     const synced = Boolean(target);
+    const toolLabel = allTools.find((t) => t.id === toolId)?.label || toolId;
+
+    // Each confirmation (incompatible tool version, existing target) retries
+    // the sync with that check waived; resolves false when the user cancels
+    const syncWithPrompts = async (overwrite?: boolean, ignoreCompatibility?: boolean): Promise<boolean> => {
+      try {
+        const result = await api.syncSkillToTool(
+          skill.central_path, skill.id, toolId, skill.name, overwrite, ignoreCompatibility,
+        );
+        showCopyVerificationWarning(result, skill.name, toolLabel, t);
+        return true;
+      } catch (error) {
+        const errMsg = String(error);
+        const incompatible = parseIncompatibleToolVersionError(errMsg);
+        if (incompatible) {
+          const proceed = await confirmIncompatibleSync(
+            skill.name, toolLabel, incompatible.constraint, incompatible.version, t,
+          );
+          return proceed && syncWithPrompts(overwrite, true);
+        }
+        if (!overwrite && errMsg.includes('TARGET_EXISTS|')) {
+          const match = errMsg.match(/TARGET_EXISTS\|(.+)/);
+          const targetPath = match ? match[1] : '';
+          const conflict = await api.previewTargetConflict(skill.id, toolId).catch(() => null);
+          const shouldOverwrite = await confirmTargetOverwrite(skill.name, toolLabel, targetPath, t, conflict);
+          return shouldOverwrite && syncWithPrompts(true, ignoreCompatibility);
+        }
+        throw error;
+      }
+    };
 
     setActionLoading(true);
     try {
       if (synced) {
         await api.unsyncSkillFromTool(skill.id, toolId);
-      } else {
-        await api.syncSkillToTool(skill.central_path, skill.id, toolId, skill.name);
+      } else if (!(await syncWithPrompts())) {
+        return;
       }
       await refresh();
       await refreshTrayMenu();
     } catch (error) {
-      const errMsg = String(error);
-      if (errMsg.includes('TARGET_EXISTS|')) {
-        const match = errMsg.match(/TARGET_EXISTS\|(.+)/);
-        const targetPath = match ? match[1] : '';
-        const toolLabel = allTools.find((t) => t.id === toolId)?.label || toolId;
-        const shouldOverwrite = await confirmTargetOverwrite(skill.name, toolLabel, targetPath, t);
-        if (shouldOverwrite) {
-          try {
-            await api.syncSkillToTool(skill.central_path, skill.id, toolId, skill.name, true);
-            await refresh();
-            await refreshTrayMenu();
-          } catch (retryError) {
-            message.error(String(retryError));
-          }
-        }
-      } else {
-        showGitError(errMsg, t, allTools);
-      }
+      showGitError(String(error), t, allTools);
     } finally {
       setActionLoading(false);
     }
   }, [allTools, t, refresh]);
 
-  const handleUpdate = React.useCallback(async (skill: ManagedSkill) => {
+  const handleUpdate = React.useCallback(async (skill: ManagedSkill, checksum?: string) => {
     if (updatingSkillIds.includes(skill.id)) {
       return;
     }
 
     setUpdatingSkillIds((prev) => [...prev, skill.id]);
     try {
-      await updateSkill(skill);
+      await updateSkill(skill, undefined, checksum);
     } catch (error) {
-      showGitError(String(error), t, allTools);
+      const errMsg = String(error);
+      // The archive host publishes no checksum: ask the user for it
+      const checksumUrl = skill.source_type === 'archive' ? parseChecksumRequiredError(errMsg) : null;
+      if (checksumUrl) {
+        const checksum = await promptArchiveChecksum(skill.name, checksumUrl, t);
+        if (!checksum) {
+          return;
+        }
+        try {
+          await updateSkill(skill, undefined, checksum);
+          message.success(t('skills.checksum.updated'));
+        } catch (retryError) {
+          showGitError(String(retryError), t, allTools);
+        }
+        return;
+      }
+      if (!isLocalModifiedError(errMsg)) {
+        showGitError(errMsg, t, allTools);
+        return;
+      }
+      // Git skill edited locally: the update needs an explicit choice
+      const resolution = await chooseLocalChangesResolution(skill.name, t);
+      if (!resolution) {
+        return;
+      }
+      try {
+        await updateSkill(skill, resolution);
+        message.success(t(resolution === 'keep_local'
+          ? 'skills.localChanges.forked'
+          : 'skills.localChanges.updated'));
+      } catch (retryError) {
+        const retryMsg = String(retryError);
+        const conflicts = parseMergeConflictError(retryMsg);
+        if (conflicts) {
+          Modal.warning({
+            title: t('skills.localChanges.conflictTitle'),
+            content: t('skills.localChanges.conflictMessage', { files: conflicts.join(', ') }),
+          });
+        } else if (retryMsg.includes('MERGE_BASE_UNAVAILABLE|')) {
+          message.error(t('skills.localChanges.baseUnavailable'));
+        } else {
+          showGitError(retryMsg, t, allTools);
+        }
+      }
     } finally {
       setUpdatingSkillIds((prev) => prev.filter((id) => id !== skill.id));
     }
@@ -171,11 +240,34 @@ export function useSkillActions({ allTools }: UseSkillActionsOptions): UseSkillA
   const handleBatchRefresh = React.useCallback(async (skillIds: string[]) => {
     setActionLoading(true);
     try {
+      let skippedLocal = 0;
+      let skippedChecksum = 0;
       for (const id of skillIds) {
-        await api.updateManagedSkill(id);
+        try {
+          await api.updateManagedSkill(id);
+        } catch (error) {
+          // Locally edited git skills and archives without a known checksum
+          // are only updated one by one, with a choice
+          const errMsg = String(error);
+          if (isLocalModifiedError(errMsg)) {
+            skippedLocal += 1;
+          } else if (parseChecksumRequiredError(errMsg)) {
+            skippedChecksum += 1;
+          } else {
+            throw error;
+          }
+        }
       }
       await refresh();
-      message.success(t('skills.batch.refreshSuccess', { count: skillIds.length }));
+      message.success(t('skills.batch.refreshSuccess', {
+        count: skillIds.length - skippedLocal - skippedChecksum,
+      }));
+      if (skippedLocal > 0) {
+        message.warning(t('skills.localChanges.batchSkipped', { count: skippedLocal }));
+      }
+      if (skippedChecksum > 0) {
+        message.warning(t('skills.checksum.batchSkipped', { count: skippedChecksum }));
+      }
     } catch (error) {
       showGitError(String(error), t, allTools);
     } finally {
@@ -193,8 +285,9 @@ export function useSkillActions({ allTools }: UseSkillActionsOptions): UseSkillA
     if (batchDeleteIds.length === 0) return;
     setActionLoading(true);
     try {
+      const ticket = await api.requestConfirmation('delete_central_content', batchDeleteIds);
       for (const id of batchDeleteIds) {
-        await api.deleteManagedSkill(id);
+        await api.deleteManagedSkill(id, undefined, ticket.token);
       }
       await refresh();
       await refreshTrayMenu();
@@ -375,6 +468,57 @@ export function useSkillActions({ allTools }: UseSkillActionsOptions): UseSkillA
     }
   }, [allTools, refresh, t]);
 
+  const handleSetArchived = React.useCallback(async (skill: ManagedSkill, archived: boolean) => {
+    setActionLoading(true);
+    try {
+      if (archived) {
+        await api.archiveSkill(skill.id);
+      } else {
+        // Restored skills go back to the tools they were synced to before archiving
+        const restoreTools = await api.unarchiveSkill(skill.id);
+        for (const toolId of restoreTools.filter((id) => allTools.some((tool) => tool.id === id))) {
+          await api.syncSkillToTool(skill.central_path, skill.id, toolId, skill.name, true);
+        }
+      }
+      await refresh();
+      await refreshTrayMenu();
+      message.success(archived ? t('skills.archive.archived') : t('skills.archive.restored'));
+      return true;
+    } catch (error) {
+      showGitError(String(error), t, allTools);
+      return false;
+    } finally {
+      setActionLoading(false);
+    }
+  }, [allTools, refresh, t]);
+
+  const handleConvertTargetMode = React.useCallback(async (
+    skill: ManagedSkill,
+    toolId: string,
+    mode: TargetSyncMode,
+  ) => {
+    setActionLoading(true);
+    try {
+      await api.convertTargetMode(skill.id, toolId, mode);
+      await refresh();
+      message.success(t('skills.convertMode.converted', {
+        tool: allTools.find((tool) => tool.id === toolId)?.label ?? toolId,
+        mode: t(`skills.convertMode.${mode}`),
+      }));
+    } catch (error) {
+      const errMsg = String(error);
+      if (errMsg.includes('TARGET_MODIFIED|')) {
+        message.error(t('skills.convertMode.targetModified'));
+      } else if (errMsg.includes('LINK_UNSUPPORTED|')) {
+        message.error(t('skills.convertMode.linkUnsupported'));
+      } else {
+        showGitError(errMsg, t, allTools);
+      }
+    } finally {
+      setActionLoading(false);
+    }
+  }, [allTools, refresh, t]);
+
   return {
     actionLoading,
     updatingSkillIds,
@@ -396,5 +540,7 @@ export function useSkillActions({ allTools }: UseSkillActionsOptions): UseSkillA
     handleBatchSetGroup,
     handleBatchSetManagementEnabled,
     handleSetManagementEnabled,
+    handleSetArchived,
+    handleConvertTargetMode,
   };
 }
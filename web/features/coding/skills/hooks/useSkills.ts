@@ -3,7 +3,8 @@ import { listen } from '@tauri-apps/api/event';
 import { useTranslation } from 'react-i18next';
 import { useSkillsStore } from '../stores/skillsStore';
 import * as api from '../services/skillsApi';
-import type { ManagedSkill } from '../types';
+import type { LocalChangesResolution, ManagedSkill } from '../types';
+import { isFolderSource } from '../utils/skillPath';
 
 export function useSkills() {
   const store = useSkillsStore();
@@ -16,10 +17,10 @@ export function useSkills() {
     }
   }, [store.isModalOpen]);
 
-  // Listen for skills-changed events from tray
+  // Listen for skills-changed events from tray, background auto-import and auto re-sync
   React.useEffect(() => {
     const unlisten = listen<string>('skills-changed', (event) => {
-      if (event.payload === 'tray') {
+      if (['tray', 'auto_adopt', 'auto_resync'].includes(event.payload)) {
         store.loadSkills();
       }
     });
@@ -66,7 +67,7 @@ export function useSkills() {
       const info = getGithubInfo(skill.source_ref);
       return info ? info.label : skill.source_ref || 'Git';
     }
-    if (skill.source_type === 'local') {
+    if (isFolderSource(skill.source_type)) {
       // Return just the folder name
       const path = skill.source_ref || '';
       const parts = path.split(/[\/\\]/);
@@ -80,9 +81,9 @@ export function useSkills() {
 
   // Update skill
   const updateSkill = React.useCallback(
-    async (skill: ManagedSkill) => {
+    async (skill: ManagedSkill, resolution?: LocalChangesResolution, checksum?: string) => {
       try {
-        await api.updateManagedSkill(skill.id);
+        await api.updateManagedSkill(skill.id, resolution, checksum);
         await store.loadSkills();
       } catch (error) {
         console.error('Failed to update skill:', error);
@@ -96,7 +97,8 @@ export function useSkills() {
   const deleteSkill = React.useCallback(
     async (skillId: string) => {
       try {
-        await api.deleteManagedSkill(skillId);
+        const ticket = await api.requestConfirmation('delete_central_content', [skillId]);
+        await api.deleteManagedSkill(skillId, undefined, ticket.token);
         await store.loadSkills();
       } catch (error) {
         console.error('Failed to delete skill:', error);
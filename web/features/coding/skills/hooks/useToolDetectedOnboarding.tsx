@@ -0,0 +1,53 @@
+import React from 'react';
+import { App, Button } from 'antd';
+import { listen } from '@tauri-apps/api/event';
+import { useNavigate } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { useSkillsStore } from '../stores/skillsStore';
+import type { ToolDetectedEvent } from '../types';
+
+/**
+ * When a tool is installed while the app runs, the backend scans just that
+ * tool and emits `skills-tool-detected`. Offer to import what it found.
+ */
+export function useToolDetectedOnboarding() {
+  const { t } = useTranslation();
+  const { notification } = App.useApp();
+  const navigate = useNavigate();
+
+  React.useEffect(() => {
+    const unlisten = listen<ToolDetectedEvent>('skills-tool-detected', (event) => {
+      const { tool, tool_display, plan } = event.payload;
+      void useSkillsStore.getState().loadToolStatus();
+      if (plan.total_skills_found === 0) return;
+
+      const key = `tool-detected-${tool}`;
+      notification.info({
+        key,
+        message: t('skills.toolDetected.title', { tool: tool_display }),
+        description: t('skills.toolDetected.body', {
+          tool: tool_display,
+          count: plan.total_skills_found,
+        }),
+        btn: (
+          <Button
+            type="primary"
+            size="small"
+            onClick={() => {
+              notification.destroy(key);
+              navigate('/skills');
+              useSkillsStore.getState().setImportModalOpen(true);
+            }}
+          >
+            {t('skills.toolDetected.review')}
+          </Button>
+        ),
+        duration: 0,
+      });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(console.error);
+    };
+  }, [navigate, notification, t]);
+}
@@ -1,4 +1,5 @@
 import React, { useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
 import { useSkillsStore } from '../stores/skillsStore';
 
 export function useToolStatus() {
@@ -13,6 +14,18 @@ export function useToolStatus() {
     loadToolStatus();
   }, [loadToolStatus]);
 
+  // Detection is cached in the backend; reload only when the background
+  // re-check finds tools installed or removed
+  React.useEffect(() => {
+    const unlisten = listen('tools-detection-changed', () => {
+      loadToolStatus();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadToolStatus]);
+
   return {
     toolStatus,
     installedTools: toolStatus?.installed || [],
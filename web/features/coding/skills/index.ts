@@ -23,6 +23,9 @@ export { SkillsSettingsModal } from './components/modals/SkillsSettingsModal';
 export { useSkills } from './hooks/useSkills';
 export { useSkillActions } from './hooks/useSkillActions';
 export { useToolStatus } from './hooks/useToolStatus';
+export { useDefaultSkillSetOffers } from './hooks/useDefaultSkillSetOffers';
+export { useToolDetectedOnboarding } from './hooks/useToolDetectedOnboarding';
+export { useExternalChangeAlerts } from './hooks/useExternalChangeAlerts';
 
 // Utils
 export {
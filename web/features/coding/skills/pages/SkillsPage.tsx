@@ -12,6 +12,8 @@ import {
   GripVertical,
   Import,
   FileJson,
+  FileWarning,
+  Layers,
   LayoutGrid,
   ListTree,
   MinusCircle,
@@ -48,8 +50,13 @@ import { SkillsSettingsModal } from '../components/modals/SkillsSettingsModal';
 import { DeleteConfirmModal } from '../components/modals/DeleteConfirmModal';
 import { NewToolsModal } from '../components/modals/NewToolsModal';
 import { SkillMetadataModal } from '../components/modals/SkillMetadataModal';
+import { SkillUpdatePreviewModal } from '../components/modals/SkillUpdatePreviewModal';
+import { SkillPathRulesModal } from '../components/modals/SkillPathRulesModal';
+import { PullFromTargetModal } from '../components/modals/PullFromTargetModal';
 import { SkillGroupsModal } from '../components/modals/SkillGroupsModal';
+import { SkillCollectionsModal } from '../components/modals/SkillCollectionsModal';
 import { SkillInventoryModal } from '../components/modals/SkillInventoryModal';
+import { ExternalChangesModal } from '../components/modals/ExternalChangesModal';
 import * as api from '../services/skillsApi';
 import {
   buildSkillGroups,
@@ -214,6 +221,8 @@ const SkillsPage: React.FC = () => {
     isSettingsModalOpen,
     setSettingsModalOpen,
     isNewToolsModalOpen,
+    isExternalChangesModalOpen,
+    setExternalChangesModalOpen,
     groups,
     loading,
   } = useSkillsStore();
@@ -234,9 +243,13 @@ const SkillsPage: React.FC = () => {
   const [selectionMode, setSelectionMode] = React.useState(false);
   const [reorderMode, setReorderMode] = React.useState(false);
   const [metadataSkill, setMetadataSkill] = React.useState<ManagedSkill | null>(null);
+  const [updatePreviewSkill, setUpdatePreviewSkill] = React.useState<ManagedSkill | null>(null);
+  const [pathRulesTarget, setPathRulesTarget] = React.useState<{ skill: ManagedSkill; toolId: string } | null>(null);
+  const [pullTarget, setPullTarget] = React.useState<{ skill: ManagedSkill; toolId: string } | null>(null);
   const [batchGroupModalOpen, setBatchGroupModalOpen] = React.useState(false);
   const [batchGroupValue, setBatchGroupValue] = React.useState('');
   const [groupsModalOpen, setGroupsModalOpen] = React.useState(false);
+  const [collectionsModalOpen, setCollectionsModalOpen] = React.useState(false);
   const [inventoryModalOpen, setInventoryModalOpen] = React.useState(false);
   const [enabledFilter, setEnabledFilter] = React.useState<SkillEnabledFilter>('all');
   const [groupToolMode, setGroupToolMode] = React.useState(false);
@@ -296,6 +309,8 @@ const SkillsPage: React.FC = () => {
     handleBatchSetGroup,
     handleBatchSetManagementEnabled,
     handleSetManagementEnabled,
+    handleSetArchived,
+    handleConvertTargetMode,
   } = useSkillActions({ allTools });
 
   // Filter skills by search text
@@ -565,6 +580,20 @@ const SkillsPage: React.FC = () => {
     });
   }, [allTools, handleSetManagementEnabled, t]);
 
+  const handleSetSkillArchived = React.useCallback((skill: ManagedSkill, archived: boolean) => {
+    if (!archived) {
+      void handleSetArchived(skill, false);
+      return;
+    }
+    Modal.confirm({
+      title: t('skills.archive.confirmTitle'),
+      content: t('skills.archive.confirmContent', { name: skill.name }),
+      okText: t('skills.archive.archive'),
+      cancelText: t('common.cancel'),
+      onOk: () => handleSetArchived(skill, true),
+    });
+  }, [handleSetArchived, t]);
+
   const groupedSkills = React.useMemo<SkillGroup[]>(() => {
     if (viewMode !== 'grouped') return [];
 
@@ -943,6 +972,15 @@ const SkillsPage: React.FC = () => {
                         setGroupsModalOpen(true);
                       }}
                     />
+                    <ToolbarActionItem
+                      icon={<Layers size={14} aria-hidden="true" />}
+                      title={t('skills.toolbar.collections')}
+                      description={t('skills.toolbar.collectionsDescription')}
+                      onClick={() => {
+                        close();
+                        setCollectionsModalOpen(true);
+                      }}
+                    />
                     <ToolbarActionItem
                       icon={<FileJson size={14} aria-hidden="true" />}
                       title={t('skills.toolbar.inventory')}
@@ -952,6 +990,15 @@ const SkillsPage: React.FC = () => {
                         setInventoryModalOpen(true);
                       }}
                     />
+                    <ToolbarActionItem
+                      icon={<FileWarning size={14} aria-hidden="true" />}
+                      title={t('skills.toolbar.externalChanges')}
+                      description={t('skills.toolbar.externalChangesDescription')}
+                      onClick={() => {
+                        close();
+                        setExternalChangesModalOpen(true);
+                      }}
+                    />
                   </div>
                 </section>
               </>
@@ -1056,6 +1103,11 @@ const SkillsPage: React.FC = () => {
             onToggleTool={handleToggleTool}
             onEditMetadata={setMetadataSkill}
             onSetManagementEnabled={handleSetSkillEnabled}
+            onSetArchived={handleSetSkillArchived}
+            onPreviewUpdate={setUpdatePreviewSkill}
+            onConvertTargetMode={handleConvertTargetMode}
+            onEditPathRules={(skill, toolId) => setPathRulesTarget({ skill, toolId })}
+            onPullFromTarget={(skill, toolId) => setPullTarget({ skill, toolId })}
             onDragEnd={handleDragEnd}
           />
         ) : (
@@ -1078,6 +1130,11 @@ const SkillsPage: React.FC = () => {
             onToggleTool={handleToggleTool}
             onEditMetadata={setMetadataSkill}
             onSetManagementEnabled={handleSetSkillEnabled}
+            onSetArchived={handleSetSkillArchived}
+            onPreviewUpdate={setUpdatePreviewSkill}
+            onConvertTargetMode={handleConvertTargetMode}
+            onEditPathRules={(skill, toolId) => setPathRulesTarget({ skill, toolId })}
+            onPullFromTarget={(skill, toolId) => setPullTarget({ skill, toolId })}
             groupToolMode={groupToolMode}
             onAddGroupTool={handleAddGroupTool}
             onRemoveGroupTool={handleRemoveGroupTool}
@@ -1166,6 +1223,37 @@ const SkillsPage: React.FC = () => {
         </div>
       </Modal>
 
+      <SkillUpdatePreviewModal
+        open={!!updatePreviewSkill}
+        skill={updatePreviewSkill}
+        onClose={() => setUpdatePreviewSkill(null)}
+        onUpdate={(skill, checksum) => void handleUpdate(skill, checksum)}
+      />
+
+      <SkillPathRulesModal
+        open={!!pathRulesTarget}
+        skill={pathRulesTarget?.skill ?? null}
+        toolId={pathRulesTarget?.toolId ?? null}
+        toolLabel={allTools.find((tool) => tool.id === pathRulesTarget?.toolId)?.label ?? pathRulesTarget?.toolId ?? ''}
+        onClose={() => setPathRulesTarget(null)}
+        onSuccess={() => {
+          setPathRulesTarget(null);
+          refresh();
+        }}
+      />
+
+      <PullFromTargetModal
+        open={!!pullTarget}
+        skill={pullTarget?.skill ?? null}
+        toolId={pullTarget?.toolId ?? null}
+        toolLabel={allTools.find((tool) => tool.id === pullTarget?.toolId)?.label ?? pullTarget?.toolId ?? ''}
+        onClose={() => setPullTarget(null)}
+        onSuccess={() => {
+          setPullTarget(null);
+          refresh();
+        }}
+      />
+
       <SkillMetadataModal
         open={!!metadataSkill}
         skill={metadataSkill}
@@ -1184,12 +1272,26 @@ const SkillsPage: React.FC = () => {
         onSuccess={refresh}
       />
 
+      <SkillCollectionsModal
+        open={collectionsModalOpen}
+        skills={skills}
+        tools={installedTools}
+        onClose={() => setCollectionsModalOpen(false)}
+        onSuccess={refresh}
+      />
+
       <SkillInventoryModal
         open={inventoryModalOpen}
         onClose={() => setInventoryModalOpen(false)}
         onSuccess={refresh}
       />
 
+      <ExternalChangesModal
+        open={isExternalChangesModalOpen}
+        onClose={() => setExternalChangesModalOpen(false)}
+        onSuccess={refresh}
+      />
+
       <NewToolsModal
         open={isNewToolsModalOpen}
       />
@@ -2,14 +2,27 @@ import { invoke } from '@tauri-apps/api/core';
 import type {
   ManagedSkill,
   ToolStatus,
+  ToolLoadPreview,
+  LinkRepairResult,
+  SkillSyncRun,
+  TargetConflictReport,
   InstallResult,
   SyncResult,
+  SyncPreview,
+  TargetSyncMode,
   UpdateResult,
+  SkillUpdatePreview,
+  LocalChangesResolution,
   GitSkillCandidate,
+  GitCollectionCandidate,
   OnboardingPlan,
   SkillRepo,
   CustomTool,
+  PreferredSyncMode,
   SkillGroupRecord,
+  SkillCollection,
+  SkillCollectionToolResult,
+  SkillCollectionImportResult,
   SkillInventoryPreview,
   SkillViewMode,
   CentralRepoPathStatus,
@@ -19,6 +32,49 @@ import type {
   CentralRepoScan,
   AdoptCentralSkillsResult,
   DeleteManagedSkillOptions,
+  DangerousOp,
+  ConfirmationTicket,
+  SkillSourceMirror,
+  StaleArtifactReport,
+  SweepResult,
+  MarkerRecoveryResult,
+  DatabaseRebuildResult,
+  LibraryAudit,
+  LibraryAuditIssue,
+  LibraryAuditFixResult,
+  StartupCheckSummary,
+  SkillCompatibility,
+  SkillLocalization,
+  FileSkillTool,
+  SkillQuery,
+  ConsolidationCandidate,
+  ConsolidationResult,
+  DuplicateContentReport,
+  ConflictOverview,
+  SyncJournalEntry,
+  SyncTrashPurgeSummary,
+  ExternalChange,
+  TargetPullPreview,
+  TargetPullResult,
+  DefaultSkillSets,
+  NameCollisionPolicies,
+  DefaultSetOffer,
+  DefaultSetApplyResult,
+  StorageReport,
+  VariantPolicy,
+  AdoptOnboardingResult,
+  SkillTagCount,
+  SkillTagSuggestions,
+  SkillQualityFlag,
+  SkillQualityReport,
+  LinkedFolderStatus,
+  ExtraRootScan,
+  AdoptionSelection,
+  AdoptionSimulation,
+  TeamMirrorTarget,
+  MirrorPublishResult,
+  MirrorListing,
+  MirrorUpdate,
 } from '../types';
 
 // Tool Status
@@ -82,6 +138,11 @@ export const getManagedSkills = async (): Promise<ManagedSkill[]> => {
   return invoke<ManagedSkill[]>('skills_get_managed_skills');
 };
 
+/** Filter managed skills server-side; `filter` uses the `tool:codex drift:drifted` syntax */
+export const querySkills = async (query?: SkillQuery, filter?: string): Promise<ManagedSkill[]> => {
+  return invoke<ManagedSkill[]>('skills_query', { query: query ?? null, filter: filter ?? null });
+};
+
 // Install Skills
 export const installLocalSkill = async (
   sourcePath: string,
@@ -110,10 +171,171 @@ export const installGitSkill = async (
   return invoke<InstallResult>('skills_install_git', { repoUrl, branch, overwrite });
 };
 
+export const installArchiveUrl = async (
+  url: string,
+  checksum?: string,
+  overwrite?: boolean
+): Promise<InstallResult> => {
+  return invoke<InstallResult>('skills_install_archive_url', { url, checksum, overwrite });
+};
+
+export const cleanDanglingLinks = async (tool: string): Promise<string[]> => {
+  return invoke<string[]>('skills_clean_dangling_links', { tool });
+};
+
+export const previewToolLoad = async (tool: string): Promise<ToolLoadPreview> => {
+  return invoke<ToolLoadPreview>('skills_preview_tool_load', { tool });
+};
+
+export const repairLinks = async (): Promise<LinkRepairResult> => {
+  return invoke<LinkRepairResult>('skills_repair_links');
+};
+
+export const getAutoSyncInterval = async (): Promise<number> => {
+  return invoke<number>('skills_get_auto_sync_interval');
+};
+
+export const setAutoSyncInterval = async (minutes: number): Promise<void> => {
+  await invoke('skills_set_auto_sync_interval', { minutes });
+};
+
+export const runSkillSyncNow = async (): Promise<SkillSyncRun> => {
+  return invoke<SkillSyncRun>('skills_run_skill_sync_now');
+};
+
+export const listSyncRuns = async (limit?: number): Promise<SkillSyncRun[]> => {
+  return invoke<SkillSyncRun[]>('skills_list_sync_runs', { limit });
+};
+
+export const listFileSkillTools = async (): Promise<FileSkillTool[]> => {
+  return invoke<FileSkillTool[]>('skills_list_file_skill_tools');
+};
+
+export const writeFileSkill = async (skillId: string, tool: string): Promise<void> => {
+  await invoke('skills_write_file_skill', { skillId, tool });
+};
+
+export const removeFileSkill = async (skillId: string, tool: string): Promise<boolean> => {
+  return invoke<boolean>('skills_remove_file_skill', { skillId, tool });
+};
+
+export const getDisallowedLicenses = async (): Promise<string[]> => {
+  return invoke<string[]>('skills_get_disallowed_licenses');
+};
+
+export const saveDisallowedLicenses = async (licenses: string[]): Promise<void> => {
+  await invoke('skills_save_disallowed_licenses', { licenses });
+};
+
+export const getCopyIgnorePatterns = async (): Promise<string[]> => {
+  return invoke<string[]>('skills_get_copy_ignore_patterns');
+};
+
+export const saveCopyIgnorePatterns = async (patterns: string[]): Promise<string[]> => {
+  return invoke<string[]>('skills_save_copy_ignore_patterns', { patterns });
+};
+
+export const getRelativeSymlinks = async (): Promise<boolean> => {
+  return invoke<boolean>('skills_get_relative_symlinks');
+};
+
+export const setRelativeSymlinks = async (enabled: boolean): Promise<void> => {
+  return invoke('skills_set_relative_symlinks', { enabled });
+};
+
+export const scanExternalChanges = async (): Promise<ExternalChange[]> => {
+  return invoke<ExternalChange[]>('skills_scan_external_changes');
+};
+
+export const acceptExternalChange = async (skillId: string): Promise<void> => {
+  return invoke('skills_accept_external_change', { skillId });
+};
+
+export const revertExternalChange = async (skillId: string): Promise<void> => {
+  return invoke('skills_revert_external_change', { skillId });
+};
+
+export const previewPullFromTarget = async (
+  skillId: string,
+  tool: string,
+): Promise<TargetPullPreview> => {
+  return invoke<TargetPullPreview>('skills_preview_pull_from_target', { skillId, tool });
+};
+
+export const pullFromTarget = async (
+  skillId: string,
+  tool: string,
+  paths?: string[],
+): Promise<TargetPullResult> => {
+  return invoke<TargetPullResult>('skills_pull_from_target', { skillId, tool, paths: paths ?? null });
+};
+
+export const listSyncJournal = async (target?: string): Promise<SyncJournalEntry[]> => {
+  return invoke<SyncJournalEntry[]>('skills_list_sync_journal', { target: target ?? null });
+};
+
+export const rollbackLastSync = async (target: string): Promise<SyncJournalEntry> => {
+  return invoke<SyncJournalEntry>('skills_rollback_last_sync', { target });
+};
+
+/** Needs a `purge_trash` token from `requestConfirmation` */
+export const purgeSyncTrash = async (
+  confirmationToken?: string,
+): Promise<SyncTrashPurgeSummary> => {
+  return invoke<SyncTrashPurgeSummary>('skills_purge_sync_trash', { confirmationToken });
+};
+
+export const scanStaleToolArtifacts = async (): Promise<StaleArtifactReport> => {
+  return invoke<StaleArtifactReport>('skills_scan_stale_tool_artifacts');
+};
+
+export const sweepStaleToolArtifacts = async (paths: string[]): Promise<SweepResult> => {
+  return invoke<SweepResult>('skills_sweep_stale_tool_artifacts', { paths });
+};
+
+/** Restore copy-target records from the marker files in tool skills dirs */
+export const recoverTargetsFromMarkers = async (): Promise<MarkerRecoveryResult> => {
+  return invoke<MarkerRecoveryResult>('skills_recover_targets_from_markers');
+};
+
+/** Recreate skill and target records from the central repo, tool links and markers */
+export const rebuildDatabase = async (): Promise<DatabaseRebuildResult> => {
+  return invoke<DatabaseRebuildResult>('skills_rebuild_database');
+};
+
+export const auditLibrary = async (): Promise<LibraryAudit> => {
+  return invoke<LibraryAudit>('skills_audit_library');
+};
+
+export const fixLibraryAuditIssues = async (
+  issues: LibraryAuditIssue[],
+): Promise<LibraryAuditFixResult> => {
+  return invoke<LibraryAuditFixResult>('skills_fix_library_audit_issues', { issues });
+};
+
+export const getStartupCheckSummary = async (): Promise<StartupCheckSummary | null> => {
+  return invoke<StartupCheckSummary | null>('skills_get_startup_check_summary');
+};
+
+export const getSourceMirror = async (): Promise<SkillSourceMirror> => {
+  return invoke<SkillSourceMirror>('skills_get_source_mirror');
+};
+
+export const saveSourceMirror = async (config: SkillSourceMirror): Promise<void> => {
+  await invoke('skills_save_source_mirror', { config });
+};
+
 export const listGitSkills = async (repoUrl: string, branch?: string): Promise<GitSkillCandidate[]> => {
   return invoke<GitSkillCandidate[]>('skills_list_git_skills', { repoUrl, branch });
 };
 
+export const listGitCollections = async (
+  repoUrl: string,
+  branch?: string,
+): Promise<GitCollectionCandidate[]> => {
+  return invoke<GitCollectionCandidate[]>('skills_list_git_collections', { repoUrl, branch });
+};
+
 export const installGitSelection = async (
   repoUrl: string,
   subpath: string,
@@ -129,7 +351,8 @@ export const syncSkillToTool = async (
   skillId: string,
   tool: string,
   name: string,
-  overwrite?: boolean
+  overwrite?: boolean,
+  ignoreCompatibility?: boolean
 ): Promise<SyncResult> => {
   return invoke<SyncResult>('skills_sync_to_tool', {
     sourcePath,
@@ -137,9 +360,43 @@ export const syncSkillToTool = async (
     tool,
     name,
     overwrite,
+    ignoreCompatibility,
   });
 };
 
+export const checkSkillCompatibility = async (skillId: string): Promise<SkillCompatibility[]> => {
+  return invoke<SkillCompatibility[]>('skills_check_compatibility', { skillId });
+};
+
+export const setSkillCompatibility = async (
+  skillId: string,
+  compatibility: Record<string, string>,
+): Promise<void> => {
+  return invoke('skills_set_compatibility', { skillId, compatibility });
+};
+
+export const setSkillLocalizations = async (
+  skillId: string,
+  localizations: Record<string, SkillLocalization>,
+): Promise<void> => {
+  return invoke('skills_set_localizations', { skillId, localizations });
+};
+
+export const getDictionaryLocalizations = async (
+  skillName: string,
+): Promise<Record<string, SkillLocalization>> => {
+  return invoke<Record<string, SkillLocalization>>('skills_get_dictionary_localizations', { skillName });
+};
+
+/** Fill empty localizations of known skills; an empty list means every skill */
+export const fillSkillLocalizations = async (skillIds: string[] = []): Promise<number> => {
+  return invoke<number>('skills_fill_localizations', { skillIds });
+};
+
+export const setSkillWriteProtected = async (skillId: string, enabled: boolean): Promise<void> => {
+  return invoke('skills_set_write_protected', { skillId, enabled });
+};
+
 export const unsyncSkillFromTool = async (
   skillId: string,
   tool: string
@@ -147,16 +404,73 @@ export const unsyncSkillFromTool = async (
   return invoke('skills_unsync_from_tool', { skillId, tool });
 };
 
+export const previewSkillSync = async (
+  skillId: string,
+  tool: string,
+  overwrite?: boolean
+): Promise<SyncPreview> => {
+  return invoke<SyncPreview>('skills_preview_sync', { skillId, tool, overwrite });
+};
+
+export const previewTargetConflict = async (
+  skillId: string,
+  tool: string,
+): Promise<TargetConflictReport | null> => {
+  return invoke<TargetConflictReport | null>('skills_preview_target_conflict', { skillId, tool });
+};
+
+export const convertTargetMode = async (
+  skillId: string,
+  tool: string,
+  mode: TargetSyncMode,
+  relativeLinks?: boolean
+): Promise<SyncResult> => {
+  return invoke<SyncResult>('skills_convert_target_mode', { skillId, tool, mode, relativeLinks });
+};
+
+export const setTargetPathRules = async (
+  skillId: string,
+  tool: string,
+  include: string[],
+  exclude: string[]
+): Promise<SyncResult> => {
+  return invoke<SyncResult>('skills_set_target_path_rules', { skillId, tool, include, exclude });
+};
+
 // Update/Delete Skills
-export const updateManagedSkill = async (skillId: string): Promise<UpdateResult> => {
-  return invoke<UpdateResult>('skills_update_managed', { skillId });
+export const updateManagedSkill = async (
+  skillId: string,
+  resolution?: LocalChangesResolution,
+  checksum?: string,
+): Promise<UpdateResult> => {
+  return invoke<UpdateResult>('skills_update_managed', { skillId, resolution, checksum });
+};
+
+export const getLocallyModifiedSkills = async (): Promise<string[]> => {
+  return invoke<string[]>('skills_get_locally_modified');
+};
+
+export const previewSkillUpdate = async (
+  skillId: string,
+  checksum?: string,
+): Promise<SkillUpdatePreview> => {
+  return invoke<SkillUpdatePreview>('skills_preview_update', { skillId, checksum });
 };
 
 export const deleteManagedSkill = async (
   skillId: string,
   options?: DeleteManagedSkillOptions,
+  confirmationToken?: string,
 ): Promise<void> => {
-  return invoke('skills_delete_managed', { skillId, options });
+  return invoke('skills_delete_managed', { skillId, options, confirmationToken });
+};
+
+// Dangerous operations need a token issued right before the user-confirmed action
+export const requestConfirmation = async (
+  op: DangerousOp,
+  skillIds?: string[],
+): Promise<ConfirmationTicket> => {
+  return invoke<ConfirmationTicket>('skills_request_confirmation', { op, skillIds });
 };
 
 export const updateSkillMetadata = async (
@@ -174,6 +488,32 @@ export const batchUpdateSkillGroup = async (
   return invoke('skills_batch_update_group', { skillIds, groupId });
 };
 
+export const batchUpdateSkillTags = async (
+  skillIds: string[],
+  add: string[],
+  remove: string[],
+): Promise<number> => {
+  return invoke<number>('skills_batch_update_tags', { skillIds, add, remove });
+};
+
+export const getAllSkillTags = async (): Promise<SkillTagCount[]> => {
+  return invoke<SkillTagCount[]>('skills_get_all_tags');
+};
+
+export const suggestSkillTags = async (skillIds: string[]): Promise<SkillTagSuggestions[]> => {
+  return invoke<SkillTagSuggestions[]>('skills_suggest_tags', { skillIds });
+};
+
+export const analyzeSkill = async (skillId: string): Promise<SkillQualityReport> => {
+  return invoke<SkillQualityReport>('skills_analyze_skill', { skillId });
+};
+
+export const rankSkillQuality = async (
+  flag?: SkillQualityFlag | null,
+): Promise<SkillQualityReport[]> => {
+  return invoke<SkillQualityReport[]>('skills_rank_quality', { flag: flag ?? null });
+};
+
 export const getSkillGroups = async (): Promise<SkillGroupRecord[]> => {
   return invoke<SkillGroupRecord[]>('skills_get_groups');
 };
@@ -191,6 +531,102 @@ export const deleteSkillGroup = async (groupId: string): Promise<void> => {
   return invoke('skills_delete_group', { groupId });
 };
 
+// Collections
+export const getSkillCollections = async (): Promise<SkillCollection[]> => {
+  return invoke<SkillCollection[]>('skills_get_collections');
+};
+
+export const saveSkillCollection = async (
+  name: string,
+  note: string | null,
+  skillIds: string[],
+  id?: string,
+  source?: string | null,
+): Promise<string> => {
+  return invoke<string>('skills_save_collection', { id, name, note, skillIds, source });
+};
+
+export const deleteSkillCollection = async (collectionId: string): Promise<void> => {
+  return invoke('skills_delete_collection', { collectionId });
+};
+
+export const setCollectionToolEnabled = async (
+  collectionId: string,
+  tool: string,
+  enabled: boolean,
+  overwrite?: boolean,
+): Promise<SkillCollectionToolResult> => {
+  return invoke<SkillCollectionToolResult>('skills_set_collection_tool_enabled', {
+    collectionId,
+    tool,
+    enabled,
+    overwrite,
+  });
+};
+
+export const exportSkillCollectionsFile = async (collectionIds?: string[]): Promise<string> => {
+  return invoke<string>('skills_export_collections_file', { collectionIds });
+};
+
+export const importSkillCollectionsFile = async (
+  filePath: string,
+): Promise<SkillCollectionImportResult> => {
+  return invoke<SkillCollectionImportResult>('skills_import_collections_file', { filePath });
+};
+
+// Team Mirrors
+export const getMirrorTargets = async (): Promise<TeamMirrorTarget[]> => {
+  return invoke<TeamMirrorTarget[]>('skills_get_mirror_targets');
+};
+
+/** Create (no `id`) or update a mirror target; returns its id */
+export const saveMirrorTarget = async (
+  path: string,
+  enabled: boolean,
+  collectionId?: string | null,
+  id?: string,
+): Promise<string> => {
+  return invoke<string>('skills_save_mirror_target', { id, path, collectionId, enabled });
+};
+
+export const deleteMirrorTarget = async (targetId: string): Promise<void> => {
+  await invoke('skills_delete_mirror_target', { targetId });
+};
+
+/** Publish one target now, or every enabled target when `targetId` is omitted */
+export const publishMirror = async (targetId?: string): Promise<MirrorPublishResult[]> => {
+  return invoke<MirrorPublishResult[]>('skills_publish_mirror', { targetId });
+};
+
+export const getMirrorSources = async (): Promise<string[]> => {
+  return invoke<string[]>('skills_get_mirror_sources');
+};
+
+/** Remember a mirror dir or URL to install from; fails when its manifest is unreadable */
+export const addMirrorSource = async (location: string): Promise<MirrorListing> => {
+  return invoke<MirrorListing>('skills_add_mirror_source', { location });
+};
+
+export const removeMirrorSource = async (location: string): Promise<void> => {
+  await invoke('skills_remove_mirror_source', { location });
+};
+
+export const listMirrorSkills = async (location: string): Promise<MirrorListing> => {
+  return invoke<MirrorListing>('skills_list_mirror_skills', { location });
+};
+
+export const installFromMirror = async (
+  location: string,
+  name: string,
+  overwrite?: boolean,
+): Promise<InstallResult> => {
+  return invoke<InstallResult>('skills_install_from_mirror', { location, name, overwrite });
+};
+
+export const checkMirrorUpdates = async (): Promise<MirrorUpdate[]> => {
+  return invoke<MirrorUpdate[]>('skills_check_mirror_updates');
+};
+
 export const setSkillManagementEnabled = async (
   skillId: string,
   enabled: boolean,
@@ -198,6 +634,15 @@ export const setSkillManagementEnabled = async (
   return invoke<string[]>('skills_set_management_enabled', { skillId, enabled });
 };
 
+export const archiveSkill = async (skillId: string): Promise<void> => {
+  return invoke('skills_archive_skill', { skillId });
+};
+
+/** Returns the tools the skill was synced to before archiving */
+export const unarchiveSkill = async (skillId: string): Promise<string[]> => {
+  return invoke<string[]>('skills_unarchive_skill', { skillId });
+};
+
 export const exportSkillInventory = async (): Promise<string> => {
   return invoke<string>('skills_export_inventory');
 };
@@ -231,15 +676,128 @@ export const applySkillInventoryImportFile = async (
 };
 
 // Onboarding
-export const getOnboardingPlan = async (): Promise<OnboardingPlan> => {
-  return invoke<OnboardingPlan>('skills_get_onboarding_plan');
+/** `differential` reuses fingerprints of directories unchanged since the last scan */
+export const getOnboardingPlan = async (
+  differential = false,
+  includeSystem = false,
+): Promise<OnboardingPlan> => {
+  return invoke<OnboardingPlan>('skills_get_onboarding_plan', { differential, includeSystem });
+};
+
+/** Writes the anonymized plan with scan diagnostics; returns the file path */
+export const exportOnboardingPlan = async (path?: string): Promise<string> => {
+  return invoke<string>('skills_export_onboarding_plan', { path });
+};
+
+export const scanExtraRoots = async (roots: string[]): Promise<ExtraRootScan[]> => {
+  return invoke<ExtraRootScan[]>('skills_scan_extra_roots', { roots });
 };
 
 export const importExistingSkill = async (
   sourcePath: string,
-  overwrite?: boolean
+  overwrite?: boolean,
+  linkedFolder?: string,
+  trackOrigin?: boolean,
 ): Promise<InstallResult> => {
-  return invoke<InstallResult>('skills_import_existing', { sourcePath, overwrite });
+  return invoke<InstallResult>('skills_import_existing', {
+    sourcePath,
+    overwrite,
+    linkedFolder,
+    trackOrigin,
+  });
+};
+
+export const getLinkedFolderChanges = async (): Promise<LinkedFolderStatus[]> => {
+  return invoke<LinkedFolderStatus[]>('skills_get_linked_folder_changes');
+};
+
+export const setLinkedFolderTracking = async (skillId: string, tracked: boolean): Promise<void> => {
+  await invoke('skills_set_linked_folder_tracking', { skillId, tracked });
+};
+
+export const adoptOnboardingPlan = async (
+  policy: VariantPolicy,
+  names?: string[],
+  overwrite?: boolean,
+): Promise<AdoptOnboardingResult> => {
+  return invoke<AdoptOnboardingResult>('skills_adopt_onboarding_plan', { policy, names, overwrite });
+};
+
+export const getAutoAdoptOnboarding = async (): Promise<boolean> => {
+  return invoke<boolean>('skills_get_auto_adopt_onboarding');
+};
+
+export const setAutoAdoptOnboarding = async (enabled: boolean): Promise<void> => {
+  return invoke('skills_set_auto_adopt_onboarding', { enabled });
+};
+
+export const getAutoResyncCopies = async (): Promise<boolean> => {
+  return invoke<boolean>('skills_get_auto_resync_copies');
+};
+
+export const setAutoResyncCopies = async (enabled: boolean): Promise<void> => {
+  return invoke('skills_set_auto_resync_copies', { enabled });
+};
+
+export const simulateAdoption = async (
+  selection: AdoptionSelection,
+): Promise<AdoptionSimulation> => {
+  return invoke<AdoptionSimulation>('skills_simulate_adoption', { selection });
+};
+
+export const getSourcePriority = async (): Promise<string[]> => {
+  return invoke<string[]>('skills_get_source_priority');
+};
+
+export const setSourcePriority = async (priority: string[]): Promise<void> => {
+  await invoke('skills_set_source_priority', { priority });
+};
+
+export const getConsolidationCandidates = async (): Promise<ConsolidationCandidate[]> => {
+  return invoke<ConsolidationCandidate[]>('skills_get_consolidation_candidates');
+};
+
+export const consolidateCopies = async (names?: string[]): Promise<ConsolidationResult> => {
+  return invoke<ConsolidationResult>('skills_consolidate_copies', { names });
+};
+
+export const getDuplicateContent = async (): Promise<DuplicateContentReport> => {
+  return invoke<DuplicateContentReport>('skills_get_duplicate_content');
+};
+
+export const getConflictOverview = async (): Promise<ConflictOverview> => {
+  return invoke<ConflictOverview>('skills_get_conflict_overview');
+};
+
+// Default Skill Sets
+export const getNameCollisionPolicies = async (): Promise<NameCollisionPolicies> => {
+  return invoke<NameCollisionPolicies>('skills_get_name_collision_policies');
+};
+
+export const saveNameCollisionPolicies = async (policies: NameCollisionPolicies): Promise<void> => {
+  return invoke('skills_save_name_collision_policies', { policies });
+};
+
+export const getDefaultSkillSets = async (): Promise<DefaultSkillSets> => {
+  return invoke<DefaultSkillSets>('skills_get_default_skill_sets');
+};
+
+export const saveDefaultSkillSets = async (sets: DefaultSkillSets): Promise<void> => {
+  return invoke('skills_save_default_skill_sets', { sets });
+};
+
+/** Tools installed since the last check that have default-set skills to offer */
+export const checkNewTools = async (): Promise<DefaultSetOffer[]> => {
+  return invoke<DefaultSetOffer[]>('skills_check_new_tools');
+};
+
+export const applyDefaultSkillSet = async (tool: string): Promise<DefaultSetApplyResult> => {
+  return invoke<DefaultSetApplyResult>('skills_apply_default_skill_set', { tool });
+};
+
+// Storage Report
+export const getStorageReport = async (): Promise<StorageReport> => {
+  return invoke<StorageReport>('skills_get_storage_report');
 };
 
 // Git Cache
@@ -251,6 +809,15 @@ export const setGitCacheCleanupDays = async (days: number): Promise<number> => {
   return invoke<number>('skills_set_git_cache_cleanup_days', { days });
 };
 
+// Skill Variants
+export const getVariantsPerSkill = async (): Promise<number> => {
+  return invoke<number>('skills_get_variants_per_skill');
+};
+
+export const setVariantsPerSkill = async (keep: number): Promise<number> => {
+  return invoke<number>('skills_set_variants_per_skill', { keep });
+};
+
 export const getGitCacheTtlSecs = async (): Promise<number> => {
   return invoke<number>('skills_get_git_cache_ttl_secs');
 };
@@ -318,6 +885,7 @@ export const addCustomTool = async (
   relativeSkillsDir: string,
   relativeDetectDir: string,
   forceCopy?: boolean,
+  preferredSyncMode?: PreferredSyncMode,
 ): Promise<void> => {
   return invoke('skills_add_custom_tool', {
     key,
@@ -325,6 +893,7 @@ export const addCustomTool = async (
     relativeSkillsDir,
     relativeDetectDir,
     forceCopy,
+    preferredSyncMode,
   });
 };
 
@@ -340,6 +909,15 @@ export const createCustomToolPath = async (relativeSkillsDir: string): Promise<v
   return invoke('skills_create_custom_tool_path', { relativeSkillsDir });
 };
 
+/** Absolute dirs where skills may be synced outside the usual tool locations */
+export const getSyncTargetWhitelist = async (): Promise<string[]> => {
+  return invoke<string[]>('skills_get_sync_target_whitelist');
+};
+
+export const setSyncTargetWhitelist = async (dirs: string[]): Promise<void> => {
+  await invoke('skills_set_sync_target_whitelist', { dirs });
+};
+
 // Reorder Skills
 export const reorderSkills = async (ids: string[]): Promise<void> => {
   return invoke('skills_reorder', { ids });
@@ -15,6 +15,8 @@ interface SkillsState {
   onboardingPlan: OnboardingPlan | null;
   centralRepoPath: string;
   groups: SkillGroupRecord[];
+  /** Git skills edited locally since install or the last update */
+  locallyModifiedIds: string[];
 
   // UI state
   loading: boolean;
@@ -26,6 +28,7 @@ interface SkillsState {
   isImportModalOpen: boolean;
   isSettingsModalOpen: boolean;
   isNewToolsModalOpen: boolean;
+  isExternalChangesModalOpen: boolean;
 
   // Actions
   setModalOpen: (open: boolean) => void;
@@ -33,11 +36,13 @@ interface SkillsState {
   setImportModalOpen: (open: boolean) => void;
   setSettingsModalOpen: (open: boolean) => void;
   setNewToolsModalOpen: (open: boolean) => void;
+  setExternalChangesModalOpen: (open: boolean) => void;
 
   // Data actions
   loadToolStatus: () => Promise<void>;
   loadSkills: () => Promise<void>;
-  loadOnboardingPlan: () => Promise<void>;
+  loadLocallyModified: () => Promise<void>;
+  loadOnboardingPlan: (differential?: boolean, includeSystem?: boolean) => Promise<void>;
   loadCentralRepoPath: () => Promise<void>;
   loadGroups: () => Promise<void>;
   refresh: () => Promise<void>;
@@ -55,6 +60,7 @@ export const useSkillsStore = create<SkillsState>()((set, get) => ({
   onboardingPlan: null,
   centralRepoPath: '',
   groups: [],
+  locallyModifiedIds: [],
 
   // UI state
   loading: false,
@@ -66,6 +72,7 @@ export const useSkillsStore = create<SkillsState>()((set, get) => ({
   isImportModalOpen: false,
   isSettingsModalOpen: false,
   isNewToolsModalOpen: false,
+  isExternalChangesModalOpen: false,
 
   // Actions
   setModalOpen: (open) => set({ isModalOpen: open }),
@@ -73,6 +80,7 @@ export const useSkillsStore = create<SkillsState>()((set, get) => ({
   setImportModalOpen: (open) => set({ isImportModalOpen: open }),
   setSettingsModalOpen: (open) => set({ isSettingsModalOpen: open }),
   setNewToolsModalOpen: (open) => set({ isNewToolsModalOpen: open }),
+  setExternalChangesModalOpen: (open) => set({ isExternalChangesModalOpen: open }),
 
   // Data actions
   loadToolStatus: async () => {
@@ -90,15 +98,26 @@ export const useSkillsStore = create<SkillsState>()((set, get) => ({
     try {
       const skills = await api.getManagedSkills();
       set({ skills, loading: false });
+      // Hashing central copies can take a while; do not hold up the list
+      void get().loadLocallyModified();
     } catch (error) {
       console.error('Failed to load skills:', error);
       set({ error: String(error), loading: false });
     }
   },
 
-  loadOnboardingPlan: async () => {
+  loadLocallyModified: async () => {
     try {
-      const plan = await api.getOnboardingPlan();
+      const ids = await api.getLocallyModifiedSkills();
+      set({ locallyModifiedIds: ids });
+    } catch (error) {
+      console.error('Failed to check local modifications:', error);
+    }
+  },
+
+  loadOnboardingPlan: async (differential, includeSystem) => {
+    try {
+      const plan = await api.getOnboardingPlan(differential, includeSystem);
       set({ onboardingPlan: plan });
     } catch (error) {
       console.error('Failed to load onboarding plan:', error);
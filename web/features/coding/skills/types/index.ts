@@ -3,7 +3,7 @@
 export interface ManagedSkill {
   id: string;
   name: string;
-  source_type: 'local' | 'git' | 'import' | 'central';
+  source_type: 'local' | 'linked_folder' | 'git' | 'import' | 'central' | 'team_mirror';
   source_ref: string | null;
   central_path: string;
   created_at: number;
@@ -26,6 +26,31 @@ export interface ManagedSkill {
 
   // Derived from sync_details (maintained for compatibility)
   targets: SkillTarget[];
+
+  license: string | null;
+  provenance: SkillProvenance | null;
+  /** Free-form labels, independent of the group */
+  tags: string[];
+  /** User-set tool version constraints by tool key, e.g. { claude_code: '>=2.0' } */
+  compatibility: Record<string, string>;
+  /** Display names and descriptions by UI language; `name` stays the sync identity */
+  localizations: Record<string, SkillLocalization>;
+  /** Central directory kept read-only; app updates lift it while writing */
+  write_protected: boolean;
+}
+
+export interface SkillLocalization {
+  display_name: string | null;
+  description: string | null;
+}
+
+export interface SkillProvenance {
+  origin: string | null;
+  /** Actual fetch URL when routed through a mirror */
+  fetched_from: string | null;
+  revision: string | null;
+  author: string | null;
+  recorded_at: number;
 }
 
 export type SkillSourceHealth = 'ok' | 'warning';
@@ -36,8 +61,19 @@ export interface SkillTarget {
   status: string;
   target_path: string;
   synced_at: number | null;
+  /** Subpaths this target's copy receives; absent when it gets the full skill */
+  path_rules?: SkillPathRules;
+}
+
+/** Include/exclude patterns relative to the skill root; SKILL.md is always kept */
+export interface SkillPathRules {
+  include: string[];
+  exclude: string[];
 }
 
+/** Modes a target can be converted to in place */
+export type TargetSyncMode = 'symlink' | 'copy';
+
 export interface SkillRepo {
   id: string;
   owner: string;
@@ -62,6 +98,99 @@ export interface ToolInfo {
   label: string;
   installed: boolean;
   skills_dir: string;
+  /** Broken symlinks in skills_dir, whoever created them */
+  dangling_links: string[];
+}
+
+export type ToolLoadSkipReason =
+  | 'hidden'
+  | 'not_a_directory'
+  | 'broken_link'
+  | 'link_not_followed'
+  | 'no_entry_file'
+  | 'entry_file_case'
+  | 'too_deep'
+  | 'missing_keys';
+
+export interface ToolLoadRules {
+  entry_file: string;
+  max_depth: number;
+  follows_links: boolean;
+  required_keys: string[];
+}
+
+export interface ToolLoadedSkill {
+  name: string;
+  /** Relative to the tool's skills dir */
+  path: string;
+  is_link: boolean;
+  system: boolean;
+  /** Relative to the skill dir, entry file first */
+  files: string[];
+}
+
+export interface ToolLoadSkippedEntry {
+  path: string;
+  reason: ToolLoadSkipReason;
+  /** Misnamed entry file or missing frontmatter keys */
+  detail: string | null;
+}
+
+/** What a tool loads from its skills dir (skills_preview_tool_load) */
+export interface ToolLoadPreview {
+  tool: string;
+  tool_display: string;
+  skills_dir: string;
+  exists: boolean;
+  rules: ToolLoadRules;
+  loaded: ToolLoadedSkill[];
+  skipped: ToolLoadSkippedEntry[];
+}
+
+export interface LinkRepairEntry {
+  skill_id: string;
+  skill_name: string;
+  tool: string;
+  target_path: string;
+  /** Source the link points at again, or why it could not be restored */
+  detail: string;
+}
+
+export interface LinkRepairResult {
+  checked: number;
+  repaired: LinkRepairEntry[];
+  /** Targets now recorded with status `broken` */
+  broken: LinkRepairEntry[];
+}
+
+/** What overwriting an existing sync target would change, per file */
+export interface TargetConflictFile {
+  path: string;
+  status: 'added' | 'modified' | 'deleted';
+  source_hash: string | null;
+  target_hash: string | null;
+}
+
+export interface TargetConflictReport {
+  target_path: string;
+  kind: 'directory' | 'link' | 'file';
+  /** Where a `link` target points */
+  link_target: string | null;
+  files: TargetConflictFile[];
+}
+
+export interface SkillSyncRun {
+  id: string;
+  trigger: 'scheduled' | 'manual';
+  started_at: number;
+  finished_at: number;
+  /** Recorded targets checked for drift */
+  checked: number;
+  /** Drifted targets re-synced, as `skill:tool` */
+  resynced: string[];
+  /** Broken links re-created, as `skill:tool` */
+  repaired: string[];
+  errors: string[];
 }
 
 export interface ToolStatus {
@@ -75,11 +204,43 @@ export interface InstallResult {
   name: string;
   central_path: string;
   content_hash: string | null;
+  license: string | null;
+  /** Set when the license is in the disallowed list */
+  license_warning: string | null;
+}
+
+export type SyncMismatchKind = 'missing' | 'changed' | 'extra';
+
+/** A file of a copied target that does not match the source */
+export interface SyncFileMismatch {
+  path: string;
+  kind: SyncMismatchKind;
 }
 
 export interface SyncResult {
   mode_used: string;
   target_path: string;
+  /** Whether a copy matched the source file by file; null for links, hard links and unchecked copies */
+  verified: boolean | null;
+  mismatches: SyncFileMismatch[];
+  /** Set when an existing copy was updated in place instead of replaced */
+  file_changes: SyncFileChanges | null;
+}
+
+/** Per-file counts of a copy updated in place */
+export interface SyncFileChanges {
+  copied: number;
+  deleted: number;
+  unchanged: number;
+}
+
+export interface SyncPreview {
+  mode_used: string;
+  target_path: string;
+  replaced: boolean;
+  created: string[];
+  overwritten: string[];
+  removed: string[];
 }
 
 export interface UpdateResult {
@@ -90,12 +251,86 @@ export interface UpdateResult {
   updated_targets: string[];
 }
 
+/** How to update a git skill whose central copy was edited locally */
+export type LocalChangesResolution = 'overwrite' | 'keep_local' | 'merge';
+
+export interface SkillUpdateCommit {
+  revision: string;
+  author: string;
+  committed_at: number;
+  summary: string;
+}
+
+export type SkillUpdateFileStatus = 'added' | 'removed' | 'modified';
+
+// A skill whose central directory was changed outside the app
+export interface ExternalChange {
+  skill_id: string;
+  name: string;
+  central_path: string;
+  recorded_hash: string;
+  current_hash: string;
+  /** A snapshot of the last app-written content exists to diff and revert to */
+  has_baseline: boolean;
+  /** Changes since the last app-written content; empty without a baseline */
+  files: SkillUpdateFile[];
+}
+
+// Files a copy target was edited in, from the central copy to the target
+export interface TargetPullPreview {
+  skill_id: string;
+  name: string;
+  tool: string;
+  target_path: string;
+  files: SkillUpdateFile[];
+}
+
+export interface TargetPullResult {
+  /** Files written to or removed from the central copy */
+  pulled: string[];
+  content_hash: string | null;
+  /** Other tools re-synced with the new content */
+  resynced: string[];
+  /** `tool: error` for re-syncs that failed */
+  errors: string[];
+}
+
+export interface SkillUpdateFile {
+  path: string;
+  status: SkillUpdateFileStatus;
+  /** null when there is no installed revision to compare with */
+  locally_modified: boolean | null;
+  /** Unified diff from the central copy to upstream; null for large files */
+  patch: string | null;
+}
+
+export interface SkillUpdatePreview {
+  skill_id: string;
+  name: string;
+  source_type: string;
+  current_revision: string | null;
+  new_revision: string | null;
+  /** Commits after the installed revision, newest first; null when unavailable */
+  changelog: SkillUpdateCommit[] | null;
+  files: SkillUpdateFile[];
+  has_local_modifications: boolean;
+  overwritten_local_files: string[];
+}
+
 export interface GitSkillCandidate {
   name: string;
   description: string | null;
   subpath: string;
 }
 
+/** Collection defined by a repo's skill-collections.json */
+export interface GitCollectionCandidate {
+  name: string;
+  note: string | null;
+  subpaths: string[];
+  missing: string[];
+}
+
 export interface OnboardingVariant {
   tool: string;
   tool_display: string;
@@ -104,9 +339,24 @@ export interface OnboardingVariant {
   fingerprint: string | null;
   is_link: boolean;
   link_target: string | null;
+  linked_folder: string | null;
   conflicting_tools: string[];
 }
 
+export interface ExtraRootScan {
+  root: string;
+  plan: OnboardingPlan | null;
+  error: string | null;
+}
+
+export interface LinkedFolderStatus {
+  skill_id: string;
+  name: string;
+  source_path: string;
+  exists: boolean;
+  changed: boolean;
+}
+
 export interface OnboardingGroup {
   name: string;
   variants: OnboardingVariant[];
@@ -117,6 +367,210 @@ export interface OnboardingPlan {
   total_tools_scanned: number;
   total_skills_found: number;
   groups: OnboardingGroup[];
+  previous_scan_at: number | null;
+  new_since_last_scan: string[];
+  rehashed_dirs: number;
+  /** Only filled when the scan includes system skills */
+  system_skills: SystemSkill[];
+}
+
+/** A skill a tool ships and manages itself; listed read-only */
+export interface SystemSkill {
+  tool: string;
+  tool_display: string;
+  name: string;
+  path: string;
+  description: string | null;
+}
+
+/** Payload of `skills-tool-detected`: a tool installed while the app runs */
+export interface ToolDetectedEvent {
+  tool: string;
+  tool_display: string;
+  plan: OnboardingPlan;
+}
+
+export type VariantPolicy =
+  | { kind: 'prefer_tool'; tool: string }
+  | { kind: 'newest_mtime' }
+  | { kind: 'largest' };
+
+export interface AdoptOnboardingResult {
+  adopted: {
+    name: string;
+    tool: string;
+    path: string;
+    skill_id: string;
+    central_path: string;
+  }[];
+  skipped: { name: string; error: string }[];
+}
+
+export interface AdoptionSelection {
+  plan: OnboardingPlan;
+  policy: VariantPolicy;
+  names?: string[];
+  tools?: string[];
+  overwrite?: boolean;
+}
+
+export interface SimulatedOperation {
+  group: string;
+  kind: 'pick' | 'import' | 'sync';
+  tool: string | null;
+  source: string | null;
+  target: string | null;
+  mode: string | null;
+  error: string | null;
+}
+
+export interface AdoptionSimulation {
+  operations: SimulatedOperation[];
+  adopted: number;
+  failed: number;
+}
+
+export interface ConsolidationCopy {
+  tool: string;
+  tool_display: string;
+  path: string;
+}
+
+export interface ConsolidationCandidate {
+  name: string;
+  fingerprint: string;
+  copies: ConsolidationCopy[];
+  bytes_per_copy: number;
+  reclaimable_bytes: number;
+}
+
+export interface ConsolidationResult {
+  consolidated: {
+    name: string;
+    skill_id: string;
+    synced_tools: string[];
+    skipped_tools: string[];
+  }[];
+  failed: { name: string; error: string }[];
+  bytes_reclaimed: number;
+}
+
+export interface DuplicateCopy {
+  tool: string;
+  tool_display: string;
+  name: string;
+  path: string;
+  skill_id: string | null;  // Managed skill owning this copy-mode target; null when unmanaged
+}
+
+export interface DuplicateCluster {
+  fingerprint: string;
+  copies: DuplicateCopy[];
+  bytes_per_copy: number;
+  reclaimable_bytes: number;
+}
+
+export interface DuplicateContentReport {
+  clusters: DuplicateCluster[];
+  scanned: number;
+  reclaimable_bytes: number;
+  generated_at: number;
+}
+
+// Skills whose content differs between tools right now
+export interface ConflictCell {
+  tool: string;
+  tool_display: string;
+  path: string;
+  is_link: boolean;
+  managed: boolean;  // Recorded sync target of the managed skill with this name
+  fingerprint: string | null;
+  version: number | null;  // Index into the row's versions; 0 is the central repo's content when present
+}
+
+export interface SkillConflict {
+  name: string;
+  skill_id: string | null;
+  versions: number;
+  cells: ConflictCell[];
+}
+
+export interface ConflictTool {
+  key: string;
+  display: string;
+}
+
+export interface ConflictOverview {
+  skills: SkillConflict[];
+  tools: ConflictTool[];
+  scanned: number;
+  rehashed_dirs: number;
+  generated_at: number;
+}
+
+// What a sync replaced or removed, kept in the trash for a rollback
+export type SyncJournalOp = 'overwrite' | 'remove' | 'rollback';
+
+export interface SyncJournalEntry {
+  id: string;
+  target: string;
+  op: SyncJournalOp;
+  created_at: number;
+  restored_at: number | null;
+  /** Bytes the entry holds in the trash */
+  size: number;
+}
+
+export interface SyncTrashPurgeSummary {
+  removed: number;
+  freed_bytes: number;
+}
+
+// Default skill sets offered to newly detected tools
+export interface DefaultSkillSets {
+  global: string[];
+  /** Tool key -> skill ids; replaces `global` for that tool */
+  per_tool: Record<string, string[]>;
+}
+
+/** What happens when a skill's name collides with another in a tool dir */
+export type NameCollisionPolicy = 'block' | 'auto_suffix' | 'priority';
+
+export interface NameCollisionPolicies {
+  default: NameCollisionPolicy;
+  /** Tool key -> policy; replaces `default` for that tool */
+  per_tool: Record<string, NameCollisionPolicy>;
+}
+
+export interface DefaultSetOffer {
+  tool: string;
+  tool_display: string;
+  skill_ids: string[];
+}
+
+export interface DefaultSetApplyResult {
+  tool: string;
+  synced: string[];
+  errors: string[];
+}
+
+export interface SkillStorage {
+  skill_id: string;
+  name: string;
+  central_bytes: number;
+  copy_tools: string[];
+  duplicated_bytes: number;
+}
+
+export interface StorageReport {
+  skills: SkillStorage[];
+  central_bytes: number;
+  duplicated_bytes: number;
+  git_cache_bytes: number;
+  variant_snapshot_bytes: number;
+  config_snapshot_bytes: number;
+  link_savings: SkillStorage[];
+  generated_at: number;
 }
 
 export interface SkillGroup {
@@ -138,6 +592,80 @@ export interface SkillGroupRecord {
   updated_at: number;
 }
 
+export interface SkillCollection {
+  id: string;
+  name: string;
+  note: string | null;
+  skill_ids: string[];
+  /** Repo the collection was defined by, e.g. "owner/name" */
+  source: string | null;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface SkillCollectionToolResult {
+  collection_id: string;
+  tool: string;
+  changed: string[];
+  skipped: string[];
+  errors: string[];
+}
+
+export interface SkillCollectionImportResult {
+  created: number;
+  updated: number;
+  unresolved: string[];
+}
+
+/** Read-only copy of the library (or one collection) published to a share */
+export interface TeamMirrorTarget {
+  id: string;
+  path: string;
+  collection_id: string | null;
+  /** Republish automatically when skills change */
+  enabled: boolean;
+  last_published_at: number | null;
+  last_error: string | null;
+}
+
+export interface MirrorPublishResult {
+  target_id: string;
+  path: string;
+  copied: string[];
+  removed: string[];
+  unchanged: number;
+  error: string | null;
+}
+
+/** Skill published by a team mirror this app installs from */
+export interface MirrorSkill {
+  name: string;
+  description: string | null;
+  license: string | null;
+  tags: string[];
+  content_hash: string;
+  updated_at: number;
+  installed_skill_id: string | null;
+  update_available: boolean;
+}
+
+export interface MirrorListing {
+  location: string;
+  collection: string | null;
+  published_at: number;
+  skills: MirrorSkill[];
+}
+
+export interface MirrorUpdate {
+  skill_id: string;
+  name: string;
+  location: string;
+  current_hash: string | null;
+  /** null when the mirror no longer publishes the skill */
+  available_hash: string | null;
+  error: string | null;
+}
+
 export interface SkillInventoryPreview {
   valid: boolean;
   errors: string[];
@@ -267,6 +795,205 @@ export interface DeleteManagedSkillOptions {
   deleteSourceFiles?: boolean;
 }
 
+export type DangerousOp = 'delete_central_content' | 'overwrite_targets' | 'purge_trash';
+
+export interface ConfirmationTicket {
+  token: string;
+  op: DangerousOp;
+  summary: string;
+  skill_ids: string[];
+  expires_at: number;
+}
+
+export interface SkillSourceMirror {
+  enabled: boolean;
+  base_url: string;
+  /** Upstream source URL -> expected checksum (`sha256:<hex>`) */
+  checksums: Record<string, string>;
+  require_checksum: boolean;
+}
+
+export type SkillDriftState = 'in_sync' | 'drifted' | 'unsynced';
+
+/** Structured skill filter; filters are AND-ed, values inside one filter OR-ed */
+export interface SkillQuery {
+  tools?: string[];
+  drift?: SkillDriftState | null;
+  source_types?: string[];
+  /** Last sync earlier than this (ms); never-synced skills match too */
+  last_sync_before?: number | null;
+  /** Group ids or names */
+  groups?: string[];
+  /** Any of these tags */
+  tags?: string[];
+  limit?: number | null;
+}
+
+export type TagSuggestionReason = 'library_tag' | 'tool_mention' | 'keyword';
+
+export interface TagSuggestion {
+  tag: string;
+  reason: TagSuggestionReason;
+  score: number;
+}
+
+export interface SkillTagSuggestions {
+  skill_id: string;
+  skill_name: string;
+  suggestions: TagSuggestion[];
+}
+
+export interface SkillTagCount {
+  tag: string;
+  count: number;
+}
+
+export type SkillQualityFlag = 'lint' | 'bloated' | 'stale' | 'unused' | 'risky';
+
+export interface SkillLintIssue {
+  severity: 'error' | 'warning';
+  code: string;
+  message: string;
+}
+
+export interface SkillRiskFinding {
+  severity: 'high' | 'medium';
+  rule: string;
+  /** Path relative to the skill directory */
+  file: string;
+  line: number;
+}
+
+export interface SkillQualityReport {
+  skill_id: string;
+  name: string;
+  /** 0 (worst) - 100 (best) */
+  score: number;
+  flags: SkillQualityFlag[];
+  lint: SkillLintIssue[];
+  tokens: number;
+  updated_at: number;
+  age_days: number;
+  usage: {
+    enabled_tools: number;
+    synced_targets: number;
+    last_accessed_at: number | null;
+  };
+  risks: SkillRiskFinding[];
+}
+
+/** Tool that keeps skills inside a single JSON/YAML config file */
+export interface FileSkillTool {
+  key: string;
+  display_name: string;
+  config_path: string;
+  exists: boolean;
+  skill_names: string[];
+  /** Parse error of the config file, if any */
+  error: string | null;
+}
+
+export type StaleArtifactReason = 'dangling' | 'tool_not_installed';
+
+export interface StaleArtifact {
+  tool: string;
+  path: string;
+  link_target: string;
+  reason: StaleArtifactReason;
+  skill_id: string | null;
+}
+
+export interface StaleArtifactReport {
+  artifacts: StaleArtifact[];
+  scanned_dirs: string[];
+  checked_at: number;
+}
+
+export interface SweepResult {
+  removed: string[];
+  skipped: string[];
+}
+
+export interface RecoveredTarget {
+  skill_id: string;
+  skill_name: string;
+  tool: string;
+  target_path: string;
+}
+
+export interface MarkerRecoveryResult {
+  recovered: RecoveredTarget[];
+  /** Marked copies whose skill is not in the library */
+  unmatched: string[];
+}
+
+export interface DatabaseRebuildResult {
+  /** Central skill dirs recorded as skills again */
+  adopted: string[];
+  skipped: string[];
+  recovered_targets: RecoveredTarget[];
+  unmatched_markers: string[];
+}
+
+export type LibraryAuditIssueKind =
+  | 'ghost_skill'
+  | 'orphan_dir'
+  | 'duplicate_target'
+  | 'mismatched_mode'
+  | 'missing_target';
+
+export type LibraryAuditFixAction = 'delete_record' | 'adopt_dir' | 'drop_target' | 'record_mode';
+
+export interface LibraryAuditIssue {
+  kind: LibraryAuditIssueKind;
+  fix: LibraryAuditFixAction;
+  skill_id: string | null;
+  skill_name: string | null;
+  tool: string | null;
+  path: string;
+  detail: string;
+}
+
+export interface LibraryAudit {
+  central_path: string;
+  checked_skills: number;
+  checked_targets: number;
+  issues: LibraryAuditIssue[];
+  checked_at: number;
+}
+
+export interface LibraryAuditFixResult {
+  fixed: LibraryAuditIssue[];
+  skipped: LibraryAuditIssue[];
+  errors: string[];
+}
+
+export interface SkillCompatibility {
+  tool: string;
+  constraint: string;
+  /** 'user' when set on the skill, 'frontmatter' when read from SKILL.md */
+  source: 'user' | 'frontmatter';
+  detected_version: string | null;
+  /** null when the version is unknown or the constraint is invalid */
+  satisfied: boolean | null;
+}
+
+export interface StartupCheckSummary {
+  level: 'report' | 'safe' | 'full';
+  checked_at: number;
+  duration_ms: number;
+  checked_skills: number;
+  checked_targets: number;
+  settings_errors: string[];
+  central_repo_missing: boolean;
+  /** Issues left for review in the library audit */
+  issues: LibraryAuditIssue[];
+  fixed: LibraryAuditIssue[];
+  errors: string[];
+  /** Fixes were due but skipped because read-only mode is on */
+  read_only: boolean;
+}
+
 export type SkillEnabledFilter = 'all' | 'enabled' | 'disabled';
 
 export interface ToolOption {
@@ -282,4 +1009,8 @@ export interface CustomTool {
   relative_detect_dir: string;
   created_at: number;
   force_copy: boolean;
+  /** Sync mode skills always use for this tool; unset links with a copy fallback */
+  preferred_sync_mode?: PreferredSyncMode | null;
 }
+
+export type PreferredSyncMode = 'copy' | 'symlink' | 'junction' | 'hardlink';
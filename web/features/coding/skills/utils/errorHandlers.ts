@@ -1,8 +1,8 @@
 import React from 'react';
-import { Modal, Button, message } from 'antd';
+import { Modal, Button, Input, message } from 'antd';
 import type { TFunction } from 'i18next';
 import { formatGitError, isGitError } from './gitErrorParser';
-import type { ToolOption } from '../types';
+import type { LocalChangesResolution, SyncResult, TargetConflictReport, ToolOption } from '../types';
 
 /**
  * Check if error is a SKILL_EXISTS error
@@ -28,6 +28,132 @@ export function parseTargetExistsError(errMsg: string): { targetPath: string } |
   return match ? { targetPath: match[1] } : null;
 }
 
+/**
+ * Parse NAME_CASE_COLLISION|name|existing|suggestion error: two skill names
+ * that differ only by case would share a directory on macOS/Windows
+ */
+export function parseNameCaseCollisionError(
+  errMsg: string
+): { name: string; existing: string; suggestion: string } | null {
+  const match = errMsg.match(/NAME_CASE_COLLISION\|([^|]*)\|([^|]*)\|([^|\n]*)/);
+  return match ? { name: match[1], existing: match[2], suggestion: match[3] } : null;
+}
+
+/**
+ * Parse NAME_COLLISION_PRIORITY|name|winner error: under the tool's
+ * `priority` collision policy, a skill listed earlier keeps the name
+ */
+export function parseNameCollisionPriorityError(
+  errMsg: string
+): { name: string; winner: string } | null {
+  const match = errMsg.match(/NAME_COLLISION_PRIORITY\|([^|]*)\|([^|\n]*)/);
+  return match ? { name: match[1], winner: match[2] } : null;
+}
+
+/**
+ * Parse SYNC_TARGET_UNEXPECTED|tool|dir|reason error: the tool's skills
+ * directory resolved to a location skills are not synced into
+ */
+export function parseSyncTargetUnexpectedError(
+  errMsg: string
+): { tool: string; path: string; reason: string } | null {
+  const match = errMsg.match(/SYNC_TARGET_UNEXPECTED\|([^|]*)\|([^|]*)\|([^|\n]*)/);
+  return match ? { tool: match[1], path: match[2], reason: match[3] } : null;
+}
+
+/**
+ * Parse INCOMPATIBLE_TOOL_VERSION|tool|constraint|version error: the skill's
+ * compatibility constraint rules out the installed tool version
+ */
+export function parseIncompatibleToolVersionError(
+  errMsg: string
+): { tool: string; constraint: string; version: string } | null {
+  const match = errMsg.match(/INCOMPATIBLE_TOOL_VERSION\|([^|]*)\|([^|]*)\|([^|\n]*)/);
+  return match ? { tool: match[1], constraint: match[2], version: match[3] } : null;
+}
+
+/**
+ * Parse POLICY_LOCKED|rule|detail error: the administrator's group policy
+ * refuses the action
+ */
+export function parsePolicyLockedError(errMsg: string): { rule: string; detail: string } | null {
+  const match = errMsg.match(/POLICY_LOCKED\|([^|]*)\|([^\n]*)/);
+  return match ? { rule: match[1], detail: match[2] } : null;
+}
+
+const POLICY_RULE_MESSAGES: Record<string, string> = {
+  forbidden_registry: 'skills.errors.policyForbiddenRegistry',
+  mandatory_skill: 'skills.errors.policyMandatorySkill',
+  central_repo_path: 'skills.errors.policyCentralRepoPath',
+  disabled_feature: 'skills.errors.policyDisabledFeature',
+};
+
+/**
+ * Warn that a skill does not support the installed tool version; resolves
+ * true when the user chooses to enable it anyway
+ */
+export function confirmIncompatibleSync(
+  skillName: string,
+  toolLabel: string,
+  constraint: string,
+  version: string,
+  t: TFunction
+): Promise<boolean> {
+  return new Promise((resolve) => {
+    Modal.confirm({
+      title: t('skills.compatibility.incompatibleTitle'),
+      content: t('skills.compatibility.incompatibleMessage', {
+        skill: skillName,
+        tool: toolLabel,
+        constraint,
+        version,
+      }),
+      okText: t('skills.compatibility.enableAnyway'),
+      okType: 'danger',
+      cancelText: t('common.cancel'),
+      onOk: () => resolve(true),
+      onCancel: () => resolve(false),
+    });
+  });
+}
+
+const MAX_LISTED_MISMATCHES = 20;
+
+/**
+ * Warn when a copy sync left a target that does not match the source
+ */
+export function showCopyVerificationWarning(
+  result: SyncResult,
+  skillName: string,
+  toolLabel: string,
+  t: TFunction
+): void {
+  if (result.verified !== false) {
+    return;
+  }
+  const listed = result.mismatches.slice(0, MAX_LISTED_MISMATCHES);
+  const hidden = result.mismatches.length - listed.length;
+  Modal.warning({
+    title: t('skills.copyVerification.title'),
+    content: React.createElement('div', null, [
+      React.createElement('p', { key: 'msg' }, t('skills.copyVerification.message', {
+        skill: skillName,
+        tool: toolLabel,
+        count: result.mismatches.length,
+      })),
+      React.createElement('ul', { key: 'files', style: { maxHeight: 240, overflow: 'auto', fontSize: 12 } },
+        listed.map((mismatch) => React.createElement('li', { key: mismatch.path },
+          `${t(`skills.copyVerification.kind.${mismatch.kind}`)}: ${mismatch.path}`
+        ))
+      ),
+      hidden > 0
+        ? React.createElement('p', { key: 'more' }, t('skills.copyVerification.more', { count: hidden }))
+        : null,
+    ]),
+    width: 560,
+  });
+}
+
 /**
  * Show git error or general error message
  */
@@ -55,6 +181,66 @@ export function showGitError(
     return;
   }
 
+  const policyLocked = parsePolicyLockedError(errMsg);
+  if (policyLocked) {
+    const key = POLICY_RULE_MESSAGES[policyLocked.rule];
+    Modal.error({
+      title: t('skills.errors.policyLockedTitle'),
+      content: key ? t(key, { detail: policyLocked.detail }) : errMsg,
+    });
+    return;
+  }
+
+  const unexpectedTarget = parseSyncTargetUnexpectedError(errMsg);
+  if (unexpectedTarget) {
+    const tool = allTools?.find((t) => t.id === unexpectedTarget.tool);
+    Modal.error({
+      title: t('skills.errors.syncTargetUnexpectedTitle'),
+      content: t('skills.errors.syncTargetUnexpected', {
+        tool: tool?.label || unexpectedTarget.tool,
+        path: unexpectedTarget.path,
+      }),
+    });
+    return;
+  }
+
+  const collision = parseNameCaseCollisionError(errMsg);
+  if (collision) {
+    Modal.error({
+      title: t('skills.errors.nameCaseCollisionTitle'),
+      content: t('skills.errors.nameCaseCollision', collision),
+    });
+    return;
+  }
+
+  const priorityCollision = parseNameCollisionPriorityError(errMsg);
+  if (priorityCollision) {
+    Modal.error({
+      title: t('skills.errors.nameCaseCollisionTitle'),
+      content: t('skills.errors.nameCollisionPriority', priorityCollision),
+    });
+    return;
+  }
+
+  const checksumSource = parseChecksumRequiredError(errMsg);
+  if (checksumSource) {
+    Modal.error({
+      title: t('skills.checksum.requiredTitle'),
+      content: t('skills.checksum.required', { source: checksumSource }),
+    });
+    return;
+  }
+
+  const mismatch = parseChecksumMismatchError(errMsg);
+  if (mismatch) {
+    Modal.error({
+      title: t('skills.checksum.mismatchTitle'),
+      content: React.createElement('div', { style: { whiteSpace: 'pre-wrap' } },
+        t('skills.checksum.mismatch', mismatch)),
+    });
+    return;
+  }
+
   if (isGitError(errMsg)) {
     Modal.error({
       title: t('common.error'),
@@ -93,12 +279,15 @@ export function confirmTargetOverwrite(
   skillName: string,
   toolLabel: string,
   targetPath: string,
-  t: TFunction
+  t: TFunction,
+  conflict?: TargetConflictReport | null
 ): Promise<boolean> {
+  const message = t('skills.targetExists.message', { skill: skillName, tool: toolLabel, path: targetPath });
   return new Promise((resolve) => {
     Modal.confirm({
       title: t('skills.targetExists.title'),
-      content: t('skills.targetExists.message', { skill: skillName, tool: toolLabel, path: targetPath }),
+      content: conflict ? renderTargetConflict(message, conflict, t) : message,
+      width: conflict ? 620 : undefined,
       okText: t('skills.overwrite.confirm'),
       okType: 'danger',
       cancelText: t('skills.overwrite.skip'),
@@ -108,6 +297,39 @@ export function confirmTargetOverwrite(
   });
 }
 
+const shortHash = (hash: string | null) => (hash ? hash.slice(0, 8) : '—');
+
+/**
+ * Message of a TARGET_EXISTS confirmation followed by the files an overwrite
+ * would add, replace or delete
+ */
+function renderTargetConflict(
+  message: string,
+  conflict: TargetConflictReport,
+  t: TFunction
+): React.ReactNode {
+  const listed = conflict.files.slice(0, MAX_LISTED_MISMATCHES);
+  const hidden = conflict.files.length - listed.length;
+  return React.createElement('div', null, [
+    React.createElement('p', { key: 'msg', style: { whiteSpace: 'pre-wrap' } }, message),
+    conflict.link_target
+      ? React.createElement('p', { key: 'link' }, t('skills.targetConflict.link', { path: conflict.link_target }))
+      : null,
+    conflict.files.length > 0
+      ? React.createElement('p', { key: 'summary' }, t('skills.targetConflict.summary', { count: conflict.files.length }))
+      : null,
+    React.createElement('ul', { key: 'files', style: { maxHeight: 240, overflow: 'auto', fontSize: 12 } },
+      listed.map((file) => React.createElement('li', { key: file.path },
+        `${t(`skills.targetConflict.status.${file.status}`)}: ${file.path} `
+          + `(${shortHash(file.target_hash)} → ${shortHash(file.source_hash)})`
+      ))
+    ),
+    hidden > 0
+      ? React.createElement('p', { key: 'more' }, t('skills.copyVerification.more', { count: hidden }))
+      : null,
+  ]);
+}
+
 /**
  * Confirm batch overwrite with three options: overwrite, overwriteAll, skip
  */
@@ -141,3 +363,122 @@ export function confirmBatchOverwrite(
     });
   });
 }
+
+/**
+ * Parse LOCAL_MODIFIED|skillId error raised when updating an edited git skill
+ */
+export function isLocalModifiedError(errMsg: string): boolean {
+  return errMsg.includes('LOCAL_MODIFIED|');
+}
+
+/**
+ * Parse CHECKSUM_REQUIRED|source error: the source has no known checksum.
+ * Returns the source URL.
+ */
+export function parseChecksumRequiredError(errMsg: string): string | null {
+  const match = errMsg.match(/CHECKSUM_REQUIRED\|(\S+)/);
+  return match ? match[1] : null;
+}
+
+/**
+ * Parse CHECKSUM_MISMATCH|expected|actual error
+ */
+export function parseChecksumMismatchError(
+  errMsg: string
+): { expected: string; actual: string } | null {
+  const match = errMsg.match(/CHECKSUM_MISMATCH\|([^|\s]+)\|([^|\s]+)/);
+  return match ? { expected: match[1], actual: match[2] } : null;
+}
+
+const CHECKSUM_PATTERN = /^(sha256:)?[0-9a-f]{64}$/i;
+
+/**
+ * Ask for the SHA-256 of an archive whose host publishes none, so an archive
+ * skill can be updated. Resolves null when cancelled.
+ */
+export function promptArchiveChecksum(
+  skillName: string,
+  url: string,
+  t: TFunction
+): Promise<string | null> {
+  return new Promise((resolve) => {
+    let value = '';
+    Modal.confirm({
+      title: t('skills.checksum.promptTitle'),
+      width: 560,
+      content: React.createElement('div', null, [
+        React.createElement('p', { key: 'msg' }, t('skills.checksum.promptMessage', { name: skillName })),
+        React.createElement('p', {
+          key: 'url',
+          style: { fontSize: 12, color: 'var(--color-text-tertiary)', wordBreak: 'break-all' },
+        }, url),
+        React.createElement(Input, {
+          key: 'input',
+          placeholder: 'sha256:…',
+          onChange: (event: React.ChangeEvent<HTMLInputElement>) => {
+            value = event.target.value.trim();
+          },
+        }),
+      ]),
+      okText: t('skills.checksum.promptConfirm'),
+      cancelText: t('common.cancel'),
+      // Taking `close` keeps the dialog open on an invalid checksum
+      onOk: (close: () => void) => {
+        if (!CHECKSUM_PATTERN.test(value)) {
+          message.error(t('skills.checksum.invalid'));
+          return;
+        }
+        close();
+        resolve(value);
+      },
+      onCancel: () => resolve(null),
+    });
+  });
+}
+
+/**
+ * Parse MERGE_CONFLICT|path,path error from a merge update
+ */
+export function parseMergeConflictError(errMsg: string): string[] | null {
+  const match = errMsg.match(/MERGE_CONFLICT\|(.*)/);
+  return match ? match[1].split(',').filter(Boolean) : null;
+}
+
+/**
+ * Ask how to update a skill with local edits: keep local (fork), merge or overwrite
+ */
+export function chooseLocalChangesResolution(
+  skillName: string,
+  t: TFunction
+): Promise<LocalChangesResolution | null> {
+  return new Promise((resolve) => {
+    const choose = (resolution: LocalChangesResolution) => {
+      modal.destroy();
+      resolve(resolution);
+    };
+    const modal = Modal.confirm({
+      title: t('skills.localChanges.title'),
+      content: t('skills.localChanges.message', { name: skillName }),
+      width: 520,
+      onCancel: () => resolve(null),
+      footer: (_, { CancelBtn }) =>
+        React.createElement(React.Fragment, null, [
+          React.createElement(CancelBtn, { key: 'cancel' }),
+          React.createElement(Button, {
+            key: 'keepLocal',
+            onClick: () => choose('keep_local'),
+          }, t('skills.localChanges.keepLocal')),
+          React.createElement(Button, {
+            key: 'merge',
+            type: 'primary',
+            onClick: () => choose('merge'),
+          }, t('skills.localChanges.merge')),
+          React.createElement(Button, {
+            key: 'overwrite',
+            danger: true,
+            onClick: () => choose('overwrite'),
+          }, t('skills.localChanges.overwrite')),
+        ]),
+    });
+  });
+}
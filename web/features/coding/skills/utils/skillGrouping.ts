@@ -50,6 +50,10 @@ export function filterSkillsBySearch(skills: ManagedSkill[], searchText: string)
       skill.description,
       skill.user_group,
       skill.user_note,
+      ...Object.values(skill.localizations ?? {}).flatMap((localization) => [
+        localization.display_name,
+        localization.description,
+      ]),
     ];
 
     return searchableValues.some((value) => value?.toLowerCase().includes(keyword));
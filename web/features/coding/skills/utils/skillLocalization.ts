@@ -0,0 +1,20 @@
+import type { ManagedSkill, SkillLocalization } from '../types';
+
+/** UI languages a skill can carry localized text for */
+export const SKILL_LOCALIZATION_LANGUAGES = ['zh-CN', 'en-US'] as const;
+
+type LocalizedSkill = Pick<ManagedSkill, 'name' | 'description' | 'localizations'>;
+
+function localizationFor(skill: LocalizedSkill, language: string): SkillLocalization | undefined {
+  return skill.localizations?.[language];
+}
+
+/** Localized display name for `language`, falling back to the directory name */
+export function getSkillDisplayName(skill: LocalizedSkill, language: string): string {
+  return localizationFor(skill, language)?.display_name?.trim() || skill.name;
+}
+
+/** Localized description for `language`, falling back to the SKILL.md description */
+export function getSkillDisplayDescription(skill: LocalizedSkill, language: string): string | null {
+  return localizationFor(skill, language)?.description?.trim() || skill.description;
+}
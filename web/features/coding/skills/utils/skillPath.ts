@@ -33,10 +33,16 @@ function pushUniquePath(paths: string[], path: string | null | undefined): void
   }
 }
 
+/** Sources imported from a folder on disk, opened at `source_ref` */
+export function isFolderSource(sourceType: string): boolean {
+  const type = sourceType.toLowerCase();
+  return type === 'local' || type === 'linked_folder';
+}
+
 export function getSkillFolderOpenCandidates(skill: SkillOpenPathInput): string[] {
   const paths: string[] = [];
 
-  if (skill.source_type.toLowerCase() === 'local') {
+  if (isFolderSource(skill.source_type)) {
     pushUniquePath(paths, skill.source_ref);
   } else {
     pushUniquePath(paths, skill.central_path);
@@ -1,7 +1,12 @@
 import type { TFunction } from 'i18next';
 import * as api from '../services/skillsApi';
 import type { ToolOption } from '../types';
-import { confirmTargetOverwrite } from './errorHandlers';
+import {
+  confirmIncompatibleSync,
+  confirmTargetOverwrite,
+  parseIncompatibleToolVersionError,
+  showCopyVerificationWarning,
+} from './errorHandlers';
 
 export interface SyncSkillToToolsOptions {
   skillId: string;
@@ -14,6 +19,8 @@ export interface SyncSkillToToolsOptions {
    * How to handle TARGET_EXISTS errors:
    * - 'confirm': Show confirmation dialog (AddSkillModal behavior)
    * - 'skip': Auto-skip silently (ImportModal behavior)
+   * INCOMPATIBLE_TOOL_VERSION errors follow the same choice: 'confirm' asks
+   * whether to enable anyway, 'skip' leaves the tool out.
    */
   onTargetExists: 'confirm' | 'skip';
 }
@@ -24,20 +31,46 @@ export interface SyncSkillToToolsOptions {
 export async function syncSkillToTools(options: SyncSkillToToolsOptions): Promise<void> {
   const { skillId, centralPath, skillName, selectedTools, allTools, t, onTargetExists } = options;
 
+  const sync = async (toolId: string, overwrite?: boolean, ignoreCompatibility?: boolean) => {
+    const result = await api.syncSkillToTool(
+      centralPath, skillId, toolId, skillName, overwrite, ignoreCompatibility,
+    );
+    const toolLabel = allTools.find((t) => t.id === toolId)?.label || toolId;
+    showCopyVerificationWarning(result, skillName, toolLabel, t);
+  };
+
   for (const toolId of selectedTools) {
     try {
-      await api.syncSkillToTool(centralPath, skillId, toolId, skillName);
+      await sync(toolId);
     } catch (error) {
       const errMsg = String(error);
-      if (errMsg.includes('TARGET_EXISTS|')) {
+      const incompatible = parseIncompatibleToolVersionError(errMsg);
+      if (incompatible) {
+        if (onTargetExists === 'confirm') {
+          const toolLabel = allTools.find((t) => t.id === toolId)?.label || toolId;
+          const proceed = await confirmIncompatibleSync(
+            skillName, toolLabel, incompatible.constraint, incompatible.version, t,
+          );
+          if (proceed) {
+            try {
+              await sync(toolId, false, true);
+            } catch (retryError) {
+              console.error(`Failed to sync incompatible skill to ${toolId}:`, retryError);
+            }
+          }
+        } else {
+          console.log(`Skipping ${toolId}: tool version ${incompatible.version} not in ${incompatible.constraint}`);
+        }
+      } else if (errMsg.includes('TARGET_EXISTS|')) {
         if (onTargetExists === 'confirm') {
           const match = errMsg.match(/TARGET_EXISTS\|(.+)/);
           const targetPath = match ? match[1] : '';
           const toolLabel = allTools.find((t) => t.id === toolId)?.label || toolId;
-          const shouldOverwrite = await confirmTargetOverwrite(skillName, toolLabel, targetPath, t);
+          const conflict = await api.previewTargetConflict(skillId, toolId).catch(() => null);
+          const shouldOverwrite = await confirmTargetOverwrite(skillName, toolLabel, targetPath, t, conflict);
           if (shouldOverwrite) {
             try {
-              await api.syncSkillToTool(centralPath, skillId, toolId, skillName, true);
+              await sync(toolId, true);
             } catch (retryError) {
               console.error(`Failed to overwrite sync to ${toolId}:`, retryError);
             }
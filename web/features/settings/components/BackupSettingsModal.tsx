@@ -30,13 +30,16 @@ import { useTranslation } from 'react-i18next';
 import { open } from '@tauri-apps/plugin-dialog';
 import { useSettingsStore, type WebDAVConfigFE } from '@/stores';
 import {
+  getPortableExportStatus,
   listBackupFileFilterPathOptions,
   normalizeBackupCustomEntryPath,
+  runPortableExportNow,
   testWebDAVConnection,
   type BackupCustomEntry,
   type BackupCustomEntryType,
   type BackupFileFilterPathOption,
   type BackupFileFilterRule,
+  type PortableExportStatus,
 } from '@/services';
 
 interface BackupSettingsModalProps {
@@ -77,6 +80,11 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     autoBackupIntervalDays,
     autoBackupMaxKeep,
     setAutoBackupSettings,
+    portableExportEnabled,
+    portableExportPath,
+    portableExportIntervalDays,
+    portableExportMaxKeep,
+    setPortableExportSettings,
   } = useSettingsStore();
 
   const [currentBackupType, setCurrentBackupType] = React.useState<'local' | 'webdav'>(backupType);
@@ -87,6 +95,15 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
   const [currentAutoBackupEnabled, setCurrentAutoBackupEnabled] = React.useState(autoBackupEnabled);
   const [currentIntervalDays, setCurrentIntervalDays] = React.useState(autoBackupIntervalDays);
   const [currentMaxKeep, setCurrentMaxKeep] = React.useState(autoBackupMaxKeep);
+  const [currentPortableEnabled, setCurrentPortableEnabled] =
+    React.useState(portableExportEnabled);
+  const [currentPortablePath, setCurrentPortablePath] = React.useState(portableExportPath);
+  const [currentPortableIntervalDays, setCurrentPortableIntervalDays] =
+    React.useState(portableExportIntervalDays);
+  const [currentPortableMaxKeep, setCurrentPortableMaxKeep] =
+    React.useState(portableExportMaxKeep);
+  const [portableStatus, setPortableStatus] = React.useState<PortableExportStatus | null>(null);
+  const [portableExporting, setPortableExporting] = React.useState(false);
   const [currentBackupCustomEntries, setCurrentBackupCustomEntries] =
     React.useState<BackupCustomEntry[]>(backupCustomEntries);
   const [customEntryModalOpen, setCustomEntryModalOpen] = React.useState(false);
@@ -156,6 +173,10 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
       setCurrentAutoBackupEnabled(autoBackupEnabled);
       setCurrentIntervalDays(autoBackupIntervalDays);
       setCurrentMaxKeep(autoBackupMaxKeep);
+      setCurrentPortableEnabled(portableExportEnabled);
+      setCurrentPortablePath(portableExportPath);
+      setCurrentPortableIntervalDays(portableExportIntervalDays);
+      setCurrentPortableMaxKeep(portableExportMaxKeep);
       setCurrentBackupCustomEntries(backupCustomEntries);
       setCurrentFileFilterRules(backupFileFilterRules);
       form.setFieldsValue({
@@ -163,6 +184,9 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
         webdav,
       });
       void loadFilterPathOptions();
+      getPortableExportStatus()
+        .then(setPortableStatus)
+        .catch((error) => console.error('Failed to load portable export status:', error));
     }
   }, [
     isOpen,
@@ -175,6 +199,10 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     autoBackupEnabled,
     autoBackupIntervalDays,
     autoBackupMaxKeep,
+    portableExportEnabled,
+    portableExportPath,
+    portableExportIntervalDays,
+    portableExportMaxKeep,
     form,
     loadFilterPathOptions,
   ]);
@@ -194,6 +222,47 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     }
   };
 
+  const handleSelectPortableFolder = async () => {
+    try {
+      const selected = await open({
+        directory: true,
+        multiple: false,
+        title: t('settings.portableExport.selectFolder'),
+      });
+      if (selected) {
+        setCurrentPortablePath(selected as string);
+      }
+    } catch {
+      // User cancelled
+    }
+  };
+
+  const handleSavePortableExportSettings = () => setPortableExportSettings({
+    enabled: currentPortableEnabled,
+    path: currentPortablePath,
+    intervalDays: currentPortableIntervalDays,
+    maxKeep: currentPortableMaxKeep,
+  });
+
+  const handlePortableExportNow = async () => {
+    setPortableExporting(true);
+    try {
+      // Export with what is on screen, not the last saved directory
+      await handleSavePortableExportSettings();
+      const status = await runPortableExportNow();
+      setPortableStatus(status);
+      if (status.last_error) {
+        message.error(t('settings.portableExport.failed', { error: status.last_error }));
+      } else {
+        message.success(t('settings.portableExport.success'));
+      }
+    } catch (error) {
+      message.error(String(error));
+    } finally {
+      setPortableExporting(false);
+    }
+  };
+
   const handleSave = async () => {
     try {
       const values = await form.validateFields();
@@ -210,6 +279,7 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
         intervalDays: currentIntervalDays,
         maxKeep: currentMaxKeep,
       });
+      await handleSavePortableExportSettings();
       onClose();
     } catch {
       // Validation failed
@@ -659,6 +729,89 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
             </Form.Item>
           </>
         )}
+
+        <Divider />
+
+        <Form.Item
+          label={t('settings.portableExport.title')}
+          extra={t('settings.portableExport.description')}
+        >
+          <Switch
+            checked={currentPortableEnabled}
+            onChange={setCurrentPortableEnabled}
+          />
+        </Form.Item>
+        <Form.Item label={t('settings.portableExport.path')}>
+          <Space.Compact style={{ width: '100%' }}>
+            <Input
+              value={currentPortablePath}
+              readOnly
+              placeholder={t('settings.portableExport.selectFolder')}
+              style={{ flex: 1 }}
+            />
+            <Button
+              icon={<FolderOpenOutlined />}
+              onClick={handleSelectPortableFolder}
+              style={{ fontSize: 14 }}
+            >
+              {t('common.browse')}
+            </Button>
+          </Space.Compact>
+        </Form.Item>
+        {currentPortableEnabled && (
+          <>
+            <Form.Item label={t('settings.autoBackup.interval')}>
+              <InputNumber
+                value={currentPortableIntervalDays}
+                onChange={(v) => setCurrentPortableIntervalDays(v && v >= 1 ? Math.floor(v) : 1)}
+                min={1}
+                precision={0}
+                style={{ width: 60 }}
+                addonAfter={t('settings.autoBackup.days')}
+              />
+            </Form.Item>
+            <Form.Item label={t('settings.autoBackup.maxKeep')}>
+              <InputNumber
+                value={currentPortableMaxKeep}
+                onChange={(v) => setCurrentPortableMaxKeep(v != null && v >= 0 ? Math.floor(v) : 0)}
+                min={0}
+                precision={0}
+                style={{ width: 60 }}
+                addonAfter={t('settings.autoBackup.count')}
+              />
+            </Form.Item>
+          </>
+        )}
+        <Form.Item label={t('settings.portableExport.status')}>
+          <Space direction="vertical" size={0} style={{ width: '100%' }}>
+            <Typography.Text type="secondary">
+              {t('settings.portableExport.lastSuccess')}:{' '}
+              {portableStatus?.last_success_at
+                ? new Date(portableStatus.last_success_at).toLocaleString()
+                : t('settings.portableExport.never')}
+              {portableStatus ? ` | ${t('settings.portableExport.bundleCount', { count: portableStatus.bundle_count })}` : ''}
+            </Typography.Text>
+            {portableStatus?.next_due_at && (
+              <Typography.Text type="secondary">
+                {t('settings.portableExport.nextDue')}: {new Date(portableStatus.next_due_at).toLocaleString()}
+              </Typography.Text>
+            )}
+            {portableStatus?.last_error && (
+              <Typography.Text type="danger" ellipsis={{ tooltip: portableStatus.last_error }}>
+                {t('settings.portableExport.lastError')}: {portableStatus.last_error}
+              </Typography.Text>
+            )}
+            <Button
+              size="small"
+              style={{ marginTop: 8, alignSelf: 'flex-start' }}
+              loading={portableExporting || portableStatus?.running}
+              disabled={!currentPortablePath}
+              onClick={handlePortableExportNow}
+            >
+              {t('settings.portableExport.exportNow')}
+            </Button>
+          </Space>
+        </Form.Item>
       </Form>
     </Modal>
     <Modal
@@ -0,0 +1,76 @@
+import React from 'react';
+import { Alert, Space, Tag, Typography } from 'antd';
+import { LockOutlined } from '@ant-design/icons';
+import { useTranslation } from 'react-i18next';
+import type { PolicyStatus } from '@/services';
+
+const { Text } = Typography;
+
+interface PolicyNoticeProps {
+  policy: PolicyStatus | null;
+}
+
+/**
+ * Shows the values locked by the administrator's group policy file.
+ */
+const PolicyNotice: React.FC<PolicyNoticeProps> = ({ policy }) => {
+  const { t } = useTranslation();
+
+  if (!policy) {
+    return null;
+  }
+
+  if (policy.error) {
+    return (
+      <Alert
+        type="warning"
+        showIcon
+        style={{ marginBottom: 16 }}
+        message={t('settings.policy.invalidTitle')}
+        description={t('settings.policy.invalidDescription', { path: policy.path ?? '-', error: policy.error })}
+      />
+    );
+  }
+
+  if (!policy.active) {
+    return null;
+  }
+
+  const { central_repo_path, forbidden_registries, mandatory_skills, disabled_features } = policy.policy;
+  const rows: { label: string; values: string[] }[] = [
+    { label: t('settings.policy.centralRepoPath'), values: central_repo_path ? [central_repo_path] : [] },
+    { label: t('settings.policy.forbiddenRegistries'), values: forbidden_registries },
+    { label: t('settings.policy.mandatorySkills'), values: mandatory_skills },
+    {
+      label: t('settings.policy.disabledFeatures'),
+      values: disabled_features.map((feature) => t(`settings.policy.features.${feature}`, { defaultValue: feature })),
+    },
+  ].filter((row) => row.values.length > 0);
+
+  return (
+    <Alert
+      type="info"
+      showIcon
+      icon={<LockOutlined />}
+      style={{ marginBottom: 16 }}
+      message={t('settings.policy.title')}
+      description={
+        <Space direction="vertical" size={4} style={{ width: '100%' }}>
+          <Text type="secondary" style={{ fontSize: 12 }}>
+            {t('settings.policy.description', { path: policy.path ?? '-' })}
+          </Text>
+          {rows.map((row) => (
+            <div key={row.label}>
+              <Text strong style={{ marginRight: 8 }}>{row.label}:</Text>
+              {row.values.map((value) => (
+                <Tag key={value} style={{ marginBottom: 4 }}>{value}</Tag>
+              ))}
+            </div>
+          ))}
+        </Space>
+      }
+    />
+  );
+};
+
+export default PolicyNotice;
@@ -1,5 +1,5 @@
 import React from 'react';
-import { Typography, Button, Select, Space, message, Modal, Table, Switch, Progress, Input, Row, Col, Card, Divider } from 'antd';
+import { Typography, Button, Select, Space, message, Modal, Table, Switch, Progress, Input, Row, Col, Card, Divider, Tooltip } from 'antd';
 import {
   EditOutlined,
   CloudUploadOutlined,
@@ -39,7 +39,7 @@ import { useAppStore, useSettingsStore } from '@/stores';
 import { useThemeStore, type ThemeMode } from '@/stores/themeStore';
 import { languages, type Language } from '@/i18n';
 import i18n from '@/i18n';
-import { BackupSettingsModal, WebDAVRestoreModal } from '../components';
+import { BackupSettingsModal, PolicyNotice, WebDAVRestoreModal } from '../components';
 import { platform } from '@tauri-apps/plugin-os';
 import {
   backupDatabase,
@@ -48,6 +48,7 @@ import {
   backupToWebDAV,
   restoreFromWebDAV,
   type ProxyMode,
+  type StartupCheckLevel,
   type RestoreResult,
   openAppDataDir,
   getAppVersion,
@@ -55,7 +56,10 @@ import {
   openGitHubPage,
   openExternalUrl,
   installUpdate,
+  downloadUpdateOnQuit,
+  getPendingUpdateStatus,
   testProxyConnection,
+  type PendingUpdateStatus,
   type UpdateInfo,
   GITHUB_REPO,
 } from '@/services';
@@ -132,6 +136,8 @@ const GeneralSettingsPage: React.FC = () => {
     setLaunchOnStartup,
     setMinimizeToTrayOnClose,
     setStartMinimized,
+    startupCheckLevel,
+    setStartupCheckLevel,
     proxyMode,
     setProxyMode,
     proxyUrl,
@@ -141,8 +147,12 @@ const GeneralSettingsPage: React.FC = () => {
     lastAutoBackupTime,
     autoCheckUpdate,
     setAutoCheckUpdate,
+    updateManifestUrl,
+    updateReleaseRepo,
+    setUpdateSource,
     visibleTabs,
     setVisibleTabs,
+    policy,
   } = useSettingsStore();
 
   const isWindows = React.useMemo(() => platform() === 'windows', []);
@@ -156,6 +166,10 @@ const GeneralSettingsPage: React.FC = () => {
   const [proxyInput, setProxyInput] = React.useState(proxyUrl);
   const [proxyTesting, setProxyTesting] = React.useState(false);
 
+  // Update source inputs (forks / internal builds)
+  const [updateManifestInput, setUpdateManifestInput] = React.useState(updateManifestUrl);
+  const [updateRepoInput, setUpdateRepoInput] = React.useState(updateReleaseRepo);
+
   // Version and update states
   const [appVersion, setAppVersion] = React.useState<string>('');
   const [checkingUpdate, setCheckingUpdate] = React.useState(false);
@@ -166,6 +180,7 @@ const GeneralSettingsPage: React.FC = () => {
   const [updateDownloaded, setUpdateDownloaded] = React.useState<number>(0);
   const [updateTotal, setUpdateTotal] = React.useState<number>(0);
   const [updateModalOpen, setUpdateModalOpen] = React.useState(false);
+  const [pendingUpdate, setPendingUpdate] = React.useState<PendingUpdateStatus | null>(null);
 
   // Load app version on mount
   React.useEffect(() => {
@@ -219,6 +234,30 @@ const GeneralSettingsPage: React.FC = () => {
     };
   }, [t]);
 
+  // Load install-on-quit status, polling while the background download runs
+  React.useEffect(() => {
+    if (pendingUpdate && pendingUpdate.phase !== 'downloading') {
+      return;
+    }
+    const load = () => {
+      getPendingUpdateStatus()
+        .then(setPendingUpdate)
+        .catch((error) => console.error('Failed to load pending update status:', error));
+    };
+    if (!pendingUpdate) {
+      load();
+      return;
+    }
+    const timer = window.setInterval(load, 1000);
+    return () => window.clearInterval(timer);
+  }, [pendingUpdate]);
+
+  // Sync update source inputs with store
+  React.useEffect(() => {
+    setUpdateManifestInput(updateManifestUrl);
+    setUpdateRepoInput(updateReleaseRepo);
+  }, [updateManifestUrl, updateReleaseRepo]);
+
   // Sync proxyInput with proxyUrl from store
   React.useEffect(() => {
     setProxyInput(proxyUrl);
@@ -257,6 +296,15 @@ const GeneralSettingsPage: React.FC = () => {
     }
   };
 
+  const handleInstallOnQuit = async () => {
+    try {
+      setPendingUpdate(await downloadUpdateOnQuit());
+    } catch (error) {
+      console.error('Failed to start background update download:', error);
+      message.error(t('settings.about.installOnQuitFailed'));
+    }
+  };
+
   const handleGoToDownload = async () => {
     // 如果有 signature 和 url，尝试自动更新
     if (updateInfo?.signature && updateInfo?.url) {
@@ -285,7 +333,7 @@ const GeneralSettingsPage: React.FC = () => {
         setUpdateModalOpen(false);
 
         // 下载失败，提示去 GitHub Actions 下载
-        const githubActionsUrl = `https://github.com/${GITHUB_REPO}/actions`;
+        const githubActionsUrl = `https://github.com/${updateInfo?.releaseRepo || GITHUB_REPO}/actions`;
         Modal.error({
           title: t('settings.about.updateFailed'),
           content: (
@@ -561,6 +609,22 @@ const GeneralSettingsPage: React.FC = () => {
     }
   };
 
+  // Save update source when either input loses focus
+  const handleUpdateSourceSave = async () => {
+    const manifestUrl = updateManifestInput.trim();
+    const releaseRepo = updateRepoInput.trim();
+    if (manifestUrl === updateManifestUrl && releaseRepo === updateReleaseRepo) {
+      return;
+    }
+    try {
+      await setUpdateSource({ manifestUrl, releaseRepo });
+      message.success(t('common.success'));
+    } catch (error) {
+      console.error('Failed to save update source:', error);
+      message.error(t('common.error'));
+    }
+  };
+
   const handleProxyModeChange = async (nextProxyMode: ProxyMode) => {
     try {
       await setProxyMode(nextProxyMode);
@@ -627,6 +691,8 @@ const GeneralSettingsPage: React.FC = () => {
   const CODING_TABS = ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi'] as const;
   const OTHER_TABS = ['gateway', 'image', 'ssh', ...(isWindows ? ['wsl'] : [])] as string[];
 
+  const policyDisabledFeatures = policy?.active ? policy.policy.disabled_features : [];
+
   const [reorderMode, setReorderMode] = React.useState(false);
 
   // Full coding tab order: visible ones keep their order from visibleTabs,
@@ -689,6 +755,7 @@ const GeneralSettingsPage: React.FC = () => {
 
   return (
     <div className={styles.container}>
+      <PolicyNotice policy={policy} />
       <Row gutter={[16, 16]}>
         {/* Left Column: General Settings */}
         <Col xs={24} lg={12}>
@@ -756,6 +823,21 @@ const GeneralSettingsPage: React.FC = () => {
                   onChange={setMinimizeToTrayOnClose}
                 />
               </div>
+              <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center' }}>
+                <Tooltip title={t('settings.window.startupCheckLevelHint')}>
+                  <Text>{t('settings.window.startupCheckLevel')}</Text>
+                </Tooltip>
+                <Select
+                  value={startupCheckLevel}
+                  onChange={(value: StartupCheckLevel) => setStartupCheckLevel(value)}
+                  options={[
+                    { value: 'report', label: t('settings.window.startupCheckLevels.report') },
+                    { value: 'safe', label: t('settings.window.startupCheckLevels.safe') },
+                    { value: 'full', label: t('settings.window.startupCheckLevels.full') },
+                  ]}
+                  style={{ width: 160 }}
+                />
+              </div>
             </div>
 
             <Divider />
@@ -774,6 +856,28 @@ const GeneralSettingsPage: React.FC = () => {
                   onChange={setAutoCheckUpdate}
                 />
               </div>
+              <div style={{ display: 'flex', flexDirection: 'column', gap: 8 }}>
+                <Text>{t('settings.about.updateSource')}</Text>
+                <Input
+                  value={updateRepoInput}
+                  onChange={(e) => setUpdateRepoInput(e.target.value)}
+                  onBlur={handleUpdateSourceSave}
+                  onPressEnter={handleUpdateSourceSave}
+                  placeholder={GITHUB_REPO}
+                  addonBefore={t('settings.about.updateReleaseRepo')}
+                />
+                <Input
+                  value={updateManifestInput}
+                  onChange={(e) => setUpdateManifestInput(e.target.value)}
+                  onBlur={handleUpdateSourceSave}
+                  onPressEnter={handleUpdateSourceSave}
+                  placeholder={t('settings.about.updateManifestPlaceholder')}
+                  addonBefore={t('settings.about.updateManifestUrl')}
+                />
+                <Text type="secondary" style={{ fontSize: 12 }}>
+                  {t('settings.about.updateSourceHint')}
+                </Text>
+              </div>
               <Space wrap>
                 <Button
                   icon={<SyncOutlined spin={checkingUpdate} />}
@@ -787,10 +891,33 @@ const GeneralSettingsPage: React.FC = () => {
                     {t('settings.about.goToDownload')} (v{updateInfo.latestVersion})
                   </Button>
                 )}
+                {updateInfo?.hasUpdate && updateInfo.signature && updateInfo.url && (
+                  <Button
+                    onClick={handleInstallOnQuit}
+                    loading={pendingUpdate?.phase === 'downloading'}
+                    disabled={pendingUpdate?.phase === 'ready'}
+                  >
+                    {t('settings.about.installOnQuit')}
+                  </Button>
+                )}
                 <Button icon={<GithubOutlined />} onClick={handleOpenGitHub}>
                   {t('settings.about.github')}
                 </Button>
               </Space>
+              {pendingUpdate && pendingUpdate.phase !== 'none' && (
+                <Text type={pendingUpdate.phase === 'failed' ? 'danger' : 'secondary'} style={{ fontSize: 12 }}>
+                  {t(`settings.about.pendingUpdate.${pendingUpdate.phase}`, {
+                    version: pendingUpdate.version ?? '',
+                    progress: pendingUpdate.progress,
+                    error: pendingUpdate.error ?? '',
+                  })}
+                </Text>
+              )}
+              {updateInfo?.hasUpdate && updateInfo.signatureStatus !== 'verified' && (
+                <Text type="warning" style={{ fontSize: 12 }}>
+                  {t(`settings.about.signature.${updateInfo.signatureStatus}`)}
+                </Text>
+              )}
             </div>
           </Card>
         </Col>
@@ -829,7 +956,7 @@ const GeneralSettingsPage: React.FC = () => {
                 >
                   <SortableContext items={codingTabOrder} strategy={horizontalListSortingStrategy}>
                     <div className={styles.sortableChipList}>
-                      {codingTabOrder.map((key) => (
+                      {codingTabOrder.filter((key) => !policyDisabledFeatures.includes(key)).map((key) => (
                         <SortableCodingChip
                           key={key}
                           id={key}
@@ -848,7 +975,7 @@ const GeneralSettingsPage: React.FC = () => {
                   {t('settings.tabVisibility.otherModules')}
                 </Text>
                 <div className={styles.otherTabList}>
-                  {OTHER_TABS.map((key) => (
+                  {OTHER_TABS.filter((key) => !policyDisabledFeatures.includes(key)).map((key) => (
                     <div
                       key={key}
                       className={`${styles.tabPill} ${visibleTabs.includes(key) ? styles.tabPillActive : styles.tabPillInactive}`}
@@ -20,6 +20,9 @@ const GITHUB_REPO = 'coulsontl/ai-toolbox';
 export { GITHUB_REPO };
 const GITHUB_URL = `https://github.com/${GITHUB_REPO}`;
 
+/** Manifest signature checked against the key embedded in this build */
+export type UpdateSignatureStatus = 'verified' | 'unsigned' | 'foreign' | 'invalid';
+
 export interface UpdateInfo {
   hasUpdate: boolean;
   currentVersion: string;
@@ -28,6 +31,8 @@ export interface UpdateInfo {
   releaseNotes: string;
   signature?: string;
   url?: string;
+  releaseRepo: string;
+  signatureStatus: UpdateSignatureStatus;
 }
 
 interface UpdateCheckResult {
@@ -38,6 +43,8 @@ interface UpdateCheckResult {
   release_notes: string;
   signature?: string;
   url?: string;
+  release_repo: string;
+  signature_status: UpdateSignatureStatus;
 }
 
 /**
@@ -61,6 +68,8 @@ export const checkForUpdates = async (): Promise<UpdateInfo> => {
     releaseNotes: result.release_notes,
     signature: result.signature,
     url: result.url,
+    releaseRepo: result.release_repo,
+    signatureStatus: result.signature_status,
   };
 };
 
@@ -71,6 +80,46 @@ export const installUpdate = async (): Promise<boolean> => {
   return await invoke('install_update');
 };
 
+export type PendingUpdatePhase = 'none' | 'downloading' | 'ready' | 'failed';
+
+export interface PendingUpdateStatus {
+  phase: PendingUpdatePhase;
+  version?: string;
+  progress: number;
+  error?: string;
+}
+
+/**
+ * Download and verify the update in the background; it installs when the app quits
+ */
+export const downloadUpdateOnQuit = async (): Promise<PendingUpdateStatus> => {
+  return await invoke<PendingUpdateStatus>('download_update_on_quit');
+};
+
+/**
+ * Get install-on-quit progress
+ */
+export const getPendingUpdateStatus = async (): Promise<PendingUpdateStatus> => {
+  return await invoke<PendingUpdateStatus>('get_pending_update_status');
+};
+
+/** Cheap skill / MCP counts, also served at `GET /status` on the local gateway */
+export interface StatusSummary {
+  managed_skills: number;
+  broken_skill_targets: number;
+  mcp_servers: number;
+  broken_mcp_targets: number;
+  pending_updates: number;
+  active_profiles: Record<string, string>;
+}
+
+/**
+ * Get the status summary used by external status bar integrations
+ */
+export const getStatusSummary = async (): Promise<StatusSummary> => {
+  return await invoke<StatusSummary>('get_status_summary');
+};
+
 /**
  * Open GitHub repository page
  */
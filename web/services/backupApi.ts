@@ -55,6 +55,85 @@ export const getDatabasePath = async (): Promise<string> => {
   return result;
 };
 
+export interface DatabaseEncryptionStatus {
+  /** The app was built with SQLCipher support */
+  available: boolean;
+  encrypted: boolean;
+  /** A key for the database is stored in the OS keychain */
+  key_stored: boolean;
+  /** 'encrypt' / 'decrypt' applied on the next start */
+  pending: 'encrypt' | 'decrypt' | null;
+}
+
+/**
+ * Get whether the database is encrypted at rest
+ */
+export const getDatabaseEncryptionStatus = async (): Promise<DatabaseEncryptionStatus> => {
+  return await invoke<DatabaseEncryptionStatus>('get_database_encryption_status');
+};
+
+/**
+ * Encrypt or decrypt the database on the next start (restart required)
+ * @param enabled - Whether the database should be encrypted
+ */
+export const setDatabaseEncryption = async (
+  enabled: boolean,
+): Promise<DatabaseEncryptionStatus> => {
+  return await invoke<DatabaseEncryptionStatus>('set_database_encryption', { enabled });
+};
+
+/**
+ * Export library content without local paths or sync state, for another machine
+ * @param path - Target file; must not exist yet
+ * @returns Number of exported records
+ */
+export const exportPortableData = async (path: string): Promise<number> => {
+  return await invoke<number>('export_portable_data', { path });
+};
+
+/**
+ * Replace library content with a portable export; local settings are kept.
+ * Tool targets are re-derived after restarting the app.
+ * @param path - Portable export file
+ * @returns Number of imported records
+ */
+export const importPortableData = async (path: string): Promise<number> => {
+  return await invoke<number>('import_portable_data', { path });
+};
+
+/** Scheduled portable export settings and the outcome of its last run */
+export interface PortableExportStatus {
+  enabled: boolean;
+  export_dir: string;
+  interval_days: number;
+  max_keep: number;
+  next_due_at: string | null;
+  /** Bundles currently in the export directory */
+  bundle_count: number;
+  running: boolean;
+  last_attempt_at: string | null;
+  last_success_at: string | null;
+  last_file: string | null;
+  last_size_bytes: number | null;
+  /** Error of the last run; cleared by the next successful one */
+  last_error: string | null;
+}
+
+/**
+ * Get the scheduled portable export status, for diagnostics
+ */
+export const getPortableExportStatus = async (): Promise<PortableExportStatus> => {
+  return await invoke<PortableExportStatus>('get_portable_export_status');
+};
+
+/**
+ * Export a portable bundle (library + manifest) to the configured directory now
+ * @returns Status after the run; a failed run is reported in `last_error`
+ */
+export const runPortableExportNow = async (): Promise<PortableExportStatus> => {
+  return await invoke<PortableExportStatus>('run_portable_export_now');
+};
+
 /**
  * Open file dialog to select a backup file for restore
  * @returns The selected file path, or null if cancelled
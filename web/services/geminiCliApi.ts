@@ -3,6 +3,7 @@ import type {
   ConfigPathInfo,
   GeminiCliCommonConfig,
   GeminiCliCommonConfigInput,
+  GeminiCliExtension,
   GeminiCliLocalConfigInput,
   GeminiCliOfficialAccount,
   GeminiCliProvider,
@@ -22,6 +23,10 @@ export const revealGeminiCliConfigFolder = async (): Promise<void> => {
   await invoke('reveal_gemini_cli_config_folder');
 };
 
+export const listGeminiCliExtensions = async (): Promise<GeminiCliExtension[]> => {
+  return await invoke<GeminiCliExtension[]>('list_gemini_cli_extensions');
+};
+
 export const readGeminiCliSettings = async (): Promise<GeminiCliSettings> => {
   return await invoke<GeminiCliSettings>('read_gemini_cli_settings');
 };
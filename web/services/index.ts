@@ -17,5 +17,8 @@ export * from './codexPromptApi';
 export * from './piApi';
 export * from './piPromptApi';
 export * from './appApi';
+export * from './postSyncHooksApi';
+export * from './toolKeyMigrationApi';
+export * from './toolPluginsApi';
 export * from './ohMyOpenAgentApi';
 export * from '../features/coding/shared/sessionManager/sessionManagerApi';
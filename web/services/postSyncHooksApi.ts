@@ -0,0 +1,41 @@
+/**
+ * Post-Sync Hooks API Service
+ *
+ * Per-tool actions that run after Skills / MCP sync so the tool reloads.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+// Types matching Rust structures
+export type PostSyncAction =
+  | { kind: 'touch'; path?: string | null }
+  | { kind: 'signal'; process: string; signal?: string }
+  | { kind: 'command'; program: string; args?: string[] };
+
+export interface PostSyncHook {
+  action: PostSyncAction;
+  on_skills: boolean;
+  on_mcp: boolean;
+}
+
+/** Tool key -> hook */
+export type PostSyncHooks = Record<string, PostSyncHook>;
+
+export type PostSyncScope = 'skills' | 'mcp';
+
+export const getPostSyncHooks = async (): Promise<PostSyncHooks> => {
+  return await invoke<PostSyncHooks>('tools_get_post_sync_hooks');
+};
+
+export const savePostSyncHooks = async (hooks: PostSyncHooks): Promise<void> => {
+  await invoke('tools_save_post_sync_hooks', { hooks });
+};
+
+/** Run a hook immediately against the tool's current skills dir or MCP config */
+export const testPostSyncHook = async (
+  tool: string,
+  scope: PostSyncScope,
+  hook: PostSyncHook,
+): Promise<void> => {
+  await invoke('tools_test_post_sync_hook', { tool, scope, hook });
+};
@@ -89,6 +89,9 @@ export const normalizeSidebarHiddenByPage = (
   return normalizedValue;
 };
 
+// Which library audit fixes the startup consistency check applies on its own
+export type StartupCheckLevel = 'report' | 'safe' | 'full';
+
 export interface AppSettings {
   language: string;
   current_module: string;
@@ -104,6 +107,7 @@ export interface AppSettings {
   launch_on_startup: boolean;
   minimize_to_tray_on_close: boolean;
   start_minimized: boolean;
+  startup_check_level: StartupCheckLevel;
   proxy_mode: ProxyMode;
   proxy_url: string;
   theme: string;
@@ -111,12 +115,19 @@ export interface AppSettings {
   auto_backup_interval_days: number;
   auto_backup_max_keep: number;
   last_auto_backup_time: string | null;
+  portable_export_enabled: boolean;
+  portable_export_path: string;
+  portable_export_interval_days: number;
+  portable_export_max_keep: number;
   auto_check_update: boolean;
+  update_manifest_url: string;
+  update_release_repo: string;
   visible_tabs: string[];
   sidebar_hidden_by_page: SidebarHiddenByPage;
   opencode_allow_clear_applied_oh_my_config: boolean;
   codex_preserve_official_auth_on_switch: boolean;
   codex_unified_session_history_enabled: boolean;
+  read_only_mode: boolean;
 }
 
 // Default settings
@@ -150,6 +161,7 @@ export const defaultSettings: AppSettings = {
   launch_on_startup: true,
   minimize_to_tray_on_close: true,
   start_minimized: false,
+  startup_check_level: 'report',
   proxy_mode: 'system',
   proxy_url: '',
   theme: 'system',
@@ -157,12 +169,19 @@ export const defaultSettings: AppSettings = {
   auto_backup_interval_days: 7,
   auto_backup_max_keep: 10,
   last_auto_backup_time: null,
+  portable_export_enabled: false,
+  portable_export_path: '',
+  portable_export_interval_days: 1,
+  portable_export_max_keep: 7,
   auto_check_update: true,
+  update_manifest_url: '',
+  update_release_repo: '',
   visible_tabs: ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
   sidebar_hidden_by_page: createDefaultSidebarHiddenByPage(),
   opencode_allow_clear_applied_oh_my_config: false,
   codex_preserve_official_auth_on_switch: false,
   codex_unified_session_history_enabled: false,
+  read_only_mode: false,
 };
 
 /**
@@ -179,6 +198,9 @@ export const getSettings = async (): Promise<AppSettings> => {
       backup_file_filter_rules: settings.backup_file_filter_rules ?? [],
       codex_preserve_official_auth_on_switch: settings.codex_preserve_official_auth_on_switch ?? false,
       codex_unified_session_history_enabled: settings.codex_unified_session_history_enabled ?? false,
+      read_only_mode: settings.read_only_mode ?? false,
+      update_manifest_url: settings.update_manifest_url ?? '',
+      update_release_repo: settings.update_release_repo ?? '',
       sidebar_hidden_by_page: normalizeSidebarHiddenByPage(
         settings.sidebar_hidden_by_page ?? settings.sidebar_visibility_by_page
       ),
@@ -196,6 +218,30 @@ export const saveSettings = async (settings: AppSettings): Promise<void> => {
   await invoke('save_settings', { settings });
 };
 
+/**
+ * Group policy provisioned by an administrator (read-only for the user).
+ */
+export interface GroupPolicy {
+  central_repo_path: string | null;
+  forbidden_registries: string[];
+  mandatory_skills: string[];
+  disabled_features: string[];
+}
+
+export interface PolicyStatus {
+  active: boolean;
+  path: string | null;
+  error: string | null;
+  policy: GroupPolicy;
+}
+
+/**
+ * Get the group policy loaded at startup
+ */
+export const getPolicyStatus = async (): Promise<PolicyStatus> => {
+  return invoke<PolicyStatus>('get_policy_status');
+};
+
 /**
  * Normalize a custom backup path to ~/... or %APPDATA%/... when possible.
  */
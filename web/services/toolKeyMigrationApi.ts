@@ -0,0 +1,22 @@
+/**
+ * Tool Key Migration API Service
+ *
+ * Rewrites tool keys retired by a rebrand in skills, MCP servers and preferences.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ToolKeyMigration {
+  /** Table name -> rows using a retired key (preview) or rewritten */
+  rows: Record<string, number>;
+  total: number;
+}
+
+export const previewToolKeyMigration = async (): Promise<ToolKeyMigration> => {
+  return await invoke<ToolKeyMigration>('tools_preview_key_migration');
+};
+
+/** Call only after the user confirmed the preview */
+export const migrateToolKeys = async (): Promise<ToolKeyMigration> => {
+  return await invoke<ToolKeyMigration>('tools_migrate_keys');
+};
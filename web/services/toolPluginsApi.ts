@@ -0,0 +1,50 @@
+/**
+ * Tool Plugins API Service
+ *
+ * Tool adapters supplied as sandboxed WASM modules in the app data dir.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ToolPlugin {
+  /** File name in the plugins dir */
+  file: string;
+  /** SHA-256 of the file, what an approval is for */
+  sha256: string;
+  /** Only approved plugins are loaded */
+  approved: boolean;
+  key: string | null;
+  display_name: string | null;
+  installed: boolean;
+  /** Dir all of the plugin's paths stay in */
+  tool_dir: string | null;
+  skills_dir: string | null;
+  mcp_config_path: string | null;
+  /** The plugin writes the tool's MCP config itself */
+  renders_config: boolean;
+  /** Why the plugin was not loaded */
+  error: string | null;
+}
+
+export interface ToolPlugins {
+  dir: string | null;
+  plugins: ToolPlugin[];
+}
+
+export const listToolPlugins = async (): Promise<ToolPlugins> => {
+  return await invoke<ToolPlugins>('tools_list_plugins');
+};
+
+/** Load the plugins dir again after plugins were added or removed */
+export const reloadToolPlugins = async (): Promise<ToolPlugins> => {
+  return await invoke<ToolPlugins>('tools_reload_plugins');
+};
+
+/** Approve the plugin file with the listed hash, or withdraw the approval */
+export const approveToolPlugin = async (
+  file: string,
+  sha256: string,
+  approved: boolean,
+): Promise<ToolPlugins> => {
+  return await invoke<ToolPlugins>('tools_approve_plugin', { file, sha256, approved });
+};
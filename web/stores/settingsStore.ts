@@ -1,10 +1,13 @@
 import { create } from 'zustand';
 import {
+  getPolicyStatus,
   getSettings,
   saveSettings,
   setAutoLaunch,
   type AppSettings,
+  type PolicyStatus,
   type ProxyMode,
+  type StartupCheckLevel,
   type WebDAVConfig,
   type S3Config,
   type BackupCustomEntry,
@@ -13,7 +16,11 @@ import {
   type SidebarHiddenByPage,
   normalizeSidebarHiddenByPage,
 } from '@/services';
-import { buildLaunchOnStartupSettings } from './settingsStoreUtils';
+import {
+  buildLaunchOnStartupSettings,
+  buildVisibleTabsToSave,
+  getPolicyDisabledFeatures,
+} from './settingsStoreUtils';
 
 // Re-export types for convenience (using camelCase for frontend)
 export interface WebDAVConfigFE {
@@ -56,6 +63,7 @@ interface SettingsState {
   launchOnStartup: boolean;
   minimizeToTrayOnClose: boolean;
   startMinimized: boolean;
+  startupCheckLevel: StartupCheckLevel;
 
   // Proxy settings
   proxyMode: ProxyMode;
@@ -67,12 +75,23 @@ interface SettingsState {
   autoBackupMaxKeep: number;
   lastAutoBackupTime: string | null;
 
+  // Scheduled portable export settings
+  portableExportEnabled: boolean;
+  portableExportPath: string;
+  portableExportIntervalDays: number;
+  portableExportMaxKeep: number;
+
   // Update settings
   autoCheckUpdate: boolean;
+  updateManifestUrl: string;
+  updateReleaseRepo: string;
 
-  // Tab visibility settings
+  // Tab visibility settings (tabs disabled by group policy are left out)
   visibleTabs: string[];
 
+  // Group policy provisioned by an administrator
+  policy: PolicyStatus | null;
+
   // Sidebar visibility settings
   sidebarHiddenByPage: SidebarHiddenByPage;
 
@@ -98,6 +117,7 @@ interface SettingsState {
   setLaunchOnStartup: (enabled: boolean) => Promise<void>;
   setMinimizeToTrayOnClose: (enabled: boolean) => Promise<void>;
   setStartMinimized: (enabled: boolean) => Promise<void>;
+  setStartupCheckLevel: (level: StartupCheckLevel) => Promise<void>;
   setProxyMode: (mode: ProxyMode) => Promise<void>;
   setProxyUrl: (url: string) => Promise<void>;
   setAutoBackupSettings: (config: {
@@ -106,7 +126,14 @@ interface SettingsState {
     maxKeep: number;
   }) => Promise<void>;
   setLastAutoBackupTime: (time: string) => void;
+  setPortableExportSettings: (config: {
+    enabled: boolean;
+    path: string;
+    intervalDays: number;
+    maxKeep: number;
+  }) => Promise<void>;
   setAutoCheckUpdate: (enabled: boolean) => Promise<void>;
+  setUpdateSource: (source: { manifestUrl: string; releaseRepo: string }) => Promise<void>;
   setVisibleTabs: (tabs: string[]) => Promise<void>;
   setSidebarHidden: (page: SidebarPageKey, hidden: boolean) => Promise<void>;
   setOpencodeAllowClearAppliedOhMyConfig: (enabled: boolean) => Promise<void>;
@@ -187,14 +214,22 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
   launchOnStartup: true,
   minimizeToTrayOnClose: true,
   startMinimized: false,
+  startupCheckLevel: 'report',
   proxyMode: 'system',
   proxyUrl: '',
   autoBackupEnabled: false,
   autoBackupIntervalDays: 7,
   autoBackupMaxKeep: 10,
   lastAutoBackupTime: null,
+  portableExportEnabled: false,
+  portableExportPath: '',
+  portableExportIntervalDays: 1,
+  portableExportMaxKeep: 7,
   autoCheckUpdate: true,
+  updateManifestUrl: '',
+  updateReleaseRepo: '',
   visibleTabs: ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
+  policy: null,
   sidebarHiddenByPage: normalizeSidebarHiddenByPage(),
   opencodeAllowClearAppliedOhMyConfig: false,
   codexPreserveOfficialAuthOnSwitch: false,
@@ -206,6 +241,11 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     set({ isLoading: true });
     try {
       const settings = await getSettings();
+      const policy = await getPolicyStatus().catch((error) => {
+        console.error('Failed to load group policy:', error);
+        return null;
+      });
+      const policyDisabledFeatures = getPolicyDisabledFeatures(policy);
       set({
         backupType: (settings.backup_type as 'local' | 'webdav') || 'local',
         localBackupPath: settings.local_backup_path,
@@ -218,14 +258,23 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
         launchOnStartup: settings.launch_on_startup,
         minimizeToTrayOnClose: settings.minimize_to_tray_on_close,
         startMinimized: settings.start_minimized ?? false,
+        startupCheckLevel: settings.startup_check_level ?? 'report',
         proxyMode: settings.proxy_mode ?? 'system',
         proxyUrl: settings.proxy_url || '',
         autoBackupEnabled: settings.auto_backup_enabled ?? false,
         autoBackupIntervalDays: settings.auto_backup_interval_days ?? 7,
         autoBackupMaxKeep: settings.auto_backup_max_keep ?? 10,
         lastAutoBackupTime: settings.last_auto_backup_time ?? null,
+        portableExportEnabled: settings.portable_export_enabled ?? false,
+        portableExportPath: settings.portable_export_path ?? '',
+        portableExportIntervalDays: settings.portable_export_interval_days ?? 1,
+        portableExportMaxKeep: settings.portable_export_max_keep ?? 7,
         autoCheckUpdate: settings.auto_check_update ?? true,
-        visibleTabs: settings.visible_tabs ?? ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
+        updateManifestUrl: settings.update_manifest_url ?? '',
+        updateReleaseRepo: settings.update_release_repo ?? '',
+        visibleTabs: (settings.visible_tabs ?? ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'])
+          .filter((key) => !policyDisabledFeatures.includes(key)),
+        policy,
         sidebarHiddenByPage: normalizeSidebarHiddenByPage(settings.sidebar_hidden_by_page),
         opencodeAllowClearAppliedOhMyConfig: settings.opencode_allow_clear_applied_oh_my_config ?? false,
         codexPreserveOfficialAuthOnSwitch: settings.codex_preserve_official_auth_on_switch ?? false,
@@ -349,6 +398,18 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     await saveSettings(newSettings);
   },
 
+  setStartupCheckLevel: async (level) => {
+    set({ startupCheckLevel: level });
+
+    // Update database
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      startup_check_level: level,
+    };
+    await saveSettings(newSettings);
+  },
+
   setProxyMode: async (mode) => {
     set({ proxyMode: mode });
 
@@ -394,6 +455,25 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     set({ lastAutoBackupTime: time });
   },
 
+  setPortableExportSettings: async (config) => {
+    set({
+      portableExportEnabled: config.enabled,
+      portableExportPath: config.path,
+      portableExportIntervalDays: config.intervalDays,
+      portableExportMaxKeep: config.maxKeep,
+    });
+
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      portable_export_enabled: config.enabled,
+      portable_export_path: config.path,
+      portable_export_interval_days: config.intervalDays,
+      portable_export_max_keep: config.maxKeep,
+    };
+    await saveSettings(newSettings);
+  },
+
   setAutoCheckUpdate: async (enabled) => {
     set({ autoCheckUpdate: enabled });
 
@@ -406,14 +486,28 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     await saveSettings(newSettings);
   },
 
+  setUpdateSource: async ({ manifestUrl, releaseRepo }) => {
+    set({ updateManifestUrl: manifestUrl, updateReleaseRepo: releaseRepo });
+
+    // Update database
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      update_manifest_url: manifestUrl,
+      update_release_repo: releaseRepo,
+    };
+    await saveSettings(newSettings);
+  },
+
   setVisibleTabs: async (tabs) => {
-    set({ visibleTabs: tabs });
+    const policyDisabledFeatures = getPolicyDisabledFeatures(get().policy);
+    set({ visibleTabs: tabs.filter((key) => !policyDisabledFeatures.includes(key)) });
 
     // Update database
     const currentSettings = await getSettings();
     const newSettings: AppSettings = {
       ...currentSettings,
-      visible_tabs: tabs,
+      visible_tabs: buildVisibleTabsToSave(tabs, currentSettings.visible_tabs ?? [], policyDisabledFeatures),
     };
     await saveSettings(newSettings);
   },
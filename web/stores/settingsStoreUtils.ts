@@ -1,4 +1,4 @@
-import type { AppSettings } from '../services/settingsApi';
+import type { AppSettings, PolicyStatus } from '../services/settingsApi';
 
 export const buildLaunchOnStartupSettings = (
   currentSettings: AppSettings,
@@ -8,3 +8,22 @@ export const buildLaunchOnStartupSettings = (
   launch_on_startup: enabled,
   start_minimized: enabled ? currentSettings.start_minimized : false,
 });
+
+/**
+ * Features (tab keys) the group policy disables; empty without an active policy.
+ */
+export const getPolicyDisabledFeatures = (policy: PolicyStatus | null): string[] =>
+  policy?.active ? policy.policy.disabled_features : [];
+
+/**
+ * Tabs to persist when the user edits tab visibility: policy-disabled tabs are
+ * never shown, but keep the user's own choice for them in case the policy is lifted.
+ */
+export const buildVisibleTabsToSave = (
+  tabs: string[],
+  savedTabs: string[],
+  disabledFeatures: string[],
+): string[] => [
+  ...tabs.filter((key) => !disabledFeatures.includes(key)),
+  ...savedTabs.filter((key) => disabledFeatures.includes(key)),
+];
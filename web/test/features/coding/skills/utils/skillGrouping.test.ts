@@ -43,6 +43,12 @@ function makeSkill(overrides: Partial<ManagedSkill>): ManagedSkill {
     source_error: null,
     enabled_tools: [],
     targets: [],
+    license: null,
+    provenance: null,
+    tags: [],
+    compatibility: {},
+    localizations: {},
+    write_protected: false,
     ...overrides,
   };
 }
@@ -93,6 +99,20 @@ test('filterSkillsBySearch matches custom group and note', () => {
   assert.deepEqual(filterSkillsBySearch(skills, 'frida').map((skill) => skill.id), ['note']);
 });
 
+test('filterSkillsBySearch matches localized names and descriptions', () => {
+  const skills = [
+    makeSkill({
+      id: 'pdf',
+      name: 'pdf',
+      localizations: { 'zh-CN': { display_name: 'PDF 处理', description: '提取表格' } },
+    }),
+    makeSkill({ id: 'other', name: 'frontend-helper' }),
+  ];
+
+  assert.deepEqual(filterSkillsBySearch(skills, '处理').map((skill) => skill.id), ['pdf']);
+  assert.deepEqual(filterSkillsBySearch(skills, '表格').map((skill) => skill.id), ['pdf']);
+});
+
 test('buildSkillGroups groups by custom group and keeps ungrouped skills', () => {
   const skills = [
     makeSkill({ id: 'a', group_id: 'reverse', user_group: 'Reverse' }),
@@ -0,0 +1,27 @@
+import assert from 'node:assert/strict';
+import test from 'node:test';
+
+import {
+  getSkillDisplayDescription,
+  getSkillDisplayName,
+} from '../../../../../features/coding/skills/utils/skillLocalization.ts';
+
+const skill = {
+  name: 'pdf',
+  description: 'Extract text and tables from PDF files',
+  localizations: {
+    'zh-CN': { display_name: 'PDF 处理', description: '提取 PDF 文本与表格' },
+    'en-US': { display_name: '  ', description: null },
+  },
+};
+
+test('getSkillDisplayName uses the localized name of the current language', () => {
+  assert.equal(getSkillDisplayName(skill, 'zh-CN'), 'PDF 处理');
+  assert.equal(getSkillDisplayName(skill, 'en-US'), 'pdf');
+  assert.equal(getSkillDisplayName({ ...skill, localizations: {} }, 'zh-CN'), 'pdf');
+});
+
+test('getSkillDisplayDescription falls back to the SKILL.md description', () => {
+  assert.equal(getSkillDisplayDescription(skill, 'zh-CN'), '提取 PDF 文本与表格');
+  assert.equal(getSkillDisplayDescription(skill, 'en-US'), 'Extract text and tables from PDF files');
+});
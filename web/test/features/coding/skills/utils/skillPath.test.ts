@@ -43,6 +43,17 @@ test('getSkillFolderOpenCandidates uses only the local source for local skills',
   );
 });
 
+test('getSkillFolderOpenCandidates opens the original folder of linked-folder skills', () => {
+  assert.deepEqual(
+    getSkillFolderOpenCandidates({
+      source_type: 'linked_folder',
+      source_ref: '/dev/my-skills/review',
+      central_path: '/central/review',
+    }),
+    ['/dev/my-skills/review'],
+  );
+});
+
 test('getSkillFolderOpenCandidates does not fall back to central path for missing local source', () => {
   assert.deepEqual(
     getSkillFolderOpenCandidates({
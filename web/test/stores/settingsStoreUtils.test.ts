@@ -2,7 +2,10 @@ import assert from 'node:assert/strict';
 import test from 'node:test';
 
 import type { AppSettings } from '../../services/settingsApi.ts';
-import { buildLaunchOnStartupSettings } from '../../stores/settingsStoreUtils.ts';
+import {
+  buildLaunchOnStartupSettings,
+  buildVisibleTabsToSave,
+} from '../../stores/settingsStoreUtils.ts';
 
 function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
   return {
@@ -35,6 +38,7 @@ function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
     launch_on_startup: true,
     minimize_to_tray_on_close: true,
     start_minimized: false,
+    startup_check_level: 'report',
     proxy_mode: 'system',
     proxy_url: '',
     theme: 'system',
@@ -42,7 +46,13 @@ function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
     auto_backup_interval_days: 7,
     auto_backup_max_keep: 10,
     last_auto_backup_time: null,
+    portable_export_enabled: false,
+    portable_export_path: '',
+    portable_export_interval_days: 1,
+    portable_export_max_keep: 7,
     auto_check_update: true,
+    update_manifest_url: '',
+    update_release_repo: '',
     visible_tabs: ['opencode', 'claudecode', 'codex', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
     sidebar_hidden_by_page: {
       opencode: false,
@@ -56,6 +66,7 @@ function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
     opencode_allow_clear_applied_oh_my_config: false,
     codex_preserve_official_auth_on_switch: false,
     codex_unified_session_history_enabled: false,
+    read_only_mode: false,
     ...overrides,
   };
 }
@@ -85,3 +96,9 @@ test('buildLaunchOnStartupSettings preserves start_minimized when launch on star
   assert.equal(updatedSettings.launch_on_startup, true);
   assert.equal(updatedSettings.start_minimized, true);
 });
+
+test('buildVisibleTabsToSave keeps saved choices for policy-disabled tabs', () => {
+  const tabs = buildVisibleTabsToSave(['codex', 'grok'], ['codex', 'gateway', 'image'], ['gateway', 'ssh']);
+
+  assert.deepEqual(tabs, ['codex', 'grok', 'gateway']);
+});
@@ -128,3 +128,15 @@ export interface ConfigPathInfo {
   path: string;
   source: 'custom' | 'env' | 'shell' | 'default';
 }
+
+export interface GeminiCliExtension {
+  name: string;
+  version?: string;
+  installPath: string;
+  linked: boolean;
+  /** Source key for MCP import and skill onboarding */
+  sourceKey: string;
+  mcpServers: string[];
+  skills: string[];
+  contextFiles: string[];
+}